@@ -1,29 +1,199 @@
-use crate::{rpc_client::MultiRpcRequest, util::hostname_from_url};
+use crate::{
+    add_metric_entry, log,
+    logs::Priority,
+    memory::{
+        read_state, record_cycles_report, record_response_metadata, total_prepaid_cycles_debited,
+    },
+    metrics::MetricRpcMethod,
+    providers::access_hostname,
+    rpc_client::{
+        AccountSnapshotRequest, IsBlockhashValidRequest, MultiRpcRequest, OutcallsMetadata,
+        RawHttpRequest, ReducedResult,
+    },
+    upgrade::is_upgrade_in_progress,
+};
+use candid::Principal;
 use canhttp::multi::ReductionError;
 use serde::{de::DeserializeOwned, Serialize};
-use sol_rpc_types::{MultiRpcResult, RpcAccess, RpcAuth, RpcError, SupportedRpcProvider};
-use std::fmt::Debug;
+use sol_rpc_types::{
+    AccountSnapshot, CyclesReport, MultiRpcResult, RawHttpResponse, ResponseMetadata, RpcError,
+    SupportedRpcProvider,
+};
+use std::{fmt::Debug, future::Future};
 
 pub async fn send_multi<Params, Output, Error>(
     request: Result<MultiRpcRequest<Params, Output>, Error>,
 ) -> MultiRpcResult<Output>
 where
-    Params: Serialize + Clone + Debug,
-    Output: Debug + DeserializeOwned + PartialEq + Serialize,
+    Params: Serialize + Clone + Debug + 'static,
+    Output: Clone + Debug + DeserializeOwned + PartialEq + Serialize,
     Error: Into<RpcError>,
 {
     match request {
-        Ok(request) => match request.send_and_reduce().await {
-            Ok(value) => MultiRpcResult::Consistent(Ok(value)),
-            Err(err) => match err {
-                ReductionError::ConsistentError(err) => MultiRpcResult::Consistent(Err(err)),
-                ReductionError::InconsistentResults(multi_call_results) => {
-                    let results: Vec<_> = multi_call_results.into_iter().collect();
-                    MultiRpcResult::Inconsistent(results)
-                }
+        Ok(request) => {
+            if let Err(e) = authorize_caller(request.method()) {
+                return MultiRpcResult::Consistent(Err(e));
+            }
+            let (result, metadata) = reporting_cycles(request.send_and_reduce()).await;
+            into_multi_rpc_result(result, metadata)
+        }
+        Err(e) => process_error(e),
+    }
+}
+
+/// Same as [`send_multi`] for provider-specific HTTP requests that are not JSON-RPC requests.
+pub async fn send_raw_http(
+    request: Result<RawHttpRequest, RpcError>,
+) -> MultiRpcResult<RawHttpResponse> {
+    match request {
+        Ok(request) => {
+            if let Err(e) = authorize_caller(request.method()) {
+                return MultiRpcResult::Consistent(Err(e));
+            }
+            let (result, metadata) = reporting_cycles(request.send_and_reduce()).await;
+            into_multi_rpc_result(result, metadata)
+        }
+        Err(e) => process_error(e),
+    }
+}
+
+/// Same as [`send_multi`] for `getAccountSnapshot`, which combines the results of several
+/// JSON-RPC requests.
+pub async fn send_account_snapshot<Error: Into<RpcError>>(
+    request: Result<AccountSnapshotRequest, Error>,
+) -> MultiRpcResult<AccountSnapshot> {
+    match request {
+        Ok(request) => {
+            if let Err(e) = authorize_caller("getAccountSnapshot") {
+                return MultiRpcResult::Consistent(Err(e));
+            }
+            let (result, metadata) = reporting_cycles(request.send_and_reduce()).await;
+            into_multi_rpc_result(result, metadata)
+        }
+        Err(e) => process_error(e),
+    }
+}
+
+/// Awaits the given future and records the cycles attached to and consumed by the current call
+/// for the caller, which can be retrieved with `getCyclesReport`.
+///
+/// No report is recorded for the anonymous principal, which is shared by all anonymous callers.
+/// The prepaid cycles debited by concurrent calls of the same caller are attributed to each of
+/// these calls.
+async fn reporting_cycles<T>(call: impl Future<Output = T>) -> T {
+    let caller = ic_cdk::api::msg_caller();
+    let attached = ic_cdk::api::msg_cycles_available();
+    let prepaid_debited = total_prepaid_cycles_debited(&caller);
+    let result = call.await;
+    if caller != Principal::anonymous() {
+        let consumed = attached.saturating_sub(ic_cdk::api::msg_cycles_available());
+        let prepaid = total_prepaid_cycles_debited(&caller).saturating_sub(prepaid_debited);
+        record_cycles_report(
+            caller,
+            CyclesReport {
+                timestamp: ic_cdk::api::time(),
+                attached,
+                consumed,
+                prepaid,
             },
+        );
+    }
+    result
+}
+
+/// Converts the reduced result into a [`MultiRpcResult`].
+///
+/// For inconsistent results, the metadata of the responses of the providers is recorded for the
+/// caller, which can be retrieved with `getResponseMetadata`. As for cycles reports, no metadata
+/// is recorded for the anonymous principal.
+fn into_multi_rpc_result<T>(
+    result: ReducedResult<T>,
+    metadata: OutcallsMetadata,
+) -> MultiRpcResult<T> {
+    match result {
+        Ok(value) => MultiRpcResult::Consistent(Ok(value)),
+        Err(err) => match err {
+            ReductionError::ConsistentError(err) => MultiRpcResult::Consistent(Err(err)),
+            ReductionError::InconsistentResults(multi_call_results) => {
+                let results: Vec<_> = multi_call_results.into_iter().collect();
+                let caller = ic_cdk::api::msg_caller();
+                if caller != Principal::anonymous() {
+                    let responses = results
+                        .iter()
+                        .map(|(source, _result)| {
+                            let outcall = metadata.get(source).copied().unwrap_or_default();
+                            let metadata = ResponseMetadata {
+                                latency_ms: outcall.latency.map(|latency| {
+                                    u64::try_from(latency.as_millis()).unwrap_or(u64::MAX)
+                                }),
+                                uses_api_key: outcall.uses_api_key,
+                            };
+                            (source.clone(), metadata)
+                        })
+                        .collect();
+                    record_response_metadata(caller, ic_cdk::api::time(), responses);
+                }
+                MultiRpcResult::Inconsistent(results)
+            }
         },
-        Err(e) => process_error(e),
+    }
+}
+
+/// Checks that the caller is allowed to make requests resulting in HTTPS outcalls,
+/// see [`sol_rpc_types::AllowedCallers`]. Controllers are always allowed.
+///
+/// No request is accepted while the canister is about to be upgraded, see
+/// [`crate::upgrade::prepare_upgrade`].
+pub fn authorize_caller(method: &str) -> Result<(), RpcError> {
+    if is_upgrade_in_progress() {
+        log!(
+            Priority::Info,
+            "Rejected `{method}` request since the canister is about to be upgraded"
+        );
+        return Err(RpcError::UpgradeInProgress);
+    }
+    check_allowed_caller(method)
+}
+
+/// Checks that the caller is allowed to call the given method, see
+/// [`sol_rpc_types::AllowedCallers`]. Controllers are always allowed, as well as any caller
+/// for the methods exempted with [`sol_rpc_types::InstallArgs::allowed_callers_exemptions`].
+pub fn check_allowed_caller(method: &str) -> Result<(), RpcError> {
+    let caller = ic_cdk::api::msg_caller();
+    if read_state(|state| {
+        state.is_allowed_caller(&caller) || state.is_exempt_from_allowed_callers(method)
+    }) || ic_cdk::api::is_controller(&caller)
+    {
+        return Ok(());
+    }
+    add_metric_entry!(
+        unauthorized_requests,
+        MetricRpcMethod::from(method.to_string()),
+        1
+    );
+    log!(
+        Priority::Info,
+        "Rejected `{method}` request from unauthorized caller {caller}"
+    );
+    Err(RpcError::Unauthorized { caller })
+}
+
+/// Checks with the Solana `isBlockhashValid` RPC method that the recent blockhash of a
+/// transaction is still valid, so that the transaction is only sent when it can still be
+/// processed by the cluster.
+pub async fn validate_blockhash(
+    request: Result<IsBlockhashValidRequest, RpcError>,
+) -> Result<(), RpcError> {
+    match send_multi(request).await {
+        MultiRpcResult::Consistent(Ok(true)) => Ok(()),
+        MultiRpcResult::Consistent(Ok(false)) => Err(RpcError::ValidationError(
+            "The recent blockhash of the transaction is no longer valid".to_string(),
+        )),
+        MultiRpcResult::Consistent(Err(e)) => Err(e),
+        MultiRpcResult::Inconsistent(_) => Err(RpcError::ValidationError(
+            "Providers disagree on the validity of the recent blockhash of the transaction"
+                .to_string(),
+        )),
     }
 }
 
@@ -32,12 +202,5 @@ fn process_error<T, E: Into<RpcError>>(error: E) -> MultiRpcResult<T> {
 }
 
 pub fn hostname(provider: SupportedRpcProvider) -> Option<String> {
-    let url = match provider.access {
-        RpcAccess::Authenticated { auth, .. } => match auth {
-            RpcAuth::BearerToken { url } => url,
-            RpcAuth::UrlParameter { url_pattern } => url_pattern,
-        },
-        RpcAccess::Unauthenticated { public_url } => public_url,
-    };
-    hostname_from_url(url.as_str())
+    access_hostname(&provider.access)
 }
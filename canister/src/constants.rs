@@ -1,10 +1,6 @@
 // The default value of `max_response_bytes` for HTTP outcalls is 2MB.
 pub const DEFAULT_MAX_RESPONSE_BYTES: u64 = 2_000_000;
 
-// Cycles (per node) which must be passed with each RPC request
-// as processing fee.
-pub const COLLATERAL_CYCLES_PER_NODE: u128 = 10_000_000;
-
 pub const CONTENT_TYPE_HEADER_LOWERCASE: &str = "content-type";
 pub const CONTENT_TYPE_VALUE: &str = "application/json";
 
@@ -12,3 +8,13 @@ pub const API_KEY_REPLACE_STRING: &str = "{API_KEY}";
 pub const API_KEY_MAX_SIZE: usize = 512;
 pub const VALID_API_KEY_CHARS: &str =
     "0123456789ABCDEFGHIJKLMNOPQRTSUVWXYZabcdefghijklmnopqrstuvwxyz$-_.+!*";
+
+// Principal of the cycles ledger on the ICP mainnet.
+pub const DEFAULT_CYCLES_LEDGER_ID: &str = "um5iw-rqaaa-aaaaq-qaaba-cai";
+
+// Maximum number of API keys validated by a single call to `updateAndValidateApiKeys`, since the
+// HTTPS outcalls are paid by the SOL RPC canister.
+pub const MAX_VALIDATED_API_KEYS: usize = 10;
+
+// Suffix of the names of the methods estimating the cycles cost of a request, e.g. `getSlotCyclesCost`.
+pub const CYCLES_COST_METHOD_SUFFIX: &str = "CyclesCost";
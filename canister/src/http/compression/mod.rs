@@ -0,0 +1,96 @@
+//! Support for compressed HTTP responses.
+//!
+//! RPC providers may compress their responses when asked to with an `Accept-Encoding` header.
+//! Since the `max_response_bytes` limit of an HTTPS outcall applies to the response as sent by the
+//! provider, this allows large responses to fit in a smaller limit. Compressed responses are
+//! decompressed in the transform function, i.e. before the response goes through consensus.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{constants::DEFAULT_MAX_RESPONSE_BYTES, memory::read_state};
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+use ic_cdk_management_canister::HttpRequestResult;
+use std::io::Read;
+
+pub const ACCEPT_ENCODING_VALUE: &str = "gzip, deflate";
+
+/// Maximum size of a decompressed response body.
+///
+/// This is the maximum size of an HTTPS outcall response, so that decompressing a response never
+/// results in a body that could not have been received uncompressed.
+const MAX_DECOMPRESSED_BODY_BYTES: u64 = DEFAULT_MAX_RESPONSE_BYTES;
+
+/// Conservative ratio by which compressing a large JSON-RPC response reduces its size.
+/// JSON responses typically compress by a factor of 5 to 10 with `gzip`.
+const EXPECTED_COMPRESSION_RATIO: u64 = 4;
+
+/// Size of the smallest response body for which compression is taken into account when
+/// estimating the size of a response.
+const MIN_COMPRESSIBLE_BODY_BYTES: u64 = 64 * 1024;
+
+/// Returns the expected size of a response body as sent by the provider, given the maximum size
+/// of the uncompressed body.
+///
+/// When response compression is enabled, large bodies are expected to be compressed by the
+/// provider. A provider returning an uncompressed response that is too large for the resulting
+/// `max_response_bytes` is retried with a larger limit, see [`crate::http::retry`].
+pub fn expected_body_bytes(uncompressed_body_bytes: u64) -> u64 {
+    if uncompressed_body_bytes >= MIN_COMPRESSIBLE_BODY_BYTES
+        && read_state(|state| state.is_response_compression_enabled())
+    {
+        uncompressed_body_bytes.div_ceil(EXPECTED_COMPRESSION_RATIO)
+    } else {
+        uncompressed_body_bytes
+    }
+}
+
+/// Asks the RPC provider to compress its response, unless the request already
+/// specifies an `Accept-Encoding` header (e.g. a custom header of the RPC endpoint).
+pub fn accept_compressed_response(builder: http::request::Builder) -> http::request::Builder {
+    let has_accept_encoding = builder
+        .headers_ref()
+        .is_some_and(|headers| headers.contains_key(ACCEPT_ENCODING));
+    if has_accept_encoding {
+        builder
+    } else {
+        builder.header(ACCEPT_ENCODING, ACCEPT_ENCODING_VALUE)
+    }
+}
+
+/// Decompresses the body of the given response according to its `Content-Encoding` header.
+///
+/// The response is left unchanged if the body is not compressed, if the encoding is not
+/// supported, or if the body cannot be decompressed (e.g. because it is corrupted or too large).
+/// In the latter cases, the body will then fail to be parsed as a JSON-RPC response.
+pub fn decompress_response(response: &mut HttpRequestResult) {
+    let content_encoding = response
+        .headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(CONTENT_ENCODING.as_str()))
+        .map(|header| header.value.trim().to_ascii_lowercase());
+    let decompressed = match content_encoding.as_deref() {
+        Some("gzip") | Some("x-gzip") => decompress(MultiGzDecoder::new(&response.body[..])),
+        // The `deflate` content encoding should use the zlib format, but some servers
+        // incorrectly send raw deflate data.
+        Some("deflate") => decompress(ZlibDecoder::new(&response.body[..]))
+            .or_else(|| decompress(DeflateDecoder::new(&response.body[..]))),
+        _ => None,
+    };
+    if let Some(body) = decompressed {
+        response.body = body;
+    }
+}
+
+fn decompress(decoder: impl Read) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    decoder
+        .take(MAX_DECOMPRESSED_BODY_BYTES + 1)
+        .read_to_end(&mut body)
+        .ok()?;
+    if body.len() as u64 > MAX_DECOMPRESSED_BODY_BYTES {
+        return None;
+    }
+    Some(body)
+}
@@ -0,0 +1,157 @@
+use crate::http::compression::{
+    accept_compressed_response, decompress_response, ACCEPT_ENCODING_VALUE,
+    MAX_DECOMPRESSED_BODY_BYTES,
+};
+use flate2::{
+    write::{DeflateEncoder, GzEncoder, ZlibEncoder},
+    Compression,
+};
+use http::header::ACCEPT_ENCODING;
+use ic_cdk_management_canister::HttpRequestResult;
+use ic_management_canister_types::HttpHeader;
+use std::io::Write;
+
+const BODY: &[u8] = br#"{"jsonrpc":"2.0","result":{"context":{"slot":334048531},"value":{"lamports":1000000000,"data":["","base64"],"owner":"11111111111111111111111111111111","executable":false,"rentEpoch":18446744073709551615,"space":0}},"id":1}"#;
+
+mod accept_compressed_response {
+    use super::*;
+
+    #[test]
+    fn should_add_accept_encoding_header() {
+        let request = accept_compressed_response(http::Request::post("https://rpc.com"))
+            .body(())
+            .unwrap();
+
+        assert_eq!(
+            request.headers().get(ACCEPT_ENCODING).unwrap(),
+            ACCEPT_ENCODING_VALUE
+        );
+    }
+
+    #[test]
+    fn should_not_override_existing_accept_encoding_header() {
+        let request = accept_compressed_response(
+            http::Request::post("https://rpc.com").header("Accept-Encoding", "identity"),
+        )
+        .body(())
+        .unwrap();
+
+        assert_eq!(
+            request
+                .headers()
+                .get_all(ACCEPT_ENCODING)
+                .iter()
+                .collect::<Vec<_>>(),
+            vec!["identity"]
+        );
+    }
+}
+
+mod decompress_response {
+    use super::*;
+
+    #[test]
+    fn should_decompress_gzip_response() {
+        for encoding in ["gzip", "x-gzip", "GZIP"] {
+            let mut response = response(Some(encoding), gzip(BODY));
+
+            decompress_response(&mut response);
+
+            assert_eq!(response.body, BODY);
+        }
+    }
+
+    #[test]
+    fn should_decompress_deflate_response() {
+        for body in [zlib(BODY), deflate(BODY)] {
+            let mut response = response(Some("deflate"), body);
+
+            decompress_response(&mut response);
+
+            assert_eq!(response.body, BODY);
+        }
+    }
+
+    #[test]
+    fn should_find_content_encoding_header_case_insensitively() {
+        let mut response = HttpRequestResult {
+            status: 200_u16.into(),
+            headers: vec![HttpHeader {
+                name: "Content-Encoding".to_string(),
+                value: "gzip".to_string(),
+            }],
+            body: gzip(BODY),
+        };
+
+        decompress_response(&mut response);
+
+        assert_eq!(response.body, BODY);
+    }
+
+    #[test]
+    fn should_not_modify_uncompressed_response() {
+        for encoding in [None, Some("identity"), Some("br")] {
+            let mut response = response(encoding, BODY.to_vec());
+
+            decompress_response(&mut response);
+
+            assert_eq!(response.body, BODY);
+        }
+    }
+
+    #[test]
+    fn should_not_modify_corrupted_response() {
+        let corrupted = vec![0xff; 16];
+        for encoding in ["gzip", "deflate"] {
+            let mut response = response(Some(encoding), corrupted.clone());
+
+            decompress_response(&mut response);
+
+            assert_eq!(response.body, corrupted);
+        }
+    }
+
+    #[test]
+    fn should_not_decompress_too_large_response() {
+        let too_large = vec![b' '; MAX_DECOMPRESSED_BODY_BYTES as usize + 1];
+        let compressed = gzip(&too_large);
+        let mut response = response(Some("gzip"), compressed.clone());
+
+        decompress_response(&mut response);
+
+        assert_eq!(response.body, compressed);
+    }
+
+    fn response(content_encoding: Option<&str>, body: Vec<u8>) -> HttpRequestResult {
+        HttpRequestResult {
+            status: 200_u16.into(),
+            headers: content_encoding
+                .map(|value| {
+                    vec![HttpHeader {
+                        name: "content-encoding".to_string(),
+                        value: value.to_string(),
+                    }]
+                })
+                .unwrap_or_default(),
+            body,
+        }
+    }
+
+    fn gzip(body: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zlib(body: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn deflate(body: &[u8]) -> Vec<u8> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+}
@@ -0,0 +1,340 @@
+//! Limit on the number of concurrent HTTPS outcalls.
+//!
+//! When the maximum number of HTTPS outcalls configured with [`OutcallConcurrency`] is in
+//! flight, further outcalls wait in a FIFO queue until an in-flight outcall completes. Outcalls
+//! are shed when the queue is full or when they waited longer than the queue timeout, so that
+//! a burst of requests degrades predictably instead of failing with opaque transient errors
+//! once the HTTPS outcall capacity of the subnet is exhausted.
+//!
+//! HTTPS outcalls of [`RequestPriority::Expedited`] requests, which pay a premium, skip the queue
+//! and are made immediately. They still count towards the HTTPS outcalls in flight.
+//!
+//! The time spent by the HTTPS outcalls of a request in flight, i.e. excluding the time spent
+//! waiting in the queue, is recorded in its [`OutcallDuration`] extension, if any.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{add_metric_entry, http::errors::HttpClientError, memory::read_state};
+use sol_rpc_types::{OutcallConcurrency, OutcallShedReason, RequestPriority};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+thread_local! {
+    /// The limiter is not persisted across upgrades, since no HTTPS outcall is in flight
+    /// when the canister is upgraded.
+    static LIMITER: RefCell<OutcallLimiter> = RefCell::new(OutcallLimiter::default());
+}
+
+/// Number of HTTPS outcalls currently in flight.
+pub fn num_outcalls_in_flight() -> u32 {
+    LIMITER.with_borrow(|limiter| limiter.in_flight)
+}
+
+/// Number of HTTPS outcalls currently waiting in the queue.
+pub fn num_queued_outcalls() -> usize {
+    LIMITER.with_borrow(|limiter| limiter.queue.len())
+}
+
+/// [`Layer`] limiting the number of concurrent HTTPS outcalls made by the inner service,
+/// see [`LimitConcurrentOutcalls`].
+#[derive(Clone, Debug, Default)]
+pub struct LimitConcurrentOutcallsLayer {
+    priority: RequestPriority,
+}
+
+impl LimitConcurrentOutcallsLayer {
+    /// Creates a new layer for HTTPS outcalls made for requests with the given priority.
+    pub fn new(priority: RequestPriority) -> Self {
+        Self { priority }
+    }
+}
+
+impl<S> Layer<S> for LimitConcurrentOutcallsLayer {
+    type Service = LimitConcurrentOutcalls<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LimitConcurrentOutcalls {
+            inner,
+            priority: self.priority,
+        }
+    }
+}
+
+/// Service waiting for a permit of the limiter before making an HTTPS outcall with the inner
+/// service, and failing with [`HttpClientError::OutcallShed`] if the outcall was shed.
+///
+/// The permit is held until the HTTPS outcall completes.
+#[derive(Clone, Debug)]
+pub struct LimitConcurrentOutcalls<S> {
+    inner: S,
+    priority: RequestPriority,
+}
+
+impl<S, Body> Service<http::Request<Body>> for LimitConcurrentOutcalls<S>
+where
+    S: Service<http::Request<Body>, Error = HttpClientError> + Clone + 'static,
+    Body: 'static,
+{
+    type Response = S::Response;
+    type Error = HttpClientError;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, HttpClientError>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        // Use the service that was driven to readiness, see
+        // https://docs.rs/tower/latest/tower/trait.Service.html#be-careful-when-cloning-inner-services
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let priority = self.priority;
+        let duration = request.extensions().get::<OutcallDuration>().cloned();
+        Box::pin(async move {
+            let _permit = acquire_permit(priority).await?;
+            let dispatched_at = ic_cdk::api::time();
+            let result = inner.call(request).await;
+            if let Some(duration) = duration {
+                duration.add(Duration::from_nanos(
+                    ic_cdk::api::time().saturating_sub(dispatched_at),
+                ));
+            }
+            result
+        })
+    }
+}
+
+/// Request extension recording the total time spent by the HTTPS outcalls of a request in
+/// flight, i.e. excluding the time spent waiting in the queue. The time is shared by all the
+/// retries of the request, since the extensions of a request are cloned when it is retried.
+#[derive(Clone, Debug, Default)]
+pub struct OutcallDuration(Arc<Mutex<Option<Duration>>>);
+
+impl OutcallDuration {
+    /// Total time spent by the HTTPS outcalls in flight, or `None` if no HTTPS outcall was made,
+    /// e.g. because it was shed.
+    pub fn get(&self) -> Option<Duration> {
+        *self.0.lock().expect("BUG: poisoned lock")
+    }
+
+    fn add(&self, duration: Duration) {
+        let mut total = self.0.lock().expect("BUG: poisoned lock");
+        *total = Some(total.unwrap_or_default() + duration);
+    }
+}
+
+/// Waits until an HTTPS outcall may be made according to the current [`OutcallConcurrency`].
+///
+/// Every shed HTTPS outcall is recorded in the `outcalls_shed` metric.
+async fn acquire_permit(priority: RequestPriority) -> Result<OutcallPermit, OutcallShedReason> {
+    let result =
+        match LIMITER.with_borrow_mut(|limiter| limiter.try_acquire(&current_limit(), priority)) {
+            Admission::Granted => Ok(OutcallPermit),
+            Admission::Shed(reason) => Err(reason),
+            Admission::Queued(waiter) => {
+                let timer = ic_cdk_timers::set_timer(queue_timeout(&current_limit()), {
+                    let waiter = waiter.clone();
+                    async move {
+                        LIMITER.with_borrow_mut(|limiter| limiter.time_out(&waiter));
+                    }
+                });
+                let result = QueuedPermit {
+                    waiter,
+                    done: false,
+                }
+                .await;
+                ic_cdk_timers::clear_timer(timer);
+                result
+            }
+        };
+    if let Err(reason) = result {
+        add_metric_entry!(outcalls_shed, reason, 1);
+    }
+    result
+}
+
+fn current_limit() -> OutcallConcurrency {
+    read_state(|s| s.get_outcall_concurrency())
+}
+
+fn queue_timeout(limit: &OutcallConcurrency) -> Duration {
+    match limit {
+        OutcallConcurrency::Unlimited => Duration::ZERO,
+        OutcallConcurrency::Limited {
+            queue_timeout_seconds,
+            ..
+        } => Duration::from_secs(*queue_timeout_seconds as u64),
+    }
+}
+
+/// Permit to make an HTTPS outcall, which is released when dropped.
+#[derive(Debug)]
+pub struct OutcallPermit;
+
+impl Drop for OutcallPermit {
+    fn drop(&mut self) {
+        let limit = current_limit();
+        LIMITER.with_borrow_mut(|limiter| limiter.release(&limit));
+    }
+}
+
+/// Future resolving when a queued HTTPS outcall is either granted a permit or timed out.
+struct QueuedPermit {
+    waiter: Rc<RefCell<Waiter>>,
+    done: bool,
+}
+
+impl Future for QueuedPermit {
+    type Output = Result<OutcallPermit, OutcallShedReason>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut waiter = self.waiter.borrow_mut();
+        let result = match waiter.status {
+            WaiterStatus::Queued => {
+                waiter.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+            WaiterStatus::Granted => Ok(OutcallPermit),
+            WaiterStatus::TimedOut => Err(OutcallShedReason::QueueTimeout),
+        };
+        drop(waiter);
+        self.done = true;
+        Poll::Ready(result)
+    }
+}
+
+impl Drop for QueuedPermit {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        // The future was dropped before completing, e.g. because the call was cancelled,
+        // so that the waiter must not hold a place in the queue or a permit.
+        let status = self.waiter.borrow().status;
+        match status {
+            WaiterStatus::Queued => {
+                LIMITER.with_borrow_mut(|limiter| limiter.time_out(&self.waiter));
+            }
+            WaiterStatus::Granted => drop(OutcallPermit),
+            WaiterStatus::TimedOut => {}
+        }
+    }
+}
+
+/// Outcome of a request for a permit to make an HTTPS outcall.
+#[derive(Debug)]
+pub enum Admission {
+    /// The HTTPS outcall may be made immediately.
+    Granted,
+    /// The HTTPS outcall must wait in the queue.
+    Queued(Rc<RefCell<Waiter>>),
+    /// The HTTPS outcall must not be made.
+    Shed(OutcallShedReason),
+}
+
+/// An HTTPS outcall waiting in the queue.
+#[derive(Debug, Default)]
+pub struct Waiter {
+    status: WaiterStatus,
+    waker: Option<Waker>,
+}
+
+impl Waiter {
+    fn set_status(&mut self, status: WaiterStatus) {
+        self.status = status;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WaiterStatus {
+    #[default]
+    Queued,
+    Granted,
+    TimedOut,
+}
+
+/// Bookkeeping of the HTTPS outcalls in flight and waiting in the queue.
+#[derive(Debug, Default)]
+pub struct OutcallLimiter {
+    in_flight: u32,
+    queue: VecDeque<Rc<RefCell<Waiter>>>,
+}
+
+impl OutcallLimiter {
+    /// Requests a permit to make an HTTPS outcall for a request with the given priority.
+    ///
+    /// A permit is only granted immediately if no other HTTPS outcall is waiting in the queue,
+    /// so that queued outcalls are granted a permit in FIFO order. Expedited HTTPS outcalls are
+    /// always granted a permit immediately.
+    pub fn try_acquire(
+        &mut self,
+        limit: &OutcallConcurrency,
+        priority: RequestPriority,
+    ) -> Admission {
+        if priority == RequestPriority::Expedited {
+            self.in_flight += 1;
+            return Admission::Granted;
+        }
+        match limit {
+            OutcallConcurrency::Unlimited => {
+                self.in_flight += 1;
+                Admission::Granted
+            }
+            OutcallConcurrency::Limited {
+                max_in_flight,
+                max_queue_length,
+                ..
+            } => {
+                if self.in_flight < *max_in_flight && self.queue.is_empty() {
+                    self.in_flight += 1;
+                    Admission::Granted
+                } else if self.queue.len() < *max_queue_length as usize {
+                    let waiter = Rc::new(RefCell::new(Waiter::default()));
+                    self.queue.push_back(waiter.clone());
+                    Admission::Queued(waiter)
+                } else {
+                    Admission::Shed(OutcallShedReason::QueueFull)
+                }
+            }
+        }
+    }
+
+    /// Releases the permit of a completed HTTPS outcall and grants permits to the HTTPS outcalls
+    /// waiting in the queue, if possible.
+    pub fn release(&mut self, limit: &OutcallConcurrency) {
+        self.in_flight = self.in_flight.saturating_sub(1);
+        let max_in_flight = match limit {
+            OutcallConcurrency::Unlimited => u32::MAX,
+            OutcallConcurrency::Limited { max_in_flight, .. } => *max_in_flight,
+        };
+        while self.in_flight < max_in_flight {
+            let Some(waiter) = self.queue.pop_front() else {
+                break;
+            };
+            self.in_flight += 1;
+            waiter.borrow_mut().set_status(WaiterStatus::Granted);
+        }
+    }
+
+    /// Removes the given HTTPS outcall from the queue, if it is still waiting.
+    pub fn time_out(&mut self, waiter: &Rc<RefCell<Waiter>>) {
+        if waiter.borrow().status != WaiterStatus::Queued {
+            return;
+        }
+        self.queue.retain(|queued| !Rc::ptr_eq(queued, waiter));
+        waiter.borrow_mut().set_status(WaiterStatus::TimedOut);
+    }
+}
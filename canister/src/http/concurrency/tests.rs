@@ -0,0 +1,192 @@
+use crate::http::concurrency::{Admission, OutcallLimiter, Waiter, WaiterStatus};
+use assert_matches::assert_matches;
+use sol_rpc_types::{OutcallConcurrency, OutcallShedReason, RequestPriority};
+use std::{cell::RefCell, rc::Rc};
+
+const LIMIT: OutcallConcurrency = OutcallConcurrency::Limited {
+    max_in_flight: 2,
+    max_queue_length: 2,
+    queue_timeout_seconds: 10,
+};
+
+#[test]
+fn should_not_limit_outcalls_when_unlimited() {
+    let mut limiter = OutcallLimiter::default();
+
+    for _ in 0..100 {
+        assert_matches!(
+            limiter.try_acquire(&OutcallConcurrency::Unlimited, RequestPriority::Normal),
+            Admission::Granted
+        );
+    }
+    assert_eq!(limiter.in_flight, 100);
+    assert!(limiter.queue.is_empty());
+}
+
+#[test]
+fn should_queue_and_then_shed_outcalls() {
+    let mut limiter = OutcallLimiter::default();
+
+    assert_matches!(
+        limiter.try_acquire(&LIMIT, RequestPriority::Normal),
+        Admission::Granted
+    );
+    assert_matches!(
+        limiter.try_acquire(&LIMIT, RequestPriority::Normal),
+        Admission::Granted
+    );
+    assert_matches!(
+        limiter.try_acquire(&LIMIT, RequestPriority::Normal),
+        Admission::Queued(_)
+    );
+    assert_matches!(
+        limiter.try_acquire(&LIMIT, RequestPriority::Normal),
+        Admission::Queued(_)
+    );
+    assert_matches!(
+        limiter.try_acquire(&LIMIT, RequestPriority::Normal),
+        Admission::Shed(OutcallShedReason::QueueFull)
+    );
+    assert_eq!(limiter.in_flight, 2);
+    assert_eq!(limiter.queue.len(), 2);
+}
+
+#[test]
+fn should_shed_immediately_without_queue() {
+    let limit = OutcallConcurrency::Limited {
+        max_in_flight: 1,
+        max_queue_length: 0,
+        queue_timeout_seconds: 10,
+    };
+    let mut limiter = OutcallLimiter::default();
+
+    assert_matches!(
+        limiter.try_acquire(&limit, RequestPriority::Normal),
+        Admission::Granted
+    );
+    assert_matches!(
+        limiter.try_acquire(&limit, RequestPriority::Normal),
+        Admission::Shed(OutcallShedReason::QueueFull)
+    );
+}
+
+#[test]
+fn should_grant_expedited_outcalls_without_queuing() {
+    let mut limiter = OutcallLimiter::default();
+
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    let waiter = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+
+    assert_matches!(
+        limiter.try_acquire(&LIMIT, RequestPriority::Expedited),
+        Admission::Granted
+    );
+    assert_eq!(limiter.in_flight, 3);
+    assert_eq!(limiter.queue.len(), 2);
+
+    // The expedited outcall counts towards the outcalls in flight when released.
+    limiter.release(&LIMIT);
+    assert_eq!(waiter.borrow().status, WaiterStatus::Queued);
+    limiter.release(&LIMIT);
+    assert_eq!(waiter.borrow().status, WaiterStatus::Granted);
+}
+
+#[test]
+fn should_grant_queued_outcalls_in_fifo_order() {
+    let mut limiter = OutcallLimiter::default();
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    let first = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+    let second = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+
+    limiter.release(&LIMIT);
+
+    assert_eq!(first.borrow().status, WaiterStatus::Granted);
+    assert_eq!(second.borrow().status, WaiterStatus::Queued);
+    assert_eq!(limiter.in_flight, 2);
+    assert_eq!(limiter.queue.len(), 1);
+
+    limiter.release(&LIMIT);
+
+    assert_eq!(second.borrow().status, WaiterStatus::Granted);
+    assert_eq!(limiter.in_flight, 2);
+    assert!(limiter.queue.is_empty());
+
+    limiter.release(&LIMIT);
+    limiter.release(&LIMIT);
+
+    assert_eq!(limiter.in_flight, 0);
+}
+
+#[test]
+fn should_not_grant_outcall_before_queued_outcalls() {
+    let mut limiter = OutcallLimiter::default();
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    let waiter = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+    // Simulate a concurrent release that did not yet grant the queued outcall
+    limiter.in_flight = 1;
+
+    assert_matches!(
+        limiter.try_acquire(&LIMIT, RequestPriority::Normal),
+        Admission::Queued(_)
+    );
+    assert_eq!(waiter.borrow().status, WaiterStatus::Queued);
+}
+
+#[test]
+fn should_time_out_queued_outcall() {
+    let mut limiter = OutcallLimiter::default();
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    let first = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+    let second = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+
+    limiter.time_out(&first);
+
+    assert_eq!(first.borrow().status, WaiterStatus::TimedOut);
+    assert_eq!(limiter.queue.len(), 1);
+
+    limiter.release(&LIMIT);
+
+    assert_eq!(first.borrow().status, WaiterStatus::TimedOut);
+    assert_eq!(second.borrow().status, WaiterStatus::Granted);
+}
+
+#[test]
+fn should_not_time_out_granted_outcall() {
+    let mut limiter = OutcallLimiter::default();
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    let waiter = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+    limiter.release(&LIMIT);
+
+    limiter.time_out(&waiter);
+
+    assert_eq!(waiter.borrow().status, WaiterStatus::Granted);
+    assert_eq!(limiter.in_flight, 2);
+}
+
+#[test]
+fn should_grant_queued_outcalls_when_limit_is_raised() {
+    let mut limiter = OutcallLimiter::default();
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    limiter.try_acquire(&LIMIT, RequestPriority::Normal);
+    let first = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+    let second = queued(limiter.try_acquire(&LIMIT, RequestPriority::Normal));
+
+    limiter.release(&OutcallConcurrency::Unlimited);
+
+    assert_eq!(first.borrow().status, WaiterStatus::Granted);
+    assert_eq!(second.borrow().status, WaiterStatus::Granted);
+    assert_eq!(limiter.in_flight, 3);
+}
+
+fn queued(admission: Admission) -> Rc<RefCell<Waiter>> {
+    match admission {
+        Admission::Queued(waiter) => waiter,
+        other => panic!("Expected queued outcall, but got {other:?}"),
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests;
+
+use canhttp::{
+    cycles::ChargeCallerError,
+    http::{
+        json::{
+            ConsistentResponseIdFilterError, JsonRequestConversionError,
+            JsonResponseConversionError,
+        },
+        FilterNonSuccessfulHttpResponseError, HttpRequestConversionError,
+        HttpResponseConversionError,
+    },
+    HttpsOutcallError, IcError,
+};
+use derive_more::From;
+use http::{header::RETRY_AFTER, HeaderMap, StatusCode};
+use sol_rpc_types::{
+    HttpOutcallError, IpVersion, LegacyRejectionCode, OutcallShedReason, ProviderError, RpcError,
+};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, From)]
+pub enum HttpClientError {
+    #[error("IC error: {0}")]
+    IcError(IcError),
+    #[error("unknown error (most likely sign of a bug): {0}")]
+    NotHandledError(String),
+    #[error("cycles accounting error: {0}")]
+    CyclesAccountingError(ChargeCallerError),
+    #[error("HTTP response was not successful: {0}")]
+    UnsuccessfulHttpResponse(FilterNonSuccessfulHttpResponseError<Vec<u8>>),
+    #[error("Error converting response to JSON: {0}")]
+    InvalidJsonResponse(JsonResponseConversionError),
+    #[error("Invalid JSON-RPC response ID: {0}")]
+    InvalidJsonResponseId(ConsistentResponseIdFilterError),
+    #[error("HTTPS outcall shed: {0}")]
+    OutcallShed(OutcallShedReason),
+}
+
+impl From<HttpRequestConversionError> for HttpClientError {
+    fn from(value: HttpRequestConversionError) -> Self {
+        HttpClientError::NotHandledError(value.to_string())
+    }
+}
+
+impl From<HttpResponseConversionError> for HttpClientError {
+    fn from(value: HttpResponseConversionError) -> Self {
+        // Replica should return valid http::Response
+        HttpClientError::NotHandledError(value.to_string())
+    }
+}
+
+impl From<JsonRequestConversionError> for HttpClientError {
+    fn from(value: JsonRequestConversionError) -> Self {
+        HttpClientError::NotHandledError(value.to_string())
+    }
+}
+
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error(transparent)]
+pub struct UnrecoverableError(IcError);
+
+impl TryFrom<HttpClientError> for RpcError {
+    type Error = UnrecoverableError;
+
+    fn try_from(error: HttpClientError) -> Result<Self, Self::Error> {
+        match error {
+            HttpClientError::IcError(IcError::CallRejected { code, message }) => {
+                Ok(RpcError::HttpOutcallError(HttpOutcallError::IcError {
+                    code: LegacyRejectionCode::from(code),
+                    message,
+                }))
+            }
+            HttpClientError::IcError(e @ IcError::InsufficientLiquidCycleBalance { .. }) => {
+                Err(UnrecoverableError(e))
+            }
+            HttpClientError::NotHandledError(e) => Ok(RpcError::ValidationError(e)),
+            HttpClientError::CyclesAccountingError(
+                ChargeCallerError::InsufficientCyclesError { expected, received },
+            ) => Ok(RpcError::ProviderError(ProviderError::TooFewCycles {
+                expected,
+                received,
+            })),
+            HttpClientError::InvalidJsonResponse(
+                JsonResponseConversionError::InvalidJsonResponse {
+                    status,
+                    body,
+                    parsing_error,
+                },
+            ) => Ok(RpcError::HttpOutcallError(
+                HttpOutcallError::InvalidHttpJsonRpcResponse {
+                    status,
+                    body,
+                    parsing_error: Some(parsing_error),
+                },
+            )),
+            HttpClientError::UnsuccessfulHttpResponse(
+                FilterNonSuccessfulHttpResponseError::UnsuccessfulResponse(response),
+            ) => Ok(unsuccessful_response_error(&response)),
+            HttpClientError::InvalidJsonResponseId(e) => {
+                Ok(RpcError::ValidationError(e.to_string()))
+            }
+            HttpClientError::OutcallShed(reason) => {
+                Ok(RpcError::HttpOutcallError(HttpOutcallError::OutcallShed {
+                    reason,
+                }))
+            }
+        }
+    }
+}
+
+/// Maps an unsuccessful HTTP response to an [`RpcError`].
+///
+/// Responses indicating that the request was rejected because of invalid credentials or rate
+/// limiting are mapped to a dedicated [`ProviderError`], so that they can be told apart from
+/// other unsuccessful responses.
+pub fn unsuccessful_response_error(response: &http::Response<Vec<u8>>) -> RpcError {
+    let status = response.status();
+    let body = String::from_utf8_lossy(response.body()).to_string();
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            RpcError::ProviderError(ProviderError::Unauthorized {
+                status: status.as_u16(),
+                body,
+            })
+        }
+        StatusCode::TOO_MANY_REQUESTS => RpcError::ProviderError(ProviderError::RateLimited {
+            retry_after: retry_after_seconds(response.headers()),
+            body,
+        }),
+        _ => RpcError::HttpOutcallError(HttpOutcallError::InvalidHttpJsonRpcResponse {
+            status: status.as_u16(),
+            body,
+            parsing_error: None,
+        }),
+    }
+}
+
+/// Fragments of the rejection messages of HTTPS outcalls that could not connect to the host.
+const CONNECTION_ERROR_HINTS: &[&str] = &[
+    "connect",
+    "dns",
+    "resolve",
+    "unreachable",
+    "no route to host",
+];
+
+/// Returns `true` if an HTTPS outcall was rejected because the host could not be connected to,
+/// e.g. because its name could not be resolved or it is not reachable over the IP versions
+/// available to the subnet nodes, as opposed to other `SYS_FATAL` rejections such as a response
+/// exceeding the maximum response size.
+pub fn is_connection_error(code: LegacyRejectionCode, message: &str) -> bool {
+    let message = message.to_lowercase();
+    code == LegacyRejectionCode::SysFatal
+        && CONNECTION_ERROR_HINTS
+            .iter()
+            .any(|hint| message.contains(hint))
+}
+
+/// Adds the host of the provider and the IP versions over which it is known to be reachable to
+/// the message of an HTTPS outcall that could not connect to the provider, so that a provider
+/// that cannot be reached from the subnet nodes can be told apart from a provider outage.
+///
+/// Other errors are returned unchanged.
+pub fn with_transport_diagnostics(
+    error: RpcError,
+    host: &str,
+    ip_version: Option<IpVersion>,
+) -> RpcError {
+    match error {
+        RpcError::HttpOutcallError(HttpOutcallError::IcError { code, message })
+            if is_connection_error(code, &message) =>
+        {
+            let hint = match ip_version {
+                Some(IpVersion::Ipv4) => "reachable over IPv4 only, which may not be supported by all subnet nodes: consider setting a preferred IPv6 endpoint with `providerEndpoints`",
+                Some(IpVersion::Ipv6) => "reachable over IPv6 only, the provider is likely unavailable",
+                Some(IpVersion::DualStack) => "reachable over IPv4 and IPv6, the provider is likely unavailable",
+                None => "no known transport hints",
+            };
+            RpcError::HttpOutcallError(HttpOutcallError::IcError {
+                code,
+                message: format!("{message} [host: {host}, transport: {hint}]"),
+            })
+        }
+        error => error,
+    }
+}
+
+/// Parses the `Retry-After` header, if it is given as a number of seconds.
+///
+/// The header may also contain an HTTP date, which is not supported.
+fn retry_after_seconds(headers: &HeaderMap) -> Option<u64> {
+    headers.get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()
+}
+
+impl HttpsOutcallError for HttpClientError {
+    fn is_response_too_large(&self) -> bool {
+        match self {
+            HttpClientError::IcError(e) => e.is_response_too_large(),
+            HttpClientError::NotHandledError(_)
+            | HttpClientError::CyclesAccountingError(_)
+            | HttpClientError::UnsuccessfulHttpResponse(_)
+            | HttpClientError::InvalidJsonResponseId(_)
+            | HttpClientError::InvalidJsonResponse(_)
+            | HttpClientError::OutcallShed(_) => false,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::http::errors::{
+    is_connection_error, unsuccessful_response_error, with_transport_diagnostics,
+};
+use sol_rpc_types::{HttpOutcallError, IpVersion, LegacyRejectionCode, ProviderError, RpcError};
+
+#[test]
+fn should_map_unauthorized_responses() {
+    for status in [401, 403] {
+        assert_eq!(
+            unsuccessful_response_error(&response(status, &[], "invalid API key")),
+            RpcError::ProviderError(ProviderError::Unauthorized {
+                status,
+                body: "invalid API key".to_string(),
+            })
+        );
+    }
+}
+
+#[test]
+fn should_map_rate_limited_responses() {
+    for (headers, expected_retry_after) in [
+        (vec![], None),
+        (vec![("Retry-After", "30")], Some(30)),
+        (vec![("retry-after", " 5 ")], Some(5)),
+        (vec![("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT")], None),
+    ] {
+        assert_eq!(
+            unsuccessful_response_error(&response(429, &headers, "too many requests")),
+            RpcError::ProviderError(ProviderError::RateLimited {
+                retry_after: expected_retry_after,
+                body: "too many requests".to_string(),
+            })
+        );
+    }
+}
+
+#[test]
+fn should_map_other_unsuccessful_responses() {
+    for status in [400, 404, 500, 503] {
+        assert_eq!(
+            unsuccessful_response_error(&response(status, &[], "error")),
+            RpcError::HttpOutcallError(HttpOutcallError::InvalidHttpJsonRpcResponse {
+                status,
+                body: "error".to_string(),
+                parsing_error: None,
+            })
+        );
+    }
+}
+
+#[test]
+fn should_classify_connection_errors() {
+    for message in [
+        "Connecting to solana-mainnet.g.alchemy.com failed: Failed to directly connect",
+        "DNS error: failed to lookup address information",
+        "Failed to resolve host",
+        "Network is unreachable",
+    ] {
+        assert!(
+            is_connection_error(LegacyRejectionCode::SysFatal, message),
+            "{message}"
+        );
+        assert!(
+            !is_connection_error(LegacyRejectionCode::SysTransient, message),
+            "{message}"
+        );
+    }
+    assert!(!is_connection_error(
+        LegacyRejectionCode::SysFatal,
+        "Http body exceeds size limit"
+    ));
+}
+
+#[test]
+fn should_add_transport_diagnostics_to_connection_errors() {
+    let connection_error = |message: &str| {
+        RpcError::HttpOutcallError(HttpOutcallError::IcError {
+            code: LegacyRejectionCode::SysFatal,
+            message: message.to_string(),
+        })
+    };
+
+    assert_eq!(
+        with_transport_diagnostics(
+            connection_error("Failed to connect"),
+            "solana-mainnet.g.alchemy.com",
+            Some(IpVersion::Ipv4)
+        ),
+        connection_error(
+            "Failed to connect [host: solana-mainnet.g.alchemy.com, transport: reachable over IPv4 only, which may not be supported by all subnet nodes: consider setting a preferred IPv6 endpoint with `providerEndpoints`]"
+        )
+    );
+    assert_eq!(
+        with_transport_diagnostics(
+            connection_error("Failed to connect"),
+            "rpc.ankr.com",
+            Some(IpVersion::DualStack)
+        ),
+        connection_error(
+            "Failed to connect [host: rpc.ankr.com, transport: reachable over IPv4 and IPv6, the provider is likely unavailable]"
+        )
+    );
+    assert_eq!(
+        with_transport_diagnostics(connection_error("Failed to connect"), "example.com", None),
+        connection_error(
+            "Failed to connect [host: example.com, transport: no known transport hints]"
+        )
+    );
+
+    let other_error = connection_error("Http body exceeds size limit");
+    assert_eq!(
+        with_transport_diagnostics(other_error.clone(), "example.com", Some(IpVersion::Ipv4)),
+        other_error
+    );
+}
+
+fn response(status: u16, headers: &[(&str, &str)], body: &str) -> http::Response<Vec<u8>> {
+    let mut builder = http::Response::builder().status(status);
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    builder.body(body.as_bytes().to_vec()).unwrap()
+}
@@ -1,12 +1,21 @@
+pub mod compression;
+pub mod concurrency;
 pub mod errors;
+pub mod retry;
 
 use crate::{
     add_latency_metric, add_metric_entry,
-    constants::{COLLATERAL_CYCLES_PER_NODE, CONTENT_TYPE_VALUE},
-    http::errors::HttpClientError,
+    constants::CONTENT_TYPE_VALUE,
+    http::{
+        concurrency::LimitConcurrentOutcallsLayer,
+        errors::{is_connection_error, HttpClientError},
+        retry::RetryHttpsOutcalls,
+    },
+    log,
     logs::Priority,
-    memory::{next_request_id, read_state},
+    memory::{debit_prepaid_cycles, next_request_id, read_state, record_host_latency},
     metrics::{MetricRpcCallResponse, MetricRpcHost, MetricRpcMethod},
+    util::hostname_from_url,
 };
 use canhttp::cycles::CyclesAccounting;
 use canhttp::{
@@ -21,16 +30,18 @@ use canhttp::{
         FilterNonSuccessfulHttpResponse, FilterNonSuccessfulHttpResponseError,
         HttpRequestConverter, HttpResponseConverter,
     },
+    multi::Timestamp,
     observability::ObservabilityLayer,
-    retry::DoubleMaxResponseBytes,
     ConvertServiceBuilder, HttpsOutcallError, IcError,
 };
-use canlog::log;
 use http::{header::CONTENT_TYPE, HeaderValue};
 use ic_cdk_management_canister::HttpRequestArgs as IcHttpRequest;
 use serde::{de::DeserializeOwned, Serialize};
-use sol_rpc_types::{JsonRpcError, RpcError};
-use std::fmt::Debug;
+use sol_rpc_types::{
+    JsonRpcError, LegacyRejectionCode, RequestIdFormat, RequestIdStrategy, RequestPriority,
+    RpcError, RpcSource,
+};
+use std::{fmt::Debug, time::Duration};
 use tower::{
     layer::util::{Identity, Stack},
     retry::RetryLayer,
@@ -39,20 +50,42 @@ use tower::{
 };
 use tower_http::{set_header::SetRequestHeaderLayer, ServiceBuilderExt};
 
+/// Who pays for the HTTPS outcalls made by the SOL RPC canister.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CyclesPayer {
+    /// The caller attaches the cycles for the HTTPS outcalls to its request, unless the canister
+    /// is in demo mode.
+    Caller,
+    /// The SOL RPC canister pays for the HTTPS outcalls with its own cycles, e.g. for HTTPS
+    /// outcalls made by a timer.
+    Canister,
+}
+
 pub fn http_client<I, O>(
     rpc_method: MetricRpcMethod,
     retry: bool,
+    payer: CyclesPayer,
+    priority: RequestPriority,
 ) -> impl Service<HttpJsonRpcRequest<I>, Response = O, Error = RpcError>
 where
-    I: Serialize + Clone + Debug,
+    I: Serialize + Clone + Debug + 'static,
     O: DeserializeOwned + Debug,
 {
     let maybe_retry = if retry {
-        Some(RetryLayer::new(DoubleMaxResponseBytes))
+        Some(RetryLayer::new(RetryHttpsOutcalls::new(rpc_method.clone())))
+    } else {
+        None
+    };
+    // A request ID generated outside the retry layer is shared by all retries of a request,
+    // whereas a request ID generated inside the retry layer is regenerated for each retry.
+    let reuse_id_on_retry =
+        read_state(|s| s.get_request_id_strategy()) == RequestIdStrategy::ReuseOnRetry;
+    let maybe_reused_id = if retry && reuse_id_on_retry {
+        Some(MapRequestLayer::new(generate_request_id))
     } else {
         None
     };
-    let maybe_unique_id = if retry {
+    let maybe_unique_id = if retry && !reuse_id_on_retry {
         Some(MapRequestLayer::new(generate_request_id))
     } else {
         None
@@ -63,6 +96,7 @@ where
             log!(Priority::Info, "Unrecoverable error: {}", e);
             panic!("{}", e);
         }))
+        .option_layer(maybe_reused_id)
         .option_layer(maybe_retry)
         .option_layer(maybe_unique_id)
         .layer(
@@ -85,6 +119,7 @@ where
                     match response.body().as_result() {
                         Ok(_) => {
                             observe_response(MetricRpcCallResponse::Success, &req_data);
+                            observe_host_latency(&req_data);
                         }
                         Err(_) => observe_error_with_status(response.status(), &req_data),
                     }
@@ -110,8 +145,12 @@ where
                                 );
 
                                 match error {
-                                    IcError::CallRejected { code, .. } => {
-                                        observe_response(MetricRpcCallResponse::IcError(code.to_string()), &req_data);
+                                    IcError::CallRejected { code, message } => {
+                                        if is_connection_error(LegacyRejectionCode::from(*code), message) {
+                                            observe_response(MetricRpcCallResponse::IcTransportError(code.to_string()), &req_data);
+                                        } else {
+                                            observe_response(MetricRpcCallResponse::IcError(code.to_string()), &req_data);
+                                        }
                                     }
                                     IcError::InsufficientLiquidCycleBalance { .. } => {}
                                 }
@@ -166,15 +205,54 @@ where
                             log!(Priority::Info, "BUG: Unexpected error: {}", e);
                         }
                         HttpClientError::CyclesAccountingError(_) => {}
+                        HttpClientError::OutcallShed(reason) => {
+                            // Shed HTTPS outcalls are recorded in the `outcalls_shed` metric.
+                            log!(
+                                Priority::TraceHttp,
+                                "HTTPS outcall for request with id `{}` was shed: {}",
+                                req_data.request_id,
+                                reason
+                            );
+                        }
                     },
                 ),
         )
         .filter_response(CreateJsonRpcIdFilter::new())
+        .layer(LimitConcurrentOutcallsLayer::new(priority))
         .layer(service_request_builder())
         .convert_response(JsonResponseConverter::new())
         .convert_response(FilterNonSuccessfulHttpResponse)
         .convert_response(HttpResponseConverter)
-        .convert_request(CyclesAccounting::new(charging_policy_with_collateral()))
+        .convert_request(CyclesAccounting::new(charging_policy_with_prepaid_cycles(
+            rpc_method, payer, priority,
+        )))
+        .map_request(observe_cycles_spent)
+        .service(canhttp::Client::new_with_error::<HttpClientError>())
+}
+
+/// HTTP client for provider-specific requests that are not JSON-RPC requests.
+///
+/// Unlike [`http_client`], the response is neither parsed nor filtered on its status code, and
+/// requests are not retried.
+pub fn raw_http_client(
+    rpc_method: MetricRpcMethod,
+    payer: CyclesPayer,
+    priority: RequestPriority,
+) -> impl Service<http::Request<Vec<u8>>, Response = http::Response<Vec<u8>>, Error = RpcError> {
+    ServiceBuilder::new()
+        .map_err(|e: HttpClientError| {
+            RpcError::try_from(e).unwrap_or_else(|e| {
+                log!(Priority::Info, "Unrecoverable error: {}", e);
+                panic!("{}", e);
+            })
+        })
+        .layer(LimitConcurrentOutcallsLayer::new(priority))
+        .layer(raw_service_request_builder())
+        .convert_response(HttpResponseConverter)
+        .convert_request(CyclesAccounting::new(charging_policy_with_prepaid_cycles(
+            rpc_method, payer, priority,
+        )))
+        .map_request(observe_cycles_spent)
         .service(canhttp::Client::new_with_error::<HttpClientError>())
 }
 
@@ -186,16 +264,35 @@ fn extract_json_rpc_response<O>(
         Err(json_rpc_error) => Err(RpcError::JsonRpcError(JsonRpcError {
             code: json_rpc_error.code,
             message: json_rpc_error.message,
+            data: json_rpc_error.data.map(|data| data.to_string()),
         })),
     }
 }
 
 fn generate_request_id<I>(request: HttpJsonRpcRequest<I>) -> HttpJsonRpcRequest<I> {
     let (parts, mut body) = request.into_parts();
-    body.set_id(next_request_id());
+    let format = parts
+        .extensions
+        .get::<RequestIdFormat>()
+        .copied()
+        .unwrap_or_default();
+    body.set_id(next_request_id(format));
     http::Request::from_parts(parts, body)
 }
 
+/// Format of the JSON-RPC request IDs sent to the given source.
+///
+/// The format is added to the request extensions and used when generating the request ID.
+/// Since the consistent ID filter compares the ID of the response with the one of the request,
+/// it works for every format.
+pub fn resolve_request_id_format(source: &RpcSource) -> RequestIdFormat {
+    match source {
+        RpcSource::Supported(provider) => read_state(|s| s.get_request_id_format(provider)),
+        RpcSource::Custom(endpoint) => endpoint.request_id_format,
+    }
+    .unwrap_or_default()
+}
+
 fn observe_error_with_status(status: impl Into<u16>, req_data: &MetricData) {
     match status.into() {
         200 => observe_response(MetricRpcCallResponse::JsonRpcError, req_data),
@@ -212,7 +309,9 @@ fn observe_response(response: MetricRpcCallResponse, req_data: &MetricData) {
             (req_data.method.clone(), req_data.host.clone()),
             req_data.start_ns
         ),
-        MetricRpcCallResponse::IcError(_) | MetricRpcCallResponse::MaxResponseSizeExceeded => {
+        MetricRpcCallResponse::IcError(_)
+        | MetricRpcCallResponse::IcTransportError(_)
+        | MetricRpcCallResponse::MaxResponseSizeExceeded => {
             // Don't record latency for IC errors
         }
     }
@@ -228,6 +327,31 @@ fn observe_response(response: MetricRpcCallResponse, req_data: &MetricData) {
     );
 }
 
+/// Record the latency of a successful JSON-RPC call, which is used to select the fastest providers
+/// for expedited requests.
+fn observe_host_latency(req_data: &MetricData) {
+    let now = ic_cdk::api::time();
+    record_host_latency(
+        req_data.host.0.clone(),
+        Duration::from_nanos(now.saturating_sub(req_data.start_ns)),
+        Timestamp::from_nanos_since_unix_epoch(now),
+    );
+}
+
+/// Record the estimated cost of an HTTPS outcall, which only depends on the request size and
+/// the maximum response size, for the provider host it is sent to. Outcalls rejected by cycles
+/// accounting are never sent and are therefore not recorded.
+fn observe_cycles_spent(request: IcHttpRequest) -> IcHttpRequest {
+    if let Some(host) = hostname_from_url(&request.url) {
+        add_metric_entry!(
+            cycles_spent,
+            MetricRpcHost(host),
+            ic_cdk_management_canister::cost_http_request(&request)
+        );
+    }
+    request
+}
+
 struct MetricData {
     method: MetricRpcMethod,
     host: MetricRpcHost,
@@ -258,16 +382,63 @@ pub fn service_request_builder<I>() -> JsonRpcServiceBuilder<I> {
         .convert_request(HttpRequestConverter)
 }
 
+type RawHttpServiceBuilder = ServiceBuilder<
+    Stack<
+        ConvertRequestLayer<HttpRequestConverter>,
+        Stack<SetRequestHeaderLayer<HeaderValue>, Identity>,
+    >,
+>;
+
+/// Same as [`service_request_builder`] for requests whose body is already serialized.
+pub fn raw_service_request_builder() -> RawHttpServiceBuilder {
+    ServiceBuilder::new()
+        .insert_request_header_if_not_present(
+            CONTENT_TYPE,
+            HeaderValue::from_static(CONTENT_TYPE_VALUE),
+        )
+        .convert_request(HttpRequestConverter)
+}
+
 pub fn charging_policy_with_collateral(
+    payer: CyclesPayer,
+    priority: RequestPriority,
 ) -> ChargeCaller<impl Fn(&IcHttpRequest, u128) -> u128 + Clone> {
-    let charge_caller = if read_state(|s| s.is_demo_mode_active()) {
-        |_request: &IcHttpRequest, _request_cost| 0
-    } else {
-        |_request: &IcHttpRequest, request_cost| {
-            let collateral_cycles = COLLATERAL_CYCLES_PER_NODE
-                .saturating_mul(read_state(|s| s.get_num_subnet_nodes()) as u128);
-            request_cost + collateral_cycles
+    let is_free = payer == CyclesPayer::Canister || read_state(|s| s.is_demo_mode_active());
+    let charge_caller = move |_request: &IcHttpRequest, request_cost| {
+        if is_free {
+            return 0;
+        }
+        read_state(|s| s.cycles_to_charge(request_cost, priority))
+    };
+    ChargeCaller::new(charge_caller)
+}
+
+/// Same as [`charging_policy_with_collateral`], except that if the caller did not attach enough
+/// cycles to pay for an HTTPS outcall, they are taken from the caller's prepaid cycles instead,
+/// if sufficient.
+///
+/// Since prepaid cycles are debited when computing the cycles to charge, this policy must only be
+/// used for HTTPS outcalls that are actually made, and not to estimate their cost. The debited
+/// prepaid cycles are recorded in the `cycles_charged` metric of the given method, since they are
+/// not taken from the attached cycles.
+pub fn charging_policy_with_prepaid_cycles(
+    rpc_method: MetricRpcMethod,
+    payer: CyclesPayer,
+    priority: RequestPriority,
+) -> ChargeCaller<impl Fn(&IcHttpRequest, u128) -> u128 + Clone> {
+    let is_free = payer == CyclesPayer::Canister || read_state(|s| s.is_demo_mode_active());
+    let charge_caller = move |_request: &IcHttpRequest, request_cost| {
+        if is_free {
+            return 0;
+        }
+        let cycles_to_charge = read_state(|s| s.cycles_to_charge(request_cost, priority));
+        if cycles_to_charge > ic_cdk::api::msg_cycles_available()
+            && debit_prepaid_cycles(ic_cdk::api::msg_caller(), cycles_to_charge)
+        {
+            add_metric_entry!(cycles_charged, rpc_method.clone(), cycles_to_charge);
+            return 0;
         }
+        cycles_to_charge
     };
     ChargeCaller::new(charge_caller)
 }
@@ -0,0 +1,190 @@
+//! Retries of HTTPS outcalls.
+//!
+//! Besides retrying requests whose response was too large with a larger `max_response_bytes`,
+//! requests failing with a transient error, e.g. because the provider is rate limiting requests,
+//! are retried after an exponential backoff according to the [`RetryPolicy`] of the provider.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    add_metric_entry,
+    http::errors::HttpClientError,
+    memory::read_state,
+    metrics::{MetricRetryReason, MetricRpcHost, MetricRpcMethod},
+    util::sleep,
+};
+use canhttp::{
+    http::{json::HttpJsonRpcRequest, FilterNonSuccessfulHttpResponseError},
+    retry::DoubleMaxResponseBytes,
+    HttpsOutcallError, IcError,
+};
+use futures::future::{ready, FutureExt, LocalBoxFuture};
+use ic_cdk::call::RejectCode;
+use sol_rpc_types::{RetryPolicy, RpcSource};
+use std::time::Duration;
+use tower::retry::Policy;
+
+/// Maximum number of retries of a request failing with a transient error.
+pub const MAX_RETRIES: u8 = 3;
+
+/// Maximum number of retries of a `sendTransaction` request failing with a transient error.
+///
+/// The transaction may already have been forwarded to the cluster when the request fails,
+/// in which case retrying only adds load on the provider.
+pub const MAX_SEND_TRANSACTION_RETRIES: u8 = 1;
+
+/// HTTP status codes of the responses retried by a [`RetryPolicy`] not specifying any.
+pub const DEFAULT_RETRY_ON_HTTP_STATUS: [u16; 2] = [429, 503];
+
+/// Delay before the first retry of a request for a [`RetryPolicy`] not specifying any.
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximum delay before the first retry of a request.
+pub const MAX_INITIAL_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Retry policy for requests to providers without a retry policy.
+///
+/// Requests failing with a transient error are not retried, since the estimated cycles cost of a
+/// request does not include retries. Callers attaching exactly the estimated cycles cost would
+/// otherwise see the original error replaced by an error due to insufficient cycles.
+pub fn default_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: 0,
+        retry_on_transient_error: true,
+        retry_on_http_status: None,
+        initial_backoff_ms: None,
+    }
+}
+
+/// Maximum number of retries for requests with the given JSON-RPC method.
+pub fn max_retries(method: &str) -> u8 {
+    match method {
+        "sendTransaction" => MAX_SEND_TRANSACTION_RETRIES,
+        _ => MAX_RETRIES,
+    }
+}
+
+/// Returns the retry policy for requests with the given JSON-RPC method to the given source,
+/// i.e. the retry policy of the provider if any or otherwise the default retry policy of the
+/// method, with the number of retries capped by [`max_retries`].
+pub fn resolve_retry_policy(source: &RpcSource, method: &str) -> RetryPolicy {
+    let policy = source
+        .rpc_provider_id()
+        .and_then(|provider| read_state(|s| s.get_retry_policy(&provider)))
+        .unwrap_or_else(default_retry_policy);
+    cap_retry_policy(policy, method)
+}
+
+fn cap_retry_policy(policy: RetryPolicy, method: &str) -> RetryPolicy {
+    RetryPolicy {
+        max_retries: policy.max_retries.min(max_retries(method)),
+        initial_backoff_ms: policy
+            .initial_backoff_ms
+            .map(|ms| ms.min(MAX_INITIAL_BACKOFF.as_millis() as u64)),
+        ..policy
+    }
+}
+
+/// Delay before the given retry (starting at 1) of a request failing with a transient error.
+fn backoff(policy: &RetryPolicy, num_retry: u8) -> Duration {
+    let initial_backoff = policy
+        .initial_backoff_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_INITIAL_BACKOFF);
+    initial_backoff.saturating_mul(1_u32 << num_retry.saturating_sub(1).min(16))
+}
+
+/// Retry policy for HTTPS outcalls.
+///
+/// A request is retried:
+/// 1. With a doubled `max_response_bytes` when the response was too large, see
+///    [`DoubleMaxResponseBytes`].
+/// 2. When it failed with a transient error according to the [`RetryPolicy`] contained in the
+///    request extensions, if any. The retry is sent after a delay that doubles with each retry.
+///
+/// Every retry is recorded in the `retries` metric.
+#[derive(Clone, Debug)]
+pub struct RetryHttpsOutcalls {
+    rpc_method: MetricRpcMethod,
+    num_transient_retries: u8,
+}
+
+impl RetryHttpsOutcalls {
+    pub fn new(rpc_method: MetricRpcMethod) -> Self {
+        Self {
+            rpc_method,
+            num_transient_retries: 0,
+        }
+    }
+}
+
+impl<I, O> Policy<HttpJsonRpcRequest<I>, O, HttpClientError> for RetryHttpsOutcalls
+where
+    I: Clone,
+    DoubleMaxResponseBytes: Policy<HttpJsonRpcRequest<I>, O, HttpClientError>,
+{
+    type Future = LocalBoxFuture<'static, ()>;
+
+    fn retry(
+        &mut self,
+        req: &mut HttpJsonRpcRequest<I>,
+        result: &mut Result<O, HttpClientError>,
+    ) -> Option<Self::Future> {
+        let error = result.as_ref().err()?;
+        let (reason, delay) = if error.is_response_too_large() {
+            DoubleMaxResponseBytes.retry(req, result)?;
+            (MetricRetryReason::MaxResponseSizeExceeded, Duration::ZERO)
+        } else {
+            let policy = req.extensions().get::<RetryPolicy>()?;
+            if self.num_transient_retries >= policy.max_retries {
+                return None;
+            }
+            let reason = transient_error(error, policy)?;
+            self.num_transient_retries += 1;
+            (reason, backoff(policy, self.num_transient_retries))
+        };
+        add_metric_entry!(
+            retries,
+            (
+                self.rpc_method.clone(),
+                MetricRpcHost::from(req.uri().host().unwrap_or_default()),
+                reason
+            ),
+            1
+        );
+        if delay.is_zero() {
+            Some(ready(()).boxed_local())
+        } else {
+            Some(sleep(delay).boxed_local())
+        }
+    }
+
+    fn clone_request(&mut self, req: &HttpJsonRpcRequest<I>) -> Option<HttpJsonRpcRequest<I>> {
+        Some(req.clone())
+    }
+}
+
+/// Returns the reason to retry a request that failed with the given error according to the
+/// given policy, or `None` if the request should not be retried.
+fn transient_error(error: &HttpClientError, policy: &RetryPolicy) -> Option<MetricRetryReason> {
+    match error {
+        HttpClientError::IcError(IcError::CallRejected {
+            code: RejectCode::SysTransient,
+            ..
+        }) if policy.retry_on_transient_error => Some(MetricRetryReason::TransientError),
+        HttpClientError::UnsuccessfulHttpResponse(
+            FilterNonSuccessfulHttpResponseError::UnsuccessfulResponse(response),
+        ) if policy
+            .retry_on_http_status
+            .as_deref()
+            .unwrap_or(&DEFAULT_RETRY_ON_HTTP_STATUS)
+            .contains(&response.status().as_u16()) =>
+        {
+            Some(MetricRetryReason::HttpError(
+                response.status().as_u16().into(),
+            ))
+        }
+        _ => None,
+    }
+}
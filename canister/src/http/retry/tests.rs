@@ -0,0 +1,227 @@
+use crate::{
+    http::{
+        errors::HttpClientError,
+        retry::{backoff, cap_retry_policy, default_retry_policy, RetryHttpsOutcalls},
+    },
+    memory::UNSTABLE_METRICS,
+    metrics::{MetricRetryReason, MetricRpcHost, MetricRpcMethod},
+};
+use canhttp::{
+    http::{
+        json::{HttpJsonRpcRequest, JsonRpcRequest},
+        FilterNonSuccessfulHttpResponseError,
+    },
+    IcError,
+};
+use ic_cdk::call::RejectCode;
+use sol_rpc_types::RetryPolicy;
+use std::time::Duration;
+use tower::retry::Policy;
+
+const HOST: &str = "rpc.com";
+
+mod retry_https_outcalls {
+    use super::*;
+
+    #[test]
+    fn should_retry_transient_error_up_to_max_retries() {
+        let mut policy = RetryHttpsOutcalls::new(rpc_method());
+        let mut request = request_with_policy(RetryPolicy {
+            max_retries: 2,
+            retry_on_transient_error: true,
+            retry_on_http_status: Some(vec![]),
+            initial_backoff_ms: None,
+        });
+
+        for _ in 0..2 {
+            assert!(policy.retry(&mut request, &mut transient_error()).is_some());
+        }
+        assert!(policy.retry(&mut request, &mut transient_error()).is_none());
+
+        assert_eq!(retries_metric(MetricRetryReason::TransientError), Some(2));
+    }
+
+    #[test]
+    fn should_not_retry_transient_error_when_disabled() {
+        let mut policy = RetryHttpsOutcalls::new(rpc_method());
+        let mut request = request_with_policy(RetryPolicy {
+            max_retries: 2,
+            retry_on_transient_error: false,
+            retry_on_http_status: Some(vec![503]),
+            initial_backoff_ms: None,
+        });
+
+        assert!(policy.retry(&mut request, &mut transient_error()).is_none());
+        assert_eq!(retries_metric(MetricRetryReason::TransientError), None);
+    }
+
+    #[test]
+    fn should_only_retry_configured_http_status() {
+        let mut policy = RetryHttpsOutcalls::new(rpc_method());
+        let mut request = request_with_policy(RetryPolicy {
+            max_retries: 2,
+            retry_on_transient_error: true,
+            retry_on_http_status: Some(vec![429, 503]),
+            initial_backoff_ms: None,
+        });
+
+        assert!(policy.retry(&mut request, &mut http_error(500)).is_none());
+        assert!(policy.retry(&mut request, &mut http_error(503)).is_some());
+
+        assert_eq!(
+            retries_metric(MetricRetryReason::HttpError(503.into())),
+            Some(1)
+        );
+        assert_eq!(
+            retries_metric(MetricRetryReason::HttpError(500.into())),
+            None
+        );
+    }
+
+    #[test]
+    fn should_retry_429_and_503_by_default() {
+        let mut policy = RetryHttpsOutcalls::new(rpc_method());
+        let mut request = request_with_policy(RetryPolicy {
+            max_retries: 3,
+            retry_on_transient_error: false,
+            retry_on_http_status: None,
+            initial_backoff_ms: None,
+        });
+
+        assert!(policy.retry(&mut request, &mut http_error(500)).is_none());
+        assert!(policy.retry(&mut request, &mut http_error(429)).is_some());
+        assert!(policy.retry(&mut request, &mut http_error(503)).is_some());
+    }
+
+    #[test]
+    fn should_not_retry_other_errors() {
+        let mut policy = RetryHttpsOutcalls::new(rpc_method());
+        let mut request = request_with_policy(RetryPolicy {
+            max_retries: 2,
+            retry_on_transient_error: true,
+            retry_on_http_status: Some(vec![429, 503]),
+            initial_backoff_ms: None,
+        });
+
+        assert!(policy
+            .retry(
+                &mut request,
+                &mut Err::<(), _>(HttpClientError::IcError(IcError::CallRejected {
+                    code: RejectCode::SysFatal,
+                    message: "Fatal error!".to_string(),
+                }))
+            )
+            .is_none());
+        assert!(policy.retry(&mut request, &mut Ok(())).is_none());
+    }
+
+    #[test]
+    fn should_not_retry_without_policy() {
+        let mut policy = RetryHttpsOutcalls::new(rpc_method());
+        let mut request = http::Request::post(format!("https://{HOST}"))
+            .body(JsonRpcRequest::new("getSlot", ()))
+            .unwrap();
+
+        assert!(policy.retry(&mut request, &mut transient_error()).is_none());
+    }
+
+    fn rpc_method() -> MetricRpcMethod {
+        MetricRpcMethod("getSlot".to_string())
+    }
+
+    fn request_with_policy(policy: RetryPolicy) -> HttpJsonRpcRequest<()> {
+        http::Request::post(format!("https://{HOST}"))
+            .extension(policy)
+            .body(JsonRpcRequest::new("getSlot", ()))
+            .unwrap()
+    }
+
+    fn transient_error() -> Result<(), HttpClientError> {
+        Err(HttpClientError::IcError(IcError::CallRejected {
+            code: RejectCode::SysTransient,
+            message: "No consensus could be reached.".to_string(),
+        }))
+    }
+
+    fn http_error(status: u16) -> Result<(), HttpClientError> {
+        Err(HttpClientError::UnsuccessfulHttpResponse(
+            FilterNonSuccessfulHttpResponseError::UnsuccessfulResponse(
+                http::Response::builder()
+                    .status(status)
+                    .body(Vec::new())
+                    .unwrap(),
+            ),
+        ))
+    }
+
+    fn retries_metric(reason: MetricRetryReason) -> Option<u64> {
+        UNSTABLE_METRICS.with_borrow(|m| {
+            m.retries
+                .get(&(rpc_method(), MetricRpcHost::from(HOST), reason))
+                .copied()
+        })
+    }
+}
+
+mod cap_retry_policy {
+    use super::*;
+
+    #[test]
+    fn should_cap_max_retries_per_method() {
+        let policy = RetryPolicy {
+            max_retries: u8::MAX,
+            retry_on_transient_error: true,
+            retry_on_http_status: Some(vec![429]),
+            initial_backoff_ms: None,
+        };
+
+        assert_eq!(
+            cap_retry_policy(policy.clone(), "getSlot"),
+            RetryPolicy {
+                max_retries: 3,
+                ..policy.clone()
+            }
+        );
+        assert_eq!(
+            cap_retry_policy(policy.clone(), "sendTransaction"),
+            RetryPolicy {
+                max_retries: 1,
+                ..policy
+            }
+        );
+    }
+
+    #[test]
+    fn should_cap_initial_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff_ms: Some(u64::MAX),
+            ..default_retry_policy()
+        };
+
+        assert_eq!(
+            cap_retry_policy(policy, "getSlot").initial_backoff_ms,
+            Some(10_000)
+        );
+    }
+
+    #[test]
+    fn should_double_backoff_with_each_retry() {
+        let policy = RetryPolicy {
+            initial_backoff_ms: Some(500),
+            ..default_retry_policy()
+        };
+
+        assert_eq!(backoff(&policy, 1), Duration::from_millis(500));
+        assert_eq!(backoff(&policy, 2), Duration::from_secs(1));
+        assert_eq!(backoff(&policy, 3), Duration::from_secs(2));
+        assert_eq!(backoff(&default_retry_policy(), 1), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn should_not_retry_by_default() {
+        assert_eq!(
+            cap_retry_policy(default_retry_policy(), "getSlot").max_retries,
+            0
+        );
+    }
+}
@@ -5,8 +5,14 @@ pub mod lifecycle;
 pub mod logs;
 pub mod memory;
 pub mod metrics;
+pub mod payments;
 pub mod providers;
 pub mod rpc_client;
+pub mod scorecard;
+pub mod shadow;
+pub mod slot_ticker;
 pub mod types;
+pub mod upgrade;
 pub mod util;
 pub mod validate;
+pub mod version_sampler;
@@ -1,15 +1,64 @@
 use crate::{
-    logs::Priority,
-    memory::{init_state, mutate_state, State},
+    http::concurrency::num_outcalls_in_flight,
+    log,
+    logs::{drain::schedule_log_drain, Priority},
+    memory::{
+        init_state, mutate_state, read_state, restore_provider_scores, restore_provider_usage,
+        save_provider_scores, save_provider_usage, State, STATE_SCHEMA_VERSION,
+    },
+    scorecard::schedule_scorecard_rollup,
+    slot_ticker::schedule_slot_ticker,
+    version_sampler::schedule_version_sampler,
 };
-use canlog::log;
+use canhttp::multi::Timestamp;
 use sol_rpc_types::InstallArgs;
 
 pub fn init(args: InstallArgs) {
     init_state(State::from(args));
+    schedule_log_drain();
+    schedule_slot_ticker();
+    schedule_version_sampler();
+    schedule_scorecard_rollup();
+}
+
+pub fn pre_upgrade() {
+    let num_outcalls_in_flight = num_outcalls_in_flight();
+    if num_outcalls_in_flight > 0 {
+        log!(
+            Priority::Info,
+            "[pre_upgrade]: upgrading with {num_outcalls_in_flight} HTTPS outcalls in flight, which may fail. Call `prepareUpgrade` before upgrading to wait for them to complete."
+        );
+    }
+    // Deposits with an unknown outcome need not be saved, since they are kept in the state in
+    // stable memory, see `payments::PendingDeposit`.
+    save_provider_usage();
+    save_provider_scores();
 }
 
 pub fn post_upgrade(args: Option<InstallArgs>) {
+    let schema_version = read_state(|s| s.get_schema_version());
+    mutate_state(|s| s.migrate());
+    if schema_version < STATE_SCHEMA_VERSION {
+        log!(
+            Priority::Info,
+            "[post_upgrade]: migrated state from schema version {schema_version} to {STATE_SCHEMA_VERSION}"
+        );
+    }
+    let num_usages = restore_provider_usage();
+    if num_usages > 0 {
+        log!(
+            Priority::Info,
+            "[post_upgrade]: restored {num_usages} recent usages of supported providers"
+        );
+    }
+    let now = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
+    let num_requests = restore_provider_scores(now);
+    if num_requests > 0 {
+        log!(
+            Priority::Info,
+            "[post_upgrade]: restored provider scores of {num_requests} requests for the current period"
+        );
+    }
     if let Some(args) = args {
         log!(
             Priority::Info,
@@ -25,11 +74,75 @@ pub fn post_upgrade(args: Option<InstallArgs>) {
         if let Some(log_filter) = args.log_filter {
             mutate_state(|s| s.set_log_filter(log_filter));
         }
+        if let Some(log_format) = args.log_format {
+            mutate_state(|s| s.set_log_format(log_format));
+        }
         if let Some(num_subnet_nodes) = args.num_subnet_nodes {
             mutate_state(|s| s.set_num_subnet_nodes(num_subnet_nodes.into()));
         }
         if let Some(mode) = args.mode {
             mutate_state(|s| s.set_mode(mode));
         }
+        if let Some(provider_fallbacks) = args.provider_fallbacks {
+            mutate_state(|s| s.set_provider_fallbacks(provider_fallbacks));
+        }
+        if let Some(allowed_callers) = args.allowed_callers {
+            mutate_state(|s| s.set_allowed_callers(allowed_callers));
+        }
+        if let Some(exemptions) = args.allowed_callers_exemptions {
+            mutate_state(|s| s.set_allowed_callers_exemptions(exemptions));
+        }
+        if let Some(response_compression) = args.response_compression {
+            mutate_state(|s| s.set_response_compression(response_compression));
+        }
+        if let Some(provider_quotas) = args.provider_quotas {
+            mutate_state(|s| s.set_provider_quotas(provider_quotas));
+        }
+        if let Some(retry_policies) = args.retry_policies {
+            mutate_state(|s| s.set_retry_policies(retry_policies));
+        }
+        if let Some(request_id_strategy) = args.request_id_strategy {
+            mutate_state(|s| s.set_request_id_strategy(request_id_strategy));
+        }
+        if let Some(request_id_formats) = args.request_id_formats {
+            mutate_state(|s| s.set_request_id_formats(request_id_formats));
+        }
+        if let Some(log_drain) = args.log_drain {
+            mutate_state(|s| s.set_log_drain(log_drain));
+        }
+        if let Some(outcall_concurrency) = args.outcall_concurrency {
+            mutate_state(|s| s.set_outcall_concurrency(outcall_concurrency));
+        }
+        if let Some(provider_endpoints) = args.provider_endpoints {
+            mutate_state(|s| s.set_provider_endpoints(provider_endpoints));
+        }
+        if let Some(slot_ticker) = args.slot_ticker {
+            mutate_state(|s| s.set_slot_ticker(slot_ticker));
+        }
+        if let Some(pricing_policy) = args.pricing_policy {
+            mutate_state(|s| s.set_pricing_policy(pricing_policy));
+        }
+        if let Some(cycles_ledger_id) = args.cycles_ledger_id {
+            mutate_state(|s| s.set_cycles_ledger_id(cycles_ledger_id));
+        }
+        if let Some(log_throttles) = args.log_throttles {
+            mutate_state(|s| s.set_log_throttles(log_throttles));
+        }
+        if let Some(log_targets) = args.log_targets {
+            mutate_state(|s| s.set_log_targets(log_targets));
+        }
+        if let Some(shadow_providers) = args.shadow_providers {
+            mutate_state(|s| s.set_shadow_providers(shadow_providers));
+        }
+        if let Some(version_sampler) = args.version_sampler {
+            mutate_state(|s| s.set_version_sampler(version_sampler));
+        }
+    }
+    for issue in read_state(|s| s.validate()) {
+        log!(Priority::Info, "[post_upgrade]: invalid state: {issue:?}");
     }
+    schedule_log_drain();
+    schedule_slot_ticker();
+    schedule_version_sampler();
+    schedule_scorecard_rollup();
 }
@@ -0,0 +1,110 @@
+//! Periodic push of log entries to a [`LogDrain`] canister.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{log, logs::Priority, memory::read_state, upgrade::is_upgrade_in_progress};
+use canlog::{Log, LogEntry};
+use ic_cdk::call::Call;
+use sol_rpc_types::{LogDrain, LogDrainEntry};
+use std::{cell::Cell, time::Duration};
+
+/// Interval between two consecutive pushes of log entries to the log drain.
+pub const LOG_DRAIN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum number of log entries pushed to the log drain in a single call.
+pub const MAX_LOG_DRAIN_BATCH_SIZE: usize = 500;
+
+thread_local! {
+    /// Counter of the last log entry that was successfully pushed to the log drain.
+    ///
+    /// The log buffers are not persisted across upgrades, so that neither is this value.
+    static LAST_DRAINED_COUNTER: Cell<Option<u64>> = const { Cell::new(None) };
+    /// Whether log entries are currently being pushed to the log drain.
+    static IS_DRAINING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Starts a timer periodically pushing `INFO` log entries to the log drain, if enabled.
+///
+/// Timers are not persisted across upgrades, so this must be called after every
+/// installation and upgrade of the canister.
+pub fn schedule_log_drain() {
+    if let LogDrain::Enabled { canister_id, .. } = read_state(|s| s.get_log_drain()) {
+        log!(
+            Priority::Info,
+            "[schedule_log_drain]: pushing log entries to {canister_id} every {LOG_DRAIN_INTERVAL:?}"
+        );
+        ic_cdk_timers::set_timer_interval(LOG_DRAIN_INTERVAL, drain_logs);
+    }
+}
+
+async fn drain_logs() {
+    let LogDrain::Enabled {
+        canister_id,
+        method,
+    } = read_state(|s| s.get_log_drain())
+    else {
+        return;
+    };
+    // No call to the log drain is made while the canister is about to be upgraded.
+    if is_upgrade_in_progress() {
+        return;
+    }
+    // Skip this round if the previous call to the log drain has not completed yet,
+    // to avoid pushing the same entries twice.
+    if IS_DRAINING.replace(true) {
+        return;
+    }
+    let _guard = DrainGuard;
+
+    let mut log: Log<Priority> = Default::default();
+    log.push_logs(Priority::Info);
+    let batch = next_batch(log.entries, LAST_DRAINED_COUNTER.get());
+    let Some(last_counter) = batch.last().map(|entry| entry.counter) else {
+        return;
+    };
+    match Call::bounded_wait(canister_id, &method)
+        .with_arg(&batch)
+        .await
+    {
+        Ok(_) => LAST_DRAINED_COUNTER.set(Some(last_counter)),
+        // Logged with a lower priority to avoid pushing the failure itself to the log drain.
+        Err(e) => log!(
+            Priority::Debug,
+            "Failed to push {} log entries to log drain {canister_id}: {e}",
+            batch.len()
+        ),
+    }
+}
+
+/// Returns the next log entries to push to the log drain, i.e. at most
+/// [`MAX_LOG_DRAIN_BATCH_SIZE`] entries that were recorded after the entry with the given
+/// counter, ordered by increasing counter.
+fn next_batch(
+    mut entries: Vec<LogEntry<Priority>>,
+    last_drained_counter: Option<u64>,
+) -> Vec<LogDrainEntry> {
+    entries.retain(|entry| last_drained_counter.is_none_or(|counter| entry.counter > counter));
+    entries.sort_unstable_by_key(|entry| entry.counter);
+    entries
+        .into_iter()
+        .take(MAX_LOG_DRAIN_BATCH_SIZE)
+        .map(|entry| LogDrainEntry {
+            timestamp: entry.timestamp,
+            file: entry.file,
+            line: entry.line,
+            message: entry.message,
+            counter: entry.counter,
+        })
+        .collect()
+}
+
+/// Resets [`IS_DRAINING`] when dropped, including when the callback of the call to the log
+/// drain traps.
+struct DrainGuard;
+
+impl Drop for DrainGuard {
+    fn drop(&mut self) {
+        IS_DRAINING.set(false);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::logs::{
+    drain::{next_batch, MAX_LOG_DRAIN_BATCH_SIZE},
+    Priority,
+};
+use canlog::LogEntry;
+
+#[test]
+fn should_push_all_entries_ordered_by_counter() {
+    let batch = next_batch(vec![entry(2), entry(0), entry(1)], None);
+
+    assert_eq!(counters(&batch), vec![0, 1, 2]);
+    assert_eq!(batch[0].message, "message 0");
+    assert_eq!(batch[0].timestamp, 1_000);
+}
+
+#[test]
+fn should_only_push_entries_after_last_drained_entry() {
+    let batch = next_batch(vec![entry(0), entry(1), entry(2), entry(3)], Some(1));
+
+    assert_eq!(counters(&batch), vec![2, 3]);
+    assert!(next_batch(vec![entry(0), entry(1)], Some(1)).is_empty());
+}
+
+#[test]
+fn should_limit_batch_size() {
+    let num_entries = MAX_LOG_DRAIN_BATCH_SIZE as u64 + 10;
+
+    let batch = next_batch((0..num_entries).rev().map(entry).collect(), None);
+
+    assert_eq!(batch.len(), MAX_LOG_DRAIN_BATCH_SIZE);
+    assert_eq!(
+        counters(&batch),
+        (0..MAX_LOG_DRAIN_BATCH_SIZE as u64).collect::<Vec<_>>()
+    );
+}
+
+fn entry(counter: u64) -> LogEntry<Priority> {
+    LogEntry {
+        timestamp: 1_000 + counter,
+        priority: Priority::Info,
+        file: "canister/src/main.rs".to_string(),
+        line: 42,
+        message: format!("message {counter}"),
+        counter,
+    }
+}
+
+fn counters(batch: &[sol_rpc_types::LogDrainEntry]) -> Vec<u64> {
+    batch.iter().map(|entry| entry.counter).collect()
+}
@@ -0,0 +1,74 @@
+//! Log entries printed as JSON lines, see [`LogFormat::Json`].
+//!
+//! [`LogFormat::Json`]: sol_rpc_types::LogFormat::Json
+
+#[cfg(test)]
+mod tests;
+
+use crate::logs::Priority;
+use candid::Principal;
+use canlog::{GetLogFilter, LogPriorityLevels};
+use ic_canister_log::{LogEntry, Sink};
+use serde::Serialize;
+
+/// Same as the sink of a [`Priority`], except that log entries are printed as JSON lines
+/// instead of free-form lines.
+///
+/// As for free-form lines, log entries are only recorded if their free-form line matches the
+/// [`LogFilter`](canlog::LogFilter) of the canister.
+pub struct JsonPrintSink(pub Priority);
+
+impl Sink for JsonPrintSink {
+    fn append(&self, entry: LogEntry) {
+        let line = format!(
+            "{} {}:{} {}",
+            self.0.display_name(),
+            entry.file,
+            entry.line,
+            entry.message
+        );
+        if Priority::get_log_filter().is_match(&line) {
+            ic_cdk::println!(
+                "{}",
+                json_log_line(
+                    self.0,
+                    entry.timestamp,
+                    &entry.file,
+                    entry.line,
+                    &entry.message,
+                    ic_cdk::api::canister_self(),
+                )
+            );
+            self.0.get_buffer().append(entry);
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLogLine<'a> {
+    timestamp: u64,
+    level: &'static str,
+    file: &'a str,
+    line: u32,
+    message: &'a str,
+    canister_id: String,
+}
+
+fn json_log_line(
+    priority: Priority,
+    timestamp: u64,
+    file: &str,
+    line: u32,
+    message: &str,
+    canister_id: Principal,
+) -> String {
+    serde_json::to_string(&JsonLogLine {
+        timestamp,
+        level: priority.display_name(),
+        file,
+        line,
+        message,
+        canister_id: canister_id.to_text(),
+    })
+    .expect("BUG: failed to serialize log entry")
+}
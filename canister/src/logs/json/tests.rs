@@ -0,0 +1,30 @@
+use crate::logs::{json::json_log_line, Priority};
+use candid::Principal;
+use serde_json::json;
+
+#[test]
+fn should_format_log_entry_as_json() {
+    let canister_id = Principal::from_text("tghme-zyaaa-aaaar-qarca-cai").unwrap();
+
+    let line = json_log_line(
+        Priority::TraceHttp,
+        1_000,
+        "canister/src/http/mod.rs",
+        42,
+        "Sending \"getSlot\" request\nto provider",
+        canister_id,
+    );
+
+    assert!(!line.contains('\n'));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&line).unwrap(),
+        json!({
+            "timestamp": 1_000,
+            "level": "TRACE_HTTP",
+            "file": "canister/src/http/mod.rs",
+            "line": 42,
+            "message": "Sending \"getSlot\" request\nto provider",
+            "canister_id": "tghme-zyaaa-aaaar-qarca-cai",
+        })
+    );
+}
@@ -1,8 +1,38 @@
+pub mod drain;
+pub mod json;
+pub mod throttle;
+
 use crate::memory::read_state;
 use canlog::{GetLogFilter, LogFilter, LogPriorityLevels};
 use serde::{Deserialize, Serialize};
+use sol_rpc_types::LogPriority;
 use std::str::FromStr;
 
+/// Same as [`canlog::log`], except that the log entry is only recorded if allowed by the
+/// [`LogTarget`](sol_rpc_types::LogTarget) of the module in which it is logged, see
+/// [`is_log_target_enabled`](crate::memory::is_log_target_enabled), and by the
+/// [`LogThrottle`](sol_rpc_types::LogThrottle) of its priority, see [`throttle::should_record`].
+///
+/// The log message is not formatted when the log entry is suppressed. The log entry is printed
+/// according to the [`LogFormat`](sol_rpc_types::LogFormat) of the canister.
+#[macro_export]
+macro_rules! log {
+    ($priority:expr, $($args:tt)*) => {{
+        let priority: $crate::logs::Priority = $priority;
+        if $crate::memory::is_log_target_enabled(&priority.into(), module_path!())
+            && $crate::logs::throttle::should_record(priority.into())
+        {
+            match $crate::memory::get_log_format() {
+                ::sol_rpc_types::LogFormat::Text => ::canlog::log!(priority, $($args)*),
+                ::sol_rpc_types::LogFormat::Json => ::ic_canister_log::log!(
+                    $crate::logs::json::JsonPrintSink(priority),
+                    $($args)*
+                ),
+            }
+        }
+    }};
+}
+
 #[derive(LogPriorityLevels, Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
 pub enum Priority {
     #[log_level(capacity = 1000, name = "INFO")]
@@ -31,3 +61,13 @@ impl FromStr for Priority {
         }
     }
 }
+
+impl From<Priority> for LogPriority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Info => LogPriority::Info,
+            Priority::Debug => LogPriority::Debug,
+            Priority::TraceHttp => LogPriority::TraceHttp,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Sampling and rate limiting of log entries, see [`LogThrottle`].
+
+#[cfg(test)]
+mod tests;
+
+use crate::{add_metric_entry, memory::get_log_throttle, metrics::MetricLogSuppressionReason};
+use sol_rpc_types::{LogPriority, LogRateLimit, LogThrottle};
+use std::{cell::RefCell, collections::BTreeMap};
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+thread_local! {
+    /// Sampling counters and token buckets of the throttled log priorities.
+    ///
+    /// They are not persisted across upgrades, so that all token buckets are full after an upgrade.
+    static LOG_THROTTLES: RefCell<LogThrottles> = RefCell::new(LogThrottles::default());
+}
+
+/// Returns `true` if a log entry with the given priority should be recorded according to the
+/// [`LogThrottle`] of that priority, if any, and counts the suppressed log entry otherwise.
+pub fn should_record(priority: LogPriority) -> bool {
+    let Some(throttle) = get_log_throttle(&priority) else {
+        return true;
+    };
+    let now_ns = ic_cdk::api::time();
+    match LOG_THROTTLES.with_borrow_mut(|throttles| throttles.record(priority, &throttle, now_ns)) {
+        Ok(()) => true,
+        Err(reason) => {
+            add_metric_entry!(suppressed_log_entries, (priority, reason), 1);
+            false
+        }
+    }
+}
+
+/// Sampling counter and token bucket of each throttled log priority.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogThrottles(BTreeMap<LogPriority, ThrottleState>);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ThrottleState {
+    num_entries: u64,
+    bucket: Option<TokenBucket>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TokenBucket {
+    tokens: u32,
+    last_refill_ns: u64,
+}
+
+impl LogThrottles {
+    /// Records a log entry with the given priority at the given time and returns whether it
+    /// should be recorded or why it is suppressed.
+    pub fn record(
+        &mut self,
+        priority: LogPriority,
+        throttle: &LogThrottle,
+        now_ns: u64,
+    ) -> Result<(), MetricLogSuppressionReason> {
+        let state = self.0.entry(priority).or_default();
+        let index = state.num_entries;
+        state.num_entries = state.num_entries.wrapping_add(1);
+        if let Some(sample_rate) = throttle.sample_rate {
+            if index % u64::from(sample_rate.max(1)) != 0 {
+                return Err(MetricLogSuppressionReason::Sampling);
+            }
+        }
+        if let Some(rate_limit) = &throttle.rate_limit {
+            let bucket = state.bucket.get_or_insert(TokenBucket {
+                tokens: rate_limit.max_burst,
+                last_refill_ns: now_ns,
+            });
+            if !bucket.take_token(rate_limit, now_ns) {
+                return Err(MetricLogSuppressionReason::RateLimit);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TokenBucket {
+    fn take_token(&mut self, rate_limit: &LogRateLimit, now_ns: u64) -> bool {
+        let tokens_per_second = u128::from(rate_limit.tokens_per_second.max(1));
+        let elapsed_ns = u128::from(now_ns.saturating_sub(self.last_refill_ns));
+        let new_tokens = elapsed_ns * tokens_per_second / NANOS_PER_SECOND;
+        if new_tokens > 0 {
+            let tokens =
+                (u128::from(self.tokens) + new_tokens).min(u128::from(rate_limit.max_burst));
+            self.tokens = tokens as u32;
+            // Only advance by the time needed to add the new tokens, so that partially elapsed
+            // intervals are not lost, unless the bucket is full.
+            self.last_refill_ns = if self.tokens == rate_limit.max_burst {
+                now_ns
+            } else {
+                self.last_refill_ns + (new_tokens * NANOS_PER_SECOND / tokens_per_second) as u64
+            };
+        }
+        match self.tokens.checked_sub(1) {
+            Some(tokens) => {
+                self.tokens = tokens;
+                true
+            }
+            None => false,
+        }
+    }
+}
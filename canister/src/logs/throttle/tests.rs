@@ -0,0 +1,103 @@
+use crate::{logs::throttle::LogThrottles, metrics::MetricLogSuppressionReason};
+use sol_rpc_types::{LogPriority, LogRateLimit, LogThrottle};
+
+const SECOND_NS: u64 = 1_000_000_000;
+
+#[test]
+fn should_record_one_out_of_sample_rate_entries() {
+    let mut throttles = LogThrottles::default();
+    let throttle = LogThrottle {
+        sample_rate: Some(3),
+        rate_limit: None,
+    };
+
+    let results: Vec<_> = (0..7)
+        .map(|_| throttles.record(LogPriority::TraceHttp, &throttle, 0))
+        .collect();
+
+    assert_eq!(
+        results,
+        vec![
+            Ok(()),
+            Err(MetricLogSuppressionReason::Sampling),
+            Err(MetricLogSuppressionReason::Sampling),
+            Ok(()),
+            Err(MetricLogSuppressionReason::Sampling),
+            Err(MetricLogSuppressionReason::Sampling),
+            Ok(()),
+        ]
+    );
+}
+
+#[test]
+fn should_rate_limit_entries() {
+    let mut throttles = LogThrottles::default();
+    let throttle = LogThrottle {
+        sample_rate: None,
+        rate_limit: Some(LogRateLimit {
+            max_burst: 2,
+            tokens_per_second: 2,
+        }),
+    };
+    let mut record = |now_ns| throttles.record(LogPriority::Debug, &throttle, now_ns);
+
+    assert_eq!(record(0), Ok(()));
+    assert_eq!(record(0), Ok(()));
+    assert_eq!(record(0), Err(MetricLogSuppressionReason::RateLimit));
+    assert_eq!(
+        record(SECOND_NS / 2 - 1),
+        Err(MetricLogSuppressionReason::RateLimit)
+    );
+
+    assert_eq!(record(SECOND_NS / 2), Ok(()));
+    assert_eq!(
+        record(SECOND_NS / 2),
+        Err(MetricLogSuppressionReason::RateLimit)
+    );
+
+    // The bucket holds at most `max_burst` tokens.
+    assert_eq!(record(100 * SECOND_NS), Ok(()));
+    assert_eq!(record(100 * SECOND_NS), Ok(()));
+    assert_eq!(
+        record(100 * SECOND_NS),
+        Err(MetricLogSuppressionReason::RateLimit)
+    );
+}
+
+#[test]
+fn should_only_rate_limit_sampled_entries() {
+    let mut throttles = LogThrottles::default();
+    let throttle = LogThrottle {
+        sample_rate: Some(2),
+        rate_limit: Some(LogRateLimit {
+            max_burst: 1,
+            tokens_per_second: 1,
+        }),
+    };
+
+    assert_eq!(throttles.record(LogPriority::Info, &throttle, 0), Ok(()));
+    assert_eq!(
+        throttles.record(LogPriority::Info, &throttle, SECOND_NS),
+        Err(MetricLogSuppressionReason::Sampling)
+    );
+    assert_eq!(
+        throttles.record(LogPriority::Info, &throttle, SECOND_NS),
+        Ok(())
+    );
+}
+
+#[test]
+fn should_throttle_each_priority_separately() {
+    let mut throttles = LogThrottles::default();
+    let throttle = LogThrottle {
+        sample_rate: Some(2),
+        rate_limit: None,
+    };
+
+    assert_eq!(throttles.record(LogPriority::Info, &throttle, 0), Ok(()));
+    assert_eq!(throttles.record(LogPriority::Debug, &throttle, 0), Ok(()));
+    assert_eq!(
+        throttles.record(LogPriority::Info, &throttle, 0),
+        Err(MetricLogSuppressionReason::Sampling)
+    );
+}
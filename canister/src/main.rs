@@ -1,25 +1,52 @@
+use candid::Principal;
 use canhttp::multi::Timestamp;
-use canlog::{log, Log, Sort};
+use canlog::{Log, Sort};
 use ic_cdk::{api::is_controller, query, update};
 use ic_http_types::{HttpRequest, HttpResponse, HttpResponseBuilder};
 use ic_metrics_encoder::MetricsEncoder;
 use sol_rpc_canister::{
-    candid_rpc::send_multi,
-    lifecycle,
+    candid_rpc::{
+        authorize_caller, check_allowed_caller, send_account_snapshot, send_multi, send_raw_http,
+        validate_blockhash,
+    },
+    constants::{DEFAULT_MAX_RESPONSE_BYTES, MAX_VALIDATED_API_KEYS},
+    lifecycle, log,
     logs::Priority,
-    memory::{mutate_state, read_state, State},
+    memory::{
+        get_cycles_report, get_prepaid_cycles, get_provider_scorecard, get_response_metadata,
+        get_shadow_provider_stats, mutate_state, read_state, State,
+    },
     metrics::encode_metrics,
+    payments,
     providers::{get_provider, PROVIDERS},
-    rpc_client::MultiRpcRequest,
+    rpc_client::{
+        check_provider_health, json::IsBlockhashValidParams, AccountSnapshotRequest,
+        ClusterHealthRequest, IsBlockhashValidRequest, MultiRpcRequest, RawHttpRequest,
+    },
+    slot_ticker, upgrade,
+    util::hostname_from_url,
 };
 use sol_rpc_types::{
-    AccountInfo, ConfirmedBlock, ConfirmedTransactionStatusWithSignature,
-    EncodedConfirmedTransactionWithStatusMeta, GetAccountInfoParams, GetBalanceParams,
-    GetBlockParams, GetRecentPrioritizationFeesParams, GetRecentPrioritizationFeesRpcConfig,
-    GetSignatureStatusesParams, GetSignaturesForAddressParams, GetSlotParams, GetSlotRpcConfig,
-    GetTokenAccountBalanceParams, GetTransactionParams, Lamport, MultiRpcResult, PrioritizationFee,
-    RpcAccess, RpcConfig, RpcResult, RpcSources, SendTransactionParams, Signature, Slot,
-    SupportedRpcProvider, SupportedRpcProviderId, TokenAmount, TransactionStatus,
+    AccountChange, AccountInfo, AccountSnapshot, AllowedCallers, CachedSlot, Capabilities,
+    ClusterNode, ConfirmedBlock, ConfirmedTransactionStatusWithSignature, CyclesReport,
+    DepositCyclesArgs, DepositCyclesError, EncodedConfirmedTransactionWithStatusMeta, EpochInfo,
+    EpochSchedule, GetAccountInfoParams, GetAccountSnapshotParams, GetBalanceParams,
+    GetBlockHeightParams, GetBlockHeightRpcConfig, GetBlockParams, GetBlocksParams,
+    GetBlocksWithLimitParams, GetClusterNodesParams, GetEpochInfoParams, GetEpochInfoRpcConfig,
+    GetFeeForMessageParams, GetLeaderScheduleParams, GetMinimumBalanceForRentExemptionParams,
+    GetRecentPerformanceSamplesParams, GetRecentPrioritizationFeesParams,
+    GetRecentPrioritizationFeesRpcConfig, GetSignatureStatusesParams,
+    GetSignaturesForAddressParams, GetSlotLeadersParams, GetSlotParams, GetSlotRpcConfig,
+    GetSupplyParams, GetSupplyRpcConfig, GetTokenAccountBalanceParams,
+    GetTokenAccountsByDelegateParams, GetTokenAccountsByOwnerParams, GetTransactionCountParams,
+    GetTransactionCountRpcConfig, GetTransactionParams, HasAccountChangedParams,
+    HttpRequestPreview, JsonRequestRpcConfig, KeyedAccount, Lamport, LogDrain, MultiRpcResult,
+    OutcallConcurrency, PerformanceSample, PricingPolicy, PrioritizationFee, ProviderHealth,
+    ProviderHealthReport, ProviderQuota, ProviderScorecard, Pubkey, RawHttpRequestParams,
+    RawHttpResponse, ResponseMetadata, RpcAccess, RpcConfig, RpcResult, RpcSource, RpcSources,
+    ScorecardPeriod, SendTransactionParams, ShadowProviderStats, Signature, Slot, SlotTicker,
+    SnapshotSlotInfo, StateValidationIssue, Supply, SupportedRpcProvider, SupportedRpcProviderId,
+    TokenAmount, TransactionStatus, UpgradeReadiness, WithContext, WithContextRpcConfig,
 };
 use std::str::FromStr;
 
@@ -32,10 +59,30 @@ pub fn require_api_key_principal_or_controller() -> Result<(), String> {
     }
 }
 
+pub fn require_controller() -> Result<(), String> {
+    if is_controller(&ic_cdk::api::msg_caller()) {
+        Ok(())
+    } else {
+        Err("You are not authorized".to_string())
+    }
+}
+
+pub fn require_controller_or_demo_mode() -> Result<(), String> {
+    if read_state(|state| state.is_demo_mode_active()) || is_controller(&ic_cdk::api::msg_caller())
+    {
+        Ok(())
+    } else {
+        Err("You are not authorized".to_string())
+    }
+}
+
 pub fn require_base_http_outcall_fee() -> Result<(), String> {
     if read_state(|state| state.is_demo_mode_active())
+        || mutate_state(|state| state.is_base_http_outcall_fee_free())
         || (ic_cdk::api::msg_cycles_available()
             >= mutate_state(|state| state.lazy_compute_base_http_outcall_fee()))
+        || (get_prepaid_cycles(&ic_cdk::api::msg_caller())
+            >= mutate_state(|state| state.lazy_compute_base_http_outcall_fee()))
     {
         Ok(())
     } else {
@@ -43,9 +90,161 @@ pub fn require_base_http_outcall_fee() -> Result<(), String> {
     }
 }
 
+/// Names of the public endpoints of the canister, as returned by `getCapabilities`.
+const ENDPOINTS: &[&str] = &[
+    "getProviders",
+    "getCapabilities",
+    "getCachedSlot",
+    "updateApiKeys",
+    "updateAndValidateApiKeys",
+    "updateCustomApiKeys",
+    "updateAllowedCallers",
+    "updatePricingPolicy",
+    "updateProviderQuotas",
+    "addApiKeyPrincipal",
+    "removeApiKeyPrincipal",
+    "getApiKeyPrincipals",
+    "validateState",
+    "prepareUpgrade",
+    "cancelUpgrade",
+    "depositCycles",
+    "getPrepaidCycles",
+    "getCyclesReport",
+    "getResponseMetadata",
+    "getProviderScorecard",
+    "getShadowProviderStats",
+    "getAccountInfo",
+    "getAccountInfoCyclesCost",
+    "getAccountSnapshot",
+    "getAccountSnapshotCyclesCost",
+    "getAccountInfoWithContext",
+    "getAccountInfoWithContextCyclesCost",
+    "hasAccountChanged",
+    "hasAccountChangedCyclesCost",
+    "getBalance",
+    "getBalanceCyclesCost",
+    "getBalanceWithContext",
+    "getBalanceWithContextCyclesCost",
+    "getBlock",
+    "getBlockCyclesCost",
+    "getBlockHeight",
+    "getBlockHeightCyclesCost",
+    "getBlocks",
+    "getBlocksCyclesCost",
+    "getBlocksWithLimit",
+    "getBlocksWithLimitCyclesCost",
+    "getClusterNodes",
+    "getClusterNodesCyclesCost",
+    "getEpochInfo",
+    "getEpochInfoCyclesCost",
+    "getEpochSchedule",
+    "getEpochScheduleCyclesCost",
+    "getFeeForMessage",
+    "getFeeForMessageCyclesCost",
+    "getHighestSnapshotSlot",
+    "getHighestSnapshotSlotCyclesCost",
+    "getLeaderSchedule",
+    "getLeaderScheduleCyclesCost",
+    "getMinimumBalanceForRentExemption",
+    "getMinimumBalanceForRentExemptionCyclesCost",
+    "getRecentPerformanceSamples",
+    "getRecentPerformanceSamplesCyclesCost",
+    "getRecentPrioritizationFees",
+    "getRecentPrioritizationFeesCyclesCost",
+    "getSignaturesForAddress",
+    "getSignaturesForAddressCyclesCost",
+    "getSignatureStatuses",
+    "getSignatureStatusesCyclesCost",
+    "getSlot",
+    "getSlotCyclesCost",
+    "getSlotLeaders",
+    "getSlotLeadersCyclesCost",
+    "getSupply",
+    "getSupplyCyclesCost",
+    "getTokenAccountBalance",
+    "getTokenAccountBalanceCyclesCost",
+    "getTokenAccountBalanceWithContext",
+    "getTokenAccountBalanceWithContextCyclesCost",
+    "getTokenAccountsByDelegate",
+    "getTokenAccountsByDelegateCyclesCost",
+    "getTokenAccountsByOwner",
+    "getTokenAccountsByOwnerCyclesCost",
+    "getTransaction",
+    "getTransactionCyclesCost",
+    "getTransactionCount",
+    "getTransactionCountCyclesCost",
+    "minimumLedgerSlot",
+    "minimumLedgerSlotCyclesCost",
+    "sendTransaction",
+    "sendTransactionCyclesCost",
+    "jsonRequest",
+    "jsonRequestCyclesCost",
+    "rawHttpRequest",
+    "rawHttpRequestCyclesCost",
+    "getClusterHealth",
+    "getClusterHealthCyclesCost",
+    "previewRequest",
+];
+
+#[query(name = "getCapabilities")]
+fn get_capabilities() -> Capabilities {
+    read_state(|s| {
+        let features = [
+            (
+                Capabilities::FEATURE_RESPONSE_COMPRESSION,
+                s.is_response_compression_enabled(),
+            ),
+            (Capabilities::FEATURE_ZSTD_ENCODING, cfg!(feature = "zstd")),
+            (
+                Capabilities::FEATURE_OUTCALL_CONCURRENCY_LIMIT,
+                s.get_outcall_concurrency() != OutcallConcurrency::Unlimited,
+            ),
+            (
+                Capabilities::FEATURE_CALLER_ALLOWLIST,
+                s.get_allowed_callers() != AllowedCallers::Any,
+            ),
+            (
+                Capabilities::FEATURE_LOG_DRAIN,
+                s.get_log_drain() != LogDrain::Disabled,
+            ),
+            (
+                Capabilities::FEATURE_SLOT_TICKER,
+                s.get_slot_ticker() != SlotTicker::Disabled,
+            ),
+        ];
+        Capabilities {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            endpoints: ENDPOINTS.iter().map(|e| e.to_string()).collect(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            mode: s.get_mode(),
+            features: features
+                .into_iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(feature, _)| feature.to_string())
+                .collect(),
+        }
+    })
+}
+
+#[query(name = "getCachedSlot")]
+fn get_cached_slot() -> Option<CachedSlot> {
+    slot_ticker::get_cached_slot()
+}
+
 #[query(name = "getProviders")]
 fn get_providers() -> Vec<(SupportedRpcProviderId, SupportedRpcProvider)> {
-    PROVIDERS.with(|providers| providers.clone().into_iter().collect())
+    PROVIDERS.with(|providers| {
+        providers
+            .clone()
+            .into_iter()
+            .map(|(id, mut provider)| {
+                if let Some(endpoint) = read_state(|s| s.get_provider_endpoint(&id)) {
+                    provider.access = endpoint.access;
+                }
+                (id, provider)
+            })
+            .collect()
+    })
 }
 
 #[update(
@@ -59,6 +258,61 @@ fn get_providers() -> Vec<(SupportedRpcProviderId, SupportedRpcProvider)> {
 ///
 /// Panics if the list of provider IDs includes a nonexistent or "unauthenticated" (fully public) provider.
 async fn update_api_keys(api_keys: Vec<(SupportedRpcProviderId, Option<String>)>) {
+    set_api_keys(api_keys);
+}
+
+#[update(
+    name = "updateAndValidateApiKeys",
+    guard = "require_api_key_principal_or_controller"
+)]
+/// Same as `updateApiKeys`, but additionally queries `getHealth` from each provider whose API key
+/// was inserted or updated and returns the health of these providers, so that invalid or revoked
+/// API keys are detected when they are rotated rather than when requests fail.
+///
+/// The HTTPS outcalls are paid by the SOL RPC canister and are made concurrently.
+///
+/// Panics if more than [`MAX_VALIDATED_API_KEYS`] API keys are inserted or updated.
+async fn update_and_validate_api_keys(
+    api_keys: Vec<(SupportedRpcProviderId, Option<String>)>,
+) -> Vec<(SupportedRpcProviderId, ProviderHealth)> {
+    let mut updated_providers = Vec::new();
+    for (provider, api_key) in &api_keys {
+        if api_key.is_some() && !updated_providers.contains(provider) {
+            updated_providers.push(*provider);
+        }
+    }
+    if updated_providers.len() > MAX_VALIDATED_API_KEYS {
+        panic!(
+            "Too many API keys to validate: expected at most {MAX_VALIDATED_API_KEYS}, got {}",
+            updated_providers.len()
+        );
+    }
+    set_api_keys(api_keys);
+    let now = now();
+    let healths = futures::future::join_all(
+        updated_providers
+            .iter()
+            .map(|provider| check_provider_health(*provider, now)),
+    )
+    .await;
+    updated_providers
+        .into_iter()
+        .zip(healths)
+        .inspect(|(provider, health)| {
+            if let ProviderHealth::Error(e) = health {
+                log!(
+                    Priority::Info,
+                    "API key validation failed for provider {:?}: {}",
+                    provider,
+                    e
+                );
+            }
+        })
+        .collect()
+}
+
+/// Panics if the list of provider IDs includes a nonexistent or "unauthenticated" (fully public) provider.
+fn set_api_keys(api_keys: Vec<(SupportedRpcProviderId, Option<String>)>) {
     log!(
         Priority::Info,
         "[{}] Updating API keys for providers: {}",
@@ -88,6 +342,206 @@ async fn update_api_keys(api_keys: Vec<(SupportedRpcProviderId, Option<String>)>
     }
 }
 
+#[update(
+    name = "updateCustomApiKeys",
+    guard = "require_api_key_principal_or_controller"
+)]
+/// Inserts or removes API keys for custom RPC endpoints.
+///
+/// For each element of `api_keys`, passing `(url, Some(key))` corresponds to inserting or updating
+/// the API key for the given endpoint URL, while passing `(url, None)` indicates that the key should
+/// be removed from the canister. A stored API key is only used for custom endpoints whose URL is
+/// exactly the URL it was registered for.
+///
+/// Panics if a URL has no valid host.
+async fn update_custom_api_keys(api_keys: Vec<(String, Option<String>)>) {
+    log!(
+        Priority::Info,
+        "[{}] Updating API keys for custom endpoints: {}",
+        ic_cdk::api::msg_caller(),
+        api_keys
+            .iter()
+            .filter_map(|(url, _)| hostname_from_url(url))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    for (url, api_key) in api_keys {
+        if hostname_from_url(&url).is_none() {
+            panic!("Trying to set API key for an invalid URL")
+        }
+        match api_key {
+            Some(key) => mutate_state(|state| {
+                state.insert_custom_api_key(url, key.try_into().expect("Invalid API key"))
+            }),
+            None => mutate_state(|state| state.remove_custom_api_key(&url)),
+        }
+    }
+}
+
+#[update(name = "updateAllowedCallers", guard = "require_controller")]
+/// Replaces the principals allowed to call the endpoints making HTTPS outcalls.
+///
+/// Controllers are always allowed, and the methods exempted with `allowedCallersExemptions`
+/// are never restricted.
+async fn update_allowed_callers(allowed_callers: AllowedCallers) {
+    log!(
+        Priority::Info,
+        "[{}] Updating allowed callers: {:?}",
+        ic_cdk::api::msg_caller(),
+        allowed_callers
+    );
+    mutate_state(|state| state.set_allowed_callers(allowed_callers));
+}
+
+#[update(name = "updatePricingPolicy", guard = "require_controller")]
+/// Replaces the policy to charge callers for the HTTPS outcalls made on their behalf.
+async fn update_pricing_policy(pricing_policy: PricingPolicy) {
+    log!(
+        Priority::Info,
+        "[{}] Updating pricing policy: {:?}",
+        ic_cdk::api::msg_caller(),
+        pricing_policy
+    );
+    mutate_state(|state| state.set_pricing_policy(pricing_policy));
+}
+
+#[update(name = "updateProviderQuotas", guard = "require_controller")]
+/// Replaces the request quotas of the supported providers.
+///
+/// Requests recorded so far still count towards the new quotas.
+async fn update_provider_quotas(provider_quotas: Vec<(SupportedRpcProviderId, ProviderQuota)>) {
+    log!(
+        Priority::Info,
+        "[{}] Updating provider quotas: {:?}",
+        ic_cdk::api::msg_caller(),
+        provider_quotas
+    );
+    mutate_state(|state| state.set_provider_quotas(provider_quotas));
+}
+
+#[update(name = "addApiKeyPrincipal", guard = "require_controller")]
+/// Allows the given principal to manage the API keys of RPC providers, i.e. to call
+/// `updateApiKeys` and `updateCustomApiKeys`.
+async fn add_api_key_principal(principal: Principal) {
+    log!(
+        Priority::Info,
+        "[{}] Adding API key principal: {}",
+        ic_cdk::api::msg_caller(),
+        principal
+    );
+    mutate_state(|state| state.add_api_key_principal(principal));
+}
+
+#[update(name = "removeApiKeyPrincipal", guard = "require_controller")]
+/// Revokes the permission of the given principal to manage the API keys of RPC providers.
+///
+/// The API keys set by that principal are kept.
+async fn remove_api_key_principal(principal: Principal) {
+    log!(
+        Priority::Info,
+        "[{}] Removing API key principal: {}",
+        ic_cdk::api::msg_caller(),
+        principal
+    );
+    mutate_state(|state| state.remove_api_key_principal(&principal));
+}
+
+#[query(
+    name = "getApiKeyPrincipals",
+    guard = "require_api_key_principal_or_controller"
+)]
+/// Returns the principals allowed to manage the API keys of RPC providers, in addition to the
+/// controllers.
+fn get_api_key_principals() -> Vec<Principal> {
+    read_state(|state| state.get_api_key_principals())
+}
+
+#[query(name = "validateState", guard = "require_controller")]
+/// Checks the invariants of the state of the canister, e.g. after an upgrade, and returns the
+/// problems found instead of failing at first use.
+fn validate_state() -> Vec<StateValidationIssue> {
+    read_state(|state| state.validate())
+}
+
+#[update(name = "prepareUpgrade", guard = "require_controller")]
+/// Stops accepting new requests and waits for the pending HTTPS outcalls to complete, for at
+/// most the given number of seconds, so that the canister can be upgraded without making
+/// in-flight requests fail.
+async fn prepare_upgrade(timeout_seconds: Option<u32>) -> UpgradeReadiness {
+    upgrade::prepare_upgrade(timeout_seconds).await
+}
+
+#[update(name = "cancelUpgrade", guard = "require_controller")]
+/// Accepts new requests again after `prepareUpgrade`, e.g. because the upgrade was aborted.
+fn cancel_upgrade() {
+    upgrade::cancel_upgrade()
+}
+
+#[update(name = "depositCycles")]
+/// Withdraws cycles from the caller's account on the cycles ledger and adds them to the caller's
+/// prepaid cycles, which are used to pay for the caller's requests when not enough cycles are
+/// attached to them.
+async fn deposit_cycles(args: DepositCyclesArgs) -> Result<u128, DepositCyclesError> {
+    payments::deposit_cycles(ic_cdk::api::msg_caller(), args).await
+}
+
+#[query(name = "getPrepaidCycles")]
+/// Returns the prepaid cycles of the given principal, or of the caller if not specified.
+///
+/// Only controllers may retrieve the prepaid cycles of another principal.
+fn get_prepaid_cycles_balance(principal: Option<Principal>) -> u128 {
+    let caller = ic_cdk::api::msg_caller();
+    let principal = principal.unwrap_or(caller);
+    if principal != caller && !is_controller(&caller) {
+        ic_cdk::trap("Only controllers can retrieve the prepaid cycles of another principal");
+    }
+    get_prepaid_cycles(&principal)
+}
+
+#[query(name = "getCyclesReport")]
+/// Returns the cycles attached to and consumed by the most recent call of the given principal, or
+/// of the caller if not specified.
+///
+/// Only controllers may retrieve the cycles report of another principal.
+fn get_last_cycles_report(principal: Option<Principal>) -> Option<CyclesReport> {
+    let caller = ic_cdk::api::msg_caller();
+    let principal = principal.unwrap_or(caller);
+    if principal != caller && !is_controller(&caller) {
+        ic_cdk::trap("Only controllers can retrieve the cycles report of another principal");
+    }
+    get_cycles_report(&principal)
+}
+
+#[query(name = "getResponseMetadata")]
+/// Returns metadata about the responses of the providers to the most recent call of the given
+/// principal, or of the caller if not specified, that returned inconsistent results.
+///
+/// Only controllers may retrieve the response metadata of another principal.
+fn get_last_response_metadata(
+    principal: Option<Principal>,
+) -> Option<Vec<(RpcSource, ResponseMetadata)>> {
+    let caller = ic_cdk::api::msg_caller();
+    let principal = principal.unwrap_or(caller);
+    if principal != caller && !is_controller(&caller) {
+        ic_cdk::trap("Only controllers can retrieve the response metadata of another principal");
+    }
+    get_response_metadata(&principal)
+}
+
+#[query(name = "getProviderScorecard")]
+/// Returns the reliability statistics of the supported providers aggregated over the given
+/// month, or over the current month if not specified.
+fn get_scorecard(period: Option<ScorecardPeriod>) -> Option<ProviderScorecard> {
+    get_provider_scorecard(period, now())
+}
+
+#[query(name = "getShadowProviderStats")]
+/// Returns the statistics of the providers queried in shadow mode since the last upgrade, sorted
+/// by provider.
+fn get_shadow_stats() -> Vec<ShadowProviderStats> {
+    get_shadow_provider_stats()
+}
+
 #[update(name = "getAccountInfo", guard = "require_base_http_outcall_fee")]
 async fn get_account_info(
     source: RpcSources,
@@ -105,6 +559,7 @@ async fn get_account_info_cycles_cost(
     config: Option<RpcConfig>,
     params: GetAccountInfoParams,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getAccountInfoCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -113,6 +568,101 @@ async fn get_account_info_cycles_cost(
         .await
 }
 
+#[update(
+    name = "getAccountInfoWithContext",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_account_info_with_context(
+    source: RpcSources,
+    config: Option<WithContextRpcConfig>,
+    params: GetAccountInfoParams,
+) -> MultiRpcResult<WithContext<Option<AccountInfo>>> {
+    let request = MultiRpcRequest::get_account_info_with_context(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    );
+    send_multi(request).await.into()
+}
+
+#[query(name = "getAccountInfoWithContextCyclesCost")]
+async fn get_account_info_with_context_cycles_cost(
+    source: RpcSources,
+    config: Option<WithContextRpcConfig>,
+    params: GetAccountInfoParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getAccountInfoWithContextCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_account_info_with_context(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
+#[update(name = "getAccountSnapshot", guard = "require_base_http_outcall_fee")]
+/// Fetches the balance, the account info and the most recent transaction signatures of an
+/// account with a single call, by querying the Solana `getBalance`, `getAccountInfo` and
+/// `getSignaturesForAddress` RPC methods from each selected provider.
+///
+/// The results of each provider are combined before being aggregated, so that the call either
+/// returns a consistent snapshot of the account or the combined results of each provider.
+async fn get_account_snapshot(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetAccountSnapshotParams,
+) -> MultiRpcResult<AccountSnapshot> {
+    let request = AccountSnapshotRequest::new(source, config.unwrap_or_default(), params, now());
+    send_account_snapshot(request).await
+}
+
+#[query(name = "getAccountSnapshotCyclesCost")]
+async fn get_account_snapshot_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetAccountSnapshotParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getAccountSnapshotCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    AccountSnapshotRequest::new(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "hasAccountChanged", guard = "require_base_http_outcall_fee")]
+async fn has_account_changed(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: HasAccountChangedParams,
+) -> MultiRpcResult<AccountChange> {
+    let request =
+        MultiRpcRequest::has_account_changed(source, config.unwrap_or_default(), params, now());
+    send_multi(request).await.map(AccountChange::from)
+}
+
+#[query(name = "hasAccountChangedCyclesCost")]
+async fn has_account_changed_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: HasAccountChangedParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("hasAccountChangedCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::has_account_changed(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
 #[update(name = "getBalance", guard = "require_base_http_outcall_fee")]
 async fn get_balance(
     source: RpcSources,
@@ -127,38 +677,407 @@ async fn get_balance(
 async fn get_balance_cycles_cost(
     source: RpcSources,
     config: Option<RpcConfig>,
-    params: GetBalanceParams,
+    params: GetBalanceParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getBalanceCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_balance(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(
+    name = "getBalanceWithContext",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_balance_with_context(
+    source: RpcSources,
+    config: Option<WithContextRpcConfig>,
+    params: GetBalanceParams,
+) -> MultiRpcResult<WithContext<Lamport>> {
+    let request = MultiRpcRequest::get_balance_with_context(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    );
+    send_multi(request).await
+}
+
+#[query(name = "getBalanceWithContextCyclesCost")]
+async fn get_balance_with_context_cycles_cost(
+    source: RpcSources,
+    config: Option<WithContextRpcConfig>,
+    params: GetBalanceParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getBalanceWithContextCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_balance_with_context(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getBlock", guard = "require_base_http_outcall_fee")]
+async fn get_block(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetBlockParams,
+) -> MultiRpcResult<Option<ConfirmedBlock>> {
+    let request = MultiRpcRequest::get_block(source, config.unwrap_or_default(), params, now());
+    send_multi(request).await.into()
+}
+
+#[query(name = "getBlockCyclesCost")]
+async fn get_block_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetBlockParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getBlockCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_block(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getBlockHeight", guard = "require_base_http_outcall_fee")]
+async fn get_block_height(
+    source: RpcSources,
+    config: Option<GetBlockHeightRpcConfig>,
+    params: Option<GetBlockHeightParams>,
+) -> MultiRpcResult<u64> {
+    let request = MultiRpcRequest::get_block_height(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    );
+    send_multi(request).await
+}
+
+#[query(name = "getBlockHeightCyclesCost")]
+async fn get_block_height_cycles_cost(
+    source: RpcSources,
+    config: Option<GetBlockHeightRpcConfig>,
+    params: Option<GetBlockHeightParams>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getBlockHeightCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_block_height(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
+#[update(name = "getBlocks", guard = "require_base_http_outcall_fee")]
+async fn get_blocks(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetBlocksParams,
+) -> MultiRpcResult<Vec<Slot>> {
+    let request = MultiRpcRequest::get_blocks(source, config.unwrap_or_default(), params, now());
+    send_multi(request).await
+}
+
+#[query(name = "getBlocksCyclesCost")]
+async fn get_blocks_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetBlocksParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getBlocksCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_blocks(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getBlocksWithLimit", guard = "require_base_http_outcall_fee")]
+async fn get_blocks_with_limit(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetBlocksWithLimitParams,
+) -> MultiRpcResult<Vec<Slot>> {
+    let request =
+        MultiRpcRequest::get_blocks_with_limit(source, config.unwrap_or_default(), params, now());
+    send_multi(request).await
+}
+
+#[query(name = "getBlocksWithLimitCyclesCost")]
+async fn get_blocks_with_limit_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetBlocksWithLimitParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getBlocksWithLimitCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_blocks_with_limit(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getClusterNodes", guard = "require_base_http_outcall_fee")]
+async fn get_cluster_nodes(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: Option<GetClusterNodesParams>,
+) -> MultiRpcResult<Vec<ClusterNode>> {
+    let request = MultiRpcRequest::get_cluster_nodes(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    );
+    send_multi(request).await
+}
+
+#[query(name = "getClusterNodesCyclesCost")]
+async fn get_cluster_nodes_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: Option<GetClusterNodesParams>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getClusterNodesCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_cluster_nodes(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
+#[update(name = "getEpochInfo", guard = "require_base_http_outcall_fee")]
+async fn get_epoch_info(
+    source: RpcSources,
+    config: Option<GetEpochInfoRpcConfig>,
+    params: Option<GetEpochInfoParams>,
+) -> MultiRpcResult<EpochInfo> {
+    let request = MultiRpcRequest::get_epoch_info(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    );
+    send_multi(request).await
+}
+
+#[query(name = "getEpochInfoCyclesCost")]
+async fn get_epoch_info_cycles_cost(
+    source: RpcSources,
+    config: Option<GetEpochInfoRpcConfig>,
+    params: Option<GetEpochInfoParams>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getEpochInfoCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_epoch_info(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
+#[update(name = "getFeeForMessage", guard = "require_base_http_outcall_fee")]
+async fn get_fee_for_message(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetFeeForMessageParams,
+) -> MultiRpcResult<Option<Lamport>> {
+    let request =
+        MultiRpcRequest::get_fee_for_message(source, config.unwrap_or_default(), params, now());
+    send_multi(request).await
+}
+
+#[query(name = "getFeeForMessageCyclesCost")]
+async fn get_fee_for_message_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetFeeForMessageParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getFeeForMessageCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_fee_for_message(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getEpochSchedule", guard = "require_base_http_outcall_fee")]
+async fn get_epoch_schedule(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> MultiRpcResult<EpochSchedule> {
+    let request = MultiRpcRequest::get_epoch_schedule(source, config.unwrap_or_default(), now());
+    send_multi(request).await
+}
+
+#[query(name = "getEpochScheduleCyclesCost")]
+async fn get_epoch_schedule_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getEpochScheduleCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_epoch_schedule(source, config.unwrap_or_default(), now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(
+    name = "getHighestSnapshotSlot",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_highest_snapshot_slot(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> MultiRpcResult<SnapshotSlotInfo> {
+    let request =
+        MultiRpcRequest::get_highest_snapshot_slot(source, config.unwrap_or_default(), now());
+    send_multi(request).await
+}
+
+#[query(name = "getHighestSnapshotSlotCyclesCost")]
+async fn get_highest_snapshot_slot_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getHighestSnapshotSlotCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_highest_snapshot_slot(source, config.unwrap_or_default(), now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getLeaderSchedule", guard = "require_base_http_outcall_fee")]
+async fn get_leader_schedule(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetLeaderScheduleParams,
+) -> MultiRpcResult<Option<Vec<u64>>> {
+    let request =
+        MultiRpcRequest::get_leader_schedule(source, config.unwrap_or_default(), params, now());
+    send_multi(request).await
+}
+
+#[query(name = "getLeaderScheduleCyclesCost")]
+async fn get_leader_schedule_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetLeaderScheduleParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getLeaderScheduleCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_leader_schedule(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(
+    name = "getMinimumBalanceForRentExemption",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_minimum_balance_for_rent_exemption(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetMinimumBalanceForRentExemptionParams,
+) -> MultiRpcResult<Lamport> {
+    let request = MultiRpcRequest::get_minimum_balance_for_rent_exemption(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    );
+    send_multi(request).await
+}
+
+#[query(name = "getMinimumBalanceForRentExemptionCyclesCost")]
+async fn get_minimum_balance_for_rent_exemption_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetMinimumBalanceForRentExemptionParams,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getMinimumBalanceForRentExemptionCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
-    MultiRpcRequest::get_balance(source, config.unwrap_or_default(), params, now())?
-        .cycles_cost()
-        .await
+    MultiRpcRequest::get_minimum_balance_for_rent_exemption(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    )?
+    .cycles_cost()
+    .await
 }
 
-#[update(name = "getBlock", guard = "require_base_http_outcall_fee")]
-async fn get_block(
+#[update(
+    name = "getRecentPerformanceSamples",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_recent_performance_samples(
     source: RpcSources,
     config: Option<RpcConfig>,
-    params: GetBlockParams,
-) -> MultiRpcResult<Option<ConfirmedBlock>> {
-    let request = MultiRpcRequest::get_block(source, config.unwrap_or_default(), params, now());
-    send_multi(request).await.into()
+    params: Option<GetRecentPerformanceSamplesParams>,
+) -> MultiRpcResult<Vec<PerformanceSample>> {
+    let request = MultiRpcRequest::get_recent_performance_samples(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    );
+    send_multi(request).await
 }
 
-#[query(name = "getBlockCyclesCost")]
-async fn get_block_cycles_cost(
+#[query(name = "getRecentPerformanceSamplesCyclesCost")]
+async fn get_recent_performance_samples_cycles_cost(
     source: RpcSources,
     config: Option<RpcConfig>,
-    params: GetBlockParams,
+    params: Option<GetRecentPerformanceSamplesParams>,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getRecentPerformanceSamplesCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
-    MultiRpcRequest::get_block(source, config.unwrap_or_default(), params, now())?
-        .cycles_cost()
-        .await
+    MultiRpcRequest::get_recent_performance_samples(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    )?
+    .cycles_cost()
+    .await
 }
 
 #[update(
@@ -185,6 +1104,7 @@ async fn get_recent_prioritization_fees_cycles_cost(
     config: Option<GetRecentPrioritizationFeesRpcConfig>,
     params: Option<GetRecentPrioritizationFeesParams>,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getRecentPrioritizationFeesCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -222,6 +1142,7 @@ async fn get_signatures_for_address_cycles_cost(
     config: Option<RpcConfig>,
     params: GetSignaturesForAddressParams,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getSignaturesForAddressCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -247,6 +1168,7 @@ async fn get_signature_statuses_cycles_cost(
     config: Option<RpcConfig>,
     params: GetSignatureStatusesParams,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getSignatureStatusesCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -276,6 +1198,7 @@ async fn get_slot_cycles_cost(
     config: Option<GetSlotRpcConfig>,
     params: Option<GetSlotParams>,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getSlotCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -289,6 +1212,67 @@ async fn get_slot_cycles_cost(
     .await
 }
 
+#[update(name = "getSlotLeaders", guard = "require_base_http_outcall_fee")]
+async fn get_slot_leaders(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetSlotLeadersParams,
+) -> MultiRpcResult<Vec<Pubkey>> {
+    let request =
+        MultiRpcRequest::get_slot_leaders(source, config.unwrap_or_default(), params, now());
+    send_multi(request).await
+}
+
+#[query(name = "getSlotLeadersCyclesCost")]
+async fn get_slot_leaders_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetSlotLeadersParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getSlotLeadersCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_slot_leaders(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getSupply", guard = "require_base_http_outcall_fee")]
+async fn get_supply(
+    source: RpcSources,
+    config: Option<GetSupplyRpcConfig>,
+    params: Option<GetSupplyParams>,
+) -> MultiRpcResult<Supply> {
+    let request = MultiRpcRequest::get_supply(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    );
+    send_multi(request).await
+}
+
+#[query(name = "getSupplyCyclesCost")]
+async fn get_supply_cycles_cost(
+    source: RpcSources,
+    config: Option<GetSupplyRpcConfig>,
+    params: Option<GetSupplyParams>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getSupplyCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_supply(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
 #[update(
     name = "getTokenAccountBalance",
     guard = "require_base_http_outcall_fee"
@@ -313,6 +1297,7 @@ async fn get_token_account_balance_cycles_cost(
     config: Option<RpcConfig>,
     params: GetTokenAccountBalanceParams,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getTokenAccountBalanceCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -321,6 +1306,119 @@ async fn get_token_account_balance_cycles_cost(
         .await
 }
 
+#[update(
+    name = "getTokenAccountBalanceWithContext",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_token_account_balance_with_context(
+    source: RpcSources,
+    config: Option<WithContextRpcConfig>,
+    params: GetTokenAccountBalanceParams,
+) -> MultiRpcResult<WithContext<TokenAmount>> {
+    let request = MultiRpcRequest::get_token_account_balance_with_context(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    );
+    send_multi(request).await.into()
+}
+
+#[query(name = "getTokenAccountBalanceWithContextCyclesCost")]
+async fn get_token_account_balance_with_context_cycles_cost(
+    source: RpcSources,
+    config: Option<WithContextRpcConfig>,
+    params: GetTokenAccountBalanceParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getTokenAccountBalanceWithContextCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_token_account_balance_with_context(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
+#[update(
+    name = "getTokenAccountsByDelegate",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_token_accounts_by_delegate(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetTokenAccountsByDelegateParams,
+) -> MultiRpcResult<Vec<KeyedAccount>> {
+    let request = MultiRpcRequest::get_token_accounts_by_delegate(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    );
+    send_multi(request)
+        .await
+        .map(|accounts| accounts.into_iter().map(KeyedAccount::from).collect())
+}
+
+#[query(name = "getTokenAccountsByDelegateCyclesCost")]
+async fn get_token_accounts_by_delegate_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetTokenAccountsByDelegateParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getTokenAccountsByDelegateCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_token_accounts_by_delegate(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
+#[update(
+    name = "getTokenAccountsByOwner",
+    guard = "require_base_http_outcall_fee"
+)]
+async fn get_token_accounts_by_owner(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetTokenAccountsByOwnerParams,
+) -> MultiRpcResult<Vec<KeyedAccount>> {
+    let request = MultiRpcRequest::get_token_accounts_by_owner(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    );
+    send_multi(request)
+        .await
+        .map(|accounts| accounts.into_iter().map(KeyedAccount::from).collect())
+}
+
+#[query(name = "getTokenAccountsByOwnerCyclesCost")]
+async fn get_token_accounts_by_owner_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: GetTokenAccountsByOwnerParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("getTokenAccountsByOwnerCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_token_accounts_by_owner(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
 #[update(name = "getTransaction", guard = "require_base_http_outcall_fee")]
 async fn get_transaction(
     source: RpcSources,
@@ -338,6 +1436,7 @@ async fn get_transaction_cycles_cost(
     config: Option<RpcConfig>,
     params: GetTransactionParams,
 ) -> RpcResult<u128> {
+    check_allowed_caller("getTransactionCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -346,14 +1445,78 @@ async fn get_transaction_cycles_cost(
         .await
 }
 
+#[update(name = "getTransactionCount", guard = "require_base_http_outcall_fee")]
+async fn get_transaction_count(
+    source: RpcSources,
+    config: Option<GetTransactionCountRpcConfig>,
+    params: Option<GetTransactionCountParams>,
+) -> MultiRpcResult<u64> {
+    let request = MultiRpcRequest::get_transaction_count(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    );
+    send_multi(request).await
+}
+
+#[query(name = "getTransactionCountCyclesCost")]
+async fn get_transaction_count_cycles_cost(
+    source: RpcSources,
+    config: Option<GetTransactionCountRpcConfig>,
+    params: Option<GetTransactionCountParams>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getTransactionCountCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::get_transaction_count(
+        source,
+        config.unwrap_or_default(),
+        params.unwrap_or_default(),
+        now(),
+    )?
+    .cycles_cost()
+    .await
+}
+
+#[update(name = "minimumLedgerSlot", guard = "require_base_http_outcall_fee")]
+async fn minimum_ledger_slot(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> MultiRpcResult<Slot> {
+    let request = MultiRpcRequest::minimum_ledger_slot(source, config.unwrap_or_default(), now());
+    send_multi(request).await
+}
+
+#[query(name = "minimumLedgerSlotCyclesCost")]
+async fn minimum_ledger_slot_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> RpcResult<u128> {
+    check_allowed_caller("minimumLedgerSlotCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    MultiRpcRequest::minimum_ledger_slot(source, config.unwrap_or_default(), now())?
+        .cycles_cost()
+        .await
+}
+
 #[update(name = "sendTransaction", guard = "require_base_http_outcall_fee")]
 async fn send_transaction(
     source: RpcSources,
     config: Option<RpcConfig>,
     params: SendTransactionParams,
 ) -> MultiRpcResult<Signature> {
-    let request =
-        MultiRpcRequest::send_transaction(source, config.unwrap_or_default(), params, now());
+    let config = config.unwrap_or_default();
+    if params.validate_blockhash == Some(true) {
+        let request = is_blockhash_valid_request(&source, &config, &params);
+        if let Err(e) = validate_blockhash(request).await {
+            return MultiRpcResult::Consistent(Err(e));
+        }
+    }
+    let request = MultiRpcRequest::send_transaction(source, config, params, now());
     send_multi(request).await
 }
 
@@ -363,18 +1526,47 @@ async fn send_transaction_cycles_cost(
     config: Option<RpcConfig>,
     params: SendTransactionParams,
 ) -> RpcResult<u128> {
+    check_allowed_caller("sendTransactionCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
-    MultiRpcRequest::send_transaction(source, config.unwrap_or_default(), params, now())?
+    let config = config.unwrap_or_default();
+    let validation_cost = if params.validate_blockhash == Some(true) {
+        is_blockhash_valid_request(&source, &config, &params)?
+            .cycles_cost()
+            .await?
+    } else {
+        0
+    };
+    let send_cost = MultiRpcRequest::send_transaction(source, config, params, now())?
         .cycles_cost()
-        .await
+        .await?;
+    Ok(validation_cost + send_cost)
+}
+
+fn is_blockhash_valid_request(
+    source: &RpcSources,
+    config: &RpcConfig,
+    params: &SendTransactionParams,
+) -> RpcResult<IsBlockhashValidRequest> {
+    // The response size estimate applies to the `sendTransaction` request only.
+    let config = RpcConfig {
+        response_size_estimate: None,
+        ..config.clone()
+    };
+    let params = IsBlockhashValidParams::try_from(params)?;
+    Ok(MultiRpcRequest::is_blockhash_valid(
+        source.clone(),
+        config,
+        params,
+        now(),
+    )?)
 }
 
 #[update(name = "jsonRequest", guard = "require_base_http_outcall_fee")]
 async fn json_request(
     source: RpcSources,
-    config: Option<RpcConfig>,
+    config: Option<JsonRequestRpcConfig>,
     json_rpc_payload: String,
 ) -> MultiRpcResult<String> {
     let request =
@@ -385,9 +1577,10 @@ async fn json_request(
 #[query(name = "jsonRequestCyclesCost")]
 async fn json_request_cycles_cost(
     source: RpcSources,
-    config: Option<RpcConfig>,
+    config: Option<JsonRequestRpcConfig>,
     json_rpc_payload: String,
 ) -> RpcResult<u128> {
+    check_allowed_caller("jsonRequestCyclesCost")?;
     if read_state(State::is_demo_mode_active) {
         return Ok(0);
     }
@@ -396,6 +1589,85 @@ async fn json_request_cycles_cost(
         .await
 }
 
+#[update(name = "rawHttpRequest", guard = "require_base_http_outcall_fee")]
+/// Sends the given HTTP `POST` request to the selected providers without interpreting it as a
+/// JSON-RPC request, e.g. to use provider-specific REST extensions.
+///
+/// The responses are aggregated with the given consensus strategy and returned without any
+/// further validation.
+async fn raw_http_request(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: RawHttpRequestParams,
+) -> MultiRpcResult<RawHttpResponse> {
+    send_raw_http(RawHttpRequest::new(
+        source,
+        config.unwrap_or_default(),
+        params,
+        now(),
+    ))
+    .await
+}
+
+#[query(name = "rawHttpRequestCyclesCost")]
+async fn raw_http_request_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: RawHttpRequestParams,
+) -> RpcResult<u128> {
+    check_allowed_caller("rawHttpRequestCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    RawHttpRequest::new(source, config.unwrap_or_default(), params, now())?
+        .cycles_cost()
+        .await
+}
+
+#[update(name = "getClusterHealth", guard = "require_base_http_outcall_fee")]
+/// Queries the Solana `getHealth` and `getVersion` RPC methods from all selected providers and
+/// returns a report for each provider.
+///
+/// Unlike the other endpoints, the results of the different providers are not aggregated.
+async fn get_cluster_health(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> RpcResult<Vec<ProviderHealthReport>> {
+    authorize_caller("getClusterHealth")?;
+    let request = ClusterHealthRequest::new(source, config.unwrap_or_default(), now())?;
+    Ok(request.send().await)
+}
+
+#[query(name = "getClusterHealthCyclesCost")]
+async fn get_cluster_health_cycles_cost(
+    source: RpcSources,
+    config: Option<RpcConfig>,
+) -> RpcResult<u128> {
+    check_allowed_caller("getClusterHealthCyclesCost")?;
+    if read_state(State::is_demo_mode_active) {
+        return Ok(0);
+    }
+    ClusterHealthRequest::new(source, config.unwrap_or_default(), now())?
+        .cycles_cost()
+        .await
+}
+
+#[query(name = "previewRequest", guard = "require_controller_or_demo_mode")]
+/// Returns the HTTP requests that would be sent to the selected providers for the given Solana
+/// JSON-RPC method and JSON-encoded parameters, without performing any HTTPS outcall.
+///
+/// API keys in the request URLs and headers are redacted.
+async fn preview_request(
+    method: String,
+    source: RpcSources,
+    config: Option<RpcConfig>,
+    params: String,
+) -> RpcResult<Vec<HttpRequestPreview>> {
+    MultiRpcRequest::preview_request(method, source, config.unwrap_or_default(), params, now())?
+        .preview()
+        .await
+}
+
 #[query(hidden = true)]
 fn http_request(request: HttpRequest) -> HttpResponse {
     match request.path() {
@@ -489,6 +1761,11 @@ fn init(args: sol_rpc_types::InstallArgs) {
     lifecycle::init(args);
 }
 
+#[ic_cdk::pre_upgrade]
+fn pre_upgrade() {
+    lifecycle::pre_upgrade();
+}
+
 #[ic_cdk::post_upgrade]
 fn post_upgrade(args: Option<sol_rpc_types::InstallArgs>) {
     lifecycle::post_upgrade(args);
@@ -518,3 +1795,21 @@ fn check_candid_interface_compatibility() {
     )
     .unwrap();
 }
+
+#[test]
+fn check_capabilities_endpoints() {
+    use candid_parser::utils::CandidSource;
+    use std::collections::BTreeSet;
+
+    let interface = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("sol_rpc_canister.did");
+    let (env, service) = CandidSource::File(interface.as_path()).load().unwrap();
+    let methods: BTreeSet<_> = env
+        .as_service(&service.unwrap())
+        .unwrap()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    assert_eq!(ENDPOINTS.iter().copied().collect::<BTreeSet<_>>(), methods);
+}
@@ -2,9 +2,17 @@
 mod tests;
 
 use crate::{
+    constants::{API_KEY_REPLACE_STRING, CYCLES_COST_METHOD_SUFFIX, DEFAULT_CYCLES_LEDGER_ID},
     metrics::Metrics,
-    providers::SupportedRpcProviderUsage,
+    payments::PendingDeposit,
+    providers::{
+        get_provider, HostLatencies, QuotaStatus, SolanaClusterOutages,
+        SupportedRpcProviderRateLimits, SupportedRpcProviderRequests, SupportedRpcProviderUsage,
+    },
+    scorecard::{ProviderScores, RequestOutcome},
+    shadow::{ShadowOutcome, ShadowStats},
     types::{ApiKey, OverrideProvider},
+    version_sampler::parse_version,
 };
 use candid::{Deserialize, Principal};
 use canhttp::{
@@ -15,21 +23,76 @@ use canlog::LogFilter;
 use ic_stable_structures::{
     memory_manager::{MemoryId, MemoryManager, VirtualMemory},
     storable::Bound,
-    Cell, DefaultMemoryImpl, Storable,
+    Cell, DefaultMemoryImpl, StableBTreeMap, Storable,
 };
 use serde::Serialize;
-use sol_rpc_types::{InstallArgs, Mode, SupportedRpcProviderId};
-use std::{borrow::Cow, cell::RefCell, collections::BTreeMap};
+use sol_rpc_types::{
+    AllowedCallers, ClusterOutage, CyclesReport, InstallArgs, LogDrain, LogFormat, LogPriority,
+    LogTarget, LogThrottle, Mode, OutcallConcurrency, PricingPolicy, ProviderEndpoint,
+    ProviderQuota, ProviderScorecard, RequestIdFormat, RequestIdStrategy, RequestPriority,
+    ResponseCompression, ResponseMetadata, RetryPolicy, RpcAccess, RpcAuth, RpcSource,
+    ScorecardPeriod, ShadowProviderStats, SlotTicker, SolanaCluster, StateValidationIssue,
+    SupportedRpcProviderId, VersionSampler,
+};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    iter,
+    time::Duration,
+};
 
 const STATE_MEMORY_ID: MemoryId = MemoryId::new(0);
+const PREPAID_CYCLES_MEMORY_ID: MemoryId = MemoryId::new(1);
+const PROVIDER_USAGE_MEMORY_ID: MemoryId = MemoryId::new(2);
+const PROVIDER_SCORECARDS_MEMORY_ID: MemoryId = MemoryId::new(3);
+
+/// Version of the schema of the [`State`] written by this version of the canister.
+///
+/// Increment it and add the corresponding migration to [`MIGRATIONS`] whenever stored data must
+/// be transformed when upgrading from a previous version of the canister.
+pub const STATE_SCHEMA_VERSION: u32 = 1;
+
+/// Migrations of the [`State`], where the migration at index `i` migrates a state with schema
+/// version `i` to schema version `i + 1`.
+const MIGRATIONS: [fn(&mut State); STATE_SCHEMA_VERSION as usize] = [
+    // Version 1 introduced the schema version: states written before are otherwise unchanged.
+    |_state| {},
+];
+
+/// Version of the encoding of the [`ProviderUsageSnapshot`] written by this version of the
+/// canister. Snapshots written with another version are discarded.
+const PROVIDER_USAGE_SNAPSHOT_VERSION: u32 = 1;
 
 type StableMemory = VirtualMemory<DefaultMemoryImpl>;
 
+/// Maximum number of callers for which a [`CyclesReport`] is kept. When exceeded, the oldest
+/// report is evicted.
+const MAX_CYCLES_REPORTS: usize = 10_000;
+
+/// Maximum number of callers for which the [`ResponseMetadata`] of their most recent
+/// inconsistent result is kept. When exceeded, the oldest metadata is evicted.
+const MAX_RESPONSE_METADATA: usize = 10_000;
+
+/// Maximum number of monthly [`ProviderScorecard`]s kept in stable memory. When exceeded, the
+/// oldest scorecard is evicted.
+const MAX_PROVIDER_SCORECARDS: u64 = 24;
+
 thread_local! {
     // Unstable static data: these are reset when the canister is upgraded.
     pub static UNSTABLE_METRICS: RefCell<Metrics> = RefCell::new(Metrics::default());
-    static UNSTABLE_HTTP_REQUEST_COUNTER: RefCell<ConstantSizeId> = const {RefCell::new(ConstantSizeId::ZERO)};
+    static UNSTABLE_HTTP_REQUEST_COUNTER: RefCell<u64> = const {RefCell::new(0)};
     static UNSTABLE_RPC_SERVICE_OK_RESULTS_TIMESTAMPS: RefCell<SupportedRpcProviderUsage> = RefCell::new(SupportedRpcProviderUsage::default());
+    static UNSTABLE_RPC_SERVICE_REQUESTS_TIMESTAMPS: RefCell<SupportedRpcProviderRequests> = RefCell::new(SupportedRpcProviderRequests::default());
+    static UNSTABLE_RPC_SERVICE_RATE_LIMITS: RefCell<SupportedRpcProviderRateLimits> = RefCell::new(SupportedRpcProviderRateLimits::default());
+    static UNSTABLE_CLUSTER_OUTAGES: RefCell<SolanaClusterOutages> = RefCell::new(SolanaClusterOutages::default());
+    static UNSTABLE_SLOT_SKEWS: RefCell<BTreeMap<SolanaCluster, u64>> = const { RefCell::new(BTreeMap::new()) };
+    static UNSTABLE_HOST_LATENCIES: RefCell<HostLatencies> = RefCell::new(HostLatencies::default());
+    static UNSTABLE_CYCLES_REPORTS: RefCell<BTreeMap<Principal, CyclesReport>> = RefCell::new(BTreeMap::new());
+    static UNSTABLE_RESPONSE_METADATA: RefCell<BTreeMap<Principal, (u64, Vec<(RpcSource, ResponseMetadata)>)>> = RefCell::new(BTreeMap::new());
+    static UNSTABLE_PREPAID_CYCLES_DEBITED: RefCell<BTreeMap<Principal, u128>> = RefCell::new(BTreeMap::new());
+    static UNSTABLE_PROVIDER_SCORES: RefCell<Option<ProviderScores>> = const { RefCell::new(None) };
+    static UNSTABLE_SHADOW_STATS: RefCell<ShadowStats> = RefCell::new(ShadowStats::default());
 
     // Stable static data: these are preserved when the canister is upgraded.
     static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
@@ -40,6 +103,24 @@ thread_local! {
             ConfigState::default(),
         )
     );
+    // Kept separately from the `State`, which is entirely re-written whenever it is modified.
+    static PREPAID_CYCLES: RefCell<StableBTreeMap<PrincipalKey, Cycles, StableMemory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with_borrow(|m| m.get(PREPAID_CYCLES_MEMORY_ID)),
+        ));
+    // Only written before an upgrade, to restore `UNSTABLE_RPC_SERVICE_OK_RESULTS_TIMESTAMPS`.
+    static PROVIDER_USAGE: RefCell<Cell<ProviderUsageSnapshot, StableMemory>> = RefCell::new(
+        Cell::init(
+            MEMORY_MANAGER.with_borrow(|m| m.get(PROVIDER_USAGE_MEMORY_ID)),
+            ProviderUsageSnapshot::default(),
+        )
+    );
+    // Scorecards of the past periods, as well as of the current period while the canister is
+    // upgraded, keyed by `scorecard_key`.
+    static PROVIDER_SCORECARDS: RefCell<StableBTreeMap<u32, StoredProviderScorecard, StableMemory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with_borrow(|m| m.get(PROVIDER_SCORECARDS_MEMORY_ID)),
+        ));
 }
 
 /// Configuration memory of the ledger orchestrator.
@@ -82,6 +163,153 @@ impl Storable for ConfigState {
     const BOUND: Bound = Bound::Unbounded;
 }
 
+/// Principal used as key of a [`StableBTreeMap`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct PrincipalKey(Principal);
+
+impl Storable for PrincipalKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.0.as_slice())
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.0.as_slice().to_vec()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        PrincipalKey(Principal::from_slice(bytes.as_ref()))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 29,
+        is_fixed_size: false,
+    };
+}
+
+/// Amount of cycles stored in a [`StableBTreeMap`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Cycles(u128);
+
+impl Storable for Cycles {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.0.to_le_bytes().to_vec())
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.0.to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Cycles(u128::from_le_bytes(
+            bytes
+                .as_ref()
+                .try_into()
+                .expect("BUG: invalid cycles amount"),
+        ))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 16,
+        is_fixed_size: true,
+    };
+}
+
+/// Usage of the supported providers persisted in stable memory across upgrades, so that the
+/// providers are still ranked according to their recent usage right after an upgrade.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+struct ProviderUsageSnapshot {
+    version: u32,
+    /// Timestamps of the recent usages of each provider, in nanoseconds since the UNIX epoch.
+    usages: BTreeMap<SupportedRpcProviderId, Vec<u64>>,
+}
+
+impl Default for ProviderUsageSnapshot {
+    fn default() -> Self {
+        Self {
+            version: PROVIDER_USAGE_SNAPSHOT_VERSION,
+            usages: BTreeMap::default(),
+        }
+    }
+}
+
+impl From<&SupportedRpcProviderUsage> for ProviderUsageSnapshot {
+    fn from(usage: &SupportedRpcProviderUsage) -> Self {
+        Self {
+            version: PROVIDER_USAGE_SNAPSHOT_VERSION,
+            usages: usage
+                .iter()
+                .map(|(provider, timestamps)| {
+                    let timestamps = timestamps
+                        .iter()
+                        .map(|timestamp| timestamp.as_nanos_since_unix_epoch())
+                        .collect();
+                    (*provider, timestamps)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ProviderUsageSnapshot> for SupportedRpcProviderUsage {
+    fn from(snapshot: ProviderUsageSnapshot) -> Self {
+        snapshot
+            .usages
+            .into_iter()
+            .flat_map(|(provider, timestamps)| {
+                timestamps.into_iter().map(move |timestamp| {
+                    (provider, Timestamp::from_nanos_since_unix_epoch(timestamp))
+                })
+            })
+            .collect()
+    }
+}
+
+impl Storable for ProviderUsageSnapshot {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(encode(self))
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        encode(&self)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        // The usage is only used to rank providers: a snapshot that cannot be decoded, e.g.
+        // because it was written by a newer version of the canister, is discarded.
+        ciborium::de::from_reader::<Self, _>(bytes.as_ref())
+            .ok()
+            .filter(|snapshot| snapshot.version == PROVIDER_USAGE_SNAPSHOT_VERSION)
+            .unwrap_or_default()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// A [`ProviderScorecard`] stored in a [`StableBTreeMap`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct StoredProviderScorecard(ProviderScorecard);
+
+impl Storable for StoredProviderScorecard {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(encode(&self.0))
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        encode(&self.0)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        StoredProviderScorecard(decode(bytes.as_ref()))
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Key of the scorecard of the given period, so that scorecards are sorted chronologically.
+fn scorecard_key(period: &ScorecardPeriod) -> u32 {
+    u32::from(period.year) * 12 + u32::from(period.month.saturating_sub(1))
+}
+
 fn encode<S: ?Sized + serde::Serialize>(state: &S) -> Vec<u8> {
     let mut buf = vec![];
     ciborium::ser::into_writer(state, &mut buf).expect("failed to encode memory");
@@ -99,10 +327,58 @@ pub struct State {
     api_key_principals: Vec<Principal>,
     override_provider: OverrideProvider,
     log_filter: LogFilter,
+    #[serde(default)]
+    log_format: LogFormat,
     mode: Mode,
     num_subnet_nodes: u32,
     #[serde(default)]
     base_http_outcall_fee: Option<u128>,
+    #[serde(default)]
+    custom_api_keys: BTreeMap<String, ApiKey>,
+    #[serde(default)]
+    provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+    #[serde(default)]
+    allowed_callers: AllowedCallers,
+    // Compression is only enabled by default for new installations, so that upgrading
+    // the canister does not change the requests sent to the providers.
+    #[serde(default = "response_compression_disabled")]
+    response_compression: ResponseCompression,
+    #[serde(default)]
+    provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+    #[serde(default)]
+    retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+    #[serde(default)]
+    request_id_strategy: RequestIdStrategy,
+    #[serde(default)]
+    request_id_formats: BTreeMap<SupportedRpcProviderId, RequestIdFormat>,
+    #[serde(default)]
+    log_drain: LogDrain,
+    #[serde(default)]
+    outcall_concurrency: OutcallConcurrency,
+    #[serde(default)]
+    provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+    #[serde(default)]
+    slot_ticker: SlotTicker,
+    #[serde(default)]
+    pricing_policy: PricingPolicy,
+    #[serde(default)]
+    cycles_ledger_id: Option<Principal>,
+    #[serde(default)]
+    schema_version: u32,
+    #[serde(default)]
+    log_throttles: BTreeMap<LogPriority, LogThrottle>,
+    #[serde(default)]
+    log_targets: BTreeMap<String, BTreeSet<LogPriority>>,
+    #[serde(default)]
+    shadow_providers: BTreeSet<SupportedRpcProviderId>,
+    #[serde(default)]
+    version_sampler: VersionSampler,
+    #[serde(default)]
+    allowed_callers_exemptions: Option<BTreeSet<String>>,
+    // Kept in the state, so that a deposit with an unknown outcome can still be credited when it
+    // is retried after an upgrade.
+    #[serde(default)]
+    pending_deposits: BTreeSet<PendingDeposit>,
 }
 
 impl State {
@@ -118,6 +394,48 @@ impl State {
         self.api_keys.remove(provider);
     }
 
+    /// Returns all stored API keys, both for supported providers and for custom hosts.
+    pub fn get_all_api_keys(&self) -> Vec<ApiKey> {
+        self.api_keys
+            .values()
+            .chain(self.custom_api_keys.values())
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_custom_api_key(&self, url: &str) -> Option<ApiKey> {
+        self.custom_api_keys.get(url).cloned()
+    }
+
+    pub fn insert_custom_api_key(&mut self, url: String, api_key: ApiKey) {
+        self.custom_api_keys.insert(url, api_key);
+    }
+
+    pub fn remove_custom_api_key(&mut self, url: &str) {
+        self.custom_api_keys.remove(url);
+    }
+
+    pub fn get_provider_fallbacks(
+        &self,
+        provider: &SupportedRpcProviderId,
+    ) -> Vec<SupportedRpcProviderId> {
+        self.provider_fallbacks
+            .get(provider)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replaces all fallback chains.
+    ///
+    /// Panics if a fallback chain contains its own provider, duplicates, or providers
+    /// of a different Solana cluster.
+    pub fn set_provider_fallbacks(
+        &mut self,
+        provider_fallbacks: Vec<(SupportedRpcProviderId, Vec<SupportedRpcProviderId>)>,
+    ) {
+        self.provider_fallbacks = validate_provider_fallbacks(provider_fallbacks);
+    }
+
     pub fn is_api_key_principal(&self, principal: &Principal) -> bool {
         self.api_key_principals
             .iter()
@@ -133,6 +451,258 @@ impl State {
         }
     }
 
+    pub fn get_api_key_principals(&self) -> Vec<Principal> {
+        self.api_key_principals.clone()
+    }
+
+    /// Allows the given principal to manage API keys.
+    ///
+    /// Returns `false` if the principal was already allowed to do so.
+    pub fn add_api_key_principal(&mut self, principal: Principal) -> bool {
+        if self.is_api_key_principal(&principal) {
+            return false;
+        }
+        self.api_key_principals.push(principal);
+        true
+    }
+
+    /// Revokes the permission of the given principal to manage API keys.
+    ///
+    /// Returns `false` if the principal was not allowed to do so.
+    pub fn remove_api_key_principal(&mut self, principal: &Principal) -> bool {
+        let num_principals = self.api_key_principals.len();
+        self.api_key_principals.retain(|other| other != principal);
+        self.api_key_principals.len() != num_principals
+    }
+
+    /// Returns `true` if the given principal is allowed to make RPC requests.
+    ///
+    /// Controllers are not taken into account and should be checked separately.
+    pub fn is_allowed_caller(&self, principal: &Principal) -> bool {
+        self.allowed_callers.is_allowed(principal)
+    }
+
+    pub fn get_allowed_callers(&self) -> AllowedCallers {
+        self.allowed_callers.clone()
+    }
+
+    pub fn set_allowed_callers(&mut self, allowed_callers: AllowedCallers) {
+        self.allowed_callers = allowed_callers;
+    }
+
+    /// Returns `true` if any principal may call the given method, regardless of the
+    /// allowed callers.
+    ///
+    /// Unless configured otherwise, the methods estimating the cycles cost of a request are exempted.
+    pub fn is_exempt_from_allowed_callers(&self, method: &str) -> bool {
+        match &self.allowed_callers_exemptions {
+            Some(exemptions) => exemptions.contains(method),
+            None => method.ends_with(CYCLES_COST_METHOD_SUFFIX),
+        }
+    }
+
+    pub fn set_allowed_callers_exemptions(&mut self, exemptions: Vec<String>) {
+        self.allowed_callers_exemptions = Some(exemptions.into_iter().collect());
+    }
+
+    pub fn is_response_compression_enabled(&self) -> bool {
+        self.response_compression == ResponseCompression::Enabled
+    }
+
+    pub fn set_response_compression(&mut self, response_compression: ResponseCompression) {
+        self.response_compression = response_compression;
+    }
+
+    pub fn get_provider_quota(&self, provider: &SupportedRpcProviderId) -> Option<ProviderQuota> {
+        self.provider_quotas.get(provider).copied()
+    }
+
+    /// Replaces all provider quotas.
+    ///
+    /// Panics if a quota is empty, has an empty window, or if a provider has several quotas.
+    pub fn set_provider_quotas(
+        &mut self,
+        provider_quotas: Vec<(SupportedRpcProviderId, ProviderQuota)>,
+    ) {
+        self.provider_quotas = validate_provider_quotas(provider_quotas);
+    }
+
+    pub fn get_retry_policy(&self, provider: &SupportedRpcProviderId) -> Option<RetryPolicy> {
+        self.retry_policies.get(provider).cloned()
+    }
+
+    /// Replaces all retry policies.
+    ///
+    /// Panics if a policy retries a status code that is not an error, or if a provider has
+    /// several policies.
+    pub fn set_retry_policies(
+        &mut self,
+        retry_policies: Vec<(SupportedRpcProviderId, RetryPolicy)>,
+    ) {
+        self.retry_policies = validate_retry_policies(retry_policies);
+    }
+
+    pub fn get_request_id_strategy(&self) -> RequestIdStrategy {
+        self.request_id_strategy
+    }
+
+    pub fn set_request_id_strategy(&mut self, request_id_strategy: RequestIdStrategy) {
+        self.request_id_strategy = request_id_strategy;
+    }
+
+    pub fn get_request_id_format(
+        &self,
+        provider: &SupportedRpcProviderId,
+    ) -> Option<RequestIdFormat> {
+        self.request_id_formats.get(provider).copied()
+    }
+
+    /// Replaces all request ID formats.
+    ///
+    /// Panics if a provider has several formats.
+    pub fn set_request_id_formats(
+        &mut self,
+        request_id_formats: Vec<(SupportedRpcProviderId, RequestIdFormat)>,
+    ) {
+        self.request_id_formats = validate_request_id_formats(request_id_formats);
+    }
+
+    pub fn get_log_drain(&self) -> LogDrain {
+        self.log_drain.clone()
+    }
+
+    pub fn set_log_drain(&mut self, log_drain: LogDrain) {
+        self.log_drain = log_drain;
+    }
+
+    pub fn get_outcall_concurrency(&self) -> OutcallConcurrency {
+        self.outcall_concurrency.clone()
+    }
+
+    pub fn set_outcall_concurrency(&mut self, outcall_concurrency: OutcallConcurrency) {
+        self.outcall_concurrency = validate_outcall_concurrency(outcall_concurrency);
+    }
+
+    pub fn get_slot_ticker(&self) -> SlotTicker {
+        self.slot_ticker.clone()
+    }
+
+    pub fn set_slot_ticker(&mut self, slot_ticker: SlotTicker) {
+        self.slot_ticker = validate_slot_ticker(slot_ticker);
+    }
+
+    pub fn get_pricing_policy(&self) -> PricingPolicy {
+        self.pricing_policy.clone()
+    }
+
+    pub fn set_pricing_policy(&mut self, pricing_policy: PricingPolicy) {
+        self.pricing_policy = validate_pricing_policy(pricing_policy);
+    }
+
+    /// Number of cycles to charge the caller for an HTTPS outcall with the given cost made for a
+    /// request with the given priority, according to the pricing policy.
+    pub fn cycles_to_charge(&self, request_cost: u128, priority: RequestPriority) -> u128 {
+        let policy = &self.pricing_policy;
+        if request_cost <= policy.free_tier_max_cycles {
+            return 0;
+        }
+        let markup_percent = match priority {
+            RequestPriority::Normal => policy.markup_percent,
+            RequestPriority::Expedited => policy
+                .markup_percent
+                .saturating_add(policy.expedited_markup_percent()),
+        };
+        let markup = request_cost.saturating_mul(markup_percent as u128) / 100;
+        let collateral = policy
+            .collateral_cycles_per_node
+            .saturating_mul(self.num_subnet_nodes as u128);
+        request_cost
+            .saturating_add(markup)
+            .saturating_add(collateral)
+    }
+
+    pub fn get_cycles_ledger_id(&self) -> Principal {
+        self.cycles_ledger_id.unwrap_or_else(|| {
+            Principal::from_text(DEFAULT_CYCLES_LEDGER_ID).expect("BUG: invalid cycles ledger ID")
+        })
+    }
+
+    pub fn set_cycles_ledger_id(&mut self, cycles_ledger_id: Principal) {
+        self.cycles_ledger_id = Some(cycles_ledger_id);
+    }
+
+    pub fn get_log_throttle(&self, priority: &LogPriority) -> Option<LogThrottle> {
+        self.log_throttles.get(priority).cloned()
+    }
+
+    pub fn set_log_throttles(&mut self, log_throttles: Vec<(LogPriority, LogThrottle)>) {
+        self.log_throttles = validate_log_throttles(log_throttles);
+    }
+
+    /// Returns `true` if log entries with the given priority recorded in the module with the
+    /// given path are allowed by the most specific log target matching that module, if any.
+    pub fn is_log_target_enabled(&self, priority: &LogPriority, module_path: &str) -> bool {
+        find_log_target(&self.log_targets, module_path)
+            .is_none_or(|priorities| priorities.contains(priority))
+    }
+
+    /// Replaces all log targets.
+    ///
+    /// Panics if a target has an empty module path, or if a module has several targets.
+    pub fn set_log_targets(&mut self, log_targets: Vec<LogTarget>) {
+        self.log_targets = validate_log_targets(log_targets);
+    }
+
+    pub fn add_pending_deposit(&mut self, deposit: PendingDeposit) {
+        self.pending_deposits.insert(deposit);
+    }
+
+    /// Removes the given deposit from the pending deposits and returns whether it was pending.
+    pub fn remove_pending_deposit(&mut self, deposit: &PendingDeposit) -> bool {
+        self.pending_deposits.remove(deposit)
+    }
+
+    pub fn get_shadow_providers(&self) -> BTreeSet<SupportedRpcProviderId> {
+        self.shadow_providers.clone()
+    }
+
+    pub fn set_shadow_providers(&mut self, shadow_providers: Vec<SupportedRpcProviderId>) {
+        self.shadow_providers = shadow_providers.into_iter().collect();
+    }
+
+    pub fn get_version_sampler(&self) -> VersionSampler {
+        self.version_sampler.clone()
+    }
+
+    /// Panics if the interval is 0 or if the minimum version is not a valid version.
+    pub fn set_version_sampler(&mut self, version_sampler: VersionSampler) {
+        self.version_sampler = validate_version_sampler(version_sampler);
+    }
+
+    /// Returns `true` if HTTPS outcalls costing the base HTTPS outcall fee are free for the
+    /// caller, in which case callers do not need to attach cycles to their requests.
+    pub fn is_base_http_outcall_fee_free(&mut self) -> bool {
+        self.lazy_compute_base_http_outcall_fee() <= self.pricing_policy.free_tier_max_cycles
+    }
+
+    pub fn get_provider_endpoint(
+        &self,
+        provider: &SupportedRpcProviderId,
+    ) -> Option<ProviderEndpoint> {
+        self.provider_endpoints.get(provider).cloned()
+    }
+
+    /// Replaces all preferred provider endpoints.
+    ///
+    /// Panics if an endpoint is not of the same kind as the provider's default endpoint, has an
+    /// invalid URL, or if a provider has several endpoints.
+    pub fn set_provider_endpoints(
+        &mut self,
+        provider_endpoints: Vec<(SupportedRpcProviderId, ProviderEndpoint)>,
+    ) {
+        self.provider_endpoints = validate_provider_endpoints(provider_endpoints);
+    }
+
     pub fn get_override_provider(&self) -> OverrideProvider {
         self.override_provider.clone()
     }
@@ -149,6 +719,14 @@ impl State {
         self.log_filter = filter;
     }
 
+    pub fn get_log_format(&self) -> LogFormat {
+        self.log_format
+    }
+
+    pub fn set_log_format(&mut self, log_format: LogFormat) {
+        self.log_format = log_format;
+    }
+
     pub fn get_num_subnet_nodes(&self) -> u32 {
         self.num_subnet_nodes
     }
@@ -175,6 +753,96 @@ impl State {
             .base_http_outcall_fee
             .get_or_insert_with(|| compute_base_http_outcall_fee(self.num_subnet_nodes))
     }
+
+    pub fn get_schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    /// Migrates a state written by a previous version of the canister to the current
+    /// [`STATE_SCHEMA_VERSION`].
+    ///
+    /// A state written by a more recent version of the canister is left unchanged and reported by
+    /// [`State::validate`].
+    pub fn migrate(&mut self) {
+        while let Some(migration) = MIGRATIONS.get(self.schema_version as usize) {
+            migration(self);
+            self.schema_version += 1;
+        }
+    }
+
+    /// Checks the invariants of the state that are not enforced when the state is decoded, e.g.
+    /// because they depend on the providers supported by this version of the canister.
+    pub fn validate(&self) -> Vec<StateValidationIssue> {
+        self.validate_with(|provider| get_provider(provider).is_some())
+    }
+
+    fn validate_with(
+        &self,
+        is_supported: impl Fn(&SupportedRpcProviderId) -> bool,
+    ) -> Vec<StateValidationIssue> {
+        let mut issues = Vec::new();
+        if self.schema_version > STATE_SCHEMA_VERSION {
+            issues.push(StateValidationIssue::UnsupportedSchemaVersion {
+                stored: self.schema_version,
+                supported: STATE_SCHEMA_VERSION,
+            });
+        }
+        issues.extend(
+            self.api_keys
+                .keys()
+                .filter(|provider| !is_supported(provider))
+                .map(|provider| StateValidationIssue::OrphanApiKey {
+                    provider: *provider,
+                }),
+        );
+        let provider_settings: BTreeSet<_> = self
+            .provider_fallbacks
+            .iter()
+            .flat_map(|(provider, fallbacks)| iter::once(provider).chain(fallbacks))
+            .map(|provider| (provider, "providerFallbacks"))
+            .chain(
+                self.provider_quotas
+                    .keys()
+                    .map(|provider| (provider, "providerQuotas")),
+            )
+            .chain(
+                self.retry_policies
+                    .keys()
+                    .map(|provider| (provider, "retryPolicies")),
+            )
+            .chain(
+                self.request_id_formats
+                    .keys()
+                    .map(|provider| (provider, "requestIdFormats")),
+            )
+            .chain(
+                self.provider_endpoints
+                    .keys()
+                    .map(|provider| (provider, "providerEndpoints")),
+            )
+            .chain(
+                self.shadow_providers
+                    .iter()
+                    .map(|provider| (provider, "shadowProviders")),
+            )
+            .filter(|(provider, _)| !is_supported(provider))
+            .collect();
+        issues.extend(provider_settings.into_iter().map(|(provider, setting)| {
+            StateValidationIssue::OrphanProviderSetting {
+                provider: *provider,
+                setting: setting.to_string(),
+            }
+        }));
+        if let Some(substitution) = &self.override_provider.override_url {
+            if let Err(e) = substitution.pattern.compile() {
+                issues.push(StateValidationIssue::InvalidOverrideProviderRegex {
+                    pattern: substitution.pattern.0.clone(),
+                    error: e.to_string(),
+                });
+            }
+        }
+        issues
+    }
 }
 
 impl From<InstallArgs> for State {
@@ -185,11 +853,283 @@ impl From<InstallArgs> for State {
             api_key_principals: value.manage_api_keys.unwrap_or_default(),
             override_provider: value.override_provider.unwrap_or_default().into(),
             log_filter: value.log_filter.unwrap_or_default(),
+            log_format: value.log_format.unwrap_or_default(),
             mode: value.mode.unwrap_or_default(),
             num_subnet_nodes,
             base_http_outcall_fee: Some(compute_base_http_outcall_fee(num_subnet_nodes)),
+            custom_api_keys: Default::default(),
+            provider_fallbacks: validate_provider_fallbacks(
+                value.provider_fallbacks.unwrap_or_default(),
+            ),
+            allowed_callers: value.allowed_callers.unwrap_or_default(),
+            response_compression: value.response_compression.unwrap_or_default(),
+            provider_quotas: validate_provider_quotas(value.provider_quotas.unwrap_or_default()),
+            retry_policies: validate_retry_policies(value.retry_policies.unwrap_or_default()),
+            request_id_strategy: value.request_id_strategy.unwrap_or_default(),
+            request_id_formats: validate_request_id_formats(
+                value.request_id_formats.unwrap_or_default(),
+            ),
+            log_drain: value.log_drain.unwrap_or_default(),
+            outcall_concurrency: validate_outcall_concurrency(
+                value.outcall_concurrency.unwrap_or_default(),
+            ),
+            provider_endpoints: validate_provider_endpoints(
+                value.provider_endpoints.unwrap_or_default(),
+            ),
+            slot_ticker: validate_slot_ticker(value.slot_ticker.unwrap_or_default()),
+            pricing_policy: validate_pricing_policy(value.pricing_policy.unwrap_or_default()),
+            cycles_ledger_id: value.cycles_ledger_id,
+            schema_version: STATE_SCHEMA_VERSION,
+            log_throttles: validate_log_throttles(value.log_throttles.unwrap_or_default()),
+            log_targets: validate_log_targets(value.log_targets.unwrap_or_default()),
+            shadow_providers: value
+                .shadow_providers
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            version_sampler: validate_version_sampler(value.version_sampler.unwrap_or_default()),
+            allowed_callers_exemptions: value
+                .allowed_callers_exemptions
+                .map(|exemptions| exemptions.into_iter().collect()),
+            pending_deposits: Default::default(),
+        }
+    }
+}
+
+fn response_compression_disabled() -> ResponseCompression {
+    ResponseCompression::Disabled
+}
+
+fn validate_provider_fallbacks(
+    provider_fallbacks: Vec<(SupportedRpcProviderId, Vec<SupportedRpcProviderId>)>,
+) -> BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>> {
+    let mut result = BTreeMap::new();
+    for (provider, fallbacks) in provider_fallbacks {
+        let cluster = get_provider(&provider).expect("Unknown provider").cluster;
+        let mut seen = BTreeSet::from([provider]);
+        for fallback in &fallbacks {
+            assert!(
+                seen.insert(*fallback),
+                "Invalid fallback chain for {provider:?}: duplicate provider {fallback:?}"
+            );
+            assert_eq!(
+                get_provider(fallback).expect("Unknown provider").cluster,
+                cluster,
+                "Invalid fallback chain for {provider:?}: {fallback:?} belongs to another cluster"
+            );
         }
+        assert!(
+            result.insert(provider, fallbacks).is_none(),
+            "Duplicate fallback chain for {provider:?}"
+        );
+    }
+    result
+}
+
+fn validate_provider_quotas(
+    provider_quotas: Vec<(SupportedRpcProviderId, ProviderQuota)>,
+) -> BTreeMap<SupportedRpcProviderId, ProviderQuota> {
+    let mut result = BTreeMap::new();
+    for (provider, quota) in provider_quotas {
+        assert!(
+            quota.max_requests > 0,
+            "Invalid quota for {provider:?}: maximum number of requests must be greater than 0"
+        );
+        assert!(
+            quota.window_seconds > 0,
+            "Invalid quota for {provider:?}: window must be greater than 0"
+        );
+        assert!(
+            result.insert(provider, quota).is_none(),
+            "Duplicate quota for {provider:?}"
+        );
+    }
+    result
+}
+
+fn validate_outcall_concurrency(outcall_concurrency: OutcallConcurrency) -> OutcallConcurrency {
+    if let OutcallConcurrency::Limited { max_in_flight, .. } = &outcall_concurrency {
+        assert!(
+            *max_in_flight > 0,
+            "Invalid outcall concurrency: maximum number of HTTPS outcalls in flight must be greater than 0"
+        );
     }
+    outcall_concurrency
+}
+
+fn validate_pricing_policy(pricing_policy: PricingPolicy) -> PricingPolicy {
+    assert!(
+        pricing_policy.markup_percent <= PricingPolicy::MAX_MARKUP_PERCENT,
+        "Invalid pricing policy: markup must be at most {}%",
+        PricingPolicy::MAX_MARKUP_PERCENT
+    );
+    assert!(
+        pricing_policy.expedited_markup_percent() <= PricingPolicy::MAX_MARKUP_PERCENT,
+        "Invalid pricing policy: expedited markup must be at most {}%",
+        PricingPolicy::MAX_MARKUP_PERCENT
+    );
+    pricing_policy
+}
+
+fn validate_log_targets(log_targets: Vec<LogTarget>) -> BTreeMap<String, BTreeSet<LogPriority>> {
+    let mut result = BTreeMap::new();
+    for LogTarget { module, priorities } in log_targets {
+        assert!(
+            !module.is_empty(),
+            "Invalid log target: module must not be empty"
+        );
+        assert!(
+            result
+                .insert(module.clone(), priorities.into_iter().collect())
+                .is_none(),
+            "Duplicate log target for module `{module}`"
+        );
+    }
+    result
+}
+
+fn validate_log_throttles(
+    log_throttles: Vec<(LogPriority, LogThrottle)>,
+) -> BTreeMap<LogPriority, LogThrottle> {
+    let mut result = BTreeMap::new();
+    for (priority, throttle) in log_throttles {
+        assert_ne!(
+            throttle.sample_rate,
+            Some(0),
+            "Invalid log throttle for {priority:?}: sample rate must be greater than 0"
+        );
+        if let Some(rate_limit) = &throttle.rate_limit {
+            assert!(
+                rate_limit.max_burst > 0,
+                "Invalid log throttle for {priority:?}: maximum burst must be greater than 0"
+            );
+            assert!(
+                rate_limit.tokens_per_second > 0,
+                "Invalid log throttle for {priority:?}: tokens per second must be greater than 0"
+            );
+        }
+        assert!(
+            result.insert(priority, throttle).is_none(),
+            "Duplicate log throttle for {priority:?}"
+        );
+    }
+    result
+}
+
+fn validate_slot_ticker(slot_ticker: SlotTicker) -> SlotTicker {
+    if let SlotTicker::Enabled {
+        interval_seconds, ..
+    } = &slot_ticker
+    {
+        assert!(
+            *interval_seconds > 0,
+            "Invalid slot ticker: interval must be greater than 0"
+        );
+    }
+    slot_ticker
+}
+
+fn validate_version_sampler(version_sampler: VersionSampler) -> VersionSampler {
+    if let VersionSampler::Enabled {
+        interval_seconds,
+        min_version,
+        ..
+    } = &version_sampler
+    {
+        assert!(
+            *interval_seconds > 0,
+            "Invalid version sampler: interval must be greater than 0"
+        );
+        if let Some(min_version) = min_version {
+            assert!(
+                parse_version(min_version).is_some(),
+                "Invalid version sampler: invalid minimum version {min_version}"
+            );
+        }
+    }
+    version_sampler
+}
+
+fn validate_provider_endpoints(
+    provider_endpoints: Vec<(SupportedRpcProviderId, ProviderEndpoint)>,
+) -> BTreeMap<SupportedRpcProviderId, ProviderEndpoint> {
+    fn validate_url(provider: &SupportedRpcProviderId, url: &str, expect_api_key: bool) {
+        assert!(
+            url.starts_with("https://"),
+            "Invalid endpoint for {provider:?}: URL must use HTTPS"
+        );
+        assert_eq!(
+            url.contains(API_KEY_REPLACE_STRING),
+            expect_api_key,
+            "Invalid endpoint for {provider:?}: {API_KEY_REPLACE_STRING} placeholder must only be used in URL patterns"
+        );
+    }
+
+    let mut result = BTreeMap::new();
+    for (provider, endpoint) in provider_endpoints {
+        let default_access = get_provider(&provider).expect("Unknown provider").access;
+        match (&default_access, &endpoint.access) {
+            (
+                RpcAccess::Authenticated { .. },
+                RpcAccess::Authenticated { auth, public_url },
+            ) => {
+                match auth {
+                    RpcAuth::BearerToken { url } => validate_url(&provider, url, false),
+                    RpcAuth::UrlParameter { url_pattern } => {
+                        validate_url(&provider, url_pattern, true)
+                    }
+                }
+                if let Some(public_url) = public_url {
+                    validate_url(&provider, public_url, false);
+                }
+            }
+            (RpcAccess::Unauthenticated { .. }, RpcAccess::Unauthenticated { public_url }) => {
+                validate_url(&provider, public_url, false)
+            }
+            _ => panic!(
+                "Invalid endpoint for {provider:?}: access must be of the same kind as the default access {default_access:?}"
+            ),
+        }
+        assert!(
+            result.insert(provider, endpoint).is_none(),
+            "Duplicate endpoint for {provider:?}"
+        );
+    }
+    result
+}
+
+fn validate_retry_policies(
+    retry_policies: Vec<(SupportedRpcProviderId, RetryPolicy)>,
+) -> BTreeMap<SupportedRpcProviderId, RetryPolicy> {
+    let mut result = BTreeMap::new();
+    for (provider, policy) in retry_policies {
+        if let Some(status) = policy
+            .retry_on_http_status
+            .iter()
+            .flatten()
+            .find(|status| !(400..600).contains(*status))
+        {
+            panic!("Invalid retry policy for {provider:?}: status {status} is not an error");
+        }
+        assert!(
+            result.insert(provider, policy).is_none(),
+            "Duplicate retry policy for {provider:?}"
+        );
+    }
+    result
+}
+
+fn validate_request_id_formats(
+    request_id_formats: Vec<(SupportedRpcProviderId, RequestIdFormat)>,
+) -> BTreeMap<SupportedRpcProviderId, RequestIdFormat> {
+    let mut result = BTreeMap::new();
+    for (provider, format) in request_id_formats {
+        assert!(
+            result.insert(provider, format).is_none(),
+            "Duplicate request ID format for {provider:?}"
+        );
+    }
+    result
 }
 
 pub fn read_state<R>(f: impl FnOnce(&State) -> R) -> R {
@@ -233,18 +1173,418 @@ pub fn reset_state() {
     })
 }
 
-pub fn next_request_id() -> Id {
-    UNSTABLE_HTTP_REQUEST_COUNTER.with_borrow_mut(|counter| {
-        let current_request_id = counter.get_and_increment();
-        Id::from(current_request_id)
+/// Returns the [`LogFormat`] of the log entries printed to the canister logs.
+///
+/// Unlike [`read_state`], this does not panic when the state is not initialized or is being
+/// mutated, so that it can be called whenever a log entry is recorded.
+pub fn get_log_format() -> LogFormat {
+    STATE
+        .with(|cell| match cell.try_borrow().ok()?.get() {
+            ConfigState::Initialized(state) => Some(state.get_log_format()),
+            ConfigState::Uninitialized => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the priorities allowed by the most specific log target matching the module with the
+/// given path, i.e. the target with the longest module path that is either the module itself or
+/// one of its parents.
+///
+/// The module path may start with the crate name, as returned by [`module_path!`].
+fn find_log_target<'a>(
+    log_targets: &'a BTreeMap<String, BTreeSet<LogPriority>>,
+    module_path: &str,
+) -> Option<&'a BTreeSet<LogPriority>> {
+    if log_targets.is_empty() {
+        return None;
+    }
+    let module_path = module_path
+        .strip_prefix(concat!(env!("CARGO_CRATE_NAME"), "::"))
+        .unwrap_or(module_path);
+    let mut candidate = module_path;
+    loop {
+        if let Some(priorities) = log_targets.get(candidate) {
+            return Some(priorities);
+        }
+        let (parent, _) = candidate.rsplit_once("::")?;
+        candidate = parent;
+    }
+}
+
+/// Returns `true` if log entries with the given priority recorded in the module with the given
+/// path are allowed by the log targets.
+///
+/// Unlike [`read_state`], this does not panic when the state is not initialized or is being
+/// mutated, so that it can be called whenever a log entry is recorded.
+pub fn is_log_target_enabled(priority: &LogPriority, module_path: &str) -> bool {
+    STATE
+        .with(|cell| match cell.try_borrow().ok()?.get() {
+            ConfigState::Initialized(state) => {
+                Some(state.is_log_target_enabled(priority, module_path))
+            }
+            ConfigState::Uninitialized => None,
+        })
+        .unwrap_or(true)
+}
+
+/// Returns the [`LogThrottle`] of the given log priority, if any.
+///
+/// Unlike [`read_state`], this does not panic when the state is not initialized or is being
+/// mutated, so that it can be called whenever a log entry is recorded.
+pub fn get_log_throttle(priority: &LogPriority) -> Option<LogThrottle> {
+    STATE.with(|cell| match cell.try_borrow().ok()?.get() {
+        ConfigState::Initialized(state) => state.get_log_throttle(priority),
+        ConfigState::Uninitialized => None,
+    })
+}
+
+/// Prepaid cycles of the given principal, deposited with `depositCycles`.
+pub fn get_prepaid_cycles(principal: &Principal) -> u128 {
+    PREPAID_CYCLES.with_borrow(|balances| {
+        balances
+            .get(&PrincipalKey(*principal))
+            .map_or(0, |cycles| cycles.0)
+    })
+}
+
+/// Adds the given amount of cycles to the prepaid cycles of the given principal and returns the
+/// new balance.
+pub fn credit_prepaid_cycles(principal: Principal, amount: u128) -> u128 {
+    PREPAID_CYCLES.with_borrow_mut(|balances| {
+        let key = PrincipalKey(principal);
+        let balance = balances
+            .get(&key)
+            .map_or(0, |cycles| cycles.0)
+            .saturating_add(amount);
+        balances.insert(key, Cycles(balance));
+        balance
+    })
+}
+
+/// Removes the given amount of cycles from the prepaid cycles of the given principal.
+///
+/// Returns `false` and leaves the balance unchanged if it is too low.
+pub fn debit_prepaid_cycles(principal: Principal, amount: u128) -> bool {
+    PREPAID_CYCLES.with_borrow_mut(|balances| {
+        let key = PrincipalKey(principal);
+        let balance = balances.get(&key).map_or(0, |cycles| cycles.0);
+        match balance.checked_sub(amount) {
+            Some(0) => {
+                balances.remove(&key);
+            }
+            Some(remaining) => {
+                balances.insert(key, Cycles(remaining));
+            }
+            None => return false,
+        }
+        UNSTABLE_PREPAID_CYCLES_DEBITED.with_borrow_mut(|debited| {
+            let total = debited.entry(principal).or_default();
+            *total = total.saturating_add(amount);
+        });
+        true
     })
 }
 
+/// Total prepaid cycles debited from the given principal since the canister was last installed
+/// or upgraded, so that the prepaid cycles consumed by a call can be computed.
+pub fn total_prepaid_cycles_debited(principal: &Principal) -> u128 {
+    UNSTABLE_PREPAID_CYCLES_DEBITED
+        .with_borrow(|debited| debited.get(principal).copied().unwrap_or_default())
+}
+
+/// Cycles report of the most recent call of the given principal, see [`record_cycles_report`].
+pub fn get_cycles_report(principal: &Principal) -> Option<CyclesReport> {
+    UNSTABLE_CYCLES_REPORTS.with_borrow(|reports| reports.get(principal).cloned())
+}
+
+/// Records the cycles attached to and consumed by the most recent call of the given principal,
+/// replacing any previous report for that principal.
+pub fn record_cycles_report(principal: Principal, report: CyclesReport) {
+    UNSTABLE_CYCLES_REPORTS.with_borrow_mut(|reports| {
+        if reports.len() >= MAX_CYCLES_REPORTS && !reports.contains_key(&principal) {
+            let oldest = reports
+                .iter()
+                .min_by_key(|(_principal, report)| report.timestamp)
+                .map(|(principal, _report)| *principal);
+            if let Some(oldest) = oldest {
+                reports.remove(&oldest);
+            }
+        }
+        reports.insert(principal, report);
+    });
+}
+
+/// Metadata of the responses of the providers for the most recent inconsistent result returned
+/// to the given principal, see [`record_response_metadata`].
+pub fn get_response_metadata(principal: &Principal) -> Option<Vec<(RpcSource, ResponseMetadata)>> {
+    UNSTABLE_RESPONSE_METADATA.with_borrow(|metadata| {
+        metadata
+            .get(principal)
+            .map(|(_timestamp, responses)| responses.clone())
+    })
+}
+
+/// Records the metadata of the responses of the providers for the most recent inconsistent
+/// result returned to the given principal at the given time, replacing any previous metadata for
+/// that principal.
+pub fn record_response_metadata(
+    principal: Principal,
+    timestamp: u64,
+    responses: Vec<(RpcSource, ResponseMetadata)>,
+) {
+    UNSTABLE_RESPONSE_METADATA.with_borrow_mut(|metadata| {
+        if metadata.len() >= MAX_RESPONSE_METADATA && !metadata.contains_key(&principal) {
+            let oldest = metadata
+                .iter()
+                .min_by_key(|(_principal, (timestamp, _responses))| *timestamp)
+                .map(|(principal, _)| *principal);
+            if let Some(oldest) = oldest {
+                metadata.remove(&oldest);
+            }
+        }
+        metadata.insert(principal, (timestamp, responses));
+    });
+}
+
+pub fn next_request_id(format: RequestIdFormat) -> Id {
+    let current_request_id = UNSTABLE_HTTP_REQUEST_COUNTER.with_borrow_mut(|counter| {
+        let current_request_id = *counter;
+        *counter = counter.wrapping_add(1);
+        current_request_id
+    });
+    match format {
+        RequestIdFormat::ConstantSizeString => Id::from(ConstantSizeId::from(current_request_id)),
+        RequestIdFormat::Number => Id::Number(current_request_id),
+        RequestIdFormat::Uuid => Id::String(format!(
+            "00000000-0000-4000-8000-{:012x}",
+            current_request_id & 0xffff_ffff_ffff
+        )),
+    }
+}
+
 pub fn record_ok_result(provider: SupportedRpcProviderId, now: Timestamp) {
     UNSTABLE_RPC_SERVICE_OK_RESULTS_TIMESTAMPS
         .with_borrow_mut(|access| access.record_evict(provider, now));
 }
 
+/// Persists the usage of the supported providers in stable memory, so that it can be restored
+/// with [`restore_provider_usage`] after an upgrade.
+pub fn save_provider_usage() {
+    let snapshot =
+        UNSTABLE_RPC_SERVICE_OK_RESULTS_TIMESTAMPS.with_borrow(ProviderUsageSnapshot::from);
+    PROVIDER_USAGE.with_borrow_mut(|cell| cell.set(snapshot));
+}
+
+/// Restores the usage of the supported providers persisted by [`save_provider_usage`], if any,
+/// and returns the number of restored usages.
+///
+/// The persisted usage is cleared, so that it is not restored again by a later upgrade.
+pub fn restore_provider_usage() -> usize {
+    let snapshot =
+        PROVIDER_USAGE.with_borrow_mut(|cell| cell.set(ProviderUsageSnapshot::default()));
+    let usage = SupportedRpcProviderUsage::from(snapshot);
+    let num_usages = usage.iter().map(|(_, timestamps)| timestamps.len()).sum();
+    UNSTABLE_RPC_SERVICE_OK_RESULTS_TIMESTAMPS.set(usage);
+    num_usages
+}
+
+/// Records the outcome and latency of a request to the given provider in shadow mode.
+pub fn record_shadow_outcome(
+    provider: SupportedRpcProviderId,
+    outcome: ShadowOutcome,
+    latency: Duration,
+) {
+    UNSTABLE_SHADOW_STATS.with_borrow_mut(|stats| stats.record(provider, outcome, latency));
+}
+
+/// Returns the statistics of the providers in shadow mode since the last upgrade, sorted by
+/// provider.
+pub fn get_shadow_provider_stats() -> Vec<ShadowProviderStats> {
+    UNSTABLE_SHADOW_STATS.with_borrow(|stats| stats.to_vec())
+}
+
+/// Records the outcome of a request to the given provider in the scores of the period containing
+/// `now`, after rolling up the scores of a previous period, if any.
+pub fn record_provider_outcome(
+    provider: SupportedRpcProviderId,
+    outcome: RequestOutcome,
+    now: Timestamp,
+) {
+    roll_up_provider_scores(now);
+    let period = ScorecardPeriod::containing(now.as_nanos_since_unix_epoch());
+    UNSTABLE_PROVIDER_SCORES.with_borrow_mut(|scores| {
+        scores
+            .get_or_insert_with(|| ProviderScores::new(period))
+            .record(provider, outcome)
+    });
+}
+
+/// Persists the scores of a period preceding the one containing `now` as a [`ProviderScorecard`]
+/// in stable memory, and returns that scorecard.
+///
+/// Returns `None` if there are no scores of a previous period.
+pub fn roll_up_provider_scores(now: Timestamp) -> Option<ProviderScorecard> {
+    let period = ScorecardPeriod::containing(now.as_nanos_since_unix_epoch());
+    let scores = UNSTABLE_PROVIDER_SCORES
+        .with_borrow_mut(|scores| scores.take_if(|scores| scores.period() < period))?;
+    let scorecard = ProviderScorecard::from(scores);
+    store_provider_scorecard(scorecard.clone());
+    Some(scorecard)
+}
+
+/// Returns the scorecard of the given period, or of the period containing `now` if none is given.
+///
+/// The scorecard of the current period is still being aggregated, and only covers the requests
+/// made so far.
+pub fn get_provider_scorecard(
+    period: Option<ScorecardPeriod>,
+    now: Timestamp,
+) -> Option<ProviderScorecard> {
+    let period =
+        period.unwrap_or_else(|| ScorecardPeriod::containing(now.as_nanos_since_unix_epoch()));
+    UNSTABLE_PROVIDER_SCORES
+        .with_borrow(|scores| {
+            scores
+                .as_ref()
+                .filter(|scores| scores.period() == period)
+                .cloned()
+                .map(ProviderScorecard::from)
+        })
+        .or_else(|| {
+            PROVIDER_SCORECARDS.with_borrow(|scorecards| {
+                scorecards
+                    .get(&scorecard_key(&period))
+                    .map(|scorecard| scorecard.0)
+            })
+        })
+}
+
+/// Persists the scores of the current period in stable memory, so that they can be restored with
+/// [`restore_provider_scores`] after an upgrade.
+pub fn save_provider_scores() {
+    if let Some(scores) = UNSTABLE_PROVIDER_SCORES.with_borrow(Clone::clone) {
+        store_provider_scorecard(ProviderScorecard::from(scores));
+    }
+}
+
+/// Restores the scores of the period containing `now` persisted by [`save_provider_scores`], if
+/// any, and returns the number of restored requests.
+///
+/// The persisted scorecard is removed from stable memory, so that the scores keep being
+/// aggregated until the end of the period. Scores persisted for a previous period, e.g. because
+/// the canister was upgraded right before the end of a month, are kept as that period's scorecard.
+pub fn restore_provider_scores(now: Timestamp) -> u64 {
+    let period = ScorecardPeriod::containing(now.as_nanos_since_unix_epoch());
+    let Some(StoredProviderScorecard(scorecard)) = PROVIDER_SCORECARDS
+        .with_borrow_mut(|scorecards| scorecards.remove(&scorecard_key(&period)))
+    else {
+        return 0;
+    };
+    let num_requests = scorecard
+        .scores
+        .iter()
+        .map(|score| score.num_requests)
+        .sum();
+    UNSTABLE_PROVIDER_SCORES.set(Some(ProviderScores::from(scorecard)));
+    num_requests
+}
+
+fn store_provider_scorecard(scorecard: ProviderScorecard) {
+    PROVIDER_SCORECARDS.with_borrow_mut(|scorecards| {
+        scorecards.insert(
+            scorecard_key(&scorecard.period),
+            StoredProviderScorecard(scorecard),
+        );
+        while scorecards.len() > MAX_PROVIDER_SCORECARDS {
+            scorecards.pop_first();
+        }
+    });
+}
+
+/// Records a request to the given provider, if a quota is set for it.
+pub fn record_request(provider: SupportedRpcProviderId, now: Timestamp) {
+    if let Some(quota) = read_state(|s| s.get_provider_quota(&provider)) {
+        UNSTABLE_RPC_SERVICE_REQUESTS_TIMESTAMPS
+            .with_borrow_mut(|requests| requests.record_evict(provider, &quota, now));
+    }
+}
+
+/// Returns whether the request quota of the given provider is exhausted.
+///
+/// Requests are recorded in heap memory, so that all quota windows start anew when the canister
+/// is upgraded.
+pub fn quota_status(provider: SupportedRpcProviderId, now: Timestamp) -> QuotaStatus {
+    // Requests are only recorded for providers with a quota, so that the state only needs
+    // to be read when requests were recorded.
+    if !UNSTABLE_RPC_SERVICE_REQUESTS_TIMESTAMPS
+        .with_borrow(|requests| requests.contains(&provider))
+    {
+        return QuotaStatus::Available;
+    }
+    match read_state(|s| s.get_provider_quota(&provider)) {
+        Some(quota) => UNSTABLE_RPC_SERVICE_REQUESTS_TIMESTAMPS
+            .with_borrow_mut(|requests| requests.quota_status_evict(provider, &quota, now)),
+        None => QuotaStatus::Available,
+    }
+}
+
+/// Records that the given provider responded with [`ProviderError::RateLimited`].
+///
+/// [`ProviderError::RateLimited`]: sol_rpc_types::ProviderError::RateLimited
+pub fn record_rate_limited(
+    provider: SupportedRpcProviderId,
+    retry_after_seconds: Option<u64>,
+    now: Timestamp,
+) {
+    UNSTABLE_RPC_SERVICE_RATE_LIMITS
+        .with_borrow_mut(|limits| limits.record(provider, retry_after_seconds, now));
+}
+
+/// Returns `true` if the given provider was recently rate limited and did not ask to retry yet.
+pub fn is_rate_limited(provider: SupportedRpcProviderId, now: Timestamp) -> bool {
+    UNSTABLE_RPC_SERVICE_RATE_LIMITS
+        .with_borrow_mut(|limits| limits.is_rate_limited_evict(provider, now))
+}
+
+/// Records the latency of an HTTPS outcall to the given host.
+pub fn record_host_latency(host: String, latency: Duration, now: Timestamp) {
+    UNSTABLE_HOST_LATENCIES.with_borrow_mut(|latencies| latencies.record(host, latency, now))
+}
+
+/// Returns the recent latency of the HTTPS outcalls to the given host, if known.
+pub fn get_host_latency(host: &str) -> Option<Duration> {
+    UNSTABLE_HOST_LATENCIES.with_borrow(|latencies| latencies.get(host))
+}
+
+/// Records a request for which all the providers of the given cluster failed and returns `true`
+/// if it started a new outage.
+pub fn record_cluster_outage(cluster: SolanaCluster, now: Timestamp) -> bool {
+    UNSTABLE_CLUSTER_OUTAGES.with_borrow_mut(|outages| outages.record_failure(cluster, now))
+}
+
+/// Records a request for which a provider of the given cluster responded and returns the outage
+/// it ended, if any.
+pub fn record_cluster_recovery(cluster: SolanaCluster, now: Timestamp) -> Option<ClusterOutage> {
+    UNSTABLE_CLUSTER_OUTAGES.with_borrow_mut(|outages| outages.record_success(cluster, now))
+}
+
+/// Returns the most recent total outage of the given cluster, if any.
+pub fn get_cluster_outage(cluster: SolanaCluster) -> Option<ClusterOutage> {
+    UNSTABLE_CLUSTER_OUTAGES.with_borrow(|outages| outages.get(cluster))
+}
+
+/// Records the spread of the slots returned by the providers of the given cluster for the most
+/// recent `getSlot` request.
+pub fn record_slot_skew(cluster: SolanaCluster, skew: u64) {
+    UNSTABLE_SLOT_SKEWS.with_borrow_mut(|skews| skews.insert(cluster, skew));
+}
+
+/// Returns the spread of the slots returned by the providers of the given cluster for the most
+/// recent `getSlot` request, if any.
+pub fn get_slot_skew(cluster: SolanaCluster) -> Option<u64> {
+    UNSTABLE_SLOT_SKEWS.with_borrow(|skews| skews.get(&cluster).copied())
+}
+
 pub fn rank_providers(
     providers: &[SupportedRpcProviderId],
     now: Timestamp,
@@ -1,5 +1,6 @@
 use crate::{
     memory::{decode, encode, init_state, mutate_state, next_request_id, read_state, State},
+    payments::PendingDeposit,
     types::{ApiKey, OverrideProvider},
 };
 use candid::Principal;
@@ -10,7 +11,13 @@ use proptest::{
     prop_oneof, proptest,
 };
 use serde::{Deserialize, Serialize};
-use sol_rpc_types::{Mode, RegexString, RegexSubstitution, SupportedRpcProviderId};
+use sol_rpc_types::{
+    AllowedCallers, CommitmentLevel, IpVersion, LogDrain, LogFormat, LogPriority, LogRateLimit,
+    LogThrottle, Mode, OutcallConcurrency, PricingPolicy, ProviderEndpoint, ProviderQuota,
+    RegexString, RegexSubstitution, RequestIdFormat, RequestIdStrategy, ResponseCompression,
+    RetryPolicy, RpcAccess, RpcAuth, RpcSource, RpcSources, SlotTicker, SolanaCluster,
+    SupportedRpcProviderId, VersionSampler,
+};
 use std::collections::{BTreeMap, BTreeSet};
 use strum::IntoEnumIterator;
 
@@ -42,88 +49,3506 @@ mod api_key_tests {
         assert!(is_api_key_principal(&principal1));
         assert!(is_api_key_principal(&principal2));
 
-        set_api_key_principals(vec![]);
-        assert!(!is_api_key_principal(&principal1));
-        assert!(!is_api_key_principal(&principal2));
+        set_api_key_principals(vec![]);
+        assert!(!is_api_key_principal(&principal1));
+        assert!(!is_api_key_principal(&principal2));
+    }
+
+    #[test]
+    fn should_add_and_remove_api_key_principals() {
+        init_state(State::default());
+
+        let principal1 =
+            Principal::from_text("k5dlc-ijshq-lsyre-qvvpq-2bnxr-pb26c-ag3sc-t6zo5-rdavy-recje-zqe")
+                .unwrap();
+        let principal2 =
+            Principal::from_text("yxhtl-jlpgx-wqnzc-ysego-h6yqe-3zwfo-o3grn-gvuhm-nz3kv-ainub-6ae")
+                .unwrap();
+        set_api_key_principals(vec![principal1]);
+
+        assert!(mutate_state(|state| state.add_api_key_principal(principal2)));
+        assert!(!mutate_state(
+            |state| state.add_api_key_principal(principal2)
+        ));
+        assert_eq!(
+            read_state(|state| state.get_api_key_principals()),
+            vec![principal1, principal2]
+        );
+
+        assert!(mutate_state(
+            |state| state.remove_api_key_principal(&principal1)
+        ));
+        assert!(!mutate_state(
+            |state| state.remove_api_key_principal(&principal1)
+        ));
+        assert!(!is_api_key_principal(&principal1));
+        assert!(is_api_key_principal(&principal2));
+        assert_eq!(
+            read_state(|state| state.get_api_key_principals()),
+            vec![principal2]
+        );
+    }
+
+    fn set_api_key_principals(new_principals: Vec<Principal>) {
+        mutate_state(|state| state.set_api_key_principals(new_principals));
+    }
+
+    fn is_api_key_principal(principal: &Principal) -> bool {
+        read_state(|state| state.is_api_key_principal(principal))
+    }
+}
+
+mod provider_fallbacks_tests {
+    use super::*;
+
+    #[test]
+    fn should_set_provider_fallbacks() {
+        let mut state = State::default();
+
+        state.set_provider_fallbacks(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            vec![
+                SupportedRpcProviderId::AlchemyMainnet,
+                SupportedRpcProviderId::DrpcMainnet,
+            ],
+        )]);
+
+        assert_eq!(
+            state.get_provider_fallbacks(&SupportedRpcProviderId::HeliusMainnet),
+            vec![
+                SupportedRpcProviderId::AlchemyMainnet,
+                SupportedRpcProviderId::DrpcMainnet
+            ]
+        );
+        assert_eq!(
+            state.get_provider_fallbacks(&SupportedRpcProviderId::AlchemyMainnet),
+            vec![]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "belongs to another cluster")]
+    fn should_fail_for_fallback_of_another_cluster() {
+        State::default().set_provider_fallbacks(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            vec![SupportedRpcProviderId::HeliusDevnet],
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate provider")]
+    fn should_fail_for_fallback_to_itself() {
+        State::default().set_provider_fallbacks(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            vec![SupportedRpcProviderId::HeliusMainnet],
+        )]);
+    }
+}
+
+mod allowed_callers_tests {
+    use super::*;
+
+    #[test]
+    fn should_allow_any_caller_by_default() {
+        let state = State::default();
+
+        assert!(state.is_allowed_caller(&Principal::anonymous()));
+        assert!(state.is_allowed_caller(&Principal::management_canister()));
+    }
+
+    #[test]
+    fn should_only_allow_listed_callers() {
+        let allowed = Principal::from_slice(&[0x9d, 0xf7, 0x01]);
+        let mut state = State::default();
+
+        state.set_allowed_callers(AllowedCallers::Only(vec![allowed]));
+        assert!(state.is_allowed_caller(&allowed));
+        assert!(!state.is_allowed_caller(&Principal::anonymous()));
+
+        state.set_allowed_callers(AllowedCallers::Only(vec![]));
+        assert!(!state.is_allowed_caller(&allowed));
+
+        state.set_allowed_callers(AllowedCallers::Any);
+        assert!(state.is_allowed_caller(&allowed));
+        assert!(state.is_allowed_caller(&Principal::anonymous()));
+    }
+}
+
+mod response_compression_tests {
+    use super::*;
+
+    #[test]
+    fn should_enable_response_compression_by_default() {
+        assert!(State::default().is_response_compression_enabled());
+    }
+
+    #[test]
+    fn should_disable_response_compression() {
+        let mut state = State::default();
+
+        state.set_response_compression(ResponseCompression::Disabled);
+        assert!(!state.is_response_compression_enabled());
+
+        state.set_response_compression(ResponseCompression::Enabled);
+        assert!(state.is_response_compression_enabled());
+    }
+}
+
+mod provider_quotas_tests {
+    use super::*;
+
+    #[test]
+    fn should_set_provider_quotas() {
+        let quota = ProviderQuota {
+            max_requests: 100,
+            window_seconds: 60,
+        };
+        let mut state = State::default();
+
+        state.set_provider_quotas(vec![(SupportedRpcProviderId::HeliusMainnet, quota)]);
+        assert_eq!(
+            state.get_provider_quota(&SupportedRpcProviderId::HeliusMainnet),
+            Some(quota)
+        );
+        assert_eq!(
+            state.get_provider_quota(&SupportedRpcProviderId::AlchemyMainnet),
+            None
+        );
+
+        state.set_provider_quotas(vec![]);
+        assert_eq!(
+            state.get_provider_quota(&SupportedRpcProviderId::HeliusMainnet),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "maximum number of requests must be greater than 0")]
+    fn should_fail_for_empty_quota() {
+        State::default().set_provider_quotas(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            ProviderQuota {
+                max_requests: 0,
+                window_seconds: 60,
+            },
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "window must be greater than 0")]
+    fn should_fail_for_empty_window() {
+        State::default().set_provider_quotas(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            ProviderQuota {
+                max_requests: 100,
+                window_seconds: 0,
+            },
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate quota")]
+    fn should_fail_for_duplicate_quota() {
+        let quota = ProviderQuota {
+            max_requests: 100,
+            window_seconds: 60,
+        };
+        State::default().set_provider_quotas(vec![
+            (SupportedRpcProviderId::HeliusMainnet, quota),
+            (SupportedRpcProviderId::HeliusMainnet, quota),
+        ]);
+    }
+}
+
+mod retry_policies_tests {
+    use super::*;
+
+    #[test]
+    fn should_set_retry_policies() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            retry_on_transient_error: true,
+            retry_on_http_status: Some(vec![429, 503]),
+            initial_backoff_ms: None,
+        };
+        let mut state = State::default();
+
+        state.set_retry_policies(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            policy.clone(),
+        )]);
+        assert_eq!(
+            state.get_retry_policy(&SupportedRpcProviderId::HeliusMainnet),
+            Some(policy)
+        );
+        assert_eq!(
+            state.get_retry_policy(&SupportedRpcProviderId::AlchemyMainnet),
+            None
+        );
+
+        state.set_retry_policies(vec![]);
+        assert_eq!(
+            state.get_retry_policy(&SupportedRpcProviderId::HeliusMainnet),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "status 200 is not an error")]
+    fn should_fail_for_successful_status() {
+        State::default().set_retry_policies(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            RetryPolicy {
+                max_retries: 1,
+                retry_on_transient_error: false,
+                retry_on_http_status: Some(vec![429, 200]),
+                initial_backoff_ms: None,
+            },
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate retry policy")]
+    fn should_fail_for_duplicate_policy() {
+        let policy = RetryPolicy {
+            max_retries: 1,
+            retry_on_transient_error: true,
+            retry_on_http_status: Some(vec![]),
+            initial_backoff_ms: None,
+        };
+        State::default().set_retry_policies(vec![
+            (SupportedRpcProviderId::HeliusMainnet, policy.clone()),
+            (SupportedRpcProviderId::HeliusMainnet, policy),
+        ]);
+    }
+}
+
+mod provider_endpoints_tests {
+    use super::*;
+
+    #[test]
+    fn should_set_provider_endpoints() {
+        let endpoint = ProviderEndpoint {
+            access: RpcAccess::Authenticated {
+                auth: RpcAuth::UrlParameter {
+                    url_pattern: "https://ipv6.rpc.ankr.com/solana/{API_KEY}".to_string(),
+                },
+                public_url: None,
+            },
+            ip_version: IpVersion::Ipv6,
+        };
+        let mut state = State::default();
+
+        state.set_provider_endpoints(vec![(
+            SupportedRpcProviderId::AnkrMainnet,
+            endpoint.clone(),
+        )]);
+        assert_eq!(
+            state.get_provider_endpoint(&SupportedRpcProviderId::AnkrMainnet),
+            Some(endpoint)
+        );
+        assert_eq!(
+            state.get_provider_endpoint(&SupportedRpcProviderId::AnkrDevnet),
+            None
+        );
+
+        state.set_provider_endpoints(vec![]);
+        assert_eq!(
+            state.get_provider_endpoint(&SupportedRpcProviderId::AnkrMainnet),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "access must be of the same kind as the default access")]
+    fn should_fail_for_unauthenticated_endpoint_of_authenticated_provider() {
+        State::default().set_provider_endpoints(vec![(
+            SupportedRpcProviderId::AnkrMainnet,
+            unauthenticated_endpoint("https://ipv6.rpc.ankr.com/solana"),
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "URL must use HTTPS")]
+    fn should_fail_for_http_url() {
+        State::default().set_provider_endpoints(vec![(
+            SupportedRpcProviderId::PublicNodeMainnet,
+            unauthenticated_endpoint("http://solana-rpc.publicnode.com"),
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "placeholder must only be used in URL patterns")]
+    fn should_fail_for_url_pattern_without_api_key_placeholder() {
+        State::default().set_provider_endpoints(vec![(
+            SupportedRpcProviderId::AnkrMainnet,
+            ProviderEndpoint {
+                access: RpcAccess::Authenticated {
+                    auth: RpcAuth::UrlParameter {
+                        url_pattern: "https://ipv6.rpc.ankr.com/solana".to_string(),
+                    },
+                    public_url: None,
+                },
+                ip_version: IpVersion::Ipv6,
+            },
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate endpoint")]
+    fn should_fail_for_duplicate_endpoint() {
+        let endpoint = unauthenticated_endpoint("https://solana-rpc.publicnode.com");
+        State::default().set_provider_endpoints(vec![
+            (SupportedRpcProviderId::PublicNodeMainnet, endpoint.clone()),
+            (SupportedRpcProviderId::PublicNodeMainnet, endpoint),
+        ]);
+    }
+
+    fn unauthenticated_endpoint(url: &str) -> ProviderEndpoint {
+        ProviderEndpoint {
+            access: RpcAccess::Unauthenticated {
+                public_url: url.to_string(),
+            },
+            ip_version: IpVersion::DualStack,
+        }
+    }
+}
+
+mod slot_ticker_tests {
+    use super::*;
+
+    #[test]
+    fn should_disable_slot_ticker_by_default() {
+        assert_eq!(State::default().get_slot_ticker(), SlotTicker::Disabled);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval must be greater than 0")]
+    fn should_fail_for_empty_interval() {
+        State::default().set_slot_ticker(SlotTicker::Enabled {
+            interval_seconds: 0,
+            sources: RpcSources::Default(SolanaCluster::Mainnet),
+            commitment: None,
+        });
+    }
+}
+
+mod provider_usage_tests {
+    use super::*;
+    use crate::memory::{
+        rank_providers, record_ok_result, restore_provider_usage, save_provider_usage,
+        ProviderUsageSnapshot, PROVIDER_USAGE_SNAPSHOT_VERSION,
+    };
+    use canhttp::multi::Timestamp;
+    use ic_stable_structures::Storable;
+    use std::borrow::Cow;
+
+    const PROVIDERS: [SupportedRpcProviderId; 2] = [
+        SupportedRpcProviderId::AlchemyMainnet,
+        SupportedRpcProviderId::HeliusMainnet,
+    ];
+
+    #[test]
+    fn should_restore_saved_provider_usage() {
+        let now = Timestamp::UNIX_EPOCH;
+        record_ok_result(SupportedRpcProviderId::AlchemyMainnet, now);
+        record_ok_result(SupportedRpcProviderId::HeliusMainnet, now);
+        record_ok_result(SupportedRpcProviderId::HeliusMainnet, now);
+        let ranked = rank_providers(&PROVIDERS, now);
+        assert_eq!(
+            ranked,
+            vec![
+                SupportedRpcProviderId::HeliusMainnet,
+                SupportedRpcProviderId::AlchemyMainnet
+            ]
+        );
+
+        save_provider_usage();
+
+        assert_eq!(restore_provider_usage(), 3);
+        assert_eq!(rank_providers(&PROVIDERS, now), ranked);
+        assert_eq!(restore_provider_usage(), 0);
+    }
+
+    #[test]
+    fn should_discard_provider_usage_of_other_version() {
+        let snapshot = ProviderUsageSnapshot {
+            version: PROVIDER_USAGE_SNAPSHOT_VERSION + 1,
+            usages: BTreeMap::from([(SupportedRpcProviderId::AlchemyMainnet, vec![1, 2, 3])]),
+        };
+
+        assert_eq!(
+            ProviderUsageSnapshot::from_bytes(Cow::Owned(encode(&snapshot))),
+            ProviderUsageSnapshot::default()
+        );
+        assert_eq!(
+            ProviderUsageSnapshot::from_bytes(Cow::Borrowed(&[0xFF, 0x00])),
+            ProviderUsageSnapshot::default()
+        );
+    }
+}
+
+mod provider_scorecard_tests {
+    use super::*;
+    use crate::{
+        memory::{
+            get_provider_scorecard, record_provider_outcome, restore_provider_scores,
+            roll_up_provider_scores, save_provider_scores, MAX_PROVIDER_SCORECARDS,
+        },
+        scorecard::RequestOutcome,
+    };
+    use canhttp::multi::Timestamp;
+    use sol_rpc_types::{ProviderScore, ScorecardPeriod};
+
+    const PROVIDER: SupportedRpcProviderId = SupportedRpcProviderId::AlchemyMainnet;
+
+    #[test]
+    fn should_roll_up_scores_of_previous_period() {
+        let january = period(2025, 1);
+        let february = january.next();
+        record_provider_outcome(PROVIDER, RequestOutcome::Response, start(january));
+        record_provider_outcome(PROVIDER, RequestOutcome::Failure, start(january));
+        assert_eq!(roll_up_provider_scores(start(january)), None);
+
+        let scorecard = roll_up_provider_scores(start(february)).unwrap();
+
+        assert_eq!(scorecard.period, january);
+        assert_eq!(scorecard.scores, vec![score(2, 1, 1)]);
+        assert_eq!(get_provider_scorecard(None, start(february)), None);
+        assert_eq!(
+            get_provider_scorecard(Some(january), start(february)),
+            Some(scorecard)
+        );
+    }
+
+    #[test]
+    fn should_roll_up_scores_when_recording_outcome_of_next_period() {
+        let january = period(2025, 1);
+        let february = january.next();
+        record_provider_outcome(PROVIDER, RequestOutcome::Response, start(january));
+        record_provider_outcome(PROVIDER, RequestOutcome::Disagreement, start(february));
+
+        assert_eq!(
+            get_provider_scorecard(Some(january), start(february))
+                .unwrap()
+                .scores,
+            vec![score(1, 1, 0)]
+        );
+        let current = get_provider_scorecard(None, start(february)).unwrap();
+        assert_eq!(current.period, february);
+        assert_eq!(
+            current.scores,
+            vec![ProviderScore {
+                num_disagreements: 1,
+                ..score(1, 1, 0)
+            }]
+        );
+    }
+
+    #[test]
+    fn should_restore_saved_scores_of_current_period() {
+        let january = period(2025, 1);
+        record_provider_outcome(PROVIDER, RequestOutcome::Response, start(january));
+        let scorecard = get_provider_scorecard(None, start(january));
+
+        save_provider_scores();
+        reset_provider_scores();
+
+        assert_eq!(restore_provider_scores(start(january)), 1);
+        assert_eq!(get_provider_scorecard(None, start(january)), scorecard);
+        assert_eq!(restore_provider_scores(start(january)), 0);
+    }
+
+    #[test]
+    fn should_keep_saved_scores_of_previous_period() {
+        let january = period(2025, 1);
+        record_provider_outcome(PROVIDER, RequestOutcome::Response, start(january));
+        let scorecard = get_provider_scorecard(None, start(january));
+
+        save_provider_scores();
+        reset_provider_scores();
+
+        assert_eq!(restore_provider_scores(start(january.next())), 0);
+        assert_eq!(
+            get_provider_scorecard(Some(january), start(january.next())),
+            scorecard
+        );
+    }
+
+    #[test]
+    fn should_evict_oldest_scorecard() {
+        let first = period(2020, 1);
+        let mut current = first;
+        for _ in 0..=MAX_PROVIDER_SCORECARDS {
+            record_provider_outcome(PROVIDER, RequestOutcome::Response, start(current));
+            current = current.next();
+        }
+        roll_up_provider_scores(start(current));
+
+        assert_eq!(get_provider_scorecard(Some(first), start(current)), None);
+        assert!(get_provider_scorecard(Some(first.next()), start(current)).is_some());
+        assert!(get_provider_scorecard(Some(current.previous()), start(current)).is_some());
+    }
+
+    fn reset_provider_scores() {
+        crate::memory::UNSTABLE_PROVIDER_SCORES.set(None);
+    }
+
+    fn period(year: u16, month: u8) -> ScorecardPeriod {
+        ScorecardPeriod { year, month }
+    }
+
+    fn start(period: ScorecardPeriod) -> Timestamp {
+        Timestamp::from_nanos_since_unix_epoch(period.start())
+    }
+
+    fn score(num_requests: u64, num_responses: u64, num_failures: u64) -> ProviderScore {
+        ProviderScore {
+            num_requests,
+            num_responses,
+            num_failures,
+            ..ProviderScore::new(PROVIDER)
+        }
+    }
+}
+
+mod log_throttle_tests {
+    use super::*;
+
+    #[test]
+    fn should_not_throttle_logs_by_default() {
+        assert_eq!(
+            State::default().get_log_throttle(&LogPriority::TraceHttp),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "sample rate must be greater than 0")]
+    fn should_fail_for_zero_sample_rate() {
+        State::default().set_log_throttles(vec![(
+            LogPriority::TraceHttp,
+            LogThrottle {
+                sample_rate: Some(0),
+                rate_limit: None,
+            },
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "tokens per second must be greater than 0")]
+    fn should_fail_for_zero_tokens_per_second() {
+        State::default().set_log_throttles(vec![(
+            LogPriority::TraceHttp,
+            LogThrottle {
+                sample_rate: None,
+                rate_limit: Some(LogRateLimit {
+                    max_burst: 10,
+                    tokens_per_second: 0,
+                }),
+            },
+        )]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate log throttle")]
+    fn should_fail_for_duplicate_priority() {
+        State::default().set_log_throttles(vec![
+            (LogPriority::Debug, LogThrottle::default()),
+            (LogPriority::Debug, LogThrottle::default()),
+        ]);
+    }
+}
+
+mod log_target_tests {
+    use super::*;
+    use sol_rpc_types::LogTarget;
+
+    #[test]
+    fn should_enable_all_priorities_by_default() {
+        let state = State::default();
+
+        for priority in [
+            LogPriority::Info,
+            LogPriority::Debug,
+            LogPriority::TraceHttp,
+        ] {
+            assert!(state.is_log_target_enabled(&priority, "sol_rpc_canister::http"));
+        }
+    }
+
+    #[test]
+    fn should_use_most_specific_target() {
+        let mut state = State::default();
+        state.set_log_targets(vec![
+            LogTarget {
+                module: "http".to_string(),
+                priorities: vec![LogPriority::Info],
+            },
+            LogTarget {
+                module: "http::retry".to_string(),
+                priorities: vec![LogPriority::Info, LogPriority::Debug],
+            },
+            LogTarget {
+                module: "providers".to_string(),
+                priorities: vec![LogPriority::Info, LogPriority::Debug],
+            },
+        ]);
+
+        for (module_path, priority, expected) in [
+            ("sol_rpc_canister::http", LogPriority::Info, true),
+            ("sol_rpc_canister::http", LogPriority::TraceHttp, false),
+            (
+                "sol_rpc_canister::http::compression",
+                LogPriority::Debug,
+                false,
+            ),
+            ("sol_rpc_canister::http::retry", LogPriority::Debug, true),
+            (
+                "sol_rpc_canister::http::retry",
+                LogPriority::TraceHttp,
+                false,
+            ),
+            ("sol_rpc_canister::providers", LogPriority::Debug, true),
+            ("sol_rpc_canister::providers", LogPriority::TraceHttp, false),
+            ("sol_rpc_canister::httpx", LogPriority::TraceHttp, true),
+            ("sol_rpc_canister::memory", LogPriority::TraceHttp, true),
+            ("sol_rpc_canister", LogPriority::Debug, true),
+        ] {
+            assert_eq!(
+                state.is_log_target_enabled(&priority, module_path),
+                expected,
+                "Unexpected result for {priority:?} in {module_path}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "module must not be empty")]
+    fn should_fail_for_empty_module() {
+        State::default().set_log_targets(vec![LogTarget {
+            module: String::new(),
+            priorities: vec![],
+        }]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate log target")]
+    fn should_fail_for_duplicate_module() {
+        let target = LogTarget {
+            module: "http".to_string(),
+            priorities: vec![LogPriority::Info],
+        };
+        State::default().set_log_targets(vec![target.clone(), target]);
+    }
+}
+
+mod pricing_policy_tests {
+    use super::*;
+    use sol_rpc_types::{InstallArgs, RequestPriority};
+
+    const REQUEST_COST: u128 = 1_000_000_000;
+
+    #[test]
+    fn should_charge_request_cost_and_collateral_by_default() {
+        let state = State::from(InstallArgs::default());
+
+        assert_eq!(state.get_pricing_policy(), PricingPolicy::default());
+        assert_eq!(
+            state.cycles_to_charge(REQUEST_COST, RequestPriority::Normal),
+            REQUEST_COST + 34 * PricingPolicy::DEFAULT_COLLATERAL_CYCLES_PER_NODE
+        );
+        assert_eq!(
+            state.cycles_to_charge(REQUEST_COST, RequestPriority::Expedited),
+            2 * REQUEST_COST + 34 * PricingPolicy::DEFAULT_COLLATERAL_CYCLES_PER_NODE
+        );
+    }
+
+    #[test]
+    fn should_charge_according_to_pricing_policy() {
+        let mut state = State::from(InstallArgs::default());
+        state.set_num_subnet_nodes(13);
+        state.set_pricing_policy(PricingPolicy {
+            collateral_cycles_per_node: 1_000,
+            markup_percent: 25,
+            free_tier_max_cycles: 500_000_000,
+            expedited_markup_percent: Some(50),
+        });
+
+        assert_eq!(
+            state.cycles_to_charge(REQUEST_COST, RequestPriority::Normal),
+            REQUEST_COST + 250_000_000 + 13 * 1_000
+        );
+        assert_eq!(
+            state.cycles_to_charge(500_000_001, RequestPriority::Normal),
+            625_000_001 + 13 * 1_000
+        );
+        assert_eq!(
+            state.cycles_to_charge(REQUEST_COST, RequestPriority::Expedited),
+            REQUEST_COST + 750_000_000 + 13 * 1_000
+        );
+        assert_eq!(
+            state.cycles_to_charge(500_000_000, RequestPriority::Normal),
+            0
+        );
+        assert_eq!(
+            state.cycles_to_charge(500_000_000, RequestPriority::Expedited),
+            0
+        );
+        assert_eq!(state.cycles_to_charge(1, RequestPriority::Normal), 0);
+    }
+
+    #[test]
+    fn should_not_require_cycles_when_base_fee_is_free() {
+        let mut state = State::from(InstallArgs::default());
+        assert!(!state.is_base_http_outcall_fee_free());
+
+        let base_fee = state.lazy_compute_base_http_outcall_fee();
+        state.set_pricing_policy(PricingPolicy {
+            free_tier_max_cycles: base_fee,
+            ..PricingPolicy::default()
+        });
+        assert!(state.is_base_http_outcall_fee_free());
+    }
+
+    #[test]
+    #[should_panic(expected = "markup must be at most 1000%")]
+    fn should_fail_for_too_large_markup() {
+        State::default().set_pricing_policy(PricingPolicy {
+            markup_percent: 1_001,
+            ..PricingPolicy::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "expedited markup must be at most 1000%")]
+    fn should_fail_for_too_large_expedited_markup() {
+        State::default().set_pricing_policy(PricingPolicy {
+            expedited_markup_percent: Some(1_001),
+            ..PricingPolicy::default()
+        });
+    }
+}
+
+mod prepaid_cycles_tests {
+    use super::*;
+    use crate::memory::{
+        credit_prepaid_cycles, debit_prepaid_cycles, get_prepaid_cycles,
+        total_prepaid_cycles_debited,
+    };
+    use sol_rpc_types::InstallArgs;
+
+    #[test]
+    fn should_credit_and_debit_prepaid_cycles() {
+        let principal = Principal::from_slice(&[1; 29]);
+        let other_principal = Principal::from_slice(&[2]);
+        assert_eq!(get_prepaid_cycles(&principal), 0);
+
+        assert_eq!(credit_prepaid_cycles(principal, 1_000), 1_000);
+        assert_eq!(credit_prepaid_cycles(principal, 500), 1_500);
+        assert_eq!(get_prepaid_cycles(&other_principal), 0);
+
+        assert!(!debit_prepaid_cycles(principal, 1_501));
+        assert_eq!(get_prepaid_cycles(&principal), 1_500);
+        assert!(debit_prepaid_cycles(principal, 1_000));
+        assert_eq!(get_prepaid_cycles(&principal), 500);
+        assert!(debit_prepaid_cycles(principal, 500));
+        assert_eq!(get_prepaid_cycles(&principal), 0);
+        assert!(!debit_prepaid_cycles(other_principal, 1));
+
+        assert_eq!(total_prepaid_cycles_debited(&principal), 1_500);
+        assert_eq!(total_prepaid_cycles_debited(&other_principal), 0);
+    }
+
+    #[test]
+    fn should_use_mainnet_cycles_ledger_by_default() {
+        let state = State::from(InstallArgs::default());
+        assert_eq!(
+            state.get_cycles_ledger_id(),
+            Principal::from_text("um5iw-rqaaa-aaaaq-qaaba-cai").unwrap()
+        );
+
+        let cycles_ledger_id = Principal::from_slice(&[3]);
+        let state = State::from(InstallArgs {
+            cycles_ledger_id: Some(cycles_ledger_id),
+            ..Default::default()
+        });
+        assert_eq!(state.get_cycles_ledger_id(), cycles_ledger_id);
+    }
+}
+
+mod cycles_report_tests {
+    use super::*;
+    use crate::memory::{get_cycles_report, record_cycles_report, MAX_CYCLES_REPORTS};
+    use sol_rpc_types::CyclesReport;
+
+    #[test]
+    fn should_keep_most_recent_report_per_principal() {
+        let principal = Principal::from_slice(&[1; 29]);
+        assert_eq!(get_cycles_report(&principal), None);
+
+        record_cycles_report(principal, report(1, 1_000, 600));
+        record_cycles_report(principal, report(2, 2_000, 1_200));
+
+        let last_report = get_cycles_report(&principal).unwrap();
+        assert_eq!(last_report, report(2, 2_000, 1_200));
+        assert_eq!(last_report.refunded(), 800);
+        assert_eq!(get_cycles_report(&Principal::from_slice(&[2])), None);
+    }
+
+    #[test]
+    fn should_evict_oldest_report() {
+        let principal = |i: usize| Principal::from_slice(&i.to_be_bytes());
+        for i in 0..MAX_CYCLES_REPORTS {
+            // The first principal has the most recent report
+            let timestamp = if i == 0 { u64::MAX } else { i as u64 };
+            record_cycles_report(principal(i), report(timestamp, 1_000, 1_000));
+        }
+
+        record_cycles_report(principal(MAX_CYCLES_REPORTS), report(0, 1_000, 1_000));
+
+        assert!(get_cycles_report(&principal(0)).is_some());
+        assert_eq!(get_cycles_report(&principal(1)), None);
+        assert!(get_cycles_report(&principal(2)).is_some());
+        assert!(get_cycles_report(&principal(MAX_CYCLES_REPORTS)).is_some());
+    }
+
+    fn report(timestamp: u64, attached: u128, consumed: u128) -> CyclesReport {
+        CyclesReport {
+            timestamp,
+            attached,
+            consumed,
+            prepaid: 0,
+        }
+    }
+}
+
+mod state_validation_tests {
+    use super::*;
+    use crate::memory::STATE_SCHEMA_VERSION;
+    use sol_rpc_types::{InstallArgs, StateValidationIssue};
+
+    #[test]
+    fn should_write_current_schema_version_on_install() {
+        let state = State::from(InstallArgs::default());
+
+        assert_eq!(state.get_schema_version(), STATE_SCHEMA_VERSION);
+        assert_eq!(state.validate(), vec![]);
+    }
+
+    #[test]
+    fn should_migrate_state_without_schema_version() {
+        let mut state = State::default();
+        assert_eq!(state.get_schema_version(), 0);
+
+        state.migrate();
+
+        assert_eq!(state.get_schema_version(), STATE_SCHEMA_VERSION);
+        assert_eq!(
+            state,
+            State {
+                schema_version: STATE_SCHEMA_VERSION,
+                ..State::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_not_migrate_more_recent_state() {
+        let mut state = State {
+            schema_version: STATE_SCHEMA_VERSION + 1,
+            ..State::default()
+        };
+
+        state.migrate();
+
+        assert_eq!(state.get_schema_version(), STATE_SCHEMA_VERSION + 1);
+        assert_eq!(
+            state.validate(),
+            vec![StateValidationIssue::UnsupportedSchemaVersion {
+                stored: STATE_SCHEMA_VERSION + 1,
+                supported: STATE_SCHEMA_VERSION,
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_orphan_api_keys_and_settings() {
+        let mut state = State::default();
+        state.insert_api_key(
+            SupportedRpcProviderId::AlchemyMainnet,
+            ApiKey::try_from("alchemy-key".to_string()).unwrap(),
+        );
+        state.insert_api_key(
+            SupportedRpcProviderId::HeliusMainnet,
+            ApiKey::try_from("helius-key".to_string()).unwrap(),
+        );
+        state.set_provider_fallbacks(vec![
+            (
+                SupportedRpcProviderId::AlchemyMainnet,
+                vec![SupportedRpcProviderId::HeliusMainnet],
+            ),
+            (
+                SupportedRpcProviderId::AnkrMainnet,
+                vec![SupportedRpcProviderId::HeliusMainnet],
+            ),
+        ]);
+        state.set_retry_policies(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            RetryPolicy {
+                max_retries: 1,
+                retry_on_transient_error: true,
+                retry_on_http_status: Some(vec![]),
+                initial_backoff_ms: None,
+            },
+        )]);
+        state.set_request_id_formats(vec![(
+            SupportedRpcProviderId::HeliusMainnet,
+            RequestIdFormat::Number,
+        )]);
+
+        let issues =
+            state.validate_with(|provider| provider != &SupportedRpcProviderId::HeliusMainnet);
+
+        assert_eq!(
+            issues,
+            vec![
+                StateValidationIssue::OrphanApiKey {
+                    provider: SupportedRpcProviderId::HeliusMainnet
+                },
+                StateValidationIssue::OrphanProviderSetting {
+                    provider: SupportedRpcProviderId::HeliusMainnet,
+                    setting: "providerFallbacks".to_string(),
+                },
+                StateValidationIssue::OrphanProviderSetting {
+                    provider: SupportedRpcProviderId::HeliusMainnet,
+                    setting: "requestIdFormats".to_string(),
+                },
+                StateValidationIssue::OrphanProviderSetting {
+                    provider: SupportedRpcProviderId::HeliusMainnet,
+                    setting: "retryPolicies".to_string(),
+                },
+            ]
+        );
+        assert_eq!(state.validate(), vec![]);
+    }
+
+    #[test]
+    fn should_report_invalid_override_provider_regex() {
+        let mut state = State::default();
+        state.set_override_provider(OverrideProvider {
+            override_url: Some(RegexSubstitution {
+                pattern: RegexString::from("(.*"),
+                replacement: "http://localhost".to_string(),
+            }),
+        });
+
+        let issues = state.validate();
+
+        assert_matches::assert_matches!(
+            issues.as_slice(),
+            [StateValidationIssue::InvalidOverrideProviderRegex { pattern, .. }] if pattern == "(.*"
+        );
+    }
+}
+
+mod request_counter_tests {
+    use super::*;
+    use canhttp::http::json::{ConstantSizeId, Id};
+
+    #[test]
+    fn should_increment_request_id() {
+        for format in RequestIdFormat::iter() {
+            let request_ids = (0..10)
+                .map(|_| next_request_id(format).to_string())
+                .collect::<BTreeSet<_>>();
+            assert_eq!(request_ids.len(), 10);
+        }
+    }
+
+    #[test]
+    fn should_generate_request_id_with_format() {
+        let id = next_request_id(RequestIdFormat::Number);
+        let Id::Number(n) = id else {
+            panic!("Expected numeric ID, got {id:?}");
+        };
+
+        assert_eq!(
+            next_request_id(RequestIdFormat::ConstantSizeString),
+            Id::from(ConstantSizeId::from(n + 1))
+        );
+        assert_eq!(
+            next_request_id(RequestIdFormat::Uuid),
+            Id::String(format!("00000000-0000-4000-8000-{:012x}", n + 2))
+        );
+    }
+}
+
+mod upgrade_state_tests {
+    use super::*;
+    use crate::constants::VALID_API_KEY_CHARS;
+
+    proptest! {
+        #[test]
+        fn should_decode_state(state in arb_state()) {
+            let encoded = encode(&state);
+            let decoded = decode::<State>(encoded.as_slice());
+            assert_eq!(State::from(state), decoded);
+        }
+    }
+
+    #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+    #[serde(untagged)]
+    enum VersionedState {
+        V0 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+        },
+        // Added optional `base_http_outcall_fee` field
+        V1 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+        },
+        // Added `custom_api_keys` field
+        V2 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+        },
+        // Added `provider_fallbacks` field
+        V3 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+        },
+        // Added `allowed_callers` field
+        V4 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+        },
+        // Added `response_compression` field
+        V5 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+        },
+        // Added `provider_quotas` field
+        V6 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+        },
+        // Added `retry_policies` field
+        V7 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+        },
+        // Added `request_id_strategy` field
+        V8 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+        },
+        // Added `log_drain` field
+        V9 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+        },
+        // Added `outcall_concurrency` field
+        V10 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+        },
+        // Added `provider_endpoints` field
+        V11 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+        },
+        // Added `slot_ticker` field
+        V12 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+        },
+        // Added `schema_version` field
+        V13 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+        },
+        // Added `log_format` field
+        V14 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+        },
+        // Added `pricing_policy` field
+        V15 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+        },
+        // Added `cycles_ledger_id` field
+        V16 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+        },
+        // Added `log_throttles` field
+        V17 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+            log_throttles: BTreeMap<LogPriority, LogThrottle>,
+        },
+        // Added `request_id_formats` field
+        V18 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+            log_throttles: BTreeMap<LogPriority, LogThrottle>,
+            request_id_formats: BTreeMap<SupportedRpcProviderId, RequestIdFormat>,
+        },
+        // Added `log_targets` field
+        V19 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+            log_throttles: BTreeMap<LogPriority, LogThrottle>,
+            request_id_formats: BTreeMap<SupportedRpcProviderId, RequestIdFormat>,
+            log_targets: BTreeMap<String, BTreeSet<LogPriority>>,
+        },
+        // Added `shadow_providers` field
+        V20 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+            log_throttles: BTreeMap<LogPriority, LogThrottle>,
+            request_id_formats: BTreeMap<SupportedRpcProviderId, RequestIdFormat>,
+            log_targets: BTreeMap<String, BTreeSet<LogPriority>>,
+            shadow_providers: BTreeSet<SupportedRpcProviderId>,
+        },
+        // Added `version_sampler` field
+        V21 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+            log_throttles: BTreeMap<LogPriority, LogThrottle>,
+            request_id_formats: BTreeMap<SupportedRpcProviderId, RequestIdFormat>,
+            log_targets: BTreeMap<String, BTreeSet<LogPriority>>,
+            shadow_providers: BTreeSet<SupportedRpcProviderId>,
+            version_sampler: VersionSampler,
+        },
+        // Added `allowed_callers_exemptions` field
+        V22 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+            log_throttles: BTreeMap<LogPriority, LogThrottle>,
+            request_id_formats: BTreeMap<SupportedRpcProviderId, RequestIdFormat>,
+            log_targets: BTreeMap<String, BTreeSet<LogPriority>>,
+            shadow_providers: BTreeSet<SupportedRpcProviderId>,
+            version_sampler: VersionSampler,
+            allowed_callers_exemptions: Option<BTreeSet<String>>,
+        },
+        // Added `pending_deposits` field
+        V23 {
+            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
+            api_key_principals: Vec<Principal>,
+            override_provider: OverrideProvider,
+            log_filter: LogFilter,
+            mode: Mode,
+            num_subnet_nodes: u32,
+            base_http_outcall_fee: Option<u128>,
+            custom_api_keys: BTreeMap<String, ApiKey>,
+            provider_fallbacks: BTreeMap<SupportedRpcProviderId, Vec<SupportedRpcProviderId>>,
+            allowed_callers: AllowedCallers,
+            response_compression: ResponseCompression,
+            provider_quotas: BTreeMap<SupportedRpcProviderId, ProviderQuota>,
+            retry_policies: BTreeMap<SupportedRpcProviderId, RetryPolicy>,
+            request_id_strategy: RequestIdStrategy,
+            log_drain: LogDrain,
+            outcall_concurrency: OutcallConcurrency,
+            provider_endpoints: BTreeMap<SupportedRpcProviderId, ProviderEndpoint>,
+            slot_ticker: SlotTicker,
+            schema_version: u32,
+            log_format: LogFormat,
+            pricing_policy: PricingPolicy,
+            cycles_ledger_id: Option<Principal>,
+            log_throttles: BTreeMap<LogPriority, LogThrottle>,
+            request_id_formats: BTreeMap<SupportedRpcProviderId, RequestIdFormat>,
+            log_targets: BTreeMap<String, BTreeSet<LogPriority>>,
+            shadow_providers: BTreeSet<SupportedRpcProviderId>,
+            version_sampler: VersionSampler,
+            allowed_callers_exemptions: Option<BTreeSet<String>>,
+            pending_deposits: BTreeSet<PendingDeposit>,
+        },
+    }
+
+    impl From<VersionedState> for State {
+        fn from(state: VersionedState) -> State {
+            match state {
+                VersionedState::V0 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee: None,
+                    custom_api_keys: Default::default(),
+                    provider_fallbacks: Default::default(),
+                    allowed_callers: Default::default(),
+                    response_compression: ResponseCompression::Disabled,
+                    provider_quotas: Default::default(),
+                    retry_policies: Default::default(),
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V1 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys: Default::default(),
+                    provider_fallbacks: Default::default(),
+                    allowed_callers: Default::default(),
+                    response_compression: ResponseCompression::Disabled,
+                    provider_quotas: Default::default(),
+                    retry_policies: Default::default(),
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V2 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks: Default::default(),
+                    allowed_callers: Default::default(),
+                    response_compression: ResponseCompression::Disabled,
+                    provider_quotas: Default::default(),
+                    retry_policies: Default::default(),
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V3 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers: Default::default(),
+                    response_compression: ResponseCompression::Disabled,
+                    provider_quotas: Default::default(),
+                    retry_policies: Default::default(),
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V4 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression: ResponseCompression::Disabled,
+                    provider_quotas: Default::default(),
+                    retry_policies: Default::default(),
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V5 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas: Default::default(),
+                    retry_policies: Default::default(),
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V6 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies: Default::default(),
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V7 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy: Default::default(),
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V8 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain: Default::default(),
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V9 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency: Default::default(),
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V10 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints: Default::default(),
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V11 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker: Default::default(),
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V12 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version: Default::default(),
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V13 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format: Default::default(),
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V14 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy: Default::default(),
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V15 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id: Default::default(),
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V16 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles: Default::default(),
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V17 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats: Default::default(),
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V18 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets: Default::default(),
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V19 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers: Default::default(),
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V20 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler: Default::default(),
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V21 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions: Default::default(),
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V22 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions,
+                    pending_deposits: Default::default(),
+                },
+                VersionedState::V23 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions,
+                    pending_deposits,
+                } => Self {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions,
+                    pending_deposits,
+                },
+            }
+        }
+    }
+
+    fn arb_state() -> impl Strategy<Value = VersionedState> {
+        prop_oneof![
+            arb_state_v0(),
+            arb_state_v1(),
+            arb_state_v2(),
+            arb_state_v3(),
+            arb_state_v4(),
+            arb_state_v5(),
+            arb_state_v6(),
+            arb_state_v7(),
+            arb_state_v8(),
+            arb_state_v9(),
+            arb_state_v10(),
+            arb_state_v11(),
+            arb_state_v12(),
+            arb_state_v13(),
+            arb_state_v14(),
+            arb_state_v15(),
+            arb_state_v16(),
+            arb_state_v17(),
+            arb_state_v18(),
+            arb_state_v19(),
+            arb_state_v20(),
+            arb_state_v21(),
+            arb_state_v22(),
+            arb_state_v23()
+        ]
+    }
+
+    fn arb_state_v0() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_api_keys(),
+            arb_api_key_principals(),
+            arb_override_provider(),
+            arb_log_filter(),
+            arb_mode(),
+            any::<u32>(),
+        )
+            .prop_map(
+                |(
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                )| VersionedState::V0 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                },
+            )
+    }
+
+    fn arb_state_v1() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_api_keys(),
+            arb_api_key_principals(),
+            arb_override_provider(),
+            arb_log_filter(),
+            arb_mode(),
+            any::<u32>(),
+            proptest::option::of(any::<u128>()),
+        )
+            .prop_map(
+                |(
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                )| VersionedState::V1 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                },
+            )
+    }
+
+    fn arb_state_v2() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_api_keys(),
+            arb_api_key_principals(),
+            arb_override_provider(),
+            arb_log_filter(),
+            arb_mode(),
+            any::<u32>(),
+            proptest::option::of(any::<u128>()),
+            arb_custom_api_keys(),
+        )
+            .prop_map(
+                |(
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                )| VersionedState::V2 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                },
+            )
+    }
+
+    fn arb_state_v3() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_state_v2(),
+            prop::collection::btree_map(
+                arb_provider(),
+                prop::collection::vec(arb_provider(), 0..3),
+                0..3,
+            ),
+        )
+            .prop_map(|(state, provider_fallbacks)| match state {
+                VersionedState::V2 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                } => VersionedState::V3 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                },
+                _ => unreachable!(),
+            })
+    }
+
+    fn arb_state_v4() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v3(), arb_allowed_callers()).prop_map(|(state, allowed_callers)| match state {
+            VersionedState::V3 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+            } => VersionedState::V4 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+            },
+            _ => unreachable!(),
+        })
+    }
+
+    fn arb_state_v5() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v4(), arb_response_compression()).prop_map(|(state, response_compression)| {
+            match state {
+                VersionedState::V4 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                } => VersionedState::V5 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                },
+                _ => unreachable!(),
+            }
+        })
+    }
+
+    fn arb_state_v6() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_state_v5(),
+            prop::collection::btree_map(arb_provider(), arb_provider_quota(), 0..3),
+        )
+            .prop_map(|(state, provider_quotas)| match state {
+                VersionedState::V5 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                } => VersionedState::V6 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                },
+                _ => unreachable!(),
+            })
+    }
+
+    fn arb_state_v7() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_state_v6(),
+            prop::collection::btree_map(arb_provider(), arb_retry_policy(), 0..3),
+        )
+            .prop_map(|(state, retry_policies)| match state {
+                VersionedState::V6 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                } => VersionedState::V7 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                },
+                _ => unreachable!(),
+            })
+    }
+
+    fn arb_state_v8() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v7(), arb_request_id_strategy()).prop_map(|(state, request_id_strategy)| {
+            match state {
+                VersionedState::V7 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                } => VersionedState::V8 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                },
+                _ => unreachable!(),
+            }
+        })
     }
 
-    fn set_api_key_principals(new_principals: Vec<Principal>) {
-        mutate_state(|state| state.set_api_key_principals(new_principals));
+    fn arb_state_v9() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v8(), arb_log_drain()).prop_map(|(state, log_drain)| match state {
+            VersionedState::V8 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+            } => VersionedState::V9 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+            },
+            _ => unreachable!(),
+        })
     }
 
-    fn is_api_key_principal(principal: &Principal) -> bool {
-        read_state(|state| state.is_api_key_principal(principal))
+    fn arb_state_v10() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v9(), arb_outcall_concurrency()).prop_map(|(state, outcall_concurrency)| {
+            match state {
+                VersionedState::V9 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                } => VersionedState::V10 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                },
+                _ => unreachable!(),
+            }
+        })
     }
-}
 
-mod request_counter_tests {
-    use super::*;
+    fn arb_state_v11() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_state_v10(),
+            prop::collection::btree_map(arb_provider(), arb_provider_endpoint(), 0..3),
+        )
+            .prop_map(|(state, provider_endpoints)| match state {
+                VersionedState::V10 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                } => VersionedState::V11 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                },
+                _ => unreachable!(),
+            })
+    }
 
-    #[test]
-    fn should_increment_request_id() {
-        let request_ids = (0..10)
-            .map(|_| next_request_id().to_string())
-            .collect::<BTreeSet<_>>();
-        assert_eq!(request_ids.len(), 10);
+    fn arb_state_v12() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v11(), arb_slot_ticker()).prop_map(|(state, slot_ticker)| match state {
+            VersionedState::V11 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+            } => VersionedState::V12 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+            },
+            _ => unreachable!(),
+        })
     }
-}
 
-mod upgrade_state_tests {
-    use super::*;
-    use crate::constants::VALID_API_KEY_CHARS;
+    fn arb_state_v13() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v12(), any::<u32>()).prop_map(|(state, schema_version)| match state {
+            VersionedState::V12 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+            } => VersionedState::V13 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+            },
+            _ => unreachable!(),
+        })
+    }
 
-    proptest! {
-        #[test]
-        fn should_decode_state(state in arb_state()) {
-            let encoded = encode(&state);
-            let decoded = decode::<State>(encoded.as_slice());
-            assert_eq!(State::from(state), decoded);
-        }
+    fn arb_state_v14() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_state_v13(),
+            prop_oneof![Just(LogFormat::Text), Just(LogFormat::Json)],
+        )
+            .prop_map(|(state, log_format)| match state {
+                VersionedState::V13 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                } => VersionedState::V14 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                },
+                _ => unreachable!(),
+            })
     }
 
-    #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
-    #[serde(untagged)]
-    enum VersionedState {
-        V0 {
-            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
-            api_key_principals: Vec<Principal>,
-            override_provider: OverrideProvider,
-            log_filter: LogFilter,
-            mode: Mode,
-            num_subnet_nodes: u32,
-        },
-        // Added optional `base_http_outcall_fee` field
-        V1 {
-            api_keys: BTreeMap<SupportedRpcProviderId, ApiKey>,
-            api_key_principals: Vec<Principal>,
-            override_provider: OverrideProvider,
-            log_filter: LogFilter,
-            mode: Mode,
-            num_subnet_nodes: u32,
-            base_http_outcall_fee: Option<u128>,
-        },
+    fn arb_state_v15() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v14(), arb_pricing_policy()).prop_map(|(state, pricing_policy)| match state {
+            VersionedState::V14 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+            } => VersionedState::V15 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+                pricing_policy,
+            },
+            _ => unreachable!(),
+        })
     }
 
-    impl From<VersionedState> for State {
-        fn from(state: VersionedState) -> State {
-            match state {
-                VersionedState::V0 {
+    fn arb_state_v16() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v15(), proptest::option::of(arb_principal())).prop_map(
+            |(state, cycles_ledger_id)| match state {
+                VersionedState::V15 {
                     api_keys,
                     api_key_principals,
                     override_provider,
                     log_filter,
                     mode,
                     num_subnet_nodes,
-                } => Self {
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                } => VersionedState::V16 {
                     api_keys,
                     api_key_principals,
                     override_provider,
                     log_filter,
                     mode,
                     num_subnet_nodes,
-                    base_http_outcall_fee: None,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
                 },
-                VersionedState::V1 {
+                _ => unreachable!(),
+            },
+        )
+    }
+
+    fn arb_state_v17() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v16(), arb_log_throttles()).prop_map(|(state, log_throttles)| match state {
+            VersionedState::V16 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+                pricing_policy,
+                cycles_ledger_id,
+            } => VersionedState::V17 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+                pricing_policy,
+                cycles_ledger_id,
+                log_throttles,
+            },
+            _ => unreachable!(),
+        })
+    }
+
+    fn arb_state_v18() -> impl Strategy<Value = VersionedState> {
+        (
+            arb_state_v17(),
+            prop::collection::btree_map(arb_provider(), arb_request_id_format(), 0..3),
+        )
+            .prop_map(|(state, request_id_formats)| match state {
+                VersionedState::V17 {
                     api_keys,
                     api_key_principals,
                     override_provider,
@@ -131,7 +3556,23 @@ mod upgrade_state_tests {
                     mode,
                     num_subnet_nodes,
                     base_http_outcall_fee,
-                } => Self {
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                } => VersionedState::V18 {
                     api_keys,
                     api_key_principals,
                     override_provider,
@@ -139,55 +3580,219 @@ mod upgrade_state_tests {
                     mode,
                     num_subnet_nodes,
                     base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
                 },
-            }
-        }
+                _ => unreachable!(),
+            })
     }
 
-    fn arb_state() -> impl Strategy<Value = VersionedState> {
-        prop_oneof![arb_state_v0(), arb_state_v1()]
+    fn arb_state_v19() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v18(), arb_log_targets()).prop_map(|(state, log_targets)| match state {
+            VersionedState::V18 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+                pricing_policy,
+                cycles_ledger_id,
+                log_throttles,
+                request_id_formats,
+            } => VersionedState::V19 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+                pricing_policy,
+                cycles_ledger_id,
+                log_throttles,
+                request_id_formats,
+                log_targets,
+            },
+            _ => unreachable!(),
+        })
     }
 
-    fn arb_state_v0() -> impl Strategy<Value = VersionedState> {
+    fn arb_state_v20() -> impl Strategy<Value = VersionedState> {
         (
-            arb_api_keys(),
-            arb_api_key_principals(),
-            arb_override_provider(),
-            arb_log_filter(),
-            arb_mode(),
-            any::<u32>(),
+            arb_state_v19(),
+            prop::collection::btree_set(arb_provider(), 0..3),
         )
-            .prop_map(
-                |(
+            .prop_map(|(state, shadow_providers)| match state {
+                VersionedState::V19 {
                     api_keys,
                     api_key_principals,
                     override_provider,
                     log_filter,
                     mode,
                     num_subnet_nodes,
-                )| VersionedState::V0 {
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                } => VersionedState::V20 {
                     api_keys,
                     api_key_principals,
                     override_provider,
                     log_filter,
                     mode,
                     num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
                 },
-            )
+                _ => unreachable!(),
+            })
     }
 
-    fn arb_state_v1() -> impl Strategy<Value = VersionedState> {
+    fn arb_state_v21() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v20(), arb_version_sampler()).prop_map(|(state, version_sampler)| match state {
+            VersionedState::V20 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+                pricing_policy,
+                cycles_ledger_id,
+                log_throttles,
+                request_id_formats,
+                log_targets,
+                shadow_providers,
+            } => VersionedState::V21 {
+                api_keys,
+                api_key_principals,
+                override_provider,
+                log_filter,
+                mode,
+                num_subnet_nodes,
+                base_http_outcall_fee,
+                custom_api_keys,
+                provider_fallbacks,
+                allowed_callers,
+                response_compression,
+                provider_quotas,
+                retry_policies,
+                request_id_strategy,
+                log_drain,
+                outcall_concurrency,
+                provider_endpoints,
+                slot_ticker,
+                schema_version,
+                log_format,
+                pricing_policy,
+                cycles_ledger_id,
+                log_throttles,
+                request_id_formats,
+                log_targets,
+                shadow_providers,
+                version_sampler,
+            },
+            _ => unreachable!(),
+        })
+    }
+
+    fn arb_state_v22() -> impl Strategy<Value = VersionedState> {
         (
-            arb_api_keys(),
-            arb_api_key_principals(),
-            arb_override_provider(),
-            arb_log_filter(),
-            arb_mode(),
-            any::<u32>(),
-            proptest::option::of(any::<u128>()),
+            arb_state_v21(),
+            proptest::option::of(prop::collection::btree_set("[a-zA-Z]{1,30}", 0..5)),
         )
-            .prop_map(
-                |(
+            .prop_map(|(state, allowed_callers_exemptions)| match state {
+                VersionedState::V21 {
                     api_keys,
                     api_key_principals,
                     override_provider,
@@ -195,7 +3800,93 @@ mod upgrade_state_tests {
                     mode,
                     num_subnet_nodes,
                     base_http_outcall_fee,
-                )| VersionedState::V1 {
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                } => VersionedState::V22 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions,
+                },
+                _ => unreachable!(),
+            })
+    }
+
+    fn arb_state_v23() -> impl Strategy<Value = VersionedState> {
+        (arb_state_v22(), arb_pending_deposits()).prop_map(
+            |(state, pending_deposits)| match state {
+                VersionedState::V22 {
+                    api_keys,
+                    api_key_principals,
+                    override_provider,
+                    log_filter,
+                    mode,
+                    num_subnet_nodes,
+                    base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions,
+                } => VersionedState::V23 {
                     api_keys,
                     api_key_principals,
                     override_provider,
@@ -203,10 +3894,230 @@ mod upgrade_state_tests {
                     mode,
                     num_subnet_nodes,
                     base_http_outcall_fee,
+                    custom_api_keys,
+                    provider_fallbacks,
+                    allowed_callers,
+                    response_compression,
+                    provider_quotas,
+                    retry_policies,
+                    request_id_strategy,
+                    log_drain,
+                    outcall_concurrency,
+                    provider_endpoints,
+                    slot_ticker,
+                    schema_version,
+                    log_format,
+                    pricing_policy,
+                    cycles_ledger_id,
+                    log_throttles,
+                    request_id_formats,
+                    log_targets,
+                    shadow_providers,
+                    version_sampler,
+                    allowed_callers_exemptions,
+                    pending_deposits,
+                },
+                _ => unreachable!(),
+            },
+        )
+    }
+
+    fn arb_slot_ticker() -> impl Strategy<Value = SlotTicker> {
+        prop_oneof![
+            Just(SlotTicker::Disabled),
+            (
+                1..=3_600_u32,
+                prop::collection::vec(arb_provider(), 1..4),
+                proptest::option::of(prop_oneof![
+                    Just(CommitmentLevel::Processed),
+                    Just(CommitmentLevel::Confirmed),
+                    Just(CommitmentLevel::Finalized),
+                ]),
+            )
+                .prop_map(|(interval_seconds, providers, commitment)| {
+                    SlotTicker::Enabled {
+                        interval_seconds,
+                        sources: RpcSources::Custom(
+                            providers.into_iter().map(RpcSource::Supported).collect(),
+                        ),
+                        commitment,
+                    }
+                }),
+        ]
+    }
+
+    fn arb_version_sampler() -> impl Strategy<Value = VersionSampler> {
+        prop_oneof![
+            Just(VersionSampler::Disabled),
+            (
+                1..=3_600_u32,
+                prop::collection::vec(
+                    prop_oneof![Just(SolanaCluster::Mainnet), Just(SolanaCluster::Devnet)],
+                    0..3
+                ),
+                proptest::option::of("[0-9]{1,2}\\.[0-9]{1,2}\\.[0-9]{1,2}"),
+            )
+                .prop_map(|(interval_seconds, clusters, min_version)| {
+                    VersionSampler::Enabled {
+                        interval_seconds,
+                        clusters,
+                        min_version,
+                    }
+                }),
+        ]
+    }
+
+    fn arb_log_targets() -> impl Strategy<Value = BTreeMap<String, BTreeSet<LogPriority>>> {
+        prop::collection::btree_map(
+            "[a-z_]{1,10}(::[a-z_]{1,10}){0,2}",
+            prop::collection::btree_set(
+                prop_oneof![
+                    Just(LogPriority::Info),
+                    Just(LogPriority::Debug),
+                    Just(LogPriority::TraceHttp),
+                ],
+                0..3,
+            ),
+            0..3,
+        )
+    }
+
+    fn arb_log_throttles() -> impl Strategy<Value = BTreeMap<LogPriority, LogThrottle>> {
+        prop::collection::btree_map(
+            prop_oneof![
+                Just(LogPriority::Info),
+                Just(LogPriority::Debug),
+                Just(LogPriority::TraceHttp),
+            ],
+            (
+                proptest::option::of(1..=1_000_u32),
+                proptest::option::of((1..=1_000_u32, 1..=1_000_u32).prop_map(
+                    |(max_burst, tokens_per_second)| LogRateLimit {
+                        max_burst,
+                        tokens_per_second,
+                    },
+                )),
+            )
+                .prop_map(|(sample_rate, rate_limit)| LogThrottle {
+                    sample_rate,
+                    rate_limit,
+                }),
+            0..3,
+        )
+    }
+
+    fn arb_provider_endpoint() -> impl Strategy<Value = ProviderEndpoint> {
+        (
+            "https://[a-z0-9.-]{1,20}"
+                .prop_map(|public_url| RpcAccess::Unauthenticated { public_url }),
+            prop::sample::select(IpVersion::iter().collect::<Vec<_>>()),
+        )
+            .prop_map(|(access, ip_version)| ProviderEndpoint { access, ip_version })
+    }
+
+    fn arb_pricing_policy() -> impl Strategy<Value = PricingPolicy> {
+        (
+            any::<u128>(),
+            0..=PricingPolicy::MAX_MARKUP_PERCENT,
+            any::<u128>(),
+            proptest::option::of(0..=PricingPolicy::MAX_MARKUP_PERCENT),
+        )
+            .prop_map(
+                |(
+                    collateral_cycles_per_node,
+                    markup_percent,
+                    free_tier_max_cycles,
+                    expedited_markup_percent,
+                )| {
+                    PricingPolicy {
+                        collateral_cycles_per_node,
+                        markup_percent,
+                        free_tier_max_cycles,
+                        expedited_markup_percent,
+                    }
+                },
+            )
+    }
+
+    fn arb_outcall_concurrency() -> impl Strategy<Value = OutcallConcurrency> {
+        prop_oneof![
+            Just(OutcallConcurrency::Unlimited),
+            (1..=100_u32, any::<u32>(), any::<u32>()).prop_map(
+                |(max_in_flight, max_queue_length, queue_timeout_seconds)| {
+                    OutcallConcurrency::Limited {
+                        max_in_flight,
+                        max_queue_length,
+                        queue_timeout_seconds,
+                    }
+                }
+            ),
+        ]
+    }
+
+    fn arb_log_drain() -> impl Strategy<Value = LogDrain> {
+        prop_oneof![
+            Just(LogDrain::Disabled),
+            (arb_principal(), "[a-z_]{1,20}").prop_map(|(canister_id, method)| LogDrain::Enabled {
+                canister_id,
+                method
+            }),
+        ]
+    }
+
+    fn arb_request_id_strategy() -> impl Strategy<Value = RequestIdStrategy> {
+        prop::sample::select(RequestIdStrategy::iter().collect::<Vec<_>>())
+    }
+
+    fn arb_request_id_format() -> impl Strategy<Value = RequestIdFormat> {
+        prop::sample::select(RequestIdFormat::iter().collect::<Vec<_>>())
+    }
+
+    fn arb_retry_policy() -> impl Strategy<Value = RetryPolicy> {
+        (
+            any::<u8>(),
+            any::<bool>(),
+            proptest::option::of(prop::collection::vec(400_u16..600, 0..3)),
+            proptest::option::of(any::<u64>()),
+        )
+            .prop_map(
+                |(
+                    max_retries,
+                    retry_on_transient_error,
+                    retry_on_http_status,
+                    initial_backoff_ms,
+                )| {
+                    RetryPolicy {
+                        max_retries,
+                        retry_on_transient_error,
+                        retry_on_http_status,
+                        initial_backoff_ms,
+                    }
                 },
             )
     }
 
+    fn arb_provider_quota() -> impl Strategy<Value = ProviderQuota> {
+        (1..=u32::MAX, 1..=u32::MAX).prop_map(|(max_requests, window_seconds)| ProviderQuota {
+            max_requests,
+            window_seconds,
+        })
+    }
+
+    fn arb_response_compression() -> impl Strategy<Value = ResponseCompression> {
+        prop::sample::select(ResponseCompression::iter().collect::<Vec<_>>())
+    }
+
+    fn arb_allowed_callers() -> impl Strategy<Value = AllowedCallers> {
+        prop_oneof![
+            Just(AllowedCallers::Any),
+            prop::collection::vec(arb_principal(), 0..5).prop_map(AllowedCallers::Only),
+        ]
+    }
+
+    fn arb_provider() -> impl Strategy<Value = SupportedRpcProviderId> {
+        prop::sample::select(SupportedRpcProviderId::iter().collect::<Vec<_>>())
+    }
+
     fn arb_mode() -> impl Strategy<Value = Mode> {
         prop::sample::select(Mode::iter().collect::<Vec<_>>())
     }
@@ -215,6 +4126,26 @@ mod upgrade_state_tests {
         prop::collection::vec(arb_principal(), 0..10)
     }
 
+    fn arb_pending_deposits() -> impl Strategy<Value = BTreeSet<PendingDeposit>> {
+        prop::collection::btree_set(
+            (
+                arb_principal(),
+                prop::option::of(any::<[u8; 32]>()),
+                any::<u128>(),
+                any::<u64>(),
+            )
+                .prop_map(|(caller, from_subaccount, amount, created_at_time)| {
+                    PendingDeposit {
+                        caller,
+                        from_subaccount,
+                        amount,
+                        created_at_time,
+                    }
+                }),
+            0..3,
+        )
+    }
+
     fn arb_api_keys() -> impl Strategy<Value = BTreeMap<SupportedRpcProviderId, ApiKey>> {
         prop::collection::btree_map(
             prop::sample::select(SupportedRpcProviderId::iter().collect::<Vec<_>>()),
@@ -223,6 +4154,10 @@ mod upgrade_state_tests {
         )
     }
 
+    fn arb_custom_api_keys() -> impl Strategy<Value = BTreeMap<String, ApiKey>> {
+        prop::collection::btree_map("[a-z0-9.-]{1,20}", arb_api_key(), 0..5)
+    }
+
     pub fn arb_api_key() -> impl Strategy<Value = ApiKey> {
         proptest::collection::vec(
             prop::sample::select(VALID_API_KEY_CHARS.chars().collect::<Vec<_>>()),
@@ -1,8 +1,15 @@
+use crate::{
+    http::concurrency::{num_outcalls_in_flight, num_queued_outcalls},
+    memory::read_state,
+    shadow::ShadowOutcome,
+};
 use derive_more::From;
+use sol_rpc_types::{LogPriority, OutcallShedReason, SolanaCluster, SupportedRpcProviderId};
 use std::{collections::BTreeMap, time::Duration};
 
 pub const BUCKETS_DEFAULT_MS: [u64; 8] =
     [1_000, 2_000, 4_000, 6_000, 8_000, 12_000, 20_000, u64::MAX];
+pub const BUCKETS_SLOT_SKEW: [u64; 8] = [0, 10, 20, 50, 100, 200, 500, u64::MAX];
 
 #[macro_export]
 macro_rules! add_metric {
@@ -55,6 +62,17 @@ impl LatencyHistogram {
     }
 }
 
+/// Spread of the slots returned by the providers for `getSlot` requests, see
+/// [`crate::rpc_client::diagnostics::slot_skew`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SlotSkewHistogram(pub Histogram<8>);
+
+impl Default for SlotSkewHistogram {
+    fn default() -> Self {
+        Self(Histogram::new(&BUCKETS_SLOT_SKEW))
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Histogram<const NUM_BUCKETS: usize> {
     bucket_upper_bounds: &'static [u64; NUM_BUCKETS],
@@ -212,6 +230,9 @@ impl MetricLabels for MetricRpcErrorCode {
 pub enum MetricRpcCallResponse {
     Success,
     IcError(String),
+    /// The HTTPS outcall was rejected because the host could not be connected to, see
+    /// [`crate::http::errors::is_connection_error`].
+    IcTransportError(String),
     HttpError(MetricHttpStatusCode),
     JsonRpcError,
     MaxResponseSizeExceeded,
@@ -225,6 +246,12 @@ impl MetricLabels for MetricRpcCallResponse {
                 .into_iter()
                 .chain(vec![("code", rejection_code.as_str())])
                 .collect(),
+            MetricRpcCallResponse::IcTransportError(rejection_code) => {
+                [("error", "ic"), ("class", "transport")]
+                    .into_iter()
+                    .chain(vec![("code", rejection_code.as_str())])
+                    .collect()
+            }
             MetricRpcCallResponse::HttpError(status) => [("error", "http")]
                 .into_iter()
                 .chain(status.metric_labels())
@@ -237,12 +264,153 @@ impl MetricLabels for MetricRpcCallResponse {
     }
 }
 
+/// Reason why an HTTPS outcall was retried.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MetricRetryReason {
+    MaxResponseSizeExceeded,
+    TransientError,
+    HttpError(MetricHttpStatusCode),
+}
+
+impl MetricLabels for MetricRetryReason {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        match self {
+            MetricRetryReason::MaxResponseSizeExceeded => {
+                vec![("reason", "max-response-size-exceeded")]
+            }
+            MetricRetryReason::TransientError => vec![("reason", "transient-error")],
+            MetricRetryReason::HttpError(status) => [("reason", "http")]
+                .into_iter()
+                .chain(status.metric_labels())
+                .collect(),
+        }
+    }
+}
+
+/// Reason why a log entry was not recorded, see [`sol_rpc_types::LogThrottle`].
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MetricLogSuppressionReason {
+    Sampling,
+    RateLimit,
+}
+
+impl MetricLabels for MetricLogSuppressionReason {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        match self {
+            MetricLogSuppressionReason::Sampling => vec![("reason", "sampling")],
+            MetricLogSuppressionReason::RateLimit => vec![("reason", "rate-limit")],
+        }
+    }
+}
+
+impl MetricLabels for LogPriority {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        match self {
+            LogPriority::Info => vec![("priority", "INFO")],
+            LogPriority::Debug => vec![("priority", "DEBUG")],
+            LogPriority::TraceHttp => vec![("priority", "TRACE_HTTP")],
+        }
+    }
+}
+
+impl MetricLabels for ShadowOutcome {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        match self {
+            ShadowOutcome::Agreement => vec![("outcome", "agreement")],
+            ShadowOutcome::Disagreement => vec![("outcome", "disagreement")],
+            ShadowOutcome::Failure => vec![("outcome", "failure")],
+            ShadowOutcome::Uncompared => vec![("outcome", "uncompared")],
+        }
+    }
+}
+
+impl MetricLabels for OutcallShedReason {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        match self {
+            OutcallShedReason::QueueFull => vec![("reason", "queue-full")],
+            OutcallShedReason::QueueTimeout => vec![("reason", "queue-timeout")],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, From)]
+pub struct MetricRpcProvider(pub String);
+
+impl From<SupportedRpcProviderId> for MetricRpcProvider {
+    fn from(provider: SupportedRpcProviderId) -> Self {
+        MetricRpcProvider(provider.to_string())
+    }
+}
+
+impl MetricLabels for MetricRpcProvider {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        vec![("provider", &self.0)]
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, From)]
+pub struct MetricSolanaCluster(pub String);
+
+impl From<SolanaCluster> for MetricSolanaCluster {
+    fn from(cluster: SolanaCluster) -> Self {
+        MetricSolanaCluster(cluster.to_string().to_lowercase())
+    }
+}
+
+impl MetricLabels for MetricSolanaCluster {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        vec![("cluster", &self.0)]
+    }
+}
+
+/// Suspected cause of inconsistent responses from RPC providers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MetricInconsistencyCause {
+    /// Responses only differ by slot values, e.g. because providers are not at the same slot.
+    SlotSkew,
+    /// Some providers returned an error while others did not, or returned different errors.
+    ProviderError,
+    /// Responses differ in other ways.
+    PayloadMismatch,
+}
+
+impl MetricInconsistencyCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricInconsistencyCause::SlotSkew => "slot_skew",
+            MetricInconsistencyCause::ProviderError => "provider_error",
+            MetricInconsistencyCause::PayloadMismatch => "payload_mismatch",
+        }
+    }
+}
+
+impl MetricLabels for MetricInconsistencyCause {
+    fn metric_labels(&self) -> Vec<(&str, &str)> {
+        vec![("cause", self.as_str())]
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Metrics {
     pub requests: BTreeMap<(MetricRpcMethod, MetricRpcHost), u64>,
     pub responses: BTreeMap<(MetricRpcMethod, MetricRpcHost, MetricRpcCallResponse), u64>,
-    pub inconsistent_responses: BTreeMap<(MetricRpcMethod, MetricRpcHost), u64>,
+    pub inconsistent_responses:
+        BTreeMap<(MetricRpcMethod, MetricRpcHost, MetricInconsistencyCause), u64>,
     pub latencies: BTreeMap<(MetricRpcMethod, MetricRpcHost), LatencyHistogram>,
+    pub cycles_received: BTreeMap<MetricRpcMethod, u128>,
+    pub cycles_charged: BTreeMap<MetricRpcMethod, u128>,
+    pub cycles_refunded: BTreeMap<MetricRpcMethod, u128>,
+    pub cycles_spent: BTreeMap<MetricRpcHost, u128>,
+    pub unauthorized_requests: BTreeMap<MetricRpcMethod, u64>,
+    pub provider_quota_exhausted: BTreeMap<MetricRpcProvider, u64>,
+    pub provider_rate_limited: BTreeMap<MetricRpcProvider, u64>,
+    pub retries: BTreeMap<(MetricRpcMethod, MetricRpcHost, MetricRetryReason), u64>,
+    pub outcalls_shed: BTreeMap<OutcallShedReason, u64>,
+    pub total_outage: BTreeMap<(MetricRpcMethod, MetricSolanaCluster), u64>,
+    pub suppressed_log_entries: BTreeMap<(LogPriority, MetricLogSuppressionReason), u64>,
+    pub shadow_responses: BTreeMap<(MetricRpcProvider, ShadowOutcome), u64>,
+    pub slot_skews: BTreeMap<MetricSolanaCluster, SlotSkewHistogram>,
+    pub version_skew: BTreeMap<MetricSolanaCluster, u64>,
 }
 
 trait EncoderExtensions {
@@ -313,6 +481,98 @@ pub fn encode_metrics(w: &mut ic_metrics_encoder::MetricsEncoder<Vec<u8>>) -> st
             &m.inconsistent_responses,
             "Number of inconsistent JSON-RPC responses",
         );
+        w.counter_entries(
+            "solrpc_cycles_received",
+            &m.cycles_received,
+            "Cycles attached by callers to JSON-RPC requests",
+        );
+        w.counter_entries(
+            "solrpc_cycles_charged",
+            &m.cycles_charged,
+            "Cycles charged to callers for HTTPS outcalls, including collateral and prepaid cycles",
+        );
+        w.counter_entries(
+            "solrpc_cycles_refunded",
+            &m.cycles_refunded,
+            "Cycles refunded to callers of JSON-RPC requests",
+        );
+        w.counter_entries(
+            "solrpc_cycles_spent",
+            &m.cycles_spent,
+            "Estimated cycles spent on HTTPS outcalls to each provider host, excluding collateral",
+        );
+        w.counter_entries(
+            "solrpc_unauthorized_requests",
+            &m.unauthorized_requests,
+            "Number of requests rejected because the caller is not allowed",
+        );
+        w.counter_entries(
+            "solrpc_provider_quota_exhausted",
+            &m.provider_quota_exhausted,
+            "Number of times the request quota of a provider became exhausted",
+        );
+        w.counter_entries(
+            "solrpc_provider_rate_limited",
+            &m.provider_rate_limited,
+            "Number of rate-limited responses (HTTP status 429) received from each provider",
+        );
+        w.counter_entries(
+            "solrpc_retries",
+            &m.retries,
+            "Number of retried HTTPS outcalls",
+        );
+        w.counter_entries(
+            "solrpc_outcalls_shed",
+            &m.outcalls_shed,
+            "Number of HTTPS outcalls shed because too many HTTPS outcalls were in flight",
+        );
+        w.counter_entries(
+            "solrpc_total_outage",
+            &m.total_outage,
+            "Number of requests for which all the providers of a Solana cluster failed, e.g. because of a regional outage",
+        );
+        w.counter_entries(
+            "solrpc_provider_version_skew",
+            &m.version_skew,
+            "Number of times the version sampler found providers of a Solana cluster running different versions",
+        );
+        w.counter_entries(
+            "solrpc_suppressed_log_entries",
+            &m.suppressed_log_entries,
+            "Number of log entries that were not recorded because of sampling or rate limiting",
+        );
+        w.counter_entries(
+            "solrpc_shadow_responses",
+            &m.shadow_responses,
+            "Number of requests to providers in shadow mode by outcome of the comparison with the result returned to the caller",
+        );
+        w.encode_gauge(
+            "solrpc_outcalls_in_flight",
+            num_outcalls_in_flight().metric_value(),
+            "Number of HTTPS outcalls currently in flight",
+        )?;
+        w.encode_gauge(
+            "solrpc_outcall_queue_length",
+            num_queued_outcalls() as f64,
+            "Number of HTTPS outcalls currently waiting for an in-flight HTTPS outcall to complete",
+        )?;
+
+        let pricing_policy = read_state(|s| s.get_pricing_policy());
+        w.encode_gauge(
+            "solrpc_pricing_collateral_cycles_per_node",
+            pricing_policy.collateral_cycles_per_node.metric_value(),
+            "Cycles per subnet node charged on top of the cost of each HTTPS outcall",
+        )?;
+        w.encode_gauge(
+            "solrpc_pricing_markup_percent",
+            pricing_policy.markup_percent.metric_value(),
+            "Markup in percent applied to the cost of each HTTPS outcall",
+        )?;
+        w.encode_gauge(
+            "solrpc_pricing_free_tier_max_cycles",
+            pricing_policy.free_tier_max_cycles.metric_value(),
+            "Maximum cost in cycles of HTTPS outcalls that are free for the caller",
+        )?;
 
         let mut histogram_vec = w.histogram_vec(
             "solrpc_latencies",
@@ -326,6 +586,18 @@ pub fn encode_metrics(w: &mut ic_metrics_encoder::MetricsEncoder<Vec<u8>>) -> st
             )?;
         }
 
+        let mut histogram_vec = w.histogram_vec(
+            "solrpc_slot_skew",
+            "The spread (max - min) of the rounded slots returned by the providers for getSlot calls.",
+        )?;
+        for (label, histogram) in &m.slot_skews {
+            histogram_vec = histogram_vec.histogram(
+                label.metric_labels().as_slice(),
+                histogram.0.iter(),
+                histogram.0.sum() as f64,
+            )?;
+        }
+
         Ok(())
     })
 }
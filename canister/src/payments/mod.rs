@@ -0,0 +1,227 @@
+//! Prepaid cycles deposited from the cycles ledger, which callers can use instead of attaching
+//! cycles to each of their requests.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    log,
+    logs::Priority,
+    memory::{credit_prepaid_cycles, mutate_state, read_state},
+    upgrade::is_upgrade_in_progress,
+};
+use candid::{CandidType, Nat, Principal, Reserved};
+use ic_cdk::call::{Call, CallErrorExt};
+use serde::{Deserialize, Serialize};
+use sol_rpc_types::{DepositCyclesArgs, DepositCyclesError};
+
+/// A deposit whose outcome is unknown, i.e. for which the cycles may or may not have been
+/// withdrawn from the caller's account on the cycles ledger.
+///
+/// Retrying it with the same arguments results in a `Duplicate` error from the cycles ledger if
+/// the cycles were already withdrawn, in which case they are added to the caller's prepaid
+/// cycles. Pending deposits are kept in the [`crate::memory::State`], so that they are not lost
+/// when the canister is upgraded before the deposit is retried.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PendingDeposit {
+    pub caller: Principal,
+    pub from_subaccount: Option<[u8; 32]>,
+    pub amount: u128,
+    pub created_at_time: u64,
+}
+
+/// Withdraws cycles from the caller's account on the cycles ledger with `withdraw_from`, which
+/// sends them to the SOL RPC canister, and adds them to the caller's prepaid cycles.
+///
+/// The withdrawal is deduplicated by the cycles ledger using its creation time, which defaults
+/// to the current time. If the outcome of the withdrawal is unknown, e.g. because the call timed
+/// out, [`DepositCyclesError::UnknownOutcome`] is returned and the deposit can be retried with
+/// the same arguments and the returned creation time.
+///
+/// Returns the new prepaid cycles balance of the caller.
+pub async fn deposit_cycles(
+    caller: Principal,
+    args: DepositCyclesArgs,
+) -> Result<u128, DepositCyclesError> {
+    if caller == Principal::anonymous() {
+        return Err(DepositCyclesError::AnonymousCaller);
+    }
+    if is_upgrade_in_progress() {
+        return Err(DepositCyclesError::UpgradeInProgress);
+    }
+    let created_at_time = args.created_at_time.unwrap_or_else(ic_cdk::api::time);
+    let deposit = PendingDeposit {
+        caller,
+        from_subaccount: args.from_subaccount,
+        amount: args.amount,
+        created_at_time,
+    };
+    let cycles_ledger_id = read_state(|s| s.get_cycles_ledger_id());
+    let withdraw_from_args = WithdrawFromArgs {
+        from: Account {
+            owner: caller,
+            subaccount: args.from_subaccount,
+        },
+        spender_subaccount: None,
+        to: ic_cdk::api::canister_self(),
+        amount: Nat::from(args.amount),
+        created_at_time: Some(created_at_time),
+    };
+    let response = match Call::bounded_wait(cycles_ledger_id, "withdraw_from")
+        .with_arg(&withdraw_from_args)
+        .await
+    {
+        Ok(response) => response,
+        Err(e) if e.is_clean_reject() => {
+            return Err(DepositCyclesError::CallFailed {
+                message: e.to_string(),
+            })
+        }
+        Err(e) => return Err(unknown_outcome(deposit, e.to_string())),
+    };
+    // Only the success case is decoded first, so that the deposit is credited even if the
+    // error type of the cycles ledger changes.
+    let reply = match response.candid::<Result<Nat, Reserved>>() {
+        Ok(Ok(block_index)) => Ok(block_index),
+        Ok(Err(_)) => match response.candid::<Result<Nat, WithdrawFromError>>() {
+            Ok(Err(e)) => Err(e),
+            Ok(Ok(_)) | Err(_) => {
+                return Err(DepositCyclesError::LedgerError {
+                    message: "Failed to decode the error returned by the cycles ledger".to_string(),
+                })
+            }
+        },
+        Err(e) => return Err(unknown_outcome(deposit, e.to_string())),
+    };
+    let (block_index, balance) = settle_deposit(&deposit, reply)?;
+    log!(
+        Priority::Info,
+        "[deposit_cycles]: deposited {} cycles for {} (cycles ledger block {block_index}), new balance is {balance}",
+        deposit.amount,
+        deposit.caller
+    );
+    Ok(balance)
+}
+
+/// Credits the deposit to the caller's prepaid cycles if the cycles ledger withdrew the cycles,
+/// and returns the index of the block of the withdrawal together with the new balance.
+///
+/// A `Duplicate` error is only credited if the deposit is pending, i.e. if a previous attempt of
+/// the same deposit had an unknown outcome, so that a withdrawal is never credited twice.
+fn settle_deposit(
+    deposit: &PendingDeposit,
+    reply: Result<Nat, WithdrawFromError>,
+) -> Result<(Nat, u128), DepositCyclesError> {
+    let block_index = match reply {
+        Ok(block_index) => {
+            remove_pending_deposit(deposit);
+            block_index
+        }
+        Err(WithdrawFromError::Duplicate { duplicate_of }) if remove_pending_deposit(deposit) => {
+            duplicate_of
+        }
+        Err(e) => return Err(DepositCyclesError::from(e)),
+    };
+    let balance = credit_prepaid_cycles(deposit.caller, deposit.amount);
+    Ok((block_index, balance))
+}
+
+fn unknown_outcome(deposit: PendingDeposit, message: String) -> DepositCyclesError {
+    log!(
+        Priority::Info,
+        "[deposit_cycles]: unknown outcome of the deposit of {} cycles for {} created at {}: {message}",
+        deposit.amount,
+        deposit.caller,
+        deposit.created_at_time
+    );
+    let created_at_time = deposit.created_at_time;
+    mutate_state(|s| s.add_pending_deposit(deposit));
+    DepositCyclesError::UnknownOutcome { created_at_time }
+}
+
+/// Removes the given deposit from the pending deposits and returns whether it was pending.
+fn remove_pending_deposit(deposit: &PendingDeposit) -> bool {
+    mutate_state(|s| s.remove_pending_deposit(deposit))
+}
+
+/// Account on the cycles ledger, see [ICRC-1](https://github.com/dfinity/ICRC-1/blob/main/standards/ICRC-1/README.md).
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+struct Account {
+    owner: Principal,
+    subaccount: Option<[u8; 32]>,
+}
+
+/// Arguments of the `withdraw_from` method of the cycles ledger.
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+struct WithdrawFromArgs {
+    spender_subaccount: Option<[u8; 32]>,
+    from: Account,
+    to: Principal,
+    amount: Nat,
+    created_at_time: Option<u64>,
+}
+
+/// Errors returned by the `withdraw_from` method of the cycles ledger.
+///
+/// Only the fields that are relevant to the SOL RPC canister are decoded.
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+enum WithdrawFromError {
+    GenericError { message: String, error_code: Nat },
+    TemporarilyUnavailable,
+    FailedToWithdrawFrom { rejection_reason: String },
+    Duplicate { duplicate_of: Nat },
+    InsufficientFunds { balance: Nat },
+    InsufficientAllowance { allowance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    InvalidReceiver { receiver: Principal },
+}
+
+impl From<WithdrawFromError> for DepositCyclesError {
+    fn from(error: WithdrawFromError) -> Self {
+        match error {
+            WithdrawFromError::InsufficientFunds { balance } => {
+                DepositCyclesError::InsufficientFunds {
+                    balance: nat_to_u128(balance),
+                }
+            }
+            WithdrawFromError::InsufficientAllowance { allowance } => {
+                DepositCyclesError::InsufficientAllowance {
+                    allowance: nat_to_u128(allowance),
+                }
+            }
+            WithdrawFromError::GenericError {
+                message,
+                error_code,
+            } => DepositCyclesError::LedgerError {
+                message: format!("Error {error_code}: {message}"),
+            },
+            WithdrawFromError::TemporarilyUnavailable => DepositCyclesError::LedgerError {
+                message: "The cycles ledger is temporarily unavailable".to_string(),
+            },
+            WithdrawFromError::FailedToWithdrawFrom { rejection_reason } => {
+                DepositCyclesError::LedgerError {
+                    message: format!("Failed to send the cycles: {rejection_reason}"),
+                }
+            }
+            WithdrawFromError::Duplicate { duplicate_of } => DepositCyclesError::LedgerError {
+                message: format!("Duplicate of the withdrawal at block {duplicate_of}"),
+            },
+            WithdrawFromError::TooOld => DepositCyclesError::LedgerError {
+                message: "The withdrawal is too old".to_string(),
+            },
+            WithdrawFromError::CreatedInFuture { ledger_time } => DepositCyclesError::LedgerError {
+                message: format!(
+                    "The withdrawal was created in the future (ledger time: {ledger_time})"
+                ),
+            },
+            WithdrawFromError::InvalidReceiver { receiver } => DepositCyclesError::LedgerError {
+                message: format!("Invalid receiver {receiver}"),
+            },
+        }
+    }
+}
+
+fn nat_to_u128(value: Nat) -> u128 {
+    u128::try_from(value.0).unwrap_or(u128::MAX)
+}
@@ -0,0 +1,105 @@
+use super::{settle_deposit, PendingDeposit, WithdrawFromError};
+use crate::memory::{
+    get_prepaid_cycles, init_state, mutate_state, read_state, reset_state, ConfigState, State,
+};
+use candid::{Nat, Principal};
+use ic_stable_structures::Storable;
+use sol_rpc_types::DepositCyclesError;
+use std::borrow::Cow;
+
+#[test]
+fn should_convert_withdraw_from_error() {
+    assert_eq!(
+        DepositCyclesError::from(WithdrawFromError::InsufficientFunds {
+            balance: Nat::from(1_000_u64)
+        }),
+        DepositCyclesError::InsufficientFunds { balance: 1_000 }
+    );
+    assert_eq!(
+        DepositCyclesError::from(WithdrawFromError::InsufficientAllowance {
+            allowance: Nat::from(u128::MAX) + Nat::from(1_u8)
+        }),
+        DepositCyclesError::InsufficientAllowance {
+            allowance: u128::MAX
+        }
+    );
+    assert_eq!(
+        DepositCyclesError::from(WithdrawFromError::InvalidReceiver {
+            receiver: Principal::anonymous()
+        }),
+        DepositCyclesError::LedgerError {
+            message: "Invalid receiver 2vxsx-fae".to_string()
+        }
+    );
+}
+
+mod pending_deposits {
+    use super::*;
+
+    const AMOUNT: u128 = 1_000_000_000_000;
+
+    #[test]
+    fn should_credit_deposit_with_unknown_outcome_retried_after_upgrade() {
+        reset_state();
+        init_state(State::default());
+        let deposit = pending_deposit(Principal::from_slice(&[0xd1; 29]));
+        // E.g. because the call to the cycles ledger timed out.
+        mutate_state(|s| s.add_pending_deposit(deposit.clone()));
+
+        upgrade();
+
+        assert_eq!(
+            settle_deposit(&deposit, Err(duplicate_of(7))),
+            Ok((Nat::from(7_u8), AMOUNT))
+        );
+        assert_eq!(get_prepaid_cycles(&deposit.caller), AMOUNT);
+
+        assert_eq!(
+            settle_deposit(&deposit, Err(duplicate_of(7))),
+            Err(DepositCyclesError::LedgerError {
+                message: "Duplicate of the withdrawal at block 7".to_string()
+            })
+        );
+        assert_eq!(get_prepaid_cycles(&deposit.caller), AMOUNT);
+    }
+
+    #[test]
+    fn should_not_credit_duplicate_of_deposit_that_is_not_pending() {
+        reset_state();
+        init_state(State::default());
+        let deposit = pending_deposit(Principal::from_slice(&[0xd2; 29]));
+
+        assert_eq!(
+            settle_deposit(&deposit, Err(duplicate_of(7))),
+            Err(DepositCyclesError::LedgerError {
+                message: "Duplicate of the withdrawal at block 7".to_string()
+            })
+        );
+        assert_eq!(get_prepaid_cycles(&deposit.caller), 0);
+    }
+
+    /// Simulates an upgrade, which restores the state from its serialization in stable memory.
+    fn upgrade() {
+        let bytes = ConfigState::Initialized(read_state(State::clone)).into_bytes();
+        reset_state();
+        match ConfigState::from_bytes(Cow::Owned(bytes)) {
+            ConfigState::Initialized(state) => init_state(state),
+            ConfigState::Uninitialized => panic!("BUG: state was not persisted"),
+        }
+    }
+
+    fn pending_deposit(caller: Principal) -> PendingDeposit {
+        PendingDeposit {
+            caller,
+            from_subaccount: None,
+            amount: AMOUNT,
+            created_at_time: 1_700_000_000_000_000_000,
+        }
+    }
+
+    fn duplicate_of(block_index: u8) -> WithdrawFromError {
+        WithdrawFromError::Duplicate {
+            duplicate_of: Nat::from(block_index),
+        }
+    }
+}
@@ -2,20 +2,26 @@
 mod tests;
 
 use crate::{
+    add_metric_entry,
     constants::API_KEY_REPLACE_STRING,
-    memory::{rank_providers, read_state},
-    types::OverrideProvider,
+    log,
+    logs::Priority,
+    memory::{get_host_latency, is_rate_limited, quota_status, rank_providers, read_state},
+    metrics::MetricRpcProvider,
+    types::{ApiKey, OverrideProvider},
+    util::hostname_from_url,
+    version_sampler::is_outdated,
 };
-use canhttp::multi::{TimedSizedMap, TimedSizedVec, Timestamp};
+use canhttp::multi::Timestamp;
 use ic_management_canister_types::HttpHeader;
 use maplit::btreemap;
 use sol_rpc_types::{
-    ConsensusStrategy, ProviderError, RpcAccess, RpcAuth, RpcEndpoint, RpcError, RpcResult,
+    ClusterOutage, ConsensusStrategy, IpVersion, ProviderError, ProviderQuota, RequestPriority,
+    RpcAccess, RpcAuth, RpcEndpoint, RpcEndpointApiKey, RpcEndpointAuth, RpcError, RpcResult,
     RpcSource, RpcSources, SolanaCluster, SupportedRpcProvider, SupportedRpcProviderId,
 };
 use std::{
-    collections::{BTreeMap, BTreeSet},
-    num::NonZeroUsize,
+    collections::{BTreeMap, BTreeSet, VecDeque},
     time::Duration,
 };
 
@@ -120,17 +126,102 @@ thread_local! {
     };
 }
 
+/// IP versions over which the hosts of the supported providers are known to be reachable.
+///
+/// This table is only used to diagnose HTTPS outcalls that could not connect to a provider and
+/// may become outdated, so that hosts that are not listed are simply reported as unknown.
+const KNOWN_HOST_IP_VERSIONS: &[(&str, IpVersion)] = &[
+    ("solana-mainnet.g.alchemy.com", IpVersion::Ipv4),
+    ("solana-devnet.g.alchemy.com", IpVersion::Ipv4),
+    ("rpc.ankr.com", IpVersion::DualStack),
+    ("ipv6.rpc.ankr.com", IpVersion::Ipv6),
+    ("mainnet.helius-rpc.com", IpVersion::Ipv4),
+    ("devnet.helius-rpc.com", IpVersion::Ipv4),
+    ("solana-rpc.publicnode.com", IpVersion::DualStack),
+    ("ipv6.solana-rpc.publicnode.com", IpVersion::Ipv6),
+];
+
+/// Returns the IP versions over which the given host of the given source is known to be
+/// reachable, either from the preferred endpoint of a supported provider or from a static table
+/// of the hosts of the supported providers.
+pub fn known_ip_version(source: &RpcSource, host: &str) -> Option<IpVersion> {
+    let preferred_endpoint = source
+        .rpc_provider_id()
+        .and_then(|provider_id| read_state(|s| s.get_provider_endpoint(&provider_id)))
+        .filter(|endpoint| access_hostname(&endpoint.access).as_deref() == Some(host));
+    if let Some(endpoint) = preferred_endpoint {
+        return Some(endpoint.ip_version);
+    }
+    KNOWN_HOST_IP_VERSIONS
+        .iter()
+        .find(|(known_host, _)| *known_host == host)
+        .map(|(_, ip_version)| *ip_version)
+}
+
+/// Returns the host of the URL of the given access method.
+pub fn access_hostname(access: &RpcAccess) -> Option<String> {
+    let url = match access {
+        RpcAccess::Authenticated { auth, .. } => match auth {
+            RpcAuth::BearerToken { url } => url,
+            RpcAuth::UrlParameter { url_pattern } => url_pattern,
+        },
+        RpcAccess::Unauthenticated { public_url } => public_url,
+    };
+    hostname_from_url(url)
+}
+
 pub fn get_provider(provider_id: &SupportedRpcProviderId) -> Option<SupportedRpcProvider> {
     PROVIDERS.with(|providers| providers.get(provider_id).cloned())
 }
 
+/// Returns the access method of a supported provider, taking into account its preferred
+/// endpoint, if one is set.
+fn get_provider_access(provider_id: &SupportedRpcProviderId) -> RpcAccess {
+    match read_state(|s| s.get_provider_endpoint(provider_id)) {
+        Some(endpoint) => {
+            log!(
+                Priority::Debug,
+                "Using preferred {:?} endpoint for provider {provider_id:?}",
+                endpoint.ip_version
+            );
+            endpoint.access
+        }
+        None => get_provider(provider_id).expect("Unknown provider").access,
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Providers {
     /// *Non-empty* set of providers to query.
     pub sources: BTreeSet<RpcSource>,
+    /// Scheduling lane of the requests sent to the providers.
+    pub priority: RequestPriority,
 }
 
 impl Providers {
+    /// Returns the supported providers in shadow mode that serve the same cluster as the
+    /// supported providers to query, excluding the providers to query themselves.
+    ///
+    /// No provider is queried in shadow mode if only custom endpoints are queried, since their
+    /// cluster is unknown.
+    pub fn shadow_sources(&self) -> BTreeSet<RpcSource> {
+        let clusters: BTreeSet<_> = self
+            .sources
+            .iter()
+            .filter_map(RpcSource::rpc_provider_id)
+            .filter_map(|provider_id| get_provider(&provider_id).map(|provider| provider.cluster))
+            .collect();
+        read_state(|s| s.get_shadow_providers())
+            .into_iter()
+            .filter(|provider_id| {
+                get_provider(provider_id)
+                    .is_some_and(|provider| clusters.contains(&provider.cluster))
+            })
+            .map(RpcSource::Supported)
+            .filter(|source| !self.sources.contains(source))
+            .collect()
+    }
+
     // Order of providers matters!
     // The threshold consensus strategy will consider the first `total` providers in the order
     // they are specified if the providers are not explicitly specified by the caller.
@@ -156,6 +247,7 @@ impl Providers {
     pub fn new(
         source: RpcSources,
         strategy: ConsensusStrategy,
+        priority: RequestPriority,
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
         fn supported_providers(
@@ -174,8 +266,22 @@ impl Providers {
             RpcSource::Supported(supported_provider)
         }
 
-        let providers: BTreeSet<_> = match strategy {
-            ConsensusStrategy::Equality => match source {
+        if let RpcSources::Custom(custom_providers) = &source {
+            ensure_single_cluster(custom_providers)?;
+        }
+
+        // Providers are selected as for the threshold strategy, the remaining requirements
+        // of the other strategies are enforced when reducing the responses.
+        let threshold = match strategy {
+            ConsensusStrategy::Equality => None,
+            ConsensusStrategy::Threshold { total, min }
+            | ConsensusStrategy::MedianNumeric { total, min } => Some((total, min)),
+            ConsensusStrategy::SupermajorityBySlot { total }
+            | ConsensusStrategy::CollectAll { total } => Some((total, 1)),
+        };
+
+        let providers: BTreeSet<_> = match threshold {
+            None => match source {
                 RpcSources::Custom(custom_providers) => Ok(custom_providers.into_iter().collect()),
                 RpcSources::Default(cluster) => {
                     let supported_providers = supported_providers(&cluster)?;
@@ -183,14 +289,14 @@ impl Providers {
                         supported_providers.len() >= Self::DEFAULT_NUM_PROVIDERS_FOR_EQUALITY,
                         "BUG: need at least 3 providers, but got {supported_providers:?}"
                     );
-                    Ok(rank_providers(supported_providers, now)
+                    Ok(rank_available_providers(supported_providers, priority, now)
                         .into_iter()
                         .take(Self::DEFAULT_NUM_PROVIDERS_FOR_EQUALITY)
                         .map(supported_rpc_source)
                         .collect())
                 }
             },
-            ConsensusStrategy::Threshold { total, min } => {
+            Some((total, min)) => {
                 // Ensure that
                 // 0 < min <= total <= all_providers.len()
                 if min == 0 {
@@ -240,11 +346,12 @@ impl Providers {
                                 total, all_providers_len
                             )));
                         }
-                        let providers: BTreeSet<_> = rank_providers(supported_providers, now)
-                            .into_iter()
-                            .take(total as usize)
-                            .map(supported_rpc_source)
-                            .collect();
+                        let providers: BTreeSet<_> =
+                            rank_available_providers(supported_providers, priority, now)
+                                .into_iter()
+                                .take(total as usize)
+                                .map(supported_rpc_source)
+                                .collect();
                         assert_eq!(providers.len(), total as usize, "BUG: duplicate providers");
                         Ok(providers)
                     }
@@ -258,52 +365,252 @@ impl Providers {
             ));
         }
 
-        Ok(Self { sources: providers })
+        Ok(Self {
+            sources: providers,
+            priority,
+        })
     }
 }
 
-pub fn resolve_rpc_provider(service: RpcSource) -> RpcEndpoint {
+/// Ensures that the supported providers among the given sources all belong to the same cluster,
+/// since querying providers of different clusters would always result in inconsistent responses.
+fn ensure_single_cluster(sources: &[RpcSource]) -> Result<(), ProviderError> {
+    let clusters: BTreeSet<_> = sources
+        .iter()
+        .filter_map(|source| match source {
+            RpcSource::Supported(provider_id) => Some(provider_id),
+            RpcSource::Custom(_) => None,
+        })
+        .filter_map(|provider_id| get_provider(provider_id).map(|provider| provider.cluster))
+        .collect();
+    if clusters.len() > 1 {
+        return Err(ProviderError::InvalidRpcConfig(format!(
+            "Supported providers from different clusters {clusters:?} cannot be mixed"
+        )));
+    }
+    Ok(())
+}
+
+/// Ranks the given providers as [`rank_providers`] does, except that the providers whose
+/// request quota is exhausted are moved to the end, followed by the providers that are
+/// currently rate limited and by the providers running a version older than the minimum version
+/// of the version sampler, so that they are only selected if not enough other providers are
+/// available.
+///
+/// For [`RequestPriority::Expedited`] requests, the available providers are instead ranked by
+/// their recent latency, from the fastest to the slowest, followed by the providers whose
+/// latency is unknown.
+fn rank_available_providers(
+    providers: &[SupportedRpcProviderId],
+    priority: RequestPriority,
+    now: Timestamp,
+) -> Vec<SupportedRpcProviderId> {
+    let (up_to_date, outdated): (Vec<_>, Vec<_>) = rank_providers(providers, now)
+        .into_iter()
+        .partition(|provider| !is_outdated(*provider));
+    for provider in &outdated {
+        log!(
+            Priority::Debug,
+            "Provider {provider:?} runs an outdated version, deprioritizing it"
+        );
+    }
+    let (not_rate_limited, rate_limited): (Vec<_>, Vec<_>) = up_to_date
+        .into_iter()
+        .partition(|provider| !is_rate_limited(*provider, now));
+    for provider in &rate_limited {
+        log!(
+            Priority::Debug,
+            "Provider {provider:?} is rate limited, deprioritizing it"
+        );
+    }
+    let (available, exhausted): (Vec<_>, Vec<_>) =
+        not_rate_limited
+            .into_iter()
+            .partition(|provider| match quota_status(*provider, now) {
+                QuotaStatus::Available => true,
+                QuotaStatus::Exhausted => false,
+                QuotaStatus::NewlyExhausted => {
+                    add_metric_entry!(
+                        provider_quota_exhausted,
+                        MetricRpcProvider::from(*provider),
+                        1
+                    );
+                    false
+                }
+            });
+    for provider in &exhausted {
+        log!(
+            Priority::Debug,
+            "Request quota exhausted for provider {provider:?}, deprioritizing it"
+        );
+    }
+    let mut available = available;
+    if priority == RequestPriority::Expedited {
+        // The sort is stable, so that providers with the same latency keep their ranking.
+        available.sort_by_key(|provider| {
+            access_hostname(&get_provider_access(provider))
+                .and_then(|host| get_host_latency(&host))
+                .unwrap_or(Duration::MAX)
+        });
+    }
+    available
+        .into_iter()
+        .chain(exhausted)
+        .chain(rate_limited)
+        .chain(outdated)
+        .collect()
+}
+
+pub fn resolve_rpc_provider(service: RpcSource) -> RpcResult<RpcEndpoint> {
     match service {
-        RpcSource::Supported(provider_id) => get_provider(&provider_id)
-            .map(|provider| resolve_api_key(provider.access, provider_id))
-            .expect("Unknown provider"),
-        RpcSource::Custom(api) => api,
+        RpcSource::Supported(provider_id) => {
+            resolve_supported_provider(provider_id, &mut BTreeSet::from([provider_id]))
+                .map(|(endpoint, _uses_api_key)| endpoint)
+        }
+        RpcSource::Custom(api) => resolve_custom_api_key(api),
     }
 }
 
-fn resolve_api_key(access: RpcAccess, provider: SupportedRpcProviderId) -> RpcEndpoint {
-    match &access {
-        RpcAccess::Authenticated { auth, public_url } => {
-            let api_key = read_state(|s| s.get_api_key(&provider));
-            match api_key {
-                Some(api_key) => match auth {
-                    RpcAuth::BearerToken { url } => RpcEndpoint {
-                        url: url.to_string(),
-                        headers: Some(vec![HttpHeader {
-                            name: "Authorization".to_string(),
-                            value: format!("Bearer {}", api_key.read()),
-                        }]),
-                    },
-                    RpcAuth::UrlParameter { url_pattern } => RpcEndpoint {
-                        url: url_pattern.replace(API_KEY_REPLACE_STRING, api_key.read()),
-                        headers: None,
-                    },
-                },
-                None => RpcEndpoint {
-                    url: public_url.clone().unwrap_or_else(|| {
-                        panic!("API key not yet initialized for provider: {:?}", provider)
-                    }),
-                    headers: None,
-                },
+/// Resolves the endpoints of all the given sources, which are queried together, together with
+/// whether the requests to each endpoint are authenticated with an API key.
+///
+/// A provider of a fallback chain is skipped if it is itself one of the given sources or if it was
+/// already used as fallback for another source, so that the same provider is never queried twice
+/// for a single request.
+pub fn resolve_rpc_providers(
+    sources: &BTreeSet<RpcSource>,
+) -> BTreeMap<RpcSource, RpcResult<(RpcEndpoint, bool)>> {
+    let mut selected: BTreeSet<SupportedRpcProviderId> = sources
+        .iter()
+        .filter_map(|source| match source {
+            RpcSource::Supported(provider_id) => Some(*provider_id),
+            RpcSource::Custom(_) => None,
+        })
+        .collect();
+    sources
+        .iter()
+        .map(|source| {
+            let endpoint = match source {
+                RpcSource::Supported(provider_id) => {
+                    resolve_supported_provider(*provider_id, &mut selected)
+                }
+                RpcSource::Custom(api) => resolve_custom_api_key(api.clone())
+                    .map(|endpoint| (endpoint, api.auth.is_some())),
+            };
+            (source.clone(), endpoint)
+        })
+        .collect()
+}
+
+/// Resolves the endpoint of a supported provider, together with whether it uses an API key.
+/// If the provider requires an API key and none is set, the providers of its fallback chain
+/// that are not already `selected` are tried in order, before using the provider's public URL,
+/// if any. The fallback provider used, if any, is added to `selected`.
+fn resolve_supported_provider(
+    provider_id: SupportedRpcProviderId,
+    selected: &mut BTreeSet<SupportedRpcProviderId>,
+) -> RpcResult<(RpcEndpoint, bool)> {
+    let access = get_provider_access(&provider_id);
+    let RpcAccess::Authenticated { .. } = access else {
+        return Ok((
+            resolve_public_url(&access).expect("BUG: missing public URL"),
+            false,
+        ));
+    };
+    if let Some(endpoint) = resolve_api_key(&access, &provider_id) {
+        return Ok((endpoint, true));
+    }
+    for fallback_id in read_state(|s| s.get_provider_fallbacks(&provider_id)) {
+        if selected.contains(&fallback_id) {
+            continue;
+        }
+        let fallback_access = get_provider_access(&fallback_id);
+        if let Some(endpoint) = resolve_api_key(&fallback_access, &fallback_id)
+            .map(|endpoint| (endpoint, true))
+            .or_else(|| resolve_public_url(&fallback_access).map(|endpoint| (endpoint, false)))
+        {
+            log!(
+                Priority::Debug,
+                "No API key set for provider {provider_id:?}, falling back to {fallback_id:?}"
+            );
+            selected.insert(fallback_id);
+            return Ok(endpoint);
+        }
+    }
+    resolve_public_url(&access)
+        .map(|endpoint| (endpoint, false))
+        .ok_or_else(|| {
+            RpcError::ProviderError(ProviderError::InvalidRpcConfig(format!(
+                "API key not yet initialized for provider: {provider_id:?}"
+            )))
+        })
+}
+
+fn resolve_custom_api_key(mut api: RpcEndpoint) -> RpcResult<RpcEndpoint> {
+    let Some(auth) = api.auth.clone() else {
+        return Ok(api);
+    };
+    let api_key = match auth.api_key() {
+        RpcEndpointApiKey::Value(api_key) => ApiKey::try_from(api_key.clone())
+            .map_err(|e| RpcError::ValidationError(format!("Invalid API key: {e}")))?,
+        RpcEndpointApiKey::Stored => {
+            read_state(|s| s.get_custom_api_key(&api.url)).ok_or_else(|| {
+                RpcError::ValidationError(format!("No API key stored for URL: {}", api.url))
+            })?
+        }
+    };
+    api.auth = None;
+    match auth {
+        RpcEndpointAuth::BearerToken { .. } => {
+            let mut headers = api.headers.unwrap_or_default();
+            headers.push(HttpHeader {
+                name: "Authorization".to_string(),
+                value: format!("Bearer {}", api_key.read()),
+            });
+            api.headers = Some(headers);
+        }
+        RpcEndpointAuth::UrlParameter { .. } => {
+            if !api.url.contains(API_KEY_REPLACE_STRING) {
+                return Err(RpcError::ValidationError(format!(
+                    "Missing {API_KEY_REPLACE_STRING} placeholder in URL"
+                )));
             }
+            api.url = api.url.replace(API_KEY_REPLACE_STRING, api_key.read());
         }
-        RpcAccess::Unauthenticated { public_url } => RpcEndpoint {
-            url: public_url.to_string(),
-            headers: None,
-        },
+    }
+    Ok(api)
+}
+
+/// Resolves the endpoint of a provider using its API key, if one is required and set.
+fn resolve_api_key(access: &RpcAccess, provider: &SupportedRpcProviderId) -> Option<RpcEndpoint> {
+    match access {
+        RpcAccess::Authenticated { auth, .. } => {
+            let api_key = read_state(|s| s.get_api_key(provider))?;
+            Some(match auth {
+                RpcAuth::BearerToken { url } => {
+                    RpcEndpoint::new(url.clone()).with_headers(vec![HttpHeader {
+                        name: "Authorization".to_string(),
+                        value: format!("Bearer {}", api_key.read()),
+                    }])
+                }
+                RpcAuth::UrlParameter { url_pattern } => {
+                    RpcEndpoint::new(url_pattern.replace(API_KEY_REPLACE_STRING, api_key.read()))
+                }
+            })
+        }
+        RpcAccess::Unauthenticated { .. } => None,
     }
 }
 
+/// Resolves the endpoint of a provider using its public URL, if it has one.
+fn resolve_public_url(access: &RpcAccess) -> Option<RpcEndpoint> {
+    let url = match access {
+        RpcAccess::Authenticated { public_url, .. } => public_url.clone()?,
+        RpcAccess::Unauthenticated { public_url } => public_url.clone(),
+    };
+    Some(RpcEndpoint::new(url))
+}
+
 pub fn request_builder(
     endpoint: RpcEndpoint,
     override_provider: &OverrideProvider,
@@ -321,25 +628,22 @@ pub fn request_builder(
 }
 
 /// Record when a supported RPC service was used.
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct SupportedRpcProviderUsage(TimedSizedMap<SupportedRpcProviderId, ()>);
-
-impl Default for SupportedRpcProviderUsage {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+///
+/// Only the timestamps of the recent usages are recorded, so that they can be persisted across
+/// upgrades, see [`crate::memory::save_provider_usage`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SupportedRpcProviderUsage(BTreeMap<SupportedRpcProviderId, VecDeque<Timestamp>>);
 
 impl SupportedRpcProviderUsage {
-    pub fn new() -> SupportedRpcProviderUsage {
-        Self(TimedSizedMap::new(
-            Duration::from_secs(20 * 60),
-            NonZeroUsize::new(500).unwrap(),
-        ))
-    }
+    /// How long a usage is taken into account to rank the providers.
+    pub const EXPIRATION: Duration = Duration::from_secs(20 * 60);
+    /// Maximum number of usages recorded for each provider.
+    pub const CAPACITY: usize = 500;
 
     pub fn record_evict(&mut self, service: SupportedRpcProviderId, now: Timestamp) {
-        self.0.insert_evict(now, service, ());
+        let usages = self.0.entry(service).or_default();
+        usages.push_back(now);
+        Self::evict(usages, now);
     }
 
     pub fn rank_ascending_evict(
@@ -347,14 +651,302 @@ impl SupportedRpcProviderUsage {
         providers: &[SupportedRpcProviderId],
         now: Timestamp,
     ) -> Vec<SupportedRpcProviderId> {
-        fn ascending_num_elements<V>(values: Option<&TimedSizedVec<V>>) -> impl Ord {
-            std::cmp::Reverse(values.map(|v| v.len()).unwrap_or_default())
+        for provider in providers {
+            if let Some(usages) = self.0.get_mut(provider) {
+                Self::evict(usages, now);
+                if usages.is_empty() {
+                    self.0.remove(provider);
+                }
+            }
         }
+        let mut ranked = providers.to_vec();
+        // Stable sort: providers with the same usage keep their default order.
+        ranked.sort_by_key(|provider| {
+            std::cmp::Reverse(self.0.get(provider).map_or(0, VecDeque::len))
+        });
+        ranked
+    }
 
-        self.0.evict_expired(providers, now);
-        self.0
-            .sort_keys_by(providers, ascending_num_elements)
-            .copied()
-            .collect()
+    /// Returns the timestamps of the recorded usages of each provider, from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = (&SupportedRpcProviderId, &VecDeque<Timestamp>)> {
+        self.0.iter()
+    }
+
+    /// Removes the usages that expired and only keeps the last [`Self::CAPACITY`] ones.
+    fn evict(usages: &mut VecDeque<Timestamp>, now: Timestamp) {
+        let expired_before = Timestamp::from_nanos_since_unix_epoch(
+            now.as_nanos_since_unix_epoch()
+                .saturating_sub(Self::EXPIRATION.as_nanos() as u64),
+        );
+        while usages
+            .front()
+            .is_some_and(|timestamp| *timestamp < expired_before)
+        {
+            usages.pop_front();
+        }
+        while usages.len() > Self::CAPACITY {
+            usages.pop_front();
+        }
+    }
+}
+
+impl FromIterator<(SupportedRpcProviderId, Timestamp)> for SupportedRpcProviderUsage {
+    fn from_iter<I: IntoIterator<Item = (SupportedRpcProviderId, Timestamp)>>(iter: I) -> Self {
+        let mut usage = Self::default();
+        for (provider, timestamp) in iter {
+            usage.0.entry(provider).or_default().push_back(timestamp);
+        }
+        for usages in usage.0.values_mut() {
+            usages.make_contiguous().sort();
+        }
+        usage
+    }
+}
+
+/// Whether the [`ProviderQuota`] of a supported RPC provider is exhausted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuotaStatus {
+    /// Fewer than [`ProviderQuota::max_requests`] requests were made within the quota window.
+    Available,
+    /// The quota is exhausted and was already exhausted when it was last checked.
+    Exhausted,
+    /// The quota is exhausted but was not when it was last checked.
+    NewlyExhausted,
+}
+
+impl QuotaStatus {
+    pub fn is_exhausted(&self) -> bool {
+        !matches!(self, QuotaStatus::Available)
+    }
+}
+
+/// Record when requests were made to supported RPC providers, to enforce their
+/// [`ProviderQuota`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SupportedRpcProviderRequests {
+    requests: BTreeMap<SupportedRpcProviderId, VecDeque<Timestamp>>,
+    exhausted: BTreeSet<SupportedRpcProviderId>,
+}
+
+impl SupportedRpcProviderRequests {
+    pub fn record_evict(
+        &mut self,
+        provider: SupportedRpcProviderId,
+        quota: &ProviderQuota,
+        now: Timestamp,
+    ) {
+        let requests = self.requests.entry(provider).or_default();
+        requests.push_back(now);
+        Self::evict(requests, quota, now);
+    }
+
+    /// Returns whether at least [`ProviderQuota::max_requests`] requests were made to the
+    /// given provider within the quota window, and whether this changed since the last check.
+    pub fn quota_status_evict(
+        &mut self,
+        provider: SupportedRpcProviderId,
+        quota: &ProviderQuota,
+        now: Timestamp,
+    ) -> QuotaStatus {
+        let exhausted = match self.requests.get_mut(&provider) {
+            Some(requests) => {
+                Self::evict(requests, quota, now);
+                requests.len() >= quota.max_requests as usize
+            }
+            None => false,
+        };
+        match (exhausted, self.exhausted.contains(&provider)) {
+            (false, _) => {
+                self.exhausted.remove(&provider);
+                QuotaStatus::Available
+            }
+            (true, true) => QuotaStatus::Exhausted,
+            (true, false) => {
+                self.exhausted.insert(provider);
+                QuotaStatus::NewlyExhausted
+            }
+        }
+    }
+
+    /// Returns `true` if requests to the given provider were recorded and not yet evicted.
+    pub fn contains(&self, provider: &SupportedRpcProviderId) -> bool {
+        self.requests
+            .get(provider)
+            .is_some_and(|requests| !requests.is_empty())
+    }
+
+    /// Removes the requests outside the quota window. Only the last `max_requests` requests
+    /// are kept, since older requests do not matter to decide whether the quota is exhausted.
+    fn evict(requests: &mut VecDeque<Timestamp>, quota: &ProviderQuota, now: Timestamp) {
+        let window = Duration::from_secs(quota.window_seconds as u64);
+        let window_start = Timestamp::from_nanos_since_unix_epoch(
+            now.as_nanos_since_unix_epoch()
+                .saturating_sub(window.as_nanos() as u64),
+        );
+        while requests
+            .front()
+            .is_some_and(|timestamp| *timestamp < window_start)
+        {
+            requests.pop_front();
+        }
+        while requests.len() > quota.max_requests as usize {
+            requests.pop_front();
+        }
+    }
+}
+
+/// Record until when supported RPC providers are rate limited, i.e. responded with
+/// [`ProviderError::RateLimited`], so that they can be deprioritized in the meantime.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SupportedRpcProviderRateLimits(BTreeMap<SupportedRpcProviderId, Timestamp>);
+
+impl SupportedRpcProviderRateLimits {
+    /// How long a provider is deprioritized when it does not specify when to retry.
+    pub const DEFAULT_BACKOFF: Duration = Duration::from_secs(60);
+    /// Maximum duration a provider is deprioritized, regardless of when it asks to retry.
+    pub const MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+
+    /// Records that the given provider is rate limited for `retry_after_seconds`,
+    /// or [`Self::DEFAULT_BACKOFF`] if not specified, capped at [`Self::MAX_BACKOFF`].
+    pub fn record(
+        &mut self,
+        provider: SupportedRpcProviderId,
+        retry_after_seconds: Option<u64>,
+        now: Timestamp,
+    ) {
+        let backoff = retry_after_seconds
+            .map(Duration::from_secs)
+            .unwrap_or(Self::DEFAULT_BACKOFF)
+            .min(Self::MAX_BACKOFF);
+        let until = Timestamp::from_nanos_since_unix_epoch(
+            now.as_nanos_since_unix_epoch()
+                .saturating_add(backoff.as_nanos() as u64),
+        );
+        let current = self.0.entry(provider).or_insert(until);
+        *current = (*current).max(until);
+    }
+
+    /// Returns `true` if the given provider is still rate limited, and forgets it otherwise.
+    pub fn is_rate_limited_evict(
+        &mut self,
+        provider: SupportedRpcProviderId,
+        now: Timestamp,
+    ) -> bool {
+        match self.0.get(&provider) {
+            Some(until) if now < *until => true,
+            Some(_) => {
+                self.0.remove(&provider);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+/// Recent latency of the HTTPS outcalls made to each host, as an exponentially weighted moving
+/// average, e.g. to select the fastest providers for expedited requests.
+///
+/// At most [`HostLatencies::MAX_NUM_HOSTS`] hosts are tracked, the least recently updated
+/// host being forgotten when a new host is recorded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HostLatencies(BTreeMap<String, HostLatency>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct HostLatency {
+    average: Duration,
+    updated_at: Timestamp,
+}
+
+impl HostLatencies {
+    /// Maximum number of tracked hosts.
+    pub const MAX_NUM_HOSTS: usize = 100;
+    /// Weight in percent of a new latency measurement in the moving average.
+    const WEIGHT_PERCENT: u32 = 20;
+
+    /// Records the latency of an HTTPS outcall to the given host.
+    pub fn record(&mut self, host: String, latency: Duration, now: Timestamp) {
+        if let Some(entry) = self.0.get_mut(&host) {
+            entry.average = (entry.average * (100 - Self::WEIGHT_PERCENT)
+                + latency * Self::WEIGHT_PERCENT)
+                / 100;
+            entry.updated_at = now;
+            return;
+        }
+        if self.0.len() >= Self::MAX_NUM_HOSTS {
+            let least_recently_updated = self
+                .0
+                .iter()
+                .min_by_key(|(_, entry)| entry.updated_at)
+                .map(|(host, _)| host.clone());
+            if let Some(host) = least_recently_updated {
+                self.0.remove(&host);
+            }
+        }
+        self.0.insert(
+            host,
+            HostLatency {
+                average: latency,
+                updated_at: now,
+            },
+        );
+    }
+
+    /// Returns the recent latency of the HTTPS outcalls to the given host, if any was recorded.
+    pub fn get(&self, host: &str) -> Option<Duration> {
+        self.0.get(host).map(|entry| entry.average)
+    }
+}
+
+/// Record the most recent total outage of each Solana cluster, i.e. a period during which all the
+/// providers queried for that cluster failed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolanaClusterOutages(BTreeMap<SolanaCluster, ClusterOutage>);
+
+impl SolanaClusterOutages {
+    /// Records a request for which all the providers of the given cluster failed.
+    ///
+    /// Returns `true` if the request started a new outage.
+    pub fn record_failure(&mut self, cluster: SolanaCluster, now: Timestamp) -> bool {
+        let now = now.as_nanos_since_unix_epoch();
+        match self.0.get_mut(&cluster) {
+            Some(outage) if outage.ended_at.is_none() => {
+                outage.last_failure_at = now;
+                outage.num_failed_requests = outage.num_failed_requests.saturating_add(1);
+                false
+            }
+            _ => {
+                self.0.insert(
+                    cluster,
+                    ClusterOutage {
+                        started_at: now,
+                        last_failure_at: now,
+                        ended_at: None,
+                        num_failed_requests: 1,
+                    },
+                );
+                true
+            }
+        }
+    }
+
+    /// Records a request for which at least one provider of the given cluster responded.
+    ///
+    /// Returns the outage ended by the request, if any.
+    pub fn record_success(
+        &mut self,
+        cluster: SolanaCluster,
+        now: Timestamp,
+    ) -> Option<ClusterOutage> {
+        let outage = self
+            .0
+            .get_mut(&cluster)
+            .filter(|outage| outage.ended_at.is_none())?;
+        outage.ended_at = Some(now.as_nanos_since_unix_epoch());
+        Some(outage.clone())
+    }
+
+    /// Returns the most recent outage of the given cluster, if any.
+    pub fn get(&self, cluster: SolanaCluster) -> Option<ClusterOutage> {
+        self.0.get(&cluster).cloned()
     }
 }
@@ -76,8 +76,8 @@ mod providers_new {
     use canhttp::multi::Timestamp;
     use maplit::btreeset;
     use sol_rpc_types::{
-        ConsensusStrategy, ProviderError, RpcSource, RpcSources, SolanaCluster,
-        SupportedRpcProviderId,
+        ConsensusStrategy, ProviderError, RequestPriority, RpcEndpoint, RpcSource, RpcSources,
+        SolanaCluster, SupportedRpcProviderId,
     };
 
     #[test]
@@ -86,6 +86,7 @@ mod providers_new {
             Providers::new(
                 RpcSources::Custom(vec![]),
                 ConsensusStrategy::default(),
+                RequestPriority::Normal,
                 Timestamp::UNIX_EPOCH
             ),
             Err(ProviderError::InvalidRpcConfig(_))
@@ -98,6 +99,7 @@ mod providers_new {
             let providers = Providers::new(
                 RpcSources::Default(cluster),
                 ConsensusStrategy::default(),
+                RequestPriority::Normal,
                 Timestamp::UNIX_EPOCH,
             )
             .unwrap();
@@ -116,6 +118,7 @@ mod providers_new {
                 RpcSource::Supported(provider2),
             ]),
             ConsensusStrategy::default(),
+            RequestPriority::Normal,
             Timestamp::UNIX_EPOCH,
         )
         .unwrap();
@@ -128,6 +131,509 @@ mod providers_new {
             }
         );
     }
+
+    #[test]
+    fn should_fail_when_mixing_providers_from_different_clusters() {
+        for strategy in [
+            ConsensusStrategy::Equality,
+            ConsensusStrategy::Threshold {
+                total: Some(2),
+                min: 1,
+            },
+        ] {
+            assert_matches!(
+                Providers::new(
+                    RpcSources::Custom(vec![
+                        RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                        RpcSource::Supported(SupportedRpcProviderId::AlchemyDevnet),
+                    ]),
+                    strategy,
+                    RequestPriority::Normal,
+                    Timestamp::UNIX_EPOCH
+                ),
+                Err(ProviderError::InvalidRpcConfig(message)) if message.contains("different clusters")
+            );
+        }
+    }
+
+    #[test]
+    fn should_allow_custom_endpoints_with_providers_from_single_cluster() {
+        let sources = vec![
+            RpcSource::Supported(SupportedRpcProviderId::AlchemyDevnet),
+            RpcSource::Supported(SupportedRpcProviderId::HeliusDevnet),
+            RpcSource::Custom(RpcEndpoint::new("https://solana.example.com")),
+        ];
+
+        let providers = Providers::new(
+            RpcSources::Custom(sources.clone()),
+            ConsensusStrategy::default(),
+            RequestPriority::Normal,
+            Timestamp::UNIX_EPOCH,
+        )
+        .unwrap();
+
+        assert_eq!(providers.sources, sources.into_iter().collect());
+    }
+
+    #[test]
+    fn should_select_providers_as_for_threshold_strategy() {
+        for (total, min) in [(Some(4), 2), (Some(5), 5), (None, 1)] {
+            let threshold = Providers::new(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                ConsensusStrategy::Threshold { total, min },
+                RequestPriority::Normal,
+                Timestamp::UNIX_EPOCH,
+            );
+            let median = Providers::new(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                ConsensusStrategy::MedianNumeric { total, min },
+                RequestPriority::Normal,
+                Timestamp::UNIX_EPOCH,
+            );
+            let supermajority = Providers::new(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                ConsensusStrategy::SupermajorityBySlot { total },
+                RequestPriority::Normal,
+                Timestamp::UNIX_EPOCH,
+            );
+            let collect_all = Providers::new(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                ConsensusStrategy::CollectAll { total },
+                RequestPriority::Normal,
+                Timestamp::UNIX_EPOCH,
+            );
+            assert_eq!(median, threshold);
+            assert_eq!(supermajority, threshold);
+            assert_eq!(collect_all, threshold);
+        }
+    }
+}
+
+mod providers_new_with_quotas {
+    use crate::{
+        memory::{
+            init_state, record_host_latency, record_rate_limited, record_request, reset_state,
+            State,
+        },
+        providers::{access_hostname, get_provider_access, Providers},
+    };
+    use canhttp::multi::Timestamp;
+    use sol_rpc_types::{
+        ConsensusStrategy, ProviderQuota, RequestPriority, RpcSource, RpcSources, SolanaCluster,
+        SupportedRpcProviderId,
+    };
+    use std::time::Duration;
+
+    const PROVIDER: SupportedRpcProviderId = SupportedRpcProviderId::AlchemyMainnet;
+
+    #[test]
+    fn should_deprioritize_provider_with_exhausted_quota() {
+        init_state_with_quota(PROVIDER, 2, 60);
+        let source = RpcSource::Supported(PROVIDER);
+
+        record_request(PROVIDER, Timestamp::UNIX_EPOCH);
+        assert!(default_providers(Timestamp::UNIX_EPOCH).contains(&source));
+
+        record_request(PROVIDER, Timestamp::UNIX_EPOCH);
+        assert!(!default_providers(Timestamp::UNIX_EPOCH).contains(&source));
+        assert!(
+            !default_providers(Timestamp::from_unix_epoch(Duration::from_secs(60)))
+                .contains(&source)
+        );
+
+        assert!(
+            default_providers(Timestamp::from_unix_epoch(Duration::from_secs(61)))
+                .contains(&source)
+        );
+    }
+
+    #[test]
+    fn should_select_provider_with_exhausted_quota_when_needed() {
+        init_state_with_quota(PROVIDER, 1, 60);
+        record_request(PROVIDER, Timestamp::UNIX_EPOCH);
+
+        let all_providers = Providers::new(
+            RpcSources::Default(SolanaCluster::Mainnet),
+            ConsensusStrategy::Threshold {
+                total: Some(Providers::MAINNET_PROVIDERS.len() as u8),
+                min: 1,
+            },
+            RequestPriority::Normal,
+            Timestamp::UNIX_EPOCH,
+        )
+        .unwrap();
+        assert!(all_providers
+            .sources
+            .contains(&RpcSource::Supported(PROVIDER)));
+
+        let custom_providers = Providers::new(
+            RpcSources::Custom(vec![RpcSource::Supported(PROVIDER)]),
+            ConsensusStrategy::default(),
+            RequestPriority::Normal,
+            Timestamp::UNIX_EPOCH,
+        )
+        .unwrap();
+        assert!(custom_providers
+            .sources
+            .contains(&RpcSource::Supported(PROVIDER)));
+    }
+
+    #[test]
+    fn should_deprioritize_rate_limited_provider() {
+        init_state_with_quota(SupportedRpcProviderId::HeliusMainnet, 1, 60);
+        let source = RpcSource::Supported(PROVIDER);
+        assert!(default_providers(Timestamp::UNIX_EPOCH).contains(&source));
+
+        record_rate_limited(PROVIDER, Some(30), Timestamp::UNIX_EPOCH);
+        assert!(!default_providers(Timestamp::UNIX_EPOCH).contains(&source));
+        assert!(
+            !default_providers(Timestamp::from_unix_epoch(Duration::from_secs(29)))
+                .contains(&source)
+        );
+
+        assert!(
+            default_providers(Timestamp::from_unix_epoch(Duration::from_secs(30)))
+                .contains(&source)
+        );
+    }
+
+    #[test]
+    fn should_not_record_requests_without_quota() {
+        init_state_with_quota(SupportedRpcProviderId::HeliusMainnet, 1, 60);
+
+        for _ in 0..10 {
+            record_request(PROVIDER, Timestamp::UNIX_EPOCH);
+        }
+
+        assert!(default_providers(Timestamp::UNIX_EPOCH).contains(&RpcSource::Supported(PROVIDER)));
+    }
+
+    #[test]
+    fn should_select_fastest_providers_for_expedited_requests() {
+        init_state_with_quota(SupportedRpcProviderId::HeliusMainnet, 1, 60);
+        let fast_provider = SupportedRpcProviderId::PublicNodeMainnet;
+        let source = RpcSource::Supported(fast_provider);
+        assert!(!default_providers(Timestamp::UNIX_EPOCH).contains(&source));
+
+        let host = access_hostname(&get_provider_access(&fast_provider)).unwrap();
+        record_host_latency(host, Duration::from_millis(50), Timestamp::UNIX_EPOCH);
+
+        assert!(!default_providers(Timestamp::UNIX_EPOCH).contains(&source));
+        let expedited = Providers::new(
+            RpcSources::Default(SolanaCluster::Mainnet),
+            ConsensusStrategy::default(),
+            RequestPriority::Expedited,
+            Timestamp::UNIX_EPOCH,
+        )
+        .unwrap();
+        assert!(expedited.sources.contains(&source));
+        assert_eq!(expedited.priority, RequestPriority::Expedited);
+    }
+
+    fn default_providers(now: Timestamp) -> Vec<RpcSource> {
+        Providers::new(
+            RpcSources::Default(SolanaCluster::Mainnet),
+            ConsensusStrategy::default(),
+            RequestPriority::Normal,
+            now,
+        )
+        .unwrap()
+        .sources
+        .into_iter()
+        .collect()
+    }
+
+    fn init_state_with_quota(
+        provider: SupportedRpcProviderId,
+        max_requests: u32,
+        window_seconds: u32,
+    ) {
+        reset_state();
+        let mut state = State::default();
+        state.set_provider_quotas(vec![(
+            provider,
+            ProviderQuota {
+                max_requests,
+                window_seconds,
+            },
+        )]);
+        init_state(state);
+    }
+}
+
+mod supported_rpc_provider_requests {
+    use crate::providers::{QuotaStatus, SupportedRpcProviderRequests};
+    use canhttp::multi::Timestamp;
+    use sol_rpc_types::{ProviderQuota, SupportedRpcProviderId};
+    use std::time::Duration;
+
+    const PROVIDER: SupportedRpcProviderId = SupportedRpcProviderId::HeliusMainnet;
+    const QUOTA: ProviderQuota = ProviderQuota {
+        max_requests: 3,
+        window_seconds: 60,
+    };
+
+    #[test]
+    fn should_not_be_exhausted_without_requests() {
+        let mut requests = SupportedRpcProviderRequests::default();
+
+        assert!(!requests.contains(&PROVIDER));
+        assert_eq!(
+            requests.quota_status_evict(PROVIDER, &QUOTA, Timestamp::UNIX_EPOCH),
+            QuotaStatus::Available
+        );
+    }
+
+    #[test]
+    fn should_be_exhausted_after_max_requests_within_window() {
+        let mut requests = SupportedRpcProviderRequests::default();
+
+        requests.record_evict(PROVIDER, &QUOTA, at_secs(0));
+        requests.record_evict(PROVIDER, &QUOTA, at_secs(10));
+        assert_eq!(
+            requests.quota_status_evict(PROVIDER, &QUOTA, at_secs(20)),
+            QuotaStatus::Available
+        );
+
+        requests.record_evict(PROVIDER, &QUOTA, at_secs(20));
+        assert_eq!(
+            requests.quota_status_evict(PROVIDER, &QUOTA, at_secs(20)),
+            QuotaStatus::NewlyExhausted
+        );
+        assert_eq!(
+            requests.quota_status_evict(PROVIDER, &QUOTA, at_secs(60)),
+            QuotaStatus::Exhausted
+        );
+        assert_eq!(
+            requests.quota_status_evict(
+                SupportedRpcProviderId::AlchemyMainnet,
+                &QUOTA,
+                at_secs(20)
+            ),
+            QuotaStatus::Available
+        );
+
+        // The first request is outside the sliding window
+        assert_eq!(
+            requests.quota_status_evict(PROVIDER, &QUOTA, at_secs(61)),
+            QuotaStatus::Available
+        );
+
+        requests.record_evict(PROVIDER, &QUOTA, at_secs(61));
+        assert_eq!(
+            requests.quota_status_evict(PROVIDER, &QUOTA, at_secs(61)),
+            QuotaStatus::NewlyExhausted
+        );
+    }
+
+    #[test]
+    fn should_evict_all_requests_after_window() {
+        let mut requests = SupportedRpcProviderRequests::default();
+        for _ in 0..10 {
+            requests.record_evict(PROVIDER, &QUOTA, at_secs(0));
+        }
+        assert!(requests
+            .quota_status_evict(PROVIDER, &QUOTA, at_secs(0))
+            .is_exhausted());
+
+        assert!(!requests
+            .quota_status_evict(PROVIDER, &QUOTA, at_secs(61))
+            .is_exhausted());
+        assert!(!requests.contains(&PROVIDER));
+    }
+
+    fn at_secs(secs: u64) -> Timestamp {
+        Timestamp::from_unix_epoch(Duration::from_secs(secs))
+    }
+}
+
+mod supported_rpc_provider_rate_limits {
+    use crate::providers::SupportedRpcProviderRateLimits;
+    use canhttp::multi::Timestamp;
+    use sol_rpc_types::SupportedRpcProviderId;
+    use std::time::Duration;
+
+    const PROVIDER: SupportedRpcProviderId = SupportedRpcProviderId::HeliusMainnet;
+
+    #[test]
+    fn should_be_rate_limited_until_retry_after() {
+        let mut limits = SupportedRpcProviderRateLimits::default();
+        assert!(!limits.is_rate_limited_evict(PROVIDER, at_secs(0)));
+
+        limits.record(PROVIDER, Some(10), at_secs(0));
+        assert!(limits.is_rate_limited_evict(PROVIDER, at_secs(9)));
+        assert!(!limits.is_rate_limited_evict(SupportedRpcProviderId::AlchemyMainnet, at_secs(9)));
+
+        assert!(!limits.is_rate_limited_evict(PROVIDER, at_secs(10)));
+        assert_eq!(limits, SupportedRpcProviderRateLimits::default());
+    }
+
+    #[test]
+    fn should_use_default_backoff() {
+        let mut limits = SupportedRpcProviderRateLimits::default();
+        let default_backoff = SupportedRpcProviderRateLimits::DEFAULT_BACKOFF.as_secs();
+
+        limits.record(PROVIDER, None, at_secs(0));
+
+        assert!(limits.is_rate_limited_evict(PROVIDER, at_secs(default_backoff - 1)));
+        assert!(!limits.is_rate_limited_evict(PROVIDER, at_secs(default_backoff)));
+    }
+
+    #[test]
+    fn should_cap_backoff() {
+        let mut limits = SupportedRpcProviderRateLimits::default();
+        let max_backoff = SupportedRpcProviderRateLimits::MAX_BACKOFF.as_secs();
+
+        limits.record(PROVIDER, Some(u64::MAX), at_secs(0));
+
+        assert!(limits.is_rate_limited_evict(PROVIDER, at_secs(max_backoff - 1)));
+        assert!(!limits.is_rate_limited_evict(PROVIDER, at_secs(max_backoff)));
+    }
+
+    #[test]
+    fn should_keep_latest_retry_time() {
+        let mut limits = SupportedRpcProviderRateLimits::default();
+
+        limits.record(PROVIDER, Some(60), at_secs(0));
+        limits.record(PROVIDER, Some(10), at_secs(1));
+
+        assert!(limits.is_rate_limited_evict(PROVIDER, at_secs(59)));
+        assert!(!limits.is_rate_limited_evict(PROVIDER, at_secs(60)));
+    }
+
+    fn at_secs(secs: u64) -> Timestamp {
+        Timestamp::from_unix_epoch(Duration::from_secs(secs))
+    }
+}
+
+mod host_latencies {
+    use crate::providers::HostLatencies;
+    use canhttp::multi::Timestamp;
+    use std::time::Duration;
+
+    #[test]
+    fn should_compute_moving_average_of_latencies() {
+        let mut latencies = HostLatencies::default();
+        assert_eq!(latencies.get("example.com"), None);
+
+        latencies.record(
+            "example.com".to_string(),
+            Duration::from_millis(100),
+            Timestamp::UNIX_EPOCH,
+        );
+        assert_eq!(
+            latencies.get("example.com"),
+            Some(Duration::from_millis(100))
+        );
+
+        latencies.record(
+            "example.com".to_string(),
+            Duration::from_millis(600),
+            Timestamp::UNIX_EPOCH,
+        );
+        assert_eq!(
+            latencies.get("example.com"),
+            Some(Duration::from_millis(200))
+        );
+    }
+
+    #[test]
+    fn should_evict_least_recently_updated_host() {
+        let mut latencies = HostLatencies::default();
+        for i in 0..HostLatencies::MAX_NUM_HOSTS {
+            latencies.record(
+                format!("host{i}.com"),
+                Duration::from_millis(100),
+                Timestamp::from_unix_epoch(Duration::from_secs(i as u64)),
+            );
+        }
+        // Updating the first host makes the second one the least recently updated.
+        latencies.record(
+            "host0.com".to_string(),
+            Duration::from_millis(100),
+            Timestamp::from_unix_epoch(Duration::from_secs(1_000)),
+        );
+
+        latencies.record(
+            "new.com".to_string(),
+            Duration::from_millis(100),
+            Timestamp::from_unix_epoch(Duration::from_secs(1_001)),
+        );
+
+        assert!(latencies.get("new.com").is_some());
+        assert!(latencies.get("host0.com").is_some());
+        assert_eq!(latencies.get("host1.com"), None);
+        assert!(latencies.get("host2.com").is_some());
+    }
+}
+
+mod solana_cluster_outages {
+    use crate::providers::SolanaClusterOutages;
+    use canhttp::multi::Timestamp;
+    use sol_rpc_types::{ClusterOutage, SolanaCluster};
+    use std::time::Duration;
+
+    #[test]
+    fn should_record_outage_until_recovery() {
+        let mut outages = SolanaClusterOutages::default();
+        assert_eq!(
+            outages.record_success(SolanaCluster::Mainnet, at_secs(0)),
+            None
+        );
+
+        assert!(outages.record_failure(SolanaCluster::Mainnet, at_secs(1)));
+        assert!(!outages.record_failure(SolanaCluster::Mainnet, at_secs(2)));
+        assert_eq!(outages.get(SolanaCluster::Devnet), None);
+        assert_eq!(
+            outages.get(SolanaCluster::Mainnet),
+            Some(outage(1, 2, None, 2))
+        );
+
+        assert_eq!(
+            outages.record_success(SolanaCluster::Mainnet, at_secs(3)),
+            Some(outage(1, 2, Some(3), 2))
+        );
+        assert_eq!(
+            outages.record_success(SolanaCluster::Mainnet, at_secs(4)),
+            None
+        );
+        assert_eq!(
+            outages.get(SolanaCluster::Mainnet),
+            Some(outage(1, 2, Some(3), 2))
+        );
+    }
+
+    #[test]
+    fn should_start_new_outage_after_recovery() {
+        let mut outages = SolanaClusterOutages::default();
+
+        assert!(outages.record_failure(SolanaCluster::Mainnet, at_secs(1)));
+        outages.record_success(SolanaCluster::Mainnet, at_secs(2));
+        assert!(outages.record_failure(SolanaCluster::Mainnet, at_secs(3)));
+
+        assert_eq!(
+            outages.get(SolanaCluster::Mainnet),
+            Some(outage(3, 3, None, 1))
+        );
+    }
+
+    fn outage(
+        started_at: u64,
+        last_failure_at: u64,
+        ended_at: Option<u64>,
+        num_failed_requests: u64,
+    ) -> ClusterOutage {
+        ClusterOutage {
+            started_at: at_secs(started_at).as_nanos_since_unix_epoch(),
+            last_failure_at: at_secs(last_failure_at).as_nanos_since_unix_epoch(),
+            ended_at: ended_at.map(|secs| at_secs(secs).as_nanos_since_unix_epoch()),
+            num_failed_requests,
+        }
+    }
+
+    fn at_secs(secs: u64) -> Timestamp {
+        Timestamp::from_unix_epoch(Duration::from_secs(secs))
+    }
 }
 
 mod supported_rpc_provider_usage {
@@ -208,3 +714,505 @@ mod supported_rpc_provider_usage {
         ]
     }
 }
+
+mod resolve_custom_rpc_provider {
+    use crate::{
+        memory::{init_state, reset_state, State},
+        providers::resolve_rpc_provider,
+        types::ApiKey,
+    };
+    use ic_management_canister_types::HttpHeader;
+    use sol_rpc_types::{RpcEndpoint, RpcEndpointApiKey, RpcEndpointAuth, RpcError, RpcSource};
+
+    #[test]
+    fn should_not_modify_endpoint_without_auth() {
+        init_state_with_custom_api_key("https://my-solana-rpc.com/", "stored-key");
+        let endpoint = RpcEndpoint::new("https://my-solana-rpc.com/")
+            .with_headers(vec![header("X-Custom", "value")]);
+
+        let resolved = resolve_rpc_provider(RpcSource::Custom(endpoint.clone()));
+
+        assert_eq!(resolved, Ok(endpoint));
+    }
+
+    #[test]
+    fn should_add_bearer_token() {
+        init_state_with_custom_api_key("https://my-solana-rpc.com/", "stored-key");
+        for (api_key, expected_key) in [
+            (
+                RpcEndpointApiKey::Value("given-key".to_string()),
+                "given-key",
+            ),
+            (RpcEndpointApiKey::Stored, "stored-key"),
+        ] {
+            let resolved = resolve_rpc_provider(RpcSource::Custom(
+                RpcEndpoint::new("https://my-solana-rpc.com/")
+                    .with_headers(vec![header("X-Custom", "value")])
+                    .with_auth(RpcEndpointAuth::BearerToken { api_key }),
+            ));
+
+            assert_eq!(
+                resolved,
+                Ok(
+                    RpcEndpoint::new("https://my-solana-rpc.com/").with_headers(vec![
+                        header("X-Custom", "value"),
+                        header("Authorization", &format!("Bearer {expected_key}")),
+                    ])
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn should_insert_api_key_in_url() {
+        init_state_with_custom_api_key(
+            "https://my-solana-rpc.com/?api-key={API_KEY}",
+            "stored-key",
+        );
+        for (api_key, expected_key) in [
+            (
+                RpcEndpointApiKey::Value("given-key".to_string()),
+                "given-key",
+            ),
+            (RpcEndpointApiKey::Stored, "stored-key"),
+        ] {
+            let resolved = resolve_rpc_provider(RpcSource::Custom(
+                RpcEndpoint::new("https://my-solana-rpc.com/?api-key={API_KEY}")
+                    .with_auth(RpcEndpointAuth::UrlParameter { api_key }),
+            ));
+
+            assert_eq!(
+                resolved,
+                Ok(RpcEndpoint::new(format!(
+                    "https://my-solana-rpc.com/?api-key={expected_key}"
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_when_url_has_no_api_key_placeholder() {
+        init_state_with_custom_api_key("https://my-solana-rpc.com/", "stored-key");
+
+        let resolved = resolve_rpc_provider(RpcSource::Custom(
+            RpcEndpoint::new("https://my-solana-rpc.com/").with_auth(
+                RpcEndpointAuth::UrlParameter {
+                    api_key: RpcEndpointApiKey::Stored,
+                },
+            ),
+        ));
+
+        assert_eq!(
+            resolved,
+            Err(RpcError::ValidationError(
+                "Missing {API_KEY} placeholder in URL".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_not_use_stored_api_key_for_other_url() {
+        init_state_with_custom_api_key("https://my-solana-rpc.com/", "stored-key");
+
+        for url in [
+            "https://attacker.com/my-solana-rpc.com",
+            "https://my-solana-rpc.com/attacker",
+            "http://my-solana-rpc.com/",
+        ] {
+            let resolved = resolve_rpc_provider(RpcSource::Custom(
+                RpcEndpoint::new(url).with_auth(RpcEndpointAuth::BearerToken {
+                    api_key: RpcEndpointApiKey::Stored,
+                }),
+            ));
+
+            assert_eq!(
+                resolved,
+                Err(RpcError::ValidationError(format!(
+                    "No API key stored for URL: {url}"
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_for_invalid_api_key() {
+        init_state_with_custom_api_key("https://my-solana-rpc.com/", "stored-key");
+
+        let resolved = resolve_rpc_provider(RpcSource::Custom(
+            RpcEndpoint::new("https://my-solana-rpc.com/").with_auth(
+                RpcEndpointAuth::BearerToken {
+                    api_key: RpcEndpointApiKey::Value("".to_string()),
+                },
+            ),
+        ));
+
+        assert_eq!(
+            resolved,
+            Err(RpcError::ValidationError(
+                "Invalid API key: API key must not be an empty string".to_string()
+            ))
+        );
+    }
+
+    fn init_state_with_custom_api_key(url: &str, api_key: &str) {
+        reset_state();
+        let mut state = State::default();
+        state.insert_custom_api_key(
+            url.to_string(),
+            ApiKey::try_from(api_key.to_string()).unwrap(),
+        );
+        init_state(state);
+    }
+
+    fn header(name: &str, value: &str) -> HttpHeader {
+        HttpHeader {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+mod resolve_supported_rpc_provider {
+    use crate::{
+        memory::{init_state, mutate_state, reset_state, State},
+        providers::{resolve_rpc_provider, resolve_rpc_providers},
+        types::ApiKey,
+    };
+    use ic_management_canister_types::HttpHeader;
+    use maplit::btreemap;
+    use sol_rpc_types::{
+        IpVersion, ProviderEndpoint, ProviderError, RpcAccess, RpcAuth, RpcEndpoint, RpcError,
+        RpcSource, SupportedRpcProviderId,
+    };
+
+    #[test]
+    fn should_use_api_key_when_set() {
+        init_state_with(
+            &[(SupportedRpcProviderId::HeliusMainnet, "helius-key")],
+            vec![(
+                SupportedRpcProviderId::HeliusMainnet,
+                vec![SupportedRpcProviderId::DrpcMainnet],
+            )],
+        );
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet)),
+            Ok(endpoint(
+                "https://mainnet.helius-rpc.com/?api-key=helius-key"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_use_fallback_with_api_key() {
+        init_state_with(
+            &[(SupportedRpcProviderId::AlchemyMainnet, "alchemy-key")],
+            vec![(
+                SupportedRpcProviderId::HeliusMainnet,
+                vec![
+                    SupportedRpcProviderId::AlchemyMainnet,
+                    SupportedRpcProviderId::DrpcMainnet,
+                ],
+            )],
+        );
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet)),
+            Ok(
+                RpcEndpoint::new("https://solana-mainnet.g.alchemy.com/v2").with_headers(vec![
+                    HttpHeader {
+                        name: "Authorization".to_string(),
+                        value: "Bearer alchemy-key".to_string(),
+                    }
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn should_skip_fallback_without_api_key_nor_public_url() {
+        init_state_with(
+            &[],
+            vec![(
+                SupportedRpcProviderId::HeliusMainnet,
+                vec![
+                    SupportedRpcProviderId::ChainstackMainnet,
+                    SupportedRpcProviderId::DrpcMainnet,
+                ],
+            )],
+        );
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet)),
+            Ok(endpoint("https://solana.drpc.org"))
+        );
+    }
+
+    #[test]
+    fn should_prefer_fallback_over_public_url() {
+        init_state_with(
+            &[(SupportedRpcProviderId::HeliusMainnet, "helius-key")],
+            vec![(
+                SupportedRpcProviderId::DrpcMainnet,
+                vec![SupportedRpcProviderId::HeliusMainnet],
+            )],
+        );
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::DrpcMainnet)),
+            Ok(endpoint(
+                "https://mainnet.helius-rpc.com/?api-key=helius-key"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_use_public_url_without_fallback() {
+        init_state_with(&[], vec![]);
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::DrpcMainnet)),
+            Ok(endpoint("https://solana.drpc.org"))
+        );
+    }
+
+    #[test]
+    fn should_fail_without_api_key_nor_public_url() {
+        init_state_with(
+            &[],
+            vec![(
+                SupportedRpcProviderId::HeliusMainnet,
+                vec![SupportedRpcProviderId::ChainstackMainnet],
+            )],
+        );
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet)),
+            Err(RpcError::ProviderError(ProviderError::InvalidRpcConfig(
+                "API key not yet initialized for provider: HeliusMainnet".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn should_not_query_same_provider_twice_with_overlapping_fallbacks() {
+        init_state_with(
+            &[],
+            vec![
+                (
+                    SupportedRpcProviderId::AnkrMainnet,
+                    vec![
+                        SupportedRpcProviderId::DrpcMainnet,
+                        SupportedRpcProviderId::PublicNodeMainnet,
+                    ],
+                ),
+                (
+                    SupportedRpcProviderId::HeliusMainnet,
+                    vec![
+                        SupportedRpcProviderId::PublicNodeMainnet,
+                        SupportedRpcProviderId::DrpcMainnet,
+                    ],
+                ),
+            ],
+        );
+        let sources = [
+            SupportedRpcProviderId::AnkrMainnet,
+            SupportedRpcProviderId::DrpcMainnet,
+            SupportedRpcProviderId::HeliusMainnet,
+        ]
+        .map(RpcSource::Supported);
+
+        assert_eq!(
+            resolve_rpc_providers(&sources.clone().into()),
+            btreemap! {
+                // dRPC is already queried, so Ankr falls back to PublicNode
+                sources[0].clone() => Ok((endpoint("https://solana-rpc.publicnode.com"), false)),
+                sources[1].clone() => Ok((endpoint("https://solana.drpc.org"), false)),
+                // Both fallbacks of Helius are already queried
+                sources[2].clone() => Err(RpcError::ProviderError(ProviderError::InvalidRpcConfig(
+                    "API key not yet initialized for provider: HeliusMainnet".to_string()
+                ))),
+            }
+        );
+    }
+
+    #[test]
+    fn should_tell_whether_api_key_is_used() {
+        init_state_with(
+            &[(SupportedRpcProviderId::AlchemyMainnet, "alchemy-key")],
+            vec![(
+                SupportedRpcProviderId::HeliusMainnet,
+                vec![SupportedRpcProviderId::AlchemyMainnet],
+            )],
+        );
+
+        let uses_api_key = |source: RpcSource| {
+            resolve_rpc_providers(&[source.clone()].into())
+                .remove(&source)
+                .unwrap()
+                .unwrap()
+                .1
+        };
+
+        assert!(uses_api_key(RpcSource::Supported(
+            SupportedRpcProviderId::AlchemyMainnet
+        )));
+        // Falls back to Alchemy, which has an API key
+        assert!(uses_api_key(RpcSource::Supported(
+            SupportedRpcProviderId::HeliusMainnet
+        )));
+        assert!(!uses_api_key(RpcSource::Supported(
+            SupportedRpcProviderId::DrpcMainnet
+        )));
+        assert!(!uses_api_key(RpcSource::Custom(RpcEndpoint::new(
+            "https://solana.drpc.org"
+        ))));
+    }
+
+    #[test]
+    fn should_use_preferred_endpoint_with_api_key() {
+        init_state_with(&[(SupportedRpcProviderId::AnkrMainnet, "ankr-key")], vec![]);
+        mutate_state(|s| {
+            s.set_provider_endpoints(vec![(
+                SupportedRpcProviderId::AnkrMainnet,
+                ProviderEndpoint {
+                    access: RpcAccess::Authenticated {
+                        auth: RpcAuth::UrlParameter {
+                            url_pattern: "https://ipv6.rpc.ankr.com/solana/{API_KEY}".to_string(),
+                        },
+                        public_url: None,
+                    },
+                    ip_version: IpVersion::Ipv6,
+                },
+            )])
+        });
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet)),
+            Ok(endpoint("https://ipv6.rpc.ankr.com/solana/ankr-key"))
+        );
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::AnkrDevnet)),
+            Ok(endpoint("https://rpc.ankr.com/solana_devnet/"))
+        );
+    }
+
+    #[test]
+    fn should_use_preferred_endpoint_of_fallback() {
+        init_state_with(
+            &[],
+            vec![(
+                SupportedRpcProviderId::HeliusMainnet,
+                vec![SupportedRpcProviderId::PublicNodeMainnet],
+            )],
+        );
+        mutate_state(|s| {
+            s.set_provider_endpoints(vec![(
+                SupportedRpcProviderId::PublicNodeMainnet,
+                ProviderEndpoint {
+                    access: RpcAccess::Unauthenticated {
+                        public_url: "https://ipv6.solana-rpc.publicnode.com".to_string(),
+                    },
+                    ip_version: IpVersion::Ipv6,
+                },
+            )])
+        });
+
+        assert_eq!(
+            resolve_rpc_provider(RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet)),
+            Ok(endpoint("https://ipv6.solana-rpc.publicnode.com"))
+        );
+    }
+
+    fn init_state_with(
+        api_keys: &[(SupportedRpcProviderId, &str)],
+        provider_fallbacks: Vec<(SupportedRpcProviderId, Vec<SupportedRpcProviderId>)>,
+    ) {
+        reset_state();
+        let mut state = State::default();
+        for (provider, api_key) in api_keys {
+            state.insert_api_key(*provider, ApiKey::try_from(api_key.to_string()).unwrap());
+        }
+        state.set_provider_fallbacks(provider_fallbacks);
+        init_state(state);
+    }
+
+    fn endpoint(url: &str) -> RpcEndpoint {
+        RpcEndpoint::new(url)
+    }
+}
+
+mod known_ip_version {
+    use crate::{
+        memory::{init_state, mutate_state, reset_state, State},
+        providers::known_ip_version,
+    };
+    use sol_rpc_types::{
+        IpVersion, ProviderEndpoint, RpcAccess, RpcEndpoint, RpcSource, SupportedRpcProviderId,
+    };
+
+    #[test]
+    fn should_use_static_table_of_known_hosts() {
+        init_state_with_ipv6_publicnode_endpoint();
+
+        assert_eq!(
+            known_ip_version(
+                &RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                "solana-mainnet.g.alchemy.com"
+            ),
+            Some(IpVersion::Ipv4)
+        );
+        assert_eq!(
+            known_ip_version(
+                &RpcSource::Custom(RpcEndpoint::new("https://rpc.ankr.com/solana")),
+                "rpc.ankr.com"
+            ),
+            Some(IpVersion::DualStack)
+        );
+        assert_eq!(
+            known_ip_version(
+                &RpcSource::Supported(SupportedRpcProviderId::ChainstackMainnet),
+                "solana-mainnet.core.chainstack.com"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn should_prefer_ip_version_of_preferred_endpoint() {
+        init_state_with_ipv6_publicnode_endpoint();
+
+        assert_eq!(
+            known_ip_version(
+                &RpcSource::Supported(SupportedRpcProviderId::PublicNodeMainnet),
+                "my-ipv6-gateway.example.com"
+            ),
+            Some(IpVersion::Ipv6)
+        );
+        // The preferred endpoint of a provider is ignored for other hosts, e.g. when falling
+        // back to another provider.
+        assert_eq!(
+            known_ip_version(
+                &RpcSource::Supported(SupportedRpcProviderId::PublicNodeMainnet),
+                "unknown.example.com"
+            ),
+            None
+        );
+    }
+
+    fn init_state_with_ipv6_publicnode_endpoint() {
+        reset_state();
+        init_state(State::default());
+        mutate_state(|s| {
+            s.set_provider_endpoints(vec![(
+                SupportedRpcProviderId::PublicNodeMainnet,
+                ProviderEndpoint {
+                    access: RpcAccess::Unauthenticated {
+                        public_url: "https://my-ipv6-gateway.example.com".to_string(),
+                    },
+                    ip_version: IpVersion::Ipv6,
+                },
+            )])
+        });
+    }
+}
@@ -0,0 +1,229 @@
+//! Diagnostics of inconsistent responses from RPC providers.
+//!
+//! When the responses from the different providers cannot be reduced to a single result, the
+//! responses are normalized to JSON values and compared field by field, to summarize how they
+//! differ and to guess the most likely cause of the inconsistency.
+
+#[cfg(test)]
+mod tests;
+
+use crate::metrics::MetricInconsistencyCause;
+use serde::Serialize;
+use serde_json::Value;
+use sol_rpc_types::{RpcResult, RpcSource};
+use std::{collections::BTreeSet, fmt::Write};
+
+/// Maximum number of differing fields listed in a summary.
+const MAX_DIFFS: usize = 10;
+/// Maximum length of a value displayed in a summary.
+const MAX_VALUE_LENGTH: usize = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InconsistencyDiagnostics {
+    pub cause: MetricInconsistencyCause,
+    pub summary: String,
+}
+
+/// A field whose value is not the same for all providers, identified by its path in the
+/// JSON representation of the responses, e.g. `context.slot` or `[0].signature`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Diff {
+    path: String,
+    values: Vec<Option<Value>>,
+}
+
+/// Summarizes how the given results differ and guesses the cause of the inconsistency.
+///
+/// Responses of the `getSlot` method are slots, so that they are always diagnosed as a slot skew
+/// when they differ. For other methods, a slot skew is diagnosed when only fields whose name
+/// contains `slot` differ.
+pub fn diagnose_inconsistent_results<Output: Serialize>(
+    method: &str,
+    results: &[(&RpcSource, &RpcResult<Output>)],
+) -> InconsistencyDiagnostics {
+    let sources: Vec<String> = results
+        .iter()
+        .map(|(source, _result)| source_label(source))
+        .collect();
+
+    if results.iter().any(|(_source, result)| result.is_err()) {
+        let mut summary = String::from("providers returned different errors or results: ");
+        let outcomes: Vec<String> = sources
+            .iter()
+            .zip(results)
+            .map(|(source, (_, result))| match result {
+                Ok(_) => format!("{source}: Ok"),
+                Err(e) => format!("{source}: {}", truncate(e.to_string())),
+            })
+            .collect();
+        summary.push_str(&outcomes.join(", "));
+        return InconsistencyDiagnostics {
+            cause: MetricInconsistencyCause::ProviderError,
+            summary,
+        };
+    }
+
+    let values: Vec<Option<Value>> = results
+        .iter()
+        .map(|(_source, result)| {
+            result
+                .as_ref()
+                .ok()
+                .and_then(|output| serde_json::to_value(output).ok())
+        })
+        .collect();
+    let mut diffs = Vec::new();
+    collect_diffs(String::new(), &values, &mut diffs);
+
+    let slot_skew = if method == "getSlot" || diffs.iter().all(is_slot_diff) {
+        max_skew(&diffs)
+    } else {
+        None
+    };
+    let mut summary = format!("{} differing field(s)", diffs.len());
+    let cause = match slot_skew {
+        Some(skew) => {
+            let _ = write!(summary, " with a slot skew of {skew}");
+            MetricInconsistencyCause::SlotSkew
+        }
+        None => MetricInconsistencyCause::PayloadMismatch,
+    };
+    for diff in diffs.iter().take(MAX_DIFFS) {
+        let values: Vec<String> = sources
+            .iter()
+            .zip(&diff.values)
+            .map(|(source, value)| match value {
+                Some(value) => format!("{source}: {}", truncate(value.to_string())),
+                None => format!("{source}: <missing>"),
+            })
+            .collect();
+        let path = if diff.path.is_empty() {
+            "<root>"
+        } else {
+            diff.path.as_str()
+        };
+        let _ = write!(summary, "; `{path}` = [{}]", values.join(", "));
+    }
+    if diffs.len() > MAX_DIFFS {
+        let _ = write!(summary, "; ... and {} more", diffs.len() - MAX_DIFFS);
+    }
+    InconsistencyDiagnostics { cause, summary }
+}
+
+fn collect_diffs(path: String, values: &[Option<Value>], diffs: &mut Vec<Diff>) {
+    let Some(first) = values.first() else {
+        return;
+    };
+    if values.iter().all(|value| value == first) {
+        return;
+    }
+    if values
+        .iter()
+        .all(|value| matches!(value, Some(Value::Object(_))))
+    {
+        let keys: BTreeSet<&String> = values
+            .iter()
+            .flat_map(|value| value.as_ref().and_then(Value::as_object))
+            .flat_map(|object| object.keys())
+            .collect();
+        for key in keys {
+            let children: Vec<Option<Value>> = values
+                .iter()
+                .map(|value| value.as_ref().and_then(|value| value.get(key)).cloned())
+                .collect();
+            let child_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+            collect_diffs(child_path, &children, diffs);
+        }
+        return;
+    }
+    let lengths: Vec<Option<usize>> = values
+        .iter()
+        .map(|value| value.as_ref().and_then(Value::as_array).map(Vec::len))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    // Arrays are only compared element-wise if they all have the same length
+    if let [Some(length)] = lengths[..] {
+        for index in 0..length {
+            let children: Vec<Option<Value>> = values
+                .iter()
+                .map(|value| value.as_ref().and_then(|value| value.get(index)).cloned())
+                .collect();
+            collect_diffs(format!("{path}[{index}]"), &children, diffs);
+        }
+        return;
+    }
+    diffs.push(Diff {
+        path,
+        values: values.to_vec(),
+    });
+}
+
+fn is_slot_diff(diff: &Diff) -> bool {
+    let field = diff
+        .path
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .split('[')
+        .next()
+        .unwrap_or_default();
+    field.to_ascii_lowercase().contains("slot")
+}
+
+/// Returns the spread, i.e. the difference between the largest and the smallest slot, of the
+/// slots returned by the providers for a `getSlot` request, ignoring failed requests.
+///
+/// Slots are compared after being rounded, so that the spread is normalized to a multiple of
+/// the rounding error of the request. Returns `None` if fewer than two providers returned a slot.
+pub fn slot_skew<Output: Serialize>(results: &[(&RpcSource, &RpcResult<Output>)]) -> Option<u64> {
+    let slots: Vec<u64> = results
+        .iter()
+        .filter_map(|(_source, result)| result.as_ref().ok())
+        .filter_map(|output| serde_json::to_value(output).ok()?.as_u64())
+        .collect();
+    if slots.len() < 2 {
+        return None;
+    }
+    Some(slots.iter().max()? - slots.iter().min()?)
+}
+
+/// Returns the largest difference between the values of a field, if all differing
+/// values are integers.
+fn max_skew(diffs: &[Diff]) -> Option<u64> {
+    let mut max_skew = None;
+    for diff in diffs {
+        let slots = diff
+            .values
+            .iter()
+            .map(|value| value.as_ref().and_then(Value::as_u64))
+            .collect::<Option<Vec<_>>>()?;
+        let skew = slots.iter().max()? - slots.iter().min()?;
+        max_skew = max_skew.max(Some(skew));
+    }
+    max_skew
+}
+
+fn source_label(source: &RpcSource) -> String {
+    match source {
+        RpcSource::Supported(provider) => format!("{provider:?}"),
+        // Only the host is displayed, since the URL or headers could contain API keys
+        RpcSource::Custom(endpoint) => endpoint.host_str().unwrap_or("Custom".to_string()),
+    }
+}
+
+fn truncate(mut value: String) -> String {
+    if value.len() > MAX_VALUE_LENGTH {
+        let mut end = MAX_VALUE_LENGTH;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        value.push_str("...");
+    }
+    value
+}
@@ -0,0 +1,225 @@
+use crate::{
+    metrics::MetricInconsistencyCause,
+    rpc_client::diagnostics::{diagnose_inconsistent_results, slot_skew, InconsistencyDiagnostics},
+};
+use serde_json::{json, Value};
+use sol_rpc_types::{
+    JsonRpcError, RpcEndpoint, RpcError, RpcResult, RpcSource, SupportedRpcProviderId,
+};
+
+const ALCHEMY: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet);
+const ANKR: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet);
+const HELIUS: RpcSource = RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet);
+
+#[test]
+fn should_diagnose_provider_error() {
+    let results = [
+        (&ALCHEMY, Ok(json!(1_450_300))),
+        (
+            &ANKR,
+            Err(RpcError::JsonRpcError(JsonRpcError {
+                code: -32_007,
+                message: "Slot 1450300 was skipped".to_string(),
+                data: None,
+            })),
+        ),
+    ];
+
+    let diagnostics = diagnose(&results, "getBlock");
+
+    assert_eq!(diagnostics.cause, MetricInconsistencyCause::ProviderError);
+    assert!(diagnostics.summary.starts_with(
+        "providers returned different errors or results: AlchemyMainnet: Ok, AnkrMainnet: "
+    ));
+    assert!(diagnostics
+        .summary
+        .ends_with("(code: -32007): Slot 1450300 was skipped"));
+}
+
+#[test]
+fn should_diagnose_slot_skew_for_get_slot() {
+    let results = [
+        (&ALCHEMY, Ok(json!(1_450_300))),
+        (&ANKR, Ok(json!(1_450_303))),
+        (&HELIUS, Ok(json!(1_450_300))),
+    ];
+
+    let diagnostics = diagnose(&results, "getSlot");
+
+    assert_eq!(
+        diagnostics,
+        InconsistencyDiagnostics {
+            cause: MetricInconsistencyCause::SlotSkew,
+            summary: "1 differing field(s) with a slot skew of 3; `<root>` = [AlchemyMainnet: 1450300, AnkrMainnet: 1450303, HeliusMainnet: 1450300]".to_string(),
+        }
+    );
+}
+
+#[test]
+fn should_diagnose_slot_skew_for_fields_containing_slots() {
+    let results = [
+        (
+            &ALCHEMY,
+            Ok(json!({"context": {"slot": 10}, "value": {"lamports": 5}})),
+        ),
+        (
+            &ANKR,
+            Ok(json!({"context": {"slot": 12}, "value": {"lamports": 5}})),
+        ),
+    ];
+
+    let diagnostics = diagnose(&results, "getBalance");
+
+    assert_eq!(
+        diagnostics,
+        InconsistencyDiagnostics {
+            cause: MetricInconsistencyCause::SlotSkew,
+            summary: "1 differing field(s) with a slot skew of 2; `context.slot` = [AlchemyMainnet: 10, AnkrMainnet: 12]".to_string(),
+        }
+    );
+}
+
+#[test]
+fn should_diagnose_payload_mismatch() {
+    let results = [
+        (
+            &ALCHEMY,
+            Ok(json!({"context": {"slot": 10}, "value": {"lamports": 5}})),
+        ),
+        (
+            &ANKR,
+            Ok(json!({"context": {"slot": 12}, "value": {"lamports": 6}})),
+        ),
+    ];
+
+    let diagnostics = diagnose(&results, "getBalance");
+
+    assert_eq!(
+        diagnostics,
+        InconsistencyDiagnostics {
+            cause: MetricInconsistencyCause::PayloadMismatch,
+            summary: "2 differing field(s); `context.slot` = [AlchemyMainnet: 10, AnkrMainnet: 12]; `value.lamports` = [AlchemyMainnet: 5, AnkrMainnet: 6]".to_string(),
+        }
+    );
+}
+
+#[test]
+fn should_compare_arrays_element_wise_when_lengths_match() {
+    let results = [
+        (
+            &ALCHEMY,
+            Ok(json!([{"signature": "a", "slot": 1}, {"signature": "b", "slot": 2}])),
+        ),
+        (
+            &ANKR,
+            Ok(json!([{"signature": "a", "slot": 1}, {"signature": "c", "slot": 2}])),
+        ),
+    ];
+
+    let diagnostics = diagnose(&results, "getSignaturesForAddress");
+
+    assert_eq!(diagnostics.cause, MetricInconsistencyCause::PayloadMismatch);
+    assert!(diagnostics
+        .summary
+        .ends_with(r#"`[1].signature` = [AlchemyMainnet: "b", AnkrMainnet: "c"]"#));
+
+    let results = [(&ALCHEMY, Ok(json!([1, 2]))), (&ANKR, Ok(json!([1, 2, 3])))];
+
+    let diagnostics = diagnose(&results, "getBlocks");
+
+    assert_eq!(
+        diagnostics.summary,
+        "1 differing field(s); `<root>` = [AlchemyMainnet: [1,2], AnkrMainnet: [1,2,3]]"
+    );
+}
+
+#[test]
+fn should_report_missing_fields() {
+    let results = [
+        (&ALCHEMY, Ok(json!({"blockTime": 1, "blockHeight": 2}))),
+        (&ANKR, Ok(json!({"blockTime": 1}))),
+    ];
+
+    let diagnostics = diagnose(&results, "getBlock");
+
+    assert_eq!(
+        diagnostics.summary,
+        "1 differing field(s); `blockHeight` = [AlchemyMainnet: 2, AnkrMainnet: <missing>]"
+    );
+}
+
+#[test]
+fn should_bound_summary_size() {
+    let first: Value = (0..20)
+        .map(|i| (format!("field{i:02}"), json!(i)))
+        .collect();
+    let second: Value = (0..20)
+        .map(|i| (format!("field{i:02}"), json!("x".repeat(200))))
+        .collect();
+    let results = [(&ALCHEMY, Ok(first)), (&ANKR, Ok(second))];
+
+    let diagnostics = diagnose(&results, "getAccountInfo");
+
+    assert!(diagnostics.summary.starts_with("20 differing field(s)"));
+    assert!(diagnostics.summary.ends_with("; ... and 10 more"));
+    assert!(diagnostics.summary.contains("field09"));
+    assert!(!diagnostics.summary.contains("field10"));
+    assert!(!diagnostics.summary.contains(&"x".repeat(101)));
+}
+
+#[test]
+fn should_only_display_host_of_custom_sources() {
+    let custom = RpcSource::Custom(RpcEndpoint::new("https://rpc.example.com/?api-key=secret"));
+    let results = [(&ALCHEMY, Ok(json!(1))), (&custom, Ok(json!(2)))];
+
+    let diagnostics = diagnose(&results, "getBalance");
+
+    assert!(diagnostics.summary.contains("rpc.example.com: 2"));
+    assert!(!diagnostics.summary.contains("secret"));
+}
+
+#[test]
+fn should_compute_slot_skew() {
+    let results = [
+        (&ALCHEMY, Ok(json!(1_450_300))),
+        (&ANKR, Ok(json!(1_450_340))),
+        (&HELIUS, Ok(json!(1_450_320))),
+    ];
+
+    assert_eq!(skew(&results), Some(40));
+}
+
+#[test]
+fn should_compute_slot_skew_ignoring_errors() {
+    let error = || Err(RpcError::ValidationError("Invalid slot".to_string()));
+
+    assert_eq!(
+        skew(&[
+            (&ALCHEMY, Ok(json!(1_450_300))),
+            (&ANKR, error()),
+            (&HELIUS, Ok(json!(1_450_300)))
+        ]),
+        Some(0)
+    );
+    assert_eq!(
+        skew(&[(&ALCHEMY, Ok(json!(1_450_300))), (&ANKR, error())]),
+        None
+    );
+    assert_eq!(skew(&[(&ALCHEMY, error()), (&ANKR, error())]), None);
+}
+
+fn diagnose(results: &[(&RpcSource, RpcResult<Value>)], method: &str) -> InconsistencyDiagnostics {
+    let results: Vec<_> = results
+        .iter()
+        .map(|(source, result)| (*source, result))
+        .collect();
+    diagnose_inconsistent_results(method, &results)
+}
+
+fn skew(results: &[(&RpcSource, RpcResult<Value>)]) -> Option<u64> {
+    let results: Vec<_> = results
+        .iter()
+        .map(|(source, result)| (*source, result))
+        .collect();
+    slot_skew(&results)
+}
@@ -1,11 +1,12 @@
 use derive_more::From;
-use serde::Serialize;
+use serde::{ser::SerializeTuple, Deserialize, Serialize, Serializer};
 use serde_tuple::Serialize_tuple;
 use serde_with::skip_serializing_none;
 use sol_rpc_types::{
-    CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetBlockCommitmentLevel,
-    GetSignaturesForAddressLimit, GetTransactionEncoding, Pubkey, SendTransactionEncoding,
-    Signature, Slot, TransactionDetails,
+    CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetBlockCommitmentLevel, GetBlockEncoding,
+    GetBlocksLimit, GetRecentPerformanceSamplesLimit, GetSignaturesForAddressLimit,
+    GetSlotLeadersLimit, GetTransactionEncoding, Hash, Pubkey, RpcError, SendTransactionEncoding,
+    Signature, Slot, TokenAccountsFilter, TransactionDetails,
 };
 use solana_transaction_status_client_types::UiTransactionEncoding;
 
@@ -73,6 +74,49 @@ impl From<sol_rpc_types::GetAccountInfoParams> for GetAccountInfoParams {
     }
 }
 
+impl From<sol_rpc_types::HasAccountChangedParams> for GetAccountInfoParams {
+    fn from(params: sol_rpc_types::HasAccountChangedParams) -> Self {
+        let sol_rpc_types::HasAccountChangedParams {
+            pubkey,
+            // The account is hashed by the SOL RPC canister on the response.
+            last_known_hash: _,
+            commitment,
+            data_slice,
+            min_context_slot,
+        } = params;
+        Self {
+            pubkey,
+            // The account data is always requested with the same encoding, so that the account
+            // hash does not depend on it.
+            config: Some(GetAccountInfoConfig {
+                commitment,
+                encoding: Some(GetAccountInfoEncoding::Base64),
+                data_slice,
+                min_context_slot,
+            }),
+        }
+    }
+}
+
+/// The result of a `getAccountInfo` RPC method call, once the account was hashed and compared
+/// to the last known hash by the SOL RPC canister.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AccountChange {
+    pub hash: Option<Hash>,
+    pub changed: bool,
+    pub account: Option<solana_account_decoder_client_types::UiAccount>,
+}
+
+impl From<AccountChange> for sol_rpc_types::AccountChange {
+    fn from(change: AccountChange) -> Self {
+        Self {
+            hash: change.hash,
+            changed: change.changed,
+            account: change.account.map(sol_rpc_types::AccountInfo::from),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct GetAccountInfoConfig {
@@ -144,11 +188,16 @@ impl From<sol_rpc_types::GetBlockParams> for GetBlockParams {
             max_supported_transaction_version,
             transaction_details,
             rewards,
+            encoding,
         } = params;
         // We always use a non-null config since the default value for `transaction_details` is
         // `none` which is different from the Solana RPC API default of `full`.
         let config = Some(GetBlockConfig {
-            encoding: None,
+            encoding: encoding.map(|encoding| match encoding {
+                GetBlockEncoding::Base64 => UiTransactionEncoding::Base64,
+                GetBlockEncoding::Base58 => UiTransactionEncoding::Base58,
+                GetBlockEncoding::JsonParsed => UiTransactionEncoding::JsonParsed,
+            }),
             transaction_details: Some(transaction_details.unwrap_or_default()),
             rewards,
             commitment,
@@ -170,6 +219,238 @@ pub struct GetBlockConfig {
     pub max_supported_transaction_version: Option<u8>,
 }
 
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetBlockHeightParams {
+    config: Option<GetSlotConfig>,
+}
+
+impl From<sol_rpc_types::GetBlockHeightParams> for GetBlockHeightParams {
+    fn from(params: sol_rpc_types::GetBlockHeightParams) -> Self {
+        let sol_rpc_types::GetBlockHeightParams {
+            commitment,
+            min_context_slot,
+        } = params;
+        let config = if commitment.is_none() && min_context_slot.is_none() {
+            None
+        } else {
+            Some(GetSlotConfig {
+                commitment,
+                min_context_slot,
+            })
+        };
+        Self { config }
+    }
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetBlocksParams {
+    start_slot: Slot,
+    end_slot: Slot,
+    config: Option<GetBlocksConfig>,
+}
+
+impl GetBlocksParams {
+    pub fn num_slots(&self) -> u64 {
+        self.end_slot.saturating_sub(self.start_slot) + 1
+    }
+}
+
+impl TryFrom<sol_rpc_types::GetBlocksParams> for GetBlocksParams {
+    type Error = RpcError;
+
+    fn try_from(params: sol_rpc_types::GetBlocksParams) -> Result<Self, Self::Error> {
+        let sol_rpc_types::GetBlocksParams {
+            start_slot,
+            end_slot,
+            commitment,
+        } = params;
+        let max_end_slot = start_slot.saturating_add(GetBlocksLimit::MAX_LIMIT as u64 - 1);
+        // Always use an explicit end slot, since the Solana RPC API otherwise defaults to the
+        // latest block, resulting in a range of unbounded size.
+        let end_slot = end_slot.unwrap_or(max_end_slot);
+        if end_slot < start_slot || end_slot > max_end_slot {
+            return Err(RpcError::ValidationError(format!(
+                "Expected an end slot between {start_slot} and {max_end_slot}, but got {end_slot}"
+            )));
+        }
+        let config = commitment.map(|commitment| GetBlocksConfig {
+            commitment: Some(commitment),
+        });
+        Ok(Self {
+            start_slot,
+            end_slot,
+            config,
+        })
+    }
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetBlocksWithLimitParams {
+    start_slot: Slot,
+    limit: GetBlocksLimit,
+    config: Option<GetBlocksConfig>,
+}
+
+impl GetBlocksWithLimitParams {
+    pub fn get_limit(&self) -> u32 {
+        self.limit.into()
+    }
+}
+
+impl From<sol_rpc_types::GetBlocksWithLimitParams> for GetBlocksWithLimitParams {
+    fn from(params: sol_rpc_types::GetBlocksWithLimitParams) -> Self {
+        let sol_rpc_types::GetBlocksWithLimitParams {
+            start_slot,
+            limit,
+            commitment,
+        } = params;
+        let config = commitment.map(|commitment| GetBlocksConfig {
+            commitment: Some(commitment),
+        });
+        Self {
+            start_slot,
+            limit,
+            config,
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct GetBlocksConfig {
+    pub commitment: Option<GetBlockCommitmentLevel>,
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetEpochInfoParams {
+    config: Option<GetSlotConfig>,
+}
+
+impl From<sol_rpc_types::GetEpochInfoParams> for GetEpochInfoParams {
+    fn from(params: sol_rpc_types::GetEpochInfoParams) -> Self {
+        let sol_rpc_types::GetEpochInfoParams {
+            commitment,
+            min_context_slot,
+        } = params;
+        let config = if commitment.is_none() && min_context_slot.is_none() {
+            None
+        } else {
+            Some(GetSlotConfig {
+                commitment,
+                min_context_slot,
+            })
+        };
+        Self { config }
+    }
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetFeeForMessageParams {
+    message: String,
+    config: Option<GetSlotConfig>,
+}
+
+impl TryFrom<sol_rpc_types::GetFeeForMessageParams> for GetFeeForMessageParams {
+    type Error = RpcError;
+
+    fn try_from(params: sol_rpc_types::GetFeeForMessageParams) -> Result<Self, Self::Error> {
+        params.validate()?;
+        let config = if params.commitment.is_some() || params.min_context_slot.is_some() {
+            Some(GetSlotConfig {
+                commitment: params.commitment,
+                min_context_slot: params.min_context_slot,
+            })
+        } else {
+            None
+        };
+        Ok(GetFeeForMessageParams {
+            message: params.get_message().to_string(),
+            config,
+        })
+    }
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetLeaderScheduleParams {
+    slot: Option<Slot>,
+    config: GetLeaderScheduleConfig,
+}
+
+impl GetLeaderScheduleParams {
+    pub fn identity(&self) -> &Pubkey {
+        &self.config.identity
+    }
+}
+
+impl From<sol_rpc_types::GetLeaderScheduleParams> for GetLeaderScheduleParams {
+    fn from(params: sol_rpc_types::GetLeaderScheduleParams) -> Self {
+        let sol_rpc_types::GetLeaderScheduleParams {
+            slot,
+            identity,
+            commitment,
+        } = params;
+        Self {
+            slot,
+            config: GetLeaderScheduleConfig {
+                identity,
+                commitment,
+            },
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct GetLeaderScheduleConfig {
+    pub identity: Pubkey,
+    pub commitment: Option<CommitmentLevel>,
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetMinimumBalanceForRentExemptionParams {
+    data_length: u64,
+    config: Option<GetSlotConfig>,
+}
+
+impl From<sol_rpc_types::GetMinimumBalanceForRentExemptionParams>
+    for GetMinimumBalanceForRentExemptionParams
+{
+    fn from(params: sol_rpc_types::GetMinimumBalanceForRentExemptionParams) -> Self {
+        let sol_rpc_types::GetMinimumBalanceForRentExemptionParams {
+            data_length,
+            commitment,
+        } = params;
+        Self {
+            data_length,
+            config: commitment.map(|commitment| GetSlotConfig {
+                commitment: Some(commitment),
+                min_context_slot: None,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetRecentPerformanceSamplesParams {
+    limit: GetRecentPerformanceSamplesLimit,
+}
+
+impl GetRecentPerformanceSamplesParams {
+    pub fn get_limit(&self) -> u32 {
+        self.limit.into()
+    }
+}
+
+impl From<sol_rpc_types::GetRecentPerformanceSamplesParams> for GetRecentPerformanceSamplesParams {
+    fn from(params: sol_rpc_types::GetRecentPerformanceSamplesParams) -> Self {
+        // Always set the limit explicitly since Solana nodes otherwise return the maximum
+        // number of samples.
+        Self {
+            limit: params.limit.unwrap_or_default(),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Serialize, Clone, Debug)]
 #[serde(into = "(Vec<Pubkey>,)")]
@@ -212,6 +493,10 @@ impl From<sol_rpc_types::GetSignaturesForAddressParams> for GetSignaturesForAddr
             limit,
             before,
             until,
+            // Block time filters are applied by the canister on the responses
+            // and are not part of the Solana JSON-RPC API.
+            min_block_time: _,
+            max_block_time: _,
         } = params;
         let config = if commitment.is_some()
             || min_context_slot.is_some()
@@ -276,6 +561,25 @@ pub struct GetSignatureStatusesConfig {
     pub search_transaction_history: bool,
 }
 
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetSlotLeadersParams {
+    start_slot: Slot,
+    limit: GetSlotLeadersLimit,
+}
+
+impl GetSlotLeadersParams {
+    pub fn get_limit(&self) -> u32 {
+        self.limit.into()
+    }
+}
+
+impl From<sol_rpc_types::GetSlotLeadersParams> for GetSlotLeadersParams {
+    fn from(params: sol_rpc_types::GetSlotLeadersParams) -> Self {
+        let sol_rpc_types::GetSlotLeadersParams { start_slot, limit } = params;
+        Self { start_slot, limit }
+    }
+}
+
 #[derive(Serialize_tuple, Clone, Debug)]
 pub struct GetTokenAccountBalanceParams {
     pubkey: Pubkey,
@@ -300,6 +604,104 @@ pub struct GetTokenAccountBalanceConfig {
     pub commitment: Option<CommitmentLevel>,
 }
 
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetTokenAccountsByDelegateParams {
+    delegate: Pubkey,
+    filter: TokenAccountsFilter,
+    config: Option<GetAccountInfoConfig>,
+}
+
+impl From<sol_rpc_types::GetTokenAccountsByDelegateParams> for GetTokenAccountsByDelegateParams {
+    fn from(params: sol_rpc_types::GetTokenAccountsByDelegateParams) -> Self {
+        let sol_rpc_types::GetTokenAccountsByDelegateParams {
+            delegate,
+            filter,
+            commitment,
+            encoding,
+            data_slice,
+            min_context_slot,
+        } = params;
+        let config = if commitment.is_none()
+            && encoding.is_none()
+            && data_slice.is_none()
+            && min_context_slot.is_none()
+        {
+            None
+        } else {
+            Some(GetAccountInfoConfig {
+                commitment,
+                encoding,
+                data_slice,
+                min_context_slot,
+            })
+        };
+        Self {
+            delegate,
+            filter,
+            config,
+        }
+    }
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetTokenAccountsByOwnerParams {
+    owner: Pubkey,
+    filter: TokenAccountsFilter,
+    config: Option<GetAccountInfoConfig>,
+}
+
+impl From<sol_rpc_types::GetTokenAccountsByOwnerParams> for GetTokenAccountsByOwnerParams {
+    fn from(params: sol_rpc_types::GetTokenAccountsByOwnerParams) -> Self {
+        let sol_rpc_types::GetTokenAccountsByOwnerParams {
+            owner,
+            filter,
+            commitment,
+            encoding,
+            data_slice,
+            min_context_slot,
+            // Pagination is applied by the SOL RPC canister on the response.
+            after: _,
+            limit: _,
+        } = params;
+        let config = if commitment.is_none()
+            && encoding.is_none()
+            && data_slice.is_none()
+            && min_context_slot.is_none()
+        {
+            None
+        } else {
+            Some(GetAccountInfoConfig {
+                commitment,
+                encoding,
+                data_slice,
+                min_context_slot,
+            })
+        };
+        Self {
+            owner,
+            filter,
+            config,
+        }
+    }
+}
+
+/// A Solana account together with its address, as returned e.g. by the
+/// `getTokenAccountsByDelegate` RPC method.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct KeyedUiAccount {
+    pub pubkey: Pubkey,
+    pub account: solana_account_decoder_client_types::UiAccount,
+}
+
+impl From<KeyedUiAccount> for sol_rpc_types::KeyedAccount {
+    fn from(account: KeyedUiAccount) -> Self {
+        Self {
+            pubkey: account.pubkey,
+            account: account.account.into(),
+        }
+    }
+}
+
 #[derive(Serialize_tuple, Clone, Debug)]
 pub struct GetTransactionParams {
     signature: Signature,
@@ -339,14 +741,80 @@ pub struct GetTransactionConfig {
     pub encoding: Option<GetTransactionEncoding>,
 }
 
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetSupplyParams {
+    config: Option<GetSupplyConfig>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct GetSupplyConfig {
+    pub commitment: Option<CommitmentLevel>,
+    #[serde(rename = "excludeNonCirculatingAccountsList")]
+    pub exclude_non_circulating_accounts_list: Option<bool>,
+}
+
+impl GetSupplyParams {
+    pub fn excludes_non_circulating_accounts_list(&self) -> bool {
+        self.config
+            .as_ref()
+            .and_then(|config| config.exclude_non_circulating_accounts_list)
+            .unwrap_or_default()
+    }
+}
+
+impl From<sol_rpc_types::GetSupplyParams> for GetSupplyParams {
+    fn from(params: sol_rpc_types::GetSupplyParams) -> Self {
+        let sol_rpc_types::GetSupplyParams {
+            commitment,
+            exclude_non_circulating_accounts_list,
+        } = params;
+        let config = if commitment.is_none() && exclude_non_circulating_accounts_list.is_none() {
+            None
+        } else {
+            Some(GetSupplyConfig {
+                commitment,
+                exclude_non_circulating_accounts_list,
+            })
+        };
+        Self { config }
+    }
+}
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct GetTransactionCountParams {
+    config: Option<GetSlotConfig>,
+}
+
+impl From<sol_rpc_types::GetTransactionCountParams> for GetTransactionCountParams {
+    fn from(params: sol_rpc_types::GetTransactionCountParams) -> Self {
+        let sol_rpc_types::GetTransactionCountParams {
+            commitment,
+            min_context_slot,
+        } = params;
+        let config = if commitment.is_none() && min_context_slot.is_none() {
+            None
+        } else {
+            Some(GetSlotConfig {
+                commitment,
+                min_context_slot,
+            })
+        };
+        Self { config }
+    }
+}
+
 #[derive(Serialize_tuple, Clone, Debug)]
 pub struct SendTransactionParams {
     transaction: String,
     config: Option<SendTransactionConfig>,
 }
 
-impl From<sol_rpc_types::SendTransactionParams> for SendTransactionParams {
-    fn from(params: sol_rpc_types::SendTransactionParams) -> Self {
+impl TryFrom<sol_rpc_types::SendTransactionParams> for SendTransactionParams {
+    type Error = RpcError;
+
+    fn try_from(params: sol_rpc_types::SendTransactionParams) -> Result<Self, Self::Error> {
+        params.validate()?;
         let transaction = params.get_transaction().to_string();
         let encoding = params.get_encoding().cloned();
         let sol_rpc_types::SendTransactionParams {
@@ -372,10 +840,10 @@ impl From<sol_rpc_types::SendTransactionParams> for SendTransactionParams {
                 min_context_slot,
             })
         };
-        Self {
+        Ok(Self {
             transaction,
             config,
-        }
+        })
     }
 }
 
@@ -392,3 +860,64 @@ pub struct SendTransactionConfig {
     #[serde(rename = "minContextSlot")]
     pub min_context_slot: Option<u64>,
 }
+
+#[derive(Serialize_tuple, Clone, Debug)]
+pub struct IsBlockhashValidParams {
+    blockhash: String,
+    config: Option<IsBlockhashValidConfig>,
+}
+
+impl TryFrom<&sol_rpc_types::SendTransactionParams> for IsBlockhashValidParams {
+    type Error = RpcError;
+
+    fn try_from(params: &sol_rpc_types::SendTransactionParams) -> Result<Self, Self::Error> {
+        let transaction = params.decode_transaction()?;
+        let blockhash = transaction.message.recent_blockhash().to_string();
+        let config = if params.preflight_commitment.is_none() && params.min_context_slot.is_none() {
+            None
+        } else {
+            Some(IsBlockhashValidConfig {
+                commitment: params.preflight_commitment.clone(),
+                min_context_slot: params.min_context_slot,
+            })
+        };
+        Ok(Self { blockhash, config })
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct IsBlockhashValidConfig {
+    pub commitment: Option<CommitmentLevel>,
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<u64>,
+}
+
+/// Parameters for Solana RPC methods that do not take any parameters, such as `getHealth`.
+///
+/// Serialized as an empty JSON array.
+#[derive(Clone, Debug, Default)]
+pub struct NoParams;
+
+impl Serialize for NoParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_tuple(0)?.end()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GetVersionResult {
+    #[serde(rename = "solana-core")]
+    pub solana_core: String,
+    #[serde(rename = "feature-set")]
+    pub feature_set: Option<u32>,
+}
+
+impl From<GetVersionResult> for sol_rpc_types::SolanaVersion {
+    fn from(result: GetVersionResult) -> Self {
+        Self {
+            solana_core: result.solana_core,
+            feature_set: result.feature_set,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 pub mod cbor;
+mod diagnostics;
 pub mod json;
+mod reduce;
 mod sol_rpc;
 #[cfg(test)]
 mod tests;
@@ -7,16 +9,40 @@ mod tests;
 use crate::{
     add_metric_entry,
     candid_rpc::hostname,
-    constants::DEFAULT_MAX_RESPONSE_BYTES,
+    constants::{API_KEY_REPLACE_STRING, DEFAULT_MAX_RESPONSE_BYTES},
     http::{
-        charging_policy_with_collateral, errors::HttpClientError, http_client,
-        service_request_builder,
+        charging_policy_with_collateral,
+        compression::{accept_compressed_response, expected_body_bytes},
+        concurrency::OutcallDuration,
+        errors::{is_connection_error, with_transport_diagnostics, HttpClientError},
+        http_client, raw_http_client, raw_service_request_builder, resolve_request_id_format,
+        retry::resolve_retry_policy,
+        service_request_builder, CyclesPayer,
     },
+    log,
     logs::Priority,
-    memory::{read_state, record_ok_result},
-    metrics::MetricRpcMethod,
-    providers::{get_provider, request_builder, resolve_rpc_provider, Providers},
-    rpc_client::sol_rpc::ResponseTransform,
+    memory::{
+        get_cluster_outage, get_slot_skew, read_state, record_cluster_outage,
+        record_cluster_recovery, record_ok_result, record_provider_outcome, record_rate_limited,
+        record_request, record_shadow_outcome, record_slot_skew, UNSTABLE_METRICS,
+    },
+    metrics::{MetricRpcMethod, MetricRpcProvider, MetricSolanaCluster},
+    providers::{
+        get_provider, known_ip_version, request_builder, resolve_rpc_provider,
+        resolve_rpc_providers, Providers,
+    },
+    rpc_client::{
+        diagnostics::{diagnose_inconsistent_results, slot_skew},
+        reduce::{
+            ReduceCollectAll, ReduceSendTransaction, ReduceWithMedian,
+            ReduceWithSupermajorityBySlot,
+        },
+        sol_rpc::ResponseTransform,
+    },
+    scorecard::request_outcomes,
+    shadow::shadow_outcomes,
+    types::ApiKey,
+    version_sampler::get_sampled_version,
 };
 use canhttp::{
     cycles::CyclesChargingPolicy,
@@ -24,21 +50,26 @@ use canhttp::{
     multi::{MultiResults, Reduce, ReduceWithEquality, ReduceWithThreshold, Timestamp},
     MaxResponseBytesRequestExtension, TransformContextRequestExtension,
 };
-use canlog::log;
 use http::{Request, Response};
 use ic_cdk_management_canister::{
     HttpRequestArgs as IcHttpRequest, TransformContext, TransformFunc,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use sol_rpc_types::{
-    ConfirmedTransactionStatusWithSignature, ConsensusStrategy,
-    GetRecentPrioritizationFeesRpcConfig, GetSlotRpcConfig, Lamport, PrioritizationFee,
-    ProviderError, RpcConfig, RpcError, RpcResult, RpcSource, RpcSources, Signature,
-    TransactionDetails,
+    AccountInfo, AccountSnapshot, ClusterNode, ConfirmedTransactionStatusWithSignature,
+    ConsensusStrategy, EpochInfo, EpochSchedule, GetAccountSnapshotParams, GetBlockHeightRpcConfig,
+    GetEpochInfoRpcConfig, GetRecentPrioritizationFeesRpcConfig, GetSlotRpcConfig,
+    GetSupplyRpcConfig, GetTransactionCountRpcConfig, HttpHeader, HttpOutcallError,
+    HttpRequestPreview, JsonRequestRpcConfig, JsonRpcError, Lamport, LegacyRejectionCode,
+    PerformanceSample, PrioritizationFee, ProviderError, ProviderHealth, ProviderHealthReport,
+    Pubkey, RawHttpRequestParams, RawHttpResponse, RoundingError, RpcConfig, RpcEndpoint,
+    RpcEndpointApiKey, RpcError, RpcResult, RpcSource, RpcSources, Signature, SnapshotSlotInfo,
+    SolanaCluster, SolanaVersion, Supply, SupportedRpcProviderId, TransactionDetails, WithContext,
+    WithContextRpcConfig,
 };
 use solana_clock::Slot;
-use std::{fmt::Debug, marker::PhantomData};
-use tower::ServiceExt;
+use std::{collections::BTreeMap, fmt::Debug, marker::PhantomData, time::Duration};
+use tower::{Service, ServiceExt};
 
 // This constant is our approximation of the expected header size.
 // The HTTP standard doesn't define any limit, and many implementations limit
@@ -104,7 +135,12 @@ impl GetAccountInfoRequest {
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
             .unwrap_or(512 + HEADER_SIZE_LIMIT);
@@ -119,6 +155,73 @@ impl GetAccountInfoRequest {
     }
 }
 
+pub type GetAccountInfoWithContextRequest = MultiRpcRequest<
+    json::GetAccountInfoParams,
+    WithContext<Option<solana_account_decoder_client_types::UiAccount>>,
+>;
+
+impl GetAccountInfoWithContextRequest {
+    pub fn get_account_info_with_context<Params: Into<json::GetAccountInfoParams>>(
+        rpc_sources: RpcSources,
+        config: WithContextRpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(512 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getAccountInfo", params.into()),
+            max_response_bytes,
+            ResponseTransform::WithContext(config.context_slot_rounding_error.unwrap_or_default()),
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type HasAccountChangedRequest =
+    MultiRpcRequest<json::GetAccountInfoParams, json::AccountChange>;
+
+impl HasAccountChangedRequest {
+    pub fn has_account_changed(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: sol_rpc_types::HasAccountChangedParams,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let transform = ResponseTransform::HasAccountChanged {
+            last_known_hash: params.last_known_hash.as_ref().map(ToString::to_string),
+        };
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(512 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getAccountInfo", params.into()),
+            max_response_bytes,
+            transform,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
 pub type GetBalanceRequest = MultiRpcRequest<json::GetBalanceParams, Lamport>;
 
 impl GetBalanceRequest {
@@ -129,7 +232,12 @@ impl GetBalanceRequest {
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
             .unwrap_or(256 + HEADER_SIZE_LIMIT);
@@ -144,6 +252,37 @@ impl GetBalanceRequest {
     }
 }
 
+pub type GetBalanceWithContextRequest =
+    MultiRpcRequest<json::GetBalanceParams, WithContext<Lamport>>;
+
+impl GetBalanceWithContextRequest {
+    pub fn get_balance_with_context<Params: Into<json::GetBalanceParams>>(
+        rpc_sources: RpcSources,
+        config: WithContextRpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(256 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getBalance", params.into()),
+            max_response_bytes,
+            ResponseTransform::WithContext(config.context_slot_rounding_error.unwrap_or_default()),
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
 pub type GetBlockRequest = MultiRpcRequest<
     json::GetBlockParams,
     Option<solana_transaction_status_client_types::UiConfirmedBlock>,
@@ -158,7 +297,12 @@ impl GetBlockRequest {
     ) -> Result<Self, ProviderError> {
         let params = params.into();
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
             .unwrap_or(Self::response_size_estimate(&params));
@@ -173,17 +317,239 @@ impl GetBlockRequest {
     }
 
     fn response_size_estimate(params: &json::GetBlockParams) -> u64 {
-        let mut cycles = HEADER_SIZE_LIMIT;
-        cycles += match params.get_transaction_details() {
+        let mut body_bytes = match params.get_transaction_details() {
             Some(TransactionDetails::Accounts) => DEFAULT_MAX_RESPONSE_BYTES,
             Some(TransactionDetails::Signatures) => 256 * 1024,
             Some(TransactionDetails::None) | None => 512,
         };
-        cycles += match params.include_rewards() {
+        body_bytes += match params.include_rewards() {
             Some(true) | None => 256,
             Some(false) => 0,
         };
-        DEFAULT_MAX_RESPONSE_BYTES.min(cycles)
+        DEFAULT_MAX_RESPONSE_BYTES.min(expected_body_bytes(body_bytes) + HEADER_SIZE_LIMIT)
+    }
+}
+
+pub type GetBlockHeightRequest = MultiRpcRequest<json::GetBlockHeightParams, u64>;
+
+impl GetBlockHeightRequest {
+    pub fn get_block_height<Params: Into<json::GetBlockHeightParams>>(
+        rpc_sources: RpcSources,
+        config: GetBlockHeightRpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(64 + HEADER_SIZE_LIMIT);
+        let rounding_error = config.rounding_error.unwrap_or_default();
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getBlockHeight", params.into()),
+            max_response_bytes,
+            ResponseTransform::GetBlockHeight(rounding_error),
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetBlocksRequest = MultiRpcRequest<json::GetBlocksParams, Vec<Slot>>;
+
+impl GetBlocksRequest {
+    pub fn get_blocks<Params: TryInto<json::GetBlocksParams, Error = RpcError>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> RpcResult<Self> {
+        let params = params.try_into()?;
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or((params.num_slots() * 21) + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getBlocks", params),
+            max_response_bytes,
+            ResponseTransform::GetBlocks,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetBlocksWithLimitRequest = MultiRpcRequest<json::GetBlocksWithLimitParams, Vec<Slot>>;
+
+impl GetBlocksWithLimitRequest {
+    pub fn get_blocks_with_limit<Params: Into<json::GetBlocksWithLimitParams>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let params = params.into();
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or((params.get_limit() as u64 * 21) + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getBlocksWithLimit", params),
+            max_response_bytes,
+            ResponseTransform::GetBlocks,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetEpochInfoRequest = MultiRpcRequest<json::GetEpochInfoParams, EpochInfo>;
+
+impl GetEpochInfoRequest {
+    pub fn get_epoch_info<Params: Into<json::GetEpochInfoParams>>(
+        rpc_sources: RpcSources,
+        config: GetEpochInfoRpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(256 + HEADER_SIZE_LIMIT);
+        let rounding_error = config.rounding_error.unwrap_or_default();
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getEpochInfo", params.into()),
+            max_response_bytes,
+            ResponseTransform::GetEpochInfo(rounding_error),
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetFeeForMessageRequest = MultiRpcRequest<json::GetFeeForMessageParams, Option<Lamport>>;
+
+impl GetFeeForMessageRequest {
+    pub fn get_fee_for_message<Params: TryInto<json::GetFeeForMessageParams, Error = RpcError>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> RpcResult<Self> {
+        let params = params.try_into()?;
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(256 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getFeeForMessage", params),
+            max_response_bytes,
+            ResponseTransform::GetFeeForMessage,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetLeaderScheduleRequest =
+    MultiRpcRequest<json::GetLeaderScheduleParams, Option<Vec<u64>>>;
+
+impl GetLeaderScheduleRequest {
+    pub fn get_leader_schedule<Params: Into<json::GetLeaderScheduleParams>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let params = params.into();
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        // An epoch has 432,000 slots, of which even the validators with the largest stake only
+        // lead a few percent.
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or_else(|| expected_body_bytes(128 * 1024) + HEADER_SIZE_LIMIT);
+        let identity = params.identity().to_string();
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getLeaderSchedule", params),
+            max_response_bytes,
+            ResponseTransform::GetLeaderSchedule { identity },
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetMinimumBalanceForRentExemptionRequest =
+    MultiRpcRequest<json::GetMinimumBalanceForRentExemptionParams, Lamport>;
+
+impl GetMinimumBalanceForRentExemptionRequest {
+    pub fn get_minimum_balance_for_rent_exemption<
+        Params: Into<json::GetMinimumBalanceForRentExemptionParams>,
+    >(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(128 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getMinimumBalanceForRentExemption", params.into()),
+            max_response_bytes,
+            ResponseTransform::GetMinimumBalanceForRentExemption,
+            ReductionStrategy::from(consensus_strategy),
+        ))
     }
 }
 
@@ -197,24 +563,33 @@ pub type GetSignaturesForAddressRequest = MultiRpcRequest<
 >;
 
 impl GetSignaturesForAddressRequest {
-    pub fn get_signatures_for_address<Params: Into<json::GetSignaturesForAddressParams>>(
+    pub fn get_signatures_for_address(
         rpc_sources: RpcSources,
         config: RpcConfig,
-        params: Params,
+        params: sol_rpc_types::GetSignaturesForAddressParams,
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
-        let params = params.into();
+        let transform = ResponseTransform::GetSignaturesForAddress {
+            min_block_time: params.min_block_time,
+            max_block_time: params.max_block_time,
+        };
+        let params = json::GetSignaturesForAddressParams::from(params);
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
-        let max_response_bytes = config
-            .response_size_estimate
-            .unwrap_or((params.get_limit() as u64 * 256) + HEADER_SIZE_LIMIT);
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config.response_size_estimate.unwrap_or_else(|| {
+            expected_body_bytes(params.get_limit() as u64 * 256) + HEADER_SIZE_LIMIT
+        });
 
         Ok(MultiRpcRequest::new(
             providers,
             JsonRpcRequest::new("getSignaturesForAddress", params),
             max_response_bytes,
-            ResponseTransform::GetSignaturesForAddress,
+            transform,
             ReductionStrategy::from(consensus_strategy),
         ))
     }
@@ -234,7 +609,12 @@ impl GetSignatureStatusesRequest {
     ) -> Result<Self, ProviderError> {
         let params = params.into();
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
             .unwrap_or(128 + (params.num_signatures() as u64 * 256) + HEADER_SIZE_LIMIT);
@@ -259,7 +639,12 @@ impl GetSlotRequest {
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
             .unwrap_or(64 + HEADER_SIZE_LIMIT);
@@ -275,6 +660,38 @@ impl GetSlotRequest {
     }
 }
 
+pub type GetRecentPerformanceSamplesRequest =
+    MultiRpcRequest<json::GetRecentPerformanceSamplesParams, Vec<PerformanceSample>>;
+
+impl GetRecentPerformanceSamplesRequest {
+    pub fn get_recent_performance_samples<Params: Into<json::GetRecentPerformanceSamplesParams>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let params = params.into();
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or((params.get_limit() as u64 * 128) + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getRecentPerformanceSamples", params),
+            max_response_bytes,
+            ResponseTransform::GetRecentPerformanceSamples,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
 pub type GetRecentPrioritizationFeesRequest =
     MultiRpcRequest<json::GetRecentPrioritizationFeesParams, Vec<PrioritizationFee>>;
 
@@ -287,7 +704,12 @@ impl GetRecentPrioritizationFeesRequest {
     ) -> Result<Self, ProviderError> {
         let max_length = config.max_length();
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
             .unwrap_or(8 * 1024 + HEADER_SIZE_LIMIT);
@@ -305,6 +727,38 @@ impl GetRecentPrioritizationFeesRequest {
     }
 }
 
+pub type GetSlotLeadersRequest = MultiRpcRequest<json::GetSlotLeadersParams, Vec<Pubkey>>;
+
+impl GetSlotLeadersRequest {
+    pub fn get_slot_leaders<Params: Into<json::GetSlotLeadersParams>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let params = params.into();
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        // Each slot leader is a base-58 encoded public key of at most 44 characters.
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or((params.get_limit() as u64 * 47) + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getSlotLeaders", params),
+            max_response_bytes,
+            ResponseTransform::GetSlotLeaders,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
 pub type GetTokenAccountBalanceRequest = MultiRpcRequest<
     json::GetTokenAccountBalanceParams,
     solana_account_decoder_client_types::token::UiTokenAmount,
@@ -318,7 +772,12 @@ impl GetTokenAccountBalanceRequest {
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
             .unwrap_or(256 + HEADER_SIZE_LIMIT);
@@ -333,107 +792,1109 @@ impl GetTokenAccountBalanceRequest {
     }
 }
 
-pub type GetTransactionRequest = MultiRpcRequest<
-    json::GetTransactionParams,
-    Option<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>,
+pub type GetTokenAccountBalanceWithContextRequest = MultiRpcRequest<
+    json::GetTokenAccountBalanceParams,
+    WithContext<solana_account_decoder_client_types::token::UiTokenAmount>,
 >;
 
-impl GetTransactionRequest {
-    pub fn get_transaction<Params: Into<json::GetTransactionParams>>(
+impl GetTokenAccountBalanceWithContextRequest {
+    pub fn get_token_account_balance_with_context<
+        Params: Into<json::GetTokenAccountBalanceParams>,
+    >(
         rpc_sources: RpcSources,
-        config: RpcConfig,
+        config: WithContextRpcConfig,
         params: Params,
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
-            .unwrap_or(8 * 1024 + HEADER_SIZE_LIMIT);
+            .unwrap_or(256 + HEADER_SIZE_LIMIT);
 
         Ok(MultiRpcRequest::new(
             providers,
-            JsonRpcRequest::new("getTransaction", params.into()),
+            JsonRpcRequest::new("getTokenAccountBalance", params.into()),
             max_response_bytes,
-            ResponseTransform::GetBlock,
+            ResponseTransform::WithContext(config.context_slot_rounding_error.unwrap_or_default()),
             ReductionStrategy::from(consensus_strategy),
         ))
     }
 }
 
-pub type SendTransactionRequest = MultiRpcRequest<json::SendTransactionParams, Signature>;
+pub type GetTokenAccountsByDelegateRequest =
+    MultiRpcRequest<json::GetTokenAccountsByDelegateParams, Vec<json::KeyedUiAccount>>;
 
-impl SendTransactionRequest {
-    pub fn send_transaction<Params: Into<json::SendTransactionParams>>(
+impl GetTokenAccountsByDelegateRequest {
+    pub fn get_token_accounts_by_delegate<Params: Into<json::GetTokenAccountsByDelegateParams>>(
         rpc_sources: RpcSources,
         config: RpcConfig,
         params: Params,
         now: Timestamp,
     ) -> Result<Self, ProviderError> {
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        // The number of token accounts of a delegate is not bounded, but is typically small.
         let max_response_bytes = config
             .response_size_estimate
-            .unwrap_or(128 + HEADER_SIZE_LIMIT);
+            .unwrap_or(8 * 1024 + HEADER_SIZE_LIMIT);
 
         Ok(MultiRpcRequest::new(
             providers,
-            JsonRpcRequest::new("sendTransaction", params.into()),
+            JsonRpcRequest::new("getTokenAccountsByDelegate", params.into()),
+            max_response_bytes,
+            ResponseTransform::GetTokenAccountsByDelegate,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetTokenAccountsByOwnerRequest =
+    MultiRpcRequest<json::GetTokenAccountsByOwnerParams, Vec<json::KeyedUiAccount>>;
+
+impl GetTokenAccountsByOwnerRequest {
+    pub fn get_token_accounts_by_owner(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: sol_rpc_types::GetTokenAccountsByOwnerParams,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let transform = ResponseTransform::GetTokenAccountsByOwner {
+            after: params.after.map(|after| after.to_string()),
+            limit: params.limit.map(u32::from),
+        };
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        // The pagination is applied on the response of the providers, which contains all the
+        // token accounts of the owner. Requesting a `dataSlice` helps keeping it small.
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(16 * 1024 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getTokenAccountsByOwner", params.into()),
+            max_response_bytes,
+            transform,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetTransactionRequest = MultiRpcRequest<
+    json::GetTransactionParams,
+    Option<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>,
+>;
+
+impl GetTransactionRequest {
+    pub fn get_transaction<Params: Into<json::GetTransactionParams>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(8 * 1024 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getTransaction", params.into()),
+            max_response_bytes,
+            ResponseTransform::GetBlock,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetSupplyRequest = MultiRpcRequest<json::GetSupplyParams, Supply>;
+
+impl GetSupplyRequest {
+    pub fn get_supply<Params: Into<json::GetSupplyParams>>(
+        rpc_sources: RpcSources,
+        config: GetSupplyRpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let params = params.into();
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        // Each non-circulating account adds roughly 50 bytes to the response, and there are
+        // a few hundred of them on Solana Mainnet.
+        let max_body_bytes = if params.excludes_non_circulating_accounts_list() {
+            256
+        } else {
+            32 * 1024
+        };
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(max_body_bytes + HEADER_SIZE_LIMIT);
+        let rounding_error = config
+            .rounding_error
+            .unwrap_or_else(|| RoundingError::new(GetSupplyRpcConfig::DEFAULT_ROUNDING_ERROR));
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getSupply", params),
+            max_response_bytes,
+            ResponseTransform::GetSupply(rounding_error),
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetTransactionCountRequest = MultiRpcRequest<json::GetTransactionCountParams, u64>;
+
+impl GetTransactionCountRequest {
+    pub fn get_transaction_count<Params: Into<json::GetTransactionCountParams>>(
+        rpc_sources: RpcSources,
+        config: GetTransactionCountRpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(64 + HEADER_SIZE_LIMIT);
+        let rounding_error = config.rounding_error.unwrap_or_else(|| {
+            RoundingError::new(GetTransactionCountRpcConfig::DEFAULT_ROUNDING_ERROR)
+        });
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getTransactionCount", params.into()),
+            max_response_bytes,
+            ResponseTransform::GetTransactionCount(rounding_error),
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type IsBlockhashValidRequest = MultiRpcRequest<json::IsBlockhashValidParams, bool>;
+
+impl IsBlockhashValidRequest {
+    pub fn is_blockhash_valid(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: json::IsBlockhashValidParams,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(128 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("isBlockhashValid", params),
+            max_response_bytes,
+            ResponseTransform::IsBlockhashValid,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type SendTransactionRequest = MultiRpcRequest<json::SendTransactionParams, Signature>;
+
+impl SendTransactionRequest {
+    pub fn send_transaction<Params: TryInto<json::SendTransactionParams, Error = RpcError>>(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: Params,
+        now: Timestamp,
+    ) -> RpcResult<Self> {
+        let params = params.try_into()?;
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(128 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("sendTransaction", params),
             max_response_bytes,
             ResponseTransform::SendTransaction,
+            ReductionStrategy::ForSendTransaction(ReduceSendTransaction::new(
+                ReductionStrategy::from(consensus_strategy),
+            )),
+        ))
+    }
+}
+
+pub type JsonRequest = MultiRpcRequest<serde_json::Value, serde_json::Value>;
+
+impl JsonRequest {
+    pub fn json_request(
+        rpc_sources: RpcSources,
+        config: JsonRequestRpcConfig,
+        json_rpc_payload: String,
+        now: Timestamp,
+    ) -> RpcResult<Self> {
+        let request: JsonRpcRequest<serde_json::Value> =
+            match serde_json::from_str(&json_rpc_payload) {
+                Ok(req) => req,
+                Err(e) => {
+                    return Err(RpcError::ValidationError(format!(
+                        "Invalid JSON RPC request: {e}"
+                    )))
+                }
+            };
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(1024 + HEADER_SIZE_LIMIT);
+        let transform = if config.raw_responses.unwrap_or_default() {
+            if providers.sources.len() != 1 {
+                return Err(RpcError::ValidationError(format!(
+                    "Raw responses require a single provider, but {} were selected",
+                    providers.sources.len()
+                )));
+            }
+            ResponseTransform::Verbatim
+        } else {
+            ResponseTransform::Raw
+        };
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            request,
+            max_response_bytes,
+            transform,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+
+    pub fn preview_request(
+        method: String,
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: String,
+        now: Timestamp,
+    ) -> RpcResult<Self> {
+        let params: serde_json::Value = serde_json::from_str(&params).map_err(|e| {
+            RpcError::ValidationError(format!("Invalid JSON RPC request parameters: {e}"))
+        })?;
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(1024 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new(method, params),
+            max_response_bytes,
+            ResponseTransform::Raw,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+/// A provider-specific HTTP request that is not a JSON-RPC request, e.g. to use REST extensions
+/// offered by some providers.
+///
+/// The responses go through the same cycles accounting and consensus as JSON-RPC requests, but
+/// are otherwise not validated: in particular, responses with a non-successful HTTP status code
+/// are returned as-is.
+pub struct RawHttpRequest {
+    providers: Providers,
+    params: RawHttpRequestParams,
+    max_response_bytes: u64,
+    transform: ResponseTransform,
+    reduction_strategy: ReductionStrategy,
+}
+
+impl RawHttpRequest {
+    const METHOD: &'static str = "rawHttpRequest";
+
+    pub fn new(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: RawHttpRequestParams,
+        now: Timestamp,
+    ) -> RpcResult<Self> {
+        for HttpHeader { name, value } in params.headers.iter().flatten() {
+            http::HeaderName::try_from(name).map_err(|e| {
+                RpcError::ValidationError(format!("Invalid HTTP header name `{name}`: {e}"))
+            })?;
+            http::HeaderValue::try_from(value).map_err(|e| {
+                RpcError::ValidationError(format!("Invalid value for HTTP header `{name}`: {e}"))
+            })?;
+        }
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(1024 + HEADER_SIZE_LIMIT);
+        let response_headers = params
+            .response_headers
+            .iter()
+            .flatten()
+            .map(|name| name.to_ascii_lowercase())
+            .collect();
+
+        Ok(Self {
+            providers,
+            params,
+            max_response_bytes,
+            transform: ResponseTransform::RawHttp { response_headers },
+            reduction_strategy: ReductionStrategy::from(consensus_strategy),
+        })
+    }
+
+    pub fn method(&self) -> &str {
+        Self::METHOD
+    }
+
+    pub async fn send_and_reduce(self) -> (ReducedResult<RawHttpResponse>, OutcallsMetadata) {
+        let method = MetricRpcMethod::from(Self::METHOD.to_string());
+        let cycles_received = ic_cdk::api::msg_cycles_available();
+
+        let strategy = self.reduction_strategy.clone();
+        let (multi_results, metadata) = self.parallel_call().await;
+
+        observe_cycles(method.clone(), cycles_received);
+        observe_total_outage(&method, &multi_results);
+        observe_provider_scores(&multi_results);
+        observe_inconsistent_results(method, &multi_results);
+
+        (multi_results.reduce(strategy), metadata)
+    }
+
+    async fn parallel_call(self) -> (MultiCallResults<RawHttpResponse>, OutcallsMetadata) {
+        let num_providers = self.providers.sources.len();
+        let (requests, errors) = self.create_http_requests().into_inner();
+
+        let client = raw_http_client(
+            MetricRpcMethod::from(Self::METHOD.to_string()),
+            CyclesPayer::Caller,
+            self.providers.priority,
+        )
+        .map_response(|response: Response<Vec<u8>>| {
+            let (parts, body) = response.into_parts();
+            RawHttpResponse {
+                status: parts.status.as_u16(),
+                headers: parts
+                    .headers
+                    .iter()
+                    .map(|(name, value)| HttpHeader {
+                        name: name.to_string(),
+                        value: String::from_utf8_lossy(value.as_bytes()).to_string(),
+                    })
+                    .collect(),
+                body,
+            }
+        });
+
+        let (mut results, metadata) = timed_parallel_call(client, requests).await;
+        results.add_errors(errors);
+        assert_eq!(
+            results.len(),
+            num_providers,
+            "BUG: expected 1 result per provider"
+        );
+        (diagnose_connection_errors(results), metadata)
+    }
+
+    /// Estimate the exact cycles cost for the given request.
+    ///
+    /// *IMPORTANT*: the method is *synchronous* in a canister environment.
+    pub async fn cycles_cost(self) -> RpcResult<u128> {
+        async fn extract_request(
+            request: IcHttpRequest,
+        ) -> Result<Response<IcHttpRequest>, HttpClientError> {
+            Ok(Response::new(request))
+        }
+
+        let (requests, errors) = self.create_http_requests().into_inner();
+        if let Some(error) = errors.into_values().next() {
+            return Err(error);
+        }
+
+        let client = raw_service_request_builder()
+            .service_fn(extract_request)
+            .map_err(|e| {
+                RpcError::try_from(e).unwrap_or_else(|e| {
+                    log!(Priority::Info, "Unrecoverable error: {}", e);
+                    panic!("{}", e);
+                })
+            })
+            .map_response(Response::into_body);
+
+        let (_client, results) = canhttp::multi::parallel_call(client, requests).await;
+        let (requests, errors) = results.into_inner();
+        if let Some(error) = errors.into_values().next() {
+            return Err(error);
+        }
+
+        let policy = charging_policy_with_collateral(CyclesPayer::Caller, self.providers.priority);
+        Ok(requests
+            .into_values()
+            .map(|request| {
+                let request_cycles_cost = ic_cdk_management_canister::cost_http_request(&request);
+                policy.cycles_to_charge(&request, request_cycles_cost)
+            })
+            .sum())
+    }
+
+    fn create_http_requests(self) -> MultiCallResults<Request<Vec<u8>>> {
+        let transform_op = {
+            let mut buf = vec![];
+            minicbor::encode(&self.transform, &mut buf).unwrap();
+            buf
+        };
+        let mut requests = MultiResults::default();
+        for (provider, endpoint) in resolve_rpc_providers(&self.providers.sources) {
+            let request = endpoint
+                .and_then(|(endpoint, uses_api_key)| {
+                    request_builder(endpoint, &read_state(|state| state.get_override_provider()))
+                        .map(|builder| builder.extension(UsesApiKey(uses_api_key)))
+                })
+                .map(|builder| {
+                    if read_state(|state| state.is_response_compression_enabled()) {
+                        accept_compressed_response(builder)
+                    } else {
+                        builder
+                    }
+                })
+                .map(|builder| {
+                    self.params
+                        .headers
+                        .iter()
+                        .flatten()
+                        .fold(builder, |builder, HttpHeader { name, value }| {
+                            builder.header(name, value)
+                        })
+                        .max_response_bytes(self.max_response_bytes)
+                        .transform_context(TransformContext {
+                            function: TransformFunc(candid::Func {
+                                method: "cleanup_response".to_string(),
+                                principal: ic_cdk::api::canister_self(),
+                            }),
+                            context: transform_op.clone(),
+                        })
+                        .body(self.params.body.clone())
+                        .expect("BUG: invalid request")
+                });
+            requests.insert_once(provider.clone(), request);
+        }
+        requests
+    }
+}
+
+pub type GetHealthRequest = MultiRpcRequest<json::NoParams, String>;
+
+impl GetHealthRequest {
+    pub fn get_health(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(256 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getHealth", json::NoParams),
+            max_response_bytes,
+            ResponseTransform::GetHealth,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetVersionRequest = MultiRpcRequest<json::NoParams, json::GetVersionResult>;
+
+impl GetVersionRequest {
+    pub fn get_version(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(256 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getVersion", json::NoParams),
+            max_response_bytes,
+            ResponseTransform::GetVersion,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetHighestSnapshotSlotRequest = MultiRpcRequest<json::NoParams, SnapshotSlotInfo>;
+
+impl GetHighestSnapshotSlotRequest {
+    pub fn get_highest_snapshot_slot(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(128 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getHighestSnapshotSlot", json::NoParams),
+            max_response_bytes,
+            ResponseTransform::GetHighestSnapshotSlot,
             ReductionStrategy::from(consensus_strategy),
         ))
     }
 }
 
-pub type JsonRequest = MultiRpcRequest<serde_json::Value, serde_json::Value>;
+pub type GetEpochScheduleRequest = MultiRpcRequest<json::NoParams, EpochSchedule>;
 
-impl JsonRequest {
-    pub fn json_request(
+impl GetEpochScheduleRequest {
+    pub fn get_epoch_schedule(
         rpc_sources: RpcSources,
         config: RpcConfig,
-        json_rpc_payload: String,
         now: Timestamp,
-    ) -> RpcResult<Self> {
-        let request: JsonRpcRequest<serde_json::Value> =
-            match serde_json::from_str(&json_rpc_payload) {
-                Ok(req) => req,
-                Err(e) => {
-                    return Err(RpcError::ValidationError(format!(
-                        "Invalid JSON RPC request: {e}"
-                    )))
-                }
-            };
+    ) -> Result<Self, ProviderError> {
         let consensus_strategy = config.response_consensus.unwrap_or_default();
-        let providers = Providers::new(rpc_sources, consensus_strategy.clone(), now)?;
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
         let max_response_bytes = config
             .response_size_estimate
-            .unwrap_or(1024 + HEADER_SIZE_LIMIT);
+            .unwrap_or(256 + HEADER_SIZE_LIMIT);
 
         Ok(MultiRpcRequest::new(
             providers,
-            request,
+            JsonRpcRequest::new("getEpochSchedule", json::NoParams),
             max_response_bytes,
-            ResponseTransform::Raw,
+            ResponseTransform::GetEpochSchedule,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type GetClusterNodesRequest = MultiRpcRequest<json::NoParams, Vec<ClusterNode>>;
+
+impl GetClusterNodesRequest {
+    pub fn get_cluster_nodes(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: sol_rpc_types::GetClusterNodesParams,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let transform = ResponseTransform::GetClusterNodes {
+            after: params.after.map(|after| after.to_string()),
+            limit: params.limit.map(u32::from),
+        };
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        // The response of the providers contains all the nodes of the cluster with all their
+        // fields and is only trimmed and paginated in the transform. It is however typically
+        // compressed by the providers, which keeps it within this limit.
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(512 * 1024 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("getClusterNodes", json::NoParams),
+            max_response_bytes,
+            transform,
+            ReductionStrategy::from(consensus_strategy),
+        ))
+    }
+}
+
+pub type MinimumLedgerSlotRequest = MultiRpcRequest<json::NoParams, Slot>;
+
+impl MinimumLedgerSlotRequest {
+    pub fn minimum_ledger_slot(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        let consensus_strategy = config.response_consensus.unwrap_or_default();
+        let providers = Providers::new(
+            rpc_sources,
+            consensus_strategy.clone(),
+            config.priority.unwrap_or_default(),
+            now,
+        )?;
+        let max_response_bytes = config
+            .response_size_estimate
+            .unwrap_or(64 + HEADER_SIZE_LIMIT);
+
+        Ok(MultiRpcRequest::new(
+            providers,
+            JsonRpcRequest::new("minimumLedgerSlot", json::NoParams),
+            max_response_bytes,
+            ResponseTransform::MinimumLedgerSlot,
             ReductionStrategy::from(consensus_strategy),
         ))
     }
 }
 
+/// Queries `getHealth` and `getVersion` from all selected providers and reports the results
+/// of each provider separately, without aggregating them.
+pub struct ClusterHealthRequest {
+    health: GetHealthRequest,
+    version: GetVersionRequest,
+}
+
+impl ClusterHealthRequest {
+    pub fn new(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        Ok(Self {
+            health: GetHealthRequest::get_health(rpc_sources.clone(), config.clone(), now)?,
+            version: GetVersionRequest::get_version(rpc_sources, config, now)?,
+        })
+    }
+
+    pub async fn send(self) -> Vec<ProviderHealthReport> {
+        let cycles_received = ic_cdk::api::msg_cycles_available();
+
+        let (health, versions) = futures::join!(
+            self.health.parallel_call(CyclesPayer::Caller),
+            self.version.parallel_call(CyclesPayer::Caller)
+        );
+        let (health, health_errors) = health.into_inner();
+        let (versions, version_errors) = versions.into_inner();
+
+        observe_cycles(
+            MetricRpcMethod::from("getClusterHealth".to_string()),
+            cycles_received,
+        );
+
+        let health = health
+            .into_iter()
+            .map(|(source, status)| (source, Ok(status)))
+            .chain(
+                health_errors
+                    .into_iter()
+                    .map(|(source, e)| (source, Err(e))),
+            );
+        let mut versions: BTreeMap<_, RpcResult<SolanaVersion>> = versions
+            .into_iter()
+            .map(|(source, version)| (source, Ok(version.into())))
+            .chain(
+                version_errors
+                    .into_iter()
+                    .map(|(source, e)| (source, Err(e))),
+            )
+            .collect();
+
+        let mut reports: Vec<_> = health
+            .map(|(source, health)| {
+                let cluster = source
+                    .rpc_provider_id()
+                    .as_ref()
+                    .and_then(get_provider)
+                    .map(|provider| provider.cluster);
+                ProviderHealthReport {
+                    version: versions.remove(&source).unwrap_or_else(|| {
+                        Err(RpcError::ValidationError(format!(
+                            "Missing getVersion result for provider {source:?}"
+                        )))
+                    }),
+                    health: provider_health(health),
+                    cluster_outage: cluster.and_then(get_cluster_outage),
+                    slot_skew: cluster.and_then(get_slot_skew),
+                    sampled_version: source.rpc_provider_id().and_then(get_sampled_version),
+                    source,
+                }
+            })
+            .collect();
+        reports.sort_by(|left, right| left.source.cmp(&right.source));
+        reports
+    }
+
+    /// Estimate the exact cycles cost for querying both `getHealth` and `getVersion`.
+    ///
+    /// *IMPORTANT*: the method is *synchronous* in a canister environment.
+    pub async fn cycles_cost(self) -> RpcResult<u128> {
+        let health_cycles_cost = self.health.cycles_cost().await?;
+        let version_cycles_cost = self.version.cycles_cost().await?;
+        Ok(health_cycles_cost + version_cycles_cost)
+    }
+}
+
+/// Queries `getHealth` from the given supported provider, e.g. to validate its API key after it
+/// was updated, since an invalid or revoked API key results in an error.
+///
+/// The HTTPS outcall is paid by the SOL RPC canister.
+pub async fn check_provider_health(
+    provider: SupportedRpcProviderId,
+    now: Timestamp,
+) -> ProviderHealth {
+    let source = RpcSource::Supported(provider);
+    let request = match GetHealthRequest::get_health(
+        RpcSources::Custom(vec![source.clone()]),
+        RpcConfig::default(),
+        now,
+    ) {
+        Ok(request) => request,
+        Err(e) => return ProviderHealth::Error(e.into()),
+    };
+    let (mut health, mut errors) = request
+        .parallel_call(CyclesPayer::Canister)
+        .await
+        .into_inner();
+    let result = match health.remove(&source) {
+        Some(status) => Ok(status),
+        None => Err(errors
+            .remove(&source)
+            .expect("BUG: expected 1 getHealth result")),
+    };
+    provider_health(result)
+}
+
+/// Queries `getVersion` from each of the given supported providers, e.g. to detect providers
+/// running different versions of `solana-core`.
+///
+/// The HTTPS outcalls are paid by the SOL RPC canister.
+pub async fn fetch_provider_versions(
+    providers: Vec<SupportedRpcProviderId>,
+    now: Timestamp,
+) -> RpcResult<BTreeMap<SupportedRpcProviderId, RpcResult<SolanaVersion>>> {
+    let request = GetVersionRequest::get_version(
+        RpcSources::Custom(providers.into_iter().map(RpcSource::Supported).collect()),
+        RpcConfig::default(),
+        now,
+    )?;
+    let (versions, errors) = request
+        .parallel_call(CyclesPayer::Canister)
+        .await
+        .into_inner();
+    Ok(versions
+        .into_iter()
+        .map(|(source, version)| (source, Ok(version.into())))
+        .chain(errors.into_iter().map(|(source, e)| (source, Err(e))))
+        .filter_map(|(source, result)| match source {
+            RpcSource::Supported(provider) => Some((provider, result)),
+            RpcSource::Custom(_) => None,
+        })
+        .collect())
+}
+
+/// Queries `getBalance`, `getAccountInfo` and `getSignaturesForAddress` for the same account from
+/// all selected providers, and combines the results of each provider into a single
+/// [`AccountSnapshot`] that is reduced as if it were the result of a single request.
+pub struct AccountSnapshotRequest {
+    balance: GetBalanceRequest,
+    account_info: GetAccountInfoRequest,
+    signatures: GetSignaturesForAddressRequest,
+}
+
+impl AccountSnapshotRequest {
+    pub fn new(
+        rpc_sources: RpcSources,
+        config: RpcConfig,
+        params: GetAccountSnapshotParams,
+        now: Timestamp,
+    ) -> Result<Self, ProviderError> {
+        Ok(Self {
+            balance: GetBalanceRequest::get_balance(
+                rpc_sources.clone(),
+                config.clone(),
+                sol_rpc_types::GetBalanceParams::from(&params),
+                now,
+            )?,
+            account_info: GetAccountInfoRequest::get_account_info(
+                rpc_sources.clone(),
+                config.clone(),
+                sol_rpc_types::GetAccountInfoParams::from(&params),
+                now,
+            )?,
+            signatures: GetSignaturesForAddressRequest::get_signatures_for_address(
+                rpc_sources,
+                config,
+                sol_rpc_types::GetSignaturesForAddressParams::from(&params),
+                now,
+            )?,
+        })
+    }
+
+    /// The 3 underlying requests are made concurrently, so the latency of each provider is the
+    /// longest time spent in flight by one of them.
+    pub async fn send_and_reduce(self) -> (ReducedResult<AccountSnapshot>, OutcallsMetadata) {
+        let method = MetricRpcMethod::from("getAccountSnapshot".to_string());
+        let cycles_received = ic_cdk::api::msg_cycles_available();
+
+        let strategy = self.balance.reduction_strategy.clone();
+        let (
+            (balances, balance_metadata),
+            (accounts, account_metadata),
+            (signatures, signature_metadata),
+        ) = futures::join!(
+            self.balance.timed_parallel_call(CyclesPayer::Caller),
+            self.account_info.timed_parallel_call(CyclesPayer::Caller),
+            self.signatures.timed_parallel_call(CyclesPayer::Caller)
+        );
+        let multi_results = combine_account_snapshots(balances, accounts, signatures);
+
+        let mut metadata = OutcallsMetadata::new();
+        for (source, outcall) in balance_metadata
+            .into_iter()
+            .chain(account_metadata)
+            .chain(signature_metadata)
+        {
+            let combined = metadata.entry(source).or_insert(outcall);
+            combined.latency = combined.latency.max(outcall.latency);
+            combined.uses_api_key |= outcall.uses_api_key;
+        }
+
+        observe_cycles(method.clone(), cycles_received);
+        observe_provider_scores(&multi_results);
+        observe_inconsistent_results(method, &multi_results);
+
+        (multi_results.reduce(strategy), metadata)
+    }
+
+    /// Estimate the exact cycles cost for querying `getBalance`, `getAccountInfo` and
+    /// `getSignaturesForAddress`.
+    ///
+    /// *IMPORTANT*: the method is *synchronous* in a canister environment.
+    pub async fn cycles_cost(self) -> RpcResult<u128> {
+        let balance_cycles_cost = self.balance.cycles_cost().await?;
+        let account_info_cycles_cost = self.account_info.cycles_cost().await?;
+        let signatures_cycles_cost = self.signatures.cycles_cost().await?;
+        Ok(balance_cycles_cost + account_info_cycles_cost + signatures_cycles_cost)
+    }
+}
+
+/// Combines the results of the underlying requests of an [`AccountSnapshotRequest`] into one
+/// result per provider. A provider for which one of the requests failed is assigned the error of
+/// the first failed request, and a provider missing the result of one of the requests is assigned
+/// a [`RpcError::ValidationError`].
+fn combine_account_snapshots(
+    balances: MultiCallResults<Lamport>,
+    accounts: MultiCallResults<Option<solana_account_decoder_client_types::UiAccount>>,
+    signatures: MultiCallResults<Vec<ConfirmedTransactionStatusWithSignature>>,
+) -> MultiCallResults<AccountSnapshot> {
+    let (balances, balance_errors) = balances.into_inner();
+    let (mut accounts, account_errors) = accounts.into_inner();
+    let (mut signatures, signature_errors) = signatures.into_inner();
+
+    let mut errors = BTreeMap::new();
+    for (source, error) in balance_errors
+        .into_iter()
+        .chain(account_errors)
+        .chain(signature_errors)
+    {
+        errors.entry(source).or_insert(error);
+    }
+
+    let missing_result =
+        || RpcError::ValidationError("Missing result of an underlying request".to_string());
+    let mut results = MultiResults::default();
+    for (source, balance) in balances {
+        if errors.contains_key(&source) {
+            continue;
+        }
+        match (accounts.remove(&source), signatures.remove(&source)) {
+            (Some(account), Some(account_signatures)) => {
+                let snapshot = AccountSnapshot {
+                    balance,
+                    account: account.map(AccountInfo::from),
+                    signatures: account_signatures,
+                };
+                results.insert_once(source, Ok(snapshot));
+            }
+            _ => {
+                errors.insert(source, missing_result());
+            }
+        }
+    }
+    for source in accounts.into_keys().chain(signatures.into_keys()) {
+        errors.entry(source).or_insert_with(missing_result);
+    }
+    for (source, error) in errors {
+        results.insert_once(source, Err(error));
+    }
+    results
+}
+
+/// Solana JSON-RPC error code returned by `getHealth` when the node is unhealthy,
+/// see [`solana-rpc-client-api`](https://github.com/anza-xyz/agave/blob/master/rpc-client-api/src/custom_error.rs).
+const JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY: i64 = -32005;
+
+fn provider_health(result: RpcResult<String>) -> ProviderHealth {
+    match result {
+        Ok(_) => ProviderHealth::Ok,
+        Err(RpcError::JsonRpcError(JsonRpcError { code, message, .. }))
+            if code == JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY =>
+        {
+            // The error message has the form "Node is behind by {num_slots_behind} slots"
+            // when the number of slots is known and "Node is unhealthy" otherwise.
+            let num_slots_behind = message
+                .strip_prefix("Node is behind by ")
+                .and_then(|rest| rest.strip_suffix(" slots"))
+                .and_then(|num_slots| num_slots.parse().ok());
+            ProviderHealth::Behind { num_slots_behind }
+        }
+        Err(e) => ProviderHealth::Error(e),
+    }
+}
+
 impl<Params, Output> MultiRpcRequest<Params, Output> {
-    pub async fn send_and_reduce(self) -> ReducedResult<Output>
+    pub async fn send_and_reduce(self) -> (ReducedResult<Output>, OutcallsMetadata)
     where
-        Params: Serialize + Clone + Debug,
-        Output: Debug + DeserializeOwned + PartialEq + Serialize,
+        Params: Serialize + Clone + Debug + 'static,
+        Output: Clone + Debug + DeserializeOwned + PartialEq + Serialize,
+    {
+        self.send_and_reduce_paid_by(CyclesPayer::Caller).await
+    }
+
+    /// Same as [`Self::send_and_reduce`], except that the HTTPS outcalls are paid by the given
+    /// [`CyclesPayer`].
+    pub async fn send_and_reduce_paid_by(
+        self,
+        payer: CyclesPayer,
+    ) -> (ReducedResult<Output>, OutcallsMetadata)
+    where
+        Params: Serialize + Clone + Debug + 'static,
+        Output: Clone + Debug + DeserializeOwned + PartialEq + Serialize,
     {
         let method = MetricRpcMethod::from(self.request.method().to_string());
+        let cycles_received = ic_cdk::api::msg_cycles_available();
 
         let strategy = self.reduction_strategy.clone();
-        let multi_results = self.parallel_call().await;
+        let shadow_request = self.shadow_request();
+        let ((multi_results, metadata), shadow_results) = futures::join!(
+            self.timed_parallel_call(payer),
+            // The HTTPS outcalls to providers in shadow mode are not paid by the caller, since
+            // their cost is not included in the cycles cost of the request.
+            async {
+                match shadow_request {
+                    Some(request) => Some(request.timed_parallel_call(CyclesPayer::Canister).await),
+                    None => None,
+                }
+            }
+        );
 
+        observe_cycles(method.clone(), cycles_received);
+        observe_provider_scores(&multi_results);
+        observe_slot_skew(&method, &multi_results);
         observe_inconsistent_results(method, &multi_results);
 
-        multi_results.reduce(strategy)
+        let reduced_result = multi_results.reduce(strategy);
+        if let Some((shadow_results, shadow_metadata)) = shadow_results {
+            observe_shadow_results(&shadow_results, &shadow_metadata, &reduced_result);
+        }
+        (reduced_result, metadata)
+    }
+
+    /// Same request sent to the providers in shadow mode, if any, see
+    /// [`Providers::shadow_sources`].
+    fn shadow_request(&self) -> Option<Self>
+    where
+        Params: Clone,
+    {
+        let sources = self.providers.shadow_sources();
+        if sources.is_empty() {
+            return None;
+        }
+        Some(Self {
+            providers: Providers {
+                sources,
+                ..self.providers.clone()
+            },
+            ..self.clone()
+        })
     }
 
     /// Query all providers in parallel and return all results.
@@ -446,19 +1907,37 @@ impl<Params, Output> MultiRpcRequest<Params, Output> {
     /// (e.g., if different providers gave different responses).
     /// This method is useful for querying data that is critical for the system to ensure that there is no single point of failure,
     /// e.g., ethereum logs upon which ckETH will be minted.
-    async fn parallel_call(self) -> MultiCallResults<Output>
+    async fn parallel_call(self, payer: CyclesPayer) -> MultiCallResults<Output>
     where
-        Params: Serialize + Clone + Debug,
+        Params: Serialize + Clone + Debug + 'static,
+        Output: Debug + DeserializeOwned,
+    {
+        let (results, _metadata) = self.timed_parallel_call(payer).await;
+        results
+    }
+
+    /// Same as [`Self::parallel_call`], but also returns how long each provider took to respond.
+    async fn timed_parallel_call(
+        self,
+        payer: CyclesPayer,
+    ) -> (MultiCallResults<Output>, OutcallsMetadata)
+    where
+        Params: Serialize + Clone + Debug + 'static,
         Output: Debug + DeserializeOwned,
     {
         let num_providers = self.providers.sources.len();
         let rpc_method = MetricRpcMethod::from(self.request.method().to_string());
         let requests = self.create_json_rpc_requests();
 
-        let client = http_client(rpc_method, true);
+        let client = http_client(rpc_method, true, payer, self.providers.priority);
 
         let (requests, errors) = requests.into_inner();
-        let (_client, mut results) = canhttp::multi::parallel_call(client, requests).await;
+        let start = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
+        requests
+            .keys()
+            .filter_map(RpcSource::rpc_provider_id)
+            .for_each(|provider_id| record_request(provider_id, start));
+        let (mut results, metadata) = timed_parallel_call(client, requests).await;
         results.add_errors(errors);
         let now = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
         results
@@ -466,18 +1945,103 @@ impl<Params, Output> MultiRpcRequest<Params, Output> {
             .keys()
             .filter_map(RpcSource::rpc_provider_id)
             .for_each(|provider_id| record_ok_result(provider_id, now));
+        results
+            .iter()
+            .filter_map(
+                |(source, result)| match (source.rpc_provider_id(), result) {
+                    (
+                        Some(provider_id),
+                        Err(RpcError::ProviderError(ProviderError::RateLimited {
+                            retry_after,
+                            ..
+                        })),
+                    ) => Some((provider_id, *retry_after)),
+                    _ => None,
+                },
+            )
+            .for_each(|(provider_id, retry_after)| {
+                add_metric_entry!(
+                    provider_rate_limited,
+                    MetricRpcProvider::from(provider_id),
+                    1
+                );
+                record_rate_limited(provider_id, retry_after, now)
+            });
         assert_eq!(
             results.len(),
             num_providers,
             "BUG: expected 1 result per provider"
         );
-        results
+        (diagnose_connection_errors(results), metadata)
     }
 
     /// Estimate the exact cycles cost for the given request.
     ///
     /// *IMPORTANT*: the method is *synchronous* in a canister environment.
     pub async fn cycles_cost(self) -> RpcResult<u128>
+    where
+        Params: Serialize + Clone + Debug,
+    {
+        let requests = self.resolve_http_requests().await?;
+
+        let mut cycles_to_attach = 0_u128;
+
+        let policy = charging_policy_with_collateral(CyclesPayer::Caller, self.providers.priority);
+        for request in requests.into_values() {
+            let request_cycles_cost = ic_cdk_management_canister::cost_http_request(&request);
+            cycles_to_attach += policy.cycles_to_charge(&request, request_cycles_cost);
+        }
+        Ok(cycles_to_attach)
+    }
+
+    /// Resolve the HTTP requests that would be sent to each provider, without sending them.
+    /// API keys are redacted from the request URLs and headers.
+    ///
+    /// *IMPORTANT*: the method is *synchronous* in a canister environment.
+    pub async fn preview(self) -> RpcResult<Vec<HttpRequestPreview>>
+    where
+        Params: Serialize + Clone + Debug,
+    {
+        let mut api_keys = read_state(|state| state.get_all_api_keys());
+        for source in &self.providers.sources {
+            if let RpcSource::Custom(RpcEndpoint {
+                auth: Some(auth), ..
+            }) = source
+            {
+                if let RpcEndpointApiKey::Value(api_key) = auth.api_key() {
+                    api_keys.extend(ApiKey::try_from(api_key.clone()).ok());
+                }
+            }
+        }
+        let redact = |value: String| {
+            api_keys.iter().fold(value, |value, api_key| {
+                value.replace(api_key.read(), API_KEY_REPLACE_STRING)
+            })
+        };
+
+        let requests = self.resolve_http_requests().await?;
+        Ok(requests
+            .into_iter()
+            .map(|(source, request)| HttpRequestPreview {
+                source,
+                url: redact(request.url),
+                headers: request
+                    .headers
+                    .into_iter()
+                    .map(|header| HttpHeader {
+                        name: header.name,
+                        value: redact(header.value),
+                    })
+                    .collect(),
+                body: String::from_utf8_lossy(&request.body.unwrap_or_default()).to_string(),
+                max_response_bytes: request.max_response_bytes.unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Build the HTTP requests that would be sent to each provider, failing if the request
+    /// to any provider cannot be built.
+    async fn resolve_http_requests(self) -> RpcResult<BTreeMap<RpcSource, IcHttpRequest>>
     where
         Params: Serialize + Clone + Debug,
     {
@@ -518,15 +2082,7 @@ impl<Params, Output> MultiRpcRequest<Params, Output> {
             num_providers,
             "BUG: expected 1 result per provider"
         );
-
-        let mut cycles_to_attach = 0_u128;
-
-        let policy = charging_policy_with_collateral();
-        for request in requests.into_values() {
-            let request_cycles_cost = ic_cdk_management_canister::cost_http_request(&request);
-            cycles_to_attach += policy.cycles_to_charge(&request, request_cycles_cost);
-        }
-        Ok(cycles_to_attach)
+        Ok(requests)
     }
 
     fn create_json_rpc_requests(self) -> MultiCallResults<Request<JsonRpcRequest<Params>>>
@@ -539,24 +2095,34 @@ impl<Params, Output> MultiRpcRequest<Params, Output> {
             buf
         };
         let mut requests = MultiResults::default();
-        for provider in self.providers.sources {
-            let request = request_builder(
-                resolve_rpc_provider(provider.clone()),
-                &read_state(|state| state.get_override_provider()),
-            )
-            .map(|builder| {
-                builder
-                    .max_response_bytes(self.max_response_bytes)
-                    .transform_context(TransformContext {
-                        function: TransformFunc(candid::Func {
-                            method: "cleanup_response".to_string(),
-                            principal: ic_cdk::api::canister_self(),
-                        }),
-                        context: transform_op.clone(),
-                    })
-                    .body(self.request.clone())
-                    .expect("BUG: invalid request")
-            });
+        for (provider, endpoint) in resolve_rpc_providers(&self.providers.sources) {
+            let request = endpoint
+                .and_then(|(endpoint, uses_api_key)| {
+                    request_builder(endpoint, &read_state(|state| state.get_override_provider()))
+                        .map(|builder| builder.extension(UsesApiKey(uses_api_key)))
+                })
+                .map(|builder| {
+                    if read_state(|state| state.is_response_compression_enabled()) {
+                        accept_compressed_response(builder)
+                    } else {
+                        builder
+                    }
+                })
+                .map(|builder| {
+                    builder
+                        .extension(resolve_retry_policy(&provider, self.request.method()))
+                        .extension(resolve_request_id_format(&provider))
+                        .max_response_bytes(self.max_response_bytes)
+                        .transform_context(TransformContext {
+                            function: TransformFunc(candid::Func {
+                                method: "cleanup_response".to_string(),
+                                principal: ic_cdk::api::canister_self(),
+                            }),
+                            context: transform_op.clone(),
+                        })
+                        .body(self.request.clone())
+                        .expect("BUG: invalid request")
+                });
             requests.insert_once(provider.clone(), request);
         }
         requests
@@ -567,6 +2133,10 @@ impl<Params, Output> MultiRpcRequest<Params, Output> {
 pub enum ReductionStrategy {
     ByEquality(ReduceWithEquality),
     ByThreshold(ReduceWithThreshold),
+    ByMedian(ReduceWithMedian),
+    BySupermajorityBySlot(ReduceWithSupermajorityBySlot),
+    CollectAll(ReduceCollectAll),
+    ForSendTransaction(ReduceSendTransaction),
 }
 
 impl From<ConsensusStrategy> for ReductionStrategy {
@@ -576,27 +2146,101 @@ impl From<ConsensusStrategy> for ReductionStrategy {
             ConsensusStrategy::Threshold { total: _, min } => {
                 ReductionStrategy::ByThreshold(ReduceWithThreshold::new(min))
             }
+            ConsensusStrategy::MedianNumeric { total: _, min } => {
+                ReductionStrategy::ByMedian(ReduceWithMedian::new(min))
+            }
+            ConsensusStrategy::SupermajorityBySlot { total: _ } => {
+                ReductionStrategy::BySupermajorityBySlot(ReduceWithSupermajorityBySlot)
+            }
+            ConsensusStrategy::CollectAll { total: _ } => {
+                ReductionStrategy::CollectAll(ReduceCollectAll)
+            }
         }
     }
 }
 
-impl<T: PartialEq + Serialize> Reduce<RpcSource, T, RpcError> for ReductionStrategy {
+impl<T: Clone + PartialEq + Serialize> Reduce<RpcSource, T, RpcError> for ReductionStrategy {
     fn reduce(&self, results: MultiResults<RpcSource, T, RpcError>) -> ReducedResult<T> {
         match self {
             ReductionStrategy::ByEquality(r) => r.reduce(results),
             ReductionStrategy::ByThreshold(r) => r.reduce(results),
+            ReductionStrategy::ByMedian(r) => r.reduce(results),
+            ReductionStrategy::BySupermajorityBySlot(r) => r.reduce(results),
+            ReductionStrategy::CollectAll(r) => r.reduce(results),
+            ReductionStrategy::ForSendTransaction(r) => r.reduce(results),
         }
     }
 }
 
 pub type MultiCallResults<T> = MultiResults<RpcSource, T, RpcError>;
 pub type ReducedResult<T> = canhttp::multi::ReducedResult<RpcSource, T, RpcError>;
+/// Request extension telling whether the request to a provider is authenticated with an API
+/// key, as resolved when the request was built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UsesApiKey(pub bool);
+
+/// Metadata about the HTTPS outcalls made to a provider for a request, recorded when they are
+/// dispatched.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutcallMetadata {
+    /// Time spent by the HTTPS outcalls in flight, excluding the time spent waiting in the queue,
+    /// or `None` if no HTTPS outcall was made, see [`OutcallDuration`].
+    pub latency: Option<Duration>,
+    /// Whether the request was authenticated with an API key.
+    pub uses_api_key: bool,
+}
+
+/// Metadata about the HTTPS outcalls made to each provider for a request.
+pub type OutcallsMetadata = BTreeMap<RpcSource, OutcallMetadata>;
+
+/// Same as [`canhttp::multi::parallel_call`], but also returns the [`OutcallMetadata`] of the
+/// request to each provider.
+async fn timed_parallel_call<S, Body, Output>(
+    service: S,
+    mut requests: BTreeMap<RpcSource, http::Request<Body>>,
+) -> (MultiCallResults<Output>, OutcallsMetadata)
+where
+    S: Service<http::Request<Body>, Response = Output, Error = RpcError>,
+{
+    let mut durations = BTreeMap::new();
+    for (source, request) in requests.iter_mut() {
+        let duration = OutcallDuration::default();
+        request.extensions_mut().insert(duration.clone());
+        let uses_api_key = request
+            .extensions()
+            .get::<UsesApiKey>()
+            .is_some_and(|UsesApiKey(uses_api_key)| *uses_api_key);
+        durations.insert(source.clone(), (duration, uses_api_key));
+    }
+    let (_service, results) = canhttp::multi::parallel_call(service, requests).await;
+    let metadata = durations
+        .into_iter()
+        .map(|(source, (duration, uses_api_key))| {
+            let metadata = OutcallMetadata {
+                latency: duration.get(),
+                uses_api_key,
+            };
+            (source, metadata)
+        })
+        .collect();
+    (results, metadata)
+}
+
+/// Records the cycles attached to the call, the cycles charged for the HTTPS outcalls (including
+/// collateral) and the cycles that remain available and will be refunded to the caller.
+fn observe_cycles(method: MetricRpcMethod, cycles_received: u128) {
+    let cycles_refunded = ic_cdk::api::msg_cycles_available();
+    let cycles_charged = cycles_received.saturating_sub(cycles_refunded);
+    add_metric_entry!(cycles_received, method.clone(), cycles_received);
+    add_metric_entry!(cycles_charged, method.clone(), cycles_charged);
+    add_metric_entry!(cycles_refunded, method, cycles_refunded);
+}
 
 fn observe_inconsistent_results<Output>(
     method: MetricRpcMethod,
     multi_results: &MultiCallResults<Output>,
 ) where
-    Output: PartialEq,
+    Output: PartialEq + Serialize,
 {
     let relevant_results: Vec<_> = multi_results
         .iter()
@@ -614,13 +2258,182 @@ fn observe_inconsistent_results<Output>(
         }
     }
 
+    let diagnostics = diagnose_inconsistent_results(&method.0, &relevant_results);
+    log!(
+        Priority::Debug,
+        "[{}]: inconsistent responses (cause: {}): {}",
+        method.0,
+        diagnostics.cause.as_str(),
+        diagnostics.summary
+    );
+
     for (source, _result) in relevant_results {
         if let RpcSource::Supported(provider_id) = source {
             if let Some(provider) = get_provider(provider_id) {
                 if let Some(host) = hostname(provider.clone()) {
-                    add_metric_entry!(inconsistent_responses, (method.clone(), host.into()), 1);
+                    add_metric_entry!(
+                        inconsistent_responses,
+                        (method.clone(), host.into(), diagnostics.cause),
+                        1
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Records the outcome of the request to each supported provider in the scores of the current
+/// period, see [`sol_rpc_types::ProviderScorecard`].
+fn observe_shadow_results<Output: PartialEq>(
+    shadow_results: &MultiCallResults<Output>,
+    shadow_metadata: &OutcallsMetadata,
+    reduced_result: &ReducedResult<Output>,
+) {
+    for (provider, outcome, latency) in
+        shadow_outcomes(shadow_results, shadow_metadata, reduced_result)
+    {
+        add_metric_entry!(
+            shadow_responses,
+            (MetricRpcProvider::from(provider), outcome),
+            1
+        );
+        record_shadow_outcome(provider, outcome, latency);
+    }
+}
+
+fn observe_provider_scores<Output: PartialEq>(multi_results: &MultiCallResults<Output>) {
+    let now = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
+    for (provider, outcome) in request_outcomes(multi_results) {
+        record_provider_outcome(provider, outcome, now);
+    }
+}
+
+/// Records the spread of the slots returned by the providers for a `getSlot` request, see
+/// [`slot_skew`], to quantify how much providers lag behind each other.
+fn observe_slot_skew<Output: Serialize>(
+    method: &MetricRpcMethod,
+    multi_results: &MultiCallResults<Output>,
+) {
+    if method.0 != "getSlot" {
+        return;
+    }
+    let Some(cluster) = supported_cluster(multi_results) else {
+        return;
+    };
+    let results: Vec<_> = multi_results.iter().collect();
+    if let Some(skew) = slot_skew(&results) {
+        UNSTABLE_METRICS.with_borrow_mut(|m| {
+            m.slot_skews
+                .entry(MetricSolanaCluster::from(cluster))
+                .or_default()
+                .0
+                .observe_value(skew)
+        });
+        record_slot_skew(cluster, skew);
+    }
+}
+
+/// Returns the Solana cluster of the supported providers that were queried, if any.
+fn supported_cluster<Output>(multi_results: &MultiCallResults<Output>) -> Option<SolanaCluster> {
+    multi_results
+        .iter()
+        .find_map(|(source, _result)| source.rpc_provider_id())
+        .as_ref()
+        .and_then(get_provider)
+        .map(|provider| provider.cluster)
+}
+
+/// Records a total outage of the Solana cluster of the queried providers if all of them failed
+/// because of the providers themselves, e.g. during a regional outage, so that it can be told
+/// apart from requests failing because of the caller.
+fn observe_total_outage<Output>(
+    method: &MetricRpcMethod,
+    multi_results: &MultiCallResults<Output>,
+) {
+    let Some(cluster) = supported_cluster(multi_results) else {
+        return;
+    };
+    let now = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
+    if multi_results
+        .iter()
+        .all(|(_source, result)| matches!(result, Err(e) if is_provider_failure(e)))
+    {
+        add_metric_entry!(
+            total_outage,
+            (method.clone(), MetricSolanaCluster::from(cluster)),
+            1
+        );
+        if record_cluster_outage(cluster, now) {
+            log!(
+                Priority::Info,
+                "[{}]: total outage of {cluster} providers started at {}",
+                method.0,
+                now.as_nanos_since_unix_epoch()
+            );
+        }
+    } else if multi_results
+        .iter()
+        .any(|(_source, result)| result.is_ok() || matches!(result, Err(RpcError::JsonRpcError(_))))
+    {
+        if let Some(outage) = record_cluster_recovery(cluster, now) {
+            log!(
+                Priority::Info,
+                "[{}]: total outage of {cluster} providers ended: all providers failed for {} requests between {} and {}",
+                method.0,
+                outage.num_failed_requests,
+                outage.started_at,
+                outage.last_failure_at
+            );
+        }
+    }
+}
+
+/// Adds the host of the provider and its known transport hints to the errors of the HTTPS
+/// outcalls that could not connect to the provider, see [`with_transport_diagnostics`].
+fn diagnose_connection_errors<T>(results: MultiCallResults<T>) -> MultiCallResults<T> {
+    let (ok_results, errors) = results.into_inner();
+    let mut diagnosed = MultiResults::default();
+    for (source, value) in ok_results {
+        diagnosed.insert_once(source, Ok(value));
+    }
+    for (source, error) in errors {
+        let error = match &error {
+            RpcError::HttpOutcallError(HttpOutcallError::IcError { code, message })
+                if is_connection_error(*code, message) =>
+            {
+                match resolve_rpc_provider(source.clone())
+                    .ok()
+                    .and_then(|endpoint| endpoint.host_str())
+                {
+                    Some(host) => {
+                        let ip_version = known_ip_version(&source, &host);
+                        log!(
+                            Priority::Debug,
+                            "Failed to connect to {host} (known IP version: {ip_version:?}): {message}"
+                        );
+                        with_transport_diagnostics(error, &host, ip_version)
+                    }
+                    None => error,
                 }
             }
+            _ => error,
+        };
+        diagnosed.insert_once(source, Err(error));
+    }
+    diagnosed
+}
+
+/// Returns `true` if the error indicates that the provider is unavailable, as opposed to an error
+/// caused by the request or by the configuration of the provider.
+pub fn is_provider_failure(error: &RpcError) -> bool {
+    match error {
+        RpcError::HttpOutcallError(HttpOutcallError::IcError { code, .. }) => {
+            *code == LegacyRejectionCode::SysTransient
         }
+        RpcError::HttpOutcallError(HttpOutcallError::InvalidHttpJsonRpcResponse {
+            status, ..
+        }) => (500..600).contains(status),
+        RpcError::ProviderError(ProviderError::RateLimited { .. }) => true,
+        _ => false,
     }
 }
@@ -0,0 +1,245 @@
+//! Reducers for the consensus strategies that are not directly provided by [`canhttp`].
+//!
+//! Since the reducers must work for any result type, the numeric values (e.g. slot or balance)
+//! they rely on are extracted from the JSON serialization of the results.
+
+#[cfg(test)]
+mod tests;
+
+use crate::rpc_client::{MultiCallResults, ReducedResult, ReductionStrategy};
+use canhttp::multi::{MultiResults, Reduce, ReductionError};
+use serde::Serialize;
+use serde_json::Value;
+use sol_rpc_types::{JsonRpcError, RpcError, RpcSource};
+use std::collections::BTreeMap;
+
+/// Returns the median of the (non-error) results, which must be integers.
+///
+/// For an even number of results, the lower median is returned, so that the returned value
+/// is always one of the values returned by the providers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReduceWithMedian {
+    min: u8,
+}
+
+impl ReduceWithMedian {
+    pub fn new(min: u8) -> Self {
+        Self { min }
+    }
+
+    pub fn reduce<T: Serialize>(&self, results: MultiCallResults<T>) -> ReducedResult<T> {
+        let (mut ok_results, errors) = results.into_inner();
+        let mut values = Vec::with_capacity(ok_results.len());
+        for (source, result) in ok_results.iter() {
+            match serde_json::to_value(result)
+                .ok()
+                .as_ref()
+                .and_then(Value::as_u64)
+            {
+                Some(value) => values.push((value, source.clone())),
+                None => {
+                    return Err(ReductionError::ConsistentError(unsupported_result_error(
+                        "MedianNumeric",
+                        "integer",
+                    )))
+                }
+            }
+        }
+        if values.is_empty() || values.len() < self.min as usize {
+            return Err(reduction_error(ok_results, errors));
+        }
+        values.sort();
+        let (_median, source) = values.swap_remove((values.len() - 1) / 2);
+        Ok(ok_results
+            .remove(&source)
+            .expect("BUG: missing result for median"))
+    }
+}
+
+/// Returns the result with the highest slot that was reached by a supermajority, i.e.
+/// strictly more than two thirds, of the queried providers.
+///
+/// The results must either be slots or contain a `contextSlot` field together with a `value`.
+/// In the latter case, all providers that reached the returned slot must agree on the value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReduceWithSupermajorityBySlot;
+
+impl ReduceWithSupermajorityBySlot {
+    pub fn reduce<T: Serialize>(&self, results: MultiCallResults<T>) -> ReducedResult<T> {
+        let (mut ok_results, errors) = results.into_inner();
+        let num_providers = ok_results.len() + errors.len();
+        let supermajority = 2 * num_providers / 3 + 1;
+
+        let mut slotted = Vec::with_capacity(ok_results.len());
+        for (source, result) in ok_results.iter() {
+            match serde_json::to_value(result)
+                .ok()
+                .and_then(SlottedResult::from_json)
+            {
+                Some(result) => slotted.push((result, source.clone())),
+                None => {
+                    return Err(ReductionError::ConsistentError(unsupported_result_error(
+                        "SupermajorityBySlot",
+                        "slot or context slot",
+                    )))
+                }
+            }
+        }
+        if slotted.len() < supermajority {
+            return Err(reduction_error(ok_results, errors));
+        }
+
+        // Most recent results first
+        slotted.sort_by(|(left, _), (right, _)| right.slot.cmp(&left.slot));
+        let slot = slotted[supermajority - 1].0.slot;
+        let reached: Vec<_> = slotted
+            .into_iter()
+            .take_while(|(result, _)| result.slot >= slot)
+            .collect();
+        let (first, _) = &reached[0];
+        if reached
+            .iter()
+            .any(|(result, _)| result.value != first.value)
+        {
+            return Err(reduction_error(ok_results, errors));
+        }
+        let (_, source) = reached
+            .into_iter()
+            .rfind(|(result, _)| result.slot == slot)
+            .expect("BUG: no result at supermajority slot");
+        Ok(ok_results
+            .remove(&source)
+            .expect("BUG: missing result for supermajority slot"))
+    }
+}
+
+/// Returns the results of all providers without reducing them, so that the caller can apply its
+/// own consensus logic, see [`sol_rpc_types::ConsensusStrategy::CollectAll`].
+///
+/// If all providers returned the same result, or the same error, that result is returned as
+/// consistent, so that agreement of the providers is not reported as an inconsistency.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReduceCollectAll;
+
+impl ReduceCollectAll {
+    pub fn reduce<T: PartialEq>(&self, results: MultiCallResults<T>) -> ReducedResult<T> {
+        let (ok_results, errors) = results.into_inner();
+        let mut values = ok_results.values();
+        let all_equal = match values.next() {
+            Some(first) => values.all(|value| value == first),
+            None => false,
+        };
+        if all_equal && errors.is_empty() {
+            return Ok(ok_results
+                .into_values()
+                .next()
+                .expect("BUG: missing result"));
+        }
+        Err(reduction_error(ok_results, errors))
+    }
+}
+
+/// Reduces the results of `sendTransaction` with the given strategy, after counting the providers
+/// that rejected the transaction as already processed as agreeing with the signature returned by
+/// the other providers.
+///
+/// When a transaction is broadcast to several providers, or resubmitted, a provider may already
+/// have received it from another node and reject it as already processed, even though the
+/// transaction was sent successfully. The errors are only counted as agreement if all the
+/// successful results are the same signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReduceSendTransaction {
+    strategy: Box<ReductionStrategy>,
+}
+
+impl ReduceSendTransaction {
+    pub fn new(strategy: ReductionStrategy) -> Self {
+        Self {
+            strategy: Box::new(strategy),
+        }
+    }
+
+    pub fn reduce<T: Clone + PartialEq + Serialize>(
+        &self,
+        results: MultiCallResults<T>,
+    ) -> ReducedResult<T> {
+        let (ok_results, errors) = results.into_inner();
+        let mut signatures = ok_results.values();
+        let signature = match signatures.next() {
+            Some(first) if signatures.all(|other| other == first) => Some(first.clone()),
+            _ => None,
+        };
+        let mut results = MultiResults::default();
+        for (source, error) in errors {
+            match &signature {
+                Some(signature) if is_already_processed(&error) => {
+                    results.insert_once(source, Ok(signature.clone()))
+                }
+                _ => results.insert_once(source, Err(error)),
+            }
+        }
+        for (source, signature) in ok_results {
+            results.insert_once(source, Ok(signature));
+        }
+        self.strategy.as_ref().reduce(results)
+    }
+}
+
+/// Returns `true` if the error is the JSON-RPC error returned by a Solana node for a transaction
+/// that was already processed, e.g.
+/// `Transaction simulation failed: This transaction has already been processed`.
+fn is_already_processed(error: &RpcError) -> bool {
+    matches!(
+        error,
+        RpcError::JsonRpcError(JsonRpcError { message, .. })
+            if message.contains("already been processed") || message.contains("AlreadyProcessed")
+    )
+}
+
+struct SlottedResult {
+    slot: u64,
+    /// Value associated with the slot, if any.
+    value: Option<Value>,
+}
+
+impl SlottedResult {
+    fn from_json(json: Value) -> Option<Self> {
+        match json {
+            Value::Number(slot) => Some(Self {
+                slot: slot.as_u64()?,
+                value: None,
+            }),
+            Value::Object(mut fields) => Some(Self {
+                slot: fields.get("contextSlot")?.as_u64()?,
+                value: Some(fields.remove("value")?),
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn unsupported_result_error(strategy: &str, expected: &str) -> RpcError {
+    RpcError::ValidationError(format!(
+        "The {strategy} consensus strategy is not supported for this method: expected a result of type {expected}"
+    ))
+}
+
+fn reduction_error<T>(
+    ok_results: BTreeMap<RpcSource, T>,
+    errors: BTreeMap<RpcSource, RpcError>,
+) -> ReductionError<RpcSource, T, RpcError> {
+    if ok_results.is_empty() {
+        let mut distinct_errors = errors.values();
+        if let Some(first) = distinct_errors.next() {
+            if distinct_errors.all(|error| error == first) {
+                return ReductionError::ConsistentError(first.clone());
+            }
+        }
+    }
+    let mut results = MultiResults::default();
+    for (source, result) in ok_results {
+        results.insert_once(source, Ok(result));
+    }
+    results.add_errors(errors);
+    ReductionError::InconsistentResults(results)
+}
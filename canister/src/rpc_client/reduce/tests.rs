@@ -0,0 +1,335 @@
+use crate::rpc_client::{
+    reduce::{
+        ReduceCollectAll, ReduceSendTransaction, ReduceWithMedian, ReduceWithSupermajorityBySlot,
+    },
+    MultiCallResults, ReductionStrategy,
+};
+use assert_matches::assert_matches;
+use canhttp::multi::{MultiResults, ReductionError};
+use sol_rpc_types::{
+    ConsensusStrategy, JsonRpcError, RpcError, RpcResult, RpcSource, SupportedRpcProviderId,
+    WithContext,
+};
+
+const ALCHEMY: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet);
+const ANKR: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet);
+const DRPC: RpcSource = RpcSource::Supported(SupportedRpcProviderId::DrpcMainnet);
+const HELIUS: RpcSource = RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet);
+
+mod median {
+    use super::*;
+
+    #[test]
+    fn should_return_median() {
+        let results = multi_results([(ALCHEMY, Ok(100_u64)), (ANKR, Ok(300)), (DRPC, Ok(200))]);
+
+        assert_eq!(ReduceWithMedian::new(3).reduce(results), Ok(200));
+    }
+
+    #[test]
+    fn should_return_lower_median_for_even_number_of_results() {
+        let results = multi_results([
+            (ALCHEMY, Ok(100_u64)),
+            (ANKR, Ok(300)),
+            (DRPC, Ok(200)),
+            (HELIUS, Ok(u64::MAX)),
+        ]);
+
+        assert_eq!(ReduceWithMedian::new(4).reduce(results), Ok(200));
+    }
+
+    #[test]
+    fn should_ignore_errors_when_enough_results() {
+        let results = multi_results([
+            (ALCHEMY, Ok(100_u64)),
+            (ANKR, Err(error("timeout"))),
+            (DRPC, Ok(200)),
+        ]);
+
+        assert_eq!(ReduceWithMedian::new(2).reduce(results), Ok(100));
+    }
+
+    #[test]
+    fn should_fail_when_not_enough_results() {
+        let results = multi_results([
+            (ALCHEMY, Ok(100_u64)),
+            (ANKR, Err(error("timeout"))),
+            (DRPC, Ok(200)),
+        ]);
+
+        assert_matches!(
+            ReduceWithMedian::new(3).reduce(results),
+            Err(ReductionError::InconsistentResults(results)) if results.len() == 3
+        );
+    }
+
+    #[test]
+    fn should_return_consistent_error() {
+        let results = multi_results::<u64>([
+            (ALCHEMY, Err(error("timeout"))),
+            (ANKR, Err(error("timeout"))),
+        ]);
+
+        assert_eq!(
+            ReduceWithMedian::new(1).reduce(results),
+            Err(ReductionError::ConsistentError(error("timeout")))
+        );
+    }
+
+    #[test]
+    fn should_fail_for_non_numeric_results() {
+        let results = multi_results([
+            (ALCHEMY, Ok("100".to_string())),
+            (ANKR, Ok("200".to_string())),
+        ]);
+
+        assert_matches!(
+            ReduceWithMedian::new(1).reduce(results),
+            Err(ReductionError::ConsistentError(RpcError::ValidationError(
+                _
+            )))
+        );
+    }
+}
+
+mod supermajority_by_slot {
+    use super::*;
+
+    #[test]
+    fn should_return_highest_slot_reached_by_supermajority() {
+        let results = multi_results([
+            (ALCHEMY, Ok(100_u64)),
+            (ANKR, Ok(120)),
+            (DRPC, Ok(140)),
+            (HELIUS, Ok(160)),
+        ]);
+
+        // 3 out of 4 providers reached slot 120
+        assert_eq!(ReduceWithSupermajorityBySlot.reduce(results), Ok(120));
+    }
+
+    #[test]
+    fn should_fail_without_supermajority() {
+        let results = multi_results([
+            (ALCHEMY, Ok(100_u64)),
+            (ANKR, Ok(120)),
+            (DRPC, Err(error("timeout"))),
+        ]);
+
+        assert_matches!(
+            ReduceWithSupermajorityBySlot.reduce(results),
+            Err(ReductionError::InconsistentResults(results)) if results.len() == 3
+        );
+    }
+
+    #[test]
+    fn should_return_value_with_context_agreed_by_supermajority() {
+        let results = multi_results([
+            (ALCHEMY, Ok(with_context(100, 1_000))),
+            (ANKR, Ok(with_context(120, 2_000))),
+            (DRPC, Ok(with_context(140, 2_000))),
+            (HELIUS, Ok(with_context(140, 2_000))),
+        ]);
+
+        assert_eq!(
+            ReduceWithSupermajorityBySlot.reduce(results),
+            Ok(with_context(120, 2_000))
+        );
+    }
+
+    #[test]
+    fn should_fail_when_supermajority_disagrees_on_value() {
+        let results = multi_results([
+            (ALCHEMY, Ok(with_context(100, 1_000))),
+            (ANKR, Ok(with_context(120, 2_000))),
+            (DRPC, Ok(with_context(140, 3_000))),
+            (HELIUS, Ok(with_context(140, 3_000))),
+        ]);
+
+        assert_matches!(
+            ReduceWithSupermajorityBySlot.reduce(results),
+            Err(ReductionError::InconsistentResults(results)) if results.len() == 4
+        );
+    }
+
+    #[test]
+    fn should_fail_for_results_without_slot() {
+        let results = multi_results([(ALCHEMY, Ok(vec![1_u64])), (ANKR, Ok(vec![1_u64]))]);
+
+        assert_matches!(
+            ReduceWithSupermajorityBySlot.reduce(results),
+            Err(ReductionError::ConsistentError(RpcError::ValidationError(
+                _
+            )))
+        );
+    }
+
+    fn with_context(context_slot: u64, value: u64) -> WithContext<u64> {
+        WithContext {
+            context_slot,
+            value,
+        }
+    }
+}
+
+mod collect_all {
+    use super::*;
+
+    #[test]
+    fn should_return_consistent_result_when_all_providers_agree() {
+        let results = multi_results([(ALCHEMY, Ok(100_u64)), (ANKR, Ok(100)), (DRPC, Ok(100))]);
+
+        assert_eq!(ReduceCollectAll.reduce(results), Ok(100));
+    }
+
+    #[test]
+    fn should_return_consistent_error_when_all_providers_fail_with_same_error() {
+        let results = multi_results([
+            (ALCHEMY, Err::<u64, _>(error("timeout"))),
+            (ANKR, Err(error("timeout"))),
+        ]);
+
+        assert_eq!(
+            ReduceCollectAll.reduce(results),
+            Err(ReductionError::ConsistentError(error("timeout")))
+        );
+    }
+
+    #[test]
+    fn should_return_all_results_when_providers_disagree() {
+        let results = multi_results([(ALCHEMY, Ok(100_u64)), (ANKR, Ok(101)), (DRPC, Ok(100))]);
+
+        assert_matches!(
+            ReduceCollectAll.reduce(results),
+            Err(ReductionError::InconsistentResults(results)) if results.len() == 3
+        );
+    }
+
+    #[test]
+    fn should_return_all_results_when_some_provider_fails() {
+        let results = multi_results([
+            (ALCHEMY, Ok(100_u64)),
+            (ANKR, Err(error("timeout"))),
+            (DRPC, Ok(100)),
+        ]);
+
+        assert_matches!(
+            ReduceCollectAll.reduce(results),
+            Err(ReductionError::InconsistentResults(results)) if results.len() == 3
+        );
+    }
+}
+
+mod send_transaction {
+    use super::*;
+
+    const SIGNATURE: &str =
+        "5iBbqBJzgqafuQn93Np8ztWyXeYe2ReGPzUB1zXP2suZ8b5EaxSwe74ZUhg5pZQuDQkNGW7XApgfXX91tLiBWtDH";
+
+    #[test]
+    fn should_count_already_processed_as_agreement() {
+        let results = multi_results([
+            (ALCHEMY, Ok(SIGNATURE.to_string())),
+            (ANKR, Err(already_processed())),
+            (DRPC, Ok(SIGNATURE.to_string())),
+        ]);
+
+        assert_eq!(
+            reducer(ConsensusStrategy::Equality).reduce(results),
+            Ok(SIGNATURE.to_string())
+        );
+    }
+
+    #[test]
+    fn should_count_already_processed_towards_threshold() {
+        let results = multi_results([
+            (ALCHEMY, Ok(SIGNATURE.to_string())),
+            (ANKR, Err(already_processed())),
+            (DRPC, Err(error("timeout"))),
+        ]);
+
+        assert_eq!(
+            reducer(ConsensusStrategy::Threshold {
+                total: Some(3),
+                min: 2
+            })
+            .reduce(results),
+            Ok(SIGNATURE.to_string())
+        );
+    }
+
+    #[test]
+    fn should_not_count_already_processed_when_signatures_differ() {
+        let results = multi_results([
+            (ALCHEMY, Ok(SIGNATURE.to_string())),
+            (ANKR, Err(already_processed())),
+            (DRPC, Ok("other".to_string())),
+        ]);
+
+        assert_matches!(
+            reducer(ConsensusStrategy::Equality).reduce(results),
+            Err(ReductionError::InconsistentResults(_))
+        );
+    }
+
+    #[test]
+    fn should_not_count_already_processed_without_signature() {
+        let results: MultiCallResults<String> = multi_results([
+            (ALCHEMY, Err(already_processed())),
+            (ANKR, Err(already_processed())),
+        ]);
+
+        assert_eq!(
+            reducer(ConsensusStrategy::Equality).reduce(results),
+            Err(ReductionError::ConsistentError(already_processed()))
+        );
+    }
+
+    #[test]
+    fn should_not_count_other_errors_as_agreement() {
+        let results = multi_results([
+            (ALCHEMY, Ok(SIGNATURE.to_string())),
+            (
+                ANKR,
+                Err(RpcError::JsonRpcError(JsonRpcError {
+                    code: -32_002,
+                    message: "Transaction simulation failed: Blockhash not found".to_string(),
+                    data: None,
+                })),
+            ),
+            (DRPC, Ok(SIGNATURE.to_string())),
+        ]);
+
+        assert_matches!(
+            reducer(ConsensusStrategy::Equality).reduce(results),
+            Err(ReductionError::InconsistentResults(_))
+        );
+    }
+
+    fn reducer(strategy: ConsensusStrategy) -> ReduceSendTransaction {
+        ReduceSendTransaction::new(ReductionStrategy::from(strategy))
+    }
+
+    fn already_processed() -> RpcError {
+        RpcError::JsonRpcError(JsonRpcError {
+            code: -32_002,
+            message: "Transaction simulation failed: This transaction has already been processed"
+                .to_string(),
+            data: None,
+        })
+    }
+}
+
+fn multi_results<T>(
+    results: impl IntoIterator<Item = (RpcSource, RpcResult<T>)>,
+) -> MultiCallResults<T> {
+    let mut multi_results = MultiResults::default();
+    for (source, result) in results {
+        multi_results.insert_once(source, result);
+    }
+    multi_results
+}
+
+fn error(message: &str) -> RpcError {
+    RpcError::ValidationError(message.to_string())
+}
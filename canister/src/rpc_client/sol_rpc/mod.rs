@@ -1,16 +1,24 @@
 #[cfg(test)]
 mod tests;
 
-use canhttp::http::json::JsonRpcResponse;
+use crate::http::compression::decompress_response;
+use canhttp::http::json::{JsonRpcError, JsonRpcResponse};
 use ic_cdk::query;
 use ic_cdk_management_canister::{HttpRequestResult, TransformArgs};
 use minicbor::{Decode, Encode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::{from_slice, Value};
-use sol_rpc_types::{PrioritizationFee, RoundingError};
+use serde_json::{from_slice, Number, Value};
+use sha2::{Digest, Sha256};
+use sol_rpc_types::{
+    EpochInfo, EpochSchedule, Hash, PerformanceSample, PrioritizationFee, RoundingError,
+    SnapshotSlotInfo, Supply, Timestamp, WithContext,
+};
 use solana_clock::Slot;
 use solana_transaction_status_client_types::TransactionStatus;
-use std::{fmt::Debug, num::NonZeroU8};
+use std::{collections::BTreeMap, fmt::Debug, num::NonZeroU8};
+
+/// JSON-RPC error code (internal error) of the error replacing an invalid `getBlocks` result.
+const INVALID_BLOCKS_ERROR_CODE: i64 = -32603;
 
 /// Describes a payload transformation to execute before passing the HTTP response to consensus.
 /// The purpose of these transformations is to ensure that the response encoding is deterministic
@@ -33,7 +41,12 @@ pub enum ResponseTransform {
         max_length: NonZeroU8,
     },
     #[n(4)]
-    GetSignaturesForAddress,
+    GetSignaturesForAddress {
+        #[n(0)]
+        min_block_time: Option<Timestamp>,
+        #[n(1)]
+        max_block_time: Option<Timestamp>,
+    },
     #[n(5)]
     GetSignatureStatuses,
     #[n(6)]
@@ -46,6 +59,80 @@ pub enum ResponseTransform {
     SendTransaction,
     #[n(10)]
     Raw,
+    #[n(11)]
+    WithContext(#[cbor(n(0), with = "crate::rpc_client::cbor::rounding_error")] RoundingError),
+    #[n(12)]
+    GetHealth,
+    #[n(13)]
+    GetVersion,
+    #[n(14)]
+    GetBlocks,
+    #[n(15)]
+    GetTokenAccountsByDelegate,
+    #[n(16)]
+    GetSlotLeaders,
+    #[n(17)]
+    GetLeaderSchedule {
+        #[n(0)]
+        identity: String,
+    },
+    #[n(18)]
+    IsBlockhashValid,
+    #[n(19)]
+    GetBlockHeight(#[cbor(n(0), with = "crate::rpc_client::cbor::rounding_error")] RoundingError),
+    #[n(20)]
+    GetEpochInfo(#[cbor(n(0), with = "crate::rpc_client::cbor::rounding_error")] RoundingError),
+    #[n(21)]
+    GetFeeForMessage,
+    #[n(22)]
+    GetMinimumBalanceForRentExemption,
+    #[n(23)]
+    GetRecentPerformanceSamples,
+    #[n(24)]
+    GetTransactionCount(
+        #[cbor(n(0), with = "crate::rpc_client::cbor::rounding_error")] RoundingError,
+    ),
+    #[n(25)]
+    GetHighestSnapshotSlot,
+    #[n(26)]
+    MinimumLedgerSlot,
+    #[n(27)]
+    RawHttp {
+        /// Lowercase names of the response headers to keep.
+        #[n(0)]
+        response_headers: Vec<String>,
+    },
+    #[n(28)]
+    GetTokenAccountsByOwner {
+        /// Only keep the accounts whose address is strictly greater than this one.
+        #[n(0)]
+        after: Option<String>,
+        /// Maximum number of accounts to keep.
+        #[n(1)]
+        limit: Option<u32>,
+    },
+    #[n(29)]
+    HasAccountChanged {
+        /// Base-58 encoded hash of the account as last known by the caller.
+        #[n(0)]
+        last_known_hash: Option<String>,
+    },
+    #[n(30)]
+    GetSupply(#[cbor(n(0), with = "crate::rpc_client::cbor::rounding_error")] RoundingError),
+    /// Keep the response body as returned by the provider.
+    #[n(31)]
+    Verbatim,
+    #[n(32)]
+    GetEpochSchedule,
+    #[n(33)]
+    GetClusterNodes {
+        /// Only keep the nodes whose public key is strictly greater than this one.
+        #[n(0)]
+        after: Option<String>,
+        /// Maximum number of nodes to keep.
+        #[n(1)]
+        limit: Option<u32>,
+    },
 }
 
 impl ResponseTransform {
@@ -58,10 +145,74 @@ impl ResponseTransform {
             value: T,
         }
 
+        #[derive(Clone, Debug, Deserialize, Serialize)]
+        pub struct SolanaRpcContext {
+            slot: Slot,
+        }
+
+        #[derive(Clone, Debug, Deserialize, Serialize)]
+        pub struct SolanaRpcKeyedAccount {
+            pubkey: String,
+            account: Value,
+        }
+
+        /// Only the bounded subset of the fields of a cluster node that is returned to the caller.
+        #[derive(Clone, Debug, Deserialize, Serialize)]
+        pub struct SolanaRpcClusterNode {
+            pubkey: String,
+            gossip: Option<String>,
+            version: Option<String>,
+        }
+
+        #[derive(Clone, Debug, Deserialize, Serialize)]
+        pub struct SolanaRpcResultWithContext<T> {
+            context: SolanaRpcContext,
+            value: T,
+        }
+
+        #[derive(Clone, Debug, Deserialize, Serialize)]
+        pub struct SolanaRpcAccount {
+            lamports: u64,
+            data: (String, String),
+            owner: String,
+            executable: bool,
+            #[serde(rename = "rentEpoch")]
+            rent_epoch: u64,
+            space: Option<u64>,
+        }
+
+        #[derive(Clone, Debug, Deserialize, Serialize)]
+        pub struct SolanaRpcAccountChange {
+            hash: Option<String>,
+            changed: bool,
+            account: Option<SolanaRpcAccount>,
+        }
+
         fn ignore_context<T>(value: SolanaRpcResult<T>) -> T {
             value.value
         }
 
+        fn sort_by_address(mut accounts: Vec<SolanaRpcKeyedAccount>) -> Vec<SolanaRpcKeyedAccount> {
+            accounts.sort_unstable_by(|account, other_account| {
+                account.pubkey.cmp(&other_account.pubkey)
+            });
+            accounts.dedup_by(|account, other_account| account.pubkey == other_account.pubkey);
+            accounts
+        }
+
+        // The `space` field is not hashed since it is not returned by all providers and is
+        // anyway the length of the account data.
+        fn hash_account(account: &SolanaRpcAccount) -> String {
+            let mut hasher = Sha256::new();
+            hasher.update(account.lamports.to_le_bytes());
+            hasher.update((account.owner.len() as u64).to_le_bytes());
+            hasher.update(account.owner.as_bytes());
+            hasher.update([account.executable as u8]);
+            hasher.update(account.rent_epoch.to_le_bytes());
+            hasher.update(account.data.0.as_bytes());
+            Hash::from(solana_hash::Hash::new_from_array(hasher.finalize().into())).to_string()
+        }
+
         fn canonicalize_response<T, R>(body_bytes: &mut Vec<u8>, f: impl FnOnce(T) -> R)
         where
             T: Serialize + DeserializeOwned + Debug,
@@ -95,6 +246,92 @@ impl ResponseTransform {
                     value => Some(value),
                 });
             }
+            Self::GetBlockHeight(rounding_error) => {
+                canonicalize_response::<u64, u64>(body_bytes, |block_height| {
+                    rounding_error.round(block_height)
+                });
+            }
+            Self::GetBlocks => {
+                if let Ok(response) = from_slice::<JsonRpcResponse<Vec<Slot>>>(body_bytes) {
+                    let (id, result) = response.into_parts();
+                    let result = result.and_then(|mut slots| {
+                        // The Solana RPC API specifies the result to be a list of slots in
+                        // ascending order, which we enforce to avoid any problem when reaching
+                        // consensus. A slot cannot contain more than one block, so that duplicates
+                        // indicate a faulty provider, whose result is not silently repaired.
+                        slots.sort_unstable();
+                        if slots.windows(2).any(|pair| pair[0] == pair[1]) {
+                            return Err(JsonRpcError::new(
+                                INVALID_BLOCKS_ERROR_CODE,
+                                "Invalid getBlocks result: duplicate slots",
+                            ));
+                        }
+                        Ok(slots)
+                    });
+                    if let Ok(bytes) = serde_json::to_vec(&JsonRpcResponse::from_parts(id, result))
+                    {
+                        *body_bytes = bytes
+                    }
+                }
+            }
+            Self::GetEpochInfo(rounding_error) => {
+                canonicalize_response::<EpochInfo, EpochInfo>(body_bytes, |epoch_info| {
+                    // Round the absolute slot down, but not below the first slot of the epoch,
+                    // so that the epoch itself is left unchanged.
+                    let first_slot_in_epoch = epoch_info
+                        .absolute_slot
+                        .saturating_sub(epoch_info.slot_index);
+                    let absolute_slot = rounding_error
+                        .round(epoch_info.absolute_slot)
+                        .max(first_slot_in_epoch);
+                    EpochInfo {
+                        slot_index: epoch_info.slot_index
+                            - (epoch_info.absolute_slot - absolute_slot),
+                        absolute_slot,
+                        block_height: rounding_error.round(epoch_info.block_height),
+                        // The transaction count increases with every block and would prevent
+                        // reaching consensus.
+                        transaction_count: None,
+                        ..epoch_info
+                    }
+                });
+            }
+            Self::GetFeeForMessage => {
+                canonicalize_response::<SolanaRpcResult<Value>, Value>(body_bytes, ignore_context);
+            }
+            Self::GetLeaderSchedule { identity } => {
+                canonicalize_response::<Option<BTreeMap<String, Vec<u64>>>, Option<Vec<u64>>>(
+                    body_bytes,
+                    |schedule| {
+                        // Only keep the leader slots of the requested identity, in case the
+                        // provider ignored the `identity` filter, and enforce the ascending order
+                        // of the slot indices to avoid any problem when reaching consensus.
+                        schedule.map(|mut schedule| {
+                            let mut slots = schedule.remove(identity).unwrap_or_default();
+                            slots.sort_unstable();
+                            slots.dedup();
+                            slots
+                        })
+                    },
+                );
+            }
+            Self::GetMinimumBalanceForRentExemption => {
+                canonicalize_response::<u64, u64>(body_bytes, std::convert::identity);
+            }
+            Self::GetRecentPerformanceSamples => {
+                canonicalize_response::<Vec<PerformanceSample>, Vec<PerformanceSample>>(
+                    body_bytes,
+                    |mut samples| {
+                        // The samples are returned from the most recent to the oldest one in the
+                        // [API](https://solana.com/docs/rpc/http/getrecentperformancesamples),
+                        // but we enforce the sorting to avoid depending on this.
+                        samples.sort_unstable_by(|sample, other_sample| {
+                            other_sample.slot.cmp(&sample.slot)
+                        });
+                        samples
+                    },
+                );
+            }
             Self::GetRecentPrioritizationFees {
                 max_slot_rounding_error,
                 max_length,
@@ -134,9 +371,33 @@ impl ResponseTransform {
                     },
                 );
             }
-            Self::GetSignaturesForAddress => {
+            Self::GetSignaturesForAddress {
+                min_block_time: None,
+                max_block_time: None,
+            } => {
                 canonicalize_response::<Value, Value>(body_bytes, std::convert::identity);
             }
+            Self::GetSignaturesForAddress {
+                min_block_time,
+                max_block_time,
+            } => {
+                // Signatures outside the requested time range are dropped before the responses
+                // are aggregated, so that they do not need to be transferred to the caller.
+                canonicalize_response::<Vec<Value>, Vec<Value>>(body_bytes, |signatures| {
+                    signatures
+                        .into_iter()
+                        .filter(|signature| {
+                            signature
+                                .get("blockTime")
+                                .and_then(Value::as_i64)
+                                .is_some_and(|block_time| {
+                                    min_block_time.is_none_or(|min| block_time >= min)
+                                        && max_block_time.is_none_or(|max| block_time <= max)
+                                })
+                        })
+                        .collect()
+                });
+            }
             Self::GetSignatureStatuses => {
                 canonicalize_response::<
                     SolanaRpcResult<Vec<Option<TransactionStatus>>>,
@@ -156,6 +417,74 @@ impl ResponseTransform {
             Self::GetSlot(rounding_error) => {
                 canonicalize_response::<Slot, Slot>(body_bytes, |slot| rounding_error.round(slot));
             }
+            Self::GetSlotLeaders => {
+                canonicalize_response::<Vec<String>, Vec<String>>(
+                    body_bytes,
+                    std::convert::identity,
+                );
+            }
+            Self::GetTokenAccountsByDelegate => {
+                canonicalize_response::<
+                    SolanaRpcResult<Vec<SolanaRpcKeyedAccount>>,
+                    Vec<SolanaRpcKeyedAccount>,
+                >(body_bytes, |result| {
+                    // The order of the returned accounts is not specified in the
+                    // [API](https://solana.com/docs/rpc/http/gettokenaccountsbydelegate)
+                    // and may differ between providers, so we enforce sorting by address.
+                    sort_by_address(ignore_context(result))
+                });
+            }
+            Self::GetTokenAccountsByOwner { after, limit } => {
+                canonicalize_response::<
+                    SolanaRpcResult<Vec<SolanaRpcKeyedAccount>>,
+                    Vec<SolanaRpcKeyedAccount>,
+                >(body_bytes, |result| {
+                    // Same as for `getTokenAccountsByDelegate`, the accounts are sorted by
+                    // address, which also makes it possible to paginate them.
+                    sort_by_address(ignore_context(result))
+                        .into_iter()
+                        .filter(|account| {
+                            after.as_ref().is_none_or(|after| &account.pubkey > after)
+                        })
+                        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+                        .collect()
+                });
+            }
+            Self::HasAccountChanged { last_known_hash } => {
+                canonicalize_response::<
+                    SolanaRpcResult<Option<SolanaRpcAccount>>,
+                    SolanaRpcAccountChange,
+                >(body_bytes, |result| {
+                    // Only the hash of the account needs to be transferred to the caller if the
+                    // account did not change.
+                    let account = ignore_context(result);
+                    let hash = account.as_ref().map(hash_account);
+                    let changed = &hash != last_known_hash;
+                    SolanaRpcAccountChange {
+                        hash,
+                        changed,
+                        account: account.filter(|_| changed),
+                    }
+                });
+            }
+            Self::GetSupply(rounding_error) => {
+                canonicalize_response::<SolanaRpcResult<Supply>, Supply>(body_bytes, |result| {
+                    let mut supply = ignore_context(result);
+                    // Each amount is rounded separately, so that the rounded circulating and
+                    // non-circulating supplies do not necessarily add up to the rounded total.
+                    supply.total = rounding_error.round(supply.total);
+                    supply.circulating = rounding_error.round(supply.circulating);
+                    supply.non_circulating = rounding_error.round(supply.non_circulating);
+                    supply.non_circulating_accounts.sort_unstable();
+                    supply.non_circulating_accounts.dedup();
+                    supply
+                });
+            }
+            Self::GetTransactionCount(rounding_error) => {
+                canonicalize_response::<u64, u64>(body_bytes, |transaction_count| {
+                    rounding_error.round(transaction_count)
+                });
+            }
             Self::GetTransaction => {
                 canonicalize_response::<Value, Option<Value>>(body_bytes, |result| match result {
                     Value::Null => None,
@@ -165,12 +494,73 @@ impl ResponseTransform {
             Self::GetTokenAccountBalance => {
                 canonicalize_response::<SolanaRpcResult<Value>, Value>(body_bytes, ignore_context);
             }
+            Self::IsBlockhashValid => {
+                canonicalize_response::<SolanaRpcResult<bool>, bool>(body_bytes, ignore_context);
+            }
             Self::SendTransaction => {
                 canonicalize_response::<String, String>(body_bytes, std::convert::identity);
             }
             Self::Raw => {
+                canonicalize_response::<Value, Value>(body_bytes, canonicalize_json);
+            }
+            Self::Verbatim => {}
+            Self::RawHttp { .. } => {
+                // The body is not necessarily a JSON-RPC response, or even JSON.
+                if let Ok(value) = from_slice::<Value>(body_bytes) {
+                    if let Ok(bytes) = serde_json::to_vec(&value) {
+                        *body_bytes = bytes
+                    }
+                }
+            }
+            Self::GetHealth => {
+                canonicalize_response::<String, String>(body_bytes, std::convert::identity);
+            }
+            Self::GetVersion => {
                 canonicalize_response::<Value, Value>(body_bytes, std::convert::identity);
             }
+            Self::GetHighestSnapshotSlot => {
+                canonicalize_response::<SnapshotSlotInfo, SnapshotSlotInfo>(
+                    body_bytes,
+                    std::convert::identity,
+                );
+            }
+            Self::MinimumLedgerSlot => {
+                canonicalize_response::<Slot, Slot>(body_bytes, std::convert::identity);
+            }
+            Self::GetEpochSchedule => {
+                canonicalize_response::<EpochSchedule, EpochSchedule>(
+                    body_bytes,
+                    std::convert::identity,
+                );
+            }
+            Self::GetClusterNodes { after, limit } => {
+                canonicalize_response::<Vec<SolanaRpcClusterNode>, Vec<SolanaRpcClusterNode>>(
+                    body_bytes,
+                    |mut nodes| {
+                        // The nodes are returned in an arbitrary order, which also differs
+                        // between providers. Sorting them by public key makes it possible to
+                        // paginate them.
+                        nodes.sort_unstable_by(|node, other_node| {
+                            node.pubkey.cmp(&other_node.pubkey)
+                        });
+                        nodes.dedup_by(|node, other_node| node.pubkey == other_node.pubkey);
+                        nodes
+                            .into_iter()
+                            .filter(|node| after.as_ref().is_none_or(|after| &node.pubkey > after))
+                            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+                            .collect()
+                    },
+                );
+            }
+            Self::WithContext(rounding_error) => {
+                canonicalize_response::<
+                    SolanaRpcResultWithContext<Option<Value>>,
+                    WithContext<Option<Value>>,
+                >(body_bytes, |result| WithContext {
+                    context_slot: rounding_error.round(result.context.slot),
+                    value: result.value,
+                });
+            }
         }
     }
 }
@@ -178,13 +568,61 @@ impl ResponseTransform {
 #[query]
 fn cleanup_response(args: TransformArgs) -> HttpRequestResult {
     let mut args = args;
-    args.response.headers.clear();
+    decompress_response(&mut args.response);
+    let maybe_transform: Option<ResponseTransform> = if args.context.is_empty() {
+        None
+    } else {
+        minicbor::decode(&args.context[..]).ok()
+    };
+    match &maybe_transform {
+        Some(ResponseTransform::RawHttp { response_headers }) => {
+            args.response.headers.retain_mut(|header| {
+                header.name.make_ascii_lowercase();
+                response_headers.contains(&header.name)
+            });
+        }
+        _ => args.response.headers.clear(),
+    }
     let status_ok = args.response.status >= 200u16 && args.response.status < 300u16;
-    if status_ok && !args.context.is_empty() {
-        let maybe_transform: Result<ResponseTransform, _> = minicbor::decode(&args.context[..]);
-        if let Ok(transform) = maybe_transform {
+    if let Some(transform) = maybe_transform {
+        if status_ok || matches!(transform, ResponseTransform::RawHttp { .. }) {
             transform.apply(&mut args.response.body);
         }
     }
     args.response
 }
+
+/// Returns the canonical form of a JSON value, so that semantically equal values returned by
+/// different providers are serialized identically: object keys are sorted and numbers with an
+/// integral value are represented as integers, e.g. `1.0`, `1.00` and `1e0` all become `1`.
+fn canonicalize_json(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<String, Value> = map
+                .into_iter()
+                .map(|(key, value)| (key, canonicalize_json(value)))
+                .collect();
+            Value::Object(sorted.into_iter().collect())
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize_json).collect()),
+        Value::Number(number) => Value::Number(canonicalize_number(number)),
+        value => value,
+    }
+}
+
+fn canonicalize_number(number: Number) -> Number {
+    // 2^64, i.e. the smallest float that is too large to be represented as an `u64`.
+    const U64_UPPER_BOUND: f64 = 18_446_744_073_709_551_616.0;
+    match number.as_f64() {
+        Some(float) if number.is_f64() && float.fract() == 0.0 => {
+            if (0.0..U64_UPPER_BOUND).contains(&float) {
+                Number::from(float as u64)
+            } else if float >= i64::MIN as f64 && float < 0.0 {
+                Number::from(float as i64)
+            } else {
+                number
+            }
+        }
+        _ => number,
+    }
+}
@@ -1,5 +1,5 @@
-use crate::rpc_client::sol_rpc::ResponseTransform;
-use canhttp::http::json::{Id, JsonRpcResponse};
+use crate::rpc_client::sol_rpc::{ResponseTransform, INVALID_BLOCKS_ERROR_CODE};
+use canhttp::http::json::{Id, JsonRpcError, JsonRpcResponse};
 use proptest::{
     array::uniform32,
     prelude::{any, prop, Strategy},
@@ -37,6 +37,55 @@ mod normalization_tests {
         );
     }
 
+    #[test]
+    fn should_canonicalize_raw_response() {
+        assert_normalized_equal(
+            &ResponseTransform::Raw,
+            r#"{"b":{"y":[{"n":2,"m":1}],"x":null},"a":true}"#,
+            r#"{"a":true,"b":{"x":null,"y":[{"m":1,"n":2}]}}"#,
+        );
+        for (left, right) in [
+            ("1", "1.0"),
+            ("1", "1.00"),
+            ("1000", "1e3"),
+            ("-5", "-5.0"),
+            ("0", "-0.0"),
+            ("18446744073709551615", "18446744073709551615"),
+            ("0.5", "5e-1"),
+        ] {
+            assert_normalized_equal(
+                &ResponseTransform::Raw,
+                format!(r#"{{"value":{left},"values":[{left}]}}"#),
+                format!(r#"{{"values":[{right}],"value":{right}}}"#),
+            );
+        }
+        assert_normalized_not_equal(&ResponseTransform::Raw, r#"{"v":1}"#, r#"{"v":1.5}"#);
+        assert_normalized_not_equal(&ResponseTransform::Raw, r#"[1,2]"#, r#"[2,1]"#);
+        assert_normalized_not_equal(&ResponseTransform::Raw, r#"{"v":1}"#, r#"{"v":"1"}"#);
+    }
+
+    #[test]
+    fn should_normalize_raw_http_response() {
+        fn normalize(body: &str) -> Vec<u8> {
+            let mut bytes = body.as_bytes().to_vec();
+            ResponseTransform::RawHttp {
+                response_headers: vec![],
+            }
+            .apply(&mut bytes);
+            bytes
+        }
+
+        assert_eq!(
+            normalize(r#"[{"k2":"v2","k1":"v1"}]"#),
+            normalize(r#"[ { "k1": "v1", "k2": "v2" } ]"#)
+        );
+        assert_eq!(
+            normalize(r#"{"k2":"v2","k1":"v1"}"#),
+            br#"{"k1":"v1","k2":"v2"}"#
+        );
+        assert_eq!(normalize("not JSON"), b"not JSON");
+    }
+
     #[test]
     fn should_normalize_get_slot_response() {
         assert_normalized_equal(
@@ -56,6 +105,234 @@ mod normalization_tests {
         );
     }
 
+    #[test]
+    fn should_normalize_get_block_height_response() {
+        assert_normalized_equal(
+            &ResponseTransform::GetBlockHeight(RoundingError::default()),
+            "307545023",
+            "307545039",
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetBlockHeight(RoundingError::default()),
+            "307545023",
+            "307545043",
+        );
+        assert_normalized(
+            &ResponseTransform::GetBlockHeight(RoundingError::new(0)),
+            "307545023",
+            json!(307545023),
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_epoch_info_response() {
+        fn epoch_info(absolute_slot: u64, slot_index: u64, block_height: u64) -> String {
+            json!({
+                "absoluteSlot": absolute_slot,
+                "blockHeight": block_height,
+                "epoch": 27,
+                "slotIndex": slot_index,
+                "slotsInEpoch": 8192,
+                "transactionCount": absolute_slot * 100
+            })
+            .to_string()
+        }
+        fn normalized_epoch_info(transform: &ResponseTransform, epoch_info: &str) -> Value {
+            from_slice::<Value>(&normalize_result(transform, epoch_info)).unwrap()["result"].clone()
+        }
+        let transform = ResponseTransform::GetEpochInfo(RoundingError::default());
+
+        assert_eq!(
+            normalized_epoch_info(&transform, &epoch_info(166598, 2790, 166500)),
+            json!({
+                "absoluteSlot": 166580,
+                "blockHeight": 166500,
+                "epoch": 27,
+                "slotIndex": 2772,
+                "slotsInEpoch": 8192,
+                "transactionCount": null
+            })
+        );
+        assert_normalized_equal(
+            &transform,
+            epoch_info(166598, 2790, 166500),
+            epoch_info(166583, 2775, 166517),
+        );
+        assert_normalized_not_equal(
+            &transform,
+            epoch_info(166598, 2790, 166500),
+            epoch_info(166603, 2795, 166503),
+        );
+        // The absolute slot is not rounded down below the first slot of the epoch
+        assert_eq!(
+            normalized_epoch_info(&transform, &epoch_info(163810, 2, 163712)),
+            json!({
+                "absoluteSlot": 163808,
+                "blockHeight": 163700,
+                "epoch": 27,
+                "slotIndex": 0,
+                "slotsInEpoch": 8192,
+                "transactionCount": null
+            })
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_fee_for_message_response() {
+        assert_normalized(
+            &ResponseTransform::GetFeeForMessage,
+            r#"{ "context": { "slot": 5068, "apiVersion": "2.1.9" }, "value": 5000 }"#,
+            json!(5000),
+        );
+        assert_normalized(
+            &ResponseTransform::GetFeeForMessage,
+            r#"{ "context": { "slot": 5068, "apiVersion": "2.1.9" }, "value": null }"#,
+            json!(null),
+        );
+        assert_normalized_equal(
+            &ResponseTransform::GetFeeForMessage,
+            r#"{ "context": { "slot": 5068, "apiVersion": "2.1.9" }, "value": 5000 }"#,
+            r#"{ "context": { "slot": 5069, "apiVersion": "2.1.9" }, "value": 5000 }"#,
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetFeeForMessage,
+            r#"{ "context": { "slot": 5068, "apiVersion": "2.1.9" }, "value": 5000 }"#,
+            r#"{ "context": { "slot": 5068, "apiVersion": "2.1.9" }, "value": null }"#,
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_minimum_balance_for_rent_exemption_response() {
+        assert_normalized(
+            &ResponseTransform::GetMinimumBalanceForRentExemption,
+            "890880",
+            json!(890880),
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetMinimumBalanceForRentExemption,
+            "890880",
+            "2039280",
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_recent_performance_samples_response() {
+        fn sample(slot: u64, num_transactions: u64) -> Value {
+            json!({
+                "numSlots": 126,
+                "numTransactions": num_transactions,
+                "numNonVoteTransactions": num_transactions / 4,
+                "samplePeriodSecs": 60,
+                "slot": slot
+            })
+        }
+
+        let normalized_samples = from_slice::<Value>(&normalize_result(
+            &ResponseTransform::GetRecentPerformanceSamples,
+            &json!([sample(348125, 126), sample(348251, 252)]).to_string(),
+        ))
+        .unwrap()["result"]
+            .clone();
+
+        assert_eq!(
+            normalized_samples,
+            json!([sample(348251, 252), sample(348125, 126)])
+        );
+        assert_normalized_equal(
+            &ResponseTransform::GetRecentPerformanceSamples,
+            json!([sample(348251, 252), sample(348125, 126)]).to_string(),
+            json!([sample(348125, 126), sample(348251, 252)]).to_string(),
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetRecentPerformanceSamples,
+            &json!([sample(348251, 252)]).to_string(),
+            &json!([sample(348251, 253)]).to_string(),
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_transaction_count_response() {
+        let transform = ResponseTransform::GetTransactionCount(RoundingError::new(100_000));
+
+        assert_normalized_equal(&transform, "268974512345", "268974587654");
+        assert_normalized_not_equal(&transform, "268974512345", "268974612345");
+        assert_normalized(&transform, "268974512345", json!(268974500000_u64));
+        assert_normalized(
+            &ResponseTransform::GetTransactionCount(RoundingError::new(0)),
+            "268974512345",
+            json!(268974512345_u64),
+        );
+    }
+
+    #[test]
+    fn should_keep_verbatim_response() {
+        let body = r#"{"id":1,"jsonrpc":"2.0","result":{"solana-core":"2.3.6","feature-set":1.0}}"#;
+        let mut response = body.as_bytes().to_vec();
+
+        ResponseTransform::Verbatim.apply(&mut response);
+
+        assert_eq!(response, body.as_bytes());
+    }
+
+    #[test]
+    fn should_normalize_get_supply_response() {
+        fn supply(total: u64, circulating: u64, non_circulating_accounts: &[&str]) -> String {
+            json!({
+                "context": { "slot": 1114, "apiVersion": "2.3.6" },
+                "value": {
+                    "total": total,
+                    "circulating": circulating,
+                    "nonCirculating": total - circulating,
+                    "nonCirculatingAccounts": non_circulating_accounts
+                }
+            })
+            .to_string()
+        }
+        const ACCOUNT_1: &str = "FEy8pTbP5fEoqMV1GdTz83byuA8EKByqYat1PKDgVAq5";
+        const ACCOUNT_2: &str = "9huDUZfxoJ7wGMTffUE7vh1xePqef7gyrLJu9NApncqA";
+        let transform = ResponseTransform::GetSupply(RoundingError::new(1_000_000_000));
+
+        assert_normalized_equal(
+            &transform,
+            supply(
+                589_000_123_456_789_012,
+                426_000_012_345_678_901,
+                &[ACCOUNT_1, ACCOUNT_2],
+            ),
+            supply(
+                589_000_123_987_654_321,
+                426_000_012_876_543_210,
+                &[ACCOUNT_2, ACCOUNT_1],
+            ),
+        );
+        assert_normalized_not_equal(
+            &transform,
+            &supply(
+                589_000_123_456_789_012,
+                426_000_012_345_678_901,
+                &[ACCOUNT_1],
+            ),
+            &supply(
+                589_000_123_456_789_012,
+                426_000_012_345_678_901,
+                &[ACCOUNT_2],
+            ),
+        );
+        assert_eq!(
+            from_slice::<Value>(&normalize_result(
+                &transform,
+                &supply(589_000_123_456_789_012, 426_000_012_345_678_901, &[]),
+            ))
+            .unwrap()["result"],
+            json!({
+                "total": 589_000_123_000_000_000_u64,
+                "circulating": 426_000_012_000_000_000_u64,
+                "nonCirculating": 163_000_111_000_000_000_u64,
+                "nonCirculatingAccounts": []
+            })
+        );
+    }
+
     #[test]
     fn should_normalize_get_account_info_response() {
         assert_normalized_equal(
@@ -283,6 +560,496 @@ mod normalization_tests {
         );
     }
 
+    #[test]
+    fn should_normalize_is_blockhash_valid_response() {
+        assert_normalized(
+            &ResponseTransform::IsBlockhashValid,
+            r#"{ "context": { "slot": 2483, "apiVersion": "2.1.9" }, "value": false }"#,
+            json!(false),
+        );
+        assert_normalized_equal(
+            &ResponseTransform::IsBlockhashValid,
+            r#"{ "context": { "slot": 2483, "apiVersion": "2.1.9" }, "value": true }"#,
+            r#"{ "context": { "slot": 2484, "apiVersion": "2.1.9" }, "value": true }"#,
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::IsBlockhashValid,
+            r#"{ "context": { "slot": 2483, "apiVersion": "2.1.9" }, "value": true }"#,
+            r#"{ "context": { "slot": 2483, "apiVersion": "2.1.9" }, "value": false }"#,
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_blocks_response() {
+        assert_normalized(
+            &ResponseTransform::GetBlocks,
+            "[345710002, 345710000, 345710003]",
+            json!([345710000, 345710002, 345710003]),
+        );
+        assert_normalized(&ResponseTransform::GetBlocks, "[]", json!([]));
+        assert_eq!(
+            normalize_result(
+                &ResponseTransform::GetBlocks,
+                "[345710002, 345710000, 345710003, 345710000]"
+            ),
+            to_vec(&JsonRpcResponse::<Value>::from_parts(
+                Id::Number(1),
+                Err(JsonRpcError::new(
+                    INVALID_BLOCKS_ERROR_CODE,
+                    "Invalid getBlocks result: duplicate slots"
+                ))
+            ))
+            .unwrap()
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetBlocks,
+            "[345710000, 345710002]",
+            "[345710000, 345710001, 345710002]",
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_slot_leaders_response() {
+        assert_normalized(
+            &ResponseTransform::GetSlotLeaders,
+            r#"["ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n", "Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM", "ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n"]"#,
+            json!([
+                "ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n",
+                "Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM",
+                "ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n"
+            ]),
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetSlotLeaders,
+            r#"["ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n", "Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM"]"#,
+            r#"["Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM", "ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n"]"#,
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_leader_schedule_response() {
+        let transform = ResponseTransform::GetLeaderSchedule {
+            identity: "ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n".to_string(),
+        };
+
+        assert_normalized(
+            &transform,
+            r#"{"ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n": [4, 0, 2, 1, 3, 2]}"#,
+            json!([0, 1, 2, 3, 4]),
+        );
+        assert_normalized(
+            &transform,
+            r#"{"ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n": [0, 1], "Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM": [2, 3]}"#,
+            json!([0, 1]),
+        );
+        assert_normalized(&transform, "{}", json!([]));
+        assert_normalized(&transform, "null", Value::Null);
+    }
+
+    #[test]
+    fn should_normalize_get_token_accounts_by_delegate_response() {
+        fn keyed_account(pubkey: &str, lamports: u64) -> Value {
+            json!({
+                "pubkey": pubkey,
+                "account": {
+                    "data": ["", "base64"],
+                    "executable": false,
+                    "lamports": lamports,
+                    "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                    "rentEpoch": 18446744073709551615_u64,
+                    "space": 165
+                }
+            })
+        }
+        let account_1 = keyed_account("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp", 2039280);
+        let account_2 = keyed_account("9PwCPoWJ75LSgZeGMubXBdufYMVd66HrcF78QzW6ZHkV", 2039280);
+        let with_context = |slot: u64, accounts: Value| {
+            json!({
+                "context": { "apiVersion": "2.1.9", "slot": slot },
+                "value": accounts
+            })
+            .to_string()
+        };
+
+        assert_normalized_json(
+            &ResponseTransform::GetTokenAccountsByDelegate,
+            &with_context(
+                334048531,
+                json!([account_2.clone(), account_1.clone(), account_2.clone()]),
+            ),
+            json!([account_1.clone(), account_2.clone()]),
+        );
+        assert_normalized_equal(
+            &ResponseTransform::GetTokenAccountsByDelegate,
+            &with_context(334048531, json!([account_1.clone(), account_2.clone()])),
+            &with_context(334048532, json!([account_2.clone(), account_1.clone()])),
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetTokenAccountsByDelegate,
+            &with_context(334048531, json!([account_1.clone(), account_2.clone()])),
+            &with_context(334048531, json!([account_1])),
+        );
+        assert_normalized(
+            &ResponseTransform::GetTokenAccountsByDelegate,
+            &with_context(334048531, json!([])),
+            json!([]),
+        );
+    }
+
+    #[test]
+    fn should_paginate_get_token_accounts_by_owner_response() {
+        fn keyed_account(pubkey: &str) -> Value {
+            json!({
+                "pubkey": pubkey,
+                "account": {
+                    "data": ["", "base64"],
+                    "executable": false,
+                    "lamports": 2039280,
+                    "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                    "rentEpoch": 18446744073709551615_u64,
+                    "space": 165
+                }
+            })
+        }
+        let account_1 = keyed_account("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp");
+        let account_2 = keyed_account("5ZiE3vAkrdXBgyFL7KqG3RoEGBws4CjRcXVbABDLZTgx");
+        let account_3 = keyed_account("9PwCPoWJ75LSgZeGMubXBdufYMVd66HrcF78QzW6ZHkV");
+        let response = json!({
+            "context": { "apiVersion": "2.1.9", "slot": 334048531 },
+            "value": [account_3.clone(), account_1.clone(), account_2.clone()]
+        })
+        .to_string();
+
+        assert_normalized_json(
+            &ResponseTransform::GetTokenAccountsByOwner {
+                after: None,
+                limit: None,
+            },
+            &response,
+            json!([account_1.clone(), account_2.clone(), account_3.clone()]),
+        );
+        assert_normalized_json(
+            &ResponseTransform::GetTokenAccountsByOwner {
+                after: None,
+                limit: Some(2),
+            },
+            &response,
+            json!([account_1.clone(), account_2.clone()]),
+        );
+        assert_normalized_json(
+            &ResponseTransform::GetTokenAccountsByOwner {
+                after: Some("5ZiE3vAkrdXBgyFL7KqG3RoEGBws4CjRcXVbABDLZTgx".to_string()),
+                limit: Some(2),
+            },
+            &response,
+            json!([account_3]),
+        );
+        assert_normalized_json(
+            &ResponseTransform::GetTokenAccountsByOwner {
+                after: Some("9PwCPoWJ75LSgZeGMubXBdufYMVd66HrcF78QzW6ZHkV".to_string()),
+                limit: None,
+            },
+            &response,
+            json!([]),
+        );
+    }
+
+    #[test]
+    fn should_trim_and_paginate_get_cluster_nodes_response() {
+        fn cluster_node(pubkey: &str, version: Option<&str>) -> Value {
+            json!({
+                "featureSet": 2891131721_u32,
+                "gossip": "10.239.6.48:8001",
+                "pubkey": pubkey,
+                "rpc": "10.239.6.48:8899",
+                "shredVersion": 2405,
+                "tpu": "10.239.6.48:8856",
+                "tpuQuic": "10.239.6.48:8862",
+                "version": version
+            })
+        }
+        fn trimmed_cluster_node(pubkey: &str, version: Option<&str>) -> Value {
+            json!({
+                "pubkey": pubkey,
+                "gossip": "10.239.6.48:8001",
+                "version": version
+            })
+        }
+        let response = json!([
+            cluster_node(
+                "9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ",
+                Some("2.1.21")
+            ),
+            cluster_node("5D1fNXzvv5NjV1ysLjirC4WY92RNsVH18vjmcszZd8on", None),
+            cluster_node(
+                "9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ",
+                Some("2.1.21")
+            ),
+            cluster_node(
+                "7Np41oeYqPefeNQEHSv1UDhYrehxin3NStELsSKCT4K2",
+                Some("2.2.14")
+            ),
+        ])
+        .to_string();
+        let node_1 = trimmed_cluster_node("5D1fNXzvv5NjV1ysLjirC4WY92RNsVH18vjmcszZd8on", None);
+        let node_2 = trimmed_cluster_node(
+            "7Np41oeYqPefeNQEHSv1UDhYrehxin3NStELsSKCT4K2",
+            Some("2.2.14"),
+        );
+        let node_3 = trimmed_cluster_node(
+            "9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ",
+            Some("2.1.21"),
+        );
+
+        assert_normalized_json(
+            &ResponseTransform::GetClusterNodes {
+                after: None,
+                limit: None,
+            },
+            &response,
+            json!([node_1.clone(), node_2.clone(), node_3.clone()]),
+        );
+        assert_normalized_json(
+            &ResponseTransform::GetClusterNodes {
+                after: None,
+                limit: Some(2),
+            },
+            &response,
+            json!([node_1.clone(), node_2.clone()]),
+        );
+        assert_normalized_json(
+            &ResponseTransform::GetClusterNodes {
+                after: Some("7Np41oeYqPefeNQEHSv1UDhYrehxin3NStELsSKCT4K2".to_string()),
+                limit: Some(2),
+            },
+            &response,
+            json!([node_3]),
+        );
+        assert_normalized_json(
+            &ResponseTransform::GetClusterNodes {
+                after: Some("9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ".to_string()),
+                limit: None,
+            },
+            &response,
+            json!([]),
+        );
+    }
+
+    #[test]
+    fn should_only_return_changed_account() {
+        fn account_response(lamports: u64) -> Value {
+            json!({
+                "context": { "apiVersion": "2.1.9", "slot": 334048531 },
+                "value": {
+                    "data": ["AQAAAA==", "base64"],
+                    "executable": false,
+                    "lamports": lamports,
+                    "owner": "11111111111111111111111111111111",
+                    "rentEpoch": 18446744073709551615_u64,
+                    "space": 4
+                }
+            })
+        }
+        fn has_account_changed(last_known_hash: Option<String>, response: &Value) -> Value {
+            let transform = ResponseTransform::HasAccountChanged { last_known_hash };
+            let normalized = normalize_result(&transform, &response.to_string());
+            from_slice::<Value>(&normalized).unwrap()["result"].clone()
+        }
+
+        let response = account_response(1_000_000);
+        let change = has_account_changed(None, &response);
+        let hash = change["hash"].as_str().unwrap().to_string();
+        assert_eq!(change["changed"], json!(true));
+        assert_eq!(change["account"]["lamports"], json!(1_000_000));
+
+        // The hash does not depend on the context or the order of the fields.
+        let mut other_response = response.clone();
+        other_response["context"]["slot"] = json!(334048532);
+        other_response["value"]["space"] = Value::Null;
+        assert_eq!(
+            has_account_changed(Some(hash.clone()), &other_response),
+            json!({ "hash": hash, "changed": false, "account": null })
+        );
+
+        let change = has_account_changed(Some(hash.clone()), &account_response(999_999));
+        assert_eq!(change["changed"], json!(true));
+        assert_ne!(change["hash"], json!(hash));
+        assert_eq!(change["account"]["lamports"], json!(999_999));
+
+        assert_eq!(
+            has_account_changed(
+                Some(hash),
+                &json!({ "context": { "apiVersion": "2.1.9", "slot": 334048531 }, "value": null })
+            ),
+            json!({ "hash": null, "changed": true, "account": null })
+        );
+        assert_eq!(
+            has_account_changed(
+                None,
+                &json!({ "context": { "apiVersion": "2.1.9", "slot": 334048531 }, "value": null })
+            ),
+            json!({ "hash": null, "changed": false, "account": null })
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_health_response() {
+        assert_normalized(&ResponseTransform::GetHealth, r#""ok""#, json!("ok"));
+    }
+
+    #[test]
+    fn should_normalize_get_version_response() {
+        assert_normalized_equal(
+            &ResponseTransform::GetVersion,
+            r#"{ "solana-core": "2.2.14", "feature-set": 3294202862 }"#,
+            r#"{ "feature-set": 3294202862, "solana-core": "2.2.14" }"#,
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetVersion,
+            r#"{ "solana-core": "2.2.14", "feature-set": 3294202862 }"#,
+            r#"{ "solana-core": "2.2.15", "feature-set": 3294202862 }"#,
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_highest_snapshot_slot_response() {
+        assert_normalized_equal(
+            &ResponseTransform::GetHighestSnapshotSlot,
+            r#"{ "full": 368700000, "incremental": 368724300 }"#,
+            r#"{ "incremental": 368724300, "full": 368700000 }"#,
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetHighestSnapshotSlot,
+            r#"{ "full": 368700000, "incremental": 368724300 }"#,
+            r#"{ "full": 368700000, "incremental": 368724400 }"#,
+        );
+        assert_normalized(
+            &ResponseTransform::GetHighestSnapshotSlot,
+            r#"{ "full": 368700000 }"#,
+            json!({ "full": 368700000, "incremental": null }),
+        );
+    }
+
+    #[test]
+    fn should_normalize_get_epoch_schedule_response() {
+        assert_normalized_equal(
+            &ResponseTransform::GetEpochSchedule,
+            r#"{ "firstNormalEpoch": 0, "firstNormalSlot": 0, "leaderScheduleSlotOffset": 432000, "slotsPerEpoch": 432000, "warmup": false }"#,
+            r#"{ "slotsPerEpoch": 432000, "leaderScheduleSlotOffset": 432000, "warmup": false, "firstNormalEpoch": 0, "firstNormalSlot": 0 }"#,
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::GetEpochSchedule,
+            r#"{ "firstNormalEpoch": 0, "firstNormalSlot": 0, "leaderScheduleSlotOffset": 432000, "slotsPerEpoch": 432000, "warmup": false }"#,
+            r#"{ "firstNormalEpoch": 14, "firstNormalSlot": 524256, "leaderScheduleSlotOffset": 432000, "slotsPerEpoch": 432000, "warmup": true }"#,
+        );
+    }
+
+    #[test]
+    fn should_normalize_minimum_ledger_slot_response() {
+        assert_normalized(
+            &ResponseTransform::MinimumLedgerSlot,
+            "368500000",
+            json!(368500000_u64),
+        );
+        assert_normalized_not_equal(
+            &ResponseTransform::MinimumLedgerSlot,
+            "368500000",
+            "368500001",
+        );
+    }
+
+    #[test]
+    fn should_filter_get_signatures_for_address_response_by_block_time() {
+        fn signature(signature: &str, block_time: Option<i64>) -> Value {
+            json!({
+                "signature": signature,
+                "slot": 340_372_399,
+                "err": null,
+                "memo": null,
+                "blockTime": block_time,
+                "confirmationStatus": "finalized"
+            })
+        }
+        let response = json!([
+            signature("sig4", Some(1_750_000_300)),
+            signature("sig3", Some(1_750_000_200)),
+            signature("sig2", None),
+            signature("sig1", Some(1_750_000_100)),
+        ])
+        .to_string();
+
+        assert_normalized(
+            &ResponseTransform::GetSignaturesForAddress {
+                min_block_time: None,
+                max_block_time: None,
+            },
+            &response,
+            json!([
+                signature("sig4", Some(1_750_000_300)),
+                signature("sig3", Some(1_750_000_200)),
+                signature("sig2", None),
+                signature("sig1", Some(1_750_000_100)),
+            ]),
+        );
+        assert_normalized(
+            &ResponseTransform::GetSignaturesForAddress {
+                min_block_time: Some(1_750_000_200),
+                max_block_time: None,
+            },
+            &response,
+            json!([
+                signature("sig4", Some(1_750_000_300)),
+                signature("sig3", Some(1_750_000_200)),
+            ]),
+        );
+        assert_normalized(
+            &ResponseTransform::GetSignaturesForAddress {
+                min_block_time: None,
+                max_block_time: Some(1_750_000_200),
+            },
+            &response,
+            json!([
+                signature("sig3", Some(1_750_000_200)),
+                signature("sig1", Some(1_750_000_100)),
+            ]),
+        );
+        assert_normalized(
+            &ResponseTransform::GetSignaturesForAddress {
+                min_block_time: Some(1_750_000_150),
+                max_block_time: Some(1_750_000_250),
+            },
+            &response,
+            json!([signature("sig3", Some(1_750_000_200))]),
+        );
+    }
+
+    #[test]
+    fn should_normalize_response_with_context() {
+        assert_normalized(
+            &ResponseTransform::WithContext(RoundingError::default()),
+            r#"{ "context": { "slot": 334035824, "apiVersion": "2.1.9" }, "value": 1000000 }"#,
+            json!({ "contextSlot": 334035820, "value": 1000000 }),
+        );
+
+        assert_normalized(
+            &ResponseTransform::WithContext(RoundingError::default()),
+            r#"{ "context": { "slot": 334035824, "apiVersion": "2.1.9" } }"#,
+            json!({ "contextSlot": 334035820, "value": null }),
+        );
+
+        assert_normalized_equal(
+            &ResponseTransform::WithContext(RoundingError::default()),
+            r#"{ "context": { "slot": 334036571, "apiVersion": "2.1.9" }, "value": 1000000 }"#,
+            r#"{ "value": 1000000, "context": { "apiVersion": "2.1.9", "slot": 334036572 } }"#,
+        );
+
+        assert_normalized_not_equal(
+            &ResponseTransform::WithContext(RoundingError::default()),
+            r#"{ "context": { "slot": 334036571, "apiVersion": "2.1.9" }, "value": 1000000 }"#,
+            r#"{ "context": { "slot": 334036591, "apiVersion": "2.1.9" }, "value": 1000000 }"#,
+        );
+    }
+
     #[test]
     fn should_normalize_get_signature_statuses_response() {
         assert_normalized_equal(
@@ -405,6 +1172,16 @@ mod normalization_tests {
         );
     }
 
+    /// Same as [`assert_normalized`], but ignores the order of the fields of JSON objects, which
+    /// are serialized in the order of the struct fields by the transform.
+    fn assert_normalized_json(transform: &ResponseTransform, result: &str, expected: Value) {
+        let normalized_response = normalize_result(transform, result);
+        assert_eq!(
+            from_slice::<Value>(&normalized_response).unwrap(),
+            json!({"jsonrpc": "2.0", "id": 1, "result": expected}),
+        );
+    }
+
     fn normalize_result(transform: &ResponseTransform, result: &str) -> Vec<u8> {
         fn add_envelope(reply: &str) -> Vec<u8> {
             format!("{{\"jsonrpc\": \"2.0\", \"id\": 1, \"result\": {}}}", reply).into_bytes()
@@ -452,7 +1229,10 @@ mod normalization_tests {
                 ResponseTransform::GetSignatureStatuses
             }
             ResponseTransformDiscriminants::GetSignaturesForAddress => {
-                ResponseTransform::GetSignaturesForAddress
+                ResponseTransform::GetSignaturesForAddress {
+                    min_block_time: None,
+                    max_block_time: None,
+                }
             }
             ResponseTransformDiscriminants::GetSlot => {
                 ResponseTransform::GetSlot(RoundingError::default())
@@ -462,7 +1242,68 @@ mod normalization_tests {
             }
             ResponseTransformDiscriminants::GetTransaction => ResponseTransform::GetTransaction,
             ResponseTransformDiscriminants::SendTransaction => ResponseTransform::SendTransaction,
+            ResponseTransformDiscriminants::IsBlockhashValid => ResponseTransform::IsBlockhashValid,
             ResponseTransformDiscriminants::Raw => ResponseTransform::Raw,
+            ResponseTransformDiscriminants::WithContext => {
+                ResponseTransform::WithContext(RoundingError::default())
+            }
+            ResponseTransformDiscriminants::GetHealth => ResponseTransform::GetHealth,
+            ResponseTransformDiscriminants::GetVersion => ResponseTransform::GetVersion,
+            ResponseTransformDiscriminants::GetBlocks => ResponseTransform::GetBlocks,
+            ResponseTransformDiscriminants::GetTokenAccountsByDelegate => {
+                ResponseTransform::GetTokenAccountsByDelegate
+            }
+            ResponseTransformDiscriminants::GetSlotLeaders => ResponseTransform::GetSlotLeaders,
+            ResponseTransformDiscriminants::GetLeaderSchedule => {
+                ResponseTransform::GetLeaderSchedule {
+                    identity: "ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n".to_string(),
+                }
+            }
+            ResponseTransformDiscriminants::GetBlockHeight => {
+                ResponseTransform::GetBlockHeight(RoundingError::default())
+            }
+            ResponseTransformDiscriminants::GetEpochInfo => {
+                ResponseTransform::GetEpochInfo(RoundingError::default())
+            }
+            ResponseTransformDiscriminants::GetFeeForMessage => ResponseTransform::GetFeeForMessage,
+            ResponseTransformDiscriminants::GetMinimumBalanceForRentExemption => {
+                ResponseTransform::GetMinimumBalanceForRentExemption
+            }
+            ResponseTransformDiscriminants::GetRecentPerformanceSamples => {
+                ResponseTransform::GetRecentPerformanceSamples
+            }
+            ResponseTransformDiscriminants::GetTransactionCount => {
+                ResponseTransform::GetTransactionCount(RoundingError::new(100_000))
+            }
+            ResponseTransformDiscriminants::GetHighestSnapshotSlot => {
+                ResponseTransform::GetHighestSnapshotSlot
+            }
+            ResponseTransformDiscriminants::MinimumLedgerSlot => {
+                ResponseTransform::MinimumLedgerSlot
+            }
+            ResponseTransformDiscriminants::GetEpochSchedule => ResponseTransform::GetEpochSchedule,
+            ResponseTransformDiscriminants::GetClusterNodes => ResponseTransform::GetClusterNodes {
+                after: None,
+                limit: None,
+            },
+            ResponseTransformDiscriminants::RawHttp => ResponseTransform::RawHttp {
+                response_headers: vec![],
+            },
+            ResponseTransformDiscriminants::GetTokenAccountsByOwner => {
+                ResponseTransform::GetTokenAccountsByOwner {
+                    after: None,
+                    limit: None,
+                }
+            }
+            ResponseTransformDiscriminants::HasAccountChanged => {
+                ResponseTransform::HasAccountChanged {
+                    last_known_hash: None,
+                }
+            }
+            ResponseTransformDiscriminants::GetSupply => {
+                ResponseTransform::GetSupply(RoundingError::new(1_000_000_000))
+            }
+            ResponseTransformDiscriminants::Verbatim => ResponseTransform::Verbatim,
         })
     }
 }
@@ -1,18 +1,31 @@
 use crate::rpc_client::{
-    GetAccountInfoRequest, GetBlockRequest, GetSignatureStatusesRequest,
-    GetSignaturesForAddressRequest, GetSlotRequest, GetTransactionRequest, MultiRpcRequest,
-    SendTransactionRequest,
+    GetAccountInfoRequest, GetBlockHeightRequest, GetBlockRequest, GetBlocksRequest,
+    GetBlocksWithLimitRequest, GetEpochInfoRequest, GetFeeForMessageRequest, GetHealthRequest,
+    GetHighestSnapshotSlotRequest, GetLeaderScheduleRequest,
+    GetMinimumBalanceForRentExemptionRequest, GetRecentPerformanceSamplesRequest,
+    GetSignatureStatusesRequest, GetSignaturesForAddressRequest, GetSlotLeadersRequest,
+    GetSlotRequest, GetSupplyRequest, GetTokenAccountsByDelegateRequest,
+    GetTokenAccountsByOwnerRequest, GetTransactionCountRequest, GetTransactionRequest,
+    GetVersionRequest, HasAccountChangedRequest, IsBlockhashValidRequest, MinimumLedgerSlotRequest,
+    MultiRpcRequest, SendTransactionRequest,
 };
 use serde::Serialize;
 use serde_json::json;
 use sol_rpc_types::{
     CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetAccountInfoParams, GetBalanceParams,
-    GetBlockCommitmentLevel, GetBlockParams, GetRecentPrioritizationFeesParams,
+    GetBlockCommitmentLevel, GetBlockEncoding, GetBlockHeightParams, GetBlockHeightRpcConfig,
+    GetBlockParams, GetBlocksParams, GetBlocksWithLimitParams, GetEpochInfoParams,
+    GetEpochInfoRpcConfig, GetFeeForMessageParams, GetLeaderScheduleParams,
+    GetMinimumBalanceForRentExemptionParams, GetRecentPerformanceSamplesLimit,
+    GetRecentPerformanceSamplesParams, GetRecentPrioritizationFeesParams,
     GetRecentPrioritizationFeesRpcConfig, GetSignatureStatusesParams,
-    GetSignaturesForAddressParams, GetSlotParams, GetSlotRpcConfig, GetTokenAccountBalanceParams,
-    GetTransactionEncoding, GetTransactionParams, Pubkey, RpcConfig, RpcSources,
-    SendTransactionEncoding, SendTransactionParams, Signature, SolanaCluster, TransactionDetails,
-    VecWithMaxLen,
+    GetSignaturesForAddressParams, GetSlotLeadersParams, GetSlotParams, GetSlotRpcConfig,
+    GetSupplyParams, GetSupplyRpcConfig, GetTokenAccountBalanceParams,
+    GetTokenAccountsByDelegateParams, GetTokenAccountsByOwnerParams, GetTransactionCountParams,
+    GetTransactionCountRpcConfig, GetTransactionEncoding, GetTransactionParams,
+    HasAccountChangedParams, Hash, Pubkey, RpcConfig, RpcError, RpcSources,
+    SendTransactionEncoding, SendTransactionParams, Signature, SolanaCluster, TokenAccountsFilter,
+    TransactionDetails, VecWithMaxLen,
 };
 use solana_pubkey::pubkey;
 use std::str::FromStr;
@@ -24,6 +37,7 @@ const ANOTHER_SIGNATURE: &str =
 
 mod request_serialization_tests {
     use super::*;
+    use assert_matches::assert_matches;
     use canhttp::multi::Timestamp;
 
     #[test]
@@ -98,6 +112,105 @@ mod request_serialization_tests {
         );
     }
 
+    #[test]
+    fn should_serialize_get_block_height_request() {
+        assert_params_eq(
+            GetBlockHeightRequest::get_block_height(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetBlockHeightRpcConfig::default(),
+                GetBlockHeightParams::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([null]),
+        );
+        assert_params_eq(
+            GetBlockHeightRequest::get_block_height(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetBlockHeightRpcConfig::default(),
+                GetBlockHeightParams {
+                    commitment: Some(CommitmentLevel::Finalized),
+                    min_context_slot: Some(123),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                {
+                    "commitment": "finalized",
+                    "minContextSlot": 123
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_epoch_info_request() {
+        assert_params_eq(
+            GetEpochInfoRequest::get_epoch_info(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetEpochInfoRpcConfig::default(),
+                GetEpochInfoParams::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([null]),
+        );
+        assert_params_eq(
+            GetEpochInfoRequest::get_epoch_info(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetEpochInfoRpcConfig::default(),
+                GetEpochInfoParams {
+                    commitment: Some(CommitmentLevel::Finalized),
+                    min_context_slot: Some(123),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                {
+                    "commitment": "finalized",
+                    "minContextSlot": 123
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_fee_for_message_request() {
+        let message = "AQABAgIAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAEBAQAA";
+        assert_params_eq(
+            GetFeeForMessageRequest::get_fee_for_message(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetFeeForMessageParams::from_encoded_message(message.to_string()),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([message, null]),
+        );
+        assert_params_eq(
+            GetFeeForMessageRequest::get_fee_for_message(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetFeeForMessageParams {
+                    commitment: Some(CommitmentLevel::Processed),
+                    min_context_slot: Some(123),
+                    ..GetFeeForMessageParams::from_encoded_message(message.to_string())
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                message,
+                {
+                    "commitment": "processed",
+                    "minContextSlot": 123
+                },
+            ]),
+        );
+    }
+
     #[test]
     fn should_serialize_get_signatures_for_address_request() {
         assert_params_eq(
@@ -111,6 +224,8 @@ mod request_serialization_tests {
                     limit: None,
                     before: None,
                     until: None,
+                    min_block_time: None,
+                    max_block_time: None,
                 },
                 Timestamp::default(),
             )
@@ -125,9 +240,11 @@ mod request_serialization_tests {
                     pubkey: Pubkey::default(),
                     commitment: Some(CommitmentLevel::Processed),
                     min_context_slot: Some(123),
-                    limit: Some(10.try_into().unwrap()),
+                    limit: Some(10_u32.try_into().unwrap()),
                     before: Some(Signature::from_str(SOME_SIGNATURE).unwrap()),
                     until: Some(Signature::from_str(ANOTHER_SIGNATURE).unwrap()),
+                    min_block_time: Some(1_750_000_000),
+                    max_block_time: Some(1_750_003_600),
                 },
                 Timestamp::default(),
             )
@@ -185,6 +302,70 @@ mod request_serialization_tests {
         );
     }
 
+    #[test]
+    fn should_serialize_get_supply_request() {
+        assert_params_eq(
+            GetSupplyRequest::get_supply(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetSupplyRpcConfig::default(),
+                GetSupplyParams::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([null]),
+        );
+        assert_params_eq(
+            GetSupplyRequest::get_supply(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetSupplyRpcConfig::default(),
+                GetSupplyParams {
+                    commitment: Some(CommitmentLevel::Confirmed),
+                    exclude_non_circulating_accounts_list: Some(true),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                {
+                    "commitment": "confirmed",
+                    "excludeNonCirculatingAccountsList": true
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_transaction_count_request() {
+        assert_params_eq(
+            GetTransactionCountRequest::get_transaction_count(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetTransactionCountRpcConfig::default(),
+                GetTransactionCountParams::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([null]),
+        );
+        assert_params_eq(
+            GetTransactionCountRequest::get_transaction_count(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                GetTransactionCountRpcConfig::default(),
+                GetTransactionCountParams {
+                    commitment: Some(CommitmentLevel::Finalized),
+                    min_context_slot: Some(123),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                {
+                    "commitment": "finalized",
+                    "minContextSlot": 123
+                },
+            ]),
+        );
+    }
+
     #[test]
     fn should_serialize_get_transaction_request() {
         let signature = solana_signature::Signature::default().to_string();
@@ -222,6 +403,48 @@ mod request_serialization_tests {
         );
     }
 
+    #[test]
+    fn should_serialize_has_account_changed_request() {
+        assert_params_eq(
+            HasAccountChangedRequest::has_account_changed(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                HasAccountChangedParams::from(solana_pubkey::Pubkey::default()),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!(["11111111111111111111111111111111", { "encoding": "base64" }]),
+        );
+        assert_params_eq(
+            HasAccountChangedRequest::has_account_changed(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                HasAccountChangedParams {
+                    pubkey: pubkey!("11111111111111111111111111111111").into(),
+                    last_known_hash: Some(
+                        Hash::from_str("C6Cxgzq6yZWxjYnxwvxvP2dhWFeQSEVxRQbUXG2eMYsY").unwrap(),
+                    ),
+                    commitment: Some(CommitmentLevel::Finalized),
+                    data_slice: Some(DataSlice {
+                        length: 72,
+                        offset: 0,
+                    }),
+                    min_context_slot: Some(456),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+            "11111111111111111111111111111111",
+            {
+                "commitment": "finalized",
+                "encoding": "base64",
+                "dataSlice": { "length": 72, "offset": 0 },
+                "minContextSlot": 456,
+            }]),
+        );
+    }
+
     #[test]
     fn should_serialize_get_balance_request() {
         let pubkey = solana_pubkey::Pubkey::default();
@@ -292,6 +515,109 @@ mod request_serialization_tests {
         );
     }
 
+    #[test]
+    fn should_serialize_get_token_accounts_by_delegate_request() {
+        let delegate = pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+        let mint = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+        let token_program = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+        assert_params_eq(
+            GetTokenAccountsByDelegateRequest::get_token_accounts_by_delegate(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetTokenAccountsByDelegateParams::from((
+                    delegate,
+                    TokenAccountsFilter::Mint(mint.into()),
+                )),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                { "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v" },
+                null
+            ]),
+        );
+        assert_params_eq(
+            GetTokenAccountsByDelegateRequest::get_token_accounts_by_delegate(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetTokenAccountsByDelegateParams {
+                    delegate: delegate.into(),
+                    filter: TokenAccountsFilter::ProgramId(token_program.into()),
+                    commitment: Some(CommitmentLevel::Confirmed),
+                    encoding: Some(GetAccountInfoEncoding::Base64),
+                    data_slice: Some(DataSlice {
+                        length: 32,
+                        offset: 0,
+                    }),
+                    min_context_slot: Some(123),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                { "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" },
+                {
+                    "commitment": "confirmed",
+                    "encoding": "base64",
+                    "dataSlice": { "length": 32, "offset": 0 },
+                    "minContextSlot": 123,
+                }
+            ]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_token_accounts_by_owner_request() {
+        let owner = pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+        let mint = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+        assert_params_eq(
+            GetTokenAccountsByOwnerRequest::get_token_accounts_by_owner(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetTokenAccountsByOwnerParams::from((
+                    owner,
+                    TokenAccountsFilter::Mint(mint.into()),
+                )),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                { "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v" },
+                null
+            ]),
+        );
+        assert_params_eq(
+            GetTokenAccountsByOwnerRequest::get_token_accounts_by_owner(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetTokenAccountsByOwnerParams {
+                    data_slice: Some(DataSlice {
+                        length: 72,
+                        offset: 0,
+                    }),
+                    after: Some(pubkey!("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp").into()),
+                    limit: Some(10_u32.try_into().unwrap()),
+                    ..GetTokenAccountsByOwnerParams::from((
+                        owner,
+                        TokenAccountsFilter::Mint(mint.into()),
+                    ))
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                { "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v" },
+                { "dataSlice": { "length": 72, "offset": 0 } }
+            ]),
+        );
+    }
+
     #[test]
     fn should_serialize_get_block_request() {
         assert_params_eq(
@@ -314,6 +640,7 @@ mod request_serialization_tests {
                     max_supported_transaction_version: Some(2u8),
                     transaction_details: Some(TransactionDetails::Signatures),
                     rewards: Some(true),
+                    encoding: Some(GetBlockEncoding::JsonParsed),
                 },
                 Timestamp::default(),
             )
@@ -321,6 +648,7 @@ mod request_serialization_tests {
             json!([
                 123,
                 {
+                    "encoding": "jsonParsed",
                     "rewards": true,
                     "transactionDetails": "signatures",
                     "commitment": "finalized",
@@ -330,6 +658,194 @@ mod request_serialization_tests {
         );
     }
 
+    #[test]
+    fn should_serialize_get_blocks_request() {
+        assert_params_eq(
+            GetBlocksRequest::get_blocks(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetBlocksParams::from(123),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([123, 1122, null]),
+        );
+        assert_params_eq(
+            GetBlocksRequest::get_blocks(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetBlocksParams {
+                    start_slot: 123,
+                    end_slot: Some(456),
+                    commitment: Some(GetBlockCommitmentLevel::Finalized),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([123, 456, {"commitment": "finalized"}]),
+        );
+    }
+
+    #[test]
+    fn should_not_serialize_get_blocks_request_with_invalid_range() {
+        for end_slot in [122, 1123, u64::MAX] {
+            let result = GetBlocksRequest::get_blocks(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetBlocksParams {
+                    start_slot: 123,
+                    end_slot: Some(end_slot),
+                    commitment: None,
+                },
+                Timestamp::default(),
+            );
+
+            assert_eq!(
+                result.err(),
+                Some(RpcError::ValidationError(format!(
+                    "Expected an end slot between 123 and 1122, but got {end_slot}"
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn should_serialize_get_blocks_with_limit_request() {
+        assert_params_eq(
+            GetBlocksWithLimitRequest::get_blocks_with_limit(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetBlocksWithLimitParams::from(123),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([123, 1000, null]),
+        );
+        assert_params_eq(
+            GetBlocksWithLimitRequest::get_blocks_with_limit(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetBlocksWithLimitParams {
+                    start_slot: 123,
+                    limit: 10.try_into().unwrap(),
+                    commitment: Some(GetBlockCommitmentLevel::Confirmed),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([123, 10, {"commitment": "confirmed"}]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_slot_leaders_request() {
+        assert_params_eq(
+            GetSlotLeadersRequest::get_slot_leaders(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetSlotLeadersParams::from(123),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([123, 100]),
+        );
+        assert_params_eq(
+            GetSlotLeadersRequest::get_slot_leaders(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetSlotLeadersParams {
+                    start_slot: 123,
+                    limit: 10.try_into().unwrap(),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([123, 10]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_leader_schedule_request() {
+        let identity = pubkey!("ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n");
+        assert_params_eq(
+            GetLeaderScheduleRequest::get_leader_schedule(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetLeaderScheduleParams::from(identity),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([null, {"identity": identity.to_string()}]),
+        );
+        assert_params_eq(
+            GetLeaderScheduleRequest::get_leader_schedule(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetLeaderScheduleParams {
+                    slot: Some(123),
+                    identity: identity.into(),
+                    commitment: Some(CommitmentLevel::Finalized),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([123, {"identity": identity.to_string(), "commitment": "finalized"}]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_minimum_balance_for_rent_exemption_request() {
+        assert_params_eq(
+            GetMinimumBalanceForRentExemptionRequest::get_minimum_balance_for_rent_exemption(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetMinimumBalanceForRentExemptionParams::from(165),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([165, null]),
+        );
+        assert_params_eq(
+            GetMinimumBalanceForRentExemptionRequest::get_minimum_balance_for_rent_exemption(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetMinimumBalanceForRentExemptionParams {
+                    data_length: 0,
+                    commitment: Some(CommitmentLevel::Confirmed),
+                },
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([0, {"commitment": "confirmed"}]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_recent_performance_samples_request() {
+        assert_params_eq(
+            GetRecentPerformanceSamplesRequest::get_recent_performance_samples(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetRecentPerformanceSamplesParams::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([60]),
+        );
+        assert_params_eq(
+            GetRecentPerformanceSamplesRequest::get_recent_performance_samples(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                GetRecentPerformanceSamplesParams::from(
+                    GetRecentPerformanceSamplesLimit::try_from(720).unwrap(),
+                ),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([720]),
+        );
+    }
+
     #[test]
     fn should_serialize_get_recent_prioritization_fees_request() {
         assert_params_eq(
@@ -364,7 +880,8 @@ mod request_serialization_tests {
 
     #[test]
     fn should_serialize_send_transaction_request() {
-        let transaction = "4F9ksKhLSgn9e7ugVnAmRpRXL9kjke4TT96FNDxMiUNc5KVDz8p1yuv";
+        // Valid in both base-58 and base-64 encodings
+        let transaction = "4F9ksKhLSgn9e7ugVnAmRpRXL9kjke4TT96FNDxMiUNc5KVDz8p1yuvX";
         assert_params_eq(
             SendTransactionRequest::send_transaction(
                 RpcSources::Default(SolanaCluster::Mainnet),
@@ -407,6 +924,119 @@ mod request_serialization_tests {
         );
     }
 
+    #[test]
+    fn should_not_serialize_requests_with_malformed_encoded_params() {
+        let result = SendTransactionRequest::send_transaction(
+            RpcSources::Default(SolanaCluster::Mainnet),
+            RpcConfig::default(),
+            SendTransactionParams::from_encoded_transaction(
+                "0OIl".to_string(),
+                SendTransactionEncoding::Base58,
+            ),
+            Timestamp::default(),
+        );
+        assert_matches!(
+            result.err(),
+            Some(RpcError::ValidationError(message))
+                if message.starts_with("Invalid base58-encoded `transaction`")
+        );
+
+        let result = GetFeeForMessageRequest::get_fee_for_message(
+            RpcSources::Default(SolanaCluster::Mainnet),
+            RpcConfig::default(),
+            GetFeeForMessageParams::from_encoded_message("not base64!".to_string()),
+            Timestamp::default(),
+        );
+        assert_matches!(
+            result.err(),
+            Some(RpcError::ValidationError(message))
+                if message.starts_with("Invalid base64-encoded `message`")
+        );
+    }
+
+    #[test]
+    fn should_serialize_is_blockhash_valid_request() {
+        // Base64 encoding of a legacy transaction with a single signature and no instructions,
+        // whose recent blockhash is `4Pcj2yJkCYyhnWe8Ze3uK2D2EtesBxhAevweDoTcxXf3`.
+        let transaction = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEAAAAAMl47jx/74S1kZ8E9lYDf6iNL0L5BdY2lJB2DsUf9V14A";
+        let mut params = SendTransactionParams::from_encoded_transaction(
+            transaction.to_string(),
+            SendTransactionEncoding::Base64,
+        );
+        assert_params_eq(
+            IsBlockhashValidRequest::is_blockhash_valid(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                (&params).try_into().unwrap(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!(["4Pcj2yJkCYyhnWe8Ze3uK2D2EtesBxhAevweDoTcxXf3", null]),
+        );
+        params.preflight_commitment = Some(CommitmentLevel::Processed);
+        params.min_context_slot = Some(456);
+        assert_params_eq(
+            IsBlockhashValidRequest::is_blockhash_valid(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                (&params).try_into().unwrap(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([
+                "4Pcj2yJkCYyhnWe8Ze3uK2D2EtesBxhAevweDoTcxXf3",
+                {
+                    "commitment": "processed",
+                    "minContextSlot": 456,
+                }
+            ]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_health_and_get_version_requests() {
+        assert_params_eq(
+            GetHealthRequest::get_health(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([]),
+        );
+        assert_params_eq(
+            GetVersionRequest::get_version(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([]),
+        );
+    }
+
+    #[test]
+    fn should_serialize_get_highest_snapshot_slot_and_minimum_ledger_slot_requests() {
+        assert_params_eq(
+            GetHighestSnapshotSlotRequest::get_highest_snapshot_slot(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([]),
+        );
+        assert_params_eq(
+            MinimumLedgerSlotRequest::minimum_ledger_slot(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                RpcConfig::default(),
+                Timestamp::default(),
+            )
+            .unwrap(),
+            json!([]),
+        );
+    }
+
     fn assert_params_eq<Params: Serialize, Output>(
         request: MultiRpcRequest<Params, Output>,
         serialized: serde_json::Value,
@@ -417,3 +1047,337 @@ mod request_serialization_tests {
         )
     }
 }
+
+mod response_size_estimate_tests {
+    use super::*;
+    use canhttp::multi::Timestamp;
+
+    #[test]
+    fn should_estimate_smaller_get_block_response_without_rewards() {
+        fn max_response_bytes(rewards: Option<bool>, config: RpcConfig) -> u64 {
+            GetBlockRequest::get_block(
+                RpcSources::Default(SolanaCluster::Mainnet),
+                config,
+                GetBlockParams {
+                    rewards,
+                    ..GetBlockParams::from(123)
+                },
+                Timestamp::default(),
+            )
+            .unwrap()
+            .max_response_bytes
+        }
+
+        let with_rewards = max_response_bytes(Some(true), RpcConfig::default());
+        assert_eq!(max_response_bytes(None, RpcConfig::default()), with_rewards);
+        assert!(max_response_bytes(Some(false), RpcConfig::default()) < with_rewards);
+
+        let config = RpcConfig {
+            response_size_estimate: Some(1_000),
+            ..RpcConfig::default()
+        };
+        assert_eq!(max_response_bytes(Some(true), config.clone()), 1_000);
+        assert_eq!(max_response_bytes(Some(false), config), 1_000);
+    }
+}
+
+mod json_request_tests {
+    use crate::rpc_client::{sol_rpc::ResponseTransform, JsonRequest};
+    use canhttp::multi::Timestamp;
+    use sol_rpc_types::{
+        JsonRequestRpcConfig, RpcError, RpcSource, RpcSources, SolanaCluster,
+        SupportedRpcProviderId,
+    };
+
+    const GET_VERSION: &str = r#"{"jsonrpc":"2.0","id":1,"method":"getVersion"}"#;
+
+    #[test]
+    fn should_normalize_responses_by_default() {
+        let request = JsonRequest::json_request(
+            RpcSources::Default(SolanaCluster::Mainnet),
+            JsonRequestRpcConfig::default(),
+            GET_VERSION.to_string(),
+            Timestamp::default(),
+        )
+        .unwrap();
+
+        assert!(matches!(request.transform, ResponseTransform::Raw));
+    }
+
+    #[test]
+    fn should_keep_raw_responses_of_single_provider() {
+        let request = JsonRequest::json_request(
+            RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::AlchemyMainnet,
+            )]),
+            raw_responses_config(),
+            GET_VERSION.to_string(),
+            Timestamp::default(),
+        )
+        .unwrap();
+
+        assert!(matches!(request.transform, ResponseTransform::Verbatim));
+    }
+
+    #[test]
+    fn should_reject_raw_responses_of_several_providers() {
+        let result = JsonRequest::json_request(
+            RpcSources::Default(SolanaCluster::Mainnet),
+            raw_responses_config(),
+            GET_VERSION.to_string(),
+            Timestamp::default(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(RpcError::ValidationError(message)) if message.contains("single provider")
+        ));
+    }
+
+    fn raw_responses_config() -> JsonRequestRpcConfig {
+        JsonRequestRpcConfig {
+            raw_responses: Some(true),
+            ..JsonRequestRpcConfig::default()
+        }
+    }
+}
+
+mod is_provider_failure_tests {
+    use crate::rpc_client::is_provider_failure;
+    use sol_rpc_types::{
+        HttpOutcallError, JsonRpcError, LegacyRejectionCode, OutcallShedReason, ProviderError,
+        RpcError,
+    };
+
+    #[test]
+    fn should_be_provider_failure() {
+        for error in [
+            RpcError::HttpOutcallError(HttpOutcallError::IcError {
+                code: LegacyRejectionCode::SysTransient,
+                message: "Connection refused".to_string(),
+            }),
+            http_error(503),
+            RpcError::ProviderError(ProviderError::RateLimited {
+                retry_after: None,
+                body: "Too Many Requests".to_string(),
+            }),
+        ] {
+            assert!(is_provider_failure(&error), "{error:?}");
+        }
+    }
+
+    #[test]
+    fn should_not_be_provider_failure() {
+        for error in [
+            RpcError::HttpOutcallError(HttpOutcallError::IcError {
+                code: LegacyRejectionCode::SysFatal,
+                message: "Http body exceeds size limit".to_string(),
+            }),
+            RpcError::HttpOutcallError(HttpOutcallError::OutcallShed {
+                reason: OutcallShedReason::QueueFull,
+            }),
+            http_error(400),
+            RpcError::ProviderError(ProviderError::Unauthorized {
+                status: 401,
+                body: "Invalid API key".to_string(),
+            }),
+            RpcError::ProviderError(ProviderError::TooFewCycles {
+                expected: 2,
+                received: 1,
+            }),
+            RpcError::JsonRpcError(JsonRpcError {
+                code: -32602,
+                message: "Invalid params".to_string(),
+                data: None,
+            }),
+            RpcError::ValidationError("Invalid public key".to_string()),
+        ] {
+            assert!(!is_provider_failure(&error), "{error:?}");
+        }
+    }
+
+    fn http_error(status: u16) -> RpcError {
+        RpcError::HttpOutcallError(HttpOutcallError::InvalidHttpJsonRpcResponse {
+            status,
+            body: String::new(),
+            parsing_error: None,
+        })
+    }
+}
+
+mod provider_health_tests {
+    use crate::rpc_client::provider_health;
+    use sol_rpc_types::{HttpOutcallError, JsonRpcError, ProviderHealth, RpcError};
+
+    #[test]
+    fn should_report_healthy_provider() {
+        assert_eq!(provider_health(Ok("ok".to_string())), ProviderHealth::Ok);
+    }
+
+    #[test]
+    fn should_report_provider_behind() {
+        assert_eq!(
+            provider_health(Err(node_unhealthy("Node is behind by 42 slots"))),
+            ProviderHealth::Behind {
+                num_slots_behind: Some(42)
+            }
+        );
+        assert_eq!(
+            provider_health(Err(node_unhealthy("Node is unhealthy"))),
+            ProviderHealth::Behind {
+                num_slots_behind: None
+            }
+        );
+    }
+
+    #[test]
+    fn should_report_other_errors() {
+        for error in [
+            RpcError::JsonRpcError(JsonRpcError {
+                code: -32603,
+                message: "Internal error".to_string(),
+                data: None,
+            }),
+            RpcError::HttpOutcallError(HttpOutcallError::InvalidHttpJsonRpcResponse {
+                status: 503,
+                body: "Service Unavailable".to_string(),
+                parsing_error: None,
+            }),
+        ] {
+            assert_eq!(
+                provider_health(Err(error.clone())),
+                ProviderHealth::Error(error)
+            );
+        }
+    }
+
+    fn node_unhealthy(message: &str) -> RpcError {
+        RpcError::JsonRpcError(JsonRpcError {
+            code: -32005,
+            message: message.to_string(),
+            data: None,
+        })
+    }
+}
+
+mod combine_account_snapshots_tests {
+    use crate::rpc_client::{combine_account_snapshots, MultiCallResults};
+    use canhttp::multi::MultiResults;
+    use sol_rpc_types::{
+        AccountSnapshot, ConfirmedTransactionStatusWithSignature, RpcError, RpcResult, RpcSource,
+        SupportedRpcProviderId,
+    };
+
+    const ALCHEMY: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet);
+    const HELIUS: RpcSource = RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet);
+
+    #[test]
+    fn should_combine_results_of_each_provider() {
+        let snapshots = combine_account_snapshots(
+            multi_results([(ALCHEMY, Ok(1_000)), (HELIUS, Ok(2_000))]),
+            multi_results([(ALCHEMY, Ok(None)), (HELIUS, Ok(None))]),
+            multi_results([(ALCHEMY, Ok(vec![])), (HELIUS, Ok(vec![signature()]))]),
+        );
+
+        assert_eq!(
+            snapshots.into_inner(),
+            multi_results([
+                (
+                    ALCHEMY,
+                    Ok(AccountSnapshot {
+                        balance: 1_000,
+                        account: None,
+                        signatures: vec![],
+                    })
+                ),
+                (
+                    HELIUS,
+                    Ok(AccountSnapshot {
+                        balance: 2_000,
+                        account: None,
+                        signatures: vec![signature()],
+                    })
+                ),
+            ])
+            .into_inner()
+        );
+    }
+
+    #[test]
+    fn should_keep_first_error_of_each_provider() {
+        let snapshots = combine_account_snapshots(
+            multi_results([(ALCHEMY, Ok(1_000)), (HELIUS, Err(error("getBalance")))]),
+            multi_results([(ALCHEMY, Err(error("getAccountInfo"))), (HELIUS, Ok(None))]),
+            multi_results([
+                (ALCHEMY, Err(error("getSignaturesForAddress"))),
+                (HELIUS, Err(error("getSignaturesForAddress"))),
+            ]),
+        );
+
+        assert_eq!(
+            snapshots.into_inner(),
+            multi_results([
+                (ALCHEMY, Err(error("getAccountInfo"))),
+                (HELIUS, Err(error("getBalance"))),
+            ])
+            .into_inner()
+        );
+    }
+
+    #[test]
+    fn should_not_panic_when_result_of_provider_is_missing() {
+        let snapshots = combine_account_snapshots(
+            multi_results([(ALCHEMY, Ok(1_000)), (HELIUS, Ok(2_000))]),
+            multi_results([(ALCHEMY, Ok(None))]),
+            multi_results([(ALCHEMY, Ok(vec![])), (HELIUS, Ok(vec![]))]),
+        );
+
+        assert_eq!(
+            snapshots.into_inner(),
+            multi_results([
+                (
+                    ALCHEMY,
+                    Ok(AccountSnapshot {
+                        balance: 1_000,
+                        account: None,
+                        signatures: vec![],
+                    })
+                ),
+                (
+                    HELIUS,
+                    Err(RpcError::ValidationError(
+                        "Missing result of an underlying request".to_string()
+                    ))
+                ),
+            ])
+            .into_inner()
+        );
+    }
+
+    fn multi_results<T>(
+        results: impl IntoIterator<Item = (RpcSource, RpcResult<T>)>,
+    ) -> MultiCallResults<T> {
+        let mut multi_results = MultiResults::default();
+        for (source, result) in results {
+            multi_results.insert_once(source, result);
+        }
+        multi_results
+    }
+
+    fn signature() -> ConfirmedTransactionStatusWithSignature {
+        ConfirmedTransactionStatusWithSignature {
+            signature: "5iBbqBJzgqafuQn93Np8ztWyXeYe2ReGPzUB1zXP2suZ8b5EaxSwe74ZUhg5pZQuDQkNGW7XApgfXX91tLiBWtDH"
+                .parse()
+                .unwrap(),
+            slot: 340_372_399,
+            err: None,
+            memo: None,
+            block_time: Some(1_747_387_742),
+            confirmation_status: None,
+        }
+    }
+
+    fn error(method: &str) -> RpcError {
+        RpcError::ValidationError(format!("{method} failed"))
+    }
+}
@@ -0,0 +1,173 @@
+//! Monthly reliability statistics of the supported providers, see [`ProviderScorecard`].
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    log,
+    logs::Priority,
+    memory::roll_up_provider_scores,
+    rpc_client::{is_provider_failure, MultiCallResults},
+    upgrade::is_upgrade_in_progress,
+};
+use canhttp::multi::Timestamp;
+use sol_rpc_types::{
+    ProviderScore, ProviderScorecard, RpcError, RpcResult, RpcSource, ScorecardPeriod,
+    SupportedRpcProviderId,
+};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Outcome of a single request to a supported provider.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RequestOutcome {
+    /// The provider returned a response that agreed with the other providers, or no response was
+    /// shared by a strict majority of the providers.
+    Response,
+    /// The provider returned a response that differed from the response shared by a strict
+    /// majority of the providers.
+    Disagreement,
+    /// The request failed because of the provider, see [`is_provider_failure`].
+    Failure,
+    /// The request failed for another reason, e.g. because the response was too large.
+    Error,
+}
+
+/// Returns the outcome of the request to each supported provider.
+///
+/// Responses include JSON-RPC errors, since those are returned by the providers themselves.
+/// Results of [`RpcSource::Custom`] providers are ignored.
+pub fn request_outcomes<T: PartialEq>(
+    multi_results: &MultiCallResults<T>,
+) -> Vec<(SupportedRpcProviderId, RequestOutcome)> {
+    let responses: Vec<&RpcResult<T>> = multi_results
+        .iter()
+        .map(|(_source, result)| result)
+        .filter(|result| is_response(*result))
+        .collect();
+    let majority = responses
+        .iter()
+        .copied()
+        .find(|response| {
+            2 * responses.iter().filter(|other| *other == response).count() > responses.len()
+        })
+        .filter(|_| responses.len() > 1);
+
+    multi_results
+        .iter()
+        .filter_map(|(source, result)| {
+            let RpcSource::Supported(provider) = source else {
+                return None;
+            };
+            let outcome = if is_response(result) {
+                match majority {
+                    Some(majority) if majority != result => RequestOutcome::Disagreement,
+                    _ => RequestOutcome::Response,
+                }
+            } else if matches!(result, Err(e) if is_provider_failure(e)) {
+                RequestOutcome::Failure
+            } else {
+                RequestOutcome::Error
+            };
+            Some((*provider, outcome))
+        })
+        .collect()
+}
+
+/// Returns `true` if the result was returned by the provider itself, including JSON-RPC errors.
+pub fn is_response<T>(result: &RpcResult<T>) -> bool {
+    result.is_ok() || matches!(result, Err(RpcError::JsonRpcError(_)))
+}
+
+/// Reliability statistics of the supported providers, aggregated over a [`ScorecardPeriod`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderScores {
+    period: ScorecardPeriod,
+    scores: BTreeMap<SupportedRpcProviderId, ProviderScore>,
+}
+
+impl ProviderScores {
+    pub fn new(period: ScorecardPeriod) -> Self {
+        Self {
+            period,
+            scores: BTreeMap::new(),
+        }
+    }
+
+    pub fn period(&self) -> ScorecardPeriod {
+        self.period
+    }
+
+    pub fn record(&mut self, provider: SupportedRpcProviderId, outcome: RequestOutcome) {
+        let score = self
+            .scores
+            .entry(provider)
+            .or_insert_with(|| ProviderScore::new(provider));
+        score.num_requests = score.num_requests.saturating_add(1);
+        match outcome {
+            RequestOutcome::Response => {
+                score.num_responses = score.num_responses.saturating_add(1);
+            }
+            RequestOutcome::Disagreement => {
+                score.num_responses = score.num_responses.saturating_add(1);
+                score.num_disagreements = score.num_disagreements.saturating_add(1);
+            }
+            RequestOutcome::Failure => {
+                score.num_failures = score.num_failures.saturating_add(1);
+            }
+            RequestOutcome::Error => {}
+        }
+    }
+}
+
+impl From<ProviderScores> for ProviderScorecard {
+    fn from(scores: ProviderScores) -> Self {
+        Self {
+            period: scores.period,
+            scores: scores.scores.into_values().collect(),
+        }
+    }
+}
+
+impl From<ProviderScorecard> for ProviderScores {
+    fn from(scorecard: ProviderScorecard) -> Self {
+        Self {
+            period: scorecard.period,
+            scores: scorecard
+                .scores
+                .into_iter()
+                .map(|score| (score.provider, score))
+                .collect(),
+        }
+    }
+}
+
+/// Starts a timer rolling up the scores of the supported providers into a [`ProviderScorecard`]
+/// at the start of the next month, see [`roll_up_provider_scores`].
+///
+/// Timers are not persisted across upgrades, so this must be called after every
+/// installation and upgrade of the canister.
+///
+/// The roll-up is skipped while the canister is about to be upgraded. The scores are then
+/// persisted by the upgrade and rolled up by the next recorded request instead.
+pub fn schedule_scorecard_rollup() {
+    let now = ic_cdk::api::time();
+    let next_period_start = ScorecardPeriod::containing(now).next().start();
+    let delay = Duration::from_nanos(next_period_start.saturating_sub(now));
+    ic_cdk_timers::set_timer(delay, async {
+        if is_upgrade_in_progress() {
+            schedule_scorecard_rollup();
+            return;
+        }
+        let now = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
+        if let Some(scorecard) = roll_up_provider_scores(now) {
+            log!(
+                Priority::Info,
+                "[schedule_scorecard_rollup]: rolled up scorecard of {}-{:02} for {} providers",
+                scorecard.period.year,
+                scorecard.period.month,
+                scorecard.scores.len()
+            );
+        }
+        schedule_scorecard_rollup();
+    });
+}
@@ -0,0 +1,160 @@
+use crate::{
+    rpc_client::MultiCallResults,
+    scorecard::{request_outcomes, ProviderScores, RequestOutcome},
+};
+use canhttp::multi::MultiResults;
+use sol_rpc_types::{
+    HttpOutcallError, JsonRpcError, ProviderScore, ProviderScorecard, RpcEndpoint, RpcError,
+    RpcResult, RpcSource, ScorecardPeriod, SupportedRpcProviderId,
+};
+use std::collections::BTreeMap;
+
+const ALCHEMY: SupportedRpcProviderId = SupportedRpcProviderId::AlchemyMainnet;
+const ANKR: SupportedRpcProviderId = SupportedRpcProviderId::AnkrMainnet;
+const HELIUS: SupportedRpcProviderId = SupportedRpcProviderId::HeliusMainnet;
+const DRPC: SupportedRpcProviderId = SupportedRpcProviderId::DrpcMainnet;
+
+mod request_outcomes_tests {
+    use super::*;
+
+    #[test]
+    fn should_count_disagreement_with_majority() {
+        let outcomes = outcomes([
+            (ALCHEMY, Ok(1)),
+            (ANKR, Ok(1)),
+            (HELIUS, Ok(2)),
+            (DRPC, Err(json_rpc_error())),
+        ]);
+
+        assert_eq!(
+            outcomes,
+            BTreeMap::from([
+                (ALCHEMY, RequestOutcome::Response),
+                (ANKR, RequestOutcome::Response),
+                (HELIUS, RequestOutcome::Disagreement),
+                (DRPC, RequestOutcome::Disagreement),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_not_count_disagreement_without_strict_majority() {
+        let outcomes = outcomes([
+            (ALCHEMY, Ok(1)),
+            (ANKR, Ok(1)),
+            (HELIUS, Ok(2)),
+            (DRPC, Ok(2)),
+        ]);
+
+        assert!(outcomes
+            .values()
+            .all(|outcome| outcome == &RequestOutcome::Response));
+    }
+
+    #[test]
+    fn should_distinguish_provider_failures_from_other_errors() {
+        let outcomes = outcomes([
+            (ALCHEMY, Ok(1)),
+            (
+                ANKR,
+                Err(RpcError::HttpOutcallError(
+                    HttpOutcallError::InvalidHttpJsonRpcResponse {
+                        status: 503,
+                        body: "Service Unavailable".to_string(),
+                        parsing_error: None,
+                    },
+                )),
+            ),
+            (
+                HELIUS,
+                Err(RpcError::ValidationError("Invalid response".to_string())),
+            ),
+        ]);
+
+        assert_eq!(
+            outcomes,
+            BTreeMap::from([
+                (ALCHEMY, RequestOutcome::Response),
+                (ANKR, RequestOutcome::Failure),
+                (HELIUS, RequestOutcome::Error),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_ignore_custom_providers() {
+        let mut multi_results = MultiResults::default();
+        multi_results.insert_once(RpcSource::Supported(ALCHEMY), Ok(1));
+        multi_results.insert_once(
+            RpcSource::Custom(RpcEndpoint::new("https://solana.example.com")),
+            Ok(2),
+        );
+
+        assert_eq!(
+            request_outcomes(&multi_results),
+            vec![(ALCHEMY, RequestOutcome::Response)]
+        );
+    }
+
+    fn outcomes(
+        results: impl IntoIterator<Item = (SupportedRpcProviderId, RpcResult<u64>)>,
+    ) -> BTreeMap<SupportedRpcProviderId, RequestOutcome> {
+        let mut multi_results: MultiCallResults<u64> = MultiResults::default();
+        for (provider, result) in results {
+            multi_results.insert_once(RpcSource::Supported(provider), result);
+        }
+        request_outcomes(&multi_results).into_iter().collect()
+    }
+
+    fn json_rpc_error() -> RpcError {
+        RpcError::JsonRpcError(JsonRpcError {
+            code: -32_007,
+            message: "Slot 1450300 was skipped".to_string(),
+            data: None,
+        })
+    }
+}
+
+#[test]
+fn should_aggregate_outcomes_by_provider() {
+    let period = ScorecardPeriod {
+        year: 2025,
+        month: 6,
+    };
+    let mut scores = ProviderScores::new(period);
+    for (provider, outcome) in [
+        (HELIUS, RequestOutcome::Response),
+        (ALCHEMY, RequestOutcome::Response),
+        (ALCHEMY, RequestOutcome::Disagreement),
+        (ALCHEMY, RequestOutcome::Failure),
+        (ALCHEMY, RequestOutcome::Error),
+    ] {
+        scores.record(provider, outcome);
+    }
+
+    let scorecard = ProviderScorecard::from(scores.clone());
+
+    assert_eq!(
+        scorecard,
+        ProviderScorecard {
+            period,
+            scores: vec![
+                ProviderScore {
+                    provider: ALCHEMY,
+                    num_requests: 4,
+                    num_responses: 2,
+                    num_failures: 1,
+                    num_disagreements: 1,
+                },
+                ProviderScore {
+                    provider: HELIUS,
+                    num_requests: 1,
+                    num_responses: 1,
+                    num_failures: 0,
+                    num_disagreements: 0,
+                },
+            ],
+        }
+    );
+    assert_eq!(ProviderScores::from(scorecard), scores);
+}
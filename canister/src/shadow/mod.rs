@@ -0,0 +1,92 @@
+//! Shadow mode for supported providers, see [`ShadowProviderStats`].
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    rpc_client::{MultiCallResults, OutcallsMetadata, ReducedResult},
+    scorecard::is_response,
+};
+use sol_rpc_types::{RpcSource, ShadowProviderStats, SupportedRpcProviderId};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Outcome of a single request to a provider in shadow mode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ShadowOutcome {
+    /// The provider returned the consistent result returned to the caller.
+    Agreement,
+    /// The provider returned a response that differed from the consistent result returned to
+    /// the caller.
+    Disagreement,
+    /// The request failed without a response from the provider.
+    Failure,
+    /// The provider returned a response, but the providers queried by the caller did not return
+    /// a consistent result to compare it with.
+    Uncompared,
+}
+
+/// Returns the outcome of the request to each provider in shadow mode, together with its
+/// latency, by comparing its result with the result returned to the caller.
+///
+/// Responses include JSON-RPC errors, since those are returned by the providers themselves.
+pub fn shadow_outcomes<T: PartialEq>(
+    shadow_results: &MultiCallResults<T>,
+    shadow_metadata: &OutcallsMetadata,
+    reduced_result: &ReducedResult<T>,
+) -> Vec<(SupportedRpcProviderId, ShadowOutcome, Duration)> {
+    shadow_results
+        .iter()
+        .filter_map(|(source, result)| {
+            let RpcSource::Supported(provider) = source else {
+                return None;
+            };
+            let outcome = if !is_response(result) {
+                ShadowOutcome::Failure
+            } else {
+                match (result, reduced_result) {
+                    (_, Err(_)) => ShadowOutcome::Uncompared,
+                    (Ok(value), Ok(expected)) if value == expected => ShadowOutcome::Agreement,
+                    _ => ShadowOutcome::Disagreement,
+                }
+            };
+            let latency = shadow_metadata
+                .get(source)
+                .and_then(|metadata| metadata.latency)
+                .unwrap_or_default();
+            Some((*provider, outcome, latency))
+        })
+        .collect()
+}
+
+/// Statistics of the providers in shadow mode.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ShadowStats(BTreeMap<SupportedRpcProviderId, ShadowProviderStats>);
+
+impl ShadowStats {
+    pub fn record(
+        &mut self,
+        provider: SupportedRpcProviderId,
+        outcome: ShadowOutcome,
+        latency: Duration,
+    ) {
+        let stats = self
+            .0
+            .entry(provider)
+            .or_insert_with(|| ShadowProviderStats::new(provider));
+        stats.num_requests = stats.num_requests.saturating_add(1);
+        let latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        stats.total_latency_ms = stats.total_latency_ms.saturating_add(latency_ms);
+        let counter = match outcome {
+            ShadowOutcome::Agreement => &mut stats.num_agreements,
+            ShadowOutcome::Disagreement => &mut stats.num_disagreements,
+            ShadowOutcome::Failure => &mut stats.num_failures,
+            ShadowOutcome::Uncompared => &mut stats.num_uncompared,
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Returns the statistics of each provider, sorted by provider.
+    pub fn to_vec(&self) -> Vec<ShadowProviderStats> {
+        self.0.values().cloned().collect()
+    }
+}
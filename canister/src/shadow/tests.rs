@@ -0,0 +1,142 @@
+use crate::{
+    rpc_client::{MultiCallResults, OutcallMetadata, OutcallsMetadata, ReducedResult},
+    shadow::{shadow_outcomes, ShadowOutcome, ShadowStats},
+};
+use canhttp::multi::{MultiResults, ReductionError};
+use sol_rpc_types::{
+    HttpOutcallError, JsonRpcError, RpcError, RpcResult, RpcSource, ShadowProviderStats,
+    SupportedRpcProviderId,
+};
+use std::time::Duration;
+
+const ALCHEMY: SupportedRpcProviderId = SupportedRpcProviderId::AlchemyMainnet;
+const ANKR: SupportedRpcProviderId = SupportedRpcProviderId::AnkrMainnet;
+
+mod shadow_outcomes_tests {
+    use super::*;
+
+    #[test]
+    fn should_compare_with_consistent_result() {
+        for (result, expected) in [
+            (Ok(1), ShadowOutcome::Agreement),
+            (Ok(2), ShadowOutcome::Disagreement),
+            (Err(json_rpc_error()), ShadowOutcome::Disagreement),
+            (Err(http_outcall_error()), ShadowOutcome::Failure),
+        ] {
+            assert_eq!(outcome(result, Ok(1)), expected);
+        }
+    }
+
+    #[test]
+    fn should_not_compare_without_consistent_result() {
+        let failed = || Err(ReductionError::ConsistentError(json_rpc_error()));
+
+        assert_eq!(outcome(Ok(1), failed()), ShadowOutcome::Uncompared);
+        assert_eq!(
+            outcome(Err(json_rpc_error()), failed()),
+            ShadowOutcome::Uncompared
+        );
+        assert_eq!(
+            outcome(Err(http_outcall_error()), failed()),
+            ShadowOutcome::Failure
+        );
+    }
+
+    #[test]
+    fn should_return_latency_of_each_provider() {
+        let mut results: MultiCallResults<u64> = MultiResults::default();
+        results.insert_once(RpcSource::Supported(ALCHEMY), Ok(1));
+        results.insert_once(RpcSource::Supported(ANKR), Ok(2));
+        let metadata = OutcallsMetadata::from([
+            (RpcSource::Supported(ALCHEMY), latency(100)),
+            (RpcSource::Supported(ANKR), latency(200)),
+        ]);
+
+        assert_eq!(
+            shadow_outcomes(&results, &metadata, &Ok(1)),
+            vec![
+                (
+                    ALCHEMY,
+                    ShadowOutcome::Agreement,
+                    Duration::from_millis(100)
+                ),
+                (
+                    ANKR,
+                    ShadowOutcome::Disagreement,
+                    Duration::from_millis(200)
+                ),
+            ]
+        );
+    }
+
+    fn outcome(result: RpcResult<u64>, reduced_result: ReducedResult<u64>) -> ShadowOutcome {
+        let mut results: MultiCallResults<u64> = MultiResults::default();
+        results.insert_once(RpcSource::Supported(ALCHEMY), result);
+        let [(provider, outcome, _latency)] =
+            shadow_outcomes(&results, &OutcallsMetadata::new(), &reduced_result)
+                .try_into()
+                .unwrap();
+        assert_eq!(provider, ALCHEMY);
+        outcome
+    }
+
+    fn latency(millis: u64) -> OutcallMetadata {
+        OutcallMetadata {
+            latency: Some(Duration::from_millis(millis)),
+            uses_api_key: false,
+        }
+    }
+}
+
+#[test]
+fn should_aggregate_shadow_stats() {
+    let mut stats = ShadowStats::default();
+    stats.record(
+        ALCHEMY,
+        ShadowOutcome::Agreement,
+        Duration::from_millis(100),
+    );
+    stats.record(
+        ALCHEMY,
+        ShadowOutcome::Disagreement,
+        Duration::from_millis(300),
+    );
+    stats.record(ANKR, ShadowOutcome::Failure, Duration::from_millis(50));
+    stats.record(ANKR, ShadowOutcome::Uncompared, Duration::from_millis(50));
+
+    assert_eq!(
+        stats.to_vec(),
+        vec![
+            ShadowProviderStats {
+                num_requests: 2,
+                num_agreements: 1,
+                num_disagreements: 1,
+                total_latency_ms: 400,
+                ..ShadowProviderStats::new(ALCHEMY)
+            },
+            ShadowProviderStats {
+                num_requests: 2,
+                num_failures: 1,
+                num_uncompared: 1,
+                total_latency_ms: 100,
+                ..ShadowProviderStats::new(ANKR)
+            },
+        ]
+    );
+}
+
+fn json_rpc_error() -> RpcError {
+    RpcError::JsonRpcError(JsonRpcError {
+        code: -32_007,
+        message: "Slot 1450300 was skipped".to_string(),
+        data: None,
+    })
+}
+
+fn http_outcall_error() -> RpcError {
+    RpcError::HttpOutcallError(HttpOutcallError::InvalidHttpJsonRpcResponse {
+        status: 503,
+        body: String::new(),
+        parsing_error: None,
+    })
+}
@@ -0,0 +1,130 @@
+//! Periodic fetch of the current slot, configured with a [`SlotTicker`].
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    http::CyclesPayer, log, logs::Priority, memory::read_state, rpc_client::GetSlotRequest,
+    upgrade::is_upgrade_in_progress,
+};
+use canhttp::multi::{ReductionError, Timestamp};
+use sol_rpc_types::{CachedSlot, GetSlotParams, GetSlotRpcConfig, SlotTicker};
+use std::{
+    cell::{Cell, RefCell},
+    time::Duration,
+};
+
+thread_local! {
+    /// Latest slot fetched by the slot ticker.
+    ///
+    /// The cached slot is not persisted across upgrades, so that a stale slot is never served
+    /// after an upgrade.
+    static CACHED_SLOT: RefCell<Option<CachedSlot>> = const { RefCell::new(None) };
+    /// Whether the current slot is currently being fetched.
+    static IS_TICKING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Starts a timer periodically fetching the current slot, if enabled.
+///
+/// Timers are not persisted across upgrades, so this must be called after every
+/// installation and upgrade of the canister.
+pub fn schedule_slot_ticker() {
+    if let SlotTicker::Enabled {
+        interval_seconds, ..
+    } = read_state(|s| s.get_slot_ticker())
+    {
+        let interval = Duration::from_secs(interval_seconds as u64);
+        log!(
+            Priority::Info,
+            "[schedule_slot_ticker]: fetching the current slot every {interval:?}"
+        );
+        ic_cdk_timers::set_timer_interval(interval, tick);
+    }
+}
+
+/// Returns the latest slot fetched by the slot ticker, if any.
+pub fn get_cached_slot() -> Option<CachedSlot> {
+    CACHED_SLOT.with_borrow(Clone::clone)
+}
+
+async fn tick() {
+    let SlotTicker::Enabled {
+        sources,
+        commitment,
+        ..
+    } = read_state(|s| s.get_slot_ticker())
+    else {
+        return;
+    };
+    // No HTTPS outcall is made while the canister is about to be upgraded.
+    if is_upgrade_in_progress() {
+        return;
+    }
+    // Skip this round if the previous fetch has not completed yet, e.g. because
+    // HTTPS outcalls are queued, to avoid paying for several fetches of the same slot.
+    if IS_TICKING.replace(true) {
+        return;
+    }
+    let _guard = TickGuard;
+
+    let now = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
+    let params = GetSlotParams {
+        commitment: commitment.clone(),
+        min_context_slot: None,
+    };
+    let request = match GetSlotRequest::get_slot(sources, GetSlotRpcConfig::default(), params, now)
+    {
+        Ok(request) => request,
+        Err(e) => {
+            log!(Priority::Info, "[tick]: invalid slot ticker sources: {e:?}");
+            return;
+        }
+    };
+    let (result, _latencies) = request.send_and_reduce_paid_by(CyclesPayer::Canister).await;
+    match result {
+        Ok(slot) => CACHED_SLOT.with_borrow_mut(|cached| {
+            *cached = next_cached_slot(
+                cached.take(),
+                CachedSlot {
+                    slot,
+                    commitment,
+                    timestamp: ic_cdk::api::time(),
+                },
+            )
+        }),
+        Err(ReductionError::ConsistentError(e)) => {
+            log!(
+                Priority::Debug,
+                "[tick]: failed to fetch the current slot: {e:?}"
+            )
+        }
+        Err(ReductionError::InconsistentResults(results)) => log!(
+            Priority::Debug,
+            "[tick]: inconsistent results when fetching the current slot: {results:?}"
+        ),
+    }
+}
+
+/// Returns the slot to cache after fetching the given slot.
+///
+/// Since providers may lag behind, the cached slot never decreases, unless the commitment level
+/// of the slot ticker changed.
+fn next_cached_slot(current: Option<CachedSlot>, fetched: CachedSlot) -> Option<CachedSlot> {
+    match current {
+        Some(current)
+            if current.commitment == fetched.commitment && current.slot > fetched.slot =>
+        {
+            Some(current)
+        }
+        _ => Some(fetched),
+    }
+}
+
+/// Resets [`IS_TICKING`] when dropped, including when the callback of an HTTPS outcall traps.
+struct TickGuard;
+
+impl Drop for TickGuard {
+    fn drop(&mut self) {
+        IS_TICKING.set(false);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::slot_ticker::next_cached_slot;
+use sol_rpc_types::{CachedSlot, CommitmentLevel};
+
+#[test]
+fn should_cache_first_fetched_slot() {
+    let fetched = cached_slot(100, Some(CommitmentLevel::Finalized), 1);
+
+    assert_eq!(next_cached_slot(None, fetched.clone()), Some(fetched));
+}
+
+#[test]
+fn should_cache_newer_slot() {
+    let current = cached_slot(100, None, 1);
+    let fetched = cached_slot(120, None, 2);
+
+    assert_eq!(
+        next_cached_slot(Some(current), fetched.clone()),
+        Some(fetched)
+    );
+}
+
+#[test]
+fn should_keep_current_slot_when_fetched_slot_is_older() {
+    let current = cached_slot(120, None, 1);
+    let fetched = cached_slot(100, None, 2);
+
+    assert_eq!(
+        next_cached_slot(Some(current.clone()), fetched),
+        Some(current)
+    );
+}
+
+#[test]
+fn should_cache_older_slot_with_other_commitment() {
+    let current = cached_slot(120, Some(CommitmentLevel::Processed), 1);
+    let fetched = cached_slot(100, Some(CommitmentLevel::Finalized), 2);
+
+    assert_eq!(
+        next_cached_slot(Some(current), fetched.clone()),
+        Some(fetched)
+    );
+}
+
+fn cached_slot(slot: u64, commitment: Option<CommitmentLevel>, timestamp: u64) -> CachedSlot {
+    CachedSlot {
+        slot,
+        commitment,
+        timestamp,
+    }
+}
@@ -66,10 +66,7 @@ impl OverrideProvider {
             Some(substitution) => {
                 let regex = substitution.pattern.compile()?;
                 let new_url = regex.replace_all(&api.url, &substitution.replacement);
-                Ok(RpcEndpoint {
-                    url: new_url.to_string(),
-                    headers: None,
-                })
+                Ok(RpcEndpoint::new(new_url.to_string()))
             }
         }
     }
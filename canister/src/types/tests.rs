@@ -17,14 +17,11 @@ mod override_provider_tests {
         #[test]
         fn should_override_provider_with_localhost(provider in arb_provider()) {
             with_api_key_for_provider(provider);
-            let api = resolve_rpc_provider(RpcSource::Supported(provider));
+            let api = resolve_rpc_provider(RpcSource::Supported(provider)).unwrap();
             let overriden_provider  = override_to_localhost().apply(api);
             assert_eq!(
                 overriden_provider,
-                Ok(RpcEndpoint {
-                    url: "http://localhost:8545".to_string(),
-                    headers: None
-                })
+                Ok(RpcEndpoint::new("http://localhost:8545"))
             );
         }
     }
@@ -34,7 +31,7 @@ mod override_provider_tests {
         fn should_be_noop_when_empty(provider in arb_provider()) {
             with_api_key_for_provider(provider);
             let no_override = OverrideProvider::default();
-            let initial_api = resolve_rpc_provider(RpcSource::Supported(provider));
+            let initial_api = resolve_rpc_provider(RpcSource::Supported(provider)).unwrap();
             let overriden_api = no_override.apply(initial_api.clone());
             assert_eq!(Ok(initial_api), overriden_api);
         }
@@ -50,13 +47,10 @@ mod override_provider_tests {
                     replacement: ".ch".to_string(),
                 }),
             };
-            let initial_api = resolve_rpc_provider(RpcSource::Supported(provider));
+            let initial_api = resolve_rpc_provider(RpcSource::Supported(provider)).unwrap();
             let overriden_provider = identity_override.apply(initial_api.clone());
             assert_eq!(overriden_provider,
-                Ok(RpcEndpoint {
-                    url: initial_api.url.replace(".com", ".ch"),
-                    headers: None,
-                })
+                Ok(RpcEndpoint::new(initial_api.url.replace(".com", ".ch")))
             );
         }
     }
@@ -71,20 +65,16 @@ mod override_provider_tests {
                     replacement: "$1".to_string(),
                 }),
             };
-            let api_with_headers = RpcEndpoint {
-                headers: Some(vec![HttpHeader {
+            let api_with_headers = resolve_rpc_provider(RpcSource::Supported(provider))
+                .unwrap()
+                .with_headers(vec![HttpHeader {
                     name: "key".to_string(),
                     value: "123".to_string(),
-                }]),
-                ..resolve_rpc_provider(RpcSource::Supported(provider))
-            };
+                }]);
             let overriden_provider = identity_override.apply(api_with_headers.clone());
             assert_eq!(
                 overriden_provider,
-                Ok(RpcEndpoint {
-                    url: api_with_headers.url,
-                    headers: None
-                })
+                Ok(RpcEndpoint::new(api_with_headers.url))
             )
         }
     }
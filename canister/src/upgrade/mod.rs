@@ -0,0 +1,103 @@
+//! Barrier draining the pending HTTPS outcalls before an upgrade, see `prepareUpgrade`.
+//!
+//! Upgrading the SOL RPC canister while HTTPS outcalls are in flight makes the requests waiting
+//! for them fail with opaque errors. Before an upgrade, a controller may therefore call
+//! `prepareUpgrade`, after which new requests are rejected with [`RpcError::UpgradeInProgress`]
+//! and which only returns once the pending HTTPS outcalls completed or the timeout expired.
+//! Since the responses of these HTTPS outcalls are then recorded, e.g. in the provider usage and
+//! scores, they are persisted in `pre_upgrade` like the rest of the transient state. Deposits
+//! of cycles whose outcome is unknown, e.g. because the call to the cycles ledger timed out, are
+//! kept in the state in stable memory, so that they can still be credited when they are retried
+//! after the upgrade.
+//!
+//! The periodic tasks, i.e. the slot ticker, the version sampler, the log drain and the scorecard
+//! roll-up, as well as `depositCycles`, also check [`is_upgrade_in_progress`] and skip their work
+//! while the barrier is up.
+//!
+//! [`RpcError::UpgradeInProgress`]: sol_rpc_types::RpcError::UpgradeInProgress
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    http::concurrency::{num_outcalls_in_flight, num_queued_outcalls},
+    log,
+    logs::Priority,
+    util::sleep,
+};
+use sol_rpc_types::UpgradeReadiness;
+use std::{cell::Cell, time::Duration};
+
+/// Time to wait for the pending HTTPS outcalls to complete, if not specified.
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+/// Maximum time to wait for the pending HTTPS outcalls to complete.
+pub const MAX_DRAIN_TIMEOUT: Duration = Duration::from_secs(300);
+/// Time between two checks of the number of pending HTTPS outcalls.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+thread_local! {
+    /// Whether new requests are rejected because the canister is about to be upgraded.
+    ///
+    /// The barrier is not persisted, so that it is lifted by the upgrade.
+    static UPGRADE_IN_PROGRESS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether new requests are rejected because the canister is about to be upgraded.
+pub fn is_upgrade_in_progress() -> bool {
+    UPGRADE_IN_PROGRESS.get()
+}
+
+/// Stops accepting new requests and waits until the pending HTTPS outcalls completed, for at
+/// most the given number of seconds, see [`drain_timeout`].
+///
+/// Returns the HTTPS outcalls that are still pending.
+pub async fn prepare_upgrade(timeout_seconds: Option<u32>) -> UpgradeReadiness {
+    UPGRADE_IN_PROGRESS.set(true);
+    let timeout = drain_timeout(timeout_seconds);
+    let mut readiness = pending_outcalls();
+    log!(
+        Priority::Info,
+        "[prepare_upgrade]: rejecting new requests and waiting at most {timeout:?} for pending HTTPS outcalls: {readiness:?}"
+    );
+    let deadline = ic_cdk::api::time().saturating_add(timeout.as_nanos() as u64);
+    while !readiness.is_ready() {
+        let now = ic_cdk::api::time();
+        if now >= deadline {
+            log!(
+                Priority::Info,
+                "[prepare_upgrade]: timed out with pending HTTPS outcalls: {readiness:?}"
+            );
+            return readiness;
+        }
+        sleep(DRAIN_POLL_INTERVAL.min(Duration::from_nanos(deadline - now))).await;
+        readiness = pending_outcalls();
+    }
+    log!(
+        Priority::Info,
+        "[prepare_upgrade]: no pending HTTPS outcalls, ready to be upgraded"
+    );
+    readiness
+}
+
+/// Accepts new requests again, e.g. because the upgrade was aborted.
+pub fn cancel_upgrade() {
+    if UPGRADE_IN_PROGRESS.replace(false) {
+        log!(Priority::Info, "[cancel_upgrade]: accepting new requests");
+    }
+}
+
+/// Returns the time to wait for the pending HTTPS outcalls to complete, which is
+/// [`DEFAULT_DRAIN_TIMEOUT`] if not specified and at most [`MAX_DRAIN_TIMEOUT`].
+pub fn drain_timeout(timeout_seconds: Option<u32>) -> Duration {
+    timeout_seconds
+        .map(|seconds| Duration::from_secs(seconds as u64))
+        .unwrap_or(DEFAULT_DRAIN_TIMEOUT)
+        .min(MAX_DRAIN_TIMEOUT)
+}
+
+fn pending_outcalls() -> UpgradeReadiness {
+    UpgradeReadiness {
+        num_outcalls_in_flight: num_outcalls_in_flight(),
+        num_queued_outcalls: num_queued_outcalls() as u32,
+    }
+}
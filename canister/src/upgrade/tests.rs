@@ -0,0 +1,15 @@
+use super::{drain_timeout, DEFAULT_DRAIN_TIMEOUT, MAX_DRAIN_TIMEOUT};
+use std::time::Duration;
+
+#[test]
+fn should_use_default_drain_timeout() {
+    assert_eq!(drain_timeout(None), DEFAULT_DRAIN_TIMEOUT);
+}
+
+#[test]
+fn should_cap_drain_timeout() {
+    assert_eq!(drain_timeout(Some(0)), Duration::ZERO);
+    assert_eq!(drain_timeout(Some(10)), Duration::from_secs(10));
+    assert_eq!(drain_timeout(Some(300)), MAX_DRAIN_TIMEOUT);
+    assert_eq!(drain_timeout(Some(u32::MAX)), MAX_DRAIN_TIMEOUT);
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 
+use std::time::Duration;
 use url::Host;
 
 pub fn hostname_from_url(url: &str) -> Option<String> {
@@ -15,3 +16,12 @@ pub fn hostname_from_url(url: &str) -> Option<String> {
         _ => None,
     })
 }
+
+/// Canisters cannot sleep, so that this waits for a timer to fire instead.
+pub async fn sleep(duration: Duration) {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    ic_cdk_timers::set_timer(duration, async move {
+        let _ = sender.send(());
+    });
+    let _ = receiver.await;
+}
@@ -0,0 +1,185 @@
+//! Periodic fetch of the software version of the supported providers, configured with a
+//! [`VersionSampler`].
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    add_metric_entry, log, logs::Priority, memory::read_state, metrics::MetricSolanaCluster,
+    providers::PROVIDERS, rpc_client::fetch_provider_versions, upgrade::is_upgrade_in_progress,
+};
+use canhttp::multi::Timestamp;
+use sol_rpc_types::{
+    SampledVersion, SolanaCluster, SolanaVersion, SupportedRpcProviderId, VersionSampler,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+thread_local! {
+    /// Latest version fetched by the version sampler for each supported provider.
+    ///
+    /// The sampled versions are not persisted across upgrades, so that a stale version never
+    /// affects the selection of the providers after an upgrade.
+    static SAMPLED_VERSIONS: RefCell<BTreeMap<SupportedRpcProviderId, SampledVersion>> =
+        const { RefCell::new(BTreeMap::new()) };
+    /// Whether the versions are currently being fetched.
+    static IS_SAMPLING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Starts a timer periodically fetching the version of the supported providers, if enabled.
+///
+/// Timers are not persisted across upgrades, so this must be called after every
+/// installation and upgrade of the canister.
+pub fn schedule_version_sampler() {
+    if let VersionSampler::Enabled {
+        interval_seconds, ..
+    } = read_state(|s| s.get_version_sampler())
+    {
+        let interval = Duration::from_secs(interval_seconds as u64);
+        log!(
+            Priority::Info,
+            "[schedule_version_sampler]: fetching the version of the providers every {interval:?}"
+        );
+        ic_cdk_timers::set_timer_interval(interval, sample);
+    }
+}
+
+/// Returns the latest version of the given provider fetched by the version sampler, if any.
+pub fn get_sampled_version(provider: SupportedRpcProviderId) -> Option<SampledVersion> {
+    SAMPLED_VERSIONS.with_borrow(|versions| versions.get(&provider).cloned())
+}
+
+/// Returns `true` if the latest version of the given provider fetched by the version sampler is
+/// older than the minimum version of the version sampler.
+///
+/// Providers whose version is not known are never considered outdated.
+pub fn is_outdated(provider: SupportedRpcProviderId) -> bool {
+    // Versions are only sampled when the version sampler is enabled, so that the state only
+    // needs to be read when the version of the provider is known.
+    let Some(sampled) = get_sampled_version(provider) else {
+        return false;
+    };
+    match read_state(|s| s.get_version_sampler()) {
+        VersionSampler::Enabled {
+            min_version: Some(min_version),
+            ..
+        } => is_older(&sampled.version.solana_core, &min_version),
+        _ => false,
+    }
+}
+
+async fn sample() {
+    let VersionSampler::Enabled { clusters, .. } = read_state(|s| s.get_version_sampler()) else {
+        return;
+    };
+    // No HTTPS outcall is made while the canister is about to be upgraded.
+    if is_upgrade_in_progress() {
+        return;
+    }
+    // Skip this round if the previous fetch has not completed yet.
+    if IS_SAMPLING.replace(true) {
+        return;
+    }
+    let _guard = SampleGuard;
+
+    for cluster in clusters.into_iter().collect::<BTreeSet<_>>() {
+        let providers = cluster_providers(cluster);
+        if providers.is_empty() {
+            continue;
+        }
+        let now = Timestamp::from_nanos_since_unix_epoch(ic_cdk::api::time());
+        let versions = match fetch_provider_versions(providers.clone(), now).await {
+            Ok(versions) => versions,
+            Err(e) => {
+                log!(
+                    Priority::Info,
+                    "[sample]: invalid providers for {cluster:?}: {e:?}"
+                );
+                continue;
+            }
+        };
+        let timestamp = ic_cdk::api::time();
+        SAMPLED_VERSIONS.with_borrow_mut(|sampled| {
+            for (provider, result) in versions {
+                match result {
+                    Ok(version) => {
+                        sampled.insert(provider, SampledVersion { version, timestamp });
+                    }
+                    Err(e) => log!(
+                        Priority::Debug,
+                        "[sample]: failed to fetch the version of {provider:?}: {e:?}"
+                    ),
+                }
+            }
+        });
+        let versions: Vec<_> = providers
+            .into_iter()
+            .filter_map(get_sampled_version)
+            .map(|sampled| sampled.version)
+            .collect();
+        let distinct = distinct_versions(&versions);
+        if distinct.len() > 1 {
+            log!(
+                Priority::Info,
+                "[sample]: providers of {cluster:?} run different versions: {distinct:?}"
+            );
+            add_metric_entry!(version_skew, MetricSolanaCluster::from(cluster), 1);
+        }
+    }
+}
+
+/// Returns the supported providers of the given cluster.
+fn cluster_providers(cluster: SolanaCluster) -> Vec<SupportedRpcProviderId> {
+    PROVIDERS.with(|providers| {
+        providers
+            .iter()
+            .filter(|(_, provider)| provider.cluster == cluster)
+            .map(|(id, _)| *id)
+            .collect()
+    })
+}
+
+/// Returns the distinct versions of `solana-core` among the given versions.
+fn distinct_versions(versions: &[SolanaVersion]) -> BTreeSet<&str> {
+    versions
+        .iter()
+        .map(|version| version.solana_core.as_str())
+        .collect()
+}
+
+/// Parses the major, minor and patch numbers of a version of `solana-core`, e.g. `2.3.6`,
+/// ignoring any pre-release or build suffix of the patch number, e.g. `2.3.6-beta`.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut numbers = version.trim().splitn(3, '.');
+    let major = numbers.next()?.parse().ok()?;
+    let minor = numbers.next()?.parse().ok()?;
+    let patch = numbers.next()?;
+    let patch = patch
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(patch, |end| &patch[..end])
+        .parse()
+        .ok()?;
+    Some((major, minor, patch))
+}
+
+/// Returns `true` if the given version is older than the given minimum version.
+///
+/// Versions that cannot be parsed are never considered older.
+fn is_older(version: &str, min_version: &str) -> bool {
+    match (parse_version(version), parse_version(min_version)) {
+        (Some(version), Some(min_version)) => version < min_version,
+        _ => false,
+    }
+}
+
+/// Resets [`IS_SAMPLING`] when dropped, including when the callback of an HTTPS outcall traps.
+struct SampleGuard;
+
+impl Drop for SampleGuard {
+    fn drop(&mut self) {
+        IS_SAMPLING.set(false);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::version_sampler::{distinct_versions, is_older, parse_version};
+use sol_rpc_types::SolanaVersion;
+
+#[test]
+fn should_parse_version() {
+    assert_eq!(parse_version("2.3.6"), Some((2, 3, 6)));
+    assert_eq!(parse_version("1.18.26"), Some((1, 18, 26)));
+    assert_eq!(parse_version("2.2.0-beta"), Some((2, 2, 0)));
+    assert_eq!(parse_version(" 2.1.21 "), Some((2, 1, 21)));
+}
+
+#[test]
+fn should_not_parse_invalid_version() {
+    for version in ["", "2", "2.3", "2.x.6", "v2.3.6", "2.3.beta"] {
+        assert_eq!(parse_version(version), None, "{version}");
+    }
+}
+
+#[test]
+fn should_compare_versions() {
+    assert!(is_older("2.2.16", "2.3.0"));
+    assert!(is_older("1.18.26", "2.0.0"));
+    assert!(is_older("2.3.9", "2.3.10"));
+    assert!(!is_older("2.3.0", "2.3.0"));
+    assert!(!is_older("2.3.6", "2.3.0"));
+    assert!(!is_older("3.0.0", "2.3.0"));
+}
+
+#[test]
+fn should_not_consider_unknown_version_older() {
+    assert!(!is_older("unknown", "2.3.0"));
+}
+
+#[test]
+fn should_detect_distinct_versions() {
+    assert_eq!(
+        distinct_versions(&[version("2.3.6"), version("2.3.6")]).len(),
+        1
+    );
+    assert_eq!(
+        distinct_versions(&[version("2.3.6"), version("2.2.16"), version("2.3.6")])
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec!["2.2.16", "2.3.6"]
+    );
+    assert!(distinct_versions(&[]).is_empty());
+}
+
+fn version(solana_core: &str) -> SolanaVersion {
+    SolanaVersion {
+        solana_core: solana_core.to_string(),
+        feature_set: None,
+    }
+}
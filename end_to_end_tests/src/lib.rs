@@ -99,12 +99,13 @@ impl Setup {
         let balance_before = self.get_account_balance(account).await;
         let _airdrop_tx = self
             .client()
-            .json_request(json!({
+            .try_json_request(json!({
                 "jsonrpc": "2.0",
                 "id": 1,
                 "method": "requestAirdrop",
                 "params": [account.to_string(), amount]
             }))
+            .unwrap()
             .send()
             .await;
         let expected_balance = balance_before + amount;
@@ -134,7 +134,8 @@ async fn send_transaction_test<F: CreateSolanaMessage>(
     };
 
     let transaction_id = client
-        .send_transaction(transaction)
+        .try_send_transaction(transaction)
+        .unwrap()
         .send()
         .await
         .expect_consistent()
@@ -386,7 +386,8 @@ async fn should_send_transaction() {
     // Don't compare the result to the Solana validator since a transaction can only be submitted once.
     let transaction_id = setup
         .icp_client()
-        .send_transaction(transaction)
+        .try_send_transaction(transaction)
+        .unwrap()
         .send()
         .await
         .expect_consistent()
@@ -630,7 +631,7 @@ fn decode_ui_account(account: UiAccount) -> solana_account::Account {
 
 pub struct Setup {
     solana_client: SolanaRpcClient,
-    setup: sol_rpc_int_tests::Setup,
+    setup: sol_rpc_test_utils::Setup,
 }
 
 impl Setup {
@@ -650,7 +651,7 @@ impl Setup {
                 // sufficient reliability.
                 CommitmentConfig::confirmed(),
             ),
-            setup: sol_rpc_int_tests::Setup::with_pocket_ic_and_args(
+            setup: sol_rpc_test_utils::Setup::with_pocket_ic_and_args(
                 pic,
                 InstallArgs {
                     override_provider: Some(OverrideProvider {
@@ -5,24 +5,30 @@ use candid::{encode_args, Principal};
 use canhttp::http::json::{ConstantSizeId, Id};
 use ic_canister_runtime::CyclesWalletRuntime;
 use ic_cdk::call::RejectCode;
-use ic_pocket_canister_runtime::{
-    CanisterHttpReject, CanisterHttpReply, JsonRpcRequestMatcher, JsonRpcResponse,
-    MockHttpOutcalls, MockHttpOutcallsBuilder, PocketIcRuntime,
-};
 use pocket_ic::{common::rest::CanisterHttpResponse, ErrorCode, RejectResponse};
 use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
+use sol_rpc_canister::logs::Priority;
 use sol_rpc_client::{
     DefaultRequestCycles, RequestBuilder, SolRpcClient, SolRpcConfig, SolRpcEndpoint,
+    TransactionSimulationFailure,
+};
+use sol_rpc_test_utils::{
+    mock_sequential_json_rpc_responses, CanisterHttpReject, CanisterHttpReply,
+    JsonRpcRequestMatcher, JsonRpcResponse, MockHttpOutcalls, MockHttpOutcallsBuilder,
+    PocketIcRuntime, Setup, DEFAULT_CALLER_TEST_ID,
 };
-use sol_rpc_int_tests::{Setup, DEFAULT_CALLER_TEST_ID};
 use sol_rpc_types::{
-    CommitmentLevel, ConfirmedTransactionStatusWithSignature, ConsensusStrategy,
-    GetSignaturesForAddressLimit, GetSlotParams, GetTransactionEncoding, HttpOutcallError,
-    InstallArgs, InstructionError, LegacyRejectionCode, Mode, MultiRpcResult, PrioritizationFee,
-    ProviderError, RpcAccess, RpcAuth, RpcError, RpcResult, RpcSource, RpcSources, Slot,
-    SolanaCluster, SupportedRpcProvider, SupportedRpcProviderId, TransactionDetails,
-    TransactionError,
+    AccountInfo, AllowedCallers, Capabilities, CommitmentLevel,
+    ConfirmedTransactionStatusWithSignature, ConsensusStrategy, EpochInfo, GetBlocksLimit,
+    GetFeeForMessageParams, GetSignaturesForAddressLimit, GetSlotLeadersLimit, GetSlotParams,
+    GetTransactionEncoding, HttpOutcallError, InstallArgs, InstructionError, IpVersion,
+    LegacyRejectionCode, Mode, MultiRpcResult, PricingPolicy, PrioritizationFee, ProviderEndpoint,
+    ProviderError, ProviderScore, RequestIdFormat, RequestIdStrategy, RequestPriority,
+    ResponseCompression, RetryPolicy, RpcAccess, RpcAuth, RpcEndpoint, RpcEndpointApiKey,
+    RpcEndpointAuth, RpcError, RpcResult, RpcSource, RpcSources, ShadowProviderStats, Slot,
+    SolanaCluster, SupportedRpcProvider, SupportedRpcProviderId, TokenAccountsFilter,
+    TransactionDetails, TransactionError, UpgradeReadiness,
 };
 use solana_account_decoder_client_types::{
     token::UiTokenAmount, UiAccount, UiAccountData, UiAccountEncoding,
@@ -39,6 +45,10 @@ use strum::IntoEnumIterator;
 
 const USDC_PUBLIC_KEY: solana_pubkey::Pubkey =
     pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const DELEGATE_PUBLIC_KEY: solana_pubkey::Pubkey =
+    pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+const VALIDATOR_IDENTITY: solana_pubkey::Pubkey =
+    pubkey!("ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n");
 // See: https://internetcomputer.org/docs/references/cycles-cost-formulas#https-outcalls
 const HTTP_OUTCALL_BASE_FEE: u128 = (3_000_000 + 60_000 * 34) * 34;
 
@@ -76,6 +86,233 @@ mod get_provider_tests {
 
         setup.drop().await;
     }
+
+    #[tokio::test]
+    async fn should_get_providers_with_preferred_endpoint() {
+        let access = RpcAccess::Authenticated {
+            auth: RpcAuth::UrlParameter {
+                url_pattern: "https://ipv6.rpc.ankr.com/solana/{API_KEY}".to_string(),
+            },
+            public_url: None,
+        };
+        let setup = Setup::with_args(InstallArgs {
+            provider_endpoints: Some(vec![(
+                SupportedRpcProviderId::AnkrMainnet,
+                ProviderEndpoint {
+                    access: access.clone(),
+                    ip_version: IpVersion::Ipv6,
+                },
+            )]),
+            ..Default::default()
+        })
+        .await;
+        let client = setup.client(MockHttpOutcalls::never()).build();
+        let providers = client.get_providers().await;
+
+        assert_eq!(
+            providers
+                .into_iter()
+                .find(|(id, _)| id == &SupportedRpcProviderId::AnkrMainnet),
+            Some((
+                SupportedRpcProviderId::AnkrMainnet,
+                SupportedRpcProvider {
+                    cluster: SolanaCluster::Mainnet,
+                    access,
+                },
+            ))
+        );
+
+        setup.drop().await;
+    }
+}
+
+mod get_capabilities_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_capabilities() {
+        let setup = Setup::with_args(InstallArgs {
+            mode: Some(Mode::Demo),
+            response_compression: Some(ResponseCompression::Disabled),
+            allowed_callers: Some(AllowedCallers::Only(vec![])),
+            ..Default::default()
+        })
+        .await;
+        let client = setup.client(MockHttpOutcalls::never()).build();
+
+        let capabilities = client.get_capabilities().await.unwrap();
+
+        assert_eq!(capabilities.version.split('.').count(), 3);
+        assert_eq!(capabilities.mode, Mode::Demo);
+        assert_eq!(capabilities.max_response_bytes, 2_000_000);
+        for endpoint in SolRpcEndpoint::iter() {
+            assert!(capabilities.supports_endpoint(endpoint.rpc_method()));
+            assert!(capabilities.supports_endpoint(endpoint.cycles_cost_method()));
+        }
+        assert!(!capabilities.supports_endpoint("verifyApiKey"));
+        assert!(capabilities.has_feature(Capabilities::FEATURE_CALLER_ALLOWLIST));
+        assert!(!capabilities.has_feature(Capabilities::FEATURE_RESPONSE_COMPRESSION));
+
+        setup.drop().await;
+    }
+}
+
+mod validate_state_tests {
+    use super::*;
+    use sol_rpc_types::{OverrideProvider, RegexString, RegexSubstitution, StateValidationIssue};
+
+    #[tokio::test]
+    async fn should_validate_state() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        assert_eq!(setup.validate_state().await, vec![]);
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_report_invalid_override_provider_after_upgrade() {
+        let setup = Setup::new().await;
+
+        setup
+            .upgrade_canister(InstallArgs {
+                override_provider: Some(OverrideProvider {
+                    override_url: Some(RegexSubstitution {
+                        pattern: RegexString::from("(.*"),
+                        replacement: "http://localhost".to_string(),
+                    }),
+                }),
+                ..Default::default()
+            })
+            .await;
+
+        assert_matches!(
+            setup.validate_state().await.as_slice(),
+            [StateValidationIssue::InvalidOverrideProviderRegex { pattern, .. }] if pattern == "(.*"
+        );
+
+        setup.drop().await;
+    }
+}
+
+mod prepare_upgrade_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_reject_requests_until_upgrade_is_cancelled() {
+        let setup = Setup::new().await;
+        let controller = controller_client(&setup);
+
+        let readiness = controller.prepare_upgrade(Some(10)).await.unwrap();
+        assert!(readiness.is_ready(), "{readiness:?}");
+
+        // No mocks, so that the test fails if an HTTPS outcall is made.
+        let result = single_provider_client(&setup, MockHttpOutcalls::never())
+            .get_slot()
+            .send()
+            .await;
+        assert_eq!(
+            result,
+            MultiRpcResult::Consistent(Err(RpcError::UpgradeInProgress))
+        );
+
+        controller.cancel_upgrade().await.unwrap();
+
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(get_slot_request())
+            .respond_with(get_slot_response(1_450_305));
+        let result = single_provider_client(&setup, mocks)
+            .get_slot()
+            .send()
+            .await
+            .expect_consistent();
+        assert_matches!(result, Ok(_));
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_accept_requests_after_upgrade() {
+        let setup = Setup::new().await;
+
+        let readiness = controller_client(&setup)
+            .prepare_upgrade(None)
+            .await
+            .unwrap();
+        assert_eq!(readiness, UpgradeReadiness::default());
+
+        setup.upgrade_canister(InstallArgs::default()).await;
+
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(get_slot_request())
+            .respond_with(get_slot_response(1_450_305));
+        let result = single_provider_client(&setup, mocks)
+            .get_slot()
+            .send()
+            .await
+            .expect_consistent();
+        assert_matches!(result, Ok(_));
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_not_prepare_upgrade_when_not_controller() {
+        let setup = Setup::new().await;
+
+        let result = SolRpcClient::builder_for_pocket_ic(
+            setup.as_ref(),
+            Principal::anonymous(),
+            setup.sol_rpc_canister_id(),
+        )
+        .build()
+        .prepare_upgrade(None)
+        .await;
+
+        assert_matches!(result, Err(_));
+
+        setup.drop().await;
+    }
+
+    fn controller_client(setup: &Setup) -> SolRpcClient<PocketIcRuntime<'_>> {
+        SolRpcClient::builder_for_pocket_ic(
+            setup.as_ref(),
+            setup.controller(),
+            setup.sol_rpc_canister_id(),
+        )
+        .build()
+    }
+
+    fn single_provider_client(
+        setup: &Setup,
+        mocks: impl Into<MockHttpOutcalls>,
+    ) -> SolRpcClient<CyclesWalletRuntime<PocketIcRuntime<'_>>> {
+        setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::PublicNodeMainnet,
+            )]))
+            .build()
+    }
+}
+
+mod get_cached_slot_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_not_have_cached_slot_when_slot_ticker_disabled() {
+        let setup = Setup::new().await;
+        let client = setup.client(MockHttpOutcalls::never()).build();
+
+        assert_eq!(client.get_cached_slot().await.unwrap(), None);
+        assert!(!client
+            .get_capabilities()
+            .await
+            .unwrap()
+            .has_feature(Capabilities::FEATURE_SLOT_TICKER));
+
+        setup.drop().await;
+    }
 }
 
 mod get_account_info_tests {
@@ -141,6 +378,100 @@ mod get_account_info_tests {
     }
 }
 
+mod get_account_snapshot_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_account_snapshot() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(9)) {
+            let mocks = mock_account_snapshot(offset, None);
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let snapshot = client
+                .get_account_snapshot(USDC_PUBLIC_KEY)
+                .with_signatures_limit(GetSignaturesForAddressLimit::try_from(5).unwrap())
+                .send()
+                .await
+                .expect_consistent()
+                .unwrap();
+
+            assert_eq!(snapshot.balance, 389_086_612_571);
+            assert_eq!(
+                snapshot.account,
+                Some(AccountInfo::from(UiAccount {
+                    lamports: 88849814690250,
+                    data: UiAccountData::Binary("1234".to_string(), UiAccountEncoding::Base58),
+                    owner: "11111111111111111111111111111111".to_string(),
+                    executable: false,
+                    rent_epoch: 18446744073709551615,
+                    space: Some(0),
+                }))
+            );
+            assert_eq!(snapshot.signatures.len(), 5);
+        }
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_not_get_account_snapshot_when_one_request_fails() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        // The `getAccountInfo` request to the third provider fails
+        let mocks = mock_account_snapshot(0, Some(5));
+        let client = setup.client(mocks).build();
+
+        let results = client
+            .get_account_snapshot(USDC_PUBLIC_KEY)
+            .with_signatures_limit(GetSignaturesForAddressLimit::try_from(5).unwrap())
+            .send()
+            .await
+            .expect_inconsistent();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results
+                .iter()
+                .filter(|(_source, result)| result.is_err())
+                .count(),
+            1
+        );
+
+        setup.drop().await;
+    }
+
+    /// Mock the `getBalance`, `getAccountInfo` and `getSignaturesForAddress` requests made
+    /// sequentially to 3 providers, starting with the given request ID. The `getAccountInfo`
+    /// request with the given ID, if any, is rejected.
+    fn mock_account_snapshot(offset: u64, rejected_id: Option<u64>) -> MockHttpOutcallsBuilder {
+        let mut mocks = MockHttpOutcallsBuilder::new();
+        for (id, slot) in zip(offset.., SLOTS) {
+            mocks = mocks
+                .given(
+                    JsonRpcRequestMatcher::with_method("getBalance")
+                        .with_params(json!([USDC_PUBLIC_KEY.to_string(), null]))
+                        .with_id(id),
+                )
+                .respond_with(get_balance_response(slot).with_id(id));
+        }
+        for id in offset + 3..offset + 6 {
+            let request = mocks.given(get_account_info_request().with_id(id));
+            mocks = if rejected_id == Some(id) {
+                request.respond_with(CanisterHttpReject::with_reject_code(RejectCode::SysFatal))
+            } else {
+                request.respond_with(get_account_info_response().with_id(id))
+            };
+        }
+        for id in offset + 6..offset + 9 {
+            mocks = mocks
+                .given(get_signatures_for_address_request().with_id(id))
+                .respond_with(get_signatures_for_address_response().with_id(id));
+        }
+        mocks
+    }
+}
+
 mod get_block_tests {
     use super::*;
 
@@ -176,6 +507,35 @@ mod get_block_tests {
         setup.drop().await;
     }
 
+    #[tokio::test]
+    async fn should_get_block_with_rewards() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_for_ids(
+                get_block_request,
+                get_block_with_rewards_response,
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client.get_block(577996).send().await.expect_consistent();
+
+            assert_eq!(
+                results.map(|block| block.and_then(|block| block.rewards)),
+                Ok(Some(vec![solana_transaction_status_client_types::Reward {
+                    pubkey: "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy".to_string(),
+                    lamports: 2_500,
+                    post_balance: 499_999_840_001,
+                    reward_type: Some(sol_rpc_types::RewardType::Fee.into()),
+                    commission: None,
+                }]))
+            );
+        }
+
+        setup.drop().await;
+    }
+
     #[tokio::test]
     async fn should_not_get_block() {
         let setup = Setup::new().await.with_mock_api_keys().await;
@@ -193,6 +553,149 @@ mod get_block_tests {
     }
 }
 
+mod get_blocks_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_blocks() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_for_ids(get_blocks_request, get_blocks_response, offset..=offset + 2);
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client
+                .get_blocks(577996)
+                .with_end_slot(578000)
+                .send()
+                .await
+                .expect_consistent();
+
+            assert_eq!(results, Ok(vec![577996, 577997, 577999, 578000]));
+        }
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_get_blocks_in_ascending_order() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let responses = [
+            json!([577996, 577997, 577999, 578000]),
+            json!([578000, 577999, 577997, 577996]),
+            json!([577997, 577996, 578000, 577999, 577997]),
+        ]
+        .into_iter()
+        .map(|result| {
+            JsonRpcResponse::from(json!({
+                "id": Id::from(ConstantSizeId::ZERO),
+                "jsonrpc": "2.0",
+                "result": result
+            }))
+        });
+        let mocks =
+            mock_sequential_json_rpc_responses(get_blocks_with_limit_request, responses, 0..=2);
+        let client = setup.client(mocks).build();
+
+        let results = client
+            .get_blocks_with_limit(577996)
+            .with_limit(GetBlocksLimit::try_from(5).unwrap())
+            .send()
+            .await
+            .expect_consistent();
+
+        assert_eq!(results, Ok(vec![577996, 577997, 577999, 578000]));
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_not_get_blocks_for_too_large_range() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let client = setup.client(MockHttpOutcalls::never()).build();
+
+        let results = client
+            .get_blocks(577996)
+            .with_end_slot(577996 + GetBlocksLimit::MAX_LIMIT as u64)
+            .send()
+            .await
+            .expect_consistent();
+
+        assert_eq!(
+            results,
+            Err(RpcError::ValidationError(
+                "Expected an end slot between 577996 and 578995, but got 578996".to_string()
+            ))
+        );
+
+        setup.drop().await;
+    }
+}
+
+mod get_leader_schedule_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_leader_schedule() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_for_ids(
+                get_leader_schedule_request,
+                get_leader_schedule_response,
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client
+                .get_leader_schedule(VALIDATOR_IDENTITY)
+                .with_commitment(CommitmentLevel::Finalized)
+                .send()
+                .await
+                .expect_consistent();
+
+            assert_eq!(results, Ok(Some(vec![0, 1, 2, 3])));
+        }
+
+        setup.drop().await;
+    }
+}
+
+mod get_slot_leaders_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_slot_leaders() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_for_ids(
+                get_slot_leaders_request,
+                get_slot_leaders_response,
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client
+                .get_slot_leaders(577996)
+                .with_limit(GetSlotLeadersLimit::try_from(2).unwrap())
+                .send()
+                .await
+                .expect_consistent();
+
+            assert_eq!(
+                results,
+                Ok(vec![
+                    VALIDATOR_IDENTITY,
+                    pubkey!("Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM")
+                ])
+            );
+        }
+
+        setup.drop().await;
+    }
+}
+
 mod get_slot_tests {
     use super::*;
 
@@ -228,6 +731,55 @@ mod get_slot_tests {
         setup.drop().await;
     }
 
+    #[tokio::test]
+    async fn should_collect_all_results_without_reducing_them() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        let mocks = mock_with_response_slots_for_ids(
+            get_slot_request,
+            get_slot_response,
+            [1234, 1234, 1235],
+            0..=2,
+        );
+        let client = setup.client(mocks).build();
+
+        let results: Vec<_> = client
+            .get_slot()
+            .with_rounding_error(0)
+            .with_response_consensus(ConsensusStrategy::CollectAll { total: Some(3) })
+            .send()
+            .await
+            .expect_inconsistent()
+            .into_iter()
+            .map(|(_source, result)| result)
+            .collect();
+
+        assert_eq!(results, vec![Ok(1234), Ok(1234), Ok(1235)]);
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_collect_consistent_result_when_all_providers_agree() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        let mocks =
+            mock_with_response_slots_for_ids(get_slot_request, get_slot_response, [1234; 3], 0..=2);
+        let client = setup.client(mocks).build();
+
+        let slot = client
+            .get_slot()
+            .with_rounding_error(0)
+            .with_response_consensus(ConsensusStrategy::CollectAll { total: Some(3) })
+            .send()
+            .await
+            .expect_consistent();
+
+        assert_eq!(slot, Ok(1234));
+
+        setup.drop().await;
+    }
+
     #[tokio::test]
     async fn should_get_slot_without_rounding() {
         let setup = Setup::new().await.with_mock_api_keys().await;
@@ -303,6 +855,144 @@ mod get_slot_tests {
 
         setup.drop().await;
     }
+
+    #[tokio::test]
+    async fn should_record_response_metadata_of_inconsistent_results() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let mocks = mock_with_response_slots_for_ids(
+            get_slot_request,
+            get_slot_response,
+            [1234, 1229, 1237],
+            0..=2,
+        );
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                RpcSource::Supported(SupportedRpcProviderId::DrpcMainnet),
+                RpcSource::Supported(SupportedRpcProviderId::PublicNodeMainnet),
+            ]))
+            .build();
+
+        let results = client
+            .get_slot()
+            .with_rounding_error(0)
+            .send()
+            .await
+            .expect_inconsistent();
+        assert_eq!(results.len(), 3);
+
+        let metadata = setup
+            .get_response_metadata()
+            .await
+            .expect("BUG: missing response metadata");
+        assert_eq!(
+            metadata
+                .iter()
+                .map(|(source, _)| source)
+                .collect::<Vec<_>>(),
+            results.iter().map(|(source, _)| source).collect::<Vec<_>>()
+        );
+        assert!(metadata.iter().all(|(_, m)| m.latency_ms.is_some()));
+        assert_eq!(
+            metadata
+                .iter()
+                .map(|(_, m)| m.uses_api_key)
+                .collect::<Vec<_>>(),
+            vec![true, true, false]
+        );
+
+        setup.drop().await;
+    }
+}
+
+mod get_block_height_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_consistent_result_with_rounding() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_with_response_slots_for_ids(
+                get_block_height_request,
+                get_block_height_response,
+                [1234, 1229, 1237],
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client.get_block_height().send().await.expect_consistent();
+
+            assert_eq!(results, Ok(1220));
+        }
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_get_inconsistent_result_without_rounding() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_with_response_slots_for_ids(
+                get_block_height_request,
+                get_block_height_response,
+                [1234, 1229, 1237],
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results: Vec<RpcResult<_>> = client
+                .get_block_height()
+                .with_rounding_error(0)
+                .send()
+                .await
+                .expect_inconsistent()
+                .into_iter()
+                .map(|(_source, result)| result)
+                .collect();
+
+            assert_eq!(results, vec![Ok(1234), Ok(1229), Ok(1237)]);
+        }
+
+        setup.drop().await;
+    }
+}
+
+mod get_epoch_info_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_consistent_result_with_rounding() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_with_response_slots_for_ids(
+                get_epoch_info_request,
+                get_epoch_info_response,
+                [1234, 1229, 1237],
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client.get_epoch_info().send().await.expect_consistent();
+
+            assert_eq!(
+                results,
+                Ok(EpochInfo {
+                    epoch: 2,
+                    slot_index: 356,
+                    slots_in_epoch: 432,
+                    absolute_slot: 1220,
+                    block_height: 1120,
+                    transaction_count: None,
+                })
+            );
+        }
+
+        setup.drop().await;
+    }
 }
 
 mod get_recent_prioritization_fees_tests {
@@ -356,33 +1046,148 @@ mod get_recent_prioritization_fees_tests {
 
         setup.drop().await;
     }
-}
-
-mod send_transaction_tests {
-    use super::*;
+}
+
+mod send_transaction_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_send_transaction() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let transaction = some_transaction();
+
+            let mocks = mock_for_ids(
+                || send_transaction_request(&transaction),
+                send_transaction_response,
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client
+                .try_send_transaction(transaction)
+                .unwrap()
+                .send()
+                .await
+                .expect_consistent();
+
+            assert_eq!(results, Ok(some_signature()));
+        }
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_send_transaction_with_valid_blockhash() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let transaction = some_transaction();
+
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(is_blockhash_valid_request().with_id(0))
+            .respond_with(is_blockhash_valid_response(true).with_id(0))
+            .given(send_transaction_request(&transaction).with_id(1))
+            .respond_with(send_transaction_response().with_id(1));
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::AlchemyMainnet,
+            )]))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction)
+            .unwrap()
+            .with_blockhash_validation(true)
+            .send()
+            .await;
+
+        assert_eq!(result, MultiRpcResult::Consistent(Ok(some_signature())));
+
+        setup.drop().await;
+    }
 
     #[tokio::test]
-    async fn should_send_transaction() {
+    async fn should_not_send_transaction_with_expired_blockhash() {
         let setup = Setup::new().await.with_mock_api_keys().await;
 
-        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
-            let transaction = some_transaction();
+        // No mock for `sendTransaction`, so that the test fails if the transaction is sent.
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(is_blockhash_valid_request().with_id(0))
+            .respond_with(is_blockhash_valid_response(false).with_id(0));
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::AlchemyMainnet,
+            )]))
+            .build();
 
-            let mocks = mock_for_ids(
-                || send_transaction_request(&transaction),
-                send_transaction_response,
-                offset..=offset + 2,
-            );
-            let client = setup.client(mocks).with_rpc_sources(sources).build();
+        let result = client
+            .try_send_transaction(some_transaction())
+            .unwrap()
+            .with_blockhash_validation(true)
+            .send()
+            .await;
 
-            let results = client
-                .send_transaction(transaction)
-                .send()
-                .await
-                .expect_consistent();
+        assert_matches!(
+            result,
+            MultiRpcResult::Consistent(Err(RpcError::ValidationError(message))) if message.contains("no longer valid")
+        );
 
-            assert_eq!(results, Ok(some_signature()));
-        }
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_decode_preflight_failure() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let transaction = some_transaction();
+
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(send_transaction_request(&transaction).with_id(0))
+            .respond_with(JsonRpcResponse::from(json!({
+                "jsonrpc": "2.0",
+                "error": {
+                    "code": -32002,
+                    "message": "Transaction simulation failed: Error processing Instruction 0: custom program error: 0x1",
+                    "data": {
+                        "accounts": null,
+                        "err": { "InstructionError": [0, { "Custom": 1 }] },
+                        "logs": [
+                            "Program 11111111111111111111111111111111 invoke [1]",
+                            "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+                        ],
+                        "unitsConsumed": 150
+                    }
+                },
+                "id": Id::from(ConstantSizeId::ZERO)
+            })));
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::AlchemyMainnet,
+            )]))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction)
+            .unwrap()
+            .send()
+            .await
+            .expect_consistent();
+
+        let error = result.expect_err("transaction should fail the preflight checks");
+        assert_eq!(
+            TransactionSimulationFailure::from_preflight_error(&error),
+            Some(TransactionSimulationFailure {
+                err: TransactionError::InstructionError(0, InstructionError::Custom(1)),
+                logs: vec![
+                    "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+                    "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+                        .to_string(),
+                ],
+                units_consumed: Some(150),
+            })
+        );
 
         setup.drop().await;
     }
@@ -474,9 +1279,10 @@ mod generic_request_tests {
             request: RequestBuilder<Runtime, Config, Params, CandidOutput, Output>,
         ) where
             Runtime: ic_canister_runtime::Runtime,
-            Config: CandidType + Clone + Send,
-            Params: CandidType + Clone + Send,
+            Config: CandidType + Clone + Send + 'static,
+            Params: CandidType + Clone + Send + 'static,
             CandidOutput: Into<Output> + CandidType + DeserializeOwned,
+            Output: 'static,
             RequestBuilder<Runtime, Config, Params, CandidOutput, Output>: DefaultRequestCycles,
         {
             let result = request
@@ -497,12 +1303,54 @@ mod generic_request_tests {
                 SolRpcEndpoint::GetAccountInfo => {
                     check(client.get_account_info(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetAccountInfoWithContext => {
+                    check(client.get_account_info(USDC_PUBLIC_KEY).with_context()).await;
+                }
+                SolRpcEndpoint::GetAccountSnapshot => {
+                    check(client.get_account_snapshot(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetBalance => {
                     check(client.get_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetBalanceWithContext => {
+                    check(client.get_balance(USDC_PUBLIC_KEY).with_context()).await;
+                }
                 SolRpcEndpoint::GetBlock => {
                     check(client.get_block(577996)).await;
                 }
+                SolRpcEndpoint::GetBlockHeight => {
+                    check(client.get_block_height()).await;
+                }
+                SolRpcEndpoint::GetBlocks => {
+                    check(client.get_blocks(577996)).await;
+                }
+                SolRpcEndpoint::GetBlocksWithLimit => {
+                    check(client.get_blocks_with_limit(577996)).await;
+                }
+                SolRpcEndpoint::GetEpochInfo => {
+                    check(client.get_epoch_info()).await;
+                }
+                SolRpcEndpoint::GetFeeForMessage => {
+                    check(client.get_fee_for_message(some_message())).await;
+                }
+                SolRpcEndpoint::GetEpochSchedule => {
+                    check(client.get_epoch_schedule()).await;
+                }
+                SolRpcEndpoint::GetClusterNodes => {
+                    check(client.get_cluster_nodes()).await;
+                }
+                SolRpcEndpoint::GetHighestSnapshotSlot => {
+                    check(client.get_highest_snapshot_slot()).await;
+                }
+                SolRpcEndpoint::GetLeaderSchedule => {
+                    check(client.get_leader_schedule(VALIDATOR_IDENTITY)).await;
+                }
+                SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                    check(client.get_minimum_balance_for_rent_exemption(165_u64)).await;
+                }
+                SolRpcEndpoint::GetRecentPerformanceSamples => {
+                    check(client.get_recent_performance_samples()).await;
+                }
                 SolRpcEndpoint::GetRecentPrioritizationFees => {
                     check(client.get_recent_prioritization_fees(&[]).unwrap()).await;
                 }
@@ -512,17 +1360,57 @@ mod generic_request_tests {
                 SolRpcEndpoint::GetSignatureStatuses => {
                     check(client.get_signature_statuses(&[some_signature()]).unwrap()).await;
                 }
+                SolRpcEndpoint::GetSlotLeaders => {
+                    check(client.get_slot_leaders(577996)).await;
+                }
                 SolRpcEndpoint::GetTokenAccountBalance => {
                     check(client.get_token_account_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                    check(
+                        client
+                            .get_token_account_balance(USDC_PUBLIC_KEY)
+                            .with_context(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByDelegate => {
+                    check(client.get_token_accounts_by_delegate((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByOwner => {
+                    check(client.get_token_accounts_by_owner((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::HasAccountChanged => {
+                    check(client.has_account_changed(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetTransaction => {
                     check(client.get_transaction(some_signature())).await;
                 }
+                SolRpcEndpoint::GetSupply => {
+                    check(client.get_supply()).await;
+                }
+                SolRpcEndpoint::GetTransactionCount => {
+                    check(client.get_transaction_count()).await;
+                }
                 SolRpcEndpoint::JsonRequest => {
-                    check(client.json_request(get_version_request_body())).await;
+                    check(client.try_json_request(get_version_request_body()).unwrap()).await;
+                }
+                SolRpcEndpoint::MinimumLedgerSlot => {
+                    check(client.minimum_ledger_slot()).await;
+                }
+                SolRpcEndpoint::RawHttpRequest => {
+                    check(client.raw_http_request(raw_http_request_body())).await;
                 }
                 SolRpcEndpoint::SendTransaction => {
-                    check(client.send_transaction(some_transaction())).await;
+                    check(client.try_send_transaction(some_transaction()).unwrap()).await;
                 }
             }
         }
@@ -536,9 +1424,10 @@ mod generic_request_tests {
             request: RequestBuilder<Runtime, Config, Params, CandidOutput, Output>,
         ) where
             Runtime: ic_canister_runtime::Runtime,
-            Config: CandidType + Clone + Send,
-            Params: CandidType + Clone + Send,
+            Config: CandidType + Clone + Send + 'static,
+            Params: CandidType + Clone + Send + 'static,
             CandidOutput: Into<Output> + CandidType + DeserializeOwned,
+            Output: 'static,
             RequestBuilder<Runtime, Config, Params, CandidOutput, Output>: DefaultRequestCycles,
         {
             let result = request.with_cycles(0).try_send().await;
@@ -569,12 +1458,54 @@ mod generic_request_tests {
                 SolRpcEndpoint::GetAccountInfo => {
                     check(client.get_account_info(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetAccountInfoWithContext => {
+                    check(client.get_account_info(USDC_PUBLIC_KEY).with_context()).await;
+                }
+                SolRpcEndpoint::GetAccountSnapshot => {
+                    check(client.get_account_snapshot(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetBalance => {
                     check(client.get_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetBalanceWithContext => {
+                    check(client.get_balance(USDC_PUBLIC_KEY).with_context()).await;
+                }
                 SolRpcEndpoint::GetBlock => {
                     check(client.get_block(577996)).await;
                 }
+                SolRpcEndpoint::GetBlockHeight => {
+                    check(client.get_block_height()).await;
+                }
+                SolRpcEndpoint::GetBlocks => {
+                    check(client.get_blocks(577996)).await;
+                }
+                SolRpcEndpoint::GetBlocksWithLimit => {
+                    check(client.get_blocks_with_limit(577996)).await;
+                }
+                SolRpcEndpoint::GetEpochInfo => {
+                    check(client.get_epoch_info()).await;
+                }
+                SolRpcEndpoint::GetFeeForMessage => {
+                    check(client.get_fee_for_message(some_message())).await;
+                }
+                SolRpcEndpoint::GetEpochSchedule => {
+                    check(client.get_epoch_schedule()).await;
+                }
+                SolRpcEndpoint::GetClusterNodes => {
+                    check(client.get_cluster_nodes()).await;
+                }
+                SolRpcEndpoint::GetHighestSnapshotSlot => {
+                    check(client.get_highest_snapshot_slot()).await;
+                }
+                SolRpcEndpoint::GetLeaderSchedule => {
+                    check(client.get_leader_schedule(VALIDATOR_IDENTITY)).await;
+                }
+                SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                    check(client.get_minimum_balance_for_rent_exemption(165_u64)).await;
+                }
+                SolRpcEndpoint::GetRecentPerformanceSamples => {
+                    check(client.get_recent_performance_samples()).await;
+                }
                 SolRpcEndpoint::GetRecentPrioritizationFees => {
                     check(client.get_recent_prioritization_fees(&[]).unwrap()).await;
                 }
@@ -584,17 +1515,57 @@ mod generic_request_tests {
                 SolRpcEndpoint::GetSignatureStatuses => {
                     check(client.get_signature_statuses(&[some_signature()]).unwrap()).await;
                 }
+                SolRpcEndpoint::GetSlotLeaders => {
+                    check(client.get_slot_leaders(577996)).await;
+                }
                 SolRpcEndpoint::GetTokenAccountBalance => {
                     check(client.get_token_account_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                    check(
+                        client
+                            .get_token_account_balance(USDC_PUBLIC_KEY)
+                            .with_context(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByDelegate => {
+                    check(client.get_token_accounts_by_delegate((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByOwner => {
+                    check(client.get_token_accounts_by_owner((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::HasAccountChanged => {
+                    check(client.has_account_changed(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetTransaction => {
                     check(client.get_transaction(some_signature())).await;
                 }
+                SolRpcEndpoint::GetSupply => {
+                    check(client.get_supply()).await;
+                }
+                SolRpcEndpoint::GetTransactionCount => {
+                    check(client.get_transaction_count()).await;
+                }
                 SolRpcEndpoint::JsonRequest => {
-                    check(client.json_request(get_version_request_body())).await;
+                    check(client.try_json_request(get_version_request_body()).unwrap()).await;
+                }
+                SolRpcEndpoint::MinimumLedgerSlot => {
+                    check(client.minimum_ledger_slot()).await;
+                }
+                SolRpcEndpoint::RawHttpRequest => {
+                    check(client.raw_http_request(raw_http_request_body())).await;
                 }
                 SolRpcEndpoint::SendTransaction => {
-                    check(client.send_transaction(some_transaction())).await;
+                    check(client.try_send_transaction(some_transaction()).unwrap()).await;
                 }
             }
         }
@@ -615,7 +1586,8 @@ mod generic_request_tests {
         let client = setup.client(mocks).build();
 
         let result = client
-            .json_request(get_version_request_body())
+            .try_json_request(get_version_request_body())
+            .unwrap()
             .with_cycles(0)
             .send()
             .await
@@ -637,8 +1609,8 @@ mod retrieve_logs_tests {
             ..Default::default()
         })
         .await;
-        assert_eq!(setup.retrieve_logs("DEBUG").await, vec![]);
-        assert_eq!(setup.retrieve_logs("INFO").await, vec![]);
+        assert_eq!(setup.retrieve_logs::<Priority>("DEBUG").await, vec![]);
+        assert_eq!(setup.retrieve_logs::<Priority>("INFO").await, vec![]);
 
         // Generate some log
         setup
@@ -650,8 +1622,8 @@ mod retrieve_logs_tests {
             )])
             .await;
 
-        assert_eq!(setup.retrieve_logs("DEBUG").await, vec![]);
-        assert!(setup.retrieve_logs("INFO").await[0]
+        assert_eq!(setup.retrieve_logs::<Priority>("DEBUG").await, vec![]);
+        assert!(setup.retrieve_logs::<Priority>("INFO").await[0]
             .message
             .contains("Updating API keys"));
     }
@@ -659,6 +1631,7 @@ mod retrieve_logs_tests {
 
 mod update_api_key_tests {
     use super::*;
+    use sol_rpc_types::{JsonRpcError, ProviderHealth};
 
     #[tokio::test]
     async fn should_update_api_key() {
@@ -682,6 +1655,76 @@ mod update_api_key_tests {
         setup.verify_api_key((provider, None)).await;
     }
 
+    #[tokio::test]
+    async fn should_update_and_validate_api_keys() {
+        let setup = Setup::with_args(InstallArgs {
+            manage_api_keys: Some(vec![DEFAULT_CALLER_TEST_ID]),
+            ..Default::default()
+        })
+        .await;
+
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(
+                JsonRpcRequestMatcher::with_method("getHealth")
+                    .with_params(json!([]))
+                    .with_id(0),
+            )
+            .respond_with(JsonRpcResponse::from(json!({
+                "jsonrpc": "2.0",
+                "result": "ok",
+                "id": Id::from(ConstantSizeId::from(0_u8)),
+            })))
+            .given(
+                JsonRpcRequestMatcher::with_method("getHealth")
+                    .with_params(json!([]))
+                    .with_id(1),
+            )
+            .respond_with(JsonRpcResponse::from(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32600, "message": "Invalid API key" },
+                "id": Id::from(ConstantSizeId::from(1_u8)),
+            })));
+        let client = setup.client(mocks).build();
+
+        let health = client
+            .update_and_validate_api_keys(&[
+                (
+                    SupportedRpcProviderId::AlchemyMainnet,
+                    Some("valid-api-key".to_string()),
+                ),
+                (
+                    SupportedRpcProviderId::AnkrMainnet,
+                    Some("revoked-api-key".to_string()),
+                ),
+                (SupportedRpcProviderId::HeliusMainnet, None),
+            ])
+            .await;
+
+        assert_eq!(
+            health,
+            vec![
+                (SupportedRpcProviderId::AlchemyMainnet, ProviderHealth::Ok),
+                (
+                    SupportedRpcProviderId::AnkrMainnet,
+                    ProviderHealth::Error(RpcError::JsonRpcError(JsonRpcError {
+                        code: -32600,
+                        message: "Invalid API key".to_string(),
+                        data: None,
+                    }))
+                ),
+            ]
+        );
+        setup
+            .verify_api_key((
+                SupportedRpcProviderId::AnkrMainnet,
+                Some("revoked-api-key".to_string()),
+            ))
+            .await;
+        setup
+            .verify_api_key((SupportedRpcProviderId::HeliusMainnet, None))
+            .await;
+    }
+
     #[tokio::test]
     #[should_panic(expected = "You are not authorized")]
     async fn should_prevent_unauthorized_update_api_keys() {
@@ -696,6 +1739,35 @@ mod update_api_key_tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn should_add_and_remove_api_key_principal() {
+        let setup = Setup::new().await;
+        assert_eq!(setup.get_api_key_principals().await, vec![]);
+
+        setup.add_api_key_principal(DEFAULT_CALLER_TEST_ID).await;
+        assert_eq!(
+            setup.get_api_key_principals().await,
+            vec![DEFAULT_CALLER_TEST_ID]
+        );
+        let provider = SupportedRpcProviderId::AlchemyMainnet;
+        let api_key = "rotated-api-key";
+        setup
+            .client(MockHttpOutcalls::never())
+            .build()
+            .update_api_keys(&[(provider, Some(api_key.to_string()))])
+            .await;
+        setup
+            .verify_api_key((provider, Some(api_key.to_string())))
+            .await;
+
+        setup.remove_api_key_principal(DEFAULT_CALLER_TEST_ID).await;
+        assert_eq!(setup.get_api_key_principals().await, vec![]);
+        // API keys set by a removed principal are kept
+        setup
+            .verify_api_key((provider, Some(api_key.to_string())))
+            .await;
+    }
+
     #[tokio::test]
     #[should_panic(expected = "Trying to set API key for unauthenticated provider")]
     async fn should_prevent_unauthenticated_update_api_keys() {
@@ -714,6 +1786,81 @@ mod update_api_key_tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn should_use_stored_api_key_for_custom_endpoint() {
+        let setup = Setup::with_args(InstallArgs {
+            manage_api_keys: Some(vec![DEFAULT_CALLER_TEST_ID]),
+            ..Default::default()
+        })
+        .await;
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(get_slot_request().with_url("https://my-solana-rpc.com/?api-key=custom-api-key"))
+            .respond_with(get_slot_response(1200));
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Custom(
+                RpcEndpoint::new("https://my-solana-rpc.com/?api-key={API_KEY}").with_auth(
+                    RpcEndpointAuth::UrlParameter {
+                        api_key: RpcEndpointApiKey::Stored,
+                    },
+                ),
+            )]))
+            .build();
+
+        client
+            .update_custom_api_keys(&[(
+                "https://my-solana-rpc.com/?api-key={API_KEY}".to_string(),
+                Some("custom-api-key".to_string()),
+            )])
+            .await;
+        let slot = client.get_slot().send().await.expect_consistent();
+
+        assert_eq!(slot, Ok(1200));
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_use_fallback_provider_when_api_key_missing() {
+        let setup = Setup::with_args(InstallArgs {
+            provider_fallbacks: Some(vec![(
+                SupportedRpcProviderId::HeliusMainnet,
+                vec![SupportedRpcProviderId::PublicNodeMainnet],
+            )]),
+            ..Default::default()
+        })
+        .await;
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(get_slot_request().with_url("https://solana-rpc.publicnode.com"))
+            .respond_with(get_slot_response(1200));
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::HeliusMainnet,
+            )]))
+            .build();
+
+        let slot = client.get_slot().send().await.expect_consistent();
+
+        assert_eq!(slot, Ok(1200));
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "You are not authorized")]
+    async fn should_prevent_unauthorized_update_custom_api_keys() {
+        let setup = Setup::new().await;
+        setup
+            .client(MockHttpOutcalls::never())
+            .build()
+            .update_custom_api_keys(&[(
+                "https://my-solana-rpc.com/".to_string(),
+                Some("unauthorized-api-key".to_string()),
+            )])
+            .await;
+    }
+
     #[tokio::test]
     async fn should_prevent_unauthorized_call_to_verify_api_key() {
         let setup = Setup::new().await.with_mock_api_keys().await;
@@ -745,6 +1892,85 @@ mod update_api_key_tests {
     }
 }
 
+mod allowed_callers_tests {
+    use super::*;
+    use sol_rpc_types::AllowedCallers;
+
+    #[tokio::test]
+    async fn should_only_allow_listed_callers() {
+        let setup = Setup::with_args(InstallArgs {
+            allowed_callers: Some(AllowedCallers::Only(vec![DEFAULT_CALLER_TEST_ID])),
+            ..Default::default()
+        })
+        .await
+        .with_mock_api_keys()
+        .await;
+
+        let client = setup
+            .client(mock_with_response_slots_for_ids(
+                get_slot_request,
+                get_slot_response,
+                [1_450_305; 3],
+                0..=2,
+            ))
+            .build();
+        assert_eq!(
+            client.get_slot().send().await,
+            MultiRpcResult::Consistent(Ok(1_450_300))
+        );
+
+        setup
+            .upgrade_canister(InstallArgs {
+                allowed_callers: Some(AllowedCallers::Only(vec![Principal::anonymous()])),
+                ..Default::default()
+            })
+            .await;
+
+        let client = setup.client(MockHttpOutcalls::never()).build();
+        let request = client.get_slot();
+        // Estimating the cycles cost of a request is not restricted
+        assert_matches!(request.clone().request_cost().send().await, Ok(_));
+        assert_eq!(
+            request.send().await,
+            MultiRpcResult::Consistent(Err(RpcError::Unauthorized {
+                caller: DEFAULT_CALLER_TEST_ID
+            }))
+        );
+
+        setup.check_metrics().await.assert_contains_metric_matching(
+            r#"solrpc_unauthorized_requests\{method="getSlot"\} 1 \d+"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn should_only_exempt_configured_methods() {
+        let setup = Setup::with_args(InstallArgs {
+            allowed_callers: Some(AllowedCallers::Only(vec![])),
+            allowed_callers_exemptions: Some(vec!["getBalanceCyclesCost".to_string()]),
+            ..Default::default()
+        })
+        .await;
+        let client = setup.client(MockHttpOutcalls::never()).build();
+
+        assert_matches!(
+            client
+                .get_balance(USDC_PUBLIC_KEY)
+                .request_cost()
+                .send()
+                .await,
+            Ok(_)
+        );
+        assert_eq!(
+            client.get_slot().request_cost().send().await,
+            Err(RpcError::Unauthorized {
+                caller: DEFAULT_CALLER_TEST_ID
+            })
+        );
+
+        setup.drop().await;
+    }
+}
+
 mod canister_upgrade_tests {
     use super::*;
 
@@ -818,6 +2044,112 @@ mod canister_upgrade_tests {
             )])
             .await;
     }
+
+    #[tokio::test]
+    async fn upgrade_should_keep_provider_scorecard_of_current_period() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let client = setup
+            .client(mock_with_response_slots_for_ids(
+                get_slot_request,
+                get_slot_response,
+                [1234; 3],
+                0..=2,
+            ))
+            .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+            .build();
+        assert_eq!(client.get_provider_scorecard(None).await, Ok(None));
+
+        let slot = client
+            .get_slot()
+            .with_rounding_error(0)
+            .send()
+            .await
+            .expect_consistent();
+        assert_eq!(slot, Ok(1234));
+
+        let scorecard = client.get_provider_scorecard(None).await.unwrap().unwrap();
+        assert_eq!(scorecard.scores.len(), 3);
+        for score in &scorecard.scores {
+            assert_eq!(
+                score,
+                &ProviderScore {
+                    num_requests: 1,
+                    num_responses: 1,
+                    ..ProviderScore::new(score.provider)
+                }
+            );
+        }
+
+        setup.upgrade_canister(InstallArgs::default()).await;
+
+        assert_eq!(
+            client.get_provider_scorecard(None).await,
+            Ok(Some(scorecard.clone()))
+        );
+        assert_eq!(
+            client
+                .get_provider_scorecard(Some(scorecard.period.previous()))
+                .await,
+            Ok(None)
+        );
+
+        setup.drop().await;
+    }
+}
+
+#[tokio::test]
+async fn should_compare_responses_of_shadow_provider() {
+    let setup = Setup::with_args(InstallArgs {
+        shadow_providers: Some(vec![SupportedRpcProviderId::PublicNodeMainnet]),
+        ..Default::default()
+    })
+    .await
+    .with_mock_api_keys()
+    .await;
+    let client = setup
+        .client(mock_with_response_slots_for_ids(
+            get_slot_request,
+            get_slot_response,
+            [1234; 4],
+            0..=3,
+        ))
+        .with_rpc_sources(RpcSources::Custom(vec![
+            RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+            RpcSource::Supported(SupportedRpcProviderId::DrpcMainnet),
+            RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet),
+        ]))
+        .build();
+    assert_eq!(client.get_shadow_provider_stats().await, Ok(vec![]));
+
+    let slot = client
+        .get_slot()
+        .with_rounding_error(0)
+        .send()
+        .await
+        .expect_consistent();
+    assert_eq!(slot, Ok(1234));
+
+    let stats = client.get_shadow_provider_stats().await.unwrap();
+    assert_eq!(
+        stats,
+        vec![ShadowProviderStats {
+            num_requests: 1,
+            num_agreements: 1,
+            total_latency_ms: stats[0].total_latency_ms,
+            ..ShadowProviderStats::new(SupportedRpcProviderId::PublicNodeMainnet)
+        }]
+    );
+    let scorecard = client.get_provider_scorecard(None).await.unwrap().unwrap();
+    assert!(scorecard
+        .scores
+        .iter()
+        .all(|score| score.provider != SupportedRpcProviderId::PublicNodeMainnet));
+
+    setup.check_metrics().await.assert_contains_metric_matching(
+        r#"solrpc_shadow_responses\{provider="PublicNodeMainnet",outcome="agreement"\} 1 \d+"#,
+    );
+
+    setup.drop().await;
 }
 
 fn rpc_sources() -> Vec<RpcSources> {
@@ -864,17 +2196,59 @@ mod cycles_cost_tests {
                     check(client.get_slot()).await;
                 }
                 SolRpcEndpoint::JsonRequest => {
-                    check(client.json_request(get_version_request_body())).await;
+                    check(client.try_json_request(get_version_request_body()).unwrap()).await;
                 }
                 SolRpcEndpoint::GetAccountInfo => {
                     check(client.get_account_info(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetAccountInfoWithContext => {
+                    check(client.get_account_info(USDC_PUBLIC_KEY).with_context()).await;
+                }
+                SolRpcEndpoint::GetAccountSnapshot => {
+                    check(client.get_account_snapshot(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetBalance => {
                     check(client.get_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetBalanceWithContext => {
+                    check(client.get_balance(USDC_PUBLIC_KEY).with_context()).await;
+                }
                 SolRpcEndpoint::GetBlock => {
                     check(client.get_block(577996)).await;
                 }
+                SolRpcEndpoint::GetBlockHeight => {
+                    check(client.get_block_height()).await;
+                }
+                SolRpcEndpoint::GetBlocks => {
+                    check(client.get_blocks(577996)).await;
+                }
+                SolRpcEndpoint::GetBlocksWithLimit => {
+                    check(client.get_blocks_with_limit(577996)).await;
+                }
+                SolRpcEndpoint::GetEpochInfo => {
+                    check(client.get_epoch_info()).await;
+                }
+                SolRpcEndpoint::GetFeeForMessage => {
+                    check(client.get_fee_for_message(some_message())).await;
+                }
+                SolRpcEndpoint::GetEpochSchedule => {
+                    check(client.get_epoch_schedule()).await;
+                }
+                SolRpcEndpoint::GetClusterNodes => {
+                    check(client.get_cluster_nodes()).await;
+                }
+                SolRpcEndpoint::GetHighestSnapshotSlot => {
+                    check(client.get_highest_snapshot_slot()).await;
+                }
+                SolRpcEndpoint::GetLeaderSchedule => {
+                    check(client.get_leader_schedule(VALIDATOR_IDENTITY)).await;
+                }
+                SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                    check(client.get_minimum_balance_for_rent_exemption(165_u64)).await;
+                }
+                SolRpcEndpoint::GetRecentPerformanceSamples => {
+                    check(client.get_recent_performance_samples()).await;
+                }
                 SolRpcEndpoint::GetRecentPrioritizationFees => {
                     check(client.get_recent_prioritization_fees(&[]).unwrap()).await
                 }
@@ -887,11 +2261,51 @@ mod cycles_cost_tests {
                 SolRpcEndpoint::GetTransaction => {
                     check(client.get_transaction(some_signature())).await;
                 }
+                SolRpcEndpoint::GetSupply => {
+                    check(client.get_supply()).await;
+                }
+                SolRpcEndpoint::GetTransactionCount => {
+                    check(client.get_transaction_count()).await;
+                }
+                SolRpcEndpoint::GetSlotLeaders => {
+                    check(client.get_slot_leaders(577996)).await;
+                }
                 SolRpcEndpoint::GetTokenAccountBalance => {
                     check(client.get_token_account_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                    check(
+                        client
+                            .get_token_account_balance(USDC_PUBLIC_KEY)
+                            .with_context(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByDelegate => {
+                    check(client.get_token_accounts_by_delegate((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByOwner => {
+                    check(client.get_token_accounts_by_owner((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::HasAccountChanged => {
+                    check(client.has_account_changed(USDC_PUBLIC_KEY)).await;
+                }
+                SolRpcEndpoint::MinimumLedgerSlot => {
+                    check(client.minimum_ledger_slot()).await;
+                }
+                SolRpcEndpoint::RawHttpRequest => {
+                    check(client.raw_http_request(raw_http_request_body())).await;
+                }
                 SolRpcEndpoint::SendTransaction => {
-                    check(client.send_transaction(some_transaction())).await;
+                    check(client.try_send_transaction(some_transaction()).unwrap()).await;
                 }
             }
         }
@@ -899,6 +2313,132 @@ mod cycles_cost_tests {
         setup.drop().await;
     }
 
+    #[tokio::test]
+    async fn should_apply_pricing_policy() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let client = setup.client(MockHttpOutcalls::never()).build();
+        let cycles_cost = || async { client.get_slot().request_cost().send().await.unwrap() };
+        let expedited_cycles_cost = || async {
+            client
+                .get_slot()
+                .with_priority(RequestPriority::Expedited)
+                .request_cost()
+                .send()
+                .await
+                .unwrap()
+        };
+
+        let default_cycles_cost = cycles_cost().await;
+
+        let upgrade_with_pricing_policy = |pricing_policy: PricingPolicy| {
+            setup.upgrade_canister(InstallArgs {
+                pricing_policy: Some(pricing_policy),
+                ..Default::default()
+            })
+        };
+        upgrade_with_pricing_policy(PricingPolicy {
+            collateral_cycles_per_node: 0,
+            markup_percent: 0,
+            free_tier_max_cycles: 0,
+            expedited_markup_percent: None,
+        })
+        .await;
+        let cycles_cost_without_collateral = cycles_cost().await;
+        assert!(cycles_cost_without_collateral < default_cycles_cost);
+        assert_eq!(
+            expedited_cycles_cost().await,
+            2 * cycles_cost_without_collateral
+        );
+
+        upgrade_with_pricing_policy(PricingPolicy {
+            collateral_cycles_per_node: 0,
+            markup_percent: 100,
+            free_tier_max_cycles: 0,
+            expedited_markup_percent: Some(200),
+        })
+        .await;
+        assert_eq!(cycles_cost().await, 2 * cycles_cost_without_collateral);
+        assert_eq!(
+            expedited_cycles_cost().await,
+            4 * cycles_cost_without_collateral
+        );
+
+        upgrade_with_pricing_policy(PricingPolicy {
+            free_tier_max_cycles: u128::MAX,
+            ..PricingPolicy::default()
+        })
+        .await;
+        assert_eq!(cycles_cost().await, 0);
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_report_consumed_cycles() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let mocks = mock_with_response_slots_for_ids(
+            get_balance_request,
+            get_balance_response,
+            SLOTS,
+            0..=2,
+        );
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+            .build();
+        let request = client
+            .get_balance(USDC_PUBLIC_KEY)
+            .with_min_context_slot(100)
+            .with_commitment(CommitmentLevel::Confirmed);
+        let cycles_cost = request.clone().request_cost().send().await.unwrap();
+        assert_eq!(setup.get_cycles_report().await, None);
+
+        let result = request
+            .with_cycles(cycles_cost + 1_000_000)
+            .send()
+            .await
+            .expect_consistent();
+
+        assert_eq!(result, Ok(389_086_612_571_u64));
+        let report = setup.get_cycles_report().await.unwrap();
+        assert_eq!(report.attached, cycles_cost + 1_000_000);
+        assert_eq!(report.consumed, cycles_cost);
+        assert_eq!(report.refunded(), 1_000_000);
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_only_allow_controllers_to_get_cycles_report_of_another_principal() {
+        let setup = Setup::new().await;
+        let get_cycles_report = |caller: Principal, principal: Principal| {
+            setup.as_ref().query_call(
+                setup.sol_rpc_canister_id(),
+                caller,
+                "getCyclesReport",
+                encode_args((Some(principal),)).unwrap(),
+            )
+        };
+
+        let result = get_cycles_report(DEFAULT_CALLER_TEST_ID, setup.controller()).await;
+        assert!(result.is_err_and(|err| err
+            .to_string()
+            .contains("Only controllers can retrieve the cycles report of another principal")));
+
+        assert!(
+            get_cycles_report(DEFAULT_CALLER_TEST_ID, DEFAULT_CALLER_TEST_ID)
+                .await
+                .is_ok()
+        );
+        assert!(
+            get_cycles_report(setup.controller(), DEFAULT_CALLER_TEST_ID)
+                .await
+                .is_ok()
+        );
+
+        setup.drop().await;
+    }
+
     #[tokio::test]
     async fn should_be_zero_when_in_demo_mode() {
         async fn check<Config, Params, CandidOutput, Output>(
@@ -934,12 +2474,54 @@ mod cycles_cost_tests {
                 SolRpcEndpoint::GetAccountInfo => {
                     check(client.get_account_info(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetAccountInfoWithContext => {
+                    check(client.get_account_info(USDC_PUBLIC_KEY).with_context()).await;
+                }
+                SolRpcEndpoint::GetAccountSnapshot => {
+                    check(client.get_account_snapshot(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetBalance => {
                     check(client.get_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetBalanceWithContext => {
+                    check(client.get_balance(USDC_PUBLIC_KEY).with_context()).await;
+                }
                 SolRpcEndpoint::GetBlock => {
                     check(client.get_block(577996)).await;
                 }
+                SolRpcEndpoint::GetBlockHeight => {
+                    check(client.get_block_height()).await;
+                }
+                SolRpcEndpoint::GetBlocks => {
+                    check(client.get_blocks(577996)).await;
+                }
+                SolRpcEndpoint::GetBlocksWithLimit => {
+                    check(client.get_blocks_with_limit(577996)).await;
+                }
+                SolRpcEndpoint::GetEpochInfo => {
+                    check(client.get_epoch_info()).await;
+                }
+                SolRpcEndpoint::GetFeeForMessage => {
+                    check(client.get_fee_for_message(some_message())).await;
+                }
+                SolRpcEndpoint::GetEpochSchedule => {
+                    check(client.get_epoch_schedule()).await;
+                }
+                SolRpcEndpoint::GetClusterNodes => {
+                    check(client.get_cluster_nodes()).await;
+                }
+                SolRpcEndpoint::GetHighestSnapshotSlot => {
+                    check(client.get_highest_snapshot_slot()).await;
+                }
+                SolRpcEndpoint::GetLeaderSchedule => {
+                    check(client.get_leader_schedule(VALIDATOR_IDENTITY)).await;
+                }
+                SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                    check(client.get_minimum_balance_for_rent_exemption(165_u64)).await;
+                }
+                SolRpcEndpoint::GetRecentPerformanceSamples => {
+                    check(client.get_recent_performance_samples()).await;
+                }
                 SolRpcEndpoint::GetRecentPrioritizationFees => {
                     check(client.get_recent_prioritization_fees(&[]).unwrap()).await;
                 }
@@ -949,17 +2531,57 @@ mod cycles_cost_tests {
                 SolRpcEndpoint::GetSignatureStatuses => {
                     check(client.get_signature_statuses(&[some_signature()]).unwrap()).await;
                 }
+                SolRpcEndpoint::GetSlotLeaders => {
+                    check(client.get_slot_leaders(577996)).await;
+                }
                 SolRpcEndpoint::GetTokenAccountBalance => {
                     check(client.get_token_account_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                    check(
+                        client
+                            .get_token_account_balance(USDC_PUBLIC_KEY)
+                            .with_context(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByDelegate => {
+                    check(client.get_token_accounts_by_delegate((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByOwner => {
+                    check(client.get_token_accounts_by_owner((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::HasAccountChanged => {
+                    check(client.has_account_changed(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetTransaction => {
                     check(client.get_transaction(some_signature())).await;
                 }
+                SolRpcEndpoint::GetSupply => {
+                    check(client.get_supply()).await;
+                }
+                SolRpcEndpoint::GetTransactionCount => {
+                    check(client.get_transaction_count()).await;
+                }
                 SolRpcEndpoint::JsonRequest => {
-                    check(client.json_request(get_version_request_body())).await;
+                    check(client.try_json_request(get_version_request_body()).unwrap()).await;
+                }
+                SolRpcEndpoint::MinimumLedgerSlot => {
+                    check(client.minimum_ledger_slot()).await;
+                }
+                SolRpcEndpoint::RawHttpRequest => {
+                    check(client.raw_http_request(raw_http_request_body())).await;
                 }
                 SolRpcEndpoint::SendTransaction => {
-                    check(client.send_transaction(some_transaction())).await;
+                    check(client.try_send_transaction(some_transaction()).unwrap()).await;
                 }
             }
         }
@@ -981,10 +2603,10 @@ mod cycles_cost_tests {
             expected_cycles_cost: u128,
         ) where
             Runtime: ic_canister_runtime::Runtime,
-            Config: CandidType + Clone + Send,
-            Params: CandidType + Clone + Send,
+            Config: CandidType + Clone + Send + 'static,
+            Params: CandidType + Clone + Send + 'static,
             CandidOutput: CandidType + DeserializeOwned,
-            Output: Debug,
+            Output: Debug + 'static,
             MultiRpcResult<CandidOutput>: Into<MultiRpcResult<Output>>,
             RequestBuilder<
                 Runtime,
@@ -1065,14 +2687,24 @@ mod cycles_cost_tests {
         let mut ids = 0_u64..;
         for endpoint in SolRpcEndpoint::iter() {
             match endpoint {
-                SolRpcEndpoint::JsonRequest => mocks = add_mocks_for("getVersion", mocks, &mut ids),
                 // Mock once for each value of `TransactionDetails`
                 SolRpcEndpoint::GetBlock => {
                     for _ in 0..3 {
                         mocks = add_mocks_for(endpoint.rpc_method(), mocks, &mut ids)
                     }
                 }
-                _ => mocks = add_mocks_for(endpoint.rpc_method(), mocks, &mut ids),
+                // No HTTPS outcalls are made, since the cycles cost of `getAccountSnapshot` is
+                // compared with the cycles costs of its underlying requests
+                SolRpcEndpoint::GetAccountSnapshot => {}
+                // The JSON-RPC request ID of a raw HTTP request is not set by the SOL RPC canister
+                SolRpcEndpoint::RawHttpRequest => {
+                    for _ in 0..5 {
+                        mocks = mocks
+                            .given(raw_http_request())
+                            .respond_with(CanisterHttpReply::with_status(403));
+                    }
+                }
+                _ => mocks = add_mocks_for(solana_rpc_method(&endpoint), mocks, &mut ids),
             };
         }
         let client = setup.client(mocks).build();
@@ -1089,15 +2721,64 @@ mod cycles_cost_tests {
                     )
                     .await;
                 }
+                SolRpcEndpoint::GetAccountInfoWithContext => {
+                    check(
+                        &setup,
+                        client.get_account_info(USDC_PUBLIC_KEY).with_context(),
+                        1_752_822_400,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetAccountSnapshot => {
+                    // The underlying requests are checked individually, and the first failing
+                    // request of a provider determines its error, so that a `TooFewCycles` error
+                    // for the last request is not observable.
+                    let cycles_cost = client
+                        .get_account_snapshot(USDC_PUBLIC_KEY)
+                        .request_cost()
+                        .send()
+                        .await
+                        .unwrap();
+                    let balance_cycles_cost = client
+                        .get_balance(USDC_PUBLIC_KEY)
+                        .request_cost()
+                        .send()
+                        .await
+                        .unwrap();
+                    let account_info_cycles_cost = client
+                        .get_account_info(USDC_PUBLIC_KEY)
+                        .request_cost()
+                        .send()
+                        .await
+                        .unwrap();
+                    let signatures_cycles_cost = client
+                        .get_signatures_for_address(USDC_PUBLIC_KEY)
+                        .request_cost()
+                        .send()
+                        .await
+                        .unwrap();
+                    assert_eq!(
+                        cycles_cost,
+                        balance_cycles_cost + account_info_cycles_cost + signatures_cycles_cost
+                    );
+                }
                 SolRpcEndpoint::GetBalance => {
                     check(&setup, client.get_balance(USDC_PUBLIC_KEY), 1_731_769_600).await;
                 }
+                SolRpcEndpoint::GetBalanceWithContext => {
+                    check(
+                        &setup,
+                        client.get_balance(USDC_PUBLIC_KEY).with_context(),
+                        1_731_769_600,
+                    )
+                    .await;
+                }
                 SolRpcEndpoint::GetBlock => {
                     for transaction_details in TransactionDetails::iter() {
                         let expected_cycles_cost = match transaction_details {
-                            TransactionDetails::Accounts => 164_743_232_800,
+                            TransactionDetails::Accounts => 42_515_572_000,
                             TransactionDetails::None => 1_772_855_200,
-                            TransactionDetails::Signatures => 23_122_271_200,
+                            TransactionDetails::Signatures => 7_063_391_200,
                         };
                         check(
                             &setup,
@@ -1109,38 +2790,134 @@ mod cycles_cost_tests {
                         .await
                     }
                 }
-                SolRpcEndpoint::GetRecentPrioritizationFees => {
+                SolRpcEndpoint::GetBlockHeight => {
+                    check(&setup, client.get_block_height(), 1_714_388_800).await;
+                }
+                SolRpcEndpoint::GetBlocks => {
+                    check(&setup, client.get_blocks(577996), 3_423_133_600).await;
+                }
+                SolRpcEndpoint::GetBlocksWithLimit => {
+                    check(&setup, client.get_blocks_with_limit(577996), 3_423_419_200).await;
+                }
+                SolRpcEndpoint::GetEpochInfo => {
+                    check(&setup, client.get_epoch_info(), 1_729_974_400).await;
+                }
+                SolRpcEndpoint::GetFeeForMessage => {
+                    check(
+                        &setup,
+                        client.get_fee_for_message(some_message()),
+                        1_733_972_800,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetEpochSchedule => {
+                    check(&setup, client.get_epoch_schedule(), 1_729_974_400).await;
+                }
+                SolRpcEndpoint::GetClusterNodes => {
+                    check(&setup, client.get_cluster_nodes(), 44_490_944_800).await;
+                }
+                SolRpcEndpoint::GetHighestSnapshotSlot => {
+                    check(&setup, client.get_highest_snapshot_slot(), 1_719_774_400).await;
+                }
+                SolRpcEndpoint::GetLeaderSchedule => {
+                    check(
+                        &setup,
+                        client.get_leader_schedule(VALIDATOR_IDENTITY),
+                        4_385_605_600,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                    check(
+                        &setup,
+                        client.get_minimum_balance_for_rent_exemption(165_u64),
+                        1_720_508_800,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetRecentPerformanceSamples => {
+                    check(
+                        &setup,
+                        client.get_recent_performance_samples(),
+                        2_336_303_200,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetRecentPrioritizationFees => {
+                    check(
+                        &setup,
+                        client.get_recent_prioritization_fees(&[]).unwrap(),
+                        2_378_204_800,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetSignaturesForAddress => {
+                    check(
+                        &setup,
+                        client.get_signatures_for_address(USDC_PUBLIC_KEY),
+                        6_933_810_400,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetSignatureStatuses => {
+                    check(
+                        &setup,
+                        client.get_signature_statuses(&[some_signature()]).unwrap(),
+                        1_744_458_400,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetSlot => {
+                    check(&setup, client.get_slot(), 1_714_103_200).await;
+                }
+                SolRpcEndpoint::GetSlotLeaders => {
+                    check(&setup, client.get_slot_leaders(577996), 2_092_931_200).await;
+                }
+                SolRpcEndpoint::GetTokenAccountBalance => {
+                    check(
+                        &setup,
+                        client.get_token_account_balance(USDC_PUBLIC_KEY),
+                        1_732_259_200,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
                     check(
                         &setup,
-                        client.get_recent_prioritization_fees(&[]).unwrap(),
-                        2_378_204_800,
+                        client
+                            .get_token_account_balance(USDC_PUBLIC_KEY)
+                            .with_context(),
+                        1_732_259_200,
                     )
                     .await;
                 }
-                SolRpcEndpoint::GetSignaturesForAddress => {
+                SolRpcEndpoint::GetTokenAccountsByDelegate => {
                     check(
                         &setup,
-                        client.get_signatures_for_address(USDC_PUBLIC_KEY),
-                        22_601_010_400,
+                        client.get_token_accounts_by_delegate((
+                            USDC_PUBLIC_KEY,
+                            TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                        )),
+                        2_382_284_800,
                     )
                     .await;
                 }
-                SolRpcEndpoint::GetSignatureStatuses => {
+                SolRpcEndpoint::GetTokenAccountsByOwner => {
                     check(
                         &setup,
-                        client.get_signature_statuses(&[some_signature()]).unwrap(),
-                        1_744_458_400,
+                        client.get_token_accounts_by_owner((
+                            USDC_PUBLIC_KEY,
+                            TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                        )),
+                        3_050_629_600,
                     )
                     .await;
                 }
-                SolRpcEndpoint::GetSlot => {
-                    check(&setup, client.get_slot(), 1_714_103_200).await;
-                }
-                SolRpcEndpoint::GetTokenAccountBalance => {
+                SolRpcEndpoint::HasAccountChanged => {
                     check(
                         &setup,
-                        client.get_token_account_balance(USDC_PUBLIC_KEY),
-                        1_732_259_200,
+                        client.has_account_changed(USDC_PUBLIC_KEY),
+                        1_753_556_800,
                     )
                     .await;
                 }
@@ -1152,10 +2929,27 @@ mod cycles_cost_tests {
                     )
                     .await;
                 }
+                SolRpcEndpoint::GetSupply => {
+                    check(&setup, client.get_supply(), 4_382_831_200).await;
+                }
+                SolRpcEndpoint::GetTransactionCount => {
+                    check(&setup, client.get_transaction_count(), 1_714_592_800).await;
+                }
                 SolRpcEndpoint::JsonRequest => {
                     check(
                         &setup,
-                        client.json_request(get_version_request_body()),
+                        client.try_json_request(get_version_request_body()).unwrap(),
+                        1_791_582_400,
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::MinimumLedgerSlot => {
+                    check(&setup, client.minimum_ledger_slot(), 1_714_348_000).await;
+                }
+                SolRpcEndpoint::RawHttpRequest => {
+                    check(
+                        &setup,
+                        client.raw_http_request(raw_http_request_body()),
                         1_791_582_400,
                     )
                     .await;
@@ -1163,7 +2957,7 @@ mod cycles_cost_tests {
                 SolRpcEndpoint::SendTransaction => {
                     check(
                         &setup,
-                        client.send_transaction(some_transaction()),
+                        client.try_send_transaction(some_transaction()).unwrap(),
                         1_799_416_000,
                     )
                     .await
@@ -1190,10 +2984,10 @@ mod rpc_config_tests {
                 MultiRpcResult<Output>,
             >,
         ) where
-            Config: CandidType + Clone + Send + SolRpcConfig + Default,
-            Params: CandidType + Clone + Send,
+            Config: CandidType + Clone + Send + SolRpcConfig + Default + 'static,
+            Params: CandidType + Clone + Send + 'static,
             CandidOutput: CandidType + DeserializeOwned,
-            Output: Debug + PartialEq,
+            Output: Debug + PartialEq + 'static,
             MultiRpcResult<CandidOutput>: Into<MultiRpcResult<Output>>,
             RequestBuilder<
                 CyclesWalletRuntime<PocketIcRuntime<'a>>,
@@ -1227,10 +3021,48 @@ mod rpc_config_tests {
                 SolRpcEndpoint::GetAccountInfo => {
                     check(client.get_account_info(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetAccountInfoWithContext => {
+                    check(client.get_account_info(USDC_PUBLIC_KEY).with_context()).await;
+                }
+                SolRpcEndpoint::GetAccountSnapshot => {
+                    check(client.get_account_snapshot(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetBalance => {
                     check(client.get_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetBalanceWithContext => {
+                    check(client.get_balance(USDC_PUBLIC_KEY).with_context()).await;
+                }
                 SolRpcEndpoint::GetBlock => check(client.get_block(577996)).await,
+                SolRpcEndpoint::GetBlockHeight => check(client.get_block_height()).await,
+                SolRpcEndpoint::GetBlocks => check(client.get_blocks(577996)).await,
+                SolRpcEndpoint::GetBlocksWithLimit => {
+                    check(client.get_blocks_with_limit(577996)).await
+                }
+                SolRpcEndpoint::GetEpochInfo => {
+                    check(client.get_epoch_info()).await;
+                }
+                SolRpcEndpoint::GetFeeForMessage => {
+                    check(client.get_fee_for_message(some_message())).await;
+                }
+                SolRpcEndpoint::GetEpochSchedule => {
+                    check(client.get_epoch_schedule()).await;
+                }
+                SolRpcEndpoint::GetClusterNodes => {
+                    check(client.get_cluster_nodes()).await;
+                }
+                SolRpcEndpoint::GetHighestSnapshotSlot => {
+                    check(client.get_highest_snapshot_slot()).await;
+                }
+                SolRpcEndpoint::GetLeaderSchedule => {
+                    check(client.get_leader_schedule(VALIDATOR_IDENTITY)).await;
+                }
+                SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                    check(client.get_minimum_balance_for_rent_exemption(165_u64)).await;
+                }
+                SolRpcEndpoint::GetRecentPerformanceSamples => {
+                    check(client.get_recent_performance_samples()).await;
+                }
                 SolRpcEndpoint::GetRecentPrioritizationFees => {
                     check(client.get_recent_prioritization_fees(&[]).unwrap()).await;
                 }
@@ -1243,17 +3075,57 @@ mod rpc_config_tests {
                 SolRpcEndpoint::GetSlot => {
                     check(client.get_slot()).await;
                 }
+                SolRpcEndpoint::GetSlotLeaders => {
+                    check(client.get_slot_leaders(577996)).await;
+                }
                 SolRpcEndpoint::GetTokenAccountBalance => {
                     check(client.get_token_account_balance(USDC_PUBLIC_KEY)).await;
                 }
+                SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                    check(
+                        client
+                            .get_token_account_balance(USDC_PUBLIC_KEY)
+                            .with_context(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByDelegate => {
+                    check(client.get_token_accounts_by_delegate((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByOwner => {
+                    check(client.get_token_accounts_by_owner((
+                        USDC_PUBLIC_KEY,
+                        TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                    )))
+                    .await;
+                }
+                SolRpcEndpoint::HasAccountChanged => {
+                    check(client.has_account_changed(USDC_PUBLIC_KEY)).await;
+                }
                 SolRpcEndpoint::GetTransaction => {
                     check(client.get_transaction(some_signature())).await;
                 }
+                SolRpcEndpoint::GetSupply => {
+                    check(client.get_supply()).await;
+                }
+                SolRpcEndpoint::GetTransactionCount => {
+                    check(client.get_transaction_count()).await;
+                }
                 SolRpcEndpoint::JsonRequest => {
-                    check(client.json_request(get_version_request_body())).await;
+                    check(client.try_json_request(get_version_request_body()).unwrap()).await;
+                }
+                SolRpcEndpoint::MinimumLedgerSlot => {
+                    check(client.minimum_ledger_slot()).await;
+                }
+                SolRpcEndpoint::RawHttpRequest => {
+                    check(client.raw_http_request(raw_http_request_body())).await;
                 }
                 SolRpcEndpoint::SendTransaction => {
-                    check(client.send_transaction(some_transaction())).await
+                    check(client.try_send_transaction(some_transaction()).unwrap()).await
                 }
             }
         }
@@ -1279,10 +3151,10 @@ mod rpc_config_tests {
                 MultiRpcResult<CandidOutput>,
                 MultiRpcResult<Output>,
             >,
-            Config: CandidType + Clone + Send + SolRpcConfig + Default,
-            Params: CandidType + Clone + Send,
+            Config: CandidType + Clone + Send + SolRpcConfig + Default + 'static,
+            Params: CandidType + Clone + Send + 'static,
             CandidOutput: CandidType + DeserializeOwned,
-            Output: Debug + PartialEq,
+            Output: Debug + PartialEq + 'static,
             MultiRpcResult<CandidOutput>: Into<MultiRpcResult<Output>>,
             RequestBuilder<
                 CyclesWalletRuntime<PocketIcRuntime<'a>>,
@@ -1342,6 +3214,20 @@ mod rpc_config_tests {
                     )
                     .await;
                 }
+                SolRpcEndpoint::GetAccountInfoWithContext => {
+                    check(
+                        &setup,
+                        |client| client.get_account_info(USDC_PUBLIC_KEY).with_context(),
+                        &mut offset,
+                        get_account_info_request(),
+                        get_account_info_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetAccountSnapshot => {
+                    // The results of the underlying requests are combined before being reduced,
+                    // see `get_account_snapshot_tests`.
+                }
                 SolRpcEndpoint::GetBalance => {
                     check(
                         &setup,
@@ -1357,6 +3243,22 @@ mod rpc_config_tests {
                     )
                     .await;
                 }
+                SolRpcEndpoint::GetBalanceWithContext => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_balance(USDC_PUBLIC_KEY)
+                                .with_min_context_slot(100)
+                                .with_commitment(CommitmentLevel::Confirmed)
+                                .with_context()
+                        },
+                        &mut offset,
+                        get_balance_request(),
+                        get_balance_response(SLOT),
+                    )
+                    .await;
+                }
                 SolRpcEndpoint::GetBlock => {
                     check(
                         &setup,
@@ -1367,6 +3269,136 @@ mod rpc_config_tests {
                     )
                     .await
                 }
+                SolRpcEndpoint::GetBlockHeight => {
+                    check(
+                        &setup,
+                        |client| client.get_block_height(),
+                        &mut offset,
+                        get_block_height_request(),
+                        get_block_height_response(1234),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetBlocks => {
+                    check(
+                        &setup,
+                        |client| client.get_blocks(577996).with_end_slot(578000),
+                        &mut offset,
+                        get_blocks_request(),
+                        get_blocks_response(),
+                    )
+                    .await
+                }
+                SolRpcEndpoint::GetBlocksWithLimit => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_blocks_with_limit(577996)
+                                .with_limit(GetBlocksLimit::try_from(5).unwrap())
+                        },
+                        &mut offset,
+                        get_blocks_with_limit_request(),
+                        get_blocks_response(),
+                    )
+                    .await
+                }
+                SolRpcEndpoint::GetEpochInfo => {
+                    check(
+                        &setup,
+                        |client| client.get_epoch_info(),
+                        &mut offset,
+                        get_epoch_info_request(),
+                        get_epoch_info_response(1234),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetFeeForMessage => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_fee_for_message(some_message())
+                                .with_commitment(CommitmentLevel::Confirmed)
+                        },
+                        &mut offset,
+                        get_fee_for_message_request(),
+                        get_fee_for_message_response(SLOT),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetEpochSchedule => {
+                    check(
+                        &setup,
+                        |client| client.get_epoch_schedule(),
+                        &mut offset,
+                        get_epoch_schedule_request(),
+                        get_epoch_schedule_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetClusterNodes => {
+                    check(
+                        &setup,
+                        |client| client.get_cluster_nodes(),
+                        &mut offset,
+                        get_cluster_nodes_request(),
+                        get_cluster_nodes_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetHighestSnapshotSlot => {
+                    check(
+                        &setup,
+                        |client| client.get_highest_snapshot_slot(),
+                        &mut offset,
+                        get_highest_snapshot_slot_request(),
+                        get_highest_snapshot_slot_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetLeaderSchedule => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_leader_schedule(VALIDATOR_IDENTITY)
+                                .with_commitment(CommitmentLevel::Finalized)
+                        },
+                        &mut offset,
+                        get_leader_schedule_request(),
+                        get_leader_schedule_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_minimum_balance_for_rent_exemption(165_u64)
+                                .with_commitment(CommitmentLevel::Confirmed)
+                        },
+                        &mut offset,
+                        get_minimum_balance_for_rent_exemption_request(),
+                        get_minimum_balance_for_rent_exemption_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetRecentPerformanceSamples => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_recent_performance_samples()
+                                .with_limit(2.try_into().unwrap())
+                        },
+                        &mut offset,
+                        get_recent_performance_samples_request(),
+                        get_recent_performance_samples_response(),
+                    )
+                    .await;
+                }
                 SolRpcEndpoint::GetRecentPrioritizationFees => {
                     check(
                         &setup,
@@ -1422,6 +3454,20 @@ mod rpc_config_tests {
                     )
                     .await;
                 }
+                SolRpcEndpoint::GetSlotLeaders => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_slot_leaders(577996)
+                                .with_limit(GetSlotLeadersLimit::try_from(2).unwrap())
+                        },
+                        &mut offset,
+                        get_slot_leaders_request(),
+                        get_slot_leaders_response(),
+                    )
+                    .await;
+                }
                 SolRpcEndpoint::GetTokenAccountBalance => {
                     check(
                         &setup,
@@ -1436,6 +3482,65 @@ mod rpc_config_tests {
                     )
                     .await;
                 }
+                SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_token_account_balance(USDC_PUBLIC_KEY)
+                                .with_commitment(CommitmentLevel::Confirmed)
+                                .with_context()
+                        },
+                        &mut offset,
+                        get_token_account_balance_request(),
+                        get_token_account_balance_response(SLOT),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByDelegate => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_token_accounts_by_delegate((
+                                    DELEGATE_PUBLIC_KEY,
+                                    TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                                ))
+                                .with_commitment(CommitmentLevel::Confirmed)
+                        },
+                        &mut offset,
+                        get_token_accounts_by_delegate_request(),
+                        get_token_accounts_by_delegate_response(SLOT),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTokenAccountsByOwner => {
+                    check(
+                        &setup,
+                        |client| {
+                            client
+                                .get_token_accounts_by_owner((
+                                    DELEGATE_PUBLIC_KEY,
+                                    TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                                ))
+                                .with_commitment(CommitmentLevel::Confirmed)
+                        },
+                        &mut offset,
+                        get_token_accounts_by_owner_request(),
+                        get_token_accounts_by_owner_response(SLOT),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::HasAccountChanged => {
+                    check(
+                        &setup,
+                        |client| client.has_account_changed(USDC_PUBLIC_KEY),
+                        &mut offset,
+                        has_account_changed_request(),
+                        get_account_info_response(),
+                    )
+                    .await;
+                }
                 SolRpcEndpoint::GetTransaction => {
                     check(
                         &setup,
@@ -1450,21 +3555,55 @@ mod rpc_config_tests {
                     )
                     .await;
                 }
+                SolRpcEndpoint::GetSupply => {
+                    check(
+                        &setup,
+                        |client| client.get_supply(),
+                        &mut offset,
+                        get_supply_request(),
+                        get_supply_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::GetTransactionCount => {
+                    check(
+                        &setup,
+                        |client| client.get_transaction_count(),
+                        &mut offset,
+                        get_transaction_count_request(),
+                        get_transaction_count_response(268_974_512_345),
+                    )
+                    .await;
+                }
                 SolRpcEndpoint::JsonRequest => {
                     check(
                         &setup,
-                        |client| client.json_request(get_version_request_body()),
+                        |client| client.try_json_request(get_version_request_body()).unwrap(),
                         &mut offset,
                         get_version_request(),
                         get_version_response(),
                     )
                     .await;
                 }
+                SolRpcEndpoint::MinimumLedgerSlot => {
+                    check(
+                        &setup,
+                        |client| client.minimum_ledger_slot(),
+                        &mut offset,
+                        minimum_ledger_slot_request(),
+                        minimum_ledger_slot_response(),
+                    )
+                    .await;
+                }
+                SolRpcEndpoint::RawHttpRequest => {
+                    // The JSON-RPC request ID of a raw HTTP request is not set by the SOL RPC
+                    // canister, see `raw_http_request_tests`.
+                }
                 SolRpcEndpoint::SendTransaction => {
                     let transaction = some_transaction();
                     check(
                         &setup,
-                        |client| client.send_transaction(transaction.clone()),
+                        |client| client.try_send_transaction(transaction.clone()).unwrap(),
                         &mut offset,
                         send_transaction_request(&transaction),
                         send_transaction_response(),
@@ -1502,44 +3641,101 @@ mod get_balance_tests {
                 .await
                 .expect_consistent();
 
-            assert_eq!(results, Ok(389_086_612_571_u64));
+            assert_eq!(results, Ok(389_086_612_571_u64));
+        }
+
+        setup.drop().await;
+    }
+}
+
+mod get_token_account_balance_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_token_account_balance() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
+            let mocks = mock_with_response_slots_for_ids(
+                get_token_account_balance_request,
+                get_token_account_balance_response,
+                SLOTS,
+                offset..=offset + 2,
+            );
+            let client = setup.client(mocks).with_rpc_sources(sources).build();
+
+            let results = client
+                .get_token_account_balance(USDC_PUBLIC_KEY)
+                .with_commitment(CommitmentLevel::Confirmed)
+                .send()
+                .await
+                .expect_consistent();
+
+            assert_eq!(
+                results,
+                Ok(UiTokenAmount {
+                    amount: "9864".to_string(),
+                    decimals: 2,
+                    ui_amount: Some(98.64),
+                    ui_amount_string: "98.64".to_string(),
+                })
+            );
         }
 
         setup.drop().await;
     }
 }
 
-mod get_token_account_balance_tests {
+mod get_token_accounts_by_delegate_tests {
     use super::*;
+    use sol_rpc_types::{AccountData, AccountEncoding, AccountInfo, KeyedAccount};
 
     #[tokio::test]
-    async fn should_get_token_account_balance() {
+    async fn should_get_token_accounts_by_delegate() {
         let setup = Setup::new().await.with_mock_api_keys().await;
 
         for (sources, offset) in zip(rpc_sources(), (0..).step_by(3)) {
             let mocks = mock_with_response_slots_for_ids(
-                get_token_account_balance_request,
-                get_token_account_balance_response,
+                get_token_accounts_by_delegate_request,
+                get_token_accounts_by_delegate_response,
                 SLOTS,
                 offset..=offset + 2,
             );
             let client = setup.client(mocks).with_rpc_sources(sources).build();
 
             let results = client
-                .get_token_account_balance(USDC_PUBLIC_KEY)
+                .get_token_accounts_by_delegate((
+                    DELEGATE_PUBLIC_KEY,
+                    TokenAccountsFilter::Mint(USDC_PUBLIC_KEY.into()),
+                ))
                 .with_commitment(CommitmentLevel::Confirmed)
                 .send()
                 .await
                 .expect_consistent();
 
+            let token_account = |pubkey: solana_pubkey::Pubkey, data: &str| KeyedAccount {
+                pubkey: pubkey.into(),
+                account: AccountInfo {
+                    lamports: 2_039_280,
+                    data: AccountData::Binary(data.to_string(), AccountEncoding::Base64),
+                    owner: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+                    executable: false,
+                    rent_epoch: 18_446_744_073_709_551_615,
+                    space: 165,
+                },
+            };
             assert_eq!(
                 results,
-                Ok(UiTokenAmount {
-                    amount: "9864".to_string(),
-                    decimals: 2,
-                    ui_amount: Some(98.64),
-                    ui_amount_string: "98.64".to_string(),
-                })
+                Ok(vec![
+                    token_account(
+                        pubkey!("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp"),
+                        "AQID"
+                    ),
+                    token_account(
+                        pubkey!("9PwCPoWJ75LSgZeGMubXBdufYMVd66HrcF78QzW6ZHkV"),
+                        "BAUG"
+                    ),
+                ])
             );
         }
 
@@ -1662,6 +3858,344 @@ mod get_signatures_for_address_tests {
     }
 }
 
+mod get_cluster_health_tests {
+    use super::*;
+    use sol_rpc_types::{JsonRpcError, ProviderHealth, ProviderHealthReport, SolanaVersion};
+
+    #[tokio::test]
+    async fn should_get_cluster_health() {
+        fn get_health_request() -> JsonRpcRequestMatcher {
+            JsonRpcRequestMatcher::with_method("getHealth").with_params(json!([]))
+        }
+
+        fn get_version_request() -> JsonRpcRequestMatcher {
+            JsonRpcRequestMatcher::with_method("getVersion").with_params(json!([]))
+        }
+
+        fn json_rpc_error(id: u8, code: i64, message: &str) -> JsonRpcResponse {
+            JsonRpcResponse::from(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": code, "message": message },
+                "id": Id::from(ConstantSizeId::from(id)),
+            }))
+        }
+
+        let setup = Setup::new().await.with_mock_api_keys().await;
+
+        let mut mocks = MockHttpOutcallsBuilder::new()
+            .given(get_health_request().with_id(0))
+            .respond_with(JsonRpcResponse::from(json!({
+                "jsonrpc": "2.0",
+                "result": "ok",
+                "id": Id::from(ConstantSizeId::from(0_u8)),
+            })))
+            .given(get_health_request().with_id(1))
+            .respond_with(json_rpc_error(1, -32005, "Node is behind by 42 slots"))
+            .given(get_health_request().with_id(2))
+            .respond_with(json_rpc_error(2, -32603, "Internal error"));
+        for id in 3_u8..=5 {
+            mocks =
+                mocks
+                    .given(get_version_request().with_id(id))
+                    .respond_with(JsonRpcResponse::from(json!({
+                        "jsonrpc": "2.0",
+                        "result": { "feature-set": 3294202862_u32, "solana-core": "2.2.14" },
+                        "id": Id::from(ConstantSizeId::from(id)),
+                    })));
+        }
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet),
+                RpcSource::Supported(SupportedRpcProviderId::PublicNodeMainnet),
+            ]))
+            .build();
+
+        let reports = client.get_cluster_health().await.unwrap();
+
+        let version = Ok(SolanaVersion {
+            solana_core: "2.2.14".to_string(),
+            feature_set: Some(3_294_202_862),
+        });
+        assert_eq!(
+            reports,
+            Ok(vec![
+                ProviderHealthReport {
+                    source: RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                    health: ProviderHealth::Ok,
+                    version: version.clone(),
+                    cluster_outage: None,
+                    slot_skew: None,
+                    sampled_version: None,
+                },
+                ProviderHealthReport {
+                    source: RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet),
+                    health: ProviderHealth::Behind {
+                        num_slots_behind: Some(42)
+                    },
+                    version: version.clone(),
+                    cluster_outage: None,
+                    slot_skew: None,
+                    sampled_version: None,
+                },
+                ProviderHealthReport {
+                    source: RpcSource::Supported(SupportedRpcProviderId::PublicNodeMainnet),
+                    health: ProviderHealth::Error(RpcError::JsonRpcError(JsonRpcError {
+                        code: -32603,
+                        message: "Internal error".to_string(),
+                        data: None,
+                    })),
+                    version,
+                    cluster_outage: None,
+                    slot_skew: None,
+                    sampled_version: None,
+                },
+            ])
+        );
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_report_slot_skew() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let sources = RpcSources::Custom(vec![
+            RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+            RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet),
+        ]);
+
+        // Slots are rounded down to a multiple of 20 with the default rounding error.
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(get_slot_request().with_id(0))
+            .respond_with(get_slot_response(1_450_305).with_id(0))
+            .given(get_slot_request().with_id(1))
+            .respond_with(get_slot_response(1_450_345).with_id(1));
+        let result = setup
+            .client(mocks)
+            .with_rpc_sources(sources.clone())
+            .build()
+            .get_slot()
+            .send()
+            .await;
+        assert_matches!(result, MultiRpcResult::Inconsistent(_));
+
+        let mut mocks = MockHttpOutcallsBuilder::new();
+        for id in 2_u8..=3 {
+            mocks = mocks
+                .given(JsonRpcRequestMatcher::with_method("getHealth").with_id(id))
+                .respond_with(JsonRpcResponse::from(json!({
+                    "jsonrpc": "2.0",
+                    "result": "ok",
+                    "id": Id::from(ConstantSizeId::from(id)),
+                })));
+        }
+        for id in 4_u8..=5 {
+            mocks = mocks
+                .given(JsonRpcRequestMatcher::with_method("getVersion").with_id(id))
+                .respond_with(JsonRpcResponse::from(json!({
+                    "jsonrpc": "2.0",
+                    "result": { "feature-set": 3294202862_u32, "solana-core": "2.2.14" },
+                    "id": Id::from(ConstantSizeId::from(id)),
+                })));
+        }
+        let reports = setup
+            .client(mocks)
+            .with_rpc_sources(sources)
+            .build()
+            .get_cluster_health()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| report.slot_skew)
+                .collect::<Vec<_>>(),
+            vec![Some(40), Some(40)]
+        );
+        setup
+            .check_metrics()
+            .await
+            .assert_contains_metric_matching(r#"solrpc_slot_skew_sum\{cluster="mainnet"\} 40 \d+"#);
+    }
+}
+
+mod preview_request_tests {
+    use super::*;
+    use sol_rpc_test_utils::MOCK_API_KEY;
+
+    #[tokio::test]
+    async fn should_preview_request_with_redacted_api_keys() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let client = setup
+            .client(MockHttpOutcalls::never())
+            .with_rpc_sources(RpcSources::Custom(vec![
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet),
+                RpcSource::Supported(SupportedRpcProviderId::PublicNodeMainnet),
+            ]))
+            .build();
+
+        let previews = client
+            .preview_request("getSlot", json!([{"commitment": "finalized"}]))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            previews
+                .iter()
+                .map(|preview| (preview.source.clone(), preview.url.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                    "https://solana-mainnet.g.alchemy.com/v2"
+                ),
+                (
+                    RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet),
+                    "https://rpc.ankr.com/solana/{API_KEY}"
+                ),
+                (
+                    RpcSource::Supported(SupportedRpcProviderId::PublicNodeMainnet),
+                    "https://solana-rpc.publicnode.com"
+                ),
+            ]
+        );
+        assert!(previews[0]
+            .headers
+            .iter()
+            .any(|header| header.name == "Authorization" && header.value == "Bearer {API_KEY}"));
+        for preview in &previews {
+            assert!(!preview.url.contains(MOCK_API_KEY));
+            assert!(preview
+                .headers
+                .iter()
+                .all(|header| !header.value.contains(MOCK_API_KEY)));
+            let body: Value = serde_json::from_str(&preview.body).unwrap();
+            assert_eq!(body["method"], "getSlot");
+            assert_eq!(body["params"], json!([{"commitment": "finalized"}]));
+            assert_eq!(preview.max_response_bytes, 1024 + 2 * 1024);
+        }
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_prevent_unauthorized_call_to_preview_request() {
+        let setup = Setup::new().await;
+        let args = (
+            "getSlot".to_string(),
+            RpcSources::Default(SolanaCluster::Mainnet),
+            None::<sol_rpc_types::RpcConfig>,
+            "[]".to_string(),
+        );
+
+        let result = setup
+            .as_ref()
+            .query_call(
+                setup.sol_rpc_canister_id(),
+                DEFAULT_CALLER_TEST_ID,
+                "previewRequest",
+                encode_args(args).unwrap(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(RejectResponse {
+                reject_code: pocket_ic::RejectCode::CanisterReject,
+                reject_message: "You are not authorized".to_string(),
+                error_code: ErrorCode::CanisterRejectedMessage,
+                certified: false,
+            })
+        );
+
+        setup.drop().await;
+    }
+}
+
+mod raw_http_request_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_return_response_without_json_rpc_validation() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let mut mocks = MockHttpOutcallsBuilder::new();
+        for _ in 0..3 {
+            mocks = mocks
+                .given(raw_http_request())
+                .respond_with(JsonRpcResponse::from(json!({
+                    "jsonrpc": "2.0",
+                    "error": { "message": "Method not found", "code": -32601 },
+                    "id": Id::from(ConstantSizeId::ZERO),
+                })));
+        }
+        let client = setup.client(mocks).build();
+
+        let response = client
+            .raw_http_request(raw_http_request_body())
+            .send()
+            .await
+            .expect_consistent()
+            .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.headers, vec![]);
+        assert_eq!(
+            serde_json::from_slice::<Value>(&response.body).unwrap(),
+            json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32601, "message": "Method not found" },
+                "id": Id::from(ConstantSizeId::ZERO),
+            })
+        );
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_non_successful_response() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let mut mocks = MockHttpOutcallsBuilder::new();
+        for _ in 0..3 {
+            mocks = mocks
+                .given(raw_http_request())
+                .respond_with(CanisterHttpReply::with_status(404));
+        }
+        let client = setup.client(mocks).build();
+
+        let response = client
+            .raw_http_request(raw_http_request_body())
+            .send()
+            .await
+            .expect_consistent()
+            .unwrap();
+
+        assert_eq!(response.status, 404);
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_request_header() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        let client = setup.client(MockHttpOutcalls::never()).build();
+
+        let result = client
+            .raw_http_request(raw_http_request_body())
+            .with_header("Invalid header", "value")
+            .send()
+            .await
+            .expect_consistent();
+
+        assert_matches!(result, Err(RpcError::ValidationError(_)));
+
+        setup.drop().await;
+    }
+}
+
 mod metrics_tests {
     use super::*;
     use ic_pocket_canister_runtime::CanisterHttpReject;
@@ -1765,6 +4299,9 @@ mod metrics_tests {
             .assert_contains_metric_matching(r#"solrpc_responses\{method="getSlot",host="solana-rpc.publicnode.com",error="ic",code="SYS_FATAL"\} .*"#)
             // `solrpc_responses` counters: insufficient cycles
             .assert_contains_metric_matching(r#"solrpc_responses\{method="getAccountInfo",host="solana-mainnet.g.alchemy.com",error="max-response-size-exceeded"\} .*"#)
+            // `solrpc_cycles_spent` counters
+            .assert_contains_metric_matching(r#"solrpc_cycles_spent\{host="solana-mainnet.g.alchemy.com"\} [1-9]\d* \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_cycles_spent\{host="rpc.ankr.com"\} [1-9]\d* \d+"#)
             // `solrpc_latencies` latency histograms
             .assert_contains_metric_matching(r#"solrpc_latencies_bucket\{method="getSlot",host="solana-mainnet.g.alchemy.com",le="\d+"\} 1 \d+"#)
             .assert_contains_metric_matching(r#"solrpc_latencies_bucket\{method="getSlot",host="rpc.ankr.com",le="\d+"\} 1 \d+"#)
@@ -1774,13 +4311,19 @@ mod metrics_tests {
             .assert_does_not_contain_metric_matching(r#"solrpc_latencies\{method="getSlot",host="solana-rpc.publicnode.com",le="\d+"\} 1 \d+"#)
             .assert_does_not_contain_metric_matching(r#"solrpc_latencies_bucket\{method="getAccountInfo",host="solana-mainnet.g.alchemy.com",le="\d+"\} 1 \d+"#)
             // `solrpc_inconsistent_responses` counters: inconsistent results
-            .assert_contains_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="solana-mainnet.g.alchemy.com"} 1 \d+"#)
-            .assert_contains_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="rpc.ankr.com"} 1 \d+"#)
-            .assert_contains_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="solana-mainnet.core.chainstack.com"} 1 \d+"#)
-            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="lb.drpc.org"} 1 \d+"#)
-            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="mainnet.helius-rpc.com"} 1 \d+"#)
-            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="solana-rpc.publicnode.com"} 1 \d+"#)
-            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getAccountInfo",host="solana-mainnet.g.alchemy.com"} 1 \d+"#);
+            .assert_contains_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="solana-mainnet.g.alchemy.com",cause="provider_error"} 1 \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="rpc.ankr.com",cause="provider_error"} 1 \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="solana-mainnet.core.chainstack.com",cause="provider_error"} 1 \d+"#)
+            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="lb.drpc.org",.*} 1 \d+"#)
+            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="mainnet.helius-rpc.com",.*} 1 \d+"#)
+            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getSlot",host="solana-rpc.publicnode.com",.*} 1 \d+"#)
+            // `solrpc_cycles_*` counters
+            .assert_contains_metric_matching(r#"solrpc_cycles_received\{method="getSlot"\} \d+ \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_cycles_charged\{method="getSlot"\} \d+ \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_cycles_received\{method="getAccountInfo"\} 1000000000000 \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_cycles_charged\{method="getAccountInfo"\} \d+ \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_cycles_refunded\{method="getAccountInfo"\} \d+ \d+"#)
+            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses\{method="getAccountInfo",host="solana-mainnet.g.alchemy.com",.*} 1 \d+"#);
     }
 
     #[tokio::test]
@@ -1807,10 +4350,141 @@ mod metrics_tests {
             .assert_does_not_contain_metric_matching(r#"solrpc_requests.*"#)
             .assert_does_not_contain_metric_matching(r#"solrpc_responses.*"#)
             .assert_does_not_contain_metric_matching(r#"solrpc_latencies_bucket.*"#)
-            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses.*"#);
+            .assert_does_not_contain_metric_matching(r#"solrpc_inconsistent_responses.*"#)
+            .assert_contains_metric_matching(
+                r#"solrpc_cycles_received\{method="getSlot"\} 550000000 \d+"#,
+            )
+            .assert_contains_metric_matching(
+                r#"solrpc_cycles_refunded\{method="getSlot"\} 550000000 \d+"#,
+            )
+            .assert_does_not_contain_metric_matching(r#"solrpc_cycles_charged.*"#)
+            .assert_does_not_contain_metric_matching(r#"solrpc_cycles_spent.*"#);
+    }
+
+    #[tokio::test]
+    async fn should_retry_according_to_retry_policy() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        setup
+            .upgrade_canister(InstallArgs {
+                retry_policies: Some(vec![(
+                    SupportedRpcProviderId::AlchemyMainnet,
+                    RetryPolicy {
+                        max_retries: 1,
+                        retry_on_transient_error: false,
+                        retry_on_http_status: Some(vec![429, 503]),
+                        initial_backoff_ms: Some(0),
+                    },
+                )]),
+                ..Default::default()
+            })
+            .await;
+
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(get_slot_request().with_id(0))
+            .respond_with(CanisterHttpReply::with_status(503))
+            .given(get_slot_request().with_id(1))
+            .respond_with(get_slot_response(1_450_305).with_id(1));
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::AlchemyMainnet,
+            )]))
+            .build();
+
+        let result = client.get_slot().send().await;
+        assert_eq!(result, MultiRpcResult::Consistent(Ok(1_450_300)));
+
+        setup
+            .check_metrics()
+            .await
+            .assert_contains_metric_matching(r#"solrpc_requests\{method="getSlot",host="solana-mainnet.g.alchemy.com"\} 2 \d+"#)
+            .assert_contains_metric_matching(r#"solrpc_retries\{method="getSlot",host="solana-mainnet.g.alchemy.com",reason="http",status="503"\} 1 \d+"#);
+
+        setup.drop().await;
+    }
+
+    #[tokio::test]
+    async fn should_reuse_request_id_on_retry() {
+        let setup = Setup::new().await.with_mock_api_keys().await;
+        setup
+            .upgrade_canister(InstallArgs {
+                retry_policies: Some(vec![(
+                    SupportedRpcProviderId::AlchemyMainnet,
+                    RetryPolicy {
+                        max_retries: 1,
+                        retry_on_transient_error: false,
+                        retry_on_http_status: Some(vec![503]),
+                        initial_backoff_ms: Some(0),
+                    },
+                )]),
+                request_id_strategy: Some(RequestIdStrategy::ReuseOnRetry),
+                ..Default::default()
+            })
+            .await;
+
+        let transaction = some_transaction();
+        let mocks = MockHttpOutcallsBuilder::new()
+            .given(send_transaction_request(&transaction).with_id(0))
+            .respond_with(CanisterHttpReply::with_status(503))
+            .given(send_transaction_request(&transaction).with_id(0))
+            .respond_with(send_transaction_response().with_id(0));
+        let client = setup
+            .client(mocks)
+            .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::AlchemyMainnet,
+            )]))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction)
+            .unwrap()
+            .send()
+            .await;
+        assert_eq!(result, MultiRpcResult::Consistent(Ok(some_signature())));
+
+        setup
+            .check_metrics()
+            .await
+            .assert_contains_metric_matching(r#"solrpc_retries\{method="sendTransaction",host="solana-mainnet.g.alchemy.com",reason="http",status="503"\} 1 \d+"#);
+
+        setup.drop().await;
     }
 }
 
+#[tokio::test]
+async fn should_send_request_id_with_configured_format() {
+    let setup = Setup::new().await.with_mock_api_keys().await;
+    setup
+        .upgrade_canister(InstallArgs {
+            request_id_formats: Some(vec![(
+                SupportedRpcProviderId::AlchemyMainnet,
+                RequestIdFormat::Number,
+            )]),
+            ..Default::default()
+        })
+        .await;
+
+    // The response is only accepted if its ID matches the numeric ID of the request.
+    let mocks = MockHttpOutcallsBuilder::new()
+        .given(JsonRpcRequestMatcher::with_method("getSlot").with_params(json!([null])))
+        .respond_with(JsonRpcResponse::from(json!({
+            "id": 0,
+            "jsonrpc": "2.0",
+            "result": 1_450_305,
+        })));
+    let client = setup
+        .client(mocks)
+        .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+            SupportedRpcProviderId::AlchemyMainnet,
+        )]))
+        .build();
+
+    let result = client.get_slot().send().await;
+    assert_eq!(result, MultiRpcResult::Consistent(Ok(1_450_300)));
+
+    setup.drop().await;
+}
+
 #[tokio::test]
 async fn should_not_drain_canister_balance_when_insufficient_cycles_attached() {
     let setup = Setup::new().await.with_mock_api_keys().await;
@@ -1882,7 +4556,7 @@ async fn should_log_request_and_response() {
         .expect_consistent();
     assert_eq!(results, Ok(1234));
 
-    let logs = setup.retrieve_logs("TRACE_HTTP").await;
+    let logs = setup.retrieve_logs::<Priority>("TRACE_HTTP").await;
     assert_eq!(logs.len(), 2, "Unexpected amount of logs: {logs:?}");
 
     assert_eq!(logs[0].message, "JSON-RPC request with id `00000000000000000000` to solana-mainnet.g.alchemy.com: JsonRpcRequest { jsonrpc: V2, method: \"getSlot\", id: String(\"00000000000000000000\"), params: Some(GetSlotParams { config: None }) }");
@@ -1988,6 +4662,14 @@ fn some_transaction() -> solana_transaction::Transaction {
     )
 }
 
+fn some_message() -> solana_message::Message {
+    solana_message::Message::new_with_blockhash(
+        &[],
+        Some(&USDC_PUBLIC_KEY),
+        &solana_hash::Hash::from_str("4Pcj2yJkCYyhnWe8Ze3uK2D2EtesBxhAevweDoTcxXf3").unwrap(),
+    )
+}
+
 fn some_signature() -> solana_signature::Signature {
     solana_signature::Signature::from_str(
         "KbYRTmvx4uz3xuRRGNdKyt1jBngz2TjLp9nPebT4h3LQzAG7BfYrd5pSU2xDT7dVg3EXXbZugH8XbKwiGU7Jqzw",
@@ -2002,6 +4684,20 @@ fn another_signature() -> solana_signature::Signature {
     .unwrap()
 }
 
+/// The Solana JSON-RPC method called by the given endpoint of the SOL RPC canister.
+fn solana_rpc_method(endpoint: &SolRpcEndpoint) -> &'static str {
+    match endpoint {
+        SolRpcEndpoint::JsonRequest => "getVersion",
+        SolRpcEndpoint::RawHttpRequest => "getHealth",
+        SolRpcEndpoint::GetAccountInfoWithContext | SolRpcEndpoint::HasAccountChanged => {
+            "getAccountInfo"
+        }
+        SolRpcEndpoint::GetBalanceWithContext => "getBalance",
+        SolRpcEndpoint::GetTokenAccountBalanceWithContext => "getTokenAccountBalance",
+        _ => endpoint.rpc_method(),
+    }
+}
+
 fn mock_all_endpoints(
     request: impl Fn(JsonRpcRequestMatcher) -> JsonRpcRequestMatcher,
     response: impl Into<CanisterHttpResponse>,
@@ -2010,17 +4706,30 @@ fn mock_all_endpoints(
     let mut ids = 0_u64..;
     let response = response.into();
     for endpoint in SolRpcEndpoint::iter() {
-        let rpc_method = if endpoint == SolRpcEndpoint::JsonRequest {
-            "getVersion"
-        } else {
-            endpoint.rpc_method()
+        if endpoint == SolRpcEndpoint::RawHttpRequest {
+            // The JSON-RPC request ID of a raw HTTP request is not set by the SOL RPC canister
+            for _ in 0..3 {
+                mocks = mocks
+                    .given(request(raw_http_request()))
+                    .respond_with(response.clone());
+            }
+            continue;
+        }
+        let rpc_methods = match endpoint {
+            // Each of the underlying requests is sent to all providers
+            SolRpcEndpoint::GetAccountSnapshot => {
+                vec!["getBalance", "getAccountInfo", "getSignaturesForAddress"]
+            }
+            _ => vec![solana_rpc_method(&endpoint)],
         };
-        for id in ids.by_ref().take(3) {
-            mocks = mocks
-                .given(request(
-                    JsonRpcRequestMatcher::with_method(rpc_method).with_id(id),
-                ))
-                .respond_with(response.clone());
+        for rpc_method in rpc_methods {
+            for id in ids.by_ref().take(3) {
+                mocks = mocks
+                    .given(request(
+                        JsonRpcRequestMatcher::with_method(rpc_method).with_id(id),
+                    ))
+                    .respond_with(response.clone());
+            }
         }
     }
     mocks
@@ -2032,6 +4741,12 @@ fn get_account_info_request() -> JsonRpcRequestMatcher {
         .with_id(0)
 }
 
+fn has_account_changed_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getAccountInfo")
+        .with_params(json!([USDC_PUBLIC_KEY.to_string(), {"encoding": "base64"}]))
+        .with_id(0)
+}
+
 fn get_balance_request() -> JsonRpcRequestMatcher {
     JsonRpcRequestMatcher::with_method("getBalance")
         .with_params(json!([USDC_PUBLIC_KEY.to_string(), {"commitment": "confirmed", "minContextSlot": 100}]))
@@ -2044,6 +4759,58 @@ fn get_block_request() -> JsonRpcRequestMatcher {
         .with_id(0)
 }
 
+fn get_block_height_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getBlockHeight")
+        .with_params(json!([null]))
+        .with_id(0)
+}
+
+fn get_blocks_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getBlocks")
+        .with_params(json!([577996, 578000, null]))
+        .with_id(0)
+}
+
+fn get_blocks_with_limit_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getBlocksWithLimit")
+        .with_params(json!([577996, 5, null]))
+        .with_id(0)
+}
+
+fn get_epoch_info_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getEpochInfo")
+        .with_params(json!([null]))
+        .with_id(0)
+}
+
+fn get_fee_for_message_request() -> JsonRpcRequestMatcher {
+    let params = GetFeeForMessageParams::try_from(some_message()).unwrap();
+    JsonRpcRequestMatcher::with_method("getFeeForMessage")
+        .with_params(json!([params.get_message(), {"commitment": "confirmed"}]))
+        .with_id(0)
+}
+
+fn get_leader_schedule_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getLeaderSchedule")
+        .with_params(json!([
+            null,
+            {"identity": VALIDATOR_IDENTITY.to_string(), "commitment": "finalized"}
+        ]))
+        .with_id(0)
+}
+
+fn get_minimum_balance_for_rent_exemption_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getMinimumBalanceForRentExemption")
+        .with_params(json!([165, {"commitment": "confirmed"}]))
+        .with_id(0)
+}
+
+fn get_recent_performance_samples_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getRecentPerformanceSamples")
+        .with_params(json!([2]))
+        .with_id(0)
+}
+
 fn get_recent_prioritization_fees_request() -> JsonRpcRequestMatcher {
     JsonRpcRequestMatcher::with_method("getRecentPrioritizationFees")
         .with_params(json!([[USDC_PUBLIC_KEY.to_string()]]))
@@ -2071,18 +4838,80 @@ fn get_slot_request() -> JsonRpcRequestMatcher {
         .with_id(0)
 }
 
+fn get_slot_leaders_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getSlotLeaders")
+        .with_params(json!([577996, 2]))
+        .with_id(0)
+}
+
 fn get_token_account_balance_request() -> JsonRpcRequestMatcher {
     JsonRpcRequestMatcher::with_method("getTokenAccountBalance")
         .with_params(json!([USDC_PUBLIC_KEY.to_string(), {"commitment": "confirmed"}]))
         .with_id(0)
 }
 
+fn get_token_accounts_by_delegate_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getTokenAccountsByDelegate")
+        .with_params(json!([
+            DELEGATE_PUBLIC_KEY.to_string(),
+            {"mint": USDC_PUBLIC_KEY.to_string()},
+            {"commitment": "confirmed"}
+        ]))
+        .with_id(0)
+}
+
+fn get_token_accounts_by_owner_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getTokenAccountsByOwner")
+        .with_params(json!([
+            DELEGATE_PUBLIC_KEY.to_string(),
+            {"mint": USDC_PUBLIC_KEY.to_string()},
+            {"commitment": "confirmed"}
+        ]))
+        .with_id(0)
+}
+
 fn get_transaction_request() -> JsonRpcRequestMatcher {
     JsonRpcRequestMatcher::with_method("getTransaction")
         .with_params(json!([some_signature().to_string(), {"encoding": "base64"}]))
         .with_id(0)
 }
 
+fn get_supply_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getSupply")
+        .with_params(json!([null]))
+        .with_id(0)
+}
+
+fn get_transaction_count_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getTransactionCount")
+        .with_params(json!([null]))
+        .with_id(0)
+}
+
+fn get_cluster_nodes_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getClusterNodes")
+        .with_params(json!([]))
+        .with_id(0)
+}
+
+fn get_epoch_schedule_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getEpochSchedule")
+        .with_params(json!([]))
+        .with_id(0)
+}
+
+fn get_highest_snapshot_slot_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getHighestSnapshotSlot")
+        .with_params(json!([]))
+        .with_id(0)
+}
+
+fn minimum_ledger_slot_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("minimumLedgerSlot")
+        .with_params(json!([]))
+        .with_id(0)
+}
+
 fn get_version_request_body() -> Value {
     json!({"jsonrpc": "2.0", "id": Id::from(ConstantSizeId::ZERO), "method": "getVersion"})
 }
@@ -2091,6 +4920,19 @@ fn get_version_request() -> JsonRpcRequestMatcher {
     JsonRpcRequestMatcher::with_method("getVersion").with_id(0)
 }
 
+/// Body of the `rawHttpRequest` calls in tests covering all endpoints. The Solana `getHealth`
+/// method is not called by any other endpoint, so that the mocked HTTPS outcalls are unambiguous.
+fn raw_http_request_body() -> Vec<u8> {
+    serde_json::to_vec(
+        &json!({"jsonrpc": "2.0", "id": Id::from(ConstantSizeId::ZERO), "method": "getHealth"}),
+    )
+    .unwrap()
+}
+
+fn raw_http_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("getHealth").with_id(0)
+}
+
 fn send_transaction_request(
     transaction: &solana_transaction::Transaction,
 ) -> JsonRpcRequestMatcher {
@@ -2105,6 +4947,26 @@ fn send_transaction_request(
         .with_id(0)
 }
 
+fn is_blockhash_valid_request() -> JsonRpcRequestMatcher {
+    JsonRpcRequestMatcher::with_method("isBlockhashValid")
+        .with_params(json!([
+            "4Pcj2yJkCYyhnWe8Ze3uK2D2EtesBxhAevweDoTcxXf3",
+            null
+        ]))
+        .with_id(0)
+}
+
+fn is_blockhash_valid_response(is_valid: bool) -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "slot": 2483, "apiVersion": "2.1.9" },
+            "value": is_valid
+        },
+    }))
+}
+
 fn get_account_info_response() -> JsonRpcResponse {
     JsonRpcResponse::from(json!({
         "id": Id::from(ConstantSizeId::ZERO),
@@ -2148,6 +5010,78 @@ fn get_block_response() -> JsonRpcResponse {
     }))
 }
 
+fn get_block_with_rewards_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result":{
+            "blockHeight": 360854634,
+            "blockTime": 1744122369,
+            "parentSlot": 372877611,
+            "blockhash": "8QeCusqSTKeC23NwjTKRBDcPuEfVLtszkxbpL6mXQEp4",
+            "previousBlockhash": "4Pcj2yJkCYyhnWe8Ze3uK2D2EtesBxhAevweDoTcxXf3",
+            "rewards": [
+                {
+                    "commission": null,
+                    "lamports": 2500,
+                    "postBalance": 499999840001_u64,
+                    "pubkey": "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy",
+                    "rewardType": "Fee"
+                }
+            ]
+        }
+    }))
+}
+
+fn get_blocks_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": [577996, 577997, 577999, 578000]
+    }))
+}
+
+fn get_leader_schedule_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            VALIDATOR_IDENTITY.to_string(): [3, 1, 2, 0]
+        }
+    }))
+}
+
+fn get_minimum_balance_for_rent_exemption_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": 2039280
+    }))
+}
+
+fn get_recent_performance_samples_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": [
+            {
+                "numNonVoteTransactions": 58_213,
+                "numSlots": 152,
+                "numTransactions": 243_718,
+                "samplePeriodSecs": 60,
+                "slot": 348_125_760
+            },
+            {
+                "numNonVoteTransactions": 61_094,
+                "numSlots": 149,
+                "numTransactions": 239_405,
+                "samplePeriodSecs": 60,
+                "slot": 348_125_608
+            }
+        ]
+    }))
+}
+
 fn get_recent_prioritization_fees_response() -> JsonRpcResponse {
     JsonRpcResponse::from(json!({
         "jsonrpc": "2.0",
@@ -2831,6 +5765,42 @@ fn get_signature_statuses_response(slot: Slot) -> JsonRpcResponse {
     }))
 }
 
+fn get_block_height_response(block_height: u64) -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": block_height,
+    }))
+}
+
+// Epoch info of a cluster with epochs of 432 slots, whose current epoch 2 started at slot 864
+fn get_epoch_info_response(slot: Slot) -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            "absoluteSlot": slot,
+            "blockHeight": slot - 100,
+            "epoch": 2,
+            "slotIndex": slot - 864,
+            "slotsInEpoch": 432,
+            "transactionCount": slot * 10
+        },
+    }))
+}
+
+fn get_fee_for_message_response(slot: Slot) -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            // context should be filtered out by transform
+            "context": { "slot": slot, "apiVersion": "2.1.9" },
+            "value": 5000
+        },
+    }))
+}
+
 fn get_slot_response(slot: Slot) -> JsonRpcResponse {
     JsonRpcResponse::from(json!({
         "id": Id::from(ConstantSizeId::ZERO),
@@ -2839,6 +5809,17 @@ fn get_slot_response(slot: Slot) -> JsonRpcResponse {
     }))
 }
 
+fn get_slot_leaders_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": [
+            "ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n",
+            "Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM"
+        ]
+    }))
+}
+
 fn get_token_account_balance_response(slot: Slot) -> JsonRpcResponse {
     JsonRpcResponse::from(json!({
         "id": Id::from(ConstantSizeId::ZERO),
@@ -2856,6 +5837,52 @@ fn get_token_account_balance_response(slot: Slot) -> JsonRpcResponse {
     }))
 }
 
+fn get_token_accounts_by_delegate_response(slot: Slot) -> JsonRpcResponse {
+    let mut accounts = vec![
+        json!({
+            "pubkey": "28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp",
+            "account": {
+                "data": ["AQID", "base64"],
+                "executable": false,
+                "lamports": 2039280,
+                "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                "rentEpoch": 18446744073709551615u64,
+                "space": 165
+            }
+        }),
+        json!({
+            "pubkey": "9PwCPoWJ75LSgZeGMubXBdufYMVd66HrcF78QzW6ZHkV",
+            "account": {
+                "data": ["BAUG", "base64"],
+                "executable": false,
+                "lamports": 2039280,
+                "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                "rentEpoch": 18446744073709551615u64,
+                "space": 165
+            }
+        }),
+    ];
+    // The order of the accounts is not specified and may differ between providers
+    if slot % 2 == 0 {
+        accounts.reverse();
+    }
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            // context should be filtered out by transform
+            "context": { "slot": slot, "apiVersion": "2.1.9" },
+            "value": accounts
+        },
+    }))
+}
+
+fn get_token_accounts_by_owner_response(slot: Slot) -> JsonRpcResponse {
+    // Same accounts as for `getTokenAccountsByDelegate`, which are also returned in an
+    // unspecified order.
+    get_token_accounts_by_delegate_response(slot)
+}
+
 fn get_transaction_response() -> JsonRpcResponse {
     JsonRpcResponse::from(json!({
         "id": Id::from(ConstantSizeId::ZERO),
@@ -2913,6 +5940,84 @@ fn get_transaction_response() -> JsonRpcResponse {
     }))
 }
 
+fn get_supply_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "slot": 1114, "apiVersion": "2.3.6" },
+            "value": {
+                "total": 589_000_123_456_789_012_u64,
+                "circulating": 426_000_012_345_678_901_u64,
+                "nonCirculating": 163_000_111_111_110_111_u64,
+                "nonCirculatingAccounts": [
+                    "FEy8pTbP5fEoqMV1GdTz83byuA8EKByqYat1PKDgVAq5",
+                    "9huDUZfxoJ7wGMTffUE7vh1xePqef7gyrLJu9NApncqA"
+                ]
+            }
+        },
+    }))
+}
+
+fn get_transaction_count_response(transaction_count: u64) -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": transaction_count,
+    }))
+}
+
+fn get_cluster_nodes_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": [
+            {
+                "featureSet": 2891131721_u32,
+                "gossip": "10.239.6.48:8001",
+                "pubkey": "9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ",
+                "rpc": "10.239.6.48:8899",
+                "shredVersion": 2405,
+                "tpu": "10.239.6.48:8856",
+                "version": "2.1.21"
+            }
+        ],
+    }))
+}
+
+fn get_epoch_schedule_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            "firstNormalEpoch": 0,
+            "firstNormalSlot": 0,
+            "leaderScheduleSlotOffset": 432_000,
+            "slotsPerEpoch": 432_000,
+            "warmup": false
+        },
+    }))
+}
+
+fn get_highest_snapshot_slot_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": {
+            "full": 368_700_000_u64,
+            "incremental": 368_724_300_u64
+        },
+    }))
+}
+
+fn minimum_ledger_slot_response() -> JsonRpcResponse {
+    JsonRpcResponse::from(json!({
+        "id": Id::from(ConstantSizeId::ZERO),
+        "jsonrpc": "2.0",
+        "result": 368_500_000_u64,
+    }))
+}
+
 fn get_version_response() -> JsonRpcResponse {
     JsonRpcResponse::from(json!({
         "jsonrpc": "2.0",
@@ -2941,13 +6046,7 @@ fn mock_for_ids(
     response: impl Fn() -> JsonRpcResponse,
     ids: impl IntoIterator<Item = u64>,
 ) -> MockHttpOutcallsBuilder {
-    let mut mocks = MockHttpOutcallsBuilder::new();
-    for id in ids {
-        mocks = mocks
-            .given(request().with_id(id))
-            .respond_with(response().with_id(id))
-    }
-    mocks
+    mock_sequential_json_rpc_responses(request, std::iter::repeat_with(response), ids)
 }
 
 fn mock_with_response_slots_for_ids(
@@ -2956,11 +6055,5 @@ fn mock_with_response_slots_for_ids(
     slots: impl IntoIterator<Item = Slot>,
     ids: impl IntoIterator<Item = u64>,
 ) -> MockHttpOutcallsBuilder {
-    let mut mocks = MockHttpOutcallsBuilder::new();
-    for (slot, id) in zip(slots, ids) {
-        mocks = mocks
-            .given(request().with_id(id))
-            .respond_with(response(slot).with_id(id))
-    }
-    mocks
+    mock_sequential_json_rpc_responses(request, slots.into_iter().map(response), ids)
 }
@@ -0,0 +1,213 @@
+//! Module for decoding the data of Solana [accounts](https://solana.com/docs/references/terminology#account)
+//! fetched with a [`DataSlice`].
+
+use serde::de::DeserializeOwned;
+use sol_rpc_types::DataSlice;
+use solana_account_decoder_client_types::UiAccount;
+use std::ops::Range;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// The decoded data of a Solana account, together with the [`DataSlice`] that was requested
+/// when fetching the account, if any.
+///
+/// When a `getAccountInfo` request specifies a data slice, the returned account data only
+/// consists of the requested bytes, starting at offset 0. Decoding that data as if it were the
+/// whole account data silently yields wrong results. A [`SlicedAccountData`] instead keeps track
+/// of the position of the returned bytes within the account data, so that bytes can be accessed
+/// by their offset in the account data and decoding fails with a descriptive error when the
+/// requested bytes were not fetched.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::account::{SlicedAccountData, SlicedAccountDataError};
+/// use sol_rpc_types::DataSlice;
+/// use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
+///
+/// // Bytes 2..6 of an account whose data consists of the bytes 0..10
+/// let account = UiAccount {
+///     lamports: 1_000_000,
+///     data: UiAccountData::Binary("AgMEBQ==".to_string(), UiAccountEncoding::Base64),
+///     owner: "11111111111111111111111111111111".to_string(),
+///     executable: false,
+///     rent_epoch: u64::MAX,
+///     space: Some(10),
+/// };
+/// let data_slice = DataSlice {
+///     offset: 2,
+///     length: 4,
+/// };
+///
+/// let data = SlicedAccountData::from_account(&account, Some(data_slice)).unwrap();
+///
+/// assert_eq!(data.get(3, 2), Ok([3_u8, 4].as_slice()));
+/// assert_eq!(
+///     data.get(0, 2),
+///     Err(SlicedAccountDataError::OutOfSliceBounds {
+///         requested: 0..2,
+///         available: 2..6,
+///     })
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlicedAccountData {
+    data: Vec<u8>,
+    data_slice: Option<DataSlice>,
+    space: Option<u64>,
+}
+
+impl SlicedAccountData {
+    /// Decodes the data of the given account, which was fetched with the given data slice.
+    ///
+    /// # Errors
+    ///
+    /// The method will return an instance of [`SlicedAccountDataError`] if the account data
+    /// cannot be decoded or if its size does not match the requested data slice and the size of
+    /// the account data. See [`SlicedAccountDataError`] for more details.
+    pub fn from_account(
+        account: &UiAccount,
+        data_slice: Option<DataSlice>,
+    ) -> Result<Self, SlicedAccountDataError> {
+        let data = account
+            .data
+            .decode()
+            .ok_or(SlicedAccountDataError::UnsupportedEncodingFormat)?;
+        let expected_size = match (&data_slice, account.space) {
+            (Some(data_slice), Some(space)) => {
+                Some((data_slice.length as u64).min(space.saturating_sub(data_slice.offset as u64)))
+            }
+            (None, space) => space,
+            // The size of the account data is unknown, so that fewer bytes than requested
+            // may legitimately be returned.
+            (Some(_), None) => None,
+        };
+        if let Some(expected) = expected_size {
+            if data.len() as u64 != expected {
+                return Err(SlicedAccountDataError::UnexpectedDataSize {
+                    expected,
+                    actual: data.len() as u64,
+                });
+            }
+        }
+        Ok(Self {
+            data,
+            data_slice,
+            space: account.space,
+        })
+    }
+
+    /// The data slice that was requested when fetching the account, if any.
+    pub fn data_slice(&self) -> Option<&DataSlice> {
+        self.data_slice.as_ref()
+    }
+
+    /// The range of the account data, in bytes, that was fetched.
+    pub fn range(&self) -> Range<u64> {
+        let offset = self
+            .data_slice
+            .as_ref()
+            .map_or(0, |data_slice| data_slice.offset as u64);
+        offset..offset + self.data.len() as u64
+    }
+
+    /// Returns `true` if the fetched data consists of the whole account data.
+    pub fn is_complete(&self) -> bool {
+        match self.space {
+            Some(space) => self.range() == (0..space),
+            None => self.data_slice.is_none(),
+        }
+    }
+
+    /// The fetched bytes, where the first byte is located at [`SlicedAccountData::range`]`.start`
+    /// in the account data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns `length` bytes starting at `offset` in the account data.
+    ///
+    /// # Errors
+    ///
+    /// The method will return [`SlicedAccountDataError::OutOfSliceBounds`] if any of the requested
+    /// bytes was not fetched.
+    pub fn get(&self, offset: u64, length: u64) -> Result<&[u8], SlicedAccountDataError> {
+        let available = self.range();
+        let requested = offset..offset.saturating_add(length);
+        if requested.start < available.start || requested.end > available.end {
+            return Err(SlicedAccountDataError::OutOfSliceBounds {
+                requested,
+                available,
+            });
+        }
+        let start = (requested.start - available.start) as usize;
+        let end = (requested.end - available.start) as usize;
+        Ok(&self.data[start..end])
+    }
+
+    /// Returns the whole account data.
+    ///
+    /// # Errors
+    ///
+    /// The method will return [`SlicedAccountDataError::IncompleteData`] if only part of the
+    /// account data was fetched.
+    pub fn complete_data(&self) -> Result<&[u8], SlicedAccountDataError> {
+        if !self.is_complete() {
+            return Err(SlicedAccountDataError::IncompleteData {
+                available: self.range(),
+                space: self.space,
+            });
+        }
+        Ok(&self.data)
+    }
+
+    /// Deserializes the whole account data with [`bincode`].
+    ///
+    /// # Errors
+    ///
+    /// The method will return [`SlicedAccountDataError::IncompleteData`] if only part of the
+    /// account data was fetched and [`SlicedAccountDataError::InvalidAccountData`] if the
+    /// account data cannot be deserialized.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, SlicedAccountDataError> {
+        bincode::deserialize(self.complete_data()?)
+            .map_err(|e| SlicedAccountDataError::InvalidAccountData(e.to_string()))
+    }
+}
+
+/// Errors that might happen when decoding account data with [`SlicedAccountData`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum SlicedAccountDataError {
+    /// The account data is encoded in a format that is not supported. Currently, this
+    /// only applies to account data encoded in `jsonParsed` format.
+    #[error("Unsupported encoding format")]
+    UnsupportedEncodingFormat,
+    /// The size of the decoded account data does not match the requested data slice.
+    #[error("Unexpected account data size: expected {expected} bytes, but got {actual} bytes")]
+    UnexpectedDataSize {
+        /// Expected size of the account data in bytes.
+        expected: u64,
+        /// Actual size of the account data in bytes.
+        actual: u64,
+    },
+    /// Some of the requested bytes are not part of the fetched data slice.
+    #[error("Requested bytes {requested:?} are outside of the fetched bytes {available:?}")]
+    OutOfSliceBounds {
+        /// The requested range of the account data.
+        requested: Range<u64>,
+        /// The fetched range of the account data.
+        available: Range<u64>,
+    },
+    /// Only part of the account data was fetched, but the whole account data is required.
+    #[error("Incomplete account data: only bytes {available:?} out of {space:?} were fetched")]
+    IncompleteData {
+        /// The fetched range of the account data.
+        available: Range<u64>,
+        /// The size of the account data in bytes, if known.
+        space: Option<u64>,
+    },
+    /// The account data could not be deserialized.
+    #[error("Invalid account data: {0}")]
+    InvalidAccountData(String),
+}
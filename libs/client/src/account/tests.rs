@@ -0,0 +1,169 @@
+use crate::account::{SlicedAccountData, SlicedAccountDataError};
+use assert_matches::assert_matches;
+use sol_rpc_types::DataSlice;
+use solana_account_decoder_client_types::{
+    ParsedAccount, UiAccount, UiAccountData, UiAccountEncoding,
+};
+
+// Base64 encoding of the bytes 0..10
+const FULL_DATA: &str = "AAECAwQFBgcICQ==";
+// Base64 encoding of the bytes 2..6
+const SLICED_DATA: &str = "AgMEBQ==";
+// Base64 encoding of the bytes 0..8, i.e. the `u64` value 42 encoded with `bincode`
+const U64_DATA: &str = "KgAAAAAAAAA=";
+
+mod from_account {
+    use super::*;
+
+    #[test]
+    fn should_decode_complete_data() {
+        let data = SlicedAccountData::from_account(&account(FULL_DATA, Some(10)), None).unwrap();
+
+        assert_eq!(data.range(), 0..10);
+        assert!(data.is_complete());
+        assert_eq!(data.as_bytes(), (0..10).collect::<Vec<u8>>().as_slice());
+    }
+
+    #[test]
+    fn should_decode_sliced_data() {
+        let data =
+            SlicedAccountData::from_account(&account(SLICED_DATA, Some(10)), Some(slice(2, 4)))
+                .unwrap();
+
+        assert_eq!(data.data_slice(), Some(&slice(2, 4)));
+        assert_eq!(data.range(), 2..6);
+        assert!(!data.is_complete());
+        assert_eq!(data.as_bytes(), &[2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn should_decode_slice_exceeding_account_data() {
+        let data =
+            SlicedAccountData::from_account(&account(SLICED_DATA, Some(6)), Some(slice(2, 100)))
+                .unwrap();
+
+        assert_eq!(data.range(), 2..6);
+    }
+
+    #[test]
+    fn should_be_complete_when_slice_covers_account_data() {
+        let data =
+            SlicedAccountData::from_account(&account(FULL_DATA, Some(10)), Some(slice(0, 100)))
+                .unwrap();
+
+        assert!(data.is_complete());
+    }
+
+    #[test]
+    fn should_fail_when_data_does_not_match_slice() {
+        assert_eq!(
+            SlicedAccountData::from_account(&account(FULL_DATA, Some(10)), Some(slice(2, 4))),
+            Err(SlicedAccountDataError::UnexpectedDataSize {
+                expected: 4,
+                actual: 10
+            })
+        );
+        assert_eq!(
+            SlicedAccountData::from_account(&account(SLICED_DATA, Some(10)), None),
+            Err(SlicedAccountDataError::UnexpectedDataSize {
+                expected: 10,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_for_json_parsed_data() {
+        let mut account = account(FULL_DATA, Some(10));
+        account.data = UiAccountData::Json(ParsedAccount {
+            program: "spl-token".to_string(),
+            parsed: serde_json::json!({}),
+            space: 10,
+        });
+
+        assert_eq!(
+            SlicedAccountData::from_account(&account, None),
+            Err(SlicedAccountDataError::UnsupportedEncodingFormat)
+        );
+    }
+}
+
+mod get {
+    use super::*;
+
+    #[test]
+    fn should_get_bytes_by_offset_in_account_data() {
+        let data =
+            SlicedAccountData::from_account(&account(SLICED_DATA, Some(10)), Some(slice(2, 4)))
+                .unwrap();
+
+        assert_eq!(data.get(2, 4), Ok([2_u8, 3, 4, 5].as_slice()));
+        assert_eq!(data.get(4, 1), Ok([4_u8].as_slice()));
+        assert_eq!(data.get(6, 0), Ok([].as_slice()));
+    }
+
+    #[test]
+    fn should_fail_for_bytes_outside_of_slice() {
+        let data =
+            SlicedAccountData::from_account(&account(SLICED_DATA, Some(10)), Some(slice(2, 4)))
+                .unwrap();
+
+        for (offset, length) in [(0, 4), (1, 2), (5, 2), (6, 1), (u64::MAX, 1)] {
+            assert_matches!(
+                data.get(offset, length),
+                Err(SlicedAccountDataError::OutOfSliceBounds { available, .. }) if available == (2..6)
+            );
+        }
+    }
+}
+
+mod deserialize {
+    use super::*;
+
+    #[test]
+    fn should_deserialize_complete_data() {
+        let data = SlicedAccountData::from_account(&account(U64_DATA, Some(8)), None).unwrap();
+
+        assert_eq!(data.deserialize::<u64>(), Ok(42));
+    }
+
+    #[test]
+    fn should_not_deserialize_sliced_data() {
+        let data =
+            SlicedAccountData::from_account(&account(SLICED_DATA, Some(10)), Some(slice(2, 4)))
+                .unwrap();
+
+        assert_eq!(
+            data.deserialize::<u32>(),
+            Err(SlicedAccountDataError::IncompleteData {
+                available: 2..6,
+                space: Some(10),
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_for_invalid_data() {
+        let data = SlicedAccountData::from_account(&account(SLICED_DATA, Some(4)), None).unwrap();
+
+        assert_matches!(
+            data.deserialize::<u64>(),
+            Err(SlicedAccountDataError::InvalidAccountData(_))
+        );
+    }
+}
+
+fn account(data: &str, space: Option<u64>) -> UiAccount {
+    UiAccount {
+        lamports: 1_000_000,
+        data: UiAccountData::Binary(data.to_string(), UiAccountEncoding::Base64),
+        owner: "11111111111111111111111111111111".to_string(),
+        executable: false,
+        rent_epoch: u64::MAX,
+        space,
+    }
+}
+
+fn slice(offset: u32, length: u32) -> DataSlice {
+    DataSlice { length, offset }
+}
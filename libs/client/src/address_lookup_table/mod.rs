@@ -0,0 +1,247 @@
+//! Module for interacting with Solana [address lookup tables](https://solana.com/developers/guides/advanced/lookup-tables).
+
+use serde::Deserialize;
+use sol_rpc_types::{RpcError, RpcResult, RpcSource, Slot};
+use solana_account_decoder_client_types::UiAccount;
+use solana_message::v0::{LoadedAddresses, Message};
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::address_lookup_table;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Size in bytes of the metadata at the beginning of an address lookup table account.
+/// The addresses stored in the table immediately follow the metadata.
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// The content of an initialized address lookup table account.
+///
+/// This is a decoded version of the address lookup table account state, as defined by the
+/// `solana_address_lookup_table_interface` crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressLookupTable {
+    /// Address of the address lookup table account.
+    pub key: Pubkey,
+    /// Metadata of the address lookup table.
+    pub meta: AddressLookupTableMeta,
+    /// Addresses stored in the address lookup table.
+    pub addresses: Vec<Pubkey>,
+}
+
+impl AddressLookupTable {
+    /// Returns the address stored at the given index, if any.
+    pub fn get(&self, index: u8) -> Option<&Pubkey> {
+        self.addresses.get(index as usize)
+    }
+}
+
+/// Metadata of an address lookup table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressLookupTableMeta {
+    /// Slot at which the table was deactivated, or `u64::MAX` if it was not deactivated.
+    pub deactivation_slot: Slot,
+    /// Slot at which the table was last extended.
+    pub last_extended_slot: Slot,
+    /// Index of the first address added in the slot at which the table was last extended.
+    pub last_extended_slot_start_index: u8,
+    /// Key allowed to extend, deactivate and close the table, or `None` if the table is frozen.
+    pub authority: Option<Pubkey>,
+}
+
+impl AddressLookupTableMeta {
+    /// Returns `true` if the table was deactivated.
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivation_slot != Slot::MAX
+    }
+}
+
+/// Mirrors the `ProgramState` type of the `solana_address_lookup_table_interface` crate.
+#[derive(Deserialize)]
+enum ProgramState {
+    Uninitialized,
+    LookupTable(LookupTableMeta),
+}
+
+/// Mirrors the `LookupTableMeta` type of the `solana_address_lookup_table_interface` crate.
+#[derive(Deserialize)]
+struct LookupTableMeta {
+    deactivation_slot: Slot,
+    last_extended_slot: Slot,
+    last_extended_slot_start_index: u8,
+    authority: Option<[u8; 32]>,
+    _padding: u16,
+}
+
+/// Decodes an address lookup table from the response of a `getAccountInfo` RPC call for the
+/// account with the given address.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::address_lookup_table::address_lookup_table_from_account;
+/// use solana_account_decoder_client_types::UiAccount;
+/// use solana_pubkey::pubkey;
+///
+/// # use sol_rpc_client::fixtures::address_lookup_table_account;
+/// let account = UiAccount::from(address_lookup_table_account());
+///
+/// let table = address_lookup_table_from_account(
+///     pubkey!("2immgwYNHBbyVQKVGCEkgWpi53bLwWNRMB5G2nbgYV17"),
+///     &account,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(table.addresses.len(), 3);
+/// assert_eq!(table.get(2), Some(&pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")));
+/// ```
+///
+/// # Errors
+///
+/// The method will return an instance of [`ExtractAddressLookupTableError`] if the account data
+/// does not correspond to a valid and properly encoded address lookup table account. See
+/// [`ExtractAddressLookupTableError`] for more details.
+pub fn address_lookup_table_from_account(
+    key: impl Into<Pubkey>,
+    account: &UiAccount,
+) -> Result<AddressLookupTable, ExtractAddressLookupTableError> {
+    if account.owner != address_lookup_table::ID.to_string() {
+        return Err(ExtractAddressLookupTableError::InvalidAccountOwner(
+            account.owner.clone(),
+        ));
+    }
+    let data = account
+        .data
+        .decode()
+        .ok_or(ExtractAddressLookupTableError::UnsupportedEncodingFormat)?;
+    if data.len() < LOOKUP_TABLE_META_SIZE
+        || (data.len() - LOOKUP_TABLE_META_SIZE) % size_of::<Pubkey>() != 0
+    {
+        return Err(ExtractAddressLookupTableError::UnexpectedDataSize(
+            data.len(),
+        ));
+    }
+    let (meta, addresses) = data.split_at(LOOKUP_TABLE_META_SIZE);
+    let meta = match bincode::deserialize::<ProgramState>(meta)
+        .map_err(|e| ExtractAddressLookupTableError::InvalidAccountData(e.to_string()))?
+    {
+        ProgramState::Uninitialized => {
+            return Err(ExtractAddressLookupTableError::Uninitialized);
+        }
+        ProgramState::LookupTable(meta) => meta,
+    };
+    Ok(AddressLookupTable {
+        key: key.into(),
+        meta: AddressLookupTableMeta {
+            deactivation_slot: meta.deactivation_slot,
+            last_extended_slot: meta.last_extended_slot,
+            last_extended_slot_start_index: meta.last_extended_slot_start_index,
+            authority: meta.authority.map(Pubkey::new_from_array),
+        },
+        addresses: addresses
+            .chunks_exact(size_of::<Pubkey>())
+            .map(|bytes| Pubkey::new_from_array(bytes.try_into().expect("BUG: expected 32 bytes")))
+            .collect(),
+    })
+}
+
+/// Resolves the addresses loaded by a versioned message from address lookup tables.
+///
+/// All the address lookup tables referenced by the message must be part of the given `tables`.
+/// The resolved addresses can then be used, together with the static account keys of the
+/// message, to reconstruct the full list of accounts used by a transaction, e.g. with
+/// [`solana_message::v0::LoadedMessage`].
+///
+/// Note that address lookup tables are append-only, so that tables fetched after a transaction
+/// was executed can be used to resolve its addresses, as long as the tables were not closed.
+///
+/// # Errors
+///
+/// The method will return an instance of [`ResolveLoadedAddressesError`] if a referenced table
+/// is missing or if an index is out of bounds for the corresponding table.
+pub fn resolve_loaded_addresses(
+    message: &Message,
+    tables: &[AddressLookupTable],
+) -> Result<LoadedAddresses, ResolveLoadedAddressesError> {
+    // `solana_message` may depend on a different version of the `solana_pubkey` crate
+    let mut writable = Vec::new();
+    let mut readonly = Vec::new();
+    for lookup in &message.address_table_lookups {
+        let table_key = Pubkey::new_from_array(lookup.account_key.to_bytes());
+        let table = tables
+            .iter()
+            .find(|table| table.key == table_key)
+            .ok_or(ResolveLoadedAddressesError::MissingLookupTable(table_key))?;
+        let resolve = |index: &u8| {
+            table
+                .get(*index)
+                .map(|address| address.to_bytes().into())
+                .ok_or(ResolveLoadedAddressesError::InvalidLookupIndex {
+                    table: table_key,
+                    index: *index,
+                })
+        };
+        for index in &lookup.writable_indexes {
+            writable.push(resolve(index)?);
+        }
+        for index in &lookup.readonly_indexes {
+            readonly.push(resolve(index)?);
+        }
+    }
+    Ok(LoadedAddresses { writable, readonly })
+}
+
+/// Errors that might happen when calling the [`address_lookup_table_from_account`] method.
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum ExtractAddressLookupTableError {
+    /// The account data does not represent a valid address lookup table account.
+    #[error("Invalid account data: {0}")]
+    InvalidAccountData(String),
+    /// The account owner is not the address lookup table program.
+    #[error("Invalid account owner: {0}")]
+    InvalidAccountOwner(String),
+    /// The account data is too short or its size is not a multiple of the address size.
+    #[error("Unexpected account data size: {0} bytes")]
+    UnexpectedDataSize(usize),
+    /// The account data is encoded in a format that is not supported. Currently, this
+    /// only applies to account data encoded in `jsonParsed` format.
+    #[error("Unsupported encoding format")]
+    UnsupportedEncodingFormat,
+    /// The address lookup table account exists but is not initialized.
+    #[error("Address lookup table is not initialized")]
+    Uninitialized,
+}
+
+/// Errors that might happen when calling the [`resolve_loaded_addresses`] method.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ResolveLoadedAddressesError {
+    /// The message references an address lookup table that was not provided.
+    #[error("Missing address lookup table: {0}")]
+    MissingLookupTable(Pubkey),
+    /// The message references an index that is out of bounds for the address lookup table.
+    #[error("Invalid index {index} for address lookup table {table}")]
+    InvalidLookupIndex {
+        /// Address of the address lookup table.
+        table: Pubkey,
+        /// The out of bounds index.
+        index: u8,
+    },
+}
+
+/// An error that occurred while trying to fetch an address lookup table.
+/// See [`SolRpcClient::get_address_lookup_table`](crate::SolRpcClient::get_address_lookup_table).
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum GetAddressLookupTableError {
+    /// The results from the different providers were not consistent for the `getAccountInfo` call.
+    #[error("Inconsistent result while fetching account: {0:?}")]
+    ConsensusError(Vec<(RpcSource, RpcResult<Option<UiAccount>>)>),
+    /// An error occurred during the `getAccountInfo` call.
+    #[error("Error while fetching account: {0}")]
+    RpcError(RpcError),
+    /// The fetched account is not a valid address lookup table account.
+    #[error("Invalid address lookup table: {0}")]
+    InvalidAddressLookupTable(ExtractAddressLookupTableError),
+    /// An IC error occurred while making the request.
+    #[error("IC error: {0}")]
+    IcError(crate::IcError),
+}
@@ -0,0 +1,208 @@
+use crate::{
+    address_lookup_table::{
+        address_lookup_table_from_account, resolve_loaded_addresses, AddressLookupTable,
+        AddressLookupTableMeta, ExtractAddressLookupTableError, ResolveLoadedAddressesError,
+    },
+    fixtures::address_lookup_table_account,
+};
+use assert_matches::assert_matches;
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
+use solana_pubkey::{pubkey, Pubkey};
+
+const TABLE: Pubkey = pubkey!("2immgwYNHBbyVQKVGCEkgWpi53bLwWNRMB5G2nbgYV17");
+const AUTHORITY: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+const SYSTEM_PROGRAM: Pubkey = pubkey!("11111111111111111111111111111111");
+const TOKEN_PROGRAM: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+mod address_lookup_table_account {
+    use super::*;
+
+    #[test]
+    fn should_decode_address_lookup_table() {
+        let account = UiAccount::from(address_lookup_table_account());
+
+        let table = address_lookup_table_from_account(TABLE, &account);
+
+        assert_eq!(table, Ok(expected_table()));
+        assert!(!table.unwrap().meta.is_deactivated());
+    }
+
+    #[test]
+    fn should_decode_deactivated_address_lookup_table_without_authority() {
+        let mut account = UiAccount::from(address_lookup_table_account());
+        account.data = UiAccountData::Binary(
+            "AQAAAKRIkBQAAAAAQEiQFAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAADG+nrzvtutOj1l82qryXQxsbvkwtL24OR8pgIDRS9dYQ==".to_string(),
+            UiAccountEncoding::Base64,
+        );
+
+        let table = address_lookup_table_from_account(TABLE, &account).unwrap();
+
+        assert_eq!(
+            table.meta,
+            AddressLookupTableMeta {
+                deactivation_slot: 345_000_100,
+                last_extended_slot: 345_000_000,
+                last_extended_slot_start_index: 0,
+                authority: None,
+            }
+        );
+        assert!(table.meta.is_deactivated());
+        assert_eq!(table.addresses, vec![USDC_MINT]);
+    }
+
+    #[test]
+    fn should_fail_for_uninitialized_account() {
+        let mut account = UiAccount::from(address_lookup_table_account());
+        account.data = UiAccountData::Binary(
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+                .to_string(),
+            UiAccountEncoding::Base64,
+        );
+
+        let table = address_lookup_table_from_account(TABLE, &account);
+
+        assert_eq!(table, Err(ExtractAddressLookupTableError::Uninitialized))
+    }
+
+    #[test]
+    fn should_fail_for_invalid_account_data() {
+        let mut account = UiAccount::from(address_lookup_table_account());
+        account.data = UiAccountData::Binary(
+            "AgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+                .to_string(),
+            UiAccountEncoding::Base64,
+        );
+
+        let table = address_lookup_table_from_account(TABLE, &account);
+
+        assert_matches!(
+            table,
+            Err(ExtractAddressLookupTableError::InvalidAccountData(_))
+        )
+    }
+
+    #[test]
+    fn should_fail_for_unexpected_data_size() {
+        for data in ["", "AQAAAA==", "AQAAAP//////////QEiQFAAAAAAAAdRLKVxB3UPPBB2IcYMgNX/TRujMHiUtB0MDaev+XupLAAAAAAAAAAAAAAAAAAAAAA=="] {
+            let mut account = UiAccount::from(address_lookup_table_account());
+            account.data = UiAccountData::Binary(data.to_string(), UiAccountEncoding::Base64);
+            let data_size = account.data.decode().unwrap().len();
+
+            let table = address_lookup_table_from_account(TABLE, &account);
+
+            assert_eq!(
+                table,
+                Err(ExtractAddressLookupTableError::UnexpectedDataSize(
+                    data_size
+                ))
+            )
+        }
+    }
+
+    #[test]
+    fn should_fail_for_invalid_account_owner() {
+        let mut account = UiAccount::from(address_lookup_table_account());
+        account.owner = "11111111111111111111111111111111".to_string();
+
+        let table = address_lookup_table_from_account(TABLE, &account);
+
+        assert_eq!(
+            table,
+            Err(ExtractAddressLookupTableError::InvalidAccountOwner(
+                "11111111111111111111111111111111".to_string()
+            ))
+        )
+    }
+}
+
+mod resolve_loaded_addresses {
+    use super::*;
+    use solana_message::v0::{Message, MessageAddressTableLookup};
+
+    #[test]
+    fn should_resolve_loaded_addresses() {
+        let message = message_with_lookup(TABLE, vec![2], vec![1, 0]);
+
+        let loaded_addresses = resolve_loaded_addresses(&message, &[expected_table()]).unwrap();
+
+        assert_eq!(
+            to_bytes(&loaded_addresses.writable),
+            vec![USDC_MINT.to_bytes()]
+        );
+        assert_eq!(
+            to_bytes(&loaded_addresses.readonly),
+            vec![TOKEN_PROGRAM.to_bytes(), SYSTEM_PROGRAM.to_bytes()]
+        );
+    }
+
+    #[test]
+    fn should_resolve_no_addresses_for_message_without_lookups() {
+        let loaded_addresses = resolve_loaded_addresses(&Message::default(), &[]).unwrap();
+
+        assert!(loaded_addresses.writable.is_empty());
+        assert!(loaded_addresses.readonly.is_empty());
+    }
+
+    #[test]
+    fn should_fail_for_missing_table() {
+        let message = message_with_lookup(AUTHORITY, vec![0], vec![]);
+
+        let result = resolve_loaded_addresses(&message, &[expected_table()]);
+
+        assert_eq!(
+            result,
+            Err(ResolveLoadedAddressesError::MissingLookupTable(AUTHORITY))
+        );
+    }
+
+    #[test]
+    fn should_fail_for_invalid_index() {
+        let message = message_with_lookup(TABLE, vec![0], vec![3]);
+
+        let result = resolve_loaded_addresses(&message, &[expected_table()]);
+
+        assert_eq!(
+            result,
+            Err(ResolveLoadedAddressesError::InvalidLookupIndex {
+                table: TABLE,
+                index: 3
+            })
+        );
+    }
+
+    fn message_with_lookup(
+        table: Pubkey,
+        writable_indexes: Vec<u8>,
+        readonly_indexes: Vec<u8>,
+    ) -> Message {
+        Message {
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: table.to_bytes().into(),
+                writable_indexes,
+                readonly_indexes,
+            }],
+            ..Message::default()
+        }
+    }
+
+    fn to_bytes<A: AsRef<[u8]>>(addresses: &[A]) -> Vec<[u8; 32]> {
+        addresses
+            .iter()
+            .map(|address| address.as_ref().try_into().unwrap())
+            .collect()
+    }
+}
+
+fn expected_table() -> AddressLookupTable {
+    AddressLookupTable {
+        key: TABLE,
+        meta: AddressLookupTableMeta {
+            deactivation_slot: u64::MAX,
+            last_extended_slot: 345_000_000,
+            last_extended_slot_start_index: 0,
+            authority: Some(AUTHORITY),
+        },
+        addresses: vec![SYSTEM_PROGRAM, TOKEN_PROGRAM, USDC_MINT],
+    }
+}
@@ -0,0 +1,54 @@
+//! Module for fetching the balances of several Solana accounts at once.
+//! See [`SolRpcClient::get_balance_many`](crate::SolRpcClient::get_balance_many).
+
+use sol_rpc_types::{Lamport, RpcError, RpcResult, RpcSource};
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of distinct public keys whose balance can be fetched with
+/// [`SolRpcClient::get_balance_many`](crate::SolRpcClient::get_balance_many).
+///
+/// This is the same limit as for the Solana
+/// [`getMultipleAccounts`](https://solana.com/docs/rpc/http/getmultipleaccounts) RPC method.
+pub const MAX_GET_BALANCE_MANY_PUBKEYS: usize = 100;
+
+/// An error that occurred while trying to fetch the balances of several accounts.
+/// See [`SolRpcClient::get_balance_many`](crate::SolRpcClient::get_balance_many).
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum GetBalanceManyError {
+    /// More than [`MAX_GET_BALANCE_MANY_PUBKEYS`] distinct public keys were given.
+    #[error("Too many public keys: expected at most {max}, got {actual}")]
+    TooManyPubkeys {
+        /// The maximum number of distinct public keys.
+        max: usize,
+        /// The number of distinct public keys that were given.
+        actual: usize,
+    },
+    /// The results from the different providers were not consistent for the `getBalance` call.
+    #[error("Inconsistent result while fetching the balance of {pubkey}: {results:?}")]
+    ConsensusError {
+        /// The public key of the account whose balance could not be fetched.
+        pubkey: Pubkey,
+        /// The results from the different providers.
+        results: Vec<(RpcSource, RpcResult<Lamport>)>,
+    },
+    /// An error occurred during the `getBalance` call.
+    #[error("Error while fetching the balance of {pubkey}: {error}")]
+    RpcError {
+        /// The public key of the account whose balance could not be fetched.
+        pubkey: Pubkey,
+        /// The error returned by the SOL RPC canister.
+        error: RpcError,
+    },
+    /// An IC error occurred while making the `getBalance` call.
+    #[error("IC error while fetching the balance of {pubkey}: {error}")]
+    IcError {
+        /// The public key of the account whose balance could not be fetched.
+        pubkey: Pubkey,
+        /// The IC error.
+        error: crate::IcError,
+    },
+}
@@ -0,0 +1,70 @@
+use crate::{
+    balance::{GetBalanceManyError, MAX_GET_BALANCE_MANY_PUBKEYS},
+    fixtures::{MockCall, MockRuntime},
+    SolRpcClient,
+};
+use sol_rpc_types::{GetBalanceParams, MultiRpcResult, RpcConfig, RpcError, RpcSources};
+use solana_pubkey::{pubkey, Pubkey};
+use std::collections::BTreeMap;
+
+const ALICE: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+const BOB: Pubkey = pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+
+type GetBalanceArgs = (RpcSources, Option<RpcConfig>, GetBalanceParams);
+
+#[tokio::test]
+async fn should_fetch_each_balance_once() {
+    let runtime = MockRuntime::new()
+        .with_call(get_balance(ALICE).respond_with(MultiRpcResult::Consistent(Ok(1_u64))))
+        .with_call(get_balance(BOB).respond_with(MultiRpcResult::Consistent(Ok(2_u64))));
+
+    let balances = client(&runtime).get_balance_many([BOB, ALICE, BOB]).await;
+
+    assert_eq!(balances, Ok(BTreeMap::from([(ALICE, 1), (BOB, 2)])));
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_fail_with_first_error() {
+    let error = RpcError::ValidationError("Invalid public key".to_string());
+    let runtime = MockRuntime::new()
+        .with_call(get_balance(ALICE).respond_with(MultiRpcResult::Consistent(Ok(1_u64))))
+        .with_call(
+            get_balance(BOB).respond_with(MultiRpcResult::<u64>::Consistent(Err(error.clone()))),
+        );
+
+    let balances = client(&runtime).get_balance_many([ALICE, BOB]).await;
+
+    assert_eq!(
+        balances,
+        Err(GetBalanceManyError::RpcError { pubkey: BOB, error })
+    );
+}
+
+#[tokio::test]
+async fn should_fail_with_too_many_pubkeys() {
+    let runtime = MockRuntime::new();
+    let pubkeys = (0..=MAX_GET_BALANCE_MANY_PUBKEYS).map(|i| Pubkey::new_from_array([i as u8; 32]));
+
+    let balances = client(&runtime).get_balance_many(pubkeys).await;
+
+    assert_eq!(
+        balances,
+        Err(GetBalanceManyError::TooManyPubkeys {
+            max: MAX_GET_BALANCE_MANY_PUBKEYS,
+            actual: MAX_GET_BALANCE_MANY_PUBKEYS + 1,
+        })
+    );
+    assert!(runtime.calls().is_empty());
+}
+
+fn get_balance(pubkey: Pubkey) -> MockCall {
+    MockCall::new("getBalance")
+        .with_args_matching(move |(_, _, params): GetBalanceArgs| params.pubkey == pubkey.into())
+}
+
+fn client(runtime: &MockRuntime) -> SolRpcClient<MockRuntime> {
+    SolRpcClient::builder_for_ic()
+        .with_mock_runtime(runtime.clone())
+        .build()
+}
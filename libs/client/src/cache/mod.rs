@@ -0,0 +1,239 @@
+//! Module to cache the responses of the SOL RPC canister on the caller side, to avoid paying
+//! cycles for the same request sent repeatedly within a short period of time.
+
+#[cfg(test)]
+mod tests;
+
+use crate::SolRpcEndpoint;
+use candid::{CandidType, Reserved};
+use serde::de::DeserializeOwned;
+use sol_rpc_types::MultiRpcResult;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Caches the successful responses of the SOL RPC canister for a configurable time-to-live (TTL)
+/// per endpoint.
+///
+/// When a [`ResponseCache`] is set on a client with
+/// [`ClientBuilder::with_response_cache`](crate::ClientBuilder::with_response_cache), each
+/// request to an endpoint for which a TTL was configured with [`ResponseCache::with_ttl`] is
+/// first looked up in the cache:
+/// * if a response to an identical request (same endpoint, RPC sources, RPC config and
+///   parameters) was cached less than TTL ago, it is returned without calling the SOL RPC
+///   canister, and therefore without spending any cycles nor calling the client's response hooks;
+/// * otherwise, the request is sent to the SOL RPC canister and its response is cached if it is
+///   consistent and successful.
+///
+/// Requests to endpoints without a configured TTL are never cached. Since cached responses may
+/// be outdated by up to the TTL, only cache endpoints whose results can tolerate that, e.g.
+/// `getSlot` for a second, or `getAccountInfo` with the `finalized` commitment level for a few
+/// seconds. In particular, `sendTransaction` should never be cached.
+///
+/// A [`ResponseCache`] can be cloned cheaply and all clones share the same cached responses.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::{ResponseCache, SolRpcClient, SolRpcEndpoint};
+/// use std::time::Duration;
+///
+/// let cache = ResponseCache::new()
+///     .with_ttl(SolRpcEndpoint::GetSlot, Duration::from_secs(1))
+///     .with_ttl(SolRpcEndpoint::GetAccountInfo, Duration::from_secs(10));
+/// assert_eq!(
+///     cache.ttl(&SolRpcEndpoint::GetSlot),
+///     Some(Duration::from_secs(1))
+/// );
+/// assert_eq!(cache.ttl(&SolRpcEndpoint::SendTransaction), None);
+///
+/// let client = SolRpcClient::builder_for_ic()
+///     .with_response_cache(cache)
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ResponseCache {
+    ttls: BTreeMap<SolRpcEndpoint, Duration>,
+    max_entries: usize,
+    clock: fn() -> u64,
+    entries: Arc<Mutex<BTreeMap<CacheKey, CacheEntry>>>,
+}
+
+type CacheKey = (SolRpcEndpoint, Vec<u8>);
+
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    expires_at_nanos: u64,
+    candid_output: Vec<u8>,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self {
+            ttls: BTreeMap::new(),
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            clock: now_nanos,
+            entries: Arc::default(),
+        }
+    }
+}
+
+impl ResponseCache {
+    /// Default maximum number of responses kept in the cache.
+    pub const DEFAULT_MAX_ENTRIES: usize = 1_000;
+
+    /// Creates a new empty [`ResponseCache`] without any configured TTL, i.e. not caching any
+    /// endpoint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caches the responses of the given endpoint for the given duration.
+    ///
+    /// A TTL of zero disables caching for that endpoint.
+    pub fn with_ttl(mut self, endpoint: SolRpcEndpoint, ttl: Duration) -> Self {
+        if ttl.is_zero() {
+            self.ttls.remove(&endpoint);
+        } else {
+            self.ttls.insert(endpoint, ttl);
+        }
+        self
+    }
+
+    /// Keeps at most the given number of responses in the cache, evicting the responses
+    /// expiring the soonest when full. Defaults to [`Self::DEFAULT_MAX_ENTRIES`].
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Returns the TTL of the cached responses of the given endpoint, if any.
+    pub fn ttl(&self, endpoint: &SolRpcEndpoint) -> Option<Duration> {
+        self.ttls.get(endpoint).copied()
+    }
+
+    /// Returns the number of responses currently in the cache, including expired ones that were
+    /// not yet evicted.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns `true` if the cache does not contain any response.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all responses from the cache.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_clock(mut self, clock: fn() -> u64) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Computes the cache key of a request to the given endpoint, if that endpoint is cached.
+    pub(crate) fn key<Args: CandidType>(
+        &self,
+        endpoint: &SolRpcEndpoint,
+        args: &Args,
+    ) -> Option<CacheKey> {
+        self.ttls.get(endpoint)?;
+        let encoded_args = candid::encode_one(args).ok()?;
+        Some((endpoint.clone(), encoded_args))
+    }
+
+    /// Returns the cached response for the given key, if any and not yet expired.
+    pub(crate) fn get<CandidOutput: DeserializeOwned>(
+        &self,
+        key: &CacheKey,
+    ) -> Option<CandidOutput> {
+        let now = (self.clock)();
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some(entry) if entry.expires_at_nanos > now => {
+                candid::decode_one(&entry.candid_output).ok()
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches the given response for the given key if it is a consistent and successful
+    /// [`MultiRpcResult`].
+    pub(crate) fn insert<CandidOutput: CandidType>(&self, key: CacheKey, output: &CandidOutput) {
+        let Some(ttl) = self.ttls.get(&key.0) else {
+            return;
+        };
+        let Ok(candid_output) = candid::encode_one(output) else {
+            return;
+        };
+        if !is_consistent_ok(&candid_output) || self.max_entries == 0 {
+            return;
+        }
+        let now = (self.clock)();
+        let expires_at_nanos = now.saturating_add(ttl.as_nanos().try_into().unwrap_or(u64::MAX));
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| entry.expires_at_nanos > now);
+        while entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let soonest_expiring = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at_nanos)
+                .map(|(key, _)| key.clone())
+                .expect("BUG: cache should not be empty");
+            entries.remove(&soonest_expiring);
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                expires_at_nanos,
+                candid_output,
+            },
+        );
+    }
+}
+
+/// Two [`ResponseCache`]s are equal if they share the same cached responses.
+impl PartialEq for ResponseCache {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl Eq for ResponseCache {}
+
+/// Returns `true` if the given Candid-encoded response is a consistent and successful
+/// [`MultiRpcResult`], regardless of the type of the value it contains.
+fn is_consistent_ok(candid_output: &[u8]) -> bool {
+    matches!(
+        candid::decode_one::<MultiRpcResult<Reserved>>(candid_output),
+        Ok(MultiRpcResult::Consistent(Ok(_)))
+    )
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm-web")))]
+fn now_nanos() -> u64 {
+    ic_cdk::api::time()
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm-web"))]
+fn now_nanos() -> u64 {
+    (js_sys::Date::now() * 1_000_000.0) as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_nanos() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("System time should be after the UNIX epoch")
+        .as_nanos()
+        .try_into()
+        .unwrap_or(u64::MAX)
+}
@@ -0,0 +1,150 @@
+use crate::{ResponseCache, SolRpcClient, SolRpcEndpoint};
+use sol_rpc_types::{
+    CommitmentLevel, MultiRpcResult, RpcError, RpcSource, Slot, SupportedRpcProviderId,
+};
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+thread_local! {
+    static NOW_NANOS: Cell<u64> = const { Cell::new(1_000_000_000) };
+}
+
+fn now_nanos() -> u64 {
+    NOW_NANOS.with(|now| now.get())
+}
+
+fn advance_time(duration: Duration) {
+    NOW_NANOS.with(|now| now.set(now.get() + duration.as_nanos() as u64));
+}
+
+fn cache() -> ResponseCache {
+    ResponseCache::new()
+        .with_ttl(SolRpcEndpoint::GetSlot, Duration::from_secs(1))
+        .with_clock(now_nanos)
+}
+
+fn slot(slot: Slot) -> MultiRpcResult<Slot> {
+    MultiRpcResult::Consistent(Ok(slot))
+}
+
+#[test]
+fn should_only_cache_endpoints_with_ttl() {
+    let cache = cache().with_ttl(SolRpcEndpoint::GetBalance, Duration::ZERO);
+
+    assert!(cache.key(&SolRpcEndpoint::GetSlot, &1_u64).is_some());
+    assert_eq!(cache.key(&SolRpcEndpoint::GetBalance, &1_u64), None);
+    assert_eq!(cache.key(&SolRpcEndpoint::SendTransaction, &1_u64), None);
+}
+
+#[test]
+fn should_expire_cached_response_after_ttl() {
+    let cache = cache();
+    let key = cache.key(&SolRpcEndpoint::GetSlot, &"params").unwrap();
+
+    cache.insert(key.clone(), &slot(100));
+    assert_eq!(cache.get::<MultiRpcResult<Slot>>(&key), Some(slot(100)));
+
+    advance_time(Duration::from_millis(999));
+    assert_eq!(cache.get::<MultiRpcResult<Slot>>(&key), Some(slot(100)));
+
+    advance_time(Duration::from_millis(1));
+    assert_eq!(cache.get::<MultiRpcResult<Slot>>(&key), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn should_only_cache_consistent_successful_responses() {
+    let cache = cache();
+    let key = cache.key(&SolRpcEndpoint::GetSlot, &"params").unwrap();
+
+    cache.insert(
+        key.clone(),
+        &MultiRpcResult::<Slot>::Consistent(Err(RpcError::ValidationError("error".to_string()))),
+    );
+    cache.insert(
+        key.clone(),
+        &MultiRpcResult::Inconsistent(vec![(
+            RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+            Ok(100_u64),
+        )]),
+    );
+    assert!(cache.is_empty());
+
+    cache.insert(key.clone(), &slot(100));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn should_evict_soonest_expiring_responses_when_full() {
+    let cache = cache().with_max_entries(2);
+    let keys: Vec<_> = (0_u64..3)
+        .map(|i| cache.key(&SolRpcEndpoint::GetSlot, &i).unwrap())
+        .collect();
+
+    for (i, key) in keys.iter().enumerate() {
+        cache.insert(key.clone(), &slot(i as Slot));
+        advance_time(Duration::from_millis(1));
+    }
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get::<MultiRpcResult<Slot>>(&keys[0]), None);
+    assert_eq!(cache.get::<MultiRpcResult<Slot>>(&keys[1]), Some(slot(1)));
+    assert_eq!(cache.get::<MultiRpcResult<Slot>>(&keys[2]), Some(slot(2)));
+}
+
+#[test]
+fn should_share_cached_responses_between_clones() {
+    let cache = cache();
+    let clone = cache.clone();
+    let key = cache.key(&SolRpcEndpoint::GetSlot, &"params").unwrap();
+
+    clone.insert(key.clone(), &slot(100));
+
+    assert_eq!(cache.get::<MultiRpcResult<Slot>>(&key), Some(slot(100)));
+    assert_eq!(cache, clone);
+    assert_ne!(cache, ResponseCache::new());
+
+    cache.clear();
+    assert!(clone.is_empty());
+}
+
+#[tokio::test]
+async fn should_serve_identical_requests_from_cache() {
+    let num_calls = Arc::new(AtomicU64::new(0));
+    let counter = num_calls.clone();
+    let client = SolRpcClient::builder_for_ic()
+        .with_stub_responses()
+        .add_stub_response(slot(100))
+        .add_stub_response(slot(200))
+        .add_stub_response(slot(300))
+        .with_response_cache(cache())
+        .with_response_hook(move |_response| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        })
+        .build();
+
+    assert_eq!(client.get_slot().send().await, slot(100));
+    assert_eq!(client.get_slot().send().await, slot(100));
+    assert_eq!(num_calls.load(Ordering::Relaxed), 1);
+
+    // Different parameters are cached separately
+    assert_eq!(
+        client
+            .get_slot()
+            .with_commitment(CommitmentLevel::Finalized)
+            .send()
+            .await,
+        slot(200)
+    );
+    assert_eq!(num_calls.load(Ordering::Relaxed), 2);
+
+    advance_time(Duration::from_secs(1));
+    assert_eq!(client.get_slot().send().await, slot(300));
+    assert_eq!(num_calls.load(Ordering::Relaxed), 3);
+}
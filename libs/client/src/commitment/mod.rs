@@ -0,0 +1,300 @@
+//! Module for the commitment levels of requests.
+//!
+//! The [`CommitmentPolicy`] of an endpoint defines which commitment level is used for a request
+//! to that endpoint, given the default commitment level of the client.
+//!
+//! Requests can also be retried at a lower commitment level when providers do not agree.
+//! See [`RequestBuilder::with_commitment_downgrade`].
+//!
+//! Providers lagging behind may only have `confirmed` data for a recent slot while the others
+//! already have `finalized` data, in which case the threshold consensus can fail. Since
+//! `confirmed` data is typically available at all providers, retrying the request at that
+//! commitment level can often reach consensus, at the cost of a weaker finality guarantee.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{DefaultRequestCycles, IcError, RequestBuilder, SolRpcEndpoint};
+use candid::CandidType;
+use ic_canister_runtime::Runtime;
+use serde::de::DeserializeOwned;
+use sol_rpc_types::{
+    CommitmentLevel, GetAccountInfoParams, GetAccountSnapshotParams, GetBalanceParams,
+    GetBlockCommitmentLevel, GetBlockHeightParams, GetBlockParams, GetBlocksParams,
+    GetBlocksWithLimitParams, GetEpochInfoParams, GetFeeForMessageParams, GetLeaderScheduleParams,
+    GetMinimumBalanceForRentExemptionParams, GetSignaturesForAddressParams, GetSlotParams,
+    GetSupplyParams, GetTokenAccountBalanceParams, GetTokenAccountsByDelegateParams,
+    GetTokenAccountsByOwnerParams, GetTransactionCountParams, GetTransactionParams,
+    HasAccountChangedParams, MultiRpcResult,
+};
+
+/// Defines how a commitment level applies to the requests to an endpoint, e.g. the default
+/// commitment level of a [`SolRpcClient`](crate::SolRpcClient).
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::{commitment::CommitmentPolicy, SolRpcEndpoint};
+/// use sol_rpc_types::CommitmentLevel;
+///
+/// let policy = CommitmentPolicy::for_endpoint(&SolRpcEndpoint::GetBlock);
+///
+/// assert_eq!(policy, CommitmentPolicy::ConfirmedOrFinalized);
+/// assert_eq!(
+///     policy.effective_commitment(CommitmentLevel::Processed),
+///     Some(CommitmentLevel::Confirmed)
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitmentPolicy {
+    /// The endpoint does not take a commitment level, e.g. `getSignatureStatuses`.
+    Unsupported,
+    /// The endpoint supports all commitment levels. For `sendTransaction`, the commitment level
+    /// is used as preflight commitment level.
+    Any,
+    /// The endpoint does not support the `processed` commitment level, e.g. `getBlock`, in which
+    /// case `confirmed` is used instead. Not setting a commitment level would be equivalent to
+    /// `finalized`, which would be further from the requested commitment level.
+    ConfirmedOrFinalized,
+}
+
+impl CommitmentPolicy {
+    /// Returns the commitment policy of the given endpoint.
+    pub fn for_endpoint(endpoint: &SolRpcEndpoint) -> Self {
+        match endpoint {
+            SolRpcEndpoint::GetBlock
+            | SolRpcEndpoint::GetBlocks
+            | SolRpcEndpoint::GetBlocksWithLimit => CommitmentPolicy::ConfirmedOrFinalized,
+            SolRpcEndpoint::GetAccountInfo
+            | SolRpcEndpoint::GetAccountInfoWithContext
+            | SolRpcEndpoint::GetAccountSnapshot
+            | SolRpcEndpoint::GetBalance
+            | SolRpcEndpoint::GetBalanceWithContext
+            | SolRpcEndpoint::GetBlockHeight
+            | SolRpcEndpoint::GetEpochInfo
+            | SolRpcEndpoint::GetFeeForMessage
+            | SolRpcEndpoint::GetLeaderSchedule
+            | SolRpcEndpoint::GetMinimumBalanceForRentExemption
+            | SolRpcEndpoint::GetSignaturesForAddress
+            | SolRpcEndpoint::GetSlot
+            | SolRpcEndpoint::GetSupply
+            | SolRpcEndpoint::GetTokenAccountBalance
+            | SolRpcEndpoint::GetTokenAccountBalanceWithContext
+            | SolRpcEndpoint::GetTokenAccountsByDelegate
+            | SolRpcEndpoint::GetTokenAccountsByOwner
+            | SolRpcEndpoint::GetTransaction
+            | SolRpcEndpoint::GetTransactionCount
+            | SolRpcEndpoint::HasAccountChanged
+            | SolRpcEndpoint::SendTransaction => CommitmentPolicy::Any,
+            SolRpcEndpoint::GetClusterNodes
+            | SolRpcEndpoint::GetEpochSchedule
+            | SolRpcEndpoint::GetHighestSnapshotSlot
+            | SolRpcEndpoint::GetRecentPerformanceSamples
+            | SolRpcEndpoint::GetRecentPrioritizationFees
+            | SolRpcEndpoint::GetSignatureStatuses
+            | SolRpcEndpoint::GetSlotLeaders
+            | SolRpcEndpoint::JsonRequest
+            | SolRpcEndpoint::MinimumLedgerSlot
+            | SolRpcEndpoint::RawHttpRequest => CommitmentPolicy::Unsupported,
+        }
+    }
+
+    /// Returns the commitment level used for a request to an endpoint with that policy when the
+    /// given commitment level is requested, or `None` if the endpoint does not take a commitment
+    /// level.
+    pub fn effective_commitment(&self, commitment: CommitmentLevel) -> Option<CommitmentLevel> {
+        match self {
+            CommitmentPolicy::Unsupported => None,
+            CommitmentPolicy::Any => Some(commitment),
+            CommitmentPolicy::ConfirmedOrFinalized => match commitment {
+                CommitmentLevel::Processed | CommitmentLevel::Confirmed => {
+                    Some(CommitmentLevel::Confirmed)
+                }
+                CommitmentLevel::Finalized => Some(CommitmentLevel::Finalized),
+            },
+        }
+    }
+}
+
+/// Request parameters whose commitment level can be downgraded.
+pub trait DowngradeCommitment {
+    /// Lower the commitment level of the parameters from `finalized` (the default when none
+    /// is set) to `confirmed`.
+    ///
+    /// Returns the new commitment level, or `None` if the commitment level was not changed
+    /// because it is already `confirmed` or lower.
+    fn downgrade_commitment(&mut self) -> Option<CommitmentLevel>;
+}
+
+macro_rules! impl_downgrade_commitment {
+    ($($params: ty),* $(,)?) => {
+        $(
+            impl DowngradeCommitment for $params {
+                fn downgrade_commitment(&mut self) -> Option<CommitmentLevel> {
+                    match self.commitment {
+                        None | Some(CommitmentLevel::Finalized) => {
+                            self.commitment = Some(CommitmentLevel::Confirmed);
+                            Some(CommitmentLevel::Confirmed)
+                        }
+                        Some(CommitmentLevel::Confirmed) | Some(CommitmentLevel::Processed) => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_downgrade_commitment!(
+    GetAccountInfoParams,
+    GetAccountSnapshotParams,
+    GetBalanceParams,
+    GetBlockHeightParams,
+    GetEpochInfoParams,
+    GetFeeForMessageParams,
+    GetLeaderScheduleParams,
+    GetMinimumBalanceForRentExemptionParams,
+    GetSignaturesForAddressParams,
+    GetSlotParams,
+    GetSupplyParams,
+    GetTokenAccountBalanceParams,
+    GetTokenAccountsByDelegateParams,
+    GetTokenAccountsByOwnerParams,
+    GetTransactionCountParams,
+    GetTransactionParams,
+    HasAccountChangedParams,
+);
+
+macro_rules! impl_downgrade_block_commitment {
+    ($($params: ty),* $(,)?) => {
+        $(
+            impl DowngradeCommitment for $params {
+                fn downgrade_commitment(&mut self) -> Option<CommitmentLevel> {
+                    match self.commitment {
+                        None | Some(GetBlockCommitmentLevel::Finalized) => {
+                            self.commitment = Some(GetBlockCommitmentLevel::Confirmed);
+                            Some(CommitmentLevel::Confirmed)
+                        }
+                        Some(GetBlockCommitmentLevel::Confirmed) => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_downgrade_block_commitment!(GetBlockParams, GetBlocksParams, GetBlocksWithLimitParams);
+
+impl<P: DowngradeCommitment + Default> DowngradeCommitment for Option<P> {
+    fn downgrade_commitment(&mut self) -> Option<CommitmentLevel> {
+        self.get_or_insert_with(P::default).downgrade_commitment()
+    }
+}
+
+/// The result of a request sent with a [`CommitmentDowngradeRequestBuilder`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommitmentDowngradeResult<T> {
+    /// The result of the last request that was sent.
+    pub result: MultiRpcResult<T>,
+    /// The commitment level the request was retried with, if the providers did not agree on
+    /// the result of the original request. `None` if the original request was not retried.
+    pub downgraded_to: Option<CommitmentLevel>,
+}
+
+/// A builder to send a request that is retried at a lower commitment level if the providers
+/// do not agree on its result.
+///
+/// To construct a [`CommitmentDowngradeRequestBuilder`], use
+/// [`RequestBuilder::with_commitment_downgrade`].
+#[must_use = "CommitmentDowngradeRequestBuilder does nothing until you 'send' it"]
+pub struct CommitmentDowngradeRequestBuilder<R, Config, Params, CandidOutput, Output> {
+    request: RequestBuilder<R, Config, Params, CandidOutput, Output>,
+}
+
+impl<R, Config, Params: DowngradeCommitment, CandidOutput, Output>
+    RequestBuilder<R, Config, Params, CandidOutput, Output>
+{
+    /// Retry the request at the `confirmed` commitment level if the providers do not agree
+    /// on its result at the requested commitment level (`finalized` by default).
+    ///
+    /// The retried request attaches the same amount of cycles as the original one, and the
+    /// returned [`CommitmentDowngradeResult`] indicates whether the result was obtained with
+    /// a downgraded commitment level. Requests whose commitment level is already `confirmed`
+    /// or lower are never retried.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(1_000_000_000_u64)))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let balance = client
+    ///     .get_balance(pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN"))
+    ///     .with_commitment_downgrade()
+    ///     .send()
+    ///     .await;
+    ///
+    /// assert_eq!(balance.result, MultiRpcResult::Consistent(Ok(1_000_000_000)));
+    /// assert_eq!(balance.downgraded_to, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_commitment_downgrade(
+        self,
+    ) -> CommitmentDowngradeRequestBuilder<R, Config, Params, CandidOutput, Output> {
+        CommitmentDowngradeRequestBuilder { request: self }
+    }
+}
+
+impl<R: Runtime, Config, Params, CandidOutput, T>
+    CommitmentDowngradeRequestBuilder<R, Config, Params, CandidOutput, MultiRpcResult<T>>
+where
+    Config: Clone + CandidType + Send + 'static,
+    Params: Clone + DowngradeCommitment + CandidType + Send + 'static,
+    CandidOutput: Into<MultiRpcResult<T>> + CandidType + DeserializeOwned,
+    T: 'static,
+    RequestBuilder<R, Config, Params, CandidOutput, MultiRpcResult<T>>: DefaultRequestCycles,
+{
+    /// Sends the request, retrying it at a lower commitment level if needed.
+    ///
+    /// # Panics
+    ///
+    /// If one of the requests was not successful.
+    pub async fn send(self) -> CommitmentDowngradeResult<T> {
+        self.try_send()
+            .await
+            .unwrap_or_else(|e| panic!("Client error: failed to send request: {e:?}"))
+    }
+
+    /// Sends the request, retrying it at a lower commitment level if needed. This method
+    /// returns either the result or any error that occurs while sending the requests.
+    pub async fn try_send(self) -> Result<CommitmentDowngradeResult<T>, IcError> {
+        let result = self.request.clone().try_send().await?;
+        if !matches!(result, MultiRpcResult::Inconsistent(_)) {
+            return Ok(CommitmentDowngradeResult {
+                result,
+                downgraded_to: None,
+            });
+        }
+        let mut downgraded_to = None;
+        let request = self
+            .request
+            .modify_params(|params| downgraded_to = params.downgrade_commitment());
+        let result = match downgraded_to {
+            Some(_) => request.try_send().await?,
+            None => result,
+        };
+        Ok(CommitmentDowngradeResult {
+            result,
+            downgraded_to,
+        })
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    commitment::{CommitmentDowngradeResult, CommitmentPolicy, DowngradeCommitment},
+    fixtures::{MockCall, MockRuntime},
+    SolRpcClient, SolRpcEndpoint,
+};
+use sol_rpc_types::{
+    CommitmentLevel, GetBalanceParams, GetBlockCommitmentLevel, GetBlockParams, GetSlotParams,
+    MultiRpcResult, RpcConfig, RpcError, RpcSource, RpcSources, SupportedRpcProviderId,
+};
+use solana_pubkey::{pubkey, Pubkey};
+use strum::IntoEnumIterator;
+
+const ALICE: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+
+type GetBalanceArgs = (RpcSources, Option<RpcConfig>, GetBalanceParams);
+
+#[test]
+fn should_downgrade_commitment() {
+    let mut params = GetBalanceParams::from(ALICE);
+    assert_eq!(
+        params.downgrade_commitment(),
+        Some(CommitmentLevel::Confirmed)
+    );
+    assert_eq!(params.commitment, Some(CommitmentLevel::Confirmed));
+    assert_eq!(params.downgrade_commitment(), None);
+
+    let mut params = GetBlockParams::from(1_u64);
+    params.commitment = Some(GetBlockCommitmentLevel::Finalized);
+    assert_eq!(
+        params.downgrade_commitment(),
+        Some(CommitmentLevel::Confirmed)
+    );
+    assert_eq!(params.commitment, Some(GetBlockCommitmentLevel::Confirmed));
+
+    let mut params: Option<GetSlotParams> = None;
+    assert_eq!(
+        params.downgrade_commitment(),
+        Some(CommitmentLevel::Confirmed)
+    );
+    assert_eq!(
+        params.and_then(|params| params.commitment),
+        Some(CommitmentLevel::Confirmed)
+    );
+}
+
+#[test]
+fn should_map_commitment_level_per_endpoint() {
+    for endpoint in SolRpcEndpoint::iter() {
+        let policy = CommitmentPolicy::for_endpoint(&endpoint);
+        let expected_policy = match endpoint {
+            SolRpcEndpoint::GetBlock
+            | SolRpcEndpoint::GetBlocks
+            | SolRpcEndpoint::GetBlocksWithLimit => CommitmentPolicy::ConfirmedOrFinalized,
+            SolRpcEndpoint::GetClusterNodes
+            | SolRpcEndpoint::GetEpochSchedule
+            | SolRpcEndpoint::GetHighestSnapshotSlot
+            | SolRpcEndpoint::GetRecentPerformanceSamples
+            | SolRpcEndpoint::GetRecentPrioritizationFees
+            | SolRpcEndpoint::GetSignatureStatuses
+            | SolRpcEndpoint::GetSlotLeaders
+            | SolRpcEndpoint::JsonRequest
+            | SolRpcEndpoint::MinimumLedgerSlot
+            | SolRpcEndpoint::RawHttpRequest => CommitmentPolicy::Unsupported,
+            _ => CommitmentPolicy::Any,
+        };
+        assert_eq!(
+            policy, expected_policy,
+            "Unexpected policy for {endpoint:?}"
+        );
+    }
+
+    for (commitment, expected_any, expected_confirmed_or_finalized) in [
+        (
+            CommitmentLevel::Processed,
+            CommitmentLevel::Processed,
+            CommitmentLevel::Confirmed,
+        ),
+        (
+            CommitmentLevel::Confirmed,
+            CommitmentLevel::Confirmed,
+            CommitmentLevel::Confirmed,
+        ),
+        (
+            CommitmentLevel::Finalized,
+            CommitmentLevel::Finalized,
+            CommitmentLevel::Finalized,
+        ),
+    ] {
+        assert_eq!(
+            CommitmentPolicy::Any.effective_commitment(commitment.clone()),
+            Some(expected_any)
+        );
+        assert_eq!(
+            CommitmentPolicy::ConfirmedOrFinalized.effective_commitment(commitment.clone()),
+            Some(expected_confirmed_or_finalized)
+        );
+        assert_eq!(
+            CommitmentPolicy::Unsupported.effective_commitment(commitment),
+            None
+        );
+    }
+}
+
+#[test]
+fn should_use_effective_commitment_as_default() {
+    for commitment in [
+        CommitmentLevel::Processed,
+        CommitmentLevel::Confirmed,
+        CommitmentLevel::Finalized,
+    ] {
+        let client = SolRpcClient::builder_for_ic()
+            .with_default_commitment_level(commitment.clone())
+            .build();
+
+        let slot_params = client.get_slot().build_args().args.2;
+        assert_eq!(
+            slot_params.and_then(|params| params.commitment),
+            client.effective_commitment(&SolRpcEndpoint::GetSlot)
+        );
+
+        let block_params = client.get_block(1_u64).build_args().args.2;
+        let expected_block_commitment = match client.effective_commitment(&SolRpcEndpoint::GetBlock)
+        {
+            Some(CommitmentLevel::Confirmed) => GetBlockCommitmentLevel::Confirmed,
+            Some(CommitmentLevel::Finalized) => GetBlockCommitmentLevel::Finalized,
+            other => panic!("Unexpected commitment level for `getBlock`: {other:?}"),
+        };
+        assert_eq!(block_params.commitment, Some(expected_block_commitment));
+    }
+
+    let client = SolRpcClient::builder_for_ic().build();
+    assert_eq!(client.effective_commitment(&SolRpcEndpoint::GetSlot), None);
+}
+
+#[tokio::test]
+async fn should_not_retry_consistent_result() {
+    let runtime = MockRuntime::new()
+        .with_call(get_balance(None).respond_with(MultiRpcResult::Consistent(Ok(1_u64))));
+
+    let result = client(&runtime)
+        .get_balance(ALICE)
+        .with_commitment_downgrade()
+        .send()
+        .await;
+
+    assert_eq!(
+        result,
+        CommitmentDowngradeResult {
+            result: MultiRpcResult::Consistent(Ok(1)),
+            downgraded_to: None,
+        }
+    );
+    assert_eq!(runtime.calls().len(), 1);
+}
+
+#[tokio::test]
+async fn should_retry_inconsistent_result_with_confirmed_commitment() {
+    let runtime = MockRuntime::new()
+        .with_call(get_balance(None).respond_with(inconsistent()))
+        .with_call(
+            get_balance(Some(CommitmentLevel::Confirmed))
+                .respond_with(MultiRpcResult::Consistent(Ok(2_u64))),
+        );
+
+    let result = client(&runtime)
+        .get_balance(ALICE)
+        .with_commitment_downgrade()
+        .send()
+        .await;
+
+    assert_eq!(
+        result,
+        CommitmentDowngradeResult {
+            result: MultiRpcResult::Consistent(Ok(2)),
+            downgraded_to: Some(CommitmentLevel::Confirmed),
+        }
+    );
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_not_retry_when_commitment_already_confirmed() {
+    let runtime = MockRuntime::new()
+        .with_call(get_balance(Some(CommitmentLevel::Confirmed)).respond_with(inconsistent()));
+
+    let result = client(&runtime)
+        .get_balance(ALICE)
+        .with_commitment(CommitmentLevel::Confirmed)
+        .with_commitment_downgrade()
+        .send()
+        .await;
+
+    assert_eq!(
+        result,
+        CommitmentDowngradeResult {
+            result: inconsistent(),
+            downgraded_to: None,
+        }
+    );
+    assert_eq!(runtime.calls().len(), 1);
+}
+
+fn inconsistent() -> MultiRpcResult<u64> {
+    MultiRpcResult::Inconsistent(vec![
+        (
+            RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+            Ok(1),
+        ),
+        (
+            RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet),
+            Err(RpcError::ValidationError("Slot not finalized".to_string())),
+        ),
+    ])
+}
+
+fn get_balance(commitment: Option<CommitmentLevel>) -> MockCall {
+    MockCall::new("getBalance").with_args_matching(move |(_, _, params): GetBalanceArgs| {
+        params.pubkey == ALICE.into() && params.commitment == commitment
+    })
+}
+
+fn client(runtime: &MockRuntime) -> SolRpcClient<MockRuntime> {
+    SolRpcClient::builder_for_ic()
+        .with_mock_runtime(runtime.clone())
+        .build()
+}
@@ -0,0 +1,175 @@
+//! Module to check the confirmation level of transactions, e.g. as returned by
+//! `getSignatureStatuses`, regardless of whether the transaction status is represented with
+//! [`sol_rpc_types`] or with Solana types.
+
+#[cfg(test)]
+mod tests;
+
+use sol_rpc_types::{
+    CommitmentLevel, ConfirmedTransactionStatusWithSignature, TransactionConfirmationStatus,
+};
+use solana_transaction_status_client_types::TransactionStatus;
+
+/// Confirmation level reached by a transaction, ordered from the least to the most final.
+///
+/// See [Commitment](https://solana.com/docs/rpc#configuring-state-commitment) for more on
+/// optimistic confirmation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confirmation {
+    /// The transaction was processed by a leader, but may be dropped.
+    Processed,
+    /// The transaction was included in a block that was voted on by a supermajority of the cluster.
+    Confirmed,
+    /// The transaction was included in a block that is finalized and cannot be rolled back.
+    Finalized,
+}
+
+impl Confirmation {
+    /// Returns `true` if the transaction reached at least the [`Confirmation::Confirmed`] level.
+    pub fn is_confirmed(self) -> bool {
+        self >= Confirmation::Confirmed
+    }
+
+    /// Returns `true` if the transaction reached the [`Confirmation::Finalized`] level.
+    pub fn is_finalized(self) -> bool {
+        self == Confirmation::Finalized
+    }
+
+    /// Returns `true` if the transaction reached at least the given commitment level.
+    pub fn satisfies(self, commitment: CommitmentLevel) -> bool {
+        self >= Confirmation::from(commitment)
+    }
+}
+
+impl From<CommitmentLevel> for Confirmation {
+    fn from(commitment: CommitmentLevel) -> Self {
+        match commitment {
+            CommitmentLevel::Processed => Self::Processed,
+            CommitmentLevel::Confirmed => Self::Confirmed,
+            CommitmentLevel::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<Confirmation> for CommitmentLevel {
+    fn from(confirmation: Confirmation) -> Self {
+        match confirmation {
+            Confirmation::Processed => Self::Processed,
+            Confirmation::Confirmed => Self::Confirmed,
+            Confirmation::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<TransactionConfirmationStatus> for Confirmation {
+    fn from(status: TransactionConfirmationStatus) -> Self {
+        match status {
+            TransactionConfirmationStatus::Processed => Self::Processed,
+            TransactionConfirmationStatus::Confirmed => Self::Confirmed,
+            TransactionConfirmationStatus::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<Confirmation> for TransactionConfirmationStatus {
+    fn from(confirmation: Confirmation) -> Self {
+        match confirmation {
+            Confirmation::Processed => Self::Processed,
+            Confirmation::Confirmed => Self::Confirmed,
+            Confirmation::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<solana_transaction_status_client_types::TransactionConfirmationStatus> for Confirmation {
+    fn from(status: solana_transaction_status_client_types::TransactionConfirmationStatus) -> Self {
+        Self::from(TransactionConfirmationStatus::from(status))
+    }
+}
+
+impl From<Confirmation> for solana_transaction_status_client_types::TransactionConfirmationStatus {
+    fn from(confirmation: Confirmation) -> Self {
+        Self::from(TransactionConfirmationStatus::from(confirmation))
+    }
+}
+
+/// Extension trait to check the confirmation level of a transaction status.
+///
+/// The trait is implemented for the transaction statuses returned by the [`SolRpcClient`],
+/// both as [`sol_rpc_types`] and as Solana types, as well as for their [`Option`] as returned
+/// by `getSignatureStatuses` for each signature, where [`None`] means that the transaction
+/// is unknown.
+///
+/// The SOL RPC canister does not return the deprecated `confirmations` field of transaction
+/// statuses, so that a transaction status without confirmation status is considered to have
+/// an unknown confirmation level and does not satisfy any commitment level.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::confirmation::{Confirmation, TransactionConfirmation};
+/// use sol_rpc_types::{CommitmentLevel, TransactionConfirmationStatus, TransactionStatus};
+///
+/// let status = Some(TransactionStatus {
+///     slot: 338_837_593,
+///     status: Ok(()),
+///     err: None,
+///     confirmation_status: Some(TransactionConfirmationStatus::Confirmed),
+/// });
+///
+/// assert_eq!(status.confirmation(), Some(Confirmation::Confirmed));
+/// assert!(status.is_confirmed());
+/// assert!(!status.is_finalized());
+/// assert!(status.satisfies(CommitmentLevel::Processed));
+/// assert!(!None::<TransactionStatus>.satisfies(CommitmentLevel::Processed));
+/// ```
+///
+/// [`SolRpcClient`]: crate::SolRpcClient
+pub trait TransactionConfirmation {
+    /// Returns the confirmation level reached by the transaction, if known.
+    fn confirmation(&self) -> Option<Confirmation>;
+
+    /// Returns `true` if the transaction is known to have reached at least the
+    /// [`Confirmation::Confirmed`] level.
+    fn is_confirmed(&self) -> bool {
+        self.confirmation().is_some_and(Confirmation::is_confirmed)
+    }
+
+    /// Returns `true` if the transaction is known to have reached the
+    /// [`Confirmation::Finalized`] level.
+    fn is_finalized(&self) -> bool {
+        self.confirmation().is_some_and(Confirmation::is_finalized)
+    }
+
+    /// Returns `true` if the transaction is known to have reached at least the given
+    /// commitment level.
+    fn satisfies(&self, commitment: CommitmentLevel) -> bool {
+        self.confirmation()
+            .is_some_and(|confirmation| confirmation.satisfies(commitment))
+    }
+}
+
+impl TransactionConfirmation for sol_rpc_types::TransactionStatus {
+    fn confirmation(&self) -> Option<Confirmation> {
+        self.confirmation_status.clone().map(Confirmation::from)
+    }
+}
+
+impl TransactionConfirmation for TransactionStatus {
+    fn confirmation(&self) -> Option<Confirmation> {
+        self.confirmation_status.clone().map(Confirmation::from)
+    }
+}
+
+impl TransactionConfirmation for ConfirmedTransactionStatusWithSignature {
+    fn confirmation(&self) -> Option<Confirmation> {
+        self.confirmation_status.clone().map(Confirmation::from)
+    }
+}
+
+impl<T: TransactionConfirmation> TransactionConfirmation for Option<T> {
+    fn confirmation(&self) -> Option<Confirmation> {
+        self.as_ref()
+            .and_then(TransactionConfirmation::confirmation)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::confirmation::{Confirmation, TransactionConfirmation};
+use sol_rpc_types::{CommitmentLevel, TransactionConfirmationStatus, TransactionStatus};
+
+const ALL_CONFIRMATIONS: [Confirmation; 3] = [
+    Confirmation::Processed,
+    Confirmation::Confirmed,
+    Confirmation::Finalized,
+];
+
+const ALL_COMMITMENT_LEVELS: [CommitmentLevel; 3] = [
+    CommitmentLevel::Processed,
+    CommitmentLevel::Confirmed,
+    CommitmentLevel::Finalized,
+];
+
+#[test]
+fn should_order_confirmations_by_finality() {
+    assert!(Confirmation::Processed < Confirmation::Confirmed);
+    assert!(Confirmation::Confirmed < Confirmation::Finalized);
+}
+
+#[test]
+fn should_satisfy_commitment_levels_up_to_confirmation() {
+    for confirmation in ALL_CONFIRMATIONS {
+        for commitment in ALL_COMMITMENT_LEVELS {
+            assert_eq!(
+                confirmation.satisfies(commitment.clone()),
+                confirmation >= Confirmation::from(commitment),
+            );
+        }
+    }
+    assert!(Confirmation::Finalized.satisfies(CommitmentLevel::Confirmed));
+    assert!(!Confirmation::Processed.satisfies(CommitmentLevel::Confirmed));
+}
+
+#[test]
+fn should_be_confirmed_and_finalized() {
+    assert!(!Confirmation::Processed.is_confirmed());
+    assert!(Confirmation::Confirmed.is_confirmed());
+    assert!(Confirmation::Finalized.is_confirmed());
+
+    assert!(!Confirmation::Processed.is_finalized());
+    assert!(!Confirmation::Confirmed.is_finalized());
+    assert!(Confirmation::Finalized.is_finalized());
+}
+
+#[test]
+fn should_convert_confirmations() {
+    for confirmation in ALL_CONFIRMATIONS {
+        assert_eq!(
+            Confirmation::from(CommitmentLevel::from(confirmation)),
+            confirmation
+        );
+        assert_eq!(
+            Confirmation::from(TransactionConfirmationStatus::from(confirmation)),
+            confirmation
+        );
+        assert_eq!(
+            Confirmation::from(
+                solana_transaction_status_client_types::TransactionConfirmationStatus::from(
+                    confirmation
+                )
+            ),
+            confirmation
+        );
+    }
+}
+
+#[test]
+fn should_have_same_confirmation_for_sol_rpc_and_solana_types() {
+    for confirmation_status in [
+        None,
+        Some(TransactionConfirmationStatus::Processed),
+        Some(TransactionConfirmationStatus::Confirmed),
+        Some(TransactionConfirmationStatus::Finalized),
+    ] {
+        let status = transaction_status(confirmation_status.clone());
+        let solana_status =
+            solana_transaction_status_client_types::TransactionStatus::from(status.clone());
+
+        assert_eq!(
+            status.confirmation(),
+            confirmation_status.map(Confirmation::from)
+        );
+        assert_eq!(solana_status.confirmation(), status.confirmation());
+        assert_eq!(Some(solana_status).confirmation(), status.confirmation());
+    }
+}
+
+#[test]
+fn should_not_satisfy_any_commitment_when_confirmation_is_unknown() {
+    for status in [None, Some(transaction_status(None))] {
+        assert_eq!(status.confirmation(), None);
+        assert!(!status.is_confirmed());
+        assert!(!status.is_finalized());
+        for commitment in ALL_COMMITMENT_LEVELS {
+            assert!(!status.satisfies(commitment));
+        }
+    }
+}
+
+fn transaction_status(
+    confirmation_status: Option<TransactionConfirmationStatus>,
+) -> TransactionStatus {
+    TransactionStatus {
+        slot: 338_837_593,
+        status: Ok(()),
+        err: None,
+        confirmation_status,
+    }
+}
@@ -6,6 +6,7 @@
 use crate::Runtime;
 use candid::Principal;
 use derive_more::{From, Into};
+use futures::future::join_all;
 use ic_canister_runtime::IcError;
 use ic_management_canister_types::{
     SchnorrAlgorithm, SchnorrKeyId, SchnorrPublicKeyArgs, SchnorrPublicKeyResult,
@@ -64,6 +65,18 @@ impl Ed25519KeyId {
             Ed25519KeyId::MainnetProdKey1 => "key_1",
         }
     }
+
+    /// The amount of cycles charged for a single `sign_with_schnorr` call using this key.
+    pub fn sign_with_schnorr_fee(&self) -> u128 {
+        match self {
+            Ed25519KeyId::MainnetTestKey1 => SIGN_WITH_SCHNORR_TEST_FEE,
+            // Threshold signatures are charged the same in local development environments as in prod, see
+            // https://internetcomputer.org/docs/references/t-sigs-how-it-works#local-development-environment
+            Ed25519KeyId::LocalDevelopment | Ed25519KeyId::MainnetProdKey1 => {
+                SIGN_WITH_SCHNORR_PRODUCTION_FEE
+            }
+        }
+    }
 }
 
 /// Sign a Solana message with threshold EdDSA, see threshold Schnorr documentation
@@ -179,14 +192,7 @@ pub async fn sign_message<R: Runtime>(
             Principal::management_canister(),
             "sign_with_schnorr",
             (arg,),
-            match key_id {
-                Ed25519KeyId::MainnetTestKey1 => SIGN_WITH_SCHNORR_TEST_FEE,
-                // Threshold signatures are charged the same in local development environments as in prod, see
-                // https://internetcomputer.org/docs/references/t-sigs-how-it-works#local-development-environment
-                Ed25519KeyId::LocalDevelopment | Ed25519KeyId::MainnetProdKey1 => {
-                    SIGN_WITH_SCHNORR_PRODUCTION_FEE
-                }
-            },
+            key_id.sign_with_schnorr_fee(),
         )
         .await?;
     solana_signature::Signature::try_from(signature).map_err(|e| {
@@ -197,6 +203,90 @@ pub async fn sign_message<R: Runtime>(
     })
 }
 
+/// Sign several Solana messages with threshold EdDSA concurrently, e.g. a transaction message
+/// requiring signatures from multiple derived keys.
+///
+/// Each message is signed with the key derived from the corresponding derivation path, and the
+/// signatures are returned in the same order as the messages. All `sign_with_schnorr` calls are
+/// made concurrently, so that signing multiple messages takes about as long as signing a single
+/// one. The total amount of cycles attached to the calls is given by [`sign_messages_fee`].
+///
+/// If any of the calls fails, the first error is returned. Note that the other calls are still
+/// completed, and hence charged.
+///
+/// # Examples
+///
+/// ```rust
+/// use candid::Principal;
+/// use ic_canister_runtime::IcRuntime;
+/// use solana_hash::Hash;
+/// use solana_message::legacy::Message;
+/// use solana_pubkey::pubkey;
+/// use solana_signature::Signature;
+/// use solana_system_interface::instruction::transfer;
+/// use sol_rpc_client::ed25519::{
+///     sign_messages, sign_messages_fee, DerivationPath, Ed25519KeyId,
+/// };
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # use sol_rpc_client::fixtures::MockRuntime;
+/// # use ic_management_canister_types::SignWithSchnorrResult;
+/// # use std::str::FromStr;
+/// let runtime = IcRuntime::new();
+/// # let runtime = MockRuntime::default()
+/// #   .with_response_for_method("sign_with_schnorr", SignWithSchnorrResult {
+/// #       signature: Signature::from_str("37HbmunhjSC1xxnVsaFX2xaS8gYnb5JYiLy9B51Ky9Up69aF7Qra6dHSLMCaiurRYq3Y8ZxSVUwC5sntziWuhZee").unwrap().as_ref().to_vec()
+/// #   });
+///
+/// let key_id = Ed25519KeyId::MainnetTestKey1;
+/// let payer_path = DerivationPath::from(b"payer".as_slice());
+/// let sender_path = DerivationPath::from(b"sender".as_slice());
+/// let payer = pubkey!("BPebStjcgCPnWTK3FXZJ8KhqwNYLk9aubC9b4Cgqb6oE");
+/// let sender = pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+/// let recipient = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+///
+/// let message = Message::new_with_blockhash(
+///     &[transfer(&sender, &recipient, 1_000_000)],
+///     Some(&payer),
+///     &Hash::default(),
+/// );
+///
+/// assert_eq!(sign_messages_fee(key_id, 2), 20_000_000_000);
+///
+/// let signatures = sign_messages(
+///     &runtime,
+///     &[(&message, Some(&payer_path)), (&message, Some(&sender_path))],
+///     key_id,
+/// )
+/// .await
+/// .unwrap();
+///
+/// assert_eq!(signatures.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn sign_messages<R: Runtime>(
+    runtime: &R,
+    messages: &[(&solana_message::Message, Option<&DerivationPath>)],
+    key_id: Ed25519KeyId,
+) -> Result<Vec<solana_signature::Signature>, IcError> {
+    join_all(
+        messages.iter().map(|(message, derivation_path)| {
+            sign_message(runtime, message, key_id, *derivation_path)
+        }),
+    )
+    .await
+    .into_iter()
+    .collect()
+}
+
+/// The total amount of cycles attached to the `sign_with_schnorr` calls when signing the given
+/// number of messages with the given key, e.g. with [`sign_messages`].
+pub fn sign_messages_fee(key_id: Ed25519KeyId, num_messages: usize) -> u128 {
+    key_id.sign_with_schnorr_fee() * num_messages as u128
+}
+
 /// Fetch the Ed25519 public key for the key ID, given canister ID and derivation path, see threshold Schnorr
 /// documentation [here](https://internetcomputer.org/docs/building-apps/network-features/signatures/t-schnorr).
 ///
@@ -0,0 +1,246 @@
+//! Module for estimating the total cost of sending a Solana transaction,
+//! see [`SolRpcClient::estimate_transaction_cost`](crate::SolRpcClient::estimate_transaction_cost).
+//!
+//! The cost of a transaction consists of
+//! * the base fee charged by the network for the transaction signatures, as returned by the
+//!   [`getFeeForMessage`](https://solana.com/docs/rpc/http/getfeeformessage) RPC method;
+//! * the [prioritization fee](https://solana.com/docs/core/fees#prioritization-fees), which is
+//!   the compute unit price multiplied by the compute unit limit of the transaction;
+//! * the minimum balance required to make every account created by the transaction
+//!   [rent-exempt](https://solana.com/docs/core/accounts#rent).
+
+#[cfg(test)]
+mod tests;
+
+use crate::instruction_decoder::{
+    decode_compiled_instruction, AssociatedTokenAccountInstruction, ComputeBudgetInstruction,
+    CreateAssociatedTokenAccount, DecodedInstruction, InstructionDecodingError, SystemInstruction,
+    TOKEN_2022_PROGRAM_ID,
+};
+use sol_rpc_types::{Lamport, PrioritizationFee, RpcError, RpcResult, RpcSource};
+use solana_message::VersionedMessage;
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+
+/// Compute unit limit of every instruction that is not an instruction of the Compute Budget
+/// program, if the transaction does not set a compute unit limit.
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+/// Maximum compute unit limit of a transaction.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Size in bytes of an SPL Token account.
+const TOKEN_ACCOUNT_SIZE: u64 = 165;
+
+/// Size in bytes of a Token-2022 account created by the Associated Token Account program,
+/// which includes the `ImmutableOwner` extension.
+const TOKEN_2022_ACCOUNT_SIZE: u64 = 170;
+
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// Estimated cost of a transaction in lamports.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// Fee charged by the network for the transaction signatures.
+    pub base_fee: Lamport,
+    /// Prioritization fee of the transaction.
+    ///
+    /// If the transaction sets a compute unit price, this is the prioritization fee resulting
+    /// from that price. Otherwise, this is the prioritization fee resulting from the median of
+    /// the recent prioritization fees paid by transactions locking the same writable accounts,
+    /// which is the fee to expect when adding a `SetComputeUnitPrice` instruction with that price.
+    pub priority_fee: Lamport,
+    /// Sum of the minimum balances required to make the accounts created by the transaction
+    /// rent-exempt.
+    pub rent: Lamport,
+    /// Total cost of the transaction, i.e. the sum of all the above.
+    pub total: Lamport,
+}
+
+impl FeeEstimate {
+    /// Creates a new estimate from its components, computing the total cost.
+    pub fn new(base_fee: Lamport, priority_fee: Lamport, rent: Lamport) -> Self {
+        Self {
+            base_fee,
+            priority_fee,
+            rent,
+            total: base_fee.saturating_add(priority_fee).saturating_add(rent),
+        }
+    }
+}
+
+/// An error that occurred while trying to estimate the cost of a transaction.
+/// See [`SolRpcClient::estimate_transaction_cost`](crate::SolRpcClient::estimate_transaction_cost).
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum EstimateTransactionCostError {
+    /// The instructions of the message could not be decoded.
+    #[error("Invalid message: {0}")]
+    InvalidMessage(InstructionDecodingError),
+    /// The fee for the message could not be computed, e.g. because the recent blockhash of
+    /// the message expired.
+    #[error("Fee for message is unavailable")]
+    FeeUnavailable,
+    /// The results from the different providers were not consistent for the `getFeeForMessage`
+    /// call.
+    #[error("Inconsistent result while fetching fee for message: {0:?}")]
+    GetFeeForMessageConsensusError(Vec<(RpcSource, RpcResult<Option<Lamport>>)>),
+    /// The results from the different providers were not consistent for the
+    /// `getRecentPrioritizationFees` call.
+    #[error("Inconsistent result while fetching recent prioritization fees: {0:?}")]
+    GetRecentPrioritizationFeesConsensusError(Vec<(RpcSource, RpcResult<Vec<PrioritizationFee>>)>),
+    /// The results from the different providers were not consistent for a
+    /// `getMinimumBalanceForRentExemption` call.
+    #[error("Inconsistent result while fetching minimum balance for rent exemption: {0:?}")]
+    GetMinimumBalanceForRentExemptionConsensusError(Vec<(RpcSource, RpcResult<Lamport>)>),
+    /// An error occurred during one of the RPC calls.
+    #[error("Error while estimating transaction cost: {0}")]
+    RpcError(RpcError),
+    /// An IC error occurred while making the request.
+    #[error("IC error: {0}")]
+    IcError(crate::IcError),
+}
+
+/// Information extracted from a message that is relevant to estimate its cost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageCostParameters {
+    /// Compute unit limit of the transaction, either set with a `SetComputeUnitLimit`
+    /// instruction or derived from the number of instructions.
+    pub compute_unit_limit: u32,
+    /// Compute unit price in micro-lamports set with a `SetComputeUnitPrice` instruction, if any.
+    pub compute_unit_price: Option<u64>,
+    /// Writable accounts among the static account keys of the message.
+    pub writable_accounts: Vec<Pubkey>,
+    /// Sizes in bytes of the accounts created by the message with the System program or the
+    /// Associated Token Account program. Associated token accounts that are created idempotently
+    /// are included, even though they might already exist.
+    pub created_account_sizes: Vec<u64>,
+}
+
+impl MessageCostParameters {
+    /// Extracts the cost parameters of the given message.
+    ///
+    /// Addresses loaded from address lookup tables are not resolved, so that accounts that are
+    /// only loaded from address lookup tables are not considered writable.
+    ///
+    /// # Errors
+    ///
+    /// The method will return an instance of [`InstructionDecodingError`] if an instruction of
+    /// the message cannot be decoded, see
+    /// [`decode_instruction`](crate::instruction_decoder::decode_instruction).
+    pub fn from_message(message: &VersionedMessage) -> Result<Self, InstructionDecodingError> {
+        let static_keys = message.static_account_keys();
+        let num_loaded_addresses = message.address_table_lookups().map_or(0, |lookups| {
+            lookups
+                .iter()
+                .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                .sum()
+        });
+        // Loaded addresses are unknown without fetching the address lookup tables and are
+        // replaced by a placeholder so that instructions referencing them can still be decoded.
+        let account_keys: Vec<Pubkey> = static_keys
+            .iter()
+            .copied()
+            .chain(std::iter::repeat_n(Pubkey::default(), num_loaded_addresses))
+            .collect();
+
+        let mut compute_unit_limit = None;
+        let mut compute_unit_price = None;
+        let mut num_non_compute_budget_instructions: u32 = 0;
+        let mut created_account_sizes = Vec::new();
+        for instruction in message.instructions() {
+            match decode_compiled_instruction(
+                &account_keys,
+                instruction.program_id_index,
+                &instruction.accounts,
+                &instruction.data,
+            )? {
+                DecodedInstruction::ComputeBudget(
+                    ComputeBudgetInstruction::SetComputeUnitLimit(limit),
+                ) => compute_unit_limit = Some(limit),
+                DecodedInstruction::ComputeBudget(
+                    ComputeBudgetInstruction::SetComputeUnitPrice(price),
+                ) => compute_unit_price = Some(price),
+                DecodedInstruction::ComputeBudget(_) => {}
+                instruction => {
+                    num_non_compute_budget_instructions += 1;
+                    if let Some(size) = created_account_size(&instruction) {
+                        created_account_sizes.push(size);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            compute_unit_limit: compute_unit_limit
+                .unwrap_or(
+                    num_non_compute_budget_instructions
+                        .saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT),
+                )
+                .min(MAX_COMPUTE_UNIT_LIMIT),
+            compute_unit_price,
+            writable_accounts: static_keys
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| is_static_account_writable(message, *index))
+                .map(|(_, key)| *key)
+                .collect(),
+            created_account_sizes,
+        })
+    }
+}
+
+/// Prioritization fee in lamports of a transaction with the given compute unit price in
+/// micro-lamports and compute unit limit, rounded up.
+pub fn prioritization_fee(compute_unit_price: u64, compute_unit_limit: u32) -> Lamport {
+    let fee = (compute_unit_price as u128 * compute_unit_limit as u128)
+        .div_ceil(MICRO_LAMPORTS_PER_LAMPORT);
+    Lamport::try_from(fee).unwrap_or(Lamport::MAX)
+}
+
+/// Median of the given recent prioritization fees in micro-lamports per compute unit,
+/// or 0 if there are none.
+pub fn median_prioritization_fee(fees: &[PrioritizationFee]) -> u64 {
+    let mut fees: Vec<u64> = fees.iter().map(|fee| fee.prioritization_fee).collect();
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    fees[fees.len() / 2]
+}
+
+fn is_static_account_writable(message: &VersionedMessage, index: usize) -> bool {
+    let header = message.header();
+    let num_signed = header.num_required_signatures as usize;
+    let num_keys = message.static_account_keys().len();
+    if index < num_signed {
+        index < num_signed.saturating_sub(header.num_readonly_signed_accounts as usize)
+    } else {
+        index < num_keys.saturating_sub(header.num_readonly_unsigned_accounts as usize)
+    }
+}
+
+fn created_account_size(instruction: &DecodedInstruction) -> Option<u64> {
+    match instruction {
+        DecodedInstruction::System(SystemInstruction::CreateAccount { space, .. })
+        | DecodedInstruction::System(SystemInstruction::CreateAccountWithSeed { space, .. }) => {
+            Some(*space)
+        }
+        DecodedInstruction::AssociatedTokenAccount(
+            AssociatedTokenAccountInstruction::Create(CreateAssociatedTokenAccount {
+                token_program,
+                ..
+            })
+            | AssociatedTokenAccountInstruction::CreateIdempotent(CreateAssociatedTokenAccount {
+                token_program,
+                ..
+            }),
+        ) => {
+            if token_program == &TOKEN_2022_PROGRAM_ID {
+                Some(TOKEN_2022_ACCOUNT_SIZE)
+            } else {
+                Some(TOKEN_ACCOUNT_SIZE)
+            }
+        }
+        _ => None,
+    }
+}
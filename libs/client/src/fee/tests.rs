@@ -0,0 +1,338 @@
+use crate::{
+    fee::{
+        median_prioritization_fee, prioritization_fee, EstimateTransactionCostError, FeeEstimate,
+        MessageCostParameters,
+    },
+    instruction_decoder::{
+        InstructionDecodingError, ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, TOKEN_2022_PROGRAM_ID,
+        TOKEN_PROGRAM_ID,
+    },
+    SolRpcClient,
+};
+use assert_matches::assert_matches;
+use sol_rpc_types::{MultiRpcResult, PrioritizationFee, RpcSource, SupportedRpcProviderId};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_message::{
+    compiled_instruction::CompiledInstruction,
+    v0::{self, MessageAddressTableLookup},
+    Message, MessageHeader, VersionedMessage,
+};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::{compute_budget, system_program};
+
+const ALICE: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+const BOB: Pubkey = pubkey!("6pPYj3CpgfR6wwBkenmUpSd7rNqmeq2hccUVJq4au5eS");
+const MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const OTHER_PROGRAM: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+mod message_cost_parameters {
+    use super::*;
+
+    #[test]
+    fn should_use_default_compute_unit_limit() {
+        let message = message(&[memo(), memo()]);
+
+        let parameters = MessageCostParameters::from_message(&message);
+
+        assert_eq!(
+            parameters,
+            Ok(MessageCostParameters {
+                compute_unit_limit: 400_000,
+                compute_unit_price: None,
+                writable_accounts: vec![ALICE],
+                created_account_sizes: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn should_use_compute_budget_instructions() {
+        let message = message(&[
+            set_compute_unit_limit(50_000),
+            set_compute_unit_price(1_000),
+            memo(),
+        ]);
+
+        let parameters = MessageCostParameters::from_message(&message).unwrap();
+
+        assert_eq!(parameters.compute_unit_limit, 50_000);
+        assert_eq!(parameters.compute_unit_price, Some(1_000));
+    }
+
+    #[test]
+    fn should_cap_compute_unit_limit() {
+        for message in [
+            message(&vec![memo(); 8]),
+            message(&[set_compute_unit_limit(2_000_000), memo()]),
+        ] {
+            let parameters = MessageCostParameters::from_message(&message).unwrap();
+
+            assert_eq!(parameters.compute_unit_limit, 1_400_000);
+        }
+    }
+
+    #[test]
+    fn should_find_created_accounts() {
+        let message = message(&[
+            create_account(BOB, 200),
+            create_associated_token_account_idempotent(TOKEN_PROGRAM_ID),
+            create_associated_token_account_idempotent(TOKEN_2022_PROGRAM_ID),
+            memo(),
+        ]);
+
+        let parameters = MessageCostParameters::from_message(&message).unwrap();
+
+        assert_eq!(parameters.created_account_sizes, vec![200, 165, 170]);
+        assert_eq!(parameters.compute_unit_limit, 800_000);
+    }
+
+    #[test]
+    fn should_find_writable_accounts() {
+        let instruction = Instruction::new_with_bytes(
+            OTHER_PROGRAM,
+            &[],
+            vec![
+                AccountMeta::new(ALICE, true),
+                AccountMeta::new(BOB, false),
+                AccountMeta::new_readonly(MINT, false),
+            ],
+        );
+
+        let parameters = MessageCostParameters::from_message(&message(&[instruction])).unwrap();
+
+        assert_eq!(parameters.writable_accounts, vec![ALICE, BOB]);
+    }
+
+    #[test]
+    fn should_decode_instructions_referencing_loaded_addresses() {
+        let message = VersionedMessage::V0(v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![ALICE, ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID],
+            recent_blockhash: Default::default(),
+            // The associated token account, the mint and the token program are loaded from an
+            // address lookup table.
+            instructions: vec![CompiledInstruction::new_from_raw_parts(
+                1,
+                vec![1],
+                vec![0, 2, 0, 3, 1, 4],
+            )],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: BOB,
+                writable_indexes: vec![0],
+                readonly_indexes: vec![1, 2],
+            }],
+        });
+
+        let parameters = MessageCostParameters::from_message(&message).unwrap();
+
+        assert_eq!(parameters.created_account_sizes, vec![165]);
+        assert_eq!(parameters.writable_accounts, vec![ALICE]);
+    }
+
+    #[test]
+    fn should_fail_with_invalid_instruction() {
+        let message = message(&[Instruction::new_with_bytes(
+            compute_budget::ID,
+            &[42],
+            vec![],
+        )]);
+
+        assert_matches!(
+            MessageCostParameters::from_message(&message),
+            Err(InstructionDecodingError::InvalidInstructionData { .. })
+        );
+    }
+}
+
+mod prioritization_fee {
+    use super::*;
+
+    #[test]
+    fn should_round_prioritization_fee_up() {
+        assert_eq!(prioritization_fee(0, 200_000), 0);
+        assert_eq!(prioritization_fee(1, 1), 1);
+        assert_eq!(prioritization_fee(20_000, 200_000), 4_000);
+        assert_eq!(prioritization_fee(20_001, 200_000), 4_001);
+        assert_eq!(prioritization_fee(u64::MAX, 1_400_000), u64::MAX);
+    }
+
+    #[test]
+    fn should_compute_median_prioritization_fee() {
+        assert_eq!(median_prioritization_fee(&[]), 0);
+        assert_eq!(median_prioritization_fee(&fees(&[3, 1, 2])), 2);
+        assert_eq!(median_prioritization_fee(&fees(&[0, 0, 0, 10, 20])), 0);
+        assert_eq!(median_prioritization_fee(&fees(&[4, 1, 3, 2])), 3);
+    }
+
+    fn fees(fees: &[u64]) -> Vec<PrioritizationFee> {
+        fees.iter()
+            .enumerate()
+            .map(|(slot, fee)| PrioritizationFee {
+                slot: slot as u64,
+                prioritization_fee: *fee,
+            })
+            .collect()
+    }
+}
+
+mod estimate_transaction_cost {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_estimate_cost_with_compute_unit_price() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_responses()
+            .add_stub_response(MultiRpcResult::Consistent(Ok(Some(10_000_u64))))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(890_880_u64)))
+            .build();
+        let message = message(&[
+            set_compute_unit_limit(100_000),
+            set_compute_unit_price(50_000),
+            create_account(BOB, 0),
+            create_account(MINT, 0),
+        ]);
+
+        let estimate = client.estimate_transaction_cost(message).await;
+
+        assert_eq!(
+            estimate,
+            Ok(FeeEstimate {
+                base_fee: 5_000,
+                priority_fee: 5_000,
+                rent: 1_781_760,
+                total: 1_791_760,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_estimate_cost_with_recent_prioritization_fees() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_responses()
+            .add_stub_response(MultiRpcResult::Consistent(Ok(Some(5_000_u64))))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(vec![
+                PrioritizationFee {
+                    slot: 1,
+                    prioritization_fee: 100,
+                },
+                PrioritizationFee {
+                    slot: 2,
+                    prioritization_fee: 300,
+                },
+                PrioritizationFee {
+                    slot: 3,
+                    prioritization_fee: 200,
+                },
+            ])))
+            .build();
+
+        let estimate = client
+            .estimate_transaction_cost(message(&[memo(), memo()]))
+            .await;
+
+        assert_eq!(estimate, Ok(FeeEstimate::new(5_000, 80, 0)));
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_fee_is_unavailable() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_response(MultiRpcResult::Consistent(Ok(None::<u64>)))
+            .build();
+
+        let estimate = client.estimate_transaction_cost(message(&[memo()])).await;
+
+        assert_eq!(estimate, Err(EstimateTransactionCostError::FeeUnavailable));
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_inconsistent_minimum_balance_for_rent_exemption() {
+        let results = vec![
+            (
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                Ok(890_880_u64),
+            ),
+            (
+                RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet),
+                Ok(890_881_u64),
+            ),
+        ];
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_responses()
+            .add_stub_response(MultiRpcResult::Consistent(Ok(Some(5_000_u64))))
+            .add_stub_response(MultiRpcResult::Inconsistent(results.clone()))
+            .build();
+        let message = message(&[set_compute_unit_price(0), create_account(BOB, 0)]);
+
+        let estimate = client.estimate_transaction_cost(message).await;
+
+        assert_eq!(
+            estimate,
+            Err(
+                EstimateTransactionCostError::GetMinimumBalanceForRentExemptionConsensusError(
+                    results
+                )
+            )
+        );
+    }
+}
+
+fn message(instructions: &[Instruction]) -> VersionedMessage {
+    VersionedMessage::Legacy(Message::new(instructions, Some(&ALICE)))
+}
+
+fn memo() -> Instruction {
+    Instruction::new_with_bytes(OTHER_PROGRAM, b"memo", vec![AccountMeta::new(ALICE, true)])
+}
+
+fn set_compute_unit_limit(limit: u32) -> Instruction {
+    Instruction::new_with_bytes(
+        compute_budget::ID,
+        &[vec![2], limit.to_le_bytes().to_vec()].concat(),
+        vec![],
+    )
+}
+
+fn set_compute_unit_price(price: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        compute_budget::ID,
+        &[vec![3], price.to_le_bytes().to_vec()].concat(),
+        vec![],
+    )
+}
+
+fn create_account(new_account: Pubkey, space: u64) -> Instruction {
+    let data = [
+        vec![0, 0, 0, 0],
+        1_000_000_u64.to_le_bytes().to_vec(),
+        space.to_le_bytes().to_vec(),
+        OTHER_PROGRAM.to_bytes().to_vec(),
+    ]
+    .concat();
+    Instruction::new_with_bytes(
+        system_program::ID,
+        &data,
+        vec![
+            AccountMeta::new(ALICE, true),
+            AccountMeta::new(new_account, true),
+        ],
+    )
+}
+
+fn create_associated_token_account_idempotent(token_program: Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID,
+        &[1],
+        vec![
+            AccountMeta::new(ALICE, true),
+            AccountMeta::new(BOB, false),
+            AccountMeta::new_readonly(ALICE, false),
+            AccountMeta::new_readonly(MINT, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+    )
+}
@@ -0,0 +1,336 @@
+//! [`Runtime`] returning scripted responses, to unit test client logic making several calls to
+//! the SOL RPC canister, e.g. retries, pagination or confirmation loops.
+
+use async_trait::async_trait;
+use candid::{
+    utils::{ArgumentDecoder, ArgumentEncoder},
+    CandidType, Principal,
+};
+use ic_canister_runtime::{IcError, Runtime};
+use ic_cdk::call::RejectCode;
+use serde::de::DeserializeOwned;
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// [`Runtime`] answering each call with the response of the first pending [`MockCall`] matching
+/// the called method and its arguments, in the order in which the mock calls were added.
+///
+/// A mock call is only used once, unless it is marked as [`MockCall::repeatedly`], so that a
+/// sequence of calls to the same method can be scripted. A call matching no pending mock call
+/// panics, since this indicates a bug in the test.
+///
+/// The runtime is cheaply cloneable and all clones share the same script, so that a clone can be
+/// kept to inspect the calls that were made with [`MockRuntime::calls`].
+///
+/// # Examples
+///
+/// ```rust
+/// use ic_cdk::call::RejectCode;
+/// use sol_rpc_client::{fixtures::{MockCall, MockRuntime}, SolRpcClient, SOL_RPC_CANISTER};
+/// use sol_rpc_types::MultiRpcResult;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let runtime = MockRuntime::new()
+///     .with_call(MockCall::new("getSlot").reject(RejectCode::SysTransient, "Subnet is busy"))
+///     .with_call(
+///         MockCall::new("getSlot")
+///             .respond_with(MultiRpcResult::Consistent(Ok(332_577_897_u64))),
+///     );
+/// let client = SolRpcClient::builder(runtime.clone(), SOL_RPC_CANISTER).build();
+///
+/// assert!(client.get_slot().try_send().await.is_err());
+/// assert_eq!(
+///     client.get_slot().send().await,
+///     MultiRpcResult::Consistent(Ok(332_577_897_u64))
+/// );
+/// assert_eq!(runtime.calls().len(), 2);
+/// assert!(runtime.is_done());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockRuntime {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    calls: Vec<MockCall>,
+    recorded_calls: Vec<RecordedCall>,
+}
+
+impl MockRuntime {
+    /// Creates a [`MockRuntime`] without any scripted call.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given call to the script.
+    pub fn with_call(self, call: MockCall) -> Self {
+        self.add_call(call);
+        self
+    }
+
+    /// Adds the given call to the script of this runtime and of all its clones.
+    pub fn add_call(&self, call: MockCall) {
+        self.state.lock().unwrap().calls.push(call);
+    }
+
+    /// Returns the calls made so far, in the order in which they were made.
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.state.lock().unwrap().recorded_calls.clone()
+    }
+
+    /// Returns `true` if all scripted calls that are not repeated were made.
+    pub fn is_done(&self) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .iter()
+            .all(|call| call.repeatedly)
+    }
+
+    async fn call<In, Out>(&self, method: &str, args: In, cycles: u128) -> Result<Out, IcError>
+    where
+        In: ArgumentEncoder + Send,
+        Out: CandidType + DeserializeOwned,
+    {
+        let args = candid::encode_args(args).expect("Failed to encode arguments");
+        let (response, delay) = {
+            let mut state = self.state.lock().unwrap();
+            state.recorded_calls.push(RecordedCall {
+                method: method.to_string(),
+                args: args.clone(),
+                cycles,
+            });
+            let index = state
+                .calls
+                .iter()
+                .position(|call| call.matches(method, &args))
+                .unwrap_or_else(|| panic!("No mock call matching call to `{method}`"));
+            let call = if state.calls[index].repeatedly {
+                state.calls[index].clone()
+            } else {
+                state.calls.remove(index)
+            };
+            (call.response, call.delay)
+        };
+        if let Some(delay) = delay {
+            Delay::new(delay).await;
+        }
+        match response {
+            MockResponse::Reply(bytes) => Ok(candid::decode_one(&bytes).unwrap_or_else(|e| {
+                panic!("Failed to decode mock response to call to `{method}`: {e}")
+            })),
+            MockResponse::Error(error) => Err(error),
+            MockResponse::Missing => panic!("No response for mock call to `{method}`"),
+        }
+    }
+}
+
+impl Debug for MockRuntime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("MockRuntime")
+            .field("calls", &state.calls)
+            .field("recorded_calls", &state.recorded_calls)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Runtime for MockRuntime {
+    async fn update_call<In, Out>(
+        &self,
+        _id: Principal,
+        method: &str,
+        args: In,
+        cycles: u128,
+    ) -> Result<Out, IcError>
+    where
+        In: ArgumentEncoder + Send,
+        Out: CandidType + DeserializeOwned,
+    {
+        self.call(method, args, cycles).await
+    }
+
+    async fn query_call<In, Out>(
+        &self,
+        _id: Principal,
+        method: &str,
+        args: In,
+    ) -> Result<Out, IcError>
+    where
+        In: ArgumentEncoder + Send,
+        Out: CandidType + DeserializeOwned,
+    {
+        self.call(method, args, 0).await
+    }
+}
+
+type ArgsMatcher = Arc<dyn Fn(&[u8]) -> bool + Send + Sync>;
+
+/// A scripted call of a [`MockRuntime`].
+#[derive(Clone)]
+pub struct MockCall {
+    method: String,
+    args_matcher: Option<ArgsMatcher>,
+    response: MockResponse,
+    delay: Option<Duration>,
+    repeatedly: bool,
+}
+
+#[derive(Clone, Debug)]
+enum MockResponse {
+    Reply(Vec<u8>),
+    Error(IcError),
+    Missing,
+}
+
+impl MockCall {
+    /// Creates a [`MockCall`] matching a call to the given method with any arguments.
+    pub fn new(method: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            args_matcher: None,
+            response: MockResponse::Missing,
+            delay: None,
+            repeatedly: false,
+        }
+    }
+
+    /// Only match calls with the given arguments, as encoded in Candid.
+    pub fn with_args<In: ArgumentEncoder>(mut self, args: In) -> Self {
+        let expected = candid::encode_args(args).expect("Failed to encode arguments");
+        self.args_matcher = Some(Arc::new(move |args| args == expected.as_slice()));
+        self
+    }
+
+    /// Only match calls whose arguments, once decoded, satisfy the given predicate.
+    pub fn with_args_matching<Args, F>(mut self, predicate: F) -> Self
+    where
+        Args: for<'a> ArgumentDecoder<'a>,
+        F: Fn(Args) -> bool + Send + Sync + 'static,
+    {
+        self.args_matcher = Some(Arc::new(move |args| {
+            candid::decode_args::<Args>(args).is_ok_and(&predicate)
+        }));
+        self
+    }
+
+    /// Reply to the call with the given value.
+    pub fn respond_with<Out: CandidType>(mut self, response: Out) -> Self {
+        self.response = MockResponse::Reply(
+            candid::encode_one(response).expect("Failed to encode mock response"),
+        );
+        self
+    }
+
+    /// Fail the call with the given error.
+    pub fn fail_with(mut self, error: IcError) -> Self {
+        self.response = MockResponse::Error(error);
+        self
+    }
+
+    /// Reject the call with the given reject code and message.
+    pub fn reject(self, code: RejectCode, message: impl Into<String>) -> Self {
+        self.fail_with(IcError::CallRejected {
+            code,
+            message: message.into(),
+        })
+    }
+
+    /// Wait for the given duration before responding, e.g. to control the order in which
+    /// concurrent calls complete.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Use this mock call for all matching calls instead of only the first one.
+    pub fn repeatedly(mut self) -> Self {
+        self.repeatedly = true;
+        self
+    }
+
+    fn matches(&self, method: &str, args: &[u8]) -> bool {
+        self.method == method
+            && self
+                .args_matcher
+                .as_ref()
+                .is_none_or(|matcher| matcher(args))
+    }
+}
+
+impl Debug for MockCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockCall")
+            .field("method", &self.method)
+            .field("matches_args", &self.args_matcher.is_some())
+            .field("response", &self.response)
+            .field("delay", &self.delay)
+            .field("repeatedly", &self.repeatedly)
+            .finish()
+    }
+}
+
+/// A call made to a [`MockRuntime`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedCall {
+    /// The called method.
+    pub method: String,
+    /// The Candid-encoded arguments of the call.
+    pub args: Vec<u8>,
+    /// The cycles attached to the call, which are always 0 for query calls.
+    pub cycles: u128,
+}
+
+impl RecordedCall {
+    /// Decodes the arguments of the call.
+    pub fn decode_args<Args: for<'a> ArgumentDecoder<'a>>(&self) -> candid::Result<Args> {
+        candid::decode_args(&self.args)
+    }
+}
+
+/// Future completing after the given duration, independently of the async runtime.
+struct Delay {
+    deadline: Instant,
+    timer_started: bool,
+}
+
+impl Delay {
+    fn new(duration: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + duration,
+            timer_started: false,
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        if !self.timer_started {
+            self.timer_started = true;
+            let deadline = self.deadline;
+            let waker = cx.waker().clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                waker.wake();
+            });
+        }
+        Poll::Pending
+    }
+}
@@ -1,11 +1,29 @@
-//! Simple types to create basic unit tests for the [`crate::SolRpcClient`].
+//! Simple types to create basic unit tests for the [`crate::SolRpcClient`], as well as realistic
+//! responses for every endpoint of the SOL RPC canister, e.g. [`confirmed_block`], which can be
+//! wrapped in a [`MultiRpcResult`] with [`consistent`] or [`inconsistent`].
 //!
 //! Types and methods for this module are only available for non-canister architecture (non `wasm32`).
 
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use mock::{MockCall, MockRuntime, RecordedCall};
+
 use crate::ClientBuilder;
 use candid::CandidType;
 use ic_canister_runtime::{IcError, StubRuntime};
-use sol_rpc_types::{AccountData, AccountEncoding, AccountInfo};
+use sol_rpc_types::{
+    AccountData, AccountEncoding, AccountInfo, ClusterNode, ConfirmedBlock,
+    ConfirmedTransactionStatusWithSignature, EncodedConfirmedTransactionWithStatusMeta,
+    EncodedTransaction, EncodedTransactionWithStatusMeta, EpochInfo, EpochSchedule,
+    InstructionError, Lamport, LoadedAddresses, MultiRpcResult, PerformanceSample,
+    PrioritizationFee, Pubkey, Reward, RewardType, RpcResult, RpcSource, Signature, Slot,
+    SnapshotSlotInfo, SolanaVersion, Supply, Timestamp, TokenAmount, TransactionBinaryEncoding,
+    TransactionConfirmationStatus, TransactionError, TransactionStatus, TransactionStatusMeta,
+    TransactionVersion,
+};
+use std::{fmt::Debug, str::FromStr};
 
 impl<R> ClientBuilder<R> {
     /// Set the runtime to a [`StubRuntime`].
@@ -20,6 +38,11 @@ impl<R> ClientBuilder<R> {
     ) -> ClientBuilder<StubRuntime> {
         self.with_stub_responses().add_stub_response(stub_response)
     }
+
+    /// Set the runtime to the given [`MockRuntime`].
+    pub fn with_mock_runtime(self, runtime: MockRuntime) -> ClientBuilder<MockRuntime> {
+        self.with_runtime(|_runtime| runtime)
+    }
 }
 
 impl ClientBuilder<StubRuntime> {
@@ -63,3 +86,321 @@ pub fn nonce_account() -> AccountInfo {
         space: 80,
     }
 }
+
+/// Stake account delegating 1 SOL to a validator.
+pub fn stake_account() -> AccountInfo {
+    AccountInfo {
+        lamports: 1_002_282_880,
+        data: AccountData::Binary("AgAAAIDVIgAAAAAA1EspXEHdQ88EHYhxgyA1f9NG6MweJS0HQwNp6/5e6kvUSylcQd1DzwQdiHGDIDV/00bozB4lLQdDA2nr/l7qSwAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAFZuOgMLD3x4KGr/BV+AnBbLSkzMlTcNhWHq/piAUnmbAMqaOwAAAAAgAwAAAAAAAP//////////AAAAAAAA0D9A4gEAAAAAAAAAAAA=".to_string(), AccountEncoding::Base64),
+        owner: "Stake11111111111111111111111111111111111111".to_string(),
+        executable: false,
+        rent_epoch: 18_446_744_073_709_551_615,
+        space: 200,
+    }
+}
+
+/// Address lookup table account containing the system program, the token program and the USDC
+/// mint addresses.
+pub fn address_lookup_table_account() -> AccountInfo {
+    AccountInfo {
+        lamports: 1_948_800,
+        data: AccountData::Binary("AQAAAP//////////QEiQFAAAAAAAAdRLKVxB3UPPBB2IcYMgNX/TRujMHiUtB0MDaev+XupLAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAbd9uHXZaGT2cvhRs7reawctIXtX1s3kTqM9YV+/wCpxvp6877brTo9ZfNqq8l0MbG75MLS9uDkfKYCA0UvXWE=".to_string(), AccountEncoding::Base64),
+        owner: "AddressLookupTab1e1111111111111111111111111".to_string(),
+        executable: false,
+        rent_epoch: 18_446_744_073_709_551_615,
+        space: 152,
+    }
+}
+
+/// Wraps the given value in a [`MultiRpcResult::Consistent`], as returned by the SOL RPC canister
+/// when all providers agree.
+pub fn consistent<T>(value: T) -> MultiRpcResult<T> {
+    MultiRpcResult::Consistent(Ok(value))
+}
+
+/// Wraps the given results in a [`MultiRpcResult::Inconsistent`], as returned by the SOL RPC
+/// canister when the providers disagree.
+pub fn inconsistent<T>(
+    results: impl IntoIterator<Item = (RpcSource, RpcResult<T>)>,
+) -> MultiRpcResult<T> {
+    MultiRpcResult::Inconsistent(results.into_iter().collect())
+}
+
+// The fixtures below are consistent with each other: they describe the same Solana Mainnet
+// transaction, included in the block at slot `SLOT` and paid by `FEE_PAYER`.
+
+/// Slot of the block returned by [`confirmed_block`], which includes [`confirmed_transaction`].
+pub const SLOT: Slot = 369_139_986;
+
+/// Fee payer of [`confirmed_transaction`].
+pub const FEE_PAYER: &str = "px1rbjiEWwwcq1epXSsTMJERyQy7h4vg4VopqFz2HwH";
+
+const BLOCK_TIME: Timestamp = 1_758_792_475;
+const BLOCK_HEIGHT: u64 = 347_350_113;
+
+/// Signature of [`confirmed_transaction`].
+pub fn transaction_signature() -> Signature {
+    parse("KbYRTmvx4uz3xuRRGNdKyt1jBngz2TjLp9nPebT4h3LQzAG7BfYrd5pSU2xDT7dVg3EXXbZugH8XbKwiGU7Jqzw")
+}
+
+/// Block at slot [`SLOT`] with its signatures and rewards, as returned by `getBlock` with
+/// `transactionDetails` set to `signatures`.
+pub fn confirmed_block() -> ConfirmedBlock {
+    ConfirmedBlock {
+        previous_blockhash: parse("Asr5aDr2PcMc8p2KCxJjREiHYgeHXZvTNDVUMBtza1uh"),
+        blockhash: parse("8QeCusqSTKeC23NwjTKRBDcPuEfVLtszkxbpL6mXQEp4"),
+        parent_slot: SLOT - 1,
+        block_time: Some(BLOCK_TIME),
+        block_height: Some(BLOCK_HEIGHT),
+        signatures: Some(vec![
+            parse("3jPA8CnZb9sfs4zVAypa9KB7VAGwrTdXB6mg9H1H9XpATN6Y8iek4Y21Nb9LjbrpYACbF9USV8RBWvXFFhVoQUAs"),
+            transaction_signature(),
+            parse("5iByUT1gTNXDY24hRx25YmQeebvUMD6jsNpGcu2jh1yjKmYwdo5GtRrYozyhdtdcn8SurwHq6EMp4YTpHgdansjc"),
+        ]),
+        rewards: Some(vec![Reward {
+            pubkey: parse("DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy"),
+            lamports: 7_500,
+            post_balance: 499_999_847_501,
+            reward_type: Some(RewardType::Fee),
+            commission: None,
+        }]),
+        num_reward_partitions: None,
+        transactions: None,
+    }
+}
+
+/// Transaction with signature [`transaction_signature`] and its metadata, as returned by
+/// `getTransaction` with the `base64` encoding.
+pub fn confirmed_transaction() -> EncodedConfirmedTransactionWithStatusMeta {
+    EncodedConfirmedTransactionWithStatusMeta {
+        slot: SLOT,
+        block_time: Some(BLOCK_TIME),
+        transaction: EncodedTransactionWithStatusMeta {
+            transaction: EncodedTransaction::Binary(
+                "ARAJPXmph5xbnfO74gv8tBIwTA0yw0BuRZvqrr113O9BTj0T4kXejUz3jh1RCasjsZkr2do/ZjMIOg56TTvRlQgBAAMGDEiA3o3u6XvTb57cHKZkhrHuNhISrOgMMafRPe48Q4QgJhAewgMolkoyq6sTbFQFuR86447k9ky2veh5uGg40kK5Pth9DxkikievxiovoyrY6lRfLhWKUZINPu2s+AlMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAADBkZv5SEXMv/srbpyw5vnvIzlu8X3EmssQ5s6QAAAAMGkhusDr3enQhfGliLPnjUOYbtCSz9fET+Twnd+37hJkr+3Zt+dBsrfJ0eCM1bDr9NITRuvFbzpE4a9q1ZEXggDBAAFAqQBAAAFAgACqAELVaozzA/wZnC9ckuJIt1EqfSq6QAzzGYyZzOAmQEAAHF0Ee4i3YhEjwv/FswzZpkBBxEiM0RVZneImaq7zN3u/wCqVTPMZpkSNFZ4mrze8BI0VniavN7wEjRWeJq83vASNFZ4mrze8BI0VniavN7wEjRWeJq83vASNFZ4mrze8BI0VniavN7wEjRWeJq83vASNFZ4mrze8AxIgN6N7ul702+e3BymZIYDAgABDAIAAADoAwAAAAAAAA==".to_string(),
+                TransactionBinaryEncoding::Base64,
+            ),
+            meta: Some(TransactionStatusMeta {
+                status: Ok(()),
+                fee: 5_000,
+                pre_balances: vec![463_360_320_850, 6_608_068, 2_060_160, 1, 1, 1_141_440],
+                post_balances: vec![463_360_314_850, 6_609_068, 2_060_160, 1, 1, 1_141_440],
+                inner_instructions: Some(vec![]),
+                log_messages: Some(vec![
+                    "Program ComputeBudget111111111111111111111111111111 invoke [1]".to_string(),
+                    "Program ComputeBudget111111111111111111111111111111 success".to_string(),
+                    "Program E2uCGJ4TtYyKPGaK57UMfbs9sgaumwDEZF1aAY6fF3mS invoke [1]".to_string(),
+                    "Program E2uCGJ4TtYyKPGaK57UMfbs9sgaumwDEZF1aAY6fF3mS consumed 110 of 270 compute units".to_string(),
+                    "Program E2uCGJ4TtYyKPGaK57UMfbs9sgaumwDEZF1aAY6fF3mS success".to_string(),
+                    "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+                    "Program 11111111111111111111111111111111 success".to_string(),
+                ]),
+                pre_token_balances: Some(vec![]),
+                post_token_balances: Some(vec![]),
+                rewards: Some(vec![]),
+                loaded_addresses: Some(LoadedAddresses {
+                    writable: vec![],
+                    readonly: vec![],
+                }),
+                return_data: None,
+                compute_units_consumed: Some(410),
+                cost_units: Some(2_084),
+            }),
+            version: Some(TransactionVersion::Legacy),
+        },
+    }
+}
+
+/// Statuses of [`transaction_signature`] and of an unknown signature, as returned by
+/// `getSignatureStatuses`.
+pub fn signature_statuses() -> Vec<Option<TransactionStatus>> {
+    vec![
+        Some(TransactionStatus {
+            slot: SLOT,
+            status: Ok(()),
+            err: None,
+            confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+        }),
+        None,
+    ]
+}
+
+/// Most recent signatures involving [`FEE_PAYER`], as returned by `getSignaturesForAddress`.
+pub fn signatures_for_address() -> Vec<ConfirmedTransactionStatusWithSignature> {
+    vec![
+        ConfirmedTransactionStatusWithSignature {
+            signature: transaction_signature(),
+            slot: SLOT,
+            err: None,
+            memo: None,
+            block_time: Some(BLOCK_TIME),
+            confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+        },
+        ConfirmedTransactionStatusWithSignature {
+            signature: parse("4V1j8jZvXjcUdRoWQBRzxFVigfr61bJdHGsCFAkTm5h4z28FkrDczuTpcvwTRamiwiGm7E77EB5DKRBwG1mUEC8f"),
+            slot: SLOT - 17,
+            err: Some(TransactionError::InstructionError(
+                3,
+                InstructionError::Custom(6_001),
+            )),
+            memo: None,
+            block_time: Some(BLOCK_TIME - 7),
+            confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+        },
+    ]
+}
+
+/// Prioritization fees of the 5 slots up to [`SLOT`], as returned by
+/// `getRecentPrioritizationFees`.
+pub fn prioritization_fees() -> Vec<PrioritizationFee> {
+    [0, 203_228, 110_788, 395_962, 0]
+        .into_iter()
+        .zip(SLOT - 4..=SLOT)
+        .map(|(prioritization_fee, slot)| PrioritizationFee {
+            slot,
+            prioritization_fee,
+        })
+        .collect()
+}
+
+/// Balance in lamports of [`FEE_PAYER`] after [`confirmed_transaction`], as returned by
+/// `getBalance`.
+pub fn balance() -> Lamport {
+    463_360_314_850
+}
+
+/// Block height of [`confirmed_block`], as returned by `getBlockHeight`.
+pub fn block_height() -> u64 {
+    BLOCK_HEIGHT
+}
+
+/// Confirmed blocks up to [`SLOT`], where slot `SLOT - 2` was skipped, as returned by
+/// `getBlocks`.
+pub fn blocks() -> Vec<Slot> {
+    vec![SLOT - 4, SLOT - 3, SLOT - 1, SLOT]
+}
+
+/// Nodes of the cluster, as returned by `getClusterNodes`.
+pub fn cluster_nodes() -> Vec<ClusterNode> {
+    vec![ClusterNode {
+        pubkey: parse("9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ"),
+        gossip: Some("10.239.6.48:8001".to_string()),
+        version: Some("2.3.6".to_string()),
+    }]
+}
+
+/// Epoch information at [`SLOT`], as returned by `getEpochInfo`.
+pub fn epoch_info() -> EpochInfo {
+    let schedule = epoch_schedule();
+    EpochInfo {
+        epoch: SLOT / schedule.slots_per_epoch,
+        slot_index: SLOT % schedule.slots_per_epoch,
+        slots_in_epoch: schedule.slots_per_epoch,
+        absolute_slot: SLOT,
+        block_height: BLOCK_HEIGHT,
+        transaction_count: None,
+    }
+}
+
+/// Epoch schedule of Solana Mainnet, as returned by `getEpochSchedule`.
+pub fn epoch_schedule() -> EpochSchedule {
+    EpochSchedule {
+        slots_per_epoch: 432_000,
+        leader_schedule_slot_offset: 432_000,
+        warmup: false,
+        first_normal_epoch: 0,
+        first_normal_slot: 0,
+    }
+}
+
+/// Fee in lamports of a message with a single signature, as returned by `getFeeForMessage`.
+pub fn fee_for_message() -> Option<Lamport> {
+    Some(5_000)
+}
+
+/// Highest snapshot slots before [`SLOT`], as returned by `getHighestSnapshotSlot`.
+pub fn highest_snapshot_slot() -> SnapshotSlotInfo {
+    SnapshotSlotInfo {
+        full: 369_100_000,
+        incremental: Some(369_139_500),
+    }
+}
+
+/// Minimum balance in lamports for a token account to be rent exempt, as returned by
+/// `getMinimumBalanceForRentExemption`.
+pub fn minimum_balance_for_rent_exemption() -> Lamport {
+    2_039_280
+}
+
+/// Performance samples of the last two minutes, as returned by `getRecentPerformanceSamples`.
+pub fn performance_samples() -> Vec<PerformanceSample> {
+    vec![
+        PerformanceSample {
+            slot: SLOT,
+            num_transactions: 243_718,
+            num_non_vote_transactions: Some(58_213),
+            num_slots: 152,
+            sample_period_secs: 60,
+        },
+        PerformanceSample {
+            slot: SLOT - 152,
+            num_transactions: 239_405,
+            num_non_vote_transactions: Some(61_094),
+            num_slots: 149,
+            sample_period_secs: 60,
+        },
+    ]
+}
+
+/// Leaders of the slots starting at [`SLOT`], as returned by `getSlotLeaders`.
+pub fn slot_leaders() -> Vec<Pubkey> {
+    vec![
+        parse("ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n"),
+        parse("Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM"),
+    ]
+}
+
+/// Supply of Solana Mainnet, as returned by `getSupply`.
+pub fn supply() -> Supply {
+    Supply {
+        total: 611_920_474_178_937_580,
+        circulating: 535_301_926_316_133_283,
+        non_circulating: 76_618_547_862_804_297,
+        non_circulating_accounts: vec![
+            parse("FEy8pTbP5fEoqMV1GdTz83byuA8EKByqYat1PKDgVAq5"),
+            parse("9huDUZfxoJ7wGMTffUE7vh1xePqef7gyrLJu9NApncqA"),
+        ],
+    }
+}
+
+/// Balance of a USDC token account holding 1,234.56 USDC, as returned by
+/// `getTokenAccountBalance`.
+pub fn token_amount() -> TokenAmount {
+    TokenAmount {
+        ui_amount: Some(1_234.56),
+        decimals: 6,
+        amount: "1234560000".to_string(),
+        ui_amount_string: "1234.56".to_string(),
+    }
+}
+
+/// Number of transactions processed by Solana Mainnet up to [`SLOT`], as returned by
+/// `getTransactionCount`.
+pub fn transaction_count() -> u64 {
+    418_427_651_021
+}
+
+/// Version of the Solana node, as returned by `getVersion`.
+pub fn version() -> SolanaVersion {
+    SolanaVersion {
+        solana_core: "2.3.6".to_string(),
+        feature_set: Some(3_640_012_085),
+    }
+}
+
+fn parse<T: FromStr>(value: &str) -> T
+where
+    T::Err: Debug,
+{
+    value.parse().expect("BUG: invalid fixture")
+}
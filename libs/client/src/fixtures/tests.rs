@@ -0,0 +1,297 @@
+use crate::{
+    fixtures::{
+        self, balance, block_height, confirmed_block, confirmed_transaction, consistent,
+        epoch_info, inconsistent, prioritization_fees, signature_statuses, signatures_for_address,
+        transaction_signature, usdc_account, MockCall, MockRuntime,
+    },
+    SolRpcClient,
+};
+use ic_canister_runtime::IcError;
+use ic_cdk::call::RejectCode;
+use sol_rpc_types::{
+    ConfirmedTransactionWithStatusMeta, GetTokenAccountsByOwnerLimit,
+    GetTokenAccountsByOwnerParams, KeyedAccount, MultiRpcResult, RpcConfig, RpcSource, RpcSources,
+    SupportedRpcProviderId, TokenAccountsFilter,
+};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock,
+};
+use std::{str::FromStr, time::Duration};
+
+const OWNER: Pubkey = pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+const MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const SLOT: u64 = 332_577_897;
+
+type GetTokenAccountsByOwnerArgs = (RpcSources, Option<RpcConfig>, GetTokenAccountsByOwnerParams);
+
+#[tokio::test]
+async fn should_respond_in_order() {
+    let runtime = MockRuntime::new()
+        .with_call(MockCall::new("getSlot").respond_with(MultiRpcResult::Consistent(Ok(SLOT))))
+        .with_call(MockCall::new("getSlot").respond_with(MultiRpcResult::Consistent(Ok(SLOT + 1))));
+    let client = client(&runtime);
+
+    assert_eq!(
+        client.get_slot().send().await,
+        MultiRpcResult::Consistent(Ok(SLOT))
+    );
+    assert_eq!(
+        client.get_slot().send().await,
+        MultiRpcResult::Consistent(Ok(SLOT + 1))
+    );
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_repeat_response() {
+    let runtime = MockRuntime::new().with_call(
+        MockCall::new("getSlot")
+            .respond_with(MultiRpcResult::Consistent(Ok(SLOT)))
+            .repeatedly(),
+    );
+    let client = client(&runtime);
+
+    for _ in 0..3 {
+        assert_eq!(
+            client.get_slot().send().await,
+            MultiRpcResult::Consistent(Ok(SLOT))
+        );
+    }
+    assert_eq!(runtime.calls().len(), 3);
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_inject_errors() {
+    let runtime = MockRuntime::new()
+        .with_call(MockCall::new("getSlot").reject(RejectCode::SysTransient, "Subnet is busy"))
+        .with_call(MockCall::new("getSlot").fail_with(IcError::CallPerformFailed))
+        .with_call(MockCall::new("getSlot").respond_with(MultiRpcResult::Consistent(Ok(SLOT))));
+    let client = client(&runtime);
+
+    assert_eq!(
+        client.get_slot().try_send().await,
+        Err(IcError::CallRejected {
+            code: RejectCode::SysTransient,
+            message: "Subnet is busy".to_string(),
+        })
+    );
+    assert_eq!(
+        client.get_slot().try_send().await,
+        Err(IcError::CallPerformFailed)
+    );
+    assert_eq!(
+        client.get_slot().try_send().await,
+        Ok(MultiRpcResult::Consistent(Ok(SLOT)))
+    );
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_delay_response() {
+    let delay = Duration::from_millis(50);
+    let runtime = MockRuntime::new().with_call(
+        MockCall::new("getSlot")
+            .respond_with(MultiRpcResult::Consistent(Ok(SLOT)))
+            .with_delay(delay),
+    );
+    let client = client(&runtime);
+
+    let start = std::time::Instant::now();
+    assert_eq!(
+        client.get_slot().send().await,
+        MultiRpcResult::Consistent(Ok(SLOT))
+    );
+    assert!(start.elapsed() >= delay);
+}
+
+#[tokio::test]
+async fn should_record_calls() {
+    let runtime = MockRuntime::new()
+        .with_call(MockCall::new("getSlot").respond_with(MultiRpcResult::Consistent(Ok(SLOT))));
+    let client = client(&runtime);
+
+    client.get_slot().with_cycles(1_000).send().await;
+
+    let calls = runtime.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].method, "getSlot");
+    assert_eq!(calls[0].cycles, 1_000);
+}
+
+#[tokio::test]
+async fn should_fetch_all_pages() {
+    let limit = GetTokenAccountsByOwnerLimit::try_from(2).unwrap();
+    let accounts: Vec<_> = (1..=3_u8).map(keyed_account).collect();
+    let runtime = MockRuntime::new()
+        .with_call(
+            MockCall::new("getTokenAccountsByOwner")
+                .with_args_matching(|(_, _, params): GetTokenAccountsByOwnerArgs| {
+                    params.after.is_none() && params.limit == Some(limit)
+                })
+                .respond_with(MultiRpcResult::Consistent(Ok(accounts[..2].to_vec()))),
+        )
+        .with_call(
+            MockCall::new("getTokenAccountsByOwner")
+                .with_args_matching({
+                    let after = accounts[1].pubkey.clone();
+                    move |(_, _, params): GetTokenAccountsByOwnerArgs| {
+                        params.after == Some(after.clone()) && params.limit == Some(limit)
+                    }
+                })
+                .respond_with(MultiRpcResult::Consistent(Ok(accounts[2..].to_vec()))),
+        );
+    let client = client(&runtime);
+
+    let result = client
+        .get_token_accounts_by_owner((OWNER, TokenAccountsFilter::Mint(MINT.into())))
+        .with_limit(limit)
+        .try_send_all_pages()
+        .await;
+
+    assert_eq!(result, Ok(MultiRpcResult::Consistent(Ok(accounts))));
+    assert!(runtime.is_done());
+    let pages: Vec<_> = runtime
+        .calls()
+        .iter()
+        .map(|call| {
+            let (_, _, params): GetTokenAccountsByOwnerArgs = call.decode_args().unwrap();
+            params.after
+        })
+        .collect();
+    assert_eq!(pages, vec![None, Some(keyed_account(2).pubkey)]);
+}
+
+#[tokio::test]
+#[should_panic(expected = "No mock call matching call to `getSlot`")]
+async fn should_panic_when_no_call_matches() {
+    let runtime = MockRuntime::new()
+        .with_call(MockCall::new("getBalance").respond_with(MultiRpcResult::Consistent(Ok(1_u64))));
+
+    client(&runtime).get_slot().send().await;
+}
+
+#[tokio::test]
+async fn should_return_fixtures() {
+    let runtime = MockRuntime::new()
+        .with_call(MockCall::new("getBlock").respond_with(consistent(Some(confirmed_block()))))
+        .with_call(
+            MockCall::new("getTransaction").respond_with(consistent(Some(confirmed_transaction()))),
+        )
+        .with_call(
+            MockCall::new("getSignatureStatuses").respond_with(consistent(signature_statuses())),
+        )
+        .with_call(
+            MockCall::new("getRecentPrioritizationFees")
+                .respond_with(consistent(prioritization_fees())),
+        );
+    let client = client(&runtime);
+    let signature = solana_signature::Signature::from(transaction_signature());
+
+    assert_eq!(
+        client.get_block(fixtures::SLOT).send().await,
+        consistent(Some(UiConfirmedBlock::from(confirmed_block())))
+    );
+    assert_eq!(
+        client.get_transaction(signature).send().await,
+        consistent(Some(EncodedConfirmedTransactionWithStatusMeta::from(
+            confirmed_transaction()
+        )))
+    );
+    assert_eq!(
+        client
+            .get_signature_statuses(&[signature, solana_signature::Signature::default()])
+            .unwrap()
+            .send()
+            .await
+            .expect_consistent()
+            .unwrap()
+            .into_iter()
+            .map(|status| status.map(|status| status.slot))
+            .collect::<Vec<_>>(),
+        vec![Some(fixtures::SLOT), None]
+    );
+    assert_eq!(
+        client
+            .get_recent_prioritization_fees(&[])
+            .unwrap()
+            .send()
+            .await,
+        consistent(prioritization_fees())
+    );
+    assert!(runtime.is_done());
+}
+
+#[test]
+fn should_have_fixtures_consistent_with_each_other() {
+    let transaction = ConfirmedTransactionWithStatusMeta::try_from(confirmed_transaction())
+        .unwrap()
+        .transaction;
+    let signature = transaction_signature();
+    let fee_payer = Pubkey::from_str(fixtures::FEE_PAYER).unwrap();
+
+    assert_eq!(
+        transaction.signatures,
+        vec![solana_signature::Signature::from(signature.clone())]
+    );
+    assert_eq!(transaction.message.static_account_keys()[0], fee_payer);
+    assert!(confirmed_block().signatures.unwrap().contains(&signature));
+    assert_eq!(signatures_for_address()[0].signature, signature);
+    assert_eq!(
+        signature_statuses()[0].as_ref().unwrap().slot,
+        fixtures::SLOT
+    );
+    assert_eq!(
+        confirmed_transaction()
+            .transaction
+            .meta
+            .unwrap()
+            .post_balances[0],
+        balance()
+    );
+    assert_eq!(
+        prioritization_fees().last().map(|fee| fee.slot),
+        Some(fixtures::SLOT)
+    );
+    let epoch_info = epoch_info();
+    assert_eq!(
+        epoch_info.epoch * epoch_info.slots_in_epoch + epoch_info.slot_index,
+        epoch_info.absolute_slot
+    );
+    assert_eq!(confirmed_block().block_height, Some(block_height()));
+}
+
+#[test]
+fn should_wrap_fixtures_in_multi_rpc_result() {
+    let source = RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet);
+    let other_source = RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet);
+
+    assert_eq!(
+        consistent(balance()),
+        MultiRpcResult::Consistent(Ok(balance()))
+    );
+    assert_eq!(
+        inconsistent([
+            (source.clone(), Ok(block_height())),
+            (other_source.clone(), Ok(block_height() + 1)),
+        ]),
+        MultiRpcResult::Inconsistent(vec![
+            (source, Ok(block_height())),
+            (other_source, Ok(block_height() + 1)),
+        ])
+    );
+}
+
+fn client(runtime: &MockRuntime) -> SolRpcClient<MockRuntime> {
+    SolRpcClient::builder_for_ic()
+        .with_mock_runtime(runtime.clone())
+        .build()
+}
+
+fn keyed_account(index: u8) -> KeyedAccount {
+    KeyedAccount {
+        pubkey: Pubkey::new_from_array([index; 32]).into(),
+        account: usdc_account(),
+    }
+}
@@ -0,0 +1,192 @@
+//! Module to observe and modify the requests sent and the responses received by a
+//! [`SolRpcClient`](crate::SolRpcClient).
+//!
+//! Hooks are registered on the [`ClientBuilder`](crate::ClientBuilder) with
+//! [`with_request_hook`](crate::ClientBuilder::with_request_hook) and
+//! [`with_response_hook`](crate::ClientBuilder::with_response_hook) and apply to all requests
+//! sent by the built client, which is useful for cross-cutting concerns such as logging,
+//! accounting or enforcing a commitment level.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{request::Request, IcError, SolRpcEndpoint};
+use sol_rpc_types::RpcSources;
+use std::{
+    any::Any,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+/// A hook called with each request just before it is sent to the SOL RPC canister.
+pub type RequestHook = Arc<dyn Fn(&mut OutgoingRequest<'_>) + Send + Sync>;
+
+/// A hook called with the result of each request sent to the SOL RPC canister.
+pub type ResponseHook = Arc<dyn Fn(&mut IncomingResponse<'_>) + Send + Sync>;
+
+/// A request about to be sent to the SOL RPC canister.
+///
+/// The request parameters and RPC config are type-erased, since their type depends on the
+/// endpoint, and can be accessed with [`OutgoingRequest::params_mut`] and
+/// [`OutgoingRequest::rpc_config_mut`] by specifying the type corresponding to the endpoint,
+/// e.g. [`GetBalanceParams`](sol_rpc_types::GetBalanceParams) for
+/// [`SolRpcEndpoint::GetBalance`].
+pub struct OutgoingRequest<'a> {
+    endpoint: &'a SolRpcEndpoint,
+    rpc_sources: &'a mut RpcSources,
+    rpc_config: &'a mut (dyn Any + 'static),
+    params: &'a mut (dyn Any + 'static),
+    cycles: &'a mut u128,
+}
+
+impl OutgoingRequest<'_> {
+    /// The endpoint called on the SOL RPC canister.
+    pub fn endpoint(&self) -> &SolRpcEndpoint {
+        self.endpoint
+    }
+
+    /// The RPC sources used for this request.
+    pub fn rpc_sources_mut(&mut self) -> &mut RpcSources {
+        self.rpc_sources
+    }
+
+    /// The amount of cycles attached to this request.
+    pub fn cycles(&self) -> u128 {
+        *self.cycles
+    }
+
+    /// Change the amount of cycles attached to this request.
+    pub fn set_cycles(&mut self, cycles: u128) {
+        *self.cycles = cycles;
+    }
+
+    /// The request parameters, if they are of type `Params`.
+    pub fn params<Params: 'static>(&self) -> Option<&Params> {
+        self.params.downcast_ref()
+    }
+
+    /// The mutable request parameters, if they are of type `Params`.
+    pub fn params_mut<Params: 'static>(&mut self) -> Option<&mut Params> {
+        self.params.downcast_mut()
+    }
+
+    /// The mutable RPC config of this request, if it is of type `Config`.
+    pub fn rpc_config_mut<Config: 'static>(&mut self) -> Option<&mut Option<Config>> {
+        self.rpc_config.downcast_mut()
+    }
+}
+
+/// The result of a request sent to the SOL RPC canister.
+///
+/// The output is type-erased, since its type depends on the endpoint, and can be accessed with
+/// [`IncomingResponse::output_mut`] by specifying the type corresponding to the endpoint, e.g.
+/// `MultiRpcResult<Lamport>` for [`SolRpcEndpoint::GetBalance`].
+pub struct IncomingResponse<'a> {
+    endpoint: &'a SolRpcEndpoint,
+    cycles: u128,
+    result: Result<&'a mut (dyn Any + 'static), &'a IcError>,
+}
+
+impl IncomingResponse<'_> {
+    /// The endpoint called on the SOL RPC canister.
+    pub fn endpoint(&self) -> &SolRpcEndpoint {
+        self.endpoint
+    }
+
+    /// The amount of cycles attached to the request.
+    pub fn cycles(&self) -> u128 {
+        self.cycles
+    }
+
+    /// The output of the request, if it succeeded and is of type `Output`.
+    pub fn output<Output: 'static>(&self) -> Option<&Output> {
+        self.result.as_ref().ok()?.downcast_ref()
+    }
+
+    /// The mutable output of the request, if it succeeded and is of type `Output`.
+    pub fn output_mut<Output: 'static>(&mut self) -> Option<&mut Output> {
+        self.result.as_mut().ok()?.downcast_mut()
+    }
+
+    /// The error that occurred while sending the request, if any.
+    pub fn error(&self) -> Option<&IcError> {
+        self.result.as_ref().err().copied()
+    }
+}
+
+/// Hooks registered on a client, called in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    request_hooks: Vec<RequestHook>,
+    response_hooks: Vec<ResponseHook>,
+}
+
+impl Hooks {
+    pub fn add_request_hook(&mut self, hook: RequestHook) {
+        self.request_hooks.push(hook);
+    }
+
+    pub fn add_response_hook(&mut self, hook: ResponseHook) {
+        self.response_hooks.push(hook);
+    }
+
+    pub fn on_request<Config: 'static, Params: 'static, CandidOutput, Output>(
+        &self,
+        request: &mut Request<Config, Params, CandidOutput, Output>,
+        cycles: &mut u128,
+    ) {
+        for hook in &self.request_hooks {
+            hook(&mut OutgoingRequest {
+                endpoint: &request.endpoint,
+                rpc_sources: &mut request.rpc_sources,
+                rpc_config: &mut request.rpc_config,
+                params: &mut request.params,
+                cycles,
+            });
+        }
+    }
+
+    pub fn on_response<Output: 'static>(
+        &self,
+        endpoint: &SolRpcEndpoint,
+        cycles: u128,
+        result: &mut Result<Output, IcError>,
+    ) {
+        for hook in &self.response_hooks {
+            hook(&mut IncomingResponse {
+                endpoint,
+                cycles,
+                result: match result {
+                    Ok(output) => Ok(output),
+                    Err(e) => Err(e),
+                },
+            });
+        }
+    }
+}
+
+impl Debug for Hooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hooks")
+            .field("request_hooks", &self.request_hooks.len())
+            .field("response_hooks", &self.response_hooks.len())
+            .finish()
+    }
+}
+
+/// Two [`Hooks`] are equal if they consist of the same hooks.
+impl PartialEq for Hooks {
+    fn eq(&self, other: &Self) -> bool {
+        fn same<T: ?Sized>(left: &[Arc<T>], right: &[Arc<T>]) -> bool {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(left, right)| Arc::ptr_eq(left, right))
+        }
+        same(&self.request_hooks, &other.request_hooks)
+            && same(&self.response_hooks, &other.response_hooks)
+    }
+}
+
+impl Eq for Hooks {}
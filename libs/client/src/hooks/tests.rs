@@ -0,0 +1,115 @@
+use crate::{SolRpcClient, SolRpcEndpoint};
+use sol_rpc_types::{
+    CommitmentLevel, GetBalanceParams, GetSlotParams, GetSlotRpcConfig, Lamport, MultiRpcResult,
+    RpcSources, SolanaCluster,
+};
+use solana_pubkey::{pubkey, Pubkey};
+use std::sync::{Arc, Mutex};
+
+const PUBKEY: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+#[tokio::test]
+async fn should_modify_request_in_registration_order() {
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let capture = captured.clone();
+    let client = SolRpcClient::builder_for_ic()
+        .with_stub_responses()
+        .add_stub_response(MultiRpcResult::Consistent(Ok(1_000_u64)))
+        .with_request_hook(|request| {
+            request.set_cycles(request.cycles() + 1);
+            *request.rpc_sources_mut() = RpcSources::Default(SolanaCluster::Devnet);
+            if let Some(params) = request.params_mut::<GetBalanceParams>() {
+                params.commitment = Some(CommitmentLevel::Finalized);
+            }
+        })
+        .with_request_hook(move |request| {
+            capture.lock().unwrap().push((
+                request.endpoint().clone(),
+                request.rpc_sources_mut().clone(),
+                request.params::<GetBalanceParams>().cloned(),
+                request.cycles(),
+            ));
+        })
+        .build();
+
+    let balance = client
+        .get_balance(PUBKEY)
+        .with_commitment(CommitmentLevel::Processed)
+        .with_cycles(1_000)
+        .send()
+        .await;
+
+    assert_eq!(balance, MultiRpcResult::Consistent(Ok(1_000)));
+    assert_eq!(
+        captured.lock().unwrap().as_slice(),
+        &[(
+            SolRpcEndpoint::GetBalance,
+            RpcSources::Default(SolanaCluster::Devnet),
+            Some(GetBalanceParams {
+                pubkey: PUBKEY.into(),
+                commitment: Some(CommitmentLevel::Finalized),
+                min_context_slot: None,
+            }),
+            1_001,
+        )]
+    );
+}
+
+#[tokio::test]
+async fn should_only_expose_params_and_config_of_matching_type() {
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let capture = captured.clone();
+    let client = SolRpcClient::builder_for_ic()
+        .with_stub_responses()
+        .add_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+        .with_request_hook(move |request| {
+            capture.lock().unwrap().push((
+                request.params::<GetBalanceParams>().is_some(),
+                request.params::<Option<GetSlotParams>>().is_some(),
+                request.rpc_config_mut::<GetSlotRpcConfig>().is_some(),
+            ));
+        })
+        .build();
+
+    let _slot = client.get_slot().with_cycles(1_000).send().await;
+
+    assert_eq!(captured.lock().unwrap().as_slice(), &[(false, true, true)]);
+}
+
+#[tokio::test]
+async fn should_modify_output() {
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let capture = captured.clone();
+    let client = SolRpcClient::builder_for_ic()
+        .with_stub_responses()
+        .add_stub_response(MultiRpcResult::Consistent(Ok(1_000_u64)))
+        .with_response_hook(|response| {
+            if let Some(MultiRpcResult::Consistent(Ok(balance))) =
+                response.output_mut::<MultiRpcResult<Lamport>>()
+            {
+                *balance += 1;
+            }
+        })
+        .with_response_hook(move |response| {
+            capture.lock().unwrap().push((
+                response.endpoint().clone(),
+                response.cycles(),
+                response.output::<MultiRpcResult<Lamport>>().cloned(),
+                response.error().is_some(),
+            ));
+        })
+        .build();
+
+    let balance = client.get_balance(PUBKEY).with_cycles(1_000).send().await;
+
+    assert_eq!(balance, MultiRpcResult::Consistent(Ok(1_001)));
+    assert_eq!(
+        captured.lock().unwrap().as_slice(),
+        &[(
+            SolRpcEndpoint::GetBalance,
+            1_000,
+            Some(MultiRpcResult::Consistent(Ok(1_001))),
+            false
+        )]
+    );
+}
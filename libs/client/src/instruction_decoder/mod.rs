@@ -0,0 +1,1022 @@
+//! Module for decoding the instructions of fetched transactions into typed instructions for
+//! well-known Solana programs, namely the
+//! [System Program](https://solana.com/docs/core/programs#system-program),
+//! the [SPL Token](https://spl.solana.com/token) and Token-2022 programs,
+//! the [Associated Token Account](https://spl.solana.com/associated-token-account) program
+//! and the [Compute Budget](https://solana.com/docs/core/fees#compute-budget) program.
+//!
+//! Instructions of any other program are returned undecoded as
+//! [`DecodedInstruction::Unknown`], together with their resolved account addresses.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sol_rpc_client::instruction_decoder::{
+//!     decode_instruction, DecodedInstruction, SystemInstruction,
+//! };
+//! use solana_pubkey::pubkey;
+//!
+//! let from = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+//! let to = pubkey!("6pPYj3CpgfR6wwBkenmUpSd7rNqmeq2hccUVJq4au5eS");
+//! // `SystemInstruction::Transfer` of 1_000_000 lamports
+//! let data = [2, 0, 0, 0, 64, 66, 15, 0, 0, 0, 0, 0];
+//!
+//! let instruction =
+//!     decode_instruction(&solana_sdk_ids::system_program::ID, &[from, to], &data).unwrap();
+//!
+//! assert_eq!(
+//!     instruction,
+//!     DecodedInstruction::System(SystemInstruction::Transfer {
+//!         from,
+//!         to,
+//!         lamports: 1_000_000,
+//!     })
+//! );
+//! assert_eq!(instruction.transferred_lamports_to(&to), Some(1_000_000));
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use sol_rpc_types::{ConfirmedTransactionWithStatusMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::{compute_budget, system_program};
+use thiserror::Error;
+
+/// Address of the [SPL Token](https://spl.solana.com/token) program.
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Address of the [Token-2022](https://spl.solana.com/token-2022) program.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Address of the [Associated Token Account](https://spl.solana.com/associated-token-account)
+/// program.
+pub const ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// An instruction decoded with [`decode_instruction`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodedInstruction {
+    /// An instruction of the System Program.
+    System(SystemInstruction),
+    /// An instruction of the SPL Token program.
+    Token(TokenInstruction),
+    /// An instruction of the Token-2022 program.
+    ///
+    /// Only the instructions shared with the SPL Token program are decoded, instructions
+    /// specific to Token-2022 extensions are decoded as [`TokenInstruction::Other`].
+    Token2022(TokenInstruction),
+    /// An instruction of the Associated Token Account program.
+    AssociatedTokenAccount(AssociatedTokenAccountInstruction),
+    /// An instruction of the Compute Budget program.
+    ComputeBudget(ComputeBudgetInstruction),
+    /// An instruction of a program that is not supported by the decoder.
+    Unknown {
+        /// Address of the program executing the instruction.
+        program_id: Pubkey,
+        /// Addresses of the accounts passed to the program.
+        accounts: Vec<Pubkey>,
+        /// Raw instruction data.
+        data: Vec<u8>,
+    },
+}
+
+impl DecodedInstruction {
+    /// Returns the amount of lamports transferred by this instruction to the given account
+    /// with the System Program, if any.
+    ///
+    /// Only [`SystemInstruction::Transfer`], [`SystemInstruction::TransferWithSeed`] and
+    /// [`SystemInstruction::WithdrawNonceAccount`] are considered. In particular, lamports
+    /// used to fund a new account with [`SystemInstruction::CreateAccount`] are not.
+    pub fn transferred_lamports_to(&self, recipient: &Pubkey) -> Option<u64> {
+        match self {
+            Self::System(SystemInstruction::Transfer { to, lamports, .. })
+            | Self::System(SystemInstruction::TransferWithSeed { to, lamports, .. })
+            | Self::System(SystemInstruction::WithdrawNonceAccount { to, lamports, .. })
+                if to == recipient =>
+            {
+                Some(*lamports)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An instruction of the System Program, see
+/// [`solana_system_interface::instruction::SystemInstruction`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SystemInstruction {
+    /// Create a new account.
+    CreateAccount {
+        /// Account funding the new account.
+        funder: Pubkey,
+        /// The new account.
+        new_account: Pubkey,
+        /// Number of lamports to transfer to the new account.
+        lamports: u64,
+        /// Number of bytes of memory to allocate.
+        space: u64,
+        /// Address of the program to assign as the owner of the new account.
+        owner: Pubkey,
+    },
+    /// Assign an account to a program.
+    Assign {
+        /// The assigned account.
+        account: Pubkey,
+        /// Address of the program to assign as the owner of the account.
+        owner: Pubkey,
+    },
+    /// Transfer lamports.
+    Transfer {
+        /// Account transferring the lamports.
+        from: Pubkey,
+        /// Account receiving the lamports.
+        to: Pubkey,
+        /// Number of lamports to transfer.
+        lamports: u64,
+    },
+    /// Create a new account at an address derived from a base pubkey and a seed.
+    CreateAccountWithSeed {
+        /// Account funding the new account.
+        funder: Pubkey,
+        /// The new account.
+        new_account: Pubkey,
+        /// Base public key.
+        base: Pubkey,
+        /// String of ASCII chars, no longer than `MAX_SEED_LEN`.
+        seed: String,
+        /// Number of lamports to transfer to the new account.
+        lamports: u64,
+        /// Number of bytes of memory to allocate.
+        space: u64,
+        /// Address of the program to assign as the owner of the new account.
+        owner: Pubkey,
+    },
+    /// Consume a stored nonce, replacing it with a successor.
+    AdvanceNonceAccount {
+        /// The nonce account.
+        nonce_account: Pubkey,
+        /// The nonce authority.
+        nonce_authority: Pubkey,
+    },
+    /// Withdraw lamports from a nonce account.
+    WithdrawNonceAccount {
+        /// The nonce account.
+        nonce_account: Pubkey,
+        /// Account receiving the lamports.
+        to: Pubkey,
+        /// The nonce authority.
+        nonce_authority: Pubkey,
+        /// Number of lamports to withdraw.
+        lamports: u64,
+    },
+    /// Drive the state of an uninitialized nonce account to initialized, setting the nonce value.
+    InitializeNonceAccount {
+        /// The nonce account.
+        nonce_account: Pubkey,
+        /// The entity authorized to execute nonce instructions on the account.
+        nonce_authority: Pubkey,
+    },
+    /// Change the entity authorized to execute nonce instructions on the account.
+    AuthorizeNonceAccount {
+        /// The nonce account.
+        nonce_account: Pubkey,
+        /// The current nonce authority.
+        nonce_authority: Pubkey,
+        /// The new nonce authority.
+        new_authority: Pubkey,
+    },
+    /// Allocate space in a (possibly new) account without funding.
+    Allocate {
+        /// The allocated account.
+        account: Pubkey,
+        /// Number of bytes of memory to allocate.
+        space: u64,
+    },
+    /// Allocate space for and assign an account at an address derived from a base public key
+    /// and a seed.
+    AllocateWithSeed {
+        /// The allocated account.
+        account: Pubkey,
+        /// Base public key.
+        base: Pubkey,
+        /// String of ASCII chars, no longer than `MAX_SEED_LEN`.
+        seed: String,
+        /// Number of bytes of memory to allocate.
+        space: u64,
+        /// Address of the program to assign as the owner of the account.
+        owner: Pubkey,
+    },
+    /// Assign an account to a program based on a seed.
+    AssignWithSeed {
+        /// The assigned account.
+        account: Pubkey,
+        /// Base public key.
+        base: Pubkey,
+        /// String of ASCII chars, no longer than `MAX_SEED_LEN`.
+        seed: String,
+        /// Address of the program to assign as the owner of the account.
+        owner: Pubkey,
+    },
+    /// Transfer lamports from a derived address.
+    TransferWithSeed {
+        /// Account transferring the lamports.
+        from: Pubkey,
+        /// Base public key from which the address of the funding account was derived.
+        from_base: Pubkey,
+        /// Account receiving the lamports.
+        to: Pubkey,
+        /// Number of lamports to transfer.
+        lamports: u64,
+        /// Seed to use to derive the address of the funding account.
+        from_seed: String,
+        /// Owner to use to derive the address of the funding account.
+        from_owner: Pubkey,
+    },
+    /// One-time idempotent upgrade of legacy nonce versions in order to bump them out of
+    /// chain blockhash domain.
+    UpgradeNonceAccount {
+        /// The nonce account.
+        nonce_account: Pubkey,
+    },
+}
+
+/// An instruction of the SPL Token or Token-2022 program.
+///
+/// Only the most common instructions are decoded, see [`TokenInstruction::Other`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenInstruction {
+    /// Initialize a new token account.
+    InitializeAccount {
+        /// The token account to initialize.
+        account: Pubkey,
+        /// The mint this account will be associated with.
+        mint: Pubkey,
+        /// The new account's owner.
+        owner: Pubkey,
+    },
+    /// Transfer tokens from one account to another.
+    Transfer {
+        /// The source token account.
+        source: Pubkey,
+        /// The destination token account.
+        destination: Pubkey,
+        /// The owner or delegate of the source account.
+        authority: Pubkey,
+        /// Amount of tokens to transfer, in base units.
+        amount: u64,
+    },
+    /// Approve a delegate to transfer up to a maximum number of tokens.
+    Approve {
+        /// The source token account.
+        source: Pubkey,
+        /// The delegate.
+        delegate: Pubkey,
+        /// The owner of the source account.
+        owner: Pubkey,
+        /// Amount of tokens the delegate is approved for, in base units.
+        amount: u64,
+    },
+    /// Revoke the delegate's authority.
+    Revoke {
+        /// The source token account.
+        source: Pubkey,
+        /// The owner of the source account.
+        owner: Pubkey,
+    },
+    /// Mint new tokens to an account.
+    MintTo {
+        /// The mint.
+        mint: Pubkey,
+        /// The token account to mint tokens to.
+        account: Pubkey,
+        /// The mint authority.
+        mint_authority: Pubkey,
+        /// Amount of tokens to mint, in base units.
+        amount: u64,
+    },
+    /// Burn tokens from an account.
+    Burn {
+        /// The token account to burn from.
+        account: Pubkey,
+        /// The mint.
+        mint: Pubkey,
+        /// The owner or delegate of the account.
+        authority: Pubkey,
+        /// Amount of tokens to burn, in base units.
+        amount: u64,
+    },
+    /// Close an account by transferring all its lamports to the destination account.
+    CloseAccount {
+        /// The token account to close.
+        account: Pubkey,
+        /// The account receiving the lamports.
+        destination: Pubkey,
+        /// The owner of the account.
+        owner: Pubkey,
+    },
+    /// Freeze an initialized account using the mint's freeze authority.
+    FreezeAccount {
+        /// The token account to freeze.
+        account: Pubkey,
+        /// The mint.
+        mint: Pubkey,
+        /// The mint freeze authority.
+        freeze_authority: Pubkey,
+    },
+    /// Thaw a frozen account using the mint's freeze authority.
+    ThawAccount {
+        /// The token account to thaw.
+        account: Pubkey,
+        /// The mint.
+        mint: Pubkey,
+        /// The mint freeze authority.
+        freeze_authority: Pubkey,
+    },
+    /// Transfer tokens from one account to another, asserting the mint and decimals.
+    TransferChecked {
+        /// The source token account.
+        source: Pubkey,
+        /// The mint.
+        mint: Pubkey,
+        /// The destination token account.
+        destination: Pubkey,
+        /// The owner or delegate of the source account.
+        authority: Pubkey,
+        /// Amount of tokens to transfer, in base units.
+        amount: u64,
+        /// Expected number of base 10 digits to the right of the decimal place.
+        decimals: u8,
+    },
+    /// Approve a delegate, asserting the mint and decimals.
+    ApproveChecked {
+        /// The source token account.
+        source: Pubkey,
+        /// The mint.
+        mint: Pubkey,
+        /// The delegate.
+        delegate: Pubkey,
+        /// The owner of the source account.
+        owner: Pubkey,
+        /// Amount of tokens the delegate is approved for, in base units.
+        amount: u64,
+        /// Expected number of base 10 digits to the right of the decimal place.
+        decimals: u8,
+    },
+    /// Mint new tokens to an account, asserting the decimals.
+    MintToChecked {
+        /// The mint.
+        mint: Pubkey,
+        /// The token account to mint tokens to.
+        account: Pubkey,
+        /// The mint authority.
+        mint_authority: Pubkey,
+        /// Amount of tokens to mint, in base units.
+        amount: u64,
+        /// Expected number of base 10 digits to the right of the decimal place.
+        decimals: u8,
+    },
+    /// Burn tokens from an account, asserting the mint and decimals.
+    BurnChecked {
+        /// The token account to burn from.
+        account: Pubkey,
+        /// The mint.
+        mint: Pubkey,
+        /// The owner or delegate of the account.
+        authority: Pubkey,
+        /// Amount of tokens to burn, in base units.
+        amount: u64,
+        /// Expected number of base 10 digits to the right of the decimal place.
+        decimals: u8,
+    },
+    /// Like [`TokenInstruction::InitializeAccount`], but the owner is provided as instruction
+    /// data rather than as an account.
+    InitializeAccount2 {
+        /// The token account to initialize.
+        account: Pubkey,
+        /// The mint this account will be associated with.
+        mint: Pubkey,
+        /// The new account's owner.
+        owner: Pubkey,
+    },
+    /// Update the amount of a native token account to match its underlying lamports.
+    SyncNative {
+        /// The native token account to sync.
+        account: Pubkey,
+    },
+    /// Like [`TokenInstruction::InitializeAccount2`], but does not require the Rent sysvar.
+    InitializeAccount3 {
+        /// The token account to initialize.
+        account: Pubkey,
+        /// The mint this account will be associated with.
+        mint: Pubkey,
+        /// The new account's owner.
+        owner: Pubkey,
+    },
+    /// Any other instruction, which is not decoded.
+    Other {
+        /// The instruction tag, i.e. the first byte of the instruction data.
+        tag: u8,
+        /// Addresses of the accounts passed to the program.
+        accounts: Vec<Pubkey>,
+        /// Raw instruction data, without the tag.
+        data: Vec<u8>,
+    },
+}
+
+/// An instruction of the Associated Token Account program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssociatedTokenAccountInstruction {
+    /// Create an associated token account for the given wallet address and token mint.
+    /// Fails if the account already exists.
+    Create(CreateAssociatedTokenAccount),
+    /// Create an associated token account for the given wallet address and token mint,
+    /// if it doesn't already exist.
+    CreateIdempotent(CreateAssociatedTokenAccount),
+    /// Transfer all tokens out of a nested associated token account, i.e. an associated token
+    /// account owned by another associated token account, and close it.
+    RecoverNested {
+        /// The nested associated token account.
+        nested_account: Pubkey,
+        /// Mint of the nested associated token account.
+        nested_mint: Pubkey,
+        /// Wallet's associated token account receiving the recovered tokens.
+        destination: Pubkey,
+        /// The owner associated token account, which owns the nested account.
+        owner_account: Pubkey,
+        /// Mint of the owner associated token account.
+        owner_mint: Pubkey,
+        /// The wallet owning the owner associated token account.
+        wallet: Pubkey,
+        /// The token program.
+        token_program: Pubkey,
+    },
+}
+
+/// Accounts of an [`AssociatedTokenAccountInstruction::Create`] or
+/// [`AssociatedTokenAccountInstruction::CreateIdempotent`] instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateAssociatedTokenAccount {
+    /// Account funding the associated token account.
+    pub funder: Pubkey,
+    /// The associated token account.
+    pub associated_account: Pubkey,
+    /// The wallet owning the associated token account.
+    pub wallet: Pubkey,
+    /// The token mint.
+    pub mint: Pubkey,
+    /// The token program.
+    pub token_program: Pubkey,
+}
+
+/// An instruction of the Compute Budget program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ComputeBudgetInstruction {
+    /// Request a specific transaction-wide program heap region size in bytes.
+    RequestHeapFrame(u32),
+    /// Set a specific compute unit limit that the transaction is allowed to consume.
+    SetComputeUnitLimit(u32),
+    /// Set a compute unit price in micro-lamports to pay a higher transaction fee for higher
+    /// transaction prioritization.
+    SetComputeUnitPrice(u64),
+    /// Set a specific transaction-wide account data size limit, in bytes.
+    SetLoadedAccountsDataSizeLimit(u32),
+}
+
+/// The decoded inner instructions invoked during a single top-level instruction of a
+/// transaction, see [`decode_inner_instructions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedInnerInstructions {
+    /// Index of the top-level instruction from which the inner instructions originated.
+    pub index: u8,
+    /// The decoded inner instructions, in the order they were invoked.
+    pub instructions: Vec<DecodedInstruction>,
+}
+
+/// Errors that might happen when decoding instructions.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum InstructionDecodingError {
+    /// An account index of an instruction does not refer to any of the transaction's accounts.
+    #[error("Invalid account index {index}: the transaction only has {num_accounts} accounts")]
+    InvalidAccountIndex {
+        /// The invalid account index.
+        index: u8,
+        /// Number of accounts of the transaction.
+        num_accounts: usize,
+    },
+    /// The transaction loads accounts from address lookup tables, but the loaded addresses
+    /// are not part of the transaction metadata.
+    #[error("Missing loaded addresses in transaction metadata")]
+    MissingLoadedAddresses,
+    /// Fewer accounts than expected were passed to an instruction.
+    #[error("Missing account at position {position} for instruction of program {program_id}")]
+    MissingAccount {
+        /// Address of the program executing the instruction.
+        program_id: Pubkey,
+        /// Position of the missing account in the instruction accounts.
+        position: usize,
+    },
+    /// The instruction data could not be decoded.
+    #[error("Invalid instruction data for program {program_id}: {reason}")]
+    InvalidInstructionData {
+        /// Address of the program executing the instruction.
+        program_id: Pubkey,
+        /// Reason why the instruction data is invalid.
+        reason: String,
+    },
+    /// The data of an inner instruction is not a valid base-58 encoded string.
+    #[error("Invalid base-58 encoded instruction data: {0}")]
+    InvalidEncoding(String),
+    /// The inner instruction was already parsed by the JSON-RPC provider and its raw data is
+    /// not available, which happens when the transaction was fetched with `jsonParsed` encoding.
+    #[error("Instruction of program {program_id} was already parsed by the provider")]
+    ParsedInstruction {
+        /// Address of the program executing the instruction.
+        program_id: Pubkey,
+    },
+}
+
+/// Decodes the instruction of the given program with the given (resolved) account addresses
+/// and data.
+///
+/// # Errors
+///
+/// The method will return an instance of [`InstructionDecodingError`] if the instruction
+/// belongs to one of the supported programs but has invalid data or too few accounts.
+pub fn decode_instruction(
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Result<DecodedInstruction, InstructionDecodingError> {
+    let decoder = Decoder {
+        program_id,
+        accounts,
+        data,
+        offset: 0,
+    };
+    if program_id == &system_program::ID {
+        decoder.decode_system().map(DecodedInstruction::System)
+    } else if program_id == &TOKEN_PROGRAM_ID {
+        decoder.decode_token().map(DecodedInstruction::Token)
+    } else if program_id == &TOKEN_2022_PROGRAM_ID {
+        decoder.decode_token().map(DecodedInstruction::Token2022)
+    } else if program_id == &ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+        decoder
+            .decode_associated_token_account()
+            .map(DecodedInstruction::AssociatedTokenAccount)
+    } else if program_id == &compute_budget::ID {
+        decoder
+            .decode_compute_budget()
+            .map(DecodedInstruction::ComputeBudget)
+    } else {
+        Ok(DecodedInstruction::Unknown {
+            program_id: *program_id,
+            accounts: accounts.to_vec(),
+            data: data.to_vec(),
+        })
+    }
+}
+
+/// Decodes the top-level instructions of the given transaction, in order.
+///
+/// Account indices are resolved against the static account keys of the transaction message,
+/// followed by the writable and then the readonly addresses loaded from address lookup tables.
+///
+/// # Errors
+///
+/// The method will return an instance of [`InstructionDecodingError`] if the account indices
+/// of an instruction cannot be resolved or if an instruction cannot be decoded, see
+/// [`decode_instruction`].
+pub fn decode_instructions(
+    transaction: &ConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<DecodedInstruction>, InstructionDecodingError> {
+    let account_keys = account_keys(transaction)?;
+    transaction
+        .transaction
+        .message
+        .instructions()
+        .iter()
+        .map(|instruction| {
+            decode_compiled_instruction(
+                &account_keys,
+                instruction.program_id_index,
+                &instruction.accounts,
+                &instruction.data,
+            )
+        })
+        .collect()
+}
+
+/// Decodes the inner instructions recorded in the metadata of the given transaction, i.e. the
+/// instructions invoked via cross-program invocations.
+///
+/// Returns an empty list if the transaction metadata does not contain any inner instructions.
+///
+/// # Errors
+///
+/// The method will return an instance of [`InstructionDecodingError`] if the account indices
+/// of an instruction cannot be resolved or if an instruction cannot be decoded, see
+/// [`decode_instruction`].
+pub fn decode_inner_instructions(
+    transaction: &ConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<DecodedInnerInstructions>, InstructionDecodingError> {
+    let Some(inner_instructions) = transaction
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.inner_instructions.as_ref())
+    else {
+        return Ok(Vec::new());
+    };
+    let account_keys = account_keys(transaction)?;
+    inner_instructions
+        .iter()
+        .map(|inner| {
+            let instructions = inner
+                .instructions
+                .iter()
+                .map(|instruction| match instruction {
+                    Instruction::Compiled(compiled) => {
+                        let data = bs58::decode(&compiled.data).into_vec().map_err(|e| {
+                            InstructionDecodingError::InvalidEncoding(e.to_string())
+                        })?;
+                        decode_compiled_instruction(
+                            &account_keys,
+                            compiled.program_id_index,
+                            &compiled.accounts,
+                            &data,
+                        )
+                    }
+                    Instruction::PartiallyDecoded(partially_decoded) => {
+                        let data =
+                            bs58::decode(&partially_decoded.data)
+                                .into_vec()
+                                .map_err(|e| {
+                                    InstructionDecodingError::InvalidEncoding(e.to_string())
+                                })?;
+                        let accounts = partially_decoded
+                            .accounts
+                            .iter()
+                            .cloned()
+                            .map(Pubkey::from)
+                            .collect::<Vec<_>>();
+                        decode_instruction(
+                            &Pubkey::from(partially_decoded.program_id.clone()),
+                            &accounts,
+                            &data,
+                        )
+                    }
+                    Instruction::Parsed(parsed) => {
+                        Err(InstructionDecodingError::ParsedInstruction {
+                            program_id: Pubkey::from(parsed.program_id.clone()),
+                        })
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(DecodedInnerInstructions {
+                index: inner.index,
+                instructions,
+            })
+        })
+        .collect()
+}
+
+fn account_keys(
+    transaction: &ConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<Pubkey>, InstructionDecodingError> {
+    let message = &transaction.transaction.message;
+    let mut keys = message.static_account_keys().to_vec();
+    let loaded_addresses = transaction
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.loaded_addresses.as_ref());
+    match (message.address_table_lookups(), loaded_addresses) {
+        (Some(lookups), None) if !lookups.is_empty() => {
+            return Err(InstructionDecodingError::MissingLoadedAddresses)
+        }
+        (_, Some(loaded_addresses)) => {
+            keys.extend(loaded_addresses.writable.iter().cloned().map(Pubkey::from));
+            keys.extend(loaded_addresses.readonly.iter().cloned().map(Pubkey::from));
+        }
+        _ => {}
+    }
+    Ok(keys)
+}
+
+pub(crate) fn decode_compiled_instruction(
+    account_keys: &[Pubkey],
+    program_id_index: u8,
+    account_indices: &[u8],
+    data: &[u8],
+) -> Result<DecodedInstruction, InstructionDecodingError> {
+    let resolve = |index: u8| {
+        account_keys.get(index as usize).copied().ok_or(
+            InstructionDecodingError::InvalidAccountIndex {
+                index,
+                num_accounts: account_keys.len(),
+            },
+        )
+    };
+    let program_id = resolve(program_id_index)?;
+    let accounts = account_indices
+        .iter()
+        .map(|index| resolve(*index))
+        .collect::<Result<Vec<_>, _>>()?;
+    decode_instruction(&program_id, &accounts, data)
+}
+
+struct Decoder<'a> {
+    program_id: &'a Pubkey,
+    accounts: &'a [Pubkey],
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Decoder<'_> {
+    fn decode_system(mut self) -> Result<SystemInstruction, InstructionDecodingError> {
+        // System instructions are serialized with `bincode`, i.e. with a `u32` tag.
+        Ok(match self.read_u32()? {
+            0 => SystemInstruction::CreateAccount {
+                funder: self.account(0)?,
+                new_account: self.account(1)?,
+                lamports: self.read_u64()?,
+                space: self.read_u64()?,
+                owner: self.read_pubkey()?,
+            },
+            1 => SystemInstruction::Assign {
+                account: self.account(0)?,
+                owner: self.read_pubkey()?,
+            },
+            2 => SystemInstruction::Transfer {
+                from: self.account(0)?,
+                to: self.account(1)?,
+                lamports: self.read_u64()?,
+            },
+            3 => SystemInstruction::CreateAccountWithSeed {
+                funder: self.account(0)?,
+                new_account: self.account(1)?,
+                base: self.read_pubkey()?,
+                seed: self.read_string()?,
+                lamports: self.read_u64()?,
+                space: self.read_u64()?,
+                owner: self.read_pubkey()?,
+            },
+            4 => SystemInstruction::AdvanceNonceAccount {
+                nonce_account: self.account(0)?,
+                nonce_authority: self.account(2)?,
+            },
+            5 => SystemInstruction::WithdrawNonceAccount {
+                nonce_account: self.account(0)?,
+                to: self.account(1)?,
+                nonce_authority: self.account(4)?,
+                lamports: self.read_u64()?,
+            },
+            6 => SystemInstruction::InitializeNonceAccount {
+                nonce_account: self.account(0)?,
+                nonce_authority: self.read_pubkey()?,
+            },
+            7 => SystemInstruction::AuthorizeNonceAccount {
+                nonce_account: self.account(0)?,
+                nonce_authority: self.account(1)?,
+                new_authority: self.read_pubkey()?,
+            },
+            8 => SystemInstruction::Allocate {
+                account: self.account(0)?,
+                space: self.read_u64()?,
+            },
+            9 => SystemInstruction::AllocateWithSeed {
+                account: self.account(0)?,
+                base: self.read_pubkey()?,
+                seed: self.read_string()?,
+                space: self.read_u64()?,
+                owner: self.read_pubkey()?,
+            },
+            10 => SystemInstruction::AssignWithSeed {
+                account: self.account(0)?,
+                base: self.read_pubkey()?,
+                seed: self.read_string()?,
+                owner: self.read_pubkey()?,
+            },
+            11 => SystemInstruction::TransferWithSeed {
+                from: self.account(0)?,
+                from_base: self.account(1)?,
+                to: self.account(2)?,
+                lamports: self.read_u64()?,
+                from_seed: self.read_string()?,
+                from_owner: self.read_pubkey()?,
+            },
+            12 => SystemInstruction::UpgradeNonceAccount {
+                nonce_account: self.account(0)?,
+            },
+            tag => return Err(self.invalid_data(format!("unknown instruction tag {tag}"))),
+        })
+    }
+
+    fn decode_token(mut self) -> Result<TokenInstruction, InstructionDecodingError> {
+        Ok(match self.read_u8()? {
+            1 => TokenInstruction::InitializeAccount {
+                account: self.account(0)?,
+                mint: self.account(1)?,
+                owner: self.account(2)?,
+            },
+            3 => TokenInstruction::Transfer {
+                source: self.account(0)?,
+                destination: self.account(1)?,
+                authority: self.account(2)?,
+                amount: self.read_u64()?,
+            },
+            4 => TokenInstruction::Approve {
+                source: self.account(0)?,
+                delegate: self.account(1)?,
+                owner: self.account(2)?,
+                amount: self.read_u64()?,
+            },
+            5 => TokenInstruction::Revoke {
+                source: self.account(0)?,
+                owner: self.account(1)?,
+            },
+            7 => TokenInstruction::MintTo {
+                mint: self.account(0)?,
+                account: self.account(1)?,
+                mint_authority: self.account(2)?,
+                amount: self.read_u64()?,
+            },
+            8 => TokenInstruction::Burn {
+                account: self.account(0)?,
+                mint: self.account(1)?,
+                authority: self.account(2)?,
+                amount: self.read_u64()?,
+            },
+            9 => TokenInstruction::CloseAccount {
+                account: self.account(0)?,
+                destination: self.account(1)?,
+                owner: self.account(2)?,
+            },
+            10 => TokenInstruction::FreezeAccount {
+                account: self.account(0)?,
+                mint: self.account(1)?,
+                freeze_authority: self.account(2)?,
+            },
+            11 => TokenInstruction::ThawAccount {
+                account: self.account(0)?,
+                mint: self.account(1)?,
+                freeze_authority: self.account(2)?,
+            },
+            12 => TokenInstruction::TransferChecked {
+                source: self.account(0)?,
+                mint: self.account(1)?,
+                destination: self.account(2)?,
+                authority: self.account(3)?,
+                amount: self.read_u64()?,
+                decimals: self.read_u8()?,
+            },
+            13 => TokenInstruction::ApproveChecked {
+                source: self.account(0)?,
+                mint: self.account(1)?,
+                delegate: self.account(2)?,
+                owner: self.account(3)?,
+                amount: self.read_u64()?,
+                decimals: self.read_u8()?,
+            },
+            14 => TokenInstruction::MintToChecked {
+                mint: self.account(0)?,
+                account: self.account(1)?,
+                mint_authority: self.account(2)?,
+                amount: self.read_u64()?,
+                decimals: self.read_u8()?,
+            },
+            15 => TokenInstruction::BurnChecked {
+                account: self.account(0)?,
+                mint: self.account(1)?,
+                authority: self.account(2)?,
+                amount: self.read_u64()?,
+                decimals: self.read_u8()?,
+            },
+            16 => TokenInstruction::InitializeAccount2 {
+                account: self.account(0)?,
+                mint: self.account(1)?,
+                owner: self.read_pubkey()?,
+            },
+            17 => TokenInstruction::SyncNative {
+                account: self.account(0)?,
+            },
+            18 => TokenInstruction::InitializeAccount3 {
+                account: self.account(0)?,
+                mint: self.account(1)?,
+                owner: self.read_pubkey()?,
+            },
+            tag => TokenInstruction::Other {
+                tag,
+                accounts: self.accounts.to_vec(),
+                data: self.data[self.offset..].to_vec(),
+            },
+        })
+    }
+
+    fn decode_associated_token_account(
+        mut self,
+    ) -> Result<AssociatedTokenAccountInstruction, InstructionDecodingError> {
+        // Legacy `Create` instructions have no data at all.
+        let tag = if self.data.is_empty() {
+            0
+        } else {
+            self.read_u8()?
+        };
+        Ok(match tag {
+            0 => AssociatedTokenAccountInstruction::Create(self.create_associated_token_account()?),
+            1 => AssociatedTokenAccountInstruction::CreateIdempotent(
+                self.create_associated_token_account()?,
+            ),
+            2 => AssociatedTokenAccountInstruction::RecoverNested {
+                nested_account: self.account(0)?,
+                nested_mint: self.account(1)?,
+                destination: self.account(2)?,
+                owner_account: self.account(3)?,
+                owner_mint: self.account(4)?,
+                wallet: self.account(5)?,
+                token_program: self.account(6)?,
+            },
+            tag => return Err(self.invalid_data(format!("unknown instruction tag {tag}"))),
+        })
+    }
+
+    fn create_associated_token_account(
+        &self,
+    ) -> Result<CreateAssociatedTokenAccount, InstructionDecodingError> {
+        // The 5th account is the System Program.
+        Ok(CreateAssociatedTokenAccount {
+            funder: self.account(0)?,
+            associated_account: self.account(1)?,
+            wallet: self.account(2)?,
+            mint: self.account(3)?,
+            token_program: self.account(5)?,
+        })
+    }
+
+    fn decode_compute_budget(
+        mut self,
+    ) -> Result<ComputeBudgetInstruction, InstructionDecodingError> {
+        Ok(match self.read_u8()? {
+            1 => ComputeBudgetInstruction::RequestHeapFrame(self.read_u32()?),
+            2 => ComputeBudgetInstruction::SetComputeUnitLimit(self.read_u32()?),
+            3 => ComputeBudgetInstruction::SetComputeUnitPrice(self.read_u64()?),
+            4 => ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(self.read_u32()?),
+            tag => return Err(self.invalid_data(format!("unknown instruction tag {tag}"))),
+        })
+    }
+
+    fn account(&self, position: usize) -> Result<Pubkey, InstructionDecodingError> {
+        self.accounts
+            .get(position)
+            .copied()
+            .ok_or(InstructionDecodingError::MissingAccount {
+                program_id: *self.program_id,
+                position,
+            })
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], InstructionDecodingError> {
+        let bytes = self.data.get(self.offset..self.offset + N).ok_or_else(|| {
+            self.invalid_data(format!(
+                "expected {N} bytes at offset {}, but data has length {}",
+                self.offset,
+                self.data.len()
+            ))
+        })?;
+        self.offset += N;
+        Ok(bytes.try_into().expect("BUG: slice has length N"))
+    }
+
+    fn read_u8(&mut self) -> Result<u8, InstructionDecodingError> {
+        self.read_bytes::<1>().map(|bytes| bytes[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, InstructionDecodingError> {
+        self.read_bytes().map(u32::from_le_bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, InstructionDecodingError> {
+        self.read_bytes().map(u64::from_le_bytes)
+    }
+
+    fn read_pubkey(&mut self) -> Result<Pubkey, InstructionDecodingError> {
+        self.read_bytes().map(Pubkey::new_from_array)
+    }
+
+    fn read_string(&mut self) -> Result<String, InstructionDecodingError> {
+        // Strings are serialized with `bincode`, i.e. prefixed with their length as `u64`.
+        let length = self.read_u64()? as usize;
+        let bytes = self
+            .data
+            .get(self.offset..self.offset.saturating_add(length))
+            .ok_or_else(|| self.invalid_data(format!("string of length {length} out of bounds")))?;
+        self.offset += length;
+        String::from_utf8(bytes.to_vec()).map_err(|e| self.invalid_data(e.to_string()))
+    }
+
+    fn invalid_data(&self, reason: String) -> InstructionDecodingError {
+        InstructionDecodingError::InvalidInstructionData {
+            program_id: *self.program_id,
+            reason,
+        }
+    }
+}
@@ -0,0 +1,480 @@
+use crate::instruction_decoder::{
+    decode_inner_instructions, decode_instruction, decode_instructions,
+    AssociatedTokenAccountInstruction, ComputeBudgetInstruction, CreateAssociatedTokenAccount,
+    DecodedInnerInstructions, DecodedInstruction, InstructionDecodingError, SystemInstruction,
+    TokenInstruction, ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use assert_matches::assert_matches;
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sdk_ids::{compute_budget, system_program};
+
+const ALICE: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+const BOB: Pubkey = pubkey!("6pPYj3CpgfR6wwBkenmUpSd7rNqmeq2hccUVJq4au5eS");
+const MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const OTHER_PROGRAM: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+mod decode_instruction {
+    use super::*;
+
+    #[test]
+    fn should_decode_system_transfer() {
+        let data = [vec![2, 0, 0, 0], 1_000_000_u64.to_le_bytes().to_vec()].concat();
+
+        let instruction = decode_instruction(&system_program::ID, &[ALICE, BOB], &data);
+
+        assert_eq!(
+            instruction,
+            Ok(DecodedInstruction::System(SystemInstruction::Transfer {
+                from: ALICE,
+                to: BOB,
+                lamports: 1_000_000,
+            }))
+        );
+        let instruction = instruction.unwrap();
+        assert_eq!(instruction.transferred_lamports_to(&BOB), Some(1_000_000));
+        assert_eq!(instruction.transferred_lamports_to(&ALICE), None);
+    }
+
+    #[test]
+    fn should_decode_system_create_account() {
+        let data = [
+            vec![0, 0, 0, 0],
+            2_039_280_u64.to_le_bytes().to_vec(),
+            165_u64.to_le_bytes().to_vec(),
+            TOKEN_PROGRAM_ID.to_bytes().to_vec(),
+        ]
+        .concat();
+
+        let instruction = decode_instruction(&system_program::ID, &[ALICE, BOB], &data);
+
+        assert_eq!(
+            instruction,
+            Ok(DecodedInstruction::System(
+                SystemInstruction::CreateAccount {
+                    funder: ALICE,
+                    new_account: BOB,
+                    lamports: 2_039_280,
+                    space: 165,
+                    owner: TOKEN_PROGRAM_ID,
+                }
+            ))
+        );
+        assert_eq!(instruction.unwrap().transferred_lamports_to(&BOB), None);
+    }
+
+    #[test]
+    fn should_decode_system_transfer_with_seed() {
+        let data = [
+            vec![11, 0, 0, 0],
+            500_u64.to_le_bytes().to_vec(),
+            4_u64.to_le_bytes().to_vec(),
+            b"seed".to_vec(),
+            OTHER_PROGRAM.to_bytes().to_vec(),
+        ]
+        .concat();
+
+        let instruction = decode_instruction(&system_program::ID, &[MINT, ALICE, BOB], &data);
+
+        assert_eq!(
+            instruction,
+            Ok(DecodedInstruction::System(
+                SystemInstruction::TransferWithSeed {
+                    from: MINT,
+                    from_base: ALICE,
+                    to: BOB,
+                    lamports: 500,
+                    from_seed: "seed".to_string(),
+                    from_owner: OTHER_PROGRAM,
+                }
+            ))
+        );
+        assert_eq!(
+            instruction.unwrap().transferred_lamports_to(&BOB),
+            Some(500)
+        );
+    }
+
+    #[test]
+    fn should_decode_token_instructions_of_both_token_programs() {
+        let data = [vec![12], 1_500_000_u64.to_le_bytes().to_vec(), vec![6]].concat();
+        let expected = TokenInstruction::TransferChecked {
+            source: ALICE,
+            mint: MINT,
+            destination: BOB,
+            authority: OTHER_PROGRAM,
+            amount: 1_500_000,
+            decimals: 6,
+        };
+
+        assert_eq!(
+            decode_instruction(&TOKEN_PROGRAM_ID, &[ALICE, MINT, BOB, OTHER_PROGRAM], &data),
+            Ok(DecodedInstruction::Token(expected.clone()))
+        );
+        assert_eq!(
+            decode_instruction(
+                &TOKEN_2022_PROGRAM_ID,
+                &[ALICE, MINT, BOB, OTHER_PROGRAM],
+                &data
+            ),
+            Ok(DecodedInstruction::Token2022(expected))
+        );
+    }
+
+    #[test]
+    fn should_decode_token_transfer() {
+        let data = [vec![3], 42_u64.to_le_bytes().to_vec()].concat();
+
+        assert_eq!(
+            decode_instruction(&TOKEN_PROGRAM_ID, &[ALICE, BOB, OTHER_PROGRAM], &data),
+            Ok(DecodedInstruction::Token(TokenInstruction::Transfer {
+                source: ALICE,
+                destination: BOB,
+                authority: OTHER_PROGRAM,
+                amount: 42,
+            }))
+        );
+    }
+
+    #[test]
+    fn should_not_decode_other_token_instructions() {
+        let data = [6, 2, 0];
+
+        assert_eq!(
+            decode_instruction(&TOKEN_PROGRAM_ID, &[ALICE, BOB], &data),
+            Ok(DecodedInstruction::Token(TokenInstruction::Other {
+                tag: 6,
+                accounts: vec![ALICE, BOB],
+                data: vec![2, 0],
+            }))
+        );
+    }
+
+    #[test]
+    fn should_decode_associated_token_account_instructions() {
+        let accounts = [
+            ALICE,
+            BOB,
+            OTHER_PROGRAM,
+            MINT,
+            system_program::ID,
+            TOKEN_PROGRAM_ID,
+        ];
+        let expected = CreateAssociatedTokenAccount {
+            funder: ALICE,
+            associated_account: BOB,
+            wallet: OTHER_PROGRAM,
+            mint: MINT,
+            token_program: TOKEN_PROGRAM_ID,
+        };
+
+        for data in [vec![], vec![0]] {
+            assert_eq!(
+                decode_instruction(&ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, &accounts, &data),
+                Ok(DecodedInstruction::AssociatedTokenAccount(
+                    AssociatedTokenAccountInstruction::Create(expected.clone())
+                ))
+            );
+        }
+        assert_eq!(
+            decode_instruction(&ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, &accounts, &[1]),
+            Ok(DecodedInstruction::AssociatedTokenAccount(
+                AssociatedTokenAccountInstruction::CreateIdempotent(expected)
+            ))
+        );
+    }
+
+    #[test]
+    fn should_decode_compute_budget_instructions() {
+        let set_limit = [vec![2], 200_000_u32.to_le_bytes().to_vec()].concat();
+        let set_price = [vec![3], 10_000_u64.to_le_bytes().to_vec()].concat();
+
+        assert_eq!(
+            decode_instruction(&compute_budget::ID, &[], &set_limit),
+            Ok(DecodedInstruction::ComputeBudget(
+                ComputeBudgetInstruction::SetComputeUnitLimit(200_000)
+            ))
+        );
+        assert_eq!(
+            decode_instruction(&compute_budget::ID, &[], &set_price),
+            Ok(DecodedInstruction::ComputeBudget(
+                ComputeBudgetInstruction::SetComputeUnitPrice(10_000)
+            ))
+        );
+    }
+
+    #[test]
+    fn should_return_unknown_instruction_for_other_programs() {
+        assert_eq!(
+            decode_instruction(&OTHER_PROGRAM, &[ALICE], b"hello"),
+            Ok(DecodedInstruction::Unknown {
+                program_id: OTHER_PROGRAM,
+                accounts: vec![ALICE],
+                data: b"hello".to_vec(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_when_account_is_missing() {
+        let data = [vec![2, 0, 0, 0], 1_000_000_u64.to_le_bytes().to_vec()].concat();
+
+        assert_eq!(
+            decode_instruction(&system_program::ID, &[ALICE], &data),
+            Err(InstructionDecodingError::MissingAccount {
+                program_id: system_program::ID,
+                position: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_when_data_is_invalid() {
+        // Truncated lamports
+        assert_matches!(
+            decode_instruction(&system_program::ID, &[ALICE, BOB], &[2, 0, 0, 0, 1, 2]),
+            Err(InstructionDecodingError::InvalidInstructionData { program_id, .. }) if program_id == system_program::ID
+        );
+        // Unknown tag
+        assert_matches!(
+            decode_instruction(&compute_budget::ID, &[], &[42]),
+            Err(InstructionDecodingError::InvalidInstructionData { program_id, .. }) if program_id == compute_budget::ID
+        );
+        // Empty data
+        assert_matches!(
+            decode_instruction(&TOKEN_PROGRAM_ID, &[], &[]),
+            Err(InstructionDecodingError::InvalidInstructionData { program_id, .. }) if program_id == TOKEN_PROGRAM_ID
+        );
+    }
+}
+
+mod decode_transaction {
+    use super::*;
+    use sol_rpc_types::{
+        CompiledInstruction, ConfirmedTransactionWithStatusMeta, InnerInstructions, Instruction,
+        LoadedAddresses, ParsedInstruction, PartiallyDecodedInstruction, TransactionStatusMeta,
+    };
+    use solana_hash::Hash;
+    use solana_message::{
+        compiled_instruction::CompiledInstruction as MessageCompiledInstruction,
+        v0::{self, MessageAddressTableLookup},
+        MessageHeader, VersionedMessage,
+    };
+    use solana_transaction::versioned::VersionedTransaction;
+
+    const LOOKUP_TABLE: Pubkey = pubkey!("2qGyrhhjdq7VSUpDJbpkxJV6ofSMRXAxDKxNcyt1iFCK");
+
+    #[test]
+    fn should_decode_instructions_with_loaded_addresses() {
+        let transaction = transaction(
+            vec![
+                MessageCompiledInstruction {
+                    program_id_index: 3,
+                    accounts: vec![],
+                    data: [vec![2], 200_000_u32.to_le_bytes().to_vec()].concat(),
+                },
+                MessageCompiledInstruction {
+                    program_id_index: 2,
+                    // BOB is loaded from the address lookup table
+                    accounts: vec![0, 4],
+                    data: [vec![2, 0, 0, 0], 1_000_u64.to_le_bytes().to_vec()].concat(),
+                },
+            ],
+            None,
+        );
+
+        assert_eq!(
+            decode_instructions(&transaction),
+            Ok(vec![
+                DecodedInstruction::ComputeBudget(ComputeBudgetInstruction::SetComputeUnitLimit(
+                    200_000
+                )),
+                DecodedInstruction::System(SystemInstruction::Transfer {
+                    from: ALICE,
+                    to: BOB,
+                    lamports: 1_000,
+                }),
+            ])
+        );
+        assert_eq!(decode_inner_instructions(&transaction), Ok(vec![]));
+    }
+
+    #[test]
+    fn should_decode_inner_instructions() {
+        let transaction = transaction(
+            vec![MessageCompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![0, 4],
+                data: vec![],
+            }],
+            Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![Instruction::Compiled(CompiledInstruction {
+                    program_id_index: 2,
+                    accounts: vec![1, 4],
+                    data: bs58::encode([vec![2, 0, 0, 0], 5_u64.to_le_bytes().to_vec()].concat())
+                        .into_string(),
+                    stack_height: Some(2),
+                })],
+            }]),
+        );
+
+        assert_eq!(
+            decode_instructions(&transaction),
+            Ok(vec![DecodedInstruction::Unknown {
+                program_id: OTHER_PROGRAM,
+                accounts: vec![ALICE, BOB],
+                data: vec![],
+            }])
+        );
+        assert_eq!(
+            decode_inner_instructions(&transaction),
+            Ok(vec![DecodedInnerInstructions {
+                index: 0,
+                instructions: vec![DecodedInstruction::System(SystemInstruction::Transfer {
+                    from: OTHER_PROGRAM,
+                    to: BOB,
+                    lamports: 5,
+                })],
+            }])
+        );
+    }
+
+    #[test]
+    fn should_decode_partially_decoded_inner_instructions() {
+        let transaction = transaction(
+            vec![MessageCompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![0, 4],
+                data: vec![],
+            }],
+            Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![
+                    Instruction::PartiallyDecoded(PartiallyDecodedInstruction {
+                        program_id: system_program::ID.into(),
+                        accounts: vec![ALICE.into(), BOB.into()],
+                        data: bs58::encode(
+                            [vec![2, 0, 0, 0], 5_u64.to_le_bytes().to_vec()].concat(),
+                        )
+                        .into_string(),
+                        stack_height: Some(2),
+                    }),
+                    Instruction::Parsed(ParsedInstruction {
+                        program: "spl-memo".to_string(),
+                        program_id: OTHER_PROGRAM.into(),
+                        parsed: "\"hello\"".to_string(),
+                        stack_height: Some(2),
+                    }),
+                ],
+            }]),
+        );
+
+        assert_eq!(
+            decode_inner_instructions(&transaction),
+            Err(InstructionDecodingError::ParsedInstruction {
+                program_id: OTHER_PROGRAM
+            })
+        );
+
+        let mut transaction = transaction;
+        transaction
+            .meta
+            .as_mut()
+            .unwrap()
+            .inner_instructions
+            .as_mut()
+            .unwrap()[0]
+            .instructions
+            .pop();
+        assert_eq!(
+            decode_inner_instructions(&transaction),
+            Ok(vec![DecodedInnerInstructions {
+                index: 0,
+                instructions: vec![DecodedInstruction::System(SystemInstruction::Transfer {
+                    from: ALICE,
+                    to: BOB,
+                    lamports: 5,
+                })],
+            }])
+        );
+    }
+
+    #[test]
+    fn should_fail_with_invalid_account_index() {
+        let transaction = transaction(
+            vec![MessageCompiledInstruction {
+                program_id_index: 2,
+                accounts: vec![0, 5],
+                data: [vec![2, 0, 0, 0], 1_000_u64.to_le_bytes().to_vec()].concat(),
+            }],
+            None,
+        );
+
+        assert_eq!(
+            decode_instructions(&transaction),
+            Err(InstructionDecodingError::InvalidAccountIndex {
+                index: 5,
+                num_accounts: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_without_loaded_addresses() {
+        let mut transaction = transaction(vec![], None);
+        transaction.meta = None;
+
+        assert_eq!(
+            decode_instructions(&transaction),
+            Err(InstructionDecodingError::MissingLoadedAddresses)
+        );
+    }
+
+    fn transaction(
+        instructions: Vec<MessageCompiledInstruction>,
+        inner_instructions: Option<Vec<InnerInstructions>>,
+    ) -> ConfirmedTransactionWithStatusMeta {
+        let message = v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 2,
+            },
+            account_keys: vec![ALICE, OTHER_PROGRAM, system_program::ID, compute_budget::ID],
+            recent_blockhash: Hash::default(),
+            instructions,
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: LOOKUP_TABLE,
+                writable_indexes: vec![0],
+                readonly_indexes: vec![],
+            }],
+        };
+        ConfirmedTransactionWithStatusMeta {
+            slot: 123,
+            block_time: None,
+            transaction: VersionedTransaction {
+                signatures: vec![Default::default()],
+                message: VersionedMessage::V0(message),
+            },
+            meta: Some(TransactionStatusMeta {
+                status: Ok(()),
+                fee: 5_000,
+                pre_balances: vec![],
+                post_balances: vec![],
+                inner_instructions,
+                log_messages: None,
+                pre_token_balances: None,
+                post_token_balances: None,
+                rewards: None,
+                loaded_addresses: Some(LoadedAddresses {
+                    writable: vec![BOB.into()],
+                    readonly: vec![],
+                }),
+                return_data: None,
+                compute_units_consumed: None,
+                cost_units: None,
+            }),
+            version: None,
+        }
+    }
+}
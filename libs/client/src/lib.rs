@@ -120,41 +120,101 @@
 //! outlined [here](https://github.com/dfinity/sol-rpc-canister/blob/main/libs/client/README.md#build-requirements)
 //! to ensure your code compiles.
 
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "wasm-web"), forbid(unsafe_code))]
+#![cfg_attr(feature = "wasm-web", deny(unsafe_code))]
 #![forbid(missing_docs)]
 
+pub mod account;
+pub mod address_lookup_table;
+pub mod balance;
+mod cache;
+pub mod commitment;
+pub mod confirmation;
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
+pub mod fee;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod fixtures;
+pub mod hooks;
+pub mod instruction_decoder;
+pub mod logs;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod nonce;
+pub mod pda;
+#[cfg(feature = "pocket-ic")]
+pub mod pocket_ic;
 mod request;
+mod runtime;
+mod slot_tracker;
+pub mod stake;
+#[cfg(feature = "ed25519")]
+pub mod transfer;
+pub mod watch;
+// The bindings generated by `wasm-bindgen` contain unsafe code.
+#[cfg(feature = "wasm-web")]
+#[allow(unsafe_code)]
+pub mod web;
 
+use crate::hooks::Hooks;
 use crate::request::{
-    GetAccountInfoRequest, GetAccountInfoRequestBuilder, GetBalanceRequest,
-    GetBalanceRequestBuilder, GetBlockRequest, GetBlockRequestBuilder,
-    GetRecentPrioritizationFeesRequest, GetRecentPrioritizationFeesRequestBuilder,
-    GetSignatureStatusesRequest, GetSignatureStatusesRequestBuilder,
-    GetSignaturesForAddressRequest, GetSignaturesForAddressRequestBuilder, GetSlotRequest,
-    GetSlotRequestBuilder, GetTokenAccountBalanceRequest, GetTokenAccountBalanceRequestBuilder,
-    GetTransactionRequest, GetTransactionRequestBuilder, JsonRequest, JsonRequestBuilder,
-    SendTransactionRequest, SendTransactionRequestBuilder,
+    GetAccountInfoRequest, GetAccountInfoRequestBuilder, GetAccountSnapshotRequest,
+    GetAccountSnapshotRequestBuilder, GetBalanceRequest, GetBalanceRequestBuilder,
+    GetBlockHeightRequest, GetBlockHeightRequestBuilder, GetBlockRequest, GetBlockRequestBuilder,
+    GetBlocksRequest, GetBlocksRequestBuilder, GetBlocksWithLimitRequest,
+    GetBlocksWithLimitRequestBuilder, GetClusterNodesRequest, GetClusterNodesRequestBuilder,
+    GetEpochInfoRequest, GetEpochInfoRequestBuilder, GetEpochScheduleRequest,
+    GetEpochScheduleRequestBuilder, GetFeeForMessageRequest, GetFeeForMessageRequestBuilder,
+    GetHighestSnapshotSlotRequest, GetHighestSnapshotSlotRequestBuilder, GetLeaderScheduleRequest,
+    GetLeaderScheduleRequestBuilder, GetMinimumBalanceForRentExemptionRequest,
+    GetMinimumBalanceForRentExemptionRequestBuilder, GetRecentPerformanceSamplesRequest,
+    GetRecentPerformanceSamplesRequestBuilder, GetRecentPrioritizationFeesRequest,
+    GetRecentPrioritizationFeesRequestBuilder, GetSignatureStatusesRequest,
+    GetSignatureStatusesRequestBuilder, GetSignaturesForAddressRequest,
+    GetSignaturesForAddressRequestBuilder, GetSlotLeadersRequest, GetSlotLeadersRequestBuilder,
+    GetSlotRequest, GetSlotRequestBuilder, GetSupplyRequest, GetSupplyRequestBuilder,
+    GetTokenAccountBalanceRequest, GetTokenAccountBalanceRequestBuilder,
+    GetTokenAccountsByDelegateRequest, GetTokenAccountsByDelegateRequestBuilder,
+    GetTokenAccountsByOwnerRequest, GetTokenAccountsByOwnerRequestBuilder,
+    GetTransactionCountRequest, GetTransactionCountRequestBuilder, GetTransactionRequest,
+    GetTransactionRequestBuilder, HasAccountChangedRequest, HasAccountChangedRequestBuilder,
+    JsonRequest, JsonRequestBuilder, MinimumLedgerSlotRequest, MinimumLedgerSlotRequestBuilder,
+    RawHttpRequest, RawHttpRequestBuilder, SendTransactionRequest, SendTransactionRequestBuilder,
 };
+pub use cache::ResponseCache;
 use candid::{CandidType, Principal};
+use futures::future::join_all;
 pub use ic_canister_runtime::IcError;
 use ic_canister_runtime::{IcRuntime, Runtime};
 pub use request::{
-    DefaultRequestCycles, GetRecentBlockError, GetRecentBlockRequestBuilder, Request,
-    RequestBuilder, SolRpcConfig, SolRpcEndpoint, SolRpcRequest,
+    DefaultRequestCycles, GetRecentBlockError, GetRecentBlockRequestBuilder, PreparedCall, Request,
+    RequestBuilder, SendPerProviderError, SendTransactionWithBlockhashRefreshError,
+    SendTransactionWithBlockhashRefreshRequestBuilder, SimulateThenSendTransactionError,
+    SimulateThenSendTransactionRequestBuilder, SolRpcConfig, SolRpcEndpoint, SolRpcRequest,
+    TransactionSimulationFailure,
 };
+pub use runtime::{CallSemantics, IcRuntimeV2};
 use serde::de::DeserializeOwned;
+pub use slot_tracker::SlotTracker;
 use sol_rpc_types::{
-    CommitmentLevel, ConsensusStrategy, GetAccountInfoParams, GetBalanceParams, GetBlockParams,
+    CachedSlot, Capabilities, CommitmentLevel, ConsensusStrategy, CyclesReport, DepositCyclesArgs,
+    DepositCyclesError, GetAccountInfoEncoding, GetAccountInfoParams, GetAccountSnapshotParams,
+    GetBalanceParams, GetBlockParams, GetBlocksParams, GetBlocksWithLimitParams,
+    GetFeeForMessageParams, GetLeaderScheduleParams, GetMinimumBalanceForRentExemptionParams,
     GetRecentPrioritizationFeesParams, GetSignatureStatusesParams, GetSignaturesForAddressParams,
-    GetTokenAccountBalanceParams, GetTransactionParams, Pubkey, RpcConfig, RpcResult, RpcSources,
-    SendTransactionParams, SolanaCluster, SupportedRpcProvider, SupportedRpcProviderId,
+    GetSlotLeadersParams, GetTokenAccountBalanceParams, GetTokenAccountsByDelegateParams,
+    GetTokenAccountsByOwnerParams, GetTransactionParams, HasAccountChangedParams,
+    HttpRequestPreview, Lamport, MultiRpcResult, ProviderHealth, ProviderHealthReport,
+    ProviderScorecard, Pubkey, RawHttpRequestParams, ResponseMetadata, RpcConfig, RpcError,
+    RpcResult, RpcSource, RpcSources, ScorecardPeriod, SendTransactionParams, ShadowProviderStats,
+    SolanaCluster, SupportedRpcProvider, SupportedRpcProviderId, UpgradeReadiness,
+};
+use solana_message::VersionedMessage;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    sync::Arc,
 };
-use std::{fmt::Debug, sync::Arc};
 
 /// The principal identifying the productive Solana RPC canister under NNS control.
 ///
@@ -190,6 +250,45 @@ impl<R> SolRpcClient<R> {
     pub fn runtime(&self) -> &R {
         &self.config.runtime
     }
+
+    /// Returns the commitment level used by the requests to the given endpoint that do not set
+    /// a commitment level, or `None` if the client has no default commitment level or if the
+    /// endpoint does not take a commitment level.
+    ///
+    /// See [`CommitmentPolicy`](commitment::CommitmentPolicy).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::{SolRpcClient, SolRpcEndpoint};
+    /// use sol_rpc_types::CommitmentLevel;
+    ///
+    /// let client = SolRpcClient::builder_for_ic()
+    ///     .with_default_commitment_level(CommitmentLevel::Processed)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     client.effective_commitment(&SolRpcEndpoint::GetSlot),
+    ///     Some(CommitmentLevel::Processed)
+    /// );
+    /// assert_eq!(
+    ///     client.effective_commitment(&SolRpcEndpoint::GetBlock),
+    ///     Some(CommitmentLevel::Confirmed)
+    /// );
+    /// assert_eq!(
+    ///     client.effective_commitment(&SolRpcEndpoint::GetSignatureStatuses),
+    ///     None
+    /// );
+    /// ```
+    pub fn effective_commitment(&self, endpoint: &SolRpcEndpoint) -> Option<CommitmentLevel> {
+        self.config
+            .default_commitment_level
+            .clone()
+            .and_then(|commitment| {
+                commitment::CommitmentPolicy::for_endpoint(endpoint)
+                    .effective_commitment(commitment)
+            })
+    }
 }
 
 impl SolRpcClient<IcRuntime> {
@@ -208,6 +307,11 @@ pub struct ClientConfig<R> {
     rpc_config: Option<RpcConfig>,
     default_commitment_level: Option<CommitmentLevel>,
     rpc_sources: RpcSources,
+    slot_tracker: Option<SlotTracker>,
+    response_cache: Option<ResponseCache>,
+    hooks: Hooks,
+    #[cfg(feature = "metrics")]
+    metrics: Option<metrics::ClientMetrics>,
 }
 
 /// A [`ClientBuilder`] to create a [`SolRpcClient`] with custom configuration.
@@ -225,6 +329,11 @@ impl<R> ClientBuilder<R> {
                 rpc_config: None,
                 default_commitment_level: None,
                 rpc_sources: RpcSources::Default(SolanaCluster::Mainnet),
+                slot_tracker: None,
+                response_cache: None,
+                hooks: Hooks::default(),
+                #[cfg(feature = "metrics")]
+                metrics: None,
             },
         }
     }
@@ -240,6 +349,11 @@ impl<R> ClientBuilder<R> {
                 rpc_config: self.config.rpc_config,
                 default_commitment_level: self.config.default_commitment_level,
                 rpc_sources: self.config.rpc_sources,
+                slot_tracker: self.config.slot_tracker,
+                response_cache: self.config.response_cache,
+                hooks: self.config.hooks,
+                #[cfg(feature = "metrics")]
+                metrics: self.config.metrics,
             },
         }
     }
@@ -283,6 +397,192 @@ impl<R> ClientBuilder<R> {
         self
     }
 
+    /// Mutates the builder to use the given [`SlotTracker`].
+    ///
+    /// The built client records the context slot of the responses of the endpoints returning a
+    /// context and sets the `minContextSlot` parameter of subsequent requests to at least the
+    /// highest recorded slot, so that reads never go backwards in time. This can be overridden by
+    /// each request, e.g. with [`GetBalanceRequestBuilder::with_min_context_slot`].
+    ///
+    /// The same [`SlotTracker`] can be shared by several clients.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::{SlotTracker, SolRpcClient};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{MultiRpcResult, WithContext};
+    /// let slot_tracker = SlotTracker::new();
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(WithContext {
+    /// #       context_slot: 364_000_000,
+    /// #       value: 389_086_612_571_u64,
+    /// #   })))
+    ///     .with_slot_tracker(slot_tracker.clone())
+    ///     .build();
+    ///
+    /// let balance = client
+    ///     .get_balance(pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"))
+    ///     .with_context()
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(balance.map(|balance| balance.value), Ok(389_086_612_571));
+    /// // Subsequent requests will use `minContextSlot: 364_000_000`
+    /// assert_eq!(slot_tracker.highest_slot(), Some(364_000_000));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_slot_tracker(mut self, slot_tracker: SlotTracker) -> Self {
+        self.config.slot_tracker = Some(slot_tracker);
+        self
+    }
+
+    /// Mutates the builder to use the given [`ResponseCache`].
+    ///
+    /// The built client returns the cached response to a request to an endpoint for which a TTL
+    /// was configured in the [`ResponseCache`], if an identical request was answered less than
+    /// TTL ago, instead of calling the SOL RPC canister again. See [`ResponseCache`] for details.
+    ///
+    /// The same [`ResponseCache`] can be shared by several clients.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::{ResponseCache, SolRpcClient, SolRpcEndpoint};
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let cache = ResponseCache::new().with_ttl(SolRpcEndpoint::GetSlot, Duration::from_secs(1));
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_responses()
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+    ///     .with_response_cache(cache.clone())
+    ///     .build();
+    ///
+    /// let slot = client.get_slot().send().await.expect_consistent();
+    /// // Served from the cache without calling the SOL RPC canister
+    /// let cached_slot = client.get_slot().send().await.expect_consistent();
+    ///
+    /// assert_eq!(slot, Ok(332_577_897));
+    /// assert_eq!(cached_slot, slot);
+    /// assert_eq!(cache.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_response_cache(mut self, response_cache: ResponseCache) -> Self {
+        self.config.response_cache = Some(response_cache);
+        self
+    }
+
+    /// Mutates the builder to call the given hook with each request, just before it is sent to
+    /// the SOL RPC canister.
+    ///
+    /// The hook can observe and modify the request, e.g. its RPC sources, attached cycles,
+    /// parameters or RPC config, see [`OutgoingRequest`](hooks::OutgoingRequest).
+    /// Several hooks can be registered and are called in registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{CommitmentLevel, GetBalanceParams};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_responses()
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(389_086_612_571_u64)))
+    ///     .with_request_hook(|request| {
+    ///         // Enforce the `finalized` commitment level for `getBalance` requests
+    ///         if let Some(params) = request.params_mut::<GetBalanceParams>() {
+    ///             params.commitment = Some(CommitmentLevel::Finalized);
+    ///         }
+    ///     })
+    ///     .build();
+    ///
+    /// let balance = client
+    ///     .get_balance(pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"))
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(balance, Ok(389_086_612_571));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_request_hook(
+        mut self,
+        hook: impl Fn(&mut hooks::OutgoingRequest<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.config.hooks.add_request_hook(Arc::new(hook));
+        self
+    }
+
+    /// Mutates the builder to call the given hook with the result of each request sent to the
+    /// SOL RPC canister, before it is returned to the caller.
+    ///
+    /// The hook can observe and modify the output of the request, see
+    /// [`IncomingResponse`](hooks::IncomingResponse).
+    /// Several hooks can be registered and are called in registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{MultiRpcResult, Slot};
+    /// use std::sync::{
+    ///     atomic::{AtomicU64, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let inconsistent_responses = Arc::new(AtomicU64::new(0));
+    /// let counter = inconsistent_responses.clone();
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_responses()
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+    ///     .with_response_hook(move |response| {
+    ///         if let Some(MultiRpcResult::Inconsistent(_)) = response.output::<MultiRpcResult<Slot>>() {
+    ///             counter.fetch_add(1, Ordering::Relaxed);
+    ///         }
+    ///     })
+    ///     .build();
+    ///
+    /// let slot = client.get_slot().send().await.expect_consistent();
+    ///
+    /// assert_eq!(slot, Ok(332_577_897));
+    /// assert_eq!(inconsistent_responses.load(Ordering::Relaxed), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_response_hook(
+        mut self,
+        hook: impl Fn(&mut hooks::IncomingResponse<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.config.hooks.add_response_hook(Arc::new(hook));
+        self
+    }
+
+    /// Mutates the builder to record metrics about the requests sent by the client into the
+    /// given [`MetricsRegistry`](metrics::MetricsRegistry), see the [`metrics`] module.
+    ///
+    /// Only available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics_registry(mut self, registry: Arc<dyn metrics::MetricsRegistry>) -> Self {
+        self.config.metrics = Some(metrics::ClientMetrics::new(registry));
+        self
+    }
+
     /// Creates a [`SolRpcClient`] from the configuration specified in the [`ClientBuilder`].
     pub fn build(self) -> SolRpcClient<R> {
         SolRpcClient {
@@ -328,6 +628,98 @@ impl<R> SolRpcClient<R> {
         RequestBuilder::new(self.clone(), GetAccountInfoRequest::new(params.into()))
     }
 
+    /// Call `getAccountSnapshot` on the SOL RPC canister.
+    ///
+    /// The balance, the account info and the most recent transaction signatures of the account
+    /// are fetched with `getBalance`, `getAccountInfo` and `getSignaturesForAddress` in a single
+    /// call to the SOL RPC canister, whose cycles cost is the sum of the three underlying requests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{GetSignaturesForAddressLimit, RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_client::fixtures::usdc_account;
+    /// # use sol_rpc_types::{AccountSnapshot, MultiRpcResult};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(AccountSnapshot {
+    /// #       balance: 389_086_612_571,
+    /// #       account: Some(usdc_account()),
+    /// #       signatures: vec![],
+    /// #   })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let snapshot = client
+    ///     .get_account_snapshot(pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"))
+    ///     .with_signatures_limit(GetSignaturesForAddressLimit::try_from(10).unwrap())
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()?;
+    ///
+    /// assert_eq!(snapshot.balance, 389_086_612_571);
+    /// assert!(snapshot.account.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_account_snapshot(
+        &self,
+        params: impl Into<GetAccountSnapshotParams>,
+    ) -> GetAccountSnapshotRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetAccountSnapshotRequest::from(params.into()))
+    }
+
+    /// Call `hasAccountChanged` on the SOL RPC canister.
+    ///
+    /// The account is fetched with `getAccountInfo` and hashed by the SOL RPC canister, which only
+    /// returns it if its hash differs from the one set with
+    /// [`HasAccountChangedRequestBuilder::with_last_known_hash`]. Polling for changes of an
+    /// account therefore only transfers the account data when it actually changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use std::str::FromStr;
+    /// # use sol_rpc_types::{AccountChange, Hash, MultiRpcResult};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(AccountChange {
+    /// #       hash: Some(Hash::from_str("C6Cxgzq6yZWxjYnxwvxvP2dhWFeQSEVxRQbUXG2eMYsY").unwrap()),
+    /// #       changed: false,
+    /// #       account: None,
+    /// #   })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let last_known_hash = "C6Cxgzq6yZWxjYnxwvxvP2dhWFeQSEVxRQbUXG2eMYsY".parse().ok();
+    /// let change = client
+    ///     .has_account_changed(pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"))
+    ///     .with_last_known_hash(last_known_hash)
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()?;
+    ///
+    /// assert!(!change.changed);
+    /// assert_eq!(change.account, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn has_account_changed(
+        &self,
+        params: impl Into<HasAccountChangedParams>,
+    ) -> HasAccountChangedRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), HasAccountChangedRequest::from(params.into()))
+    }
+
     /// Call `getBalance` on the SOL RPC canister.
     ///
     /// # Examples
@@ -429,124 +821,723 @@ impl<R> SolRpcClient<R> {
         RequestBuilder::new(self.clone(), GetBlockRequest::new(params.into()))
     }
 
-    /// Call `getTokenAccountBalance` on the SOL RPC canister.
+    /// Call `getBlockHeight` on the SOL RPC canister.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use sol_rpc_client::SolRpcClient;
-    /// use sol_rpc_types::{RpcSources, SolanaCluster};
-    /// use solana_pubkey::pubkey;
-    /// use solana_account_decoder_client_types::token::UiTokenAmount;
+    /// use sol_rpc_types::{CommitmentLevel, MultiRpcResult, RpcSources, SolanaCluster};
     ///
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// # use sol_rpc_types::{MultiRpcResult, TokenAmount};
     /// let client = SolRpcClient::builder_for_ic()
-    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(TokenAmount {
-    /// #       ui_amount: Some(251153323.575906),
-    /// #       decimals: 6,
-    /// #       amount: "251153323575906".to_string(),
-    /// #       ui_amount_string: "251153323.575906".to_string(),
-    /// #    })))
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(307_545_020_u64)))
     ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
     ///     .build();
     ///
-    /// let balance = client
-    ///     .get_token_account_balance(pubkey!("3emsAVdmGKERbHjmGfQ6oZ1e35dkf5iYcS6U4CPKFVaa"))
+    /// let block_height = client
+    ///     .get_block_height()
+    ///     .with_commitment(CommitmentLevel::Finalized)
+    ///     .with_rounding_error(10)
     ///     .send()
     ///     .await
     ///     .expect_consistent();
     ///
-    /// assert_eq!(balance, Ok(UiTokenAmount {
-    ///         ui_amount: Some(251153323.575906),
-    ///         decimals: 6,
-    ///         amount: "251153323575906".to_string(),
-    ///         ui_amount_string: "251153323.575906".to_string(),
-    /// }));
+    /// assert_eq!(block_height, Ok(307_545_020_u64));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_token_account_balance(
-        &self,
-        params: impl Into<GetTokenAccountBalanceParams>,
-    ) -> GetTokenAccountBalanceRequestBuilder<R> {
-        RequestBuilder::new(
-            self.clone(),
-            GetTokenAccountBalanceRequest::new(params.into()),
-        )
+    pub fn get_block_height(&self) -> GetBlockHeightRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetBlockHeightRequest::default())
     }
 
-    /// Call `getRecentPrioritizationFees` on the SOL RPC canister.
+    /// Call `getBlocks` on the SOL RPC canister.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use sol_rpc_client::SolRpcClient;
-    /// use sol_rpc_types::{RpcSources, SolanaCluster};
-    /// use solana_pubkey::pubkey;
-    /// #
+    /// use sol_rpc_types::{GetBlockCommitmentLevel, RpcSources, SolanaCluster};
+    ///
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// use std::num::NonZeroU8;
-    /// use sol_rpc_types::{MultiRpcResult, PrioritizationFee, TokenAmount};
+    /// # use sol_rpc_types::MultiRpcResult;
     /// let client = SolRpcClient::builder_for_ic()
-    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![PrioritizationFee{slot: 338637772, prioritization_fee: 166667}])))
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![343_459_194_u64, 343_459_196])))
     ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
     ///     .build();
     ///
-    /// let fees = client
-    ///     .get_recent_prioritization_fees(&[pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")])
-    ///     .unwrap()
-    ///     .with_max_length(NonZeroU8::MIN)
+    /// let slots = client
+    ///     .get_blocks(343_459_194)
+    ///     .with_end_slot(343_459_196)
+    ///     .with_commitment(GetBlockCommitmentLevel::Finalized)
     ///     .send()
     ///     .await
     ///     .expect_consistent();
     ///
-    /// assert_eq!
-    ///     (fees,
-    ///     Ok(vec![ PrioritizationFee {
-    ///         slot: 338637772,
-    ///         prioritization_fee: 166667
-    ///     }]));
+    /// assert_eq!(slots, Ok(vec![343_459_194, 343_459_196]));
     /// # Ok(())
     /// # }
     /// ```
+    pub fn get_blocks(&self, params: impl Into<GetBlocksParams>) -> GetBlocksRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetBlocksRequest::from(params.into()))
+    }
+
+    /// Call `getBlocksWithLimit` on the SOL RPC canister.
     ///
-    /// # Errors
-    ///
-    /// The number of account addresses that can be passed to
-    /// [`getRecentPrioritizationFees`](https://solana.com/de/docs/rpc/http/getrecentprioritizationfees)
-    /// is limited to 128. More accounts result in an error.
+    /// # Examples
     ///
     /// ```rust
-    /// use std::collections::BTreeSet;
-    /// use assert_matches::assert_matches;
-    /// use solana_pubkey::Pubkey;
     /// use sol_rpc_client::SolRpcClient;
-    /// use sol_rpc_types::{RpcSources, SolanaCluster, RpcError};
+    /// use sol_rpc_types::{GetBlocksLimit, RpcSources, SolanaCluster};
     ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
     /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![343_459_194_u64, 343_459_196])))
     ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
     ///     .build();
     ///
-    /// let too_many_accounts: BTreeSet<Pubkey> = (0..129_u8)
-    ///     .map(|i| Pubkey::from([i; 32]))
-    ///     .collect();
-    /// assert_eq!(too_many_accounts.len(), 129);
+    /// let slots = client
+    ///     .get_blocks_with_limit(343_459_194)
+    ///     .with_limit(GetBlocksLimit::try_from(2).unwrap())
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
     ///
-    /// let err = client.get_recent_prioritization_fees(&too_many_accounts).unwrap_err();
-    /// assert_matches!(err, RpcError::ValidationError(_));
+    /// assert_eq!(slots, Ok(vec![343_459_194, 343_459_196]));
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn get_recent_prioritization_fees<'a, I>(
+    pub fn get_blocks_with_limit(
         &self,
-        addresses: I,
-    ) -> RpcResult<GetRecentPrioritizationFeesRequestBuilder<R>>
-    where
-        I: IntoIterator<Item = &'a solana_pubkey::Pubkey>,
-    {
-        let params = GetRecentPrioritizationFeesParams::try_from(
-            addresses.into_iter().map(Pubkey::from).collect::<Vec<_>>(),
+        params: impl Into<GetBlocksWithLimitParams>,
+    ) -> GetBlocksWithLimitRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetBlocksWithLimitRequest::from(params.into()))
+    }
+
+    /// Call `getClusterNodes` on the SOL RPC canister.
+    ///
+    /// Since the nodes of a large cluster do not fit in a single response, only the public key,
+    /// gossip address and version of each node are returned. The nodes are sorted by public key
+    /// and can be paginated with [`with_after`](GetClusterNodesRequestBuilder::with_after) and
+    /// [`with_limit`](GetClusterNodesRequestBuilder::with_limit).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{ClusterNode, MultiRpcResult, RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![ClusterNode {
+    /// #       pubkey: pubkey!("9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ").into(),
+    /// #       gossip: Some("10.239.6.48:8001".to_string()),
+    /// #       version: Some("2.1.21".to_string()),
+    /// #   }])))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let nodes = client
+    ///     .get_cluster_nodes()
+    ///     .with_after(pubkey!("7Np41oeYqPefeNQEHSv1UDhYrehxin3NStELsSKCT4K2"))
+    ///     .with_limit(100.try_into().unwrap())
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(nodes.len(), 1);
+    /// assert_eq!(nodes[0].version.as_deref(), Some("2.1.21"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_cluster_nodes(&self) -> GetClusterNodesRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetClusterNodesRequest::default())
+    }
+
+    /// Call `getEpochInfo` on the SOL RPC canister.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{EpochInfo, MultiRpcResult, RpcSources, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(EpochInfo {
+    /// #       epoch: 800,
+    /// #       slot_index: 197_780,
+    /// #       slots_in_epoch: 432_000,
+    /// #       absolute_slot: 345_797_780,
+    /// #       block_height: 323_959_120,
+    /// #       transaction_count: None,
+    /// #   })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let epoch_info = client
+    ///     .get_epoch_info()
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(epoch_info.epoch, 800);
+    /// assert_eq!(epoch_info.absolute_slot, 345_797_780);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_epoch_info(&self) -> GetEpochInfoRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetEpochInfoRequest::default())
+    }
+
+    /// Call `getEpochSchedule` on the SOL RPC canister.
+    ///
+    /// The returned [`EpochSchedule`](sol_rpc_types::EpochSchedule) can be used to compute the
+    /// boundaries of an epoch, e.g. to schedule work around the end of the current epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{EpochSchedule, MultiRpcResult, RpcSources, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(EpochSchedule {
+    /// #       slots_per_epoch: 432_000,
+    /// #       leader_schedule_slot_offset: 432_000,
+    /// #       warmup: false,
+    /// #       first_normal_epoch: 0,
+    /// #       first_normal_slot: 0,
+    /// #   })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let epoch_schedule = client
+    ///     .get_epoch_schedule()
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// let slot = 345_797_780;
+    /// assert_eq!(epoch_schedule.epoch(slot), 800);
+    /// assert_eq!(epoch_schedule.first_slot_in_epoch(801), 346_032_000);
+    /// assert_eq!(epoch_schedule.slots_remaining_in_epoch(slot), 234_219);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_epoch_schedule(&self) -> GetEpochScheduleRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetEpochScheduleRequest)
+    }
+
+    /// Call `getFeeForMessage` on the SOL RPC canister.
+    ///
+    /// The resulting fee is `None` if it cannot be computed, e.g. because the recent blockhash
+    /// of the message expired.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{MultiRpcResult, RpcSources, SolanaCluster};
+    /// use solana_message::Message;
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(Some(5_000_u64))))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let payer = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+    /// let message = Message::new(&[], Some(&payer));
+    ///
+    /// let fee = client
+    ///     .get_fee_for_message(message)
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(fee, Ok(Some(5_000)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_fee_for_message<T>(&self, params: T) -> GetFeeForMessageRequestBuilder<R>
+    where
+        T: TryInto<GetFeeForMessageParams>,
+        <T as TryInto<GetFeeForMessageParams>>::Error: Debug,
+    {
+        let params = params
+            .try_into()
+            .expect("Unable to build request parameters");
+        RequestBuilder::new(self.clone(), GetFeeForMessageRequest::from(params))
+    }
+
+    /// Call `getHighestSnapshotSlot` on the SOL RPC canister.
+    ///
+    /// Snapshots are taken independently by each node, so that a node lagging behind the others
+    /// can be detected by comparing its highest snapshot slot with the current slot.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{MultiRpcResult, RpcSources, SnapshotSlotInfo, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(SnapshotSlotInfo {
+    /// #       full: 368_700_000,
+    /// #       incremental: Some(368_724_300),
+    /// #   })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let snapshot_slot = client
+    ///     .get_highest_snapshot_slot()
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(
+    ///     snapshot_slot,
+    ///     Ok(SnapshotSlotInfo {
+    ///         full: 368_700_000,
+    ///         incremental: Some(368_724_300),
+    ///     })
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_highest_snapshot_slot(&self) -> GetHighestSnapshotSlotRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetHighestSnapshotSlotRequest)
+    }
+
+    /// Call `getLeaderSchedule` on the SOL RPC canister.
+    ///
+    /// Only the leader slots of the validator with the given identity are returned, in ascending
+    /// order and relative to the first slot of the epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{CommitmentLevel, RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(Some(vec![0_u64, 1, 2, 3]))))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let leader_slots = client
+    ///     .get_leader_schedule(pubkey!("ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n"))
+    ///     .with_slot(345_600_000)
+    ///     .with_commitment(CommitmentLevel::Finalized)
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(leader_slots, Ok(Some(vec![0, 1, 2, 3])));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_leader_schedule(
+        &self,
+        params: impl Into<GetLeaderScheduleParams>,
+    ) -> GetLeaderScheduleRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetLeaderScheduleRequest::from(params.into()))
+    }
+
+    /// Call `getMinimumBalanceForRentExemption` on the SOL RPC canister.
+    ///
+    /// Returns the minimum balance in lamports required to make an account with the given data
+    /// length in bytes rent-exempt.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(2_039_280_u64)))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// // Size in bytes of an SPL Token account
+    /// let minimum_balance = client
+    ///     .get_minimum_balance_for_rent_exemption(165_u64)
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(minimum_balance, Ok(2_039_280));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_minimum_balance_for_rent_exemption(
+        &self,
+        params: impl Into<GetMinimumBalanceForRentExemptionParams>,
+    ) -> GetMinimumBalanceForRentExemptionRequestBuilder<R> {
+        RequestBuilder::new(
+            self.clone(),
+            GetMinimumBalanceForRentExemptionRequest::from(params.into()),
+        )
+    }
+
+    /// Call `getSupply` on the SOL RPC canister.
+    ///
+    /// The resulting total, circulating and non-circulating supplies are each rounded down to
+    /// tolerate providers being at slightly different slots, see
+    /// [`GetSupplyRpcConfig::DEFAULT_ROUNDING_ERROR`](sol_rpc_types::GetSupplyRpcConfig::DEFAULT_ROUNDING_ERROR).
+    /// The addresses of the non-circulating accounts are sorted, so that the lists returned by
+    /// the different providers can be compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{MultiRpcResult, Supply};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(Supply {
+    /// #       total: 589_000_123_000_000_000,
+    /// #       circulating: 426_000_012_000_000_000,
+    /// #       non_circulating: 163_000_111_000_000_000,
+    /// #       non_circulating_accounts: vec![],
+    /// #   })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let supply = client
+    ///     .get_supply()
+    ///     .with_exclude_non_circulating_accounts_list(true)
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(supply.circulating, 426_000_012_000_000_000);
+    /// assert!(supply.non_circulating_accounts.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_supply(&self) -> GetSupplyRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetSupplyRequest::default())
+    }
+
+    /// Call `getTokenAccountBalance` on the SOL RPC canister.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    /// use solana_account_decoder_client_types::token::UiTokenAmount;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{MultiRpcResult, TokenAmount};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(TokenAmount {
+    /// #       ui_amount: Some(251153323.575906),
+    /// #       decimals: 6,
+    /// #       amount: "251153323575906".to_string(),
+    /// #       ui_amount_string: "251153323.575906".to_string(),
+    /// #    })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let balance = client
+    ///     .get_token_account_balance(pubkey!("3emsAVdmGKERbHjmGfQ6oZ1e35dkf5iYcS6U4CPKFVaa"))
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(balance, Ok(UiTokenAmount {
+    ///         ui_amount: Some(251153323.575906),
+    ///         decimals: 6,
+    ///         amount: "251153323575906".to_string(),
+    ///         ui_amount_string: "251153323.575906".to_string(),
+    /// }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_token_account_balance(
+        &self,
+        params: impl Into<GetTokenAccountBalanceParams>,
+    ) -> GetTokenAccountBalanceRequestBuilder<R> {
+        RequestBuilder::new(
+            self.clone(),
+            GetTokenAccountBalanceRequest::new(params.into()),
+        )
+    }
+
+    /// Call `getTokenAccountsByDelegate` on the SOL RPC canister.
+    ///
+    /// The returned accounts are sorted by address, so that the responses of different providers
+    /// can be compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster, TokenAccountsFilter};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{AccountData, AccountEncoding, AccountInfo, KeyedAccount, MultiRpcResult};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![KeyedAccount {
+    /// #       pubkey: pubkey!("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp").into(),
+    /// #       account: AccountInfo {
+    /// #           lamports: 2_039_280,
+    /// #           data: AccountData::Binary("".to_string(), AccountEncoding::Base64),
+    /// #           owner: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+    /// #           executable: false,
+    /// #           rent_epoch: 18_446_744_073_709_551_615,
+    /// #           space: 165,
+    /// #       },
+    /// #   }])))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let accounts = client
+    ///     .get_token_accounts_by_delegate((
+    ///         pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T"),
+    ///         TokenAccountsFilter::Mint(pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").into()),
+    ///     ))
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(accounts.len(), 1);
+    /// assert_eq!(
+    ///     accounts[0].pubkey,
+    ///     pubkey!("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp").into()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_token_accounts_by_delegate(
+        &self,
+        params: impl Into<GetTokenAccountsByDelegateParams>,
+    ) -> GetTokenAccountsByDelegateRequestBuilder<R> {
+        RequestBuilder::new(
+            self.clone(),
+            GetTokenAccountsByDelegateRequest::from(params.into()),
+        )
+    }
+
+    /// Call `getTokenAccountsByOwner` on the SOL RPC canister.
+    ///
+    /// The returned accounts are sorted by address and can be paginated with
+    /// [`GetTokenAccountsByOwnerRequestBuilder::with_after`] and
+    /// [`GetTokenAccountsByOwnerRequestBuilder::with_limit`]. For owners with many token accounts,
+    /// use [`GetTokenAccountsByOwnerRequestBuilder::try_send_all_pages`] to fetch all of them
+    /// page by page, together with a `dataSlice` to keep the responses small.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{DataSlice, RpcSources, SolanaCluster, TokenAccountsFilter};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{AccountData, AccountEncoding, AccountInfo, KeyedAccount, MultiRpcResult};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![KeyedAccount {
+    /// #       pubkey: pubkey!("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp").into(),
+    /// #       account: AccountInfo {
+    /// #           lamports: 2_039_280,
+    /// #           data: AccountData::Binary("".to_string(), AccountEncoding::Base64),
+    /// #           owner: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+    /// #           executable: false,
+    /// #           rent_epoch: 18_446_744_073_709_551_615,
+    /// #           space: 165,
+    /// #       },
+    /// #   }])))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let accounts = client
+    ///     .get_token_accounts_by_owner((
+    ///         pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T"),
+    ///         TokenAccountsFilter::Mint(pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").into()),
+    ///     ))
+    ///     .with_data_slice(DataSlice { offset: 0, length: 72 })
+    ///     .try_send_all_pages()
+    ///     .await
+    ///     .unwrap()
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(accounts.len(), 1);
+    /// assert_eq!(
+    ///     accounts[0].pubkey,
+    ///     pubkey!("28YTZEwqtMHWrhWcvv34se7pjS7wctgqzCPB3gReCFKp").into()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_token_accounts_by_owner(
+        &self,
+        params: impl Into<GetTokenAccountsByOwnerParams>,
+    ) -> GetTokenAccountsByOwnerRequestBuilder<R> {
+        RequestBuilder::new(
+            self.clone(),
+            GetTokenAccountsByOwnerRequest::from(params.into()),
+        )
+    }
+
+    /// Call `getRecentPerformanceSamples` on the SOL RPC canister.
+    ///
+    /// Returns the most recent performance samples, from the most recent to the oldest one, which
+    /// can be used to compute the number of transactions per second processed by the network.
+    /// Samples are taken every 60 seconds and only the samples of the last hour are returned
+    /// unless a different limit is set with
+    /// [`with_limit`](GetRecentPerformanceSamplesRequestBuilder::with_limit).
+    ///
+    /// Note that each Solana node records its own performance samples, so that the responses of
+    /// different providers are likely to differ. Consider using a single provider or a
+    /// [`ConsensusStrategy::Threshold`] to avoid inconsistent results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{
+    ///     GetRecentPerformanceSamplesLimit, MultiRpcResult, PerformanceSample, RpcSources,
+    ///     SolanaCluster,
+    /// };
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![PerformanceSample {
+    /// #       slot: 348_125_000,
+    /// #       num_transactions: 240_000,
+    /// #       num_non_vote_transactions: Some(60_000),
+    /// #       num_slots: 150,
+    /// #       sample_period_secs: 60,
+    /// #   }])))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let samples = client
+    ///     .get_recent_performance_samples()
+    ///     .with_limit(GetRecentPerformanceSamplesLimit::try_from(1).unwrap())
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(samples[0].transactions_per_second(), Some(4_000.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_recent_performance_samples(&self) -> GetRecentPerformanceSamplesRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetRecentPerformanceSamplesRequest::default())
+    }
+
+    /// Call `getRecentPrioritizationFees` on the SOL RPC canister.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::num::NonZeroU8;
+    /// use sol_rpc_types::{MultiRpcResult, PrioritizationFee, TokenAmount};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![PrioritizationFee{slot: 338637772, prioritization_fee: 166667}])))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let fees = client
+    ///     .get_recent_prioritization_fees(&[pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")])
+    ///     .unwrap()
+    ///     .with_max_length(NonZeroU8::MIN)
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!
+    ///     (fees,
+    ///     Ok(vec![ PrioritizationFee {
+    ///         slot: 338637772,
+    ///         prioritization_fee: 166667
+    ///     }]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The number of account addresses that can be passed to
+    /// [`getRecentPrioritizationFees`](https://solana.com/de/docs/rpc/http/getrecentprioritizationfees)
+    /// is limited to 128. More accounts result in an error.
+    ///
+    /// ```rust
+    /// use std::collections::BTreeSet;
+    /// use assert_matches::assert_matches;
+    /// use solana_pubkey::Pubkey;
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster, RpcError};
+    ///
+    /// let client = SolRpcClient::builder_for_ic()
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let too_many_accounts: BTreeSet<Pubkey> = (0..129_u8)
+    ///     .map(|i| Pubkey::from([i; 32]))
+    ///     .collect();
+    /// assert_eq!(too_many_accounts.len(), 129);
+    ///
+    /// let err = client.get_recent_prioritization_fees(&too_many_accounts).unwrap_err();
+    /// assert_matches!(err, RpcError::ValidationError(_));
+    /// ```
+    pub fn get_recent_prioritization_fees<'a, I>(
+        &self,
+        addresses: I,
+    ) -> RpcResult<GetRecentPrioritizationFeesRequestBuilder<R>>
+    where
+        I: IntoIterator<Item = &'a solana_pubkey::Pubkey>,
+    {
+        let params = GetRecentPrioritizationFeesParams::try_from(
+            addresses.into_iter().map(Pubkey::from).collect::<Vec<_>>(),
         )?;
         Ok(RequestBuilder::new(
             self.clone(),
@@ -751,26 +1742,70 @@ impl<R> SolRpcClient<R> {
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = SolRpcClient::builder_for_ic()
-    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let slot = client
+    ///     .get_slot()
+    ///     .with_params(GetSlotParams {
+    ///         commitment: Some(CommitmentLevel::Finalized),
+    ///         ..Default::default()
+    ///     })
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(slot, Ok(332_577_897_u64));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_slot(&self) -> GetSlotRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetSlotRequest::default())
+    }
+
+    /// Call `getSlotLeaders` on the SOL RPC canister.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{GetSlotLeadersLimit, RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(vec![
+    /// #       sol_rpc_types::Pubkey::from(pubkey!("ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n")),
+    /// #       sol_rpc_types::Pubkey::from(pubkey!("Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM")),
+    /// #   ])))
     ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
     ///     .build();
     ///
-    /// let slot = client
-    ///     .get_slot()
-    ///     .with_params(GetSlotParams {
-    ///         commitment: Some(CommitmentLevel::Finalized),
-    ///         ..Default::default()
-    ///     })
+    /// let leaders = client
+    ///     .get_slot_leaders(345_600_000)
+    ///     .with_limit(GetSlotLeadersLimit::try_from(2).unwrap())
     ///     .send()
     ///     .await
     ///     .expect_consistent();
     ///
-    /// assert_eq!(slot, Ok(332_577_897_u64));
+    /// assert_eq!(
+    ///     leaders,
+    ///     Ok(vec![
+    ///         pubkey!("ChorusmmK7i1AxXeiTtQgQZhQNiXYU84ULeaYF1EH15n"),
+    ///         pubkey!("Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM"),
+    ///     ])
+    /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_slot(&self) -> GetSlotRequestBuilder<R> {
-        RequestBuilder::new(self.clone(), GetSlotRequest::default())
+    pub fn get_slot_leaders(
+        &self,
+        params: impl Into<GetSlotLeadersParams>,
+    ) -> GetSlotLeadersRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetSlotLeadersRequest::from(params.into()))
     }
 
     /// Call `getTransaction` on the SOL RPC canister.
@@ -852,6 +1887,95 @@ impl<R> SolRpcClient<R> {
         RequestBuilder::new(self.clone(), GetTransactionRequest::new(params.into()))
     }
 
+    /// Call `getTransactionCount` on the SOL RPC canister.
+    ///
+    /// The resulting transaction count is rounded down to tolerate providers being at slightly
+    /// different slots, see
+    /// [`GetTransactionCountRpcConfig::DEFAULT_ROUNDING_ERROR`](sol_rpc_types::GetTransactionCountRpcConfig::DEFAULT_ROUNDING_ERROR).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{CommitmentLevel, MultiRpcResult, RpcSources, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(268_974_500_000_u64)))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let transaction_count = client
+    ///     .get_transaction_count()
+    ///     .with_commitment(CommitmentLevel::Finalized)
+    ///     .with_rounding_error(100_000)
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(transaction_count, Ok(268_974_500_000_u64));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_transaction_count(&self) -> GetTransactionCountRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), GetTransactionCountRequest::default())
+    }
+
+    /// Call `minimumLedgerSlot` on the SOL RPC canister.
+    ///
+    /// The result is the lowest slot for which the node has information in its ledger, i.e. older
+    /// blocks and transactions can no longer be fetched from that node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{MultiRpcResult, RpcSources, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(368_500_000_u64)))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let minimum_ledger_slot = client
+    ///     .minimum_ledger_slot()
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent();
+    ///
+    /// assert_eq!(minimum_ledger_slot, Ok(368_500_000));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn minimum_ledger_slot(&self) -> MinimumLedgerSlotRequestBuilder<R> {
+        RequestBuilder::new(self.clone(), MinimumLedgerSlotRequest)
+    }
+
+    /// Call `sendTransaction` on the SOL RPC canister.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given parameters cannot be converted into [`SendTransactionParams`],
+    /// e.g. if the transaction cannot be serialized. Use
+    /// [`try_send_transaction`](SolRpcClient::try_send_transaction) instead.
+    #[deprecated(
+        since = "6.1.0",
+        note = "panics on invalid parameters, use `try_send_transaction` instead"
+    )]
+    pub fn send_transaction<T>(&self, params: T) -> SendTransactionRequestBuilder<R>
+    where
+        T: TryInto<SendTransactionParams>,
+        <T as TryInto<SendTransactionParams>>::Error: Debug,
+    {
+        let params = params
+            .try_into()
+            .expect("Unable to build request parameters");
+        RequestBuilder::new(self.clone(), SendTransactionRequest::new(params))
+    }
+
     /// Call `sendTransaction` on the SOL RPC canister.
     ///
     /// # Examples
@@ -871,10 +1995,10 @@ impl<R> SolRpcClient<R> {
     ///     .build();
     ///
     /// let transaction_id = client
-    ///     .send_transaction(SendTransactionParams::from_encoded_transaction(
+    ///     .try_send_transaction(SendTransactionParams::from_encoded_transaction(
     ///         "ASy...pwEC".to_string(),
     ///         SendTransactionEncoding::Base64,
-    ///     ))
+    ///     ))?
     ///     .send()
     ///     .await
     ///     .expect_consistent();
@@ -886,15 +2010,39 @@ impl<R> SolRpcClient<R> {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn send_transaction<T>(&self, params: T) -> SendTransactionRequestBuilder<R>
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`RpcError::ValidationError`] if the
+    /// given parameters cannot be converted into [`SendTransactionParams`], e.g. if the
+    /// transaction cannot be serialized.
+    pub fn try_send_transaction<T>(&self, params: T) -> RpcResult<SendTransactionRequestBuilder<R>>
     where
         T: TryInto<SendTransactionParams>,
-        <T as TryInto<SendTransactionParams>>::Error: Debug,
+        RpcError: From<<T as TryInto<SendTransactionParams>>::Error>,
     {
-        let params = params
-            .try_into()
-            .expect("Unable to build request parameters");
-        RequestBuilder::new(self.clone(), SendTransactionRequest::new(params))
+        let params = params.try_into()?;
+        Ok(RequestBuilder::new(
+            self.clone(),
+            SendTransactionRequest::new(params),
+        ))
+    }
+
+    /// Call `jsonRequest` on the SOL RPC canister.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given JSON value cannot be serialized. Use
+    /// [`try_json_request`](SolRpcClient::try_json_request) instead.
+    #[deprecated(
+        since = "6.1.0",
+        note = "panics on invalid JSON requests, use `try_json_request` instead"
+    )]
+    pub fn json_request(&self, json_request: serde_json::Value) -> JsonRequestBuilder<R> {
+        RequestBuilder::new(
+            self.clone(),
+            JsonRequest::try_from(json_request).expect("Client error: invalid JSON request"),
+        )
     }
 
     /// Call `jsonRequest` on the SOL RPC canister.
@@ -927,11 +2075,11 @@ impl<R> SolRpcClient<R> {
     ///     .build();
     ///
     /// let version: serde_json::Value = client
-    ///     .json_request(json!({
+    ///     .try_json_request(json!({
     ///             "jsonrpc": "2.0",
     ///             "id": 1,
     ///             "method": "getVersion"
-    ///         }))
+    ///         }))?
     ///     .send()
     ///     .await
     ///     .expect_consistent()
@@ -952,36 +2100,380 @@ impl<R> SolRpcClient<R> {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn json_request(&self, json_request: serde_json::Value) -> JsonRequestBuilder<R> {
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`RpcError::ValidationError`] if the
+    /// given JSON value cannot be serialized.
+    pub fn try_json_request(
+        &self,
+        json_request: serde_json::Value,
+    ) -> RpcResult<JsonRequestBuilder<R>> {
+        let request = JsonRequest::try_from(json_request).map_err(RpcError::ValidationError)?;
+        Ok(RequestBuilder::new(self.clone(), request))
+    }
+
+    /// Call `rawHttpRequest` on the SOL RPC canister.
+    ///
+    /// This method sends the given body as an HTTP `POST` request to the selected providers,
+    /// without interpreting it as a JSON-RPC request. This is useful for provider-specific
+    /// extensions that are not part of the Solana JSON-RPC API.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{MultiRpcResult, RawHttpResponse, RpcSources, SolanaCluster};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #    .with_stub_response(MultiRpcResult::Consistent(Ok(RawHttpResponse {
+    /// #        status: 200,
+    /// #        headers: vec![],
+    /// #        body: br#"{"ok":true}"#.to_vec(),
+    /// #    })))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let response = client
+    ///     .raw_http_request(br#"{"transactions":[]}"#.to_vec())
+    ///     .with_header("Content-Type", "application/json")
+    ///     .with_response_header("x-request-id")
+    ///     .send()
+    ///     .await
+    ///     .expect_consistent()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.status, 200);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn raw_http_request(&self, body: impl Into<Vec<u8>>) -> RawHttpRequestBuilder<R> {
         RequestBuilder::new(
             self.clone(),
-            JsonRequest::try_from(json_request).expect("Client error: invalid JSON request"),
+            RawHttpRequest::new(RawHttpRequestParams {
+                body: body.into(),
+                ..Default::default()
+            }),
         )
     }
-}
+}
+
+impl<R: Runtime> SolRpcClient<R> {
+    /// Call `getCapabilities` on the SOL RPC canister.
+    ///
+    /// Deployments of the SOL RPC canister that predate the `getCapabilities` endpoint reject the
+    /// call, in which case an error is returned instead of panicking, so that callers can fall
+    /// back to the endpoints they know to be available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{Capabilities, Mode};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(Capabilities {
+    /// #       version: "1.3.2".to_string(),
+    /// #       endpoints: vec!["getFeeForMessage".to_string()],
+    /// #       max_response_bytes: 2_000_000,
+    /// #       mode: Mode::Normal,
+    /// #       features: vec![],
+    /// #   })
+    ///     .build();
+    ///
+    /// let supports_fee_for_message = match client.get_capabilities().await {
+    ///     Ok(capabilities) => capabilities.supports_endpoint("getFeeForMessage"),
+    ///     Err(_) => false,
+    /// };
+    ///
+    /// assert!(supports_fee_for_message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_capabilities(&self) -> Result<Capabilities, IcError> {
+        self.config
+            .runtime
+            .query_call(self.config.sol_rpc_canister, "getCapabilities", ())
+            .await
+    }
+
+    /// Call `getCachedSlot` on the SOL RPC canister.
+    ///
+    /// Returns the latest slot fetched by the slot ticker of the SOL RPC canister, if enabled (see
+    /// [`sol_rpc_types::SlotTicker`]). Since this is a query, it is much cheaper than
+    /// [`SolRpcClient::get_slot`], but the returned slot may be a few seconds old, as indicated
+    /// by [`CachedSlot::timestamp`].
+    pub async fn get_cached_slot(&self) -> Result<Option<CachedSlot>, IcError> {
+        self.config
+            .runtime
+            .query_call(self.config.sol_rpc_canister, "getCachedSlot", ())
+            .await
+    }
+
+    /// Call `depositCycles` on the SOL RPC canister.
+    ///
+    /// Withdraws cycles from the account of the calling canister on the cycles ledger and adds
+    /// them to its prepaid cycles on the SOL RPC canister. The SOL RPC canister must first be
+    /// approved with `icrc2_approve` to spend at least [`DepositCyclesArgs::amount`] plus the fee
+    /// of the cycles ledger.
+    ///
+    /// The prepaid cycles are then used to pay for the requests of the calling canister to which
+    /// not enough cycles are attached, e.g. when sent with [`RequestBuilder::with_cycles`]`(0)`.
+    pub async fn deposit_cycles(
+        &self,
+        args: DepositCyclesArgs,
+    ) -> Result<Result<u128, DepositCyclesError>, IcError> {
+        self.config
+            .runtime
+            .update_call(self.config.sol_rpc_canister, "depositCycles", (args,), 0)
+            .await
+    }
+
+    /// Call `getPrepaidCycles` on the SOL RPC canister.
+    ///
+    /// Returns the prepaid cycles of the calling canister, deposited with
+    /// [`SolRpcClient::deposit_cycles`].
+    pub async fn get_prepaid_cycles(&self) -> Result<u128, IcError> {
+        self.config
+            .runtime
+            .query_call(
+                self.config.sol_rpc_canister,
+                "getPrepaidCycles",
+                (None::<Principal>,),
+            )
+            .await
+    }
+
+    /// Call `getCyclesReport` on the SOL RPC canister.
+    ///
+    /// Returns the cycles attached to and actually consumed by the most recent call of the calling
+    /// canister that made HTTPS outcalls, e.g. to adapt the amount of cycles attached to
+    /// subsequent requests, see [`RequestBuilder::with_cycles`]. Returns `None` if the SOL RPC
+    /// canister has no report for the calling canister, e.g. because it was upgraded since.
+    ///
+    /// If the calling canister makes concurrent calls, the report is for the call that
+    /// completed last.
+    pub async fn get_cycles_report(&self) -> Result<Option<CyclesReport>, IcError> {
+        self.config
+            .runtime
+            .query_call(
+                self.config.sol_rpc_canister,
+                "getCyclesReport",
+                (None::<Principal>,),
+            )
+            .await
+    }
+
+    /// Call `getResponseMetadata` on the SOL RPC canister.
+    ///
+    /// Returns metadata about the responses of the providers, such as their latency and whether
+    /// an API key was used, to the most recent call of the calling canister that returned a
+    /// [`MultiRpcResult::Inconsistent`] result. Returns `None` if the SOL RPC canister has no
+    /// such metadata for the calling canister, e.g. because it was upgraded since.
+    pub async fn get_response_metadata(
+        &self,
+    ) -> Result<Option<Vec<(RpcSource, ResponseMetadata)>>, IcError> {
+        self.config
+            .runtime
+            .query_call(
+                self.config.sol_rpc_canister,
+                "getResponseMetadata",
+                (None::<Principal>,),
+            )
+            .await
+    }
+
+    /// Call `getProviderScorecard` on the SOL RPC canister.
+    ///
+    /// Returns the reliability statistics of the supported providers aggregated over the given
+    /// month, or over the current month if `None`, e.g. as evidence when proposing to add or
+    /// remove providers from the default set of providers. Returns `None` if no request was made
+    /// during that month or if its scorecard was evicted.
+    pub async fn get_provider_scorecard(
+        &self,
+        period: Option<ScorecardPeriod>,
+    ) -> Result<Option<ProviderScorecard>, IcError> {
+        self.config
+            .runtime
+            .query_call(
+                self.config.sol_rpc_canister,
+                "getProviderScorecard",
+                (period,),
+            )
+            .await
+    }
+
+    /// Call `getShadowProviderStats` on the SOL RPC canister.
+    ///
+    /// Returns the statistics of the providers queried in shadow mode since the last upgrade of
+    /// the SOL RPC canister, e.g. to evaluate a provider before adding it to the default set of
+    /// providers.
+    pub async fn get_shadow_provider_stats(&self) -> Result<Vec<ShadowProviderStats>, IcError> {
+        self.config
+            .runtime
+            .query_call(self.config.sol_rpc_canister, "getShadowProviderStats", ())
+            .await
+    }
+
+    /// Call `getProviders` on the SOL RPC canister.
+    pub async fn get_providers(&self) -> Vec<(SupportedRpcProviderId, SupportedRpcProvider)> {
+        self.config
+            .runtime
+            .query_call(self.config.sol_rpc_canister, "getProviders", ())
+            .await
+            .unwrap()
+    }
+
+    /// Call `updateApiKeys` on the SOL RPC canister.
+    pub async fn update_api_keys(&self, api_keys: &[(SupportedRpcProviderId, Option<String>)]) {
+        self.config
+            .runtime
+            .update_call(
+                self.config.sol_rpc_canister,
+                "updateApiKeys",
+                (api_keys.to_vec(),),
+                0,
+            )
+            .await
+            .unwrap()
+    }
+
+    /// Call `updateAndValidateApiKeys` on the SOL RPC canister.
+    ///
+    /// Same as [`SolRpcClient::update_api_keys`], but additionally returns the health of each
+    /// provider whose API key was inserted or updated, as reported by the Solana `getHealth` RPC
+    /// method, so that invalid or revoked API keys are detected when they are rotated. At most 10
+    /// API keys can be inserted or updated in a single call.
+    pub async fn update_and_validate_api_keys(
+        &self,
+        api_keys: &[(SupportedRpcProviderId, Option<String>)],
+    ) -> Vec<(SupportedRpcProviderId, ProviderHealth)> {
+        self.config
+            .runtime
+            .update_call(
+                self.config.sol_rpc_canister,
+                "updateAndValidateApiKeys",
+                (api_keys.to_vec(),),
+                0,
+            )
+            .await
+            .unwrap()
+    }
+
+    /// Call `updateCustomApiKeys` on the SOL RPC canister.
+    pub async fn update_custom_api_keys(&self, api_keys: &[(String, Option<String>)]) {
+        self.config
+            .runtime
+            .update_call(
+                self.config.sol_rpc_canister,
+                "updateCustomApiKeys",
+                (api_keys.to_vec(),),
+                0,
+            )
+            .await
+            .unwrap()
+    }
+
+    /// Call `prepareUpgrade` on the SOL RPC canister.
+    ///
+    /// Makes the SOL RPC canister reject new requests with [`RpcError::UpgradeInProgress`] and
+    /// waits for its pending HTTPS outcalls to complete, for at most the given number of seconds.
+    /// The returned [`UpgradeReadiness`] tells whether the SOL RPC canister can be upgraded
+    /// without making in-flight requests fail. Only callable by a controller.
+    pub async fn prepare_upgrade(
+        &self,
+        timeout_seconds: Option<u32>,
+    ) -> Result<UpgradeReadiness, IcError> {
+        self.config
+            .runtime
+            .update_call(
+                self.config.sol_rpc_canister,
+                "prepareUpgrade",
+                (timeout_seconds,),
+                0,
+            )
+            .await
+    }
 
-impl<R: Runtime> SolRpcClient<R> {
-    /// Call `getProviders` on the SOL RPC canister.
-    pub async fn get_providers(&self) -> Vec<(SupportedRpcProviderId, SupportedRpcProvider)> {
+    /// Call `cancelUpgrade` on the SOL RPC canister.
+    ///
+    /// Makes the SOL RPC canister accept new requests again after
+    /// [`SolRpcClient::prepare_upgrade`], e.g. because the upgrade was aborted. Only callable by a
+    /// controller.
+    pub async fn cancel_upgrade(&self) -> Result<(), IcError> {
         self.config
             .runtime
-            .query_call(self.config.sol_rpc_canister, "getProviders", ())
+            .update_call(self.config.sol_rpc_canister, "cancelUpgrade", (), 0)
             .await
-            .unwrap()
     }
 
-    /// Call `updateApiKeys` on the SOL RPC canister.
-    pub async fn update_api_keys(&self, api_keys: &[(SupportedRpcProviderId, Option<String>)]) {
+    /// Call `getClusterHealth` on the SOL RPC canister.
+    ///
+    /// The amount of cycles to attach is first computed with `getClusterHealthCyclesCost`.
+    /// The returned vector contains one [`ProviderHealthReport`] for each of the providers selected
+    /// by the client's [`RpcSources`] and [`RpcConfig`].
+    pub async fn get_cluster_health(
+        &self,
+    ) -> Result<RpcResult<Vec<ProviderHealthReport>>, IcError> {
+        let args = (
+            self.config.rpc_sources.clone(),
+            self.config.rpc_config.clone(),
+        );
+        let cycles = match self
+            .config
+            .runtime
+            .query_call::<_, RpcResult<u128>>(
+                self.config.sol_rpc_canister,
+                "getClusterHealthCyclesCost",
+                args.clone(),
+            )
+            .await?
+        {
+            Ok(cycles) => cycles,
+            Err(e) => return Ok(Err(e)),
+        };
         self.config
             .runtime
             .update_call(
                 self.config.sol_rpc_canister,
-                "updateApiKeys",
-                (api_keys.to_vec(),),
-                0,
+                "getClusterHealth",
+                args,
+                cycles,
+            )
+            .await
+    }
+
+    /// Call `previewRequest` on the SOL RPC canister.
+    ///
+    /// Returns the HTTP requests that the SOL RPC canister would send for the given Solana
+    /// JSON-RPC method and parameters to each of the providers selected by the client's
+    /// [`RpcSources`] and [`RpcConfig`], without performing any HTTPS outcall.
+    /// API keys are redacted from the returned URLs and headers.
+    ///
+    /// Unless the SOL RPC canister is in demo mode, this method must be called by one of its
+    /// controllers.
+    pub async fn preview_request(
+        &self,
+        method: impl ToString,
+        params: serde_json::Value,
+    ) -> Result<RpcResult<Vec<HttpRequestPreview>>, IcError> {
+        self.config
+            .runtime
+            .query_call(
+                self.config.sol_rpc_canister,
+                "previewRequest",
+                (
+                    method.to_string(),
+                    self.config.rpc_sources.clone(),
+                    self.config.rpc_config.clone(),
+                    params.to_string(),
+                ),
             )
             .await
-            .unwrap()
     }
 
     /// Fetch a recent block based on successive calls to `getSlot` and `getBlock`.
@@ -1064,6 +2556,525 @@ impl<R: Runtime> SolRpcClient<R> {
         GetRecentBlockRequestBuilder::new(self.clone())
     }
 
+    /// Transfer lamports from the account controlled by the calling canister with the given
+    /// threshold Ed25519 key and derivation path, which also pays the transaction fee.
+    ///
+    /// The returned [`TransferRequestBuilder`](transfer::TransferRequestBuilder) builds the
+    /// transfer transaction with a recent blockhash, signs it with `sign_with_schnorr`, submits
+    /// it and waits until it is confirmed. See the [`transfer`] module for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::{
+    ///     ed25519::{DerivationPath, Ed25519KeyId},
+    ///     SolRpcClient,
+    /// };
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use ic_management_canister_types::{SchnorrPublicKeyResult, SignWithSchnorrResult};
+    /// # use sol_rpc_types::{ConfirmedBlock, MultiRpcResult, Signature, TransactionConfirmationStatus, TransactionStatus};
+    /// # use std::str::FromStr;
+    /// # let signature = Signature::from_str("37HbmunhjSC1xxnVsaFX2xaS8gYnb5JYiLy9B51Ky9Up69aF7Qra6dHSLMCaiurRYq3Y8ZxSVUwC5sntziWuhZee").unwrap();
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_responses()
+    /// #   .add_stub_response(SchnorrPublicKeyResult {
+    /// #       public_key: pubkey!("BPebStjcgCPnWTK3FXZJ8KhqwNYLk9aubC9b4Cgqb6oE").as_ref().to_vec(),
+    /// #       chain_code: "UWbC6EgDnWEJIU4KFBqASTCYAzEiJGsR".as_bytes().to_vec(),
+    /// #   })
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(Some(ConfirmedBlock {
+    /// #       previous_blockhash: Default::default(),
+    /// #       blockhash: Default::default(),
+    /// #       parent_slot: 332_577_896,
+    /// #       block_time: None,
+    /// #       block_height: None,
+    /// #       signatures: None,
+    /// #       rewards: None,
+    /// #       num_reward_partitions: None,
+    /// #       transactions: None,
+    /// #   }))))
+    /// #   .add_stub_response(SignWithSchnorrResult { signature: signature.as_ref().to_vec() })
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(signature.clone())))
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(vec![Some(TransactionStatus {
+    /// #       slot: 332_577_900,
+    /// #       status: Ok(()),
+    /// #       err: None,
+    /// #       confirmation_status: Some(TransactionConfirmationStatus::Confirmed),
+    /// #   })])))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Devnet))
+    ///     .build();
+    ///
+    /// let signature = client
+    ///     .transfer_sol(
+    ///         Ed25519KeyId::MainnetTestKey1,
+    ///         DerivationPath::from(b"wallet".as_slice()),
+    ///         pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+    ///         1_000_000,
+    ///     )
+    ///     .try_send()
+    ///     .await;
+    ///
+    /// assert_eq!(
+    ///     signature.unwrap().to_string(),
+    ///     "37HbmunhjSC1xxnVsaFX2xaS8gYnb5JYiLy9B51Ky9Up69aF7Qra6dHSLMCaiurRYq3Y8ZxSVUwC5sntziWuhZee"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "ed25519")]
+    pub fn transfer_sol(
+        &self,
+        key_id: ed25519::Ed25519KeyId,
+        payer: ed25519::DerivationPath,
+        recipient: solana_pubkey::Pubkey,
+        lamports: u64,
+    ) -> transfer::TransferRequestBuilder<R> {
+        transfer::TransferRequestBuilder::new(
+            self.clone(),
+            key_id,
+            payer,
+            transfer::Transfer::Sol {
+                recipient,
+                lamports,
+            },
+        )
+    }
+
+    /// Transfer SPL tokens of the given mint from the associated token account of the account
+    /// controlled by the calling canister with the given threshold Ed25519 key and derivation
+    /// path, which also pays the transaction fee, to the associated token account of the
+    /// recipient.
+    ///
+    /// The mint account is first fetched to determine its token program (SPL Token or
+    /// Token-2022) and its number of decimals, which are used to build a `TransferChecked`
+    /// instruction. The associated token account of the recipient must already exist.
+    /// The transfer is then signed, submitted and confirmed as with [`SolRpcClient::transfer_sol`].
+    #[cfg(feature = "ed25519")]
+    pub fn transfer_spl(
+        &self,
+        key_id: ed25519::Ed25519KeyId,
+        payer: ed25519::DerivationPath,
+        mint: solana_pubkey::Pubkey,
+        recipient: solana_pubkey::Pubkey,
+        amount: u64,
+    ) -> transfer::TransferRequestBuilder<R> {
+        transfer::TransferRequestBuilder::new(
+            self.clone(),
+            key_id,
+            payer,
+            transfer::Transfer::Spl {
+                mint,
+                recipient,
+                amount,
+            },
+        )
+    }
+
+    /// Watch an address for new transactions by polling `getSignaturesForAddress`.
+    ///
+    /// Each call to [`AddressWatcher::poll`](watch::AddressWatcher::poll) only returns the
+    /// signatures that were not returned by a previous poll, which makes it suitable to be called
+    /// periodically, e.g. from a canister timer. See [`watch::AddressWatcher`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{ConfirmedTransactionStatusWithSignature, MultiRpcResult, Signature, TransactionConfirmationStatus};
+    /// # use std::str::FromStr;
+    /// # let status = |signature: &str| ConfirmedTransactionStatusWithSignature {
+    /// #     signature: Signature::from_str(signature).unwrap(),
+    /// #     slot: 340_372_399,
+    /// #     err: None,
+    /// #     memo: None,
+    /// #     block_time: Some(1_747_389_084),
+    /// #     confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+    /// # };
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_responses()
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(vec![
+    /// #       status("5iBbqBJzgqafuQn93Np8ztWyXeYe2ReGPzUB1zXP2suZ8b5EaxSwe74ZUhg5pZQuDQkNGW7XApgfXX91YLYUuo5y"),
+    /// #   ])))
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(Vec::<ConfirmedTransactionStatusWithSignature>::new())))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let mut watcher = client.watch_address(pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"));
+    ///
+    /// let new_signatures = watcher.poll().await.unwrap();
+    /// assert_eq!(new_signatures.len(), 1);
+    ///
+    /// // No new transactions since the previous poll
+    /// let new_signatures = watcher.poll().await.unwrap();
+    /// assert_eq!(new_signatures, vec![]);
+    /// assert_eq!(
+    ///     watcher.cursor().last_seen,
+    ///     Some("5iBbqBJzgqafuQn93Np8ztWyXeYe2ReGPzUB1zXP2suZ8b5EaxSwe74ZUhg5pZQuDQkNGW7XApgfXX91YLYUuo5y".parse().unwrap())
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_address(&self, pubkey: impl Into<Pubkey>) -> watch::AddressWatcher<R> {
+        watch::AddressWatcher::new(self.clone(), pubkey.into())
+    }
+
+    /// Fetch and decode a stake account with `getAccountInfo`.
+    ///
+    /// The account data is requested with the `base64` encoding and decoded with
+    /// [`stake_account_from_account`](stake::stake_account_from_account).
+    /// This replaces the deprecated [`getStakeActivation`](https://solana.com/docs/rpc/deprecated/getstakeactivation)
+    /// RPC method. The method returns `Ok(None)` if the account does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_client::fixtures::stake_account;
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(Some(stake_account()))))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let stake_account = client
+    ///     .get_stake_account(pubkey!("5PgXd3GRSvEBvm1iVGPRJgAanMHpNmvLqZLzd7xZUNLa"))
+    ///     .await
+    ///     .unwrap()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stake_account.rent_exempt_reserve(), 2_282_880);
+    /// assert_eq!(stake_account.delegated_amount(), 1_000_000_000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_stake_account(
+        &self,
+        pubkey: impl Into<Pubkey>,
+    ) -> Result<Option<stake::StakeAccount>, stake::GetStakeAccountError> {
+        use stake::GetStakeAccountError;
+
+        let account = match self
+            .get_account_info(GetAccountInfoParams::from_pubkey(pubkey))
+            .with_encoding(GetAccountInfoEncoding::Base64)
+            .try_send()
+            .await
+        {
+            Ok(MultiRpcResult::Consistent(Ok(account))) => account,
+            Ok(MultiRpcResult::Consistent(Err(e))) => {
+                return Err(GetStakeAccountError::RpcError(e))
+            }
+            Ok(MultiRpcResult::Inconsistent(results)) => {
+                return Err(GetStakeAccountError::ConsensusError(results))
+            }
+            Err(e) => return Err(GetStakeAccountError::IcError(e)),
+        };
+        account
+            .map(|account| stake::stake_account_from_account(&account))
+            .transpose()
+            .map_err(GetStakeAccountError::InvalidStakeAccount)
+    }
+
+    /// Fetch and decode an address lookup table with `getAccountInfo`.
+    ///
+    /// The account data is requested with the `base64` encoding and decoded with
+    /// [`address_lookup_table_from_account`](address_lookup_table::address_lookup_table_from_account).
+    /// The fetched tables can be used to resolve the addresses loaded by a versioned transaction
+    /// with [`resolve_loaded_addresses`](address_lookup_table::resolve_loaded_addresses).
+    /// The method returns `Ok(None)` if the account does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_client::fixtures::address_lookup_table_account;
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(Some(address_lookup_table_account()))))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let table = client
+    ///     .get_address_lookup_table(pubkey!("2immgwYNHBbyVQKVGCEkgWpi53bLwWNRMB5G2nbgYV17"))
+    ///     .await
+    ///     .unwrap()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(table.addresses.len(), 3);
+    /// assert!(!table.meta.is_deactivated());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_address_lookup_table(
+        &self,
+        pubkey: impl Into<Pubkey>,
+    ) -> Result<
+        Option<address_lookup_table::AddressLookupTable>,
+        address_lookup_table::GetAddressLookupTableError,
+    > {
+        use address_lookup_table::GetAddressLookupTableError;
+
+        let pubkey: Pubkey = pubkey.into();
+        let account = match self
+            .get_account_info(GetAccountInfoParams::from_pubkey(pubkey.clone()))
+            .with_encoding(GetAccountInfoEncoding::Base64)
+            .try_send()
+            .await
+        {
+            Ok(MultiRpcResult::Consistent(Ok(account))) => account,
+            Ok(MultiRpcResult::Consistent(Err(e))) => {
+                return Err(GetAddressLookupTableError::RpcError(e))
+            }
+            Ok(MultiRpcResult::Inconsistent(results)) => {
+                return Err(GetAddressLookupTableError::ConsensusError(results))
+            }
+            Err(e) => return Err(GetAddressLookupTableError::IcError(e)),
+        };
+        account
+            .map(|account| {
+                address_lookup_table::address_lookup_table_from_account(pubkey, &account)
+            })
+            .transpose()
+            .map_err(GetAddressLookupTableError::InvalidAddressLookupTable)
+    }
+
+    /// Fetch the balances of several accounts with concurrent `getBalance` calls.
+    ///
+    /// Duplicate public keys are only fetched once and at most
+    /// [`MAX_GET_BALANCE_MANY_PUBKEYS`](balance::MAX_GET_BALANCE_MANY_PUBKEYS) distinct public
+    /// keys can be given. Since the calls are made concurrently, the cycles attached to each of
+    /// them must be available at the same time. The method fails with the error of the first
+    /// public key, in ascending order, whose balance could not be fetched.
+    ///
+    /// Use [`SolRpcClient::get_balance`] to set the commitment or the minimum context slot.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(1_000_000_000_u64)))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let alice = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+    /// let bob = pubkey!("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+    /// let balances = client.get_balance_many([alice, bob, alice]).await.unwrap();
+    ///
+    /// assert_eq!(balances.len(), 2);
+    /// assert_eq!(balances[&alice], 1_000_000_000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_balance_many(
+        &self,
+        pubkeys: impl IntoIterator<Item = solana_pubkey::Pubkey>,
+    ) -> Result<BTreeMap<solana_pubkey::Pubkey, Lamport>, balance::GetBalanceManyError> {
+        use balance::{GetBalanceManyError, MAX_GET_BALANCE_MANY_PUBKEYS};
+
+        let pubkeys: BTreeSet<_> = pubkeys.into_iter().collect();
+        if pubkeys.len() > MAX_GET_BALANCE_MANY_PUBKEYS {
+            return Err(GetBalanceManyError::TooManyPubkeys {
+                max: MAX_GET_BALANCE_MANY_PUBKEYS,
+                actual: pubkeys.len(),
+            });
+        }
+        let results = join_all(
+            pubkeys
+                .iter()
+                .map(|pubkey| self.get_balance(*pubkey).try_send()),
+        )
+        .await;
+        pubkeys
+            .into_iter()
+            .zip(results)
+            .map(|(pubkey, result)| match result {
+                Ok(MultiRpcResult::Consistent(Ok(balance))) => Ok((pubkey, balance)),
+                Ok(MultiRpcResult::Consistent(Err(error))) => {
+                    Err(GetBalanceManyError::RpcError { pubkey, error })
+                }
+                Ok(MultiRpcResult::Inconsistent(results)) => {
+                    Err(GetBalanceManyError::ConsensusError { pubkey, results })
+                }
+                Err(error) => Err(GetBalanceManyError::IcError { pubkey, error }),
+            })
+            .collect()
+    }
+
+    /// Estimate the total cost in lamports of sending a transaction with the given message.
+    ///
+    /// The estimate combines the following RPC calls, see the [`fee`] module for more details:
+    /// * `getFeeForMessage` for the base fee of the message;
+    /// * `getRecentPrioritizationFees` for the writable accounts of the message, if the message
+    ///   does not set a compute unit price with a `SetComputeUnitPrice` instruction;
+    /// * `getMinimumBalanceForRentExemption` for every account created by the message.
+    ///
+    /// If the message does not set a compute unit limit with a `SetComputeUnitLimit`
+    /// instruction, the compute unit limit used to compute the prioritization fee is
+    /// [`DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT`](fee::DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+    /// for every instruction, capped at [`MAX_COMPUTE_UNIT_LIMIT`](fee::MAX_COMPUTE_UNIT_LIMIT).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::{fee::FeeEstimate, SolRpcClient};
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    /// use solana_instruction::{AccountMeta, Instruction};
+    /// use solana_message::{Message, VersionedMessage};
+    /// use solana_pubkey::pubkey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::{MultiRpcResult, PrioritizationFee};
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_responses()
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(Some(5_000_u64))))
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(vec![
+    /// #       PrioritizationFee { slot: 338_637_772, prioritization_fee: 10_000 },
+    /// #       PrioritizationFee { slot: 338_637_773, prioritization_fee: 20_000 },
+    /// #       PrioritizationFee { slot: 338_637_774, prioritization_fee: 30_000 },
+    /// #   ])))
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+    ///     .build();
+    ///
+    /// let payer = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+    /// let instruction = Instruction::new_with_bytes(
+    ///     pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"),
+    ///     b"Hello, Solana!",
+    ///     vec![AccountMeta::new(payer, true)],
+    /// );
+    /// let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(&payer)));
+    ///
+    /// let estimate = client.estimate_transaction_cost(message).await.unwrap();
+    ///
+    /// // The prioritization fee results from the median recent prioritization fee of
+    /// // 20_000 micro-lamports per compute unit and the default limit of 200_000 compute units.
+    /// assert_eq!(
+    ///     estimate,
+    ///     FeeEstimate {
+    ///         base_fee: 5_000,
+    ///         priority_fee: 4_000,
+    ///         rent: 0,
+    ///         total: 9_000,
+    ///     }
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn estimate_transaction_cost(
+        &self,
+        message: VersionedMessage,
+    ) -> Result<fee::FeeEstimate, fee::EstimateTransactionCostError> {
+        use fee::EstimateTransactionCostError;
+
+        let parameters = fee::MessageCostParameters::from_message(&message)
+            .map_err(EstimateTransactionCostError::InvalidMessage)?;
+        let params = GetFeeForMessageParams::try_from(message)
+            .map_err(EstimateTransactionCostError::RpcError)?;
+
+        let fee_for_message = match self.get_fee_for_message(params).try_send().await {
+            Ok(MultiRpcResult::Consistent(Ok(Some(fee_for_message)))) => fee_for_message,
+            Ok(MultiRpcResult::Consistent(Ok(None))) => {
+                return Err(EstimateTransactionCostError::FeeUnavailable)
+            }
+            Ok(MultiRpcResult::Consistent(Err(e))) => {
+                return Err(EstimateTransactionCostError::RpcError(e))
+            }
+            Ok(MultiRpcResult::Inconsistent(results)) => {
+                return Err(EstimateTransactionCostError::GetFeeForMessageConsensusError(results))
+            }
+            Err(e) => return Err(EstimateTransactionCostError::IcError(e)),
+        };
+
+        let (base_fee, priority_fee) = match parameters.compute_unit_price {
+            Some(compute_unit_price) => {
+                // The fee for the message already includes the prioritization fee resulting
+                // from the compute unit price set by the message.
+                let priority_fee =
+                    fee::prioritization_fee(compute_unit_price, parameters.compute_unit_limit);
+                (fee_for_message.saturating_sub(priority_fee), priority_fee)
+            }
+            None => {
+                // At most 128 accounts can be passed to `getRecentPrioritizationFees`
+                let fees = match self
+                    .get_recent_prioritization_fees(parameters.writable_accounts.iter().take(128))
+                    .map_err(EstimateTransactionCostError::RpcError)?
+                    .try_send()
+                    .await
+                {
+                    Ok(MultiRpcResult::Consistent(Ok(fees))) => fees,
+                    Ok(MultiRpcResult::Consistent(Err(e))) => {
+                        return Err(EstimateTransactionCostError::RpcError(e))
+                    }
+                    Ok(MultiRpcResult::Inconsistent(results)) => {
+                        return Err(
+                            EstimateTransactionCostError::GetRecentPrioritizationFeesConsensusError(
+                                results,
+                            ),
+                        )
+                    }
+                    Err(e) => return Err(EstimateTransactionCostError::IcError(e)),
+                };
+                let priority_fee = fee::prioritization_fee(
+                    fee::median_prioritization_fee(&fees),
+                    parameters.compute_unit_limit,
+                );
+                (fee_for_message, priority_fee)
+            }
+        };
+
+        let mut num_accounts_by_size = BTreeMap::<u64, u64>::new();
+        for size in parameters.created_account_sizes {
+            *num_accounts_by_size.entry(size).or_default() += 1;
+        }
+        let mut rent: Lamport = 0;
+        for (size, num_accounts) in num_accounts_by_size {
+            let minimum_balance = match self
+                .get_minimum_balance_for_rent_exemption(size)
+                .try_send()
+                .await
+            {
+                Ok(MultiRpcResult::Consistent(Ok(minimum_balance))) => minimum_balance,
+                Ok(MultiRpcResult::Consistent(Err(e))) => {
+                    return Err(EstimateTransactionCostError::RpcError(e))
+                }
+                Ok(MultiRpcResult::Inconsistent(results)) => return Err(
+                    EstimateTransactionCostError::GetMinimumBalanceForRentExemptionConsensusError(
+                        results,
+                    ),
+                ),
+                Err(e) => return Err(EstimateTransactionCostError::IcError(e)),
+            };
+            rent = rent.saturating_add(minimum_balance.saturating_mul(num_accounts));
+        }
+
+        Ok(fee::FeeEstimate::new(base_fee, priority_fee, rent))
+    }
+
     async fn try_execute_request<Config, Params, CandidOutput, Output>(
         &self,
         request: Request<Config, Params, CandidOutput, Output>,
@@ -1074,7 +3085,16 @@ impl<R: Runtime> SolRpcClient<R> {
         Params: CandidType + Send,
         CandidOutput: Into<Output> + CandidType + DeserializeOwned,
     {
-        self.config
+        let cache_key = self.config.response_cache.as_ref().and_then(|cache| {
+            cache.key(
+                &request.endpoint,
+                &(&request.rpc_sources, &request.rpc_config, &request.params),
+            )
+        });
+        #[cfg(feature = "metrics")]
+        let endpoint = request.endpoint.clone();
+        let result = self
+            .config
             .runtime
             .update_call::<(RpcSources, Option<Config>, Params), CandidOutput>(
                 self.config.sol_rpc_canister,
@@ -1082,14 +3102,56 @@ impl<R: Runtime> SolRpcClient<R> {
                 (request.rpc_sources, request.rpc_config, request.params),
                 cycles,
             )
-            .await
-            .map(Into::into)
+            .await;
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.config.metrics.as_ref() {
+            metrics.observe_call(&endpoint, cycles, &result);
+        }
+        let output = result?;
+        if let (Some(cache), Some(key)) = (self.config.response_cache.as_ref(), cache_key) {
+            cache.insert(key, &output);
+        }
+        Ok(output.into())
+    }
+
+    fn cached_response<Config, Params, CandidOutput, Output>(
+        &self,
+        request: &Request<Config, Params, CandidOutput, Output>,
+    ) -> Option<Output>
+    where
+        Config: CandidType,
+        Params: CandidType,
+        CandidOutput: Into<Output> + DeserializeOwned,
+    {
+        let cache = self.config.response_cache.as_ref()?;
+        let key = cache.key(
+            &request.endpoint,
+            &(&request.rpc_sources, &request.rpc_config, &request.params),
+        )?;
+        cache.get::<CandidOutput>(&key).map(Into::into)
     }
 
     async fn execute_cycles_cost_request<Config, Params, CandidOutput, Output>(
         &self,
         request: Request<Config, Params, CandidOutput, Output>,
     ) -> Output
+    where
+        Config: CandidType + Send,
+        Params: CandidType + Send,
+        CandidOutput: Into<Output> + CandidType + DeserializeOwned,
+    {
+        let cycles_cost_method = request.endpoint.cycles_cost_method();
+        self.try_execute_cycles_cost_request(request)
+            .await
+            .unwrap_or_else(|e| {
+                panic!("Client error: failed to call `{cycles_cost_method}`: {e:?}")
+            })
+    }
+
+    async fn try_execute_cycles_cost_request<Config, Params, CandidOutput, Output>(
+        &self,
+        request: Request<Config, Params, CandidOutput, Output>,
+    ) -> Result<Output, IcError>
     where
         Config: CandidType + Send,
         Params: CandidType + Send,
@@ -1103,12 +3165,6 @@ impl<R: Runtime> SolRpcClient<R> {
                 (request.rpc_sources, request.rpc_config, request.params),
             )
             .await
-            .unwrap_or_else(|e| {
-                panic!(
-                    "Client error: failed to call `{}`: {e:?}",
-                    request.endpoint.cycles_cost_method()
-                )
-            })
-            .into()
+            .map(Into::into)
     }
 }
@@ -0,0 +1,137 @@
+//! Module for extracting the program log messages of fetched transactions that match a given
+//! pattern, e.g. to detect specific program events such as
+//! `Program log: Instruction: Transfer` without processing the whole transaction metadata.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sol_rpc_client::logs::LogMessagesFilter;
+//! use solana_pubkey::pubkey;
+//!
+//! let log_messages = vec![
+//!     "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [1]".to_string(),
+//!     "Program log: Instruction: Transfer".to_string(),
+//!     "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 200000 compute units".to_string(),
+//!     "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success".to_string(),
+//!     "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr invoke [1]".to_string(),
+//!     "Program log: Instruction: Transfer".to_string(),
+//!     "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr success".to_string(),
+//! ];
+//!
+//! let filter = LogMessagesFilter::new("Program log: Instruction: Transfer")
+//!     .with_program_id(pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"));
+//!
+//! assert_eq!(
+//!     filter.filter(&log_messages),
+//!     vec!["Program log: Instruction: Transfer"]
+//! );
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use sol_rpc_types::{
+    ConfirmedTransactionWithStatusMeta, EncodedConfirmedTransactionWithStatusMeta,
+    TransactionStatusMeta,
+};
+use solana_pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Filter selecting the log messages of a transaction containing a given pattern, optionally
+/// restricted to the messages emitted while a given program was executing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogMessagesFilter {
+    pattern: String,
+    program_id: Option<Pubkey>,
+}
+
+impl LogMessagesFilter {
+    /// Creates a new filter selecting the log messages containing the given pattern.
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            program_id: None,
+        }
+    }
+
+    /// Restricts the filter to the log messages emitted while the given program was executing,
+    /// i.e. between its `invoke` and `success` or `failed` log messages, excluding the messages
+    /// of the programs it invokes through cross-program invocations.
+    pub fn with_program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Returns the log messages matching this filter, in their original order.
+    pub fn filter<'a>(&self, log_messages: &'a [String]) -> Vec<&'a str> {
+        let mut invoked_programs: Vec<Option<Pubkey>> = Vec::new();
+        log_messages
+            .iter()
+            .filter(|message| {
+                let executing_program = invoked_programs.last().copied().flatten();
+                match parse_program_event(message) {
+                    Some(ProgramEvent::Invoke(program_id)) => invoked_programs.push(program_id),
+                    Some(ProgramEvent::Exit) => {
+                        invoked_programs.pop();
+                    }
+                    None => {}
+                }
+                message.contains(&self.pattern)
+                    && self
+                        .program_id
+                        .is_none_or(|program_id| executing_program == Some(program_id))
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Returns the log messages of the given transaction metadata matching this filter.
+    ///
+    /// Returns [`None`] if log message recording was not enabled for this transaction.
+    pub fn filter_meta<'a>(&self, meta: &'a TransactionStatusMeta) -> Option<Vec<&'a str>> {
+        meta.log_messages
+            .as_ref()
+            .map(|log_messages| self.filter(log_messages))
+    }
+
+    /// Returns the log messages of the given transaction matching this filter, e.g. as returned
+    /// by [`SolRpcClient::get_transaction`](crate::SolRpcClient::get_transaction).
+    ///
+    /// Returns [`None`] if the transaction metadata or its log messages are not available.
+    pub fn filter_transaction<'a>(
+        &self,
+        transaction: &'a EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Option<Vec<&'a str>> {
+        self.filter_meta(transaction.transaction.meta.as_ref()?)
+    }
+
+    /// Returns the log messages of the given decoded transaction matching this filter.
+    ///
+    /// Returns [`None`] if the transaction metadata or its log messages are not available.
+    pub fn filter_confirmed_transaction<'a>(
+        &self,
+        transaction: &'a ConfirmedTransactionWithStatusMeta,
+    ) -> Option<Vec<&'a str>> {
+        self.filter_meta(transaction.meta.as_ref()?)
+    }
+}
+
+enum ProgramEvent {
+    /// A program was invoked. The program ID is [`None`] if it could not be parsed.
+    Invoke(Option<Pubkey>),
+    /// The executing program returned, either successfully or not.
+    Exit,
+}
+
+/// Parses the log messages emitted by the Solana runtime when a program is invoked or returns,
+/// e.g. `Program 11111111111111111111111111111111 invoke [1]` or
+/// `Program 11111111111111111111111111111111 success`.
+fn parse_program_event(message: &str) -> Option<ProgramEvent> {
+    let mut words = message.strip_prefix("Program ")?.split(' ');
+    let program_id = words.next()?;
+    match words.next()? {
+        "invoke" => Some(ProgramEvent::Invoke(Pubkey::from_str(program_id).ok())),
+        "success" | "failed:" => Some(ProgramEvent::Exit),
+        _ => None,
+    }
+}
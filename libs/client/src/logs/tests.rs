@@ -0,0 +1,112 @@
+use crate::logs::LogMessagesFilter;
+use sol_rpc_types::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
+    EncodedTransactionWithStatusMeta, TransactionStatusMeta,
+};
+use solana_pubkey::{pubkey, Pubkey};
+
+const TOKEN_PROGRAM: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const OTHER_PROGRAM: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+fn log_messages() -> Vec<String> {
+    [
+        "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr invoke [1]",
+        "Program log: Instruction: Transfer",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+        "Program log: Instruction: TransferChecked",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+        "Program log: Transfer done",
+        "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr success",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [1]",
+        "Program log: Instruction: Transfer",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA failed: custom program error: 0x1",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+#[test]
+fn should_filter_log_messages_by_pattern() {
+    let log_messages = log_messages();
+
+    assert_eq!(
+        LogMessagesFilter::new("Instruction: Transfer").filter(&log_messages),
+        vec![
+            "Program log: Instruction: Transfer",
+            "Program log: Instruction: TransferChecked",
+            "Program log: Instruction: Transfer",
+        ]
+    );
+    assert_eq!(
+        LogMessagesFilter::new("Instruction: Burn").filter(&log_messages),
+        Vec::<&str>::new()
+    );
+}
+
+#[test]
+fn should_filter_log_messages_by_executing_program() {
+    let log_messages = log_messages();
+
+    assert_eq!(
+        LogMessagesFilter::new("Program log:")
+            .with_program_id(TOKEN_PROGRAM)
+            .filter(&log_messages),
+        vec![
+            "Program log: Instruction: TransferChecked",
+            "Program log: Instruction: Transfer",
+        ]
+    );
+    assert_eq!(
+        LogMessagesFilter::new("Program log:")
+            .with_program_id(OTHER_PROGRAM)
+            .filter(&log_messages),
+        vec![
+            "Program log: Instruction: Transfer",
+            "Program log: Transfer done"
+        ]
+    );
+}
+
+#[test]
+fn should_filter_log_messages_of_transaction() {
+    let filter = LogMessagesFilter::new("Instruction: Transfer").with_program_id(TOKEN_PROGRAM);
+    let mut transaction = EncodedConfirmedTransactionWithStatusMeta {
+        slot: 123,
+        block_time: None,
+        transaction: EncodedTransactionWithStatusMeta {
+            transaction: EncodedTransaction::LegacyBinary(String::new()),
+            meta: None,
+            version: None,
+        },
+    };
+    assert_eq!(filter.filter_transaction(&transaction), None);
+
+    let mut meta = TransactionStatusMeta {
+        status: Ok(()),
+        fee: 5_000,
+        pre_balances: vec![],
+        post_balances: vec![],
+        inner_instructions: None,
+        log_messages: None,
+        pre_token_balances: None,
+        post_token_balances: None,
+        rewards: None,
+        loaded_addresses: None,
+        return_data: None,
+        compute_units_consumed: None,
+        cost_units: None,
+    };
+    transaction.transaction.meta = Some(meta.clone());
+    assert_eq!(filter.filter_transaction(&transaction), None);
+
+    meta.log_messages = Some(log_messages());
+    transaction.transaction.meta = Some(meta);
+    assert_eq!(
+        filter.filter_transaction(&transaction),
+        Some(vec![
+            "Program log: Instruction: TransferChecked",
+            "Program log: Instruction: Transfer",
+        ])
+    );
+}
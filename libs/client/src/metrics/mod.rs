@@ -0,0 +1,192 @@
+//! Module to record metrics about the requests sent by a [`SolRpcClient`](crate::SolRpcClient).
+//!
+//! This module is only available with the `metrics` feature. Metrics are recorded into a
+//! [`MetricsRegistry`] registered on the [`ClientBuilder`](crate::ClientBuilder) with
+//! [`with_metrics_registry`](crate::ClientBuilder::with_metrics_registry), so that consumer
+//! canisters can expose end-to-end observability of their calls to the SOL RPC canister, e.g.
+//! on a `/metrics` HTTP endpoint, without wrapping every call manually.
+//!
+//! The following counters are recorded, all labelled by the `method` of the SOL RPC canister
+//! that was called, e.g. `getBalance`:
+//! * [`REQUESTS`]: the number of requests sent to the SOL RPC canister.
+//! * [`ERRORS`]: the number of requests that failed, e.g. because the call was rejected.
+//! * [`INCONSISTENT_RESULTS`]: the number of requests for which the providers did not agree.
+//! * [`CYCLES_ATTACHED`]: the total amount of cycles attached to the requests.
+//!
+//! Responses served from the client's [`ResponseCache`](crate::ResponseCache) are not recorded,
+//! since no request is sent to the SOL RPC canister.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{IcError, SolRpcEndpoint};
+use candid::{CandidType, Reserved};
+use sol_rpc_types::MultiRpcResult;
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Formatter, Write},
+    sync::{Arc, Mutex},
+};
+
+/// Name of the counter of requests sent to the SOL RPC canister.
+pub const REQUESTS: &str = "sol_rpc_client_requests_total";
+/// Name of the counter of requests to the SOL RPC canister that failed.
+pub const ERRORS: &str = "sol_rpc_client_errors_total";
+/// Name of the counter of requests for which the providers returned inconsistent results.
+pub const INCONSISTENT_RESULTS: &str = "sol_rpc_client_inconsistent_results_total";
+/// Name of the counter of cycles attached to the requests sent to the SOL RPC canister.
+pub const CYCLES_ATTACHED: &str = "sol_rpc_client_cycles_attached_total";
+
+/// Name of the label holding the method of the SOL RPC canister that was called.
+pub const METHOD_LABEL: &str = "method";
+
+/// A registry of Prometheus-style counters, provided by the consumer canister.
+pub trait MetricsRegistry: Send + Sync {
+    /// Increments the counter with the given name and labels by `value`.
+    fn increment_counter(&self, name: &str, labels: &[(&str, &str)], value: u128);
+}
+
+/// A simple in-memory [`MetricsRegistry`].
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::{
+///     metrics::{Counters, REQUESTS},
+///     SolRpcClient,
+/// };
+/// use std::sync::Arc;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # use sol_rpc_types::MultiRpcResult;
+/// let counters = Arc::new(Counters::default());
+/// let client = SolRpcClient::builder_for_ic()
+/// #   .with_stub_responses()
+/// #   .add_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+///     .with_metrics_registry(counters.clone())
+///     .build();
+///
+/// let _slot = client.get_slot().send().await;
+///
+/// assert_eq!(counters.get(REQUESTS, &[("method", "getSlot")]), 1);
+/// assert!(counters
+///     .encode()
+///     .contains("sol_rpc_client_requests_total{method=\"getSlot\"} 1"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Counters {
+    counters: Mutex<BTreeMap<CounterKey, u128>>,
+}
+
+type CounterKey = (String, Vec<(String, String)>);
+
+impl Counters {
+    /// Returns the value of the counter with the given name and labels, or 0 if it was never
+    /// incremented.
+    pub fn get(&self, name: &str, labels: &[(&str, &str)]) -> u128 {
+        self.counters
+            .lock()
+            .expect("BUG: poisoned lock")
+            .get(&counter_key(name, labels))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Encodes all counters in the Prometheus text exposition format.
+    pub fn encode(&self) -> String {
+        let counters = self.counters.lock().expect("BUG: poisoned lock");
+        let mut encoded = String::new();
+        let mut previous_name = None;
+        for ((name, labels), value) in counters.iter() {
+            if previous_name != Some(name) {
+                writeln!(encoded, "# TYPE {name} counter").unwrap();
+                previous_name = Some(name);
+            }
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{value}\""))
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(encoded, "{name}{{{labels}}} {value}").unwrap();
+        }
+        encoded
+    }
+}
+
+impl MetricsRegistry for Counters {
+    fn increment_counter(&self, name: &str, labels: &[(&str, &str)], value: u128) {
+        let mut counters = self.counters.lock().expect("BUG: poisoned lock");
+        let counter = counters.entry(counter_key(name, labels)).or_default();
+        *counter = counter.saturating_add(value);
+    }
+}
+
+fn counter_key(name: &str, labels: &[(&str, &str)]) -> CounterKey {
+    let mut labels: Vec<_> = labels
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    labels.sort();
+    (name.to_string(), labels)
+}
+
+/// The [`MetricsRegistry`] registered on a client.
+#[derive(Clone)]
+pub(crate) struct ClientMetrics(Arc<dyn MetricsRegistry>);
+
+impl ClientMetrics {
+    pub fn new(registry: Arc<dyn MetricsRegistry>) -> Self {
+        Self(registry)
+    }
+
+    /// Records a request sent to the SOL RPC canister with the given amount of cycles.
+    pub fn observe_call<CandidOutput: CandidType>(
+        &self,
+        endpoint: &SolRpcEndpoint,
+        cycles: u128,
+        result: &Result<CandidOutput, IcError>,
+    ) {
+        let labels = [(METHOD_LABEL, endpoint.rpc_method())];
+        self.0.increment_counter(REQUESTS, &labels, 1);
+        if cycles > 0 {
+            self.0.increment_counter(CYCLES_ATTACHED, &labels, cycles);
+        }
+        match result {
+            Ok(output) if is_inconsistent(output) => {
+                self.0.increment_counter(INCONSISTENT_RESULTS, &labels, 1)
+            }
+            Ok(_) => {}
+            Err(_) => self.0.increment_counter(ERRORS, &labels, 1),
+        }
+    }
+}
+
+impl Debug for ClientMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientMetrics").finish_non_exhaustive()
+    }
+}
+
+/// Two [`ClientMetrics`] are equal if they record into the same registry.
+impl PartialEq for ClientMetrics {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ClientMetrics {}
+
+/// Returns `true` if the given output is an inconsistent [`MultiRpcResult`], regardless of the
+/// type of the value it contains.
+fn is_inconsistent<CandidOutput: CandidType>(output: &CandidOutput) -> bool {
+    let Ok(candid_output) = candid::encode_one(output) else {
+        return false;
+    };
+    matches!(
+        candid::decode_one::<MultiRpcResult<Reserved>>(&candid_output),
+        Ok(MultiRpcResult::Inconsistent(_))
+    )
+}
@@ -0,0 +1,83 @@
+use crate::{
+    metrics::{Counters, MetricsRegistry, CYCLES_ATTACHED, ERRORS, INCONSISTENT_RESULTS, REQUESTS},
+    IcError, SolRpcClient,
+};
+use sol_rpc_types::{MultiRpcResult, RpcError, RpcSource, SupportedRpcProviderId};
+use solana_pubkey::{pubkey, Pubkey};
+use std::sync::Arc;
+
+const PUBKEY: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+#[tokio::test]
+async fn should_record_requests_by_endpoint() {
+    let counters = Arc::new(Counters::default());
+    let client = SolRpcClient::builder_for_ic()
+        .with_stub_responses()
+        .add_stub_response(MultiRpcResult::Consistent(Ok(1_000_u64)))
+        .add_stub_response(MultiRpcResult::Consistent(Ok(2_000_u64)))
+        .add_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+        .with_metrics_registry(counters.clone())
+        .build();
+
+    client.get_balance(PUBKEY).with_cycles(1_000).send().await;
+    client.get_balance(PUBKEY).with_cycles(2_000).send().await;
+    client.get_slot().with_cycles(0).send().await;
+
+    let get_balance = [("method", "getBalance")];
+    let get_slot = [("method", "getSlot")];
+    assert_eq!(counters.get(REQUESTS, &get_balance), 2);
+    assert_eq!(counters.get(REQUESTS, &get_slot), 1);
+    assert_eq!(counters.get(CYCLES_ATTACHED, &get_balance), 3_000);
+    assert_eq!(counters.get(CYCLES_ATTACHED, &get_slot), 0);
+    assert_eq!(counters.get(INCONSISTENT_RESULTS, &get_balance), 0);
+    assert_eq!(counters.get(ERRORS, &get_balance), 0);
+}
+
+#[tokio::test]
+async fn should_record_inconsistent_results_and_errors() {
+    let counters = Arc::new(Counters::default());
+    let client = SolRpcClient::builder_for_ic()
+        .with_stub_responses()
+        .add_stub_response(MultiRpcResult::<u64>::Inconsistent(vec![
+            (
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                Ok(1_000),
+            ),
+            (
+                RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet),
+                Err(RpcError::ValidationError("Invalid response".to_string())),
+            ),
+        ]))
+        .add_stub_response(MultiRpcResult::<u64>::Consistent(Err(
+            RpcError::ValidationError("Invalid response".to_string()),
+        )))
+        .add_stub_error(IcError::CallPerformFailed)
+        .with_metrics_registry(counters.clone())
+        .build();
+
+    let _ = client.get_balance(PUBKEY).try_send().await;
+    let _ = client.get_balance(PUBKEY).try_send().await;
+    let _ = client.get_balance(PUBKEY).try_send().await;
+
+    let labels = [("method", "getBalance")];
+    assert_eq!(counters.get(REQUESTS, &labels), 3);
+    assert_eq!(counters.get(INCONSISTENT_RESULTS, &labels), 1);
+    assert_eq!(counters.get(ERRORS, &labels), 1);
+}
+
+#[test]
+fn should_encode_counters_in_prometheus_format() {
+    let counters = Counters::default();
+    counters.increment_counter(REQUESTS, &[("method", "getSlot")], 2);
+    counters.increment_counter(REQUESTS, &[("method", "getBalance")], 1);
+    counters.increment_counter(ERRORS, &[("method", "getSlot")], 1);
+
+    assert_eq!(
+        counters.encode(),
+        "# TYPE sol_rpc_client_errors_total counter\n\
+         sol_rpc_client_errors_total{method=\"getSlot\"} 1\n\
+         # TYPE sol_rpc_client_requests_total counter\n\
+         sol_rpc_client_requests_total{method=\"getBalance\"} 1\n\
+         sol_rpc_client_requests_total{method=\"getSlot\"} 2\n"
+    );
+}
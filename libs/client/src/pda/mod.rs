@@ -0,0 +1,118 @@
+//! Module to derive Solana [program derived addresses](https://solana.com/docs/core/pda) (PDAs)
+//! and addresses created with a seed, e.g. to compute the address of an associated token
+//! account before fetching it with
+//! [`SolRpcClient::get_account_info`](crate::SolRpcClient::get_account_info).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sol_rpc_client::{
+//!     instruction_decoder::{ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, TOKEN_PROGRAM_ID},
+//!     pda,
+//! };
+//! use solana_pubkey::pubkey;
+//!
+//! let wallet = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+//! let usdc_mint = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+//!
+//! let ata = pda::associated_token_address(&wallet, &usdc_mint, &TOKEN_PROGRAM_ID);
+//!
+//! let (expected, _bump) = pda::find_program_address(
+//!     &[wallet.as_ref(), TOKEN_PROGRAM_ID.as_ref(), usdc_mint.as_ref()],
+//!     &ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID,
+//! );
+//! assert_eq!(ata, expected);
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use crate::instruction_decoder::ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID;
+use solana_pubkey::{pubkey, Pubkey, PubkeyError};
+use solana_sdk_ids::system_program;
+
+/// Address of the [Metaplex Token Metadata](https://developers.metaplex.com/token-metadata)
+/// program.
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Finds a valid program derived address and its bump seed for the given seeds and program.
+///
+/// The bump seed is the largest value in `0..=255` such that appending it to the seeds yields
+/// an address that is not on the Ed25519 curve, i.e. that has no corresponding private key.
+///
+/// Panics if the seeds are invalid, e.g. if there are more than 15 seeds or if a seed is longer
+/// than 32 bytes, or in the extremely unlikely case that no valid bump seed exists.
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, program_id)
+}
+
+/// Creates a program derived address from the given seeds, which must include the bump seed,
+/// and program.
+///
+/// Returns an error if the seeds are invalid or if the resulting address lies on the Ed25519
+/// curve, in which case it is not a valid program derived address.
+pub fn create_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(seeds, program_id)
+}
+
+/// Creates the address of an account derived from a base address, a seed and the program
+/// owning the account, as done by the System Program `CreateAccountWithSeed` instruction.
+///
+/// Returns an error if the seed is longer than 32 bytes or if the owner would make the address
+/// indistinguishable from a program derived address.
+pub fn create_with_seed(base: &Pubkey, seed: &str, owner: &Pubkey) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_with_seed(base, seed, owner)
+}
+
+/// Returns the address of the [associated token account](https://spl.solana.com/associated-token-account)
+/// holding the tokens of the given mint for the given wallet.
+///
+/// The token program must be the program owning the mint, e.g.
+/// [`TOKEN_PROGRAM_ID`](crate::instruction_decoder::TOKEN_PROGRAM_ID) or
+/// [`TOKEN_2022_PROGRAM_ID`](crate::instruction_decoder::TOKEN_2022_PROGRAM_ID).
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Returns the address of the [metadata account](https://developers.metaplex.com/token-metadata)
+/// of the given mint, which contains e.g. the name and symbol of the token.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    find_program_address(
+        &[
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Returns the address of the master edition account of the given NFT mint.
+pub fn master_edition_address(mint: &Pubkey) -> Pubkey {
+    find_program_address(
+        &[
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+            b"edition",
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Returns the address of a [nonce account](crate::nonce) created with a seed from the given
+/// base address, e.g. with the System Program `CreateAccountWithSeed` instruction.
+///
+/// Deriving nonce accounts from a single base address allows managing several nonce accounts,
+/// e.g. one per concurrent transaction, without generating and storing a key pair for each of
+/// them.
+pub fn nonce_account_address_with_seed(base: &Pubkey, seed: &str) -> Result<Pubkey, PubkeyError> {
+    create_with_seed(base, seed, &system_program::ID)
+}
@@ -0,0 +1,100 @@
+use crate::{
+    instruction_decoder::{
+        ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+    },
+    pda::{
+        associated_token_address, create_program_address, create_with_seed, find_program_address,
+        master_edition_address, metadata_address, nonce_account_address_with_seed,
+        TOKEN_METADATA_PROGRAM_ID,
+    },
+};
+use assert_matches::assert_matches;
+use solana_pubkey::{pubkey, Pubkey};
+
+const WALLET: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+#[test]
+fn should_derive_associated_token_address() {
+    for token_program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+        assert_eq!(
+            associated_token_address(&WALLET, &USDC_MINT, &token_program),
+            spl_associated_token_account_interface::address::get_associated_token_address_with_program_id(
+                &WALLET,
+                &USDC_MINT,
+                &token_program
+            )
+        );
+    }
+    assert_ne!(
+        associated_token_address(&WALLET, &USDC_MINT, &TOKEN_PROGRAM_ID),
+        associated_token_address(&WALLET, &USDC_MINT, &TOKEN_2022_PROGRAM_ID)
+    );
+}
+
+#[test]
+fn should_create_program_address_with_bump_seed() {
+    let seeds: &[&[u8]] = &[
+        WALLET.as_ref(),
+        TOKEN_PROGRAM_ID.as_ref(),
+        USDC_MINT.as_ref(),
+    ];
+    let (address, bump) = find_program_address(seeds, &ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID);
+
+    assert!(!address.is_on_curve());
+    assert_eq!(
+        create_program_address(
+            &[seeds[0], seeds[1], seeds[2], &[bump]],
+            &ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID
+        ),
+        Ok(address)
+    );
+}
+
+#[test]
+fn should_fail_to_create_program_address_with_too_long_seed() {
+    assert_matches!(
+        create_program_address(&[&[0_u8; 33]], &ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID),
+        Err(_)
+    );
+}
+
+#[test]
+fn should_derive_metadata_addresses() {
+    let metadata = metadata_address(&USDC_MINT);
+    let master_edition = master_edition_address(&USDC_MINT);
+
+    assert_eq!(
+        metadata,
+        find_program_address(
+            &[
+                b"metadata",
+                TOKEN_METADATA_PROGRAM_ID.as_ref(),
+                USDC_MINT.as_ref()
+            ],
+            &TOKEN_METADATA_PROGRAM_ID
+        )
+        .0
+    );
+    assert!(!metadata.is_on_curve());
+    assert!(!master_edition.is_on_curve());
+    assert_ne!(metadata, master_edition);
+}
+
+#[test]
+fn should_derive_nonce_account_address_with_seed() {
+    let nonce_account = nonce_account_address_with_seed(&WALLET, "nonce-0").unwrap();
+
+    assert_eq!(
+        Ok(nonce_account),
+        create_with_seed(&WALLET, "nonce-0", &solana_sdk_ids::system_program::ID)
+    );
+    assert_ne!(
+        Ok(nonce_account),
+        nonce_account_address_with_seed(&WALLET, "nonce-1")
+    );
+    assert_matches!(
+        nonce_account_address_with_seed(&WALLET, &"a".repeat(33)),
+        Err(_)
+    );
+}
@@ -0,0 +1,69 @@
+//! Module to interact with a SOL RPC canister running in [PocketIC](https://github.com/dfinity/pocketic),
+//! e.g. to test canisters against the SOL RPC canister Wasm module with mocked HTTPS outcalls.
+//!
+//! This module is only available with the `pocket-ic` feature and is meant for tests running
+//! natively rather than in a canister. It provides:
+//! * A [`PocketIcRuntime`], i.e. a [`Runtime`](ic_canister_runtime::Runtime) making calls to the
+//!   canisters of a [`PocketIc`] instance.
+//! * Types to mock the HTTPS outcalls made by the SOL RPC canister, e.g.
+//!   [`MockHttpOutcallsBuilder`] and [`JsonRpcRequestMatcher`].
+//!
+//! Calls made by a [`PocketIcRuntime`] are ingress messages, which cannot attach cycles. Calls to
+//! endpoints that are not free must therefore either be paid with prepaid cycles, see
+//! [`SolRpcClient::deposit_cycles`], or be made through a cycles wallet, e.g. with the
+//! `CyclesWalletRuntime` of the `ic-canister-runtime` crate.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use candid::Principal;
+//! use pocket_ic::nonblocking::PocketIc;
+//! use serde_json::json;
+//! use sol_rpc_client::{
+//!     pocket_ic::{JsonRpcRequestMatcher, JsonRpcResponse, MockHttpOutcallsBuilder},
+//!     SolRpcClient,
+//! };
+//! use sol_rpc_types::{RpcSource, RpcSources, SupportedRpcProviderId};
+//!
+//! # async fn run(env: PocketIc, sol_rpc_canister: Principal) {
+//! let mocks = MockHttpOutcallsBuilder::new()
+//!     .given(JsonRpcRequestMatcher::with_method("getSlot").with_id(0))
+//!     .respond_with(JsonRpcResponse::from(json!({"jsonrpc": "2.0", "result": 1234})).with_id(0));
+//! let client = SolRpcClient::builder_for_pocket_ic(&env, Principal::anonymous(), sol_rpc_canister)
+//!     .with_http_mocks(mocks)
+//!     .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+//!         SupportedRpcProviderId::PublicNodeMainnet,
+//!     )]))
+//!     .build();
+//!
+//! let slot = client.get_slot().send().await.expect_consistent();
+//! # }
+//! ```
+
+use crate::{ClientBuilder, SolRpcClient};
+use candid::Principal;
+use pocket_ic::nonblocking::PocketIc;
+
+pub use ic_pocket_canister_runtime::{
+    CanisterHttpReject, CanisterHttpReply, JsonRpcRequestMatcher, JsonRpcResponse,
+    MockHttpOutcalls, MockHttpOutcallsBuilder, PocketIcRuntime,
+};
+
+impl<'a> SolRpcClient<PocketIcRuntime<'a>> {
+    /// Creates a [`ClientBuilder`] to configure a [`SolRpcClient`] making calls as the given
+    /// `sender` to the SOL RPC canister with the given ID running in the given PocketIC instance.
+    pub fn builder_for_pocket_ic(
+        env: &'a PocketIc,
+        sender: Principal,
+        sol_rpc_canister: Principal,
+    ) -> ClientBuilder<PocketIcRuntime<'a>> {
+        ClientBuilder::new(PocketIcRuntime::new(env, sender), sol_rpc_canister)
+    }
+}
+
+impl ClientBuilder<PocketIcRuntime<'_>> {
+    /// Answers the HTTPS outcalls made by the SOL RPC canister with the given mocks.
+    pub fn with_http_mocks(self, mocks: impl Into<MockHttpOutcalls>) -> Self {
+        self.with_runtime(|runtime| runtime.with_http_mocks(mocks.into()))
+    }
+}
@@ -1,25 +1,43 @@
 #[cfg(test)]
 mod tests;
 
-use crate::{IcError, Runtime, SolRpcClient};
-use candid::CandidType;
+use crate::{
+    commitment::CommitmentPolicy,
+    slot_tracker::{consistent_context_slot, SlotTracker},
+    IcError, Runtime, SolRpcClient,
+};
+use candid::{CandidType, Principal};
 use derive_more::From;
-use serde::de::DeserializeOwned;
+use futures::{stream::FuturesUnordered, Stream};
+use serde::{de::DeserializeOwned, Deserialize};
 use sol_rpc_types::{
-    AccountInfo, CommitmentLevel, ConfirmedBlock, ConfirmedTransactionStatusWithSignature,
-    ConsensusStrategy, DataSlice, EncodedConfirmedTransactionWithStatusMeta,
-    GetAccountInfoEncoding, GetAccountInfoParams, GetBalanceParams, GetBlockCommitmentLevel,
-    GetBlockParams, GetRecentPrioritizationFeesParams, GetRecentPrioritizationFeesRpcConfig,
+    AccountChange, AccountInfo, AccountSnapshot, ClusterNode, CommitmentLevel, ConfirmedBlock,
+    ConfirmedTransactionStatusWithSignature, ConsensusStrategy, DataSlice,
+    EncodedConfirmedTransactionWithStatusMeta, EpochInfo, EpochSchedule, GetAccountInfoEncoding,
+    GetAccountInfoParams, GetAccountSnapshotParams, GetBalanceParams, GetBlockCommitmentLevel,
+    GetBlockEncoding, GetBlockHeightParams, GetBlockHeightRpcConfig, GetBlockParams,
+    GetBlocksLimit, GetBlocksParams, GetBlocksWithLimitParams, GetClusterNodesLimit,
+    GetClusterNodesParams, GetEpochInfoParams, GetEpochInfoRpcConfig, GetFeeForMessageParams,
+    GetLeaderScheduleParams, GetMinimumBalanceForRentExemptionParams,
+    GetRecentPerformanceSamplesLimit, GetRecentPerformanceSamplesParams,
+    GetRecentPrioritizationFeesParams, GetRecentPrioritizationFeesRpcConfig,
     GetSignatureStatusesParams, GetSignaturesForAddressLimit, GetSignaturesForAddressParams,
-    GetSlotParams, GetSlotRpcConfig, GetTokenAccountBalanceParams, GetTransactionEncoding,
-    GetTransactionParams, Lamport, MultiRpcResult, NonZeroU8, PrioritizationFee, RoundingError,
-    RpcConfig, RpcError, RpcResult, RpcSource, RpcSources, SendTransactionParams, Signature, Slot,
-    TokenAmount, TransactionDetails, TransactionStatus,
+    GetSlotLeadersLimit, GetSlotLeadersParams, GetSlotParams, GetSlotRpcConfig, GetSupplyParams,
+    GetSupplyRpcConfig, GetTokenAccountBalanceParams, GetTokenAccountsByDelegateParams,
+    GetTokenAccountsByOwnerLimit, GetTokenAccountsByOwnerParams, GetTransactionCountParams,
+    GetTransactionCountRpcConfig, GetTransactionEncoding, GetTransactionParams,
+    HasAccountChangedParams, Hash, HttpHeader, JsonRequestRpcConfig, JsonRpcError, KeyedAccount,
+    Lamport, MultiRpcResult, NonZeroU8, PerformanceSample, PrioritizationFee, Pubkey,
+    RawHttpRequestParams, RawHttpResponse, RequestPriority, RoundingError, RpcConfig, RpcError,
+    RpcResult, RpcSource, RpcSources, SendTransactionParams, Signature, Slot, SnapshotSlotInfo,
+    SolanaCluster, Supply, Timestamp, TokenAmount, TransactionDetails, TransactionError,
+    TransactionStatus, WithContext, WithContextRpcConfig,
 };
 use solana_account_decoder_client_types::token::UiTokenAmount;
-use solana_transaction_status_client_types::UiConfirmedBlock;
+use solana_transaction_status_client_types::{UiConfirmedBlock, UiTransactionError};
 use std::{
     fmt::{Debug, Formatter},
+    future::Future,
     num::NonZeroUsize,
 };
 use strum::EnumIter;
@@ -41,6 +59,10 @@ pub trait SolRpcRequest {
 
     /// Return the request parameters.
     fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params;
+
+    /// Raise the `minContextSlot` parameter to at least the highest slot observed by the given
+    /// [`SlotTracker`], if the endpoint supports that parameter.
+    fn apply_slot_tracker(_params: &mut Self::Params, _slot_tracker: &SlotTracker) {}
 }
 
 /// Endpoint on the SOL RPC canister triggering a call to Solana providers.
@@ -48,10 +70,38 @@ pub trait SolRpcRequest {
 pub enum SolRpcEndpoint {
     /// `getAccountInfo` endpoint.
     GetAccountInfo,
+    /// `getAccountInfoWithContext` endpoint.
+    GetAccountInfoWithContext,
+    /// `getAccountSnapshot` endpoint.
+    GetAccountSnapshot,
     /// `getBalance` endpoint.
     GetBalance,
+    /// `getBalanceWithContext` endpoint.
+    GetBalanceWithContext,
     /// `getBlock` endpoint.
     GetBlock,
+    /// `getBlockHeight` endpoint.
+    GetBlockHeight,
+    /// `getBlocks` endpoint.
+    GetBlocks,
+    /// `getBlocksWithLimit` endpoint.
+    GetBlocksWithLimit,
+    /// `getClusterNodes` endpoint.
+    GetClusterNodes,
+    /// `getEpochInfo` endpoint.
+    GetEpochInfo,
+    /// `getEpochSchedule` endpoint.
+    GetEpochSchedule,
+    /// `getFeeForMessage` endpoint.
+    GetFeeForMessage,
+    /// `getHighestSnapshotSlot` endpoint.
+    GetHighestSnapshotSlot,
+    /// `getLeaderSchedule` endpoint.
+    GetLeaderSchedule,
+    /// `getMinimumBalanceForRentExemption` endpoint.
+    GetMinimumBalanceForRentExemption,
+    /// `getRecentPerformanceSamples` endpoint.
+    GetRecentPerformanceSamples,
     /// `getRecentPrioritizationFees` endpoint.
     GetRecentPrioritizationFees,
     /// `getSignaturesForAddress` endpoint.
@@ -60,12 +110,30 @@ pub enum SolRpcEndpoint {
     GetSignatureStatuses,
     /// `getSlot` endpoint.
     GetSlot,
+    /// `getSlotLeaders` endpoint.
+    GetSlotLeaders,
+    /// `getSupply` endpoint.
+    GetSupply,
     /// `getTokenAccountBalance` endpoint.
     GetTokenAccountBalance,
+    /// `getTokenAccountBalanceWithContext` endpoint.
+    GetTokenAccountBalanceWithContext,
+    /// `getTokenAccountsByDelegate` endpoint.
+    GetTokenAccountsByDelegate,
+    /// `getTokenAccountsByOwner` endpoint.
+    GetTokenAccountsByOwner,
     /// `getTransaction` endpoint.
     GetTransaction,
+    /// `getTransactionCount` endpoint.
+    GetTransactionCount,
+    /// `hasAccountChanged` endpoint.
+    HasAccountChanged,
     /// `jsonRequest` endpoint.
     JsonRequest,
+    /// `minimumLedgerSlot` endpoint.
+    MinimumLedgerSlot,
+    /// `rawHttpRequest` endpoint.
+    RawHttpRequest,
     /// `sendTransaction` endpoint.
     SendTransaction,
 }
@@ -75,15 +143,42 @@ impl SolRpcEndpoint {
     pub fn rpc_method(&self) -> &'static str {
         match &self {
             SolRpcEndpoint::GetAccountInfo => "getAccountInfo",
+            SolRpcEndpoint::GetAccountInfoWithContext => "getAccountInfoWithContext",
+            SolRpcEndpoint::GetAccountSnapshot => "getAccountSnapshot",
             SolRpcEndpoint::GetBalance => "getBalance",
+            SolRpcEndpoint::GetBalanceWithContext => "getBalanceWithContext",
             SolRpcEndpoint::GetBlock => "getBlock",
+            SolRpcEndpoint::GetBlockHeight => "getBlockHeight",
+            SolRpcEndpoint::GetBlocks => "getBlocks",
+            SolRpcEndpoint::GetBlocksWithLimit => "getBlocksWithLimit",
+            SolRpcEndpoint::GetClusterNodes => "getClusterNodes",
+            SolRpcEndpoint::GetEpochInfo => "getEpochInfo",
+            SolRpcEndpoint::GetEpochSchedule => "getEpochSchedule",
+            SolRpcEndpoint::GetFeeForMessage => "getFeeForMessage",
+            SolRpcEndpoint::GetHighestSnapshotSlot => "getHighestSnapshotSlot",
+            SolRpcEndpoint::GetLeaderSchedule => "getLeaderSchedule",
+            SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                "getMinimumBalanceForRentExemption"
+            }
+            SolRpcEndpoint::GetRecentPerformanceSamples => "getRecentPerformanceSamples",
             SolRpcEndpoint::GetRecentPrioritizationFees => "getRecentPrioritizationFees",
             SolRpcEndpoint::GetSignatureStatuses => "getSignatureStatuses",
             SolRpcEndpoint::GetSignaturesForAddress => "getSignaturesForAddress",
             SolRpcEndpoint::GetSlot => "getSlot",
+            SolRpcEndpoint::GetSlotLeaders => "getSlotLeaders",
+            SolRpcEndpoint::GetSupply => "getSupply",
             SolRpcEndpoint::GetTokenAccountBalance => "getTokenAccountBalance",
+            SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                "getTokenAccountBalanceWithContext"
+            }
+            SolRpcEndpoint::GetTokenAccountsByDelegate => "getTokenAccountsByDelegate",
+            SolRpcEndpoint::GetTokenAccountsByOwner => "getTokenAccountsByOwner",
             SolRpcEndpoint::GetTransaction => "getTransaction",
+            SolRpcEndpoint::GetTransactionCount => "getTransactionCount",
+            SolRpcEndpoint::HasAccountChanged => "hasAccountChanged",
             SolRpcEndpoint::JsonRequest => "jsonRequest",
+            SolRpcEndpoint::MinimumLedgerSlot => "minimumLedgerSlot",
+            SolRpcEndpoint::RawHttpRequest => "rawHttpRequest",
             SolRpcEndpoint::SendTransaction => "sendTransaction",
         }
     }
@@ -92,15 +187,42 @@ impl SolRpcEndpoint {
     pub fn cycles_cost_method(&self) -> &'static str {
         match &self {
             SolRpcEndpoint::GetAccountInfo => "getAccountInfoCyclesCost",
+            SolRpcEndpoint::GetAccountInfoWithContext => "getAccountInfoWithContextCyclesCost",
+            SolRpcEndpoint::GetAccountSnapshot => "getAccountSnapshotCyclesCost",
             SolRpcEndpoint::GetBalance => "getBalanceCyclesCost",
+            SolRpcEndpoint::GetBalanceWithContext => "getBalanceWithContextCyclesCost",
             SolRpcEndpoint::GetBlock => "getBlockCyclesCost",
+            SolRpcEndpoint::GetBlockHeight => "getBlockHeightCyclesCost",
+            SolRpcEndpoint::GetBlocks => "getBlocksCyclesCost",
+            SolRpcEndpoint::GetBlocksWithLimit => "getBlocksWithLimitCyclesCost",
+            SolRpcEndpoint::GetClusterNodes => "getClusterNodesCyclesCost",
+            SolRpcEndpoint::GetEpochInfo => "getEpochInfoCyclesCost",
+            SolRpcEndpoint::GetEpochSchedule => "getEpochScheduleCyclesCost",
+            SolRpcEndpoint::GetFeeForMessage => "getFeeForMessageCyclesCost",
+            SolRpcEndpoint::GetHighestSnapshotSlot => "getHighestSnapshotSlotCyclesCost",
+            SolRpcEndpoint::GetLeaderSchedule => "getLeaderScheduleCyclesCost",
+            SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                "getMinimumBalanceForRentExemptionCyclesCost"
+            }
+            SolRpcEndpoint::GetRecentPerformanceSamples => "getRecentPerformanceSamplesCyclesCost",
             SolRpcEndpoint::GetRecentPrioritizationFees => "getRecentPrioritizationFeesCyclesCost",
             SolRpcEndpoint::GetSignaturesForAddress => "getSignaturesForAddressCyclesCost",
             SolRpcEndpoint::GetSignatureStatuses => "getSignatureStatusesCyclesCost",
             SolRpcEndpoint::GetSlot => "getSlotCyclesCost",
+            SolRpcEndpoint::GetSlotLeaders => "getSlotLeadersCyclesCost",
+            SolRpcEndpoint::GetSupply => "getSupplyCyclesCost",
             SolRpcEndpoint::GetTransaction => "getTransactionCyclesCost",
+            SolRpcEndpoint::GetTransactionCount => "getTransactionCountCyclesCost",
             SolRpcEndpoint::GetTokenAccountBalance => "getTokenAccountBalanceCyclesCost",
+            SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                "getTokenAccountBalanceWithContextCyclesCost"
+            }
+            SolRpcEndpoint::GetTokenAccountsByDelegate => "getTokenAccountsByDelegateCyclesCost",
+            SolRpcEndpoint::GetTokenAccountsByOwner => "getTokenAccountsByOwnerCyclesCost",
+            SolRpcEndpoint::HasAccountChanged => "hasAccountChangedCyclesCost",
             SolRpcEndpoint::JsonRequest => "jsonRequestCyclesCost",
+            SolRpcEndpoint::MinimumLedgerSlot => "minimumLedgerSlotCyclesCost",
+            SolRpcEndpoint::RawHttpRequest => "rawHttpRequestCyclesCost",
             SolRpcEndpoint::SendTransaction => "sendTransactionCyclesCost",
         }
     }
@@ -138,6 +260,10 @@ impl SolRpcRequest for GetAccountInfoRequest {
         set_default(default_commitment_level, &mut params.commitment);
         params
     }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
 }
 
 pub type GetAccountInfoRequestBuilder<R> = RequestBuilder<
@@ -178,6 +304,175 @@ impl<R> GetAccountInfoRequestBuilder<R> {
         self.request.params.min_context_slot = Some(slot);
         self
     }
+
+    /// Also return the (rounded) slot at which the `getAccountInfo` request was evaluated.
+    pub fn with_context(self) -> GetAccountInfoWithContextRequestBuilder<R> {
+        self.with_endpoint(SolRpcEndpoint::GetAccountInfoWithContext)
+            .observing_context_slot()
+    }
+}
+
+pub type GetAccountInfoWithContextRequestBuilder<R> = RequestBuilder<
+    R,
+    WithContextRpcConfig,
+    GetAccountInfoParams,
+    MultiRpcResult<WithContext<Option<AccountInfo>>>,
+    MultiRpcResult<WithContext<Option<solana_account_decoder_client_types::UiAccount>>>,
+>;
+
+impl<R> DefaultRequestCycles for GetAccountInfoWithContextRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct GetAccountSnapshotRequest(GetAccountSnapshotParams);
+
+impl SolRpcRequest for GetAccountSnapshotRequest {
+    type Config = RpcConfig;
+    type Params = GetAccountSnapshotParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<AccountSnapshot>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetAccountSnapshot
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
+}
+
+pub type GetAccountSnapshotRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetAccountSnapshotParams,
+    MultiRpcResult<AccountSnapshot>,
+    MultiRpcResult<AccountSnapshot>,
+>;
+
+impl<R> DefaultRequestCycles for GetAccountSnapshotRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        // Sum of the default cycles of the `getBalance`, `getAccountInfo` and
+        // `getSignaturesForAddress` requests.
+        22_000_000_000
+    }
+}
+
+impl<R> GetAccountSnapshotRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getAccountSnapshot` request.
+    pub fn with_commitment(mut self, commitment: impl Into<CommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment.into());
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `getAccountSnapshot` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.min_context_slot = Some(slot);
+        self
+    }
+
+    /// Change the `encoding` parameter for a `getAccountSnapshot` request.
+    pub fn with_encoding(mut self, encoding: impl Into<GetAccountInfoEncoding>) -> Self {
+        self.request.params.encoding = Some(encoding.into());
+        self
+    }
+
+    /// Change the `dataSlice` parameter for a `getAccountSnapshot` request.
+    pub fn with_data_slice(mut self, data_slice: impl Into<DataSlice>) -> Self {
+        self.request.params.data_slice = Some(data_slice.into());
+        self
+    }
+
+    /// Change the `signaturesLimit` parameter for a `getAccountSnapshot` request.
+    pub fn with_signatures_limit(mut self, limit: GetSignaturesForAddressLimit) -> Self {
+        self.request.params.signatures_limit = Some(limit);
+        self
+    }
+
+    /// Change the `signaturesBefore` parameter for a `getAccountSnapshot` request.
+    pub fn with_signatures_before(mut self, before: impl Into<Signature>) -> Self {
+        self.request.params.signatures_before = Some(before.into());
+        self
+    }
+
+    /// Change the `signaturesUntil` parameter for a `getAccountSnapshot` request.
+    pub fn with_signatures_until(mut self, until: impl Into<Signature>) -> Self {
+        self.request.params.signatures_until = Some(until.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct HasAccountChangedRequest(HasAccountChangedParams);
+
+impl SolRpcRequest for HasAccountChangedRequest {
+    type Config = RpcConfig;
+    type Params = HasAccountChangedParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<AccountChange>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::HasAccountChanged
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
+}
+
+pub type HasAccountChangedRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    HasAccountChangedParams,
+    MultiRpcResult<AccountChange>,
+    MultiRpcResult<AccountChange>,
+>;
+
+impl<R> DefaultRequestCycles for HasAccountChangedRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> HasAccountChangedRequestBuilder<R> {
+    /// Change the `lastKnownHash` parameter for a `hasAccountChanged` request, typically to the
+    /// [`AccountChange::hash`] returned by the previous call.
+    pub fn with_last_known_hash(mut self, last_known_hash: Option<Hash>) -> Self {
+        self.request.params.last_known_hash = last_known_hash;
+        self
+    }
+
+    /// Change the `commitment` parameter for a `hasAccountChanged` request.
+    pub fn with_commitment(mut self, commitment: impl Into<CommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment.into());
+        self
+    }
+
+    /// Change the `dataSlice` parameter for a `hasAccountChanged` request.
+    pub fn with_data_slice(mut self, data_slice: impl Into<DataSlice>) -> Self {
+        self.request.params.data_slice = Some(data_slice.into());
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `hasAccountChanged` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.min_context_slot = Some(slot);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -204,6 +499,10 @@ impl SolRpcRequest for GetBalanceRequest {
         set_default(default_commitment_level, &mut params.commitment);
         params
     }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
 }
 
 pub type GetBalanceRequestBuilder<R> = RequestBuilder<
@@ -232,6 +531,26 @@ impl<R> GetBalanceRequestBuilder<R> {
         self.request.params.min_context_slot = Some(slot);
         self
     }
+
+    /// Also return the (rounded) slot at which the `getBalance` request was evaluated.
+    pub fn with_context(self) -> GetBalanceWithContextRequestBuilder<R> {
+        self.with_endpoint(SolRpcEndpoint::GetBalanceWithContext)
+            .observing_context_slot()
+    }
+}
+
+pub type GetBalanceWithContextRequestBuilder<R> = RequestBuilder<
+    R,
+    WithContextRpcConfig,
+    GetBalanceParams,
+    MultiRpcResult<WithContext<Lamport>>,
+    MultiRpcResult<WithContext<Lamport>>,
+>;
+
+impl<R> DefaultRequestCycles for GetBalanceWithContextRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -255,20 +574,10 @@ impl SolRpcRequest for GetBlockRequest {
 
     fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
         let mut params = self.0;
-        let default_block_commitment_level =
-            default_commitment_level.map(|commitment| match commitment {
-                CommitmentLevel::Processed => {
-                    // The minimum commitment level for `getBlock` is `confirmed,
-                    // `processed` is not supported.
-                    // Not setting a value here would be equivalent to requiring the block to be `finalized`,
-                    // which seems to go against the chosen `default_commitment_level` of `processed` and so `confirmed`
-                    // is the best we can do here.
-                    GetBlockCommitmentLevel::Confirmed
-                }
-                CommitmentLevel::Confirmed => GetBlockCommitmentLevel::Confirmed,
-                CommitmentLevel::Finalized => GetBlockCommitmentLevel::Finalized,
-            });
-        set_default(default_block_commitment_level, &mut params.commitment);
+        set_default(
+            default_block_commitment_level(default_commitment_level),
+            &mut params.commitment,
+        );
         params
     }
 }
@@ -321,124 +630,673 @@ impl<R> GetBlockRequestBuilder<R> {
         self.request.params.rewards = Some(false);
         self
     }
+
+    /// Change the `encoding` parameter for a `getBlock` request.
+    pub fn with_encoding(mut self, encoding: GetBlockEncoding) -> Self {
+        self.request.params.encoding = Some(encoding);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct GetRecentPrioritizationFeesRequest(GetRecentPrioritizationFeesParams);
+pub struct GetBlockHeightRequest(Option<GetBlockHeightParams>);
 
-impl SolRpcRequest for GetRecentPrioritizationFeesRequest {
-    type Config = GetRecentPrioritizationFeesRpcConfig;
-    type Params = GetRecentPrioritizationFeesParams;
-    type CandidOutput = MultiRpcResult<Vec<PrioritizationFee>>;
-    type Output = Self::CandidOutput;
+impl SolRpcRequest for GetBlockHeightRequest {
+    type Config = GetBlockHeightRpcConfig;
+    type Params = Option<GetBlockHeightParams>;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<u64>;
 
     fn endpoint(&self) -> SolRpcEndpoint {
-        SolRpcEndpoint::GetRecentPrioritizationFees
+        SolRpcEndpoint::GetBlockHeight
     }
 
-    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
-        // [getRecentPrioritizationFees](https://solana.com/de/docs/rpc/http/getrecentprioritizationfees)
-        // does not use commitment levels
-        self.0
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        if let Some(slot_params) = params.as_mut() {
+            set_default(default_commitment_level, &mut slot_params.commitment);
+            return params;
+        }
+        if let Some(commitment) = default_commitment_level {
+            return Some(GetBlockHeightParams {
+                commitment: Some(commitment),
+                ..Default::default()
+            });
+        }
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        if slot_tracker.highest_slot().is_some() {
+            slot_tracker.apply(&mut params.get_or_insert_default().min_context_slot);
+        }
     }
 }
 
-impl From<GetRecentPrioritizationFeesParams> for GetRecentPrioritizationFeesRequest {
-    fn from(value: GetRecentPrioritizationFeesParams) -> Self {
-        Self(value)
+pub type GetBlockHeightRequestBuilder<R> = RequestBuilder<
+    R,
+    GetBlockHeightRpcConfig,
+    Option<GetBlockHeightParams>,
+    MultiRpcResult<u64>,
+    MultiRpcResult<u64>,
+>;
+
+impl<R> DefaultRequestCycles for GetBlockHeightRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetBlockHeightRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getBlockHeight` request.
+    pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
+        self.request.params.get_or_insert_default().commitment = Some(commitment_level);
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `getBlockHeight` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.get_or_insert_default().min_context_slot = Some(slot);
+        self
     }
 }
 
 #[derive(Debug, Clone, From)]
-pub struct GetSignaturesForAddressRequest(GetSignaturesForAddressParams);
+pub struct GetBlocksRequest(GetBlocksParams);
 
-impl SolRpcRequest for GetSignaturesForAddressRequest {
+impl SolRpcRequest for GetBlocksRequest {
     type Config = RpcConfig;
-    type Params = GetSignaturesForAddressParams;
+    type Params = GetBlocksParams;
     type CandidOutput = Self::Output;
-    type Output = MultiRpcResult<Vec<ConfirmedTransactionStatusWithSignature>>;
+    type Output = MultiRpcResult<Vec<Slot>>;
 
     fn endpoint(&self) -> SolRpcEndpoint {
-        SolRpcEndpoint::GetSignaturesForAddress
+        SolRpcEndpoint::GetBlocks
     }
 
     fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
         let mut params = self.0;
-        set_default(default_commitment_level, &mut params.commitment);
+        set_default(
+            default_block_commitment_level(default_commitment_level),
+            &mut params.commitment,
+        );
         params
     }
 }
 
-pub type GetSignaturesForAddressRequestBuilder<R> = RequestBuilder<
+pub type GetBlocksRequestBuilder<R> = RequestBuilder<
     R,
     RpcConfig,
-    GetSignaturesForAddressParams,
-    MultiRpcResult<Vec<ConfirmedTransactionStatusWithSignature>>,
-    MultiRpcResult<Vec<ConfirmedTransactionStatusWithSignature>>,
+    GetBlocksParams,
+    MultiRpcResult<Vec<Slot>>,
+    MultiRpcResult<Vec<Slot>>,
 >;
 
-impl<R> DefaultRequestCycles for GetSignaturesForAddressRequestBuilder<R> {
+impl<R> DefaultRequestCycles for GetBlocksRequestBuilder<R> {
     fn default_request_cycles(&self) -> u128 {
-        2_000_000_000 // TODO XC-338: Check heuristic
+        10_000_000_000
     }
 }
 
-impl<R> GetSignaturesForAddressRequestBuilder<R> {
-    /// Change the `commitment` parameter for a `getSignaturesForAddress` request.
-    pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
-        self.request.params.commitment = Some(commitment_level);
+impl<R> GetBlocksRequestBuilder<R> {
+    /// Change the `endSlot` parameter for a `getBlocks` request.
+    pub fn with_end_slot(mut self, end_slot: Slot) -> Self {
+        self.request.params.end_slot = Some(end_slot);
         self
     }
 
-    /// Change the `minContextSlot` parameter for a `getSignaturesForAddress` request.
-    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
-        self.request.params.min_context_slot = Some(slot);
+    /// Change the `commitment` parameter for a `getBlocks` request.
+    pub fn with_commitment(mut self, commitment_level: impl Into<GetBlockCommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment_level.into());
         self
     }
+}
 
-    /// Change the `limit` parameter for a `getSignaturesForAddress` request.
-    pub fn with_limit(mut self, limit: GetSignaturesForAddressLimit) -> Self {
-        self.request.params.limit = Some(limit);
-        self
+#[derive(Debug, Clone, From)]
+pub struct GetBlocksWithLimitRequest(GetBlocksWithLimitParams);
+
+impl SolRpcRequest for GetBlocksWithLimitRequest {
+    type Config = RpcConfig;
+    type Params = GetBlocksWithLimitParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Vec<Slot>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetBlocksWithLimit
     }
 
-    /// Change the `until` parameter for a `getSignaturesForAddress` request.
-    pub fn with_until(mut self, until: impl Into<Signature>) -> Self {
-        self.request.params.until = Some(until.into());
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(
+            default_block_commitment_level(default_commitment_level),
+            &mut params.commitment,
+        );
+        params
+    }
+}
+
+pub type GetBlocksWithLimitRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetBlocksWithLimitParams,
+    MultiRpcResult<Vec<Slot>>,
+    MultiRpcResult<Vec<Slot>>,
+>;
+
+impl<R> DefaultRequestCycles for GetBlocksWithLimitRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetBlocksWithLimitRequestBuilder<R> {
+    /// Change the `limit` parameter for a `getBlocksWithLimit` request.
+    pub fn with_limit(mut self, limit: GetBlocksLimit) -> Self {
+        self.request.params.limit = limit;
         self
     }
 
-    /// Change the `before` parameter for a `getSignaturesForAddress` request.
-    pub fn with_before(mut self, before: impl Into<Signature>) -> Self {
-        self.request.params.before = Some(before.into());
+    /// Change the `commitment` parameter for a `getBlocksWithLimit` request.
+    pub fn with_commitment(mut self, commitment_level: impl Into<GetBlockCommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment_level.into());
         self
     }
 }
 
-#[derive(Debug, Clone, Default, From)]
-pub struct GetSignatureStatusesRequest(GetSignatureStatusesParams);
+#[derive(Debug, Clone, Default)]
+pub struct GetClusterNodesRequest(Option<GetClusterNodesParams>);
 
-impl SolRpcRequest for GetSignatureStatusesRequest {
+impl SolRpcRequest for GetClusterNodesRequest {
     type Config = RpcConfig;
-    type Params = GetSignatureStatusesParams;
-    type CandidOutput = MultiRpcResult<Vec<Option<TransactionStatus>>>;
-    type Output =
-        MultiRpcResult<Vec<Option<solana_transaction_status_client_types::TransactionStatus>>>;
+    type Params = Option<GetClusterNodesParams>;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Vec<ClusterNode>>;
 
     fn endpoint(&self) -> SolRpcEndpoint {
-        SolRpcEndpoint::GetSignatureStatuses
+        SolRpcEndpoint::GetClusterNodes
     }
 
     fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        // [getClusterNodes](https://solana.com/docs/rpc/http/getclusternodes)
+        // does not have any parameters, the pagination is applied by the SOL RPC canister
         self.0
     }
 }
 
-pub type GetSignatureStatusesRequestBuilder<R> = RequestBuilder<
+pub type GetClusterNodesRequestBuilder<R> = RequestBuilder<
     R,
     RpcConfig,
-    GetSignatureStatusesParams,
-    MultiRpcResult<Vec<Option<TransactionStatus>>>,
+    Option<GetClusterNodesParams>,
+    MultiRpcResult<Vec<ClusterNode>>,
+    MultiRpcResult<Vec<ClusterNode>>,
+>;
+
+impl<R> DefaultRequestCycles for GetClusterNodesRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        50_000_000_000
+    }
+}
+
+impl<R> GetClusterNodesRequestBuilder<R> {
+    /// Change the `after` parameter for a `getClusterNodes` request.
+    pub fn with_after(mut self, after: impl Into<Pubkey>) -> Self {
+        self.request.params.get_or_insert_default().after = Some(after.into());
+        self
+    }
+
+    /// Change the `limit` parameter for a `getClusterNodes` request.
+    pub fn with_limit(mut self, limit: GetClusterNodesLimit) -> Self {
+        self.request.params.get_or_insert_default().limit = Some(limit);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetEpochInfoRequest(Option<GetEpochInfoParams>);
+
+impl SolRpcRequest for GetEpochInfoRequest {
+    type Config = GetEpochInfoRpcConfig;
+    type Params = Option<GetEpochInfoParams>;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<EpochInfo>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetEpochInfo
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        if let Some(slot_params) = params.as_mut() {
+            set_default(default_commitment_level, &mut slot_params.commitment);
+            return params;
+        }
+        if let Some(commitment) = default_commitment_level {
+            return Some(GetEpochInfoParams {
+                commitment: Some(commitment),
+                ..Default::default()
+            });
+        }
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        if slot_tracker.highest_slot().is_some() {
+            slot_tracker.apply(&mut params.get_or_insert_default().min_context_slot);
+        }
+    }
+}
+
+pub type GetEpochInfoRequestBuilder<R> = RequestBuilder<
+    R,
+    GetEpochInfoRpcConfig,
+    Option<GetEpochInfoParams>,
+    MultiRpcResult<EpochInfo>,
+    MultiRpcResult<EpochInfo>,
+>;
+
+impl<R> DefaultRequestCycles for GetEpochInfoRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetEpochInfoRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getEpochInfo` request.
+    pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
+        self.request.params.get_or_insert_default().commitment = Some(commitment_level);
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `getEpochInfo` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.get_or_insert_default().min_context_slot = Some(slot);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetEpochScheduleRequest;
+
+impl SolRpcRequest for GetEpochScheduleRequest {
+    type Config = RpcConfig;
+    type Params = ();
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<EpochSchedule>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetEpochSchedule
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        // [getEpochSchedule](https://solana.com/docs/rpc/http/getepochschedule)
+        // does not have any parameters
+    }
+}
+
+pub type GetEpochScheduleRequestBuilder<R> =
+    RequestBuilder<R, RpcConfig, (), MultiRpcResult<EpochSchedule>, MultiRpcResult<EpochSchedule>>;
+
+impl<R> DefaultRequestCycles for GetEpochScheduleRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct GetFeeForMessageRequest(GetFeeForMessageParams);
+
+impl SolRpcRequest for GetFeeForMessageRequest {
+    type Config = RpcConfig;
+    type Params = GetFeeForMessageParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Option<Lamport>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetFeeForMessage
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
+}
+
+pub type GetFeeForMessageRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetFeeForMessageParams,
+    MultiRpcResult<Option<Lamport>>,
+    MultiRpcResult<Option<Lamport>>,
+>;
+
+impl<R> DefaultRequestCycles for GetFeeForMessageRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetFeeForMessageRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getFeeForMessage` request.
+    pub fn with_commitment(mut self, commitment_level: impl Into<CommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment_level.into());
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `getFeeForMessage` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.min_context_slot = Some(slot);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetHighestSnapshotSlotRequest;
+
+impl SolRpcRequest for GetHighestSnapshotSlotRequest {
+    type Config = RpcConfig;
+    type Params = ();
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<SnapshotSlotInfo>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetHighestSnapshotSlot
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        // [getHighestSnapshotSlot](https://solana.com/docs/rpc/http/gethighestsnapshotslot)
+        // does not have any parameters
+    }
+}
+
+pub type GetHighestSnapshotSlotRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    (),
+    MultiRpcResult<SnapshotSlotInfo>,
+    MultiRpcResult<SnapshotSlotInfo>,
+>;
+
+impl<R> DefaultRequestCycles for GetHighestSnapshotSlotRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct GetLeaderScheduleRequest(GetLeaderScheduleParams);
+
+impl SolRpcRequest for GetLeaderScheduleRequest {
+    type Config = RpcConfig;
+    type Params = GetLeaderScheduleParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Option<Vec<u64>>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetLeaderSchedule
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+}
+
+pub type GetLeaderScheduleRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetLeaderScheduleParams,
+    MultiRpcResult<Option<Vec<u64>>>,
+    MultiRpcResult<Option<Vec<u64>>>,
+>;
+
+impl<R> DefaultRequestCycles for GetLeaderScheduleRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        50_000_000_000
+    }
+}
+
+impl<R> GetLeaderScheduleRequestBuilder<R> {
+    /// Change the `slot` parameter for a `getLeaderSchedule` request.
+    pub fn with_slot(mut self, slot: Slot) -> Self {
+        self.request.params.slot = Some(slot);
+        self
+    }
+
+    /// Change the `commitment` parameter for a `getLeaderSchedule` request.
+    pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
+        self.request.params.commitment = Some(commitment_level);
+        self
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct GetMinimumBalanceForRentExemptionRequest(GetMinimumBalanceForRentExemptionParams);
+
+impl SolRpcRequest for GetMinimumBalanceForRentExemptionRequest {
+    type Config = RpcConfig;
+    type Params = GetMinimumBalanceForRentExemptionParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Lamport>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetMinimumBalanceForRentExemption
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+}
+
+pub type GetMinimumBalanceForRentExemptionRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetMinimumBalanceForRentExemptionParams,
+    MultiRpcResult<Lamport>,
+    MultiRpcResult<Lamport>,
+>;
+
+impl<R> DefaultRequestCycles for GetMinimumBalanceForRentExemptionRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetMinimumBalanceForRentExemptionRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getMinimumBalanceForRentExemption` request.
+    pub fn with_commitment(mut self, commitment_level: impl Into<CommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment_level.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetRecentPerformanceSamplesRequest(Option<GetRecentPerformanceSamplesParams>);
+
+impl SolRpcRequest for GetRecentPerformanceSamplesRequest {
+    type Config = RpcConfig;
+    type Params = Option<GetRecentPerformanceSamplesParams>;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Vec<PerformanceSample>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetRecentPerformanceSamples
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        // [getRecentPerformanceSamples](https://solana.com/docs/rpc/http/getrecentperformancesamples)
+        // does not use commitment levels
+        self.0
+    }
+}
+
+pub type GetRecentPerformanceSamplesRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    Option<GetRecentPerformanceSamplesParams>,
+    MultiRpcResult<Vec<PerformanceSample>>,
+    MultiRpcResult<Vec<PerformanceSample>>,
+>;
+
+impl<R> DefaultRequestCycles for GetRecentPerformanceSamplesRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetRecentPerformanceSamplesRequestBuilder<R> {
+    /// Change the `limit` parameter for a `getRecentPerformanceSamples` request.
+    pub fn with_limit(mut self, limit: GetRecentPerformanceSamplesLimit) -> Self {
+        self.request.params.get_or_insert_default().limit = Some(limit);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetRecentPrioritizationFeesRequest(GetRecentPrioritizationFeesParams);
+
+impl SolRpcRequest for GetRecentPrioritizationFeesRequest {
+    type Config = GetRecentPrioritizationFeesRpcConfig;
+    type Params = GetRecentPrioritizationFeesParams;
+    type CandidOutput = MultiRpcResult<Vec<PrioritizationFee>>;
+    type Output = Self::CandidOutput;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetRecentPrioritizationFees
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        // [getRecentPrioritizationFees](https://solana.com/de/docs/rpc/http/getrecentprioritizationfees)
+        // does not use commitment levels
+        self.0
+    }
+}
+
+impl From<GetRecentPrioritizationFeesParams> for GetRecentPrioritizationFeesRequest {
+    fn from(value: GetRecentPrioritizationFeesParams) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct GetSignaturesForAddressRequest(GetSignaturesForAddressParams);
+
+impl SolRpcRequest for GetSignaturesForAddressRequest {
+    type Config = RpcConfig;
+    type Params = GetSignaturesForAddressParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Vec<ConfirmedTransactionStatusWithSignature>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetSignaturesForAddress
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
+}
+
+pub type GetSignaturesForAddressRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetSignaturesForAddressParams,
+    MultiRpcResult<Vec<ConfirmedTransactionStatusWithSignature>>,
+    MultiRpcResult<Vec<ConfirmedTransactionStatusWithSignature>>,
+>;
+
+impl<R> DefaultRequestCycles for GetSignaturesForAddressRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        2_000_000_000 // TODO XC-338: Check heuristic
+    }
+}
+
+impl<R> GetSignaturesForAddressRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getSignaturesForAddress` request.
+    pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
+        self.request.params.commitment = Some(commitment_level);
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `getSignaturesForAddress` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.min_context_slot = Some(slot);
+        self
+    }
+
+    /// Change the `limit` parameter for a `getSignaturesForAddress` request.
+    pub fn with_limit(mut self, limit: GetSignaturesForAddressLimit) -> Self {
+        self.request.params.limit = Some(limit);
+        self
+    }
+
+    /// Change the `until` parameter for a `getSignaturesForAddress` request.
+    pub fn with_until(mut self, until: impl Into<Signature>) -> Self {
+        self.request.params.until = Some(until.into());
+        self
+    }
+
+    /// Change the `before` parameter for a `getSignaturesForAddress` request.
+    pub fn with_before(mut self, before: impl Into<Signature>) -> Self {
+        self.request.params.before = Some(before.into());
+        self
+    }
+
+    /// Change the `minBlockTime` parameter for a `getSignaturesForAddress` request.
+    ///
+    /// Only signatures with a block time (Unix timestamp in seconds) greater than or equal to
+    /// the given value are returned.
+    pub fn with_min_block_time(mut self, min_block_time: Timestamp) -> Self {
+        self.request.params.min_block_time = Some(min_block_time);
+        self
+    }
+
+    /// Change the `maxBlockTime` parameter for a `getSignaturesForAddress` request.
+    ///
+    /// Only signatures with a block time (Unix timestamp in seconds) less than or equal to
+    /// the given value are returned.
+    pub fn with_max_block_time(mut self, max_block_time: Timestamp) -> Self {
+        self.request.params.max_block_time = Some(max_block_time);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, From)]
+pub struct GetSignatureStatusesRequest(GetSignatureStatusesParams);
+
+impl SolRpcRequest for GetSignatureStatusesRequest {
+    type Config = RpcConfig;
+    type Params = GetSignatureStatusesParams;
+    type CandidOutput = MultiRpcResult<Vec<Option<TransactionStatus>>>;
+    type Output =
+        MultiRpcResult<Vec<Option<solana_transaction_status_client_types::TransactionStatus>>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetSignatureStatuses
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        self.0
+    }
+}
+
+pub type GetSignatureStatusesRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetSignatureStatusesParams,
+    MultiRpcResult<Vec<Option<TransactionStatus>>>,
     MultiRpcResult<Vec<Option<solana_transaction_status_client_types::TransactionStatus>>>,
 >;
 
@@ -484,6 +1342,12 @@ impl SolRpcRequest for GetSlotRequest {
         }
         params
     }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        if slot_tracker.highest_slot().is_some() {
+            slot_tracker.apply(&mut params.get_or_insert_default().min_context_slot);
+        }
+    }
 }
 
 pub type GetSlotRequestBuilder<R> = RequestBuilder<
@@ -514,6 +1378,106 @@ impl<R> GetSlotRequestBuilder<R> {
     }
 }
 
+#[derive(Debug, Clone, From)]
+pub struct GetSlotLeadersRequest(GetSlotLeadersParams);
+
+impl SolRpcRequest for GetSlotLeadersRequest {
+    type Config = RpcConfig;
+    type Params = GetSlotLeadersParams;
+    type CandidOutput = MultiRpcResult<Vec<Pubkey>>;
+    type Output = MultiRpcResult<Vec<solana_pubkey::Pubkey>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetSlotLeaders
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        self.0
+    }
+}
+
+pub type GetSlotLeadersRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetSlotLeadersParams,
+    MultiRpcResult<Vec<Pubkey>>,
+    MultiRpcResult<Vec<solana_pubkey::Pubkey>>,
+>;
+
+impl<R> DefaultRequestCycles for GetSlotLeadersRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetSlotLeadersRequestBuilder<R> {
+    /// Change the `limit` parameter for a `getSlotLeaders` request.
+    pub fn with_limit(mut self, limit: GetSlotLeadersLimit) -> Self {
+        self.request.params.limit = limit;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetSupplyRequest(Option<GetSupplyParams>);
+
+impl SolRpcRequest for GetSupplyRequest {
+    type Config = GetSupplyRpcConfig;
+    type Params = Option<GetSupplyParams>;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Supply>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetSupply
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        if let Some(supply_params) = params.as_mut() {
+            set_default(default_commitment_level, &mut supply_params.commitment);
+            return params;
+        }
+        if let Some(commitment) = default_commitment_level {
+            return Some(GetSupplyParams {
+                commitment: Some(commitment),
+                ..Default::default()
+            });
+        }
+        params
+    }
+}
+
+pub type GetSupplyRequestBuilder<R> = RequestBuilder<
+    R,
+    GetSupplyRpcConfig,
+    Option<GetSupplyParams>,
+    MultiRpcResult<Supply>,
+    MultiRpcResult<Supply>,
+>;
+
+impl<R> DefaultRequestCycles for GetSupplyRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetSupplyRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getSupply` request.
+    pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
+        self.request.params.get_or_insert_default().commitment = Some(commitment_level);
+        self
+    }
+
+    /// Change the `excludeNonCirculatingAccountsList` parameter for a `getSupply` request.
+    pub fn with_exclude_non_circulating_accounts_list(mut self, exclude: bool) -> Self {
+        self.request
+            .params
+            .get_or_insert_default()
+            .exclude_non_circulating_accounts_list = Some(exclude);
+        self
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GetTokenAccountBalanceRequest(GetTokenAccountBalanceParams);
 
@@ -560,6 +1524,200 @@ impl<R> GetTokenAccountBalanceRequestBuilder<R> {
         self.request.params.commitment = Some(commitment_level);
         self
     }
+
+    /// Also return the (rounded) slot at which the `getTokenAccountBalance` request was evaluated.
+    pub fn with_context(self) -> GetTokenAccountBalanceWithContextRequestBuilder<R> {
+        self.with_endpoint(SolRpcEndpoint::GetTokenAccountBalanceWithContext)
+            .observing_context_slot()
+    }
+}
+
+pub type GetTokenAccountBalanceWithContextRequestBuilder<R> = RequestBuilder<
+    R,
+    WithContextRpcConfig,
+    GetTokenAccountBalanceParams,
+    MultiRpcResult<WithContext<TokenAmount>>,
+    MultiRpcResult<WithContext<UiTokenAmount>>,
+>;
+
+impl<R> DefaultRequestCycles for GetTokenAccountBalanceWithContextRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct GetTokenAccountsByDelegateRequest(GetTokenAccountsByDelegateParams);
+
+impl SolRpcRequest for GetTokenAccountsByDelegateRequest {
+    type Config = RpcConfig;
+    type Params = GetTokenAccountsByDelegateParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Vec<KeyedAccount>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetTokenAccountsByDelegate
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
+}
+
+pub type GetTokenAccountsByDelegateRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetTokenAccountsByDelegateParams,
+    MultiRpcResult<Vec<KeyedAccount>>,
+    MultiRpcResult<Vec<KeyedAccount>>,
+>;
+
+impl<R> DefaultRequestCycles for GetTokenAccountsByDelegateRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetTokenAccountsByDelegateRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getTokenAccountsByDelegate` request.
+    pub fn with_commitment(mut self, commitment: impl Into<CommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment.into());
+        self
+    }
+
+    /// Change the `encoding` parameter for a `getTokenAccountsByDelegate` request.
+    pub fn with_encoding(mut self, encoding: impl Into<GetAccountInfoEncoding>) -> Self {
+        self.request.params.encoding = Some(encoding.into());
+        self
+    }
+
+    /// Change the `dataSlice` parameter for a `getTokenAccountsByDelegate` request.
+    pub fn with_data_slice(mut self, data_slice: impl Into<DataSlice>) -> Self {
+        self.request.params.data_slice = Some(data_slice.into());
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `getTokenAccountsByDelegate` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.min_context_slot = Some(slot);
+        self
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub struct GetTokenAccountsByOwnerRequest(GetTokenAccountsByOwnerParams);
+
+impl SolRpcRequest for GetTokenAccountsByOwnerRequest {
+    type Config = RpcConfig;
+    type Params = GetTokenAccountsByOwnerParams;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Vec<KeyedAccount>>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetTokenAccountsByOwner
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
+}
+
+pub type GetTokenAccountsByOwnerRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetTokenAccountsByOwnerParams,
+    MultiRpcResult<Vec<KeyedAccount>>,
+    MultiRpcResult<Vec<KeyedAccount>>,
+>;
+
+impl<R> DefaultRequestCycles for GetTokenAccountsByOwnerRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetTokenAccountsByOwnerRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getTokenAccountsByOwner` request.
+    pub fn with_commitment(mut self, commitment: impl Into<CommitmentLevel>) -> Self {
+        self.request.params.commitment = Some(commitment.into());
+        self
+    }
+
+    /// Change the `encoding` parameter for a `getTokenAccountsByOwner` request.
+    pub fn with_encoding(mut self, encoding: impl Into<GetAccountInfoEncoding>) -> Self {
+        self.request.params.encoding = Some(encoding.into());
+        self
+    }
+
+    /// Change the `dataSlice` parameter for a `getTokenAccountsByOwner` request.
+    pub fn with_data_slice(mut self, data_slice: impl Into<DataSlice>) -> Self {
+        self.request.params.data_slice = Some(data_slice.into());
+        self
+    }
+
+    /// Change the `minContextSlot` parameter for a `getTokenAccountsByOwner` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.min_context_slot = Some(slot);
+        self
+    }
+
+    /// Change the `after` parameter for a `getTokenAccountsByOwner` request, so that only the
+    /// token accounts whose address is strictly greater than the given one are returned.
+    pub fn with_after(mut self, after: impl Into<Pubkey>) -> Self {
+        self.request.params.after = Some(after.into());
+        self
+    }
+
+    /// Change the `limit` parameter for a `getTokenAccountsByOwner` request.
+    pub fn with_limit(mut self, limit: GetTokenAccountsByOwnerLimit) -> Self {
+        self.request.params.limit = Some(limit);
+        self
+    }
+}
+
+impl<R: Runtime> GetTokenAccountsByOwnerRequestBuilder<R> {
+    /// Sends the `getTokenAccountsByOwner` request page by page, each page starting after the
+    /// last token account of the previous one, until a page contains fewer token accounts than
+    /// the page limit (or [`GetTokenAccountsByOwnerLimit::MAX_LIMIT`] if no limit was set).
+    ///
+    /// The token accounts of all pages are returned, ordered by address. If the result for a
+    /// page is an error or is inconsistent, it is returned as is and no further pages are
+    /// requested.
+    pub async fn try_send_all_pages(self) -> Result<MultiRpcResult<Vec<KeyedAccount>>, IcError> {
+        let limit = self.request.params.limit.unwrap_or_default();
+        let mut page = self.with_limit(limit);
+        let mut accounts: Vec<KeyedAccount> = Vec::new();
+        loop {
+            let result = page.clone().try_send().await?;
+            let page_accounts = match result {
+                MultiRpcResult::Consistent(Ok(page_accounts)) => page_accounts,
+                other => return Ok(other),
+            };
+            let last = match page_accounts.last() {
+                Some(last) if page_accounts.len() >= u32::from(limit) as usize => {
+                    last.pubkey.clone()
+                }
+                _ => {
+                    accounts.extend(page_accounts);
+                    return Ok(MultiRpcResult::Consistent(Ok(accounts)));
+                }
+            };
+            accounts.extend(page_accounts);
+            page = page.with_after(last);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -580,49 +1738,140 @@ impl SolRpcRequest for GetTransactionRequest {
     >;
 
     fn endpoint(&self) -> SolRpcEndpoint {
-        SolRpcEndpoint::GetTransaction
+        SolRpcEndpoint::GetTransaction
+    }
+
+    fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        let mut params = self.0;
+        set_default(default_commitment_level, &mut params.commitment);
+        params
+    }
+}
+
+pub type GetTransactionRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    GetTransactionParams,
+    MultiRpcResult<Option<EncodedConfirmedTransactionWithStatusMeta>>,
+    MultiRpcResult<
+        Option<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>,
+    >,
+>;
+
+impl<R> DefaultRequestCycles for GetTransactionRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> GetTransactionRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getTransaction` request.
+    pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
+        self.request.params.commitment = Some(commitment_level);
+        self
+    }
+
+    /// Change the `maxSupportedTransaction_version` parameter for a `getTransaction` request.
+    pub fn with_max_supported_transaction_version(mut self, version: u8) -> Self {
+        self.request.params.max_supported_transaction_version = Some(version);
+        self
+    }
+
+    /// Change the `encoding` parameter for a `getTransaction` request.
+    pub fn with_encoding(mut self, encoding: GetTransactionEncoding) -> Self {
+        self.request.params.encoding = Some(encoding);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GetTransactionCountRequest(Option<GetTransactionCountParams>);
+
+impl SolRpcRequest for GetTransactionCountRequest {
+    type Config = GetTransactionCountRpcConfig;
+    type Params = Option<GetTransactionCountParams>;
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<u64>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::GetTransactionCount
     }
 
     fn params(self, default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
         let mut params = self.0;
-        set_default(default_commitment_level, &mut params.commitment);
+        if let Some(count_params) = params.as_mut() {
+            set_default(default_commitment_level, &mut count_params.commitment);
+            return params;
+        }
+        if let Some(commitment) = default_commitment_level {
+            return Some(GetTransactionCountParams {
+                commitment: Some(commitment),
+                ..Default::default()
+            });
+        }
         params
     }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        if slot_tracker.highest_slot().is_some() {
+            slot_tracker.apply(&mut params.get_or_insert_default().min_context_slot);
+        }
+    }
 }
 
-pub type GetTransactionRequestBuilder<R> = RequestBuilder<
+pub type GetTransactionCountRequestBuilder<R> = RequestBuilder<
     R,
-    RpcConfig,
-    GetTransactionParams,
-    MultiRpcResult<Option<EncodedConfirmedTransactionWithStatusMeta>>,
-    MultiRpcResult<
-        Option<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>,
-    >,
+    GetTransactionCountRpcConfig,
+    Option<GetTransactionCountParams>,
+    MultiRpcResult<u64>,
+    MultiRpcResult<u64>,
 >;
 
-impl<R> DefaultRequestCycles for GetTransactionRequestBuilder<R> {
+impl<R> DefaultRequestCycles for GetTransactionCountRequestBuilder<R> {
     fn default_request_cycles(&self) -> u128 {
         10_000_000_000
     }
 }
 
-impl<R> GetTransactionRequestBuilder<R> {
-    /// Change the `commitment` parameter for a `getTransaction` request.
+impl<R> GetTransactionCountRequestBuilder<R> {
+    /// Change the `commitment` parameter for a `getTransactionCount` request.
     pub fn with_commitment(mut self, commitment_level: CommitmentLevel) -> Self {
-        self.request.params.commitment = Some(commitment_level);
+        self.request.params.get_or_insert_default().commitment = Some(commitment_level);
         self
     }
 
-    /// Change the `maxSupportedTransaction_version` parameter for a `getTransaction` request.
-    pub fn with_max_supported_transaction_version(mut self, version: u8) -> Self {
-        self.request.params.max_supported_transaction_version = Some(version);
+    /// Change the `minContextSlot` parameter for a `getTransactionCount` request.
+    pub fn with_min_context_slot(mut self, slot: Slot) -> Self {
+        self.request.params.get_or_insert_default().min_context_slot = Some(slot);
         self
     }
+}
 
-    /// Change the `encoding` parameter for a `getTransaction` request.
-    pub fn with_encoding(mut self, encoding: GetTransactionEncoding) -> Self {
-        self.request.params.encoding = Some(encoding);
-        self
+#[derive(Debug, Clone, Default)]
+pub struct MinimumLedgerSlotRequest;
+
+impl SolRpcRequest for MinimumLedgerSlotRequest {
+    type Config = RpcConfig;
+    type Params = ();
+    type CandidOutput = Self::Output;
+    type Output = MultiRpcResult<Slot>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::MinimumLedgerSlot
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        // [minimumLedgerSlot](https://solana.com/docs/rpc/http/minimumledgerslot)
+        // does not have any parameters
+    }
+}
+
+pub type MinimumLedgerSlotRequestBuilder<R> =
+    RequestBuilder<R, RpcConfig, (), MultiRpcResult<Slot>, MultiRpcResult<Slot>>;
+
+impl<R> DefaultRequestCycles for MinimumLedgerSlotRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
     }
 }
 
@@ -650,6 +1899,10 @@ impl SolRpcRequest for SendTransactionRequest {
         set_default(default_commitment_level, &mut params.preflight_commitment);
         params
     }
+
+    fn apply_slot_tracker(params: &mut Self::Params, slot_tracker: &SlotTracker) {
+        slot_tracker.apply(&mut params.min_context_slot);
+    }
 }
 
 pub type SendTransactionRequestBuilder<R> = RequestBuilder<
@@ -690,6 +1943,46 @@ impl<R> SendTransactionRequestBuilder<R> {
         self.request.params.min_context_slot = Some(slot);
         self
     }
+
+    /// Change the `validateBlockhash` parameter for a `sendTransaction` request.
+    ///
+    /// When enabled, the SOL RPC canister checks that the recent blockhash of the transaction
+    /// is still valid before sending the transaction.
+    pub fn with_blockhash_validation(mut self, validate_blockhash: bool) -> Self {
+        self.request.params.validate_blockhash = Some(validate_blockhash);
+        self
+    }
+
+    /// Simulate the transaction with [`simulateTransaction`] against a single provider before
+    /// sending it.
+    ///
+    /// If the simulation fails, the transaction is not sent and the simulation logs are returned
+    /// instead, which avoids paying for a `sendTransaction` call to all providers for a
+    /// transaction that would fail anyway.
+    /// See [`SimulateThenSendTransactionRequestBuilder`].
+    ///
+    /// [`simulateTransaction`]: https://solana.com/docs/rpc/http/simulatetransaction
+    pub fn with_preflight_simulation(self) -> SimulateThenSendTransactionRequestBuilder<R> {
+        SimulateThenSendTransactionRequestBuilder::new(self)
+    }
+
+    /// Automatically re-sign and resend the transaction with a fresh blockhash if the providers
+    /// report that the recent blockhash of the transaction was not found, e.g. because it expired.
+    ///
+    /// The given `signer` is called with the fresh blockhash and must return the parameters of the
+    /// re-signed transaction. Only the transaction and its encoding are taken from the returned
+    /// parameters, the other parameters (e.g. `skipPreflight`) of the original request are kept.
+    /// See [`SendTransactionWithBlockhashRefreshRequestBuilder`].
+    pub fn with_blockhash_refresh<F, Fut>(
+        self,
+        signer: F,
+    ) -> SendTransactionWithBlockhashRefreshRequestBuilder<R, F>
+    where
+        F: Fn(solana_hash::Hash) -> Fut,
+        Fut: Future<Output = Result<SendTransactionParams, String>>,
+    {
+        SendTransactionWithBlockhashRefreshRequestBuilder::new(self, signer)
+    }
 }
 
 pub struct JsonRequest(String);
@@ -705,7 +1998,7 @@ impl TryFrom<serde_json::Value> for JsonRequest {
 }
 
 impl SolRpcRequest for JsonRequest {
-    type Config = RpcConfig;
+    type Config = JsonRequestRpcConfig;
     type Params = String;
     type CandidOutput = MultiRpcResult<String>;
     type Output = MultiRpcResult<String>;
@@ -720,7 +2013,7 @@ impl SolRpcRequest for JsonRequest {
 }
 
 pub type JsonRequestBuilder<R> =
-    RequestBuilder<R, RpcConfig, String, MultiRpcResult<String>, MultiRpcResult<String>>;
+    RequestBuilder<R, JsonRequestRpcConfig, String, MultiRpcResult<String>, MultiRpcResult<String>>;
 
 impl<R> DefaultRequestCycles for JsonRequestBuilder<R> {
     fn default_request_cycles(&self) -> u128 {
@@ -728,6 +2021,82 @@ impl<R> DefaultRequestCycles for JsonRequestBuilder<R> {
     }
 }
 
+impl<R> JsonRequestBuilder<R> {
+    /// Return the response body of the provider without normalizing it, e.g. to debug
+    /// discrepancies between providers.
+    ///
+    /// The request must be sent to a single provider, see
+    /// [`JsonRequestRpcConfig::raw_responses`].
+    pub fn with_raw_responses(mut self) -> Self {
+        let config = self.request.rpc_config_mut().get_or_insert_default();
+        config.raw_responses = Some(true);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RawHttpRequest(RawHttpRequestParams);
+
+impl RawHttpRequest {
+    pub fn new(params: RawHttpRequestParams) -> Self {
+        Self(params)
+    }
+}
+
+impl SolRpcRequest for RawHttpRequest {
+    type Config = RpcConfig;
+    type Params = RawHttpRequestParams;
+    type CandidOutput = MultiRpcResult<RawHttpResponse>;
+    type Output = MultiRpcResult<RawHttpResponse>;
+
+    fn endpoint(&self) -> SolRpcEndpoint {
+        SolRpcEndpoint::RawHttpRequest
+    }
+
+    fn params(self, _default_commitment_level: Option<CommitmentLevel>) -> Self::Params {
+        self.0
+    }
+}
+
+pub type RawHttpRequestBuilder<R> = RequestBuilder<
+    R,
+    RpcConfig,
+    RawHttpRequestParams,
+    MultiRpcResult<RawHttpResponse>,
+    MultiRpcResult<RawHttpResponse>,
+>;
+
+impl<R> DefaultRequestCycles for RawHttpRequestBuilder<R> {
+    fn default_request_cycles(&self) -> u128 {
+        10_000_000_000
+    }
+}
+
+impl<R> RawHttpRequestBuilder<R> {
+    /// Add an HTTP header to a `rawHttpRequest` request.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.request
+            .params
+            .headers
+            .get_or_insert_with(Vec::new)
+            .push(HttpHeader {
+                name: name.into(),
+                value: value.into(),
+            });
+        self
+    }
+
+    /// Return the HTTP response header with the given name for a `rawHttpRequest` request.
+    pub fn with_response_header(mut self, name: impl Into<String>) -> Self {
+        self.request
+            .params
+            .response_headers
+            .get_or_insert_with(Vec::new)
+            .push(name.into());
+        self
+    }
+}
+
 /// A builder to construct a [`Request`].
 ///
 /// To construct a [`RequestBuilder`], refer to the [`SolRpcClient`] documentation.
@@ -735,6 +2104,8 @@ impl<R> DefaultRequestCycles for JsonRequestBuilder<R> {
 pub struct RequestBuilder<Runtime, Config, Params, CandidOutput, Output> {
     client: SolRpcClient<Runtime>,
     request: Request<Config, Params, CandidOutput, Output>,
+    /// Extracts the slot to record in the client's [`SlotTracker`] from the response, if any.
+    observed_slot: Option<fn(&Output) -> Option<Slot>>,
 }
 
 pub type GetRecentPrioritizationFeesRequestBuilder<R> = RequestBuilder<
@@ -758,6 +2129,7 @@ impl<Runtime, Config: Clone, Params: Clone, CandidOutput, Output> Clone
         Self {
             client: self.client.clone(),
             request: self.request.clone(),
+            observed_slot: self.observed_slot,
         }
     }
 }
@@ -766,10 +2138,15 @@ impl<Runtime: Debug, Config: Debug, Params: Debug, CandidOutput, Output> Debug
     for RequestBuilder<Runtime, Config, Params, CandidOutput, Output>
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let RequestBuilder { client, request } = &self;
+        let RequestBuilder {
+            client,
+            request,
+            observed_slot,
+        } = &self;
         f.debug_struct("RequestBuilder")
             .field("client", client)
             .field("request", request)
+            .field("observed_slot", &observed_slot.is_some())
             .finish()
     }
 }
@@ -788,7 +2165,10 @@ impl<Runtime, Config, Params, CandidOutput, Output>
         Config: From<RpcConfig>,
     {
         let endpoint = rpc_request.endpoint();
-        let params = rpc_request.params(client.config.default_commitment_level.clone());
+        let mut params = rpc_request.params(client.effective_commitment(&endpoint));
+        if let Some(slot_tracker) = client.config.slot_tracker.as_ref() {
+            RpcRequest::apply_slot_tracker(&mut params, slot_tracker);
+        }
         let request = Request {
             endpoint,
             rpc_sources: client.config.rpc_sources.clone(),
@@ -798,7 +2178,81 @@ impl<Runtime, Config, Params, CandidOutput, Output>
             _candid_marker: Default::default(),
             _output_marker: Default::default(),
         };
-        RequestBuilder::<Runtime, Config, Params, CandidOutput, Output> { client, request }
+        RequestBuilder::<Runtime, Config, Params, CandidOutput, Output> {
+            client,
+            request,
+            observed_slot: None,
+        }
+    }
+
+    /// Target another endpoint of the SOL RPC canister taking the same parameters, e.g. to
+    /// go from `getBalance` to `getBalanceWithContext`.
+    fn with_endpoint<NewConfig, NewCandidOutput, NewOutput>(
+        self,
+        endpoint: SolRpcEndpoint,
+    ) -> RequestBuilder<Runtime, NewConfig, Params, NewCandidOutput, NewOutput>
+    where
+        NewConfig: From<Config>,
+    {
+        RequestBuilder {
+            client: self.client,
+            request: Request {
+                endpoint,
+                rpc_sources: self.request.rpc_sources,
+                rpc_config: self.request.rpc_config.map(NewConfig::from),
+                params: self.request.params,
+                cycles: self.request.cycles,
+                _candid_marker: Default::default(),
+                _output_marker: Default::default(),
+            },
+            observed_slot: None,
+        }
+    }
+
+    /// Returns the call to the SOL RPC canister that sending that request would make, without
+    /// sending it, e.g. to inspect or encode it offline.
+    ///
+    /// The request hooks registered on the client are applied, as when sending the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_client::{SolRpcClient, SOL_RPC_CANISTER};
+    /// use sol_rpc_types::{RpcSources, SolanaCluster};
+    ///
+    /// let client = SolRpcClient::builder_for_ic()
+    ///     .with_rpc_sources(RpcSources::Default(SolanaCluster::Devnet))
+    ///     .build();
+    ///
+    /// let call = client.get_slot().with_cycles(1_000_000_000).build_args();
+    ///
+    /// assert_eq!(call.canister_id, SOL_RPC_CANISTER);
+    /// assert_eq!(call.method, "getSlot");
+    /// assert_eq!(call.args.0, RpcSources::Default(SolanaCluster::Devnet));
+    /// assert_eq!(call.cycles, 1_000_000_000);
+    /// assert!(call.encode_args().is_ok());
+    /// ```
+    pub fn build_args(self) -> PreparedCall<Config, Params>
+    where
+        Config: 'static,
+        Params: 'static,
+        Self: DefaultRequestCycles,
+    {
+        let mut cycles = self
+            .request
+            .cycles
+            .unwrap_or_else(|| self.default_request_cycles());
+        let mut request = self.request;
+        self.client
+            .config
+            .hooks
+            .on_request(&mut request, &mut cycles);
+        PreparedCall {
+            canister_id: self.client.config.sol_rpc_canister,
+            method: request.endpoint.rpc_method(),
+            args: (request.rpc_sources, request.rpc_config, request.params),
+            cycles,
+        }
     }
 
     /// Query the cycles cost for that request
@@ -845,16 +2299,157 @@ impl<Runtime, Config, Params, CandidOutput, Output>
     }
 }
 
-/// Common behavior for the RPC config for SOL RPC canister endpoints.
-pub trait SolRpcConfig {
-    /// Return a new RPC config with the given response size estimate.
-    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self;
+/// Common behavior for the RPC config for SOL RPC canister endpoints.
+pub trait SolRpcConfig {
+    /// Return a new RPC config with the given response size estimate.
+    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self;
+
+    /// Return a new RPC config with the given response consensys.
+    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self;
+
+    /// Return the response consensus of that RPC config, if set.
+    fn response_consensus(&self) -> Option<&ConsensusStrategy>;
+
+    /// Return a new RPC config with the given request priority.
+    fn with_priority(self, priority: RequestPriority) -> Self;
+}
+
+impl SolRpcConfig for RpcConfig {
+    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self {
+        Self {
+            response_size_estimate: Some(response_size_estimate),
+            ..self
+        }
+    }
+
+    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self {
+        Self {
+            response_consensus: Some(response_consensus),
+            ..self
+        }
+    }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+}
+
+impl SolRpcConfig for GetBlockHeightRpcConfig {
+    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self {
+        Self {
+            response_size_estimate: Some(response_size_estimate),
+            ..self
+        }
+    }
+
+    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self {
+        Self {
+            response_consensus: Some(response_consensus),
+            ..self
+        }
+    }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+}
+
+impl SolRpcConfig for GetEpochInfoRpcConfig {
+    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self {
+        Self {
+            response_size_estimate: Some(response_size_estimate),
+            ..self
+        }
+    }
+
+    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self {
+        Self {
+            response_consensus: Some(response_consensus),
+            ..self
+        }
+    }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+}
+
+impl SolRpcConfig for GetTransactionCountRpcConfig {
+    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self {
+        Self {
+            response_size_estimate: Some(response_size_estimate),
+            ..self
+        }
+    }
+
+    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self {
+        Self {
+            response_consensus: Some(response_consensus),
+            ..self
+        }
+    }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+}
+
+impl SolRpcConfig for JsonRequestRpcConfig {
+    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self {
+        Self {
+            response_size_estimate: Some(response_size_estimate),
+            ..self
+        }
+    }
+
+    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self {
+        Self {
+            response_consensus: Some(response_consensus),
+            ..self
+        }
+    }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
 
-    /// Return a new RPC config with the given response consensys.
-    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self;
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
 }
 
-impl SolRpcConfig for RpcConfig {
+impl SolRpcConfig for GetSupplyRpcConfig {
     fn with_response_size_estimate(self, response_size_estimate: u64) -> Self {
         Self {
             response_size_estimate: Some(response_size_estimate),
@@ -868,6 +2463,17 @@ impl SolRpcConfig for RpcConfig {
             ..self
         }
     }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
 }
 
 impl SolRpcConfig for GetSlotRpcConfig {
@@ -884,6 +2490,17 @@ impl SolRpcConfig for GetSlotRpcConfig {
             ..self
         }
     }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
 }
 
 impl SolRpcConfig for GetRecentPrioritizationFeesRpcConfig {
@@ -896,6 +2513,42 @@ impl SolRpcConfig for GetRecentPrioritizationFeesRpcConfig {
         self.set_response_consensus(response_consensus);
         self
     }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(mut self, priority: RequestPriority) -> Self {
+        self.set_priority(priority);
+        self
+    }
+}
+
+impl SolRpcConfig for WithContextRpcConfig {
+    fn with_response_size_estimate(self, response_size_estimate: u64) -> Self {
+        Self {
+            response_size_estimate: Some(response_size_estimate),
+            ..self
+        }
+    }
+
+    fn with_response_consensus(self, response_consensus: ConsensusStrategy) -> Self {
+        Self {
+            response_consensus: Some(response_consensus),
+            ..self
+        }
+    }
+
+    fn response_consensus(&self) -> Option<&ConsensusStrategy> {
+        self.response_consensus.as_ref()
+    }
+
+    fn with_priority(self, priority: RequestPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
 }
 
 impl<Runtime, Config: SolRpcConfig + Default, Params, CandidOutput, Output>
@@ -922,6 +2575,20 @@ impl<Runtime, Config: SolRpcConfig + Default, Params, CandidOutput, Output>
         );
         self
     }
+
+    /// Change the priority of that request, see [`RequestPriority`].
+    ///
+    /// Expedited requests are sent to the providers with the lowest recent latency and skip
+    /// the queue of HTTPS outcalls of the SOL RPC canister, but cost more cycles.
+    pub fn with_priority(mut self, priority: RequestPriority) -> Self {
+        self.request.rpc_config = Some(
+            self.request
+                .rpc_config
+                .unwrap_or_default()
+                .with_priority(priority),
+        );
+        self
+    }
 }
 
 impl<R: Runtime, Config, Params, CandidOutput, Output>
@@ -934,9 +2601,10 @@ impl<R: Runtime, Config, Params, CandidOutput, Output>
     /// If the request was not successful.
     pub async fn send(self) -> Output
     where
-        Config: CandidType + Send,
-        Params: CandidType + Send,
+        Config: CandidType + Send + 'static,
+        Params: CandidType + Send + 'static,
         CandidOutput: Into<Output> + CandidType + DeserializeOwned,
+        Output: 'static,
         RequestBuilder<R, Config, Params, CandidOutput, Output>: DefaultRequestCycles,
     {
         let rpc_method = self.request.endpoint.rpc_method();
@@ -949,21 +2617,176 @@ impl<R: Runtime, Config, Params, CandidOutput, Output>
     /// either the request response or any error that occurs while sending the request.
     pub async fn try_send(self) -> Result<Output, IcError>
     where
-        Config: CandidType + Send,
-        Params: CandidType + Send,
+        Config: CandidType + Send + 'static,
+        Params: CandidType + Send + 'static,
         CandidOutput: Into<Output> + CandidType + DeserializeOwned,
+        Output: 'static,
         RequestBuilder<R, Config, Params, CandidOutput, Output>: DefaultRequestCycles,
     {
-        let cycles = self
+        let mut cycles = self
             .request
             .cycles
             .unwrap_or_else(|| self.default_request_cycles());
-        self.client
-            .try_execute_request::<Config, Params, CandidOutput, Output>(self.request, cycles)
-            .await
+        let hooks = &self.client.config.hooks;
+        let mut request = self.request;
+        hooks.on_request(&mut request, &mut cycles);
+        if let Some(output) = self.client.cached_response(&request) {
+            return Ok(output);
+        }
+        let endpoint = request.endpoint.clone();
+        let mut result = self
+            .client
+            .try_execute_request::<Config, Params, CandidOutput, Output>(request, cycles)
+            .await;
+        hooks.on_response(&endpoint, cycles, &mut result);
+        let output = result?;
+        if let (Some(slot_tracker), Some(observed_slot)) =
+            (self.client.config.slot_tracker.as_ref(), self.observed_slot)
+        {
+            if let Some(slot) = observed_slot(&output) {
+                slot_tracker.observe_slot(slot);
+            }
+        }
+        Ok(output)
     }
 }
 
+impl<R: Runtime, Config, Params, CandidOutput, T>
+    RequestBuilder<R, Config, Params, CandidOutput, MultiRpcResult<T>>
+{
+    /// Sends that request separately to each of its RPC sources and returns a stream of the
+    /// result of each provider, in the order in which they arrive.
+    ///
+    /// Instead of waiting for all providers to aggregate their results with the consensus
+    /// strategy, latency-sensitive consumers can act on the first result while still observing
+    /// the other ones, e.g. for confirmation. Each provider is queried by a separate call to the
+    /// SOL RPC canister. The cycles of that request, if set, are attached to each call. Otherwise,
+    /// the cycles cost of the request to that single provider is first queried with
+    /// [`RequestBuilder::request_cost`], and any error doing so is returned as the result of that
+    /// provider.
+    ///
+    /// # Errors
+    ///
+    /// * If that request uses [`RpcSources::Default`], since the default providers are only
+    ///   chosen by the SOL RPC canister when handling the request.
+    /// * If that request uses a consensus strategy other than [`ConsensusStrategy::Equality`],
+    ///   since the result of each provider is returned separately.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use sol_rpc_client::SolRpcClient;
+    /// use sol_rpc_types::{RpcSource, RpcSources, SupportedRpcProviderId};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use sol_rpc_types::MultiRpcResult;
+    /// let client = SolRpcClient::builder_for_ic()
+    /// #   .with_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+    /// #   .add_stub_response(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+    ///     .with_rpc_sources(RpcSources::Custom(vec![
+    ///         RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+    ///         RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet),
+    ///     ]))
+    ///     .build();
+    ///
+    /// let mut results = client
+    ///     .get_slot()
+    ///     .with_cycles(2_000_000_000)
+    ///     .send_per_provider()?;
+    ///
+    /// let (_source, first) = results.next().await.unwrap();
+    /// assert_eq!(first, Ok(Ok(332_577_897_u64)));
+    /// let (_source, second) = results.next().await.unwrap();
+    /// assert_eq!(second, Ok(Ok(332_577_897_u64)));
+    /// assert!(results.next().await.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_per_provider(
+        self,
+    ) -> Result<impl Stream<Item = (RpcSource, Result<RpcResult<T>, IcError>)>, SendPerProviderError>
+    where
+        Config: CandidType + Clone + SolRpcConfig + Default + Send + 'static,
+        Params: CandidType + Clone + Send + 'static,
+        CandidOutput: Into<MultiRpcResult<T>> + CandidType + DeserializeOwned,
+        T: 'static,
+        Self: DefaultRequestCycles,
+    {
+        let sources = match &self.request.rpc_sources {
+            RpcSources::Custom(sources) => sources.clone(),
+            RpcSources::Default(cluster) => {
+                return Err(SendPerProviderError::DefaultRpcSources(*cluster))
+            }
+        };
+        if let Some(strategy) = self
+            .request
+            .rpc_config
+            .as_ref()
+            .and_then(|config| config.response_consensus())
+            .filter(|strategy| **strategy != ConsensusStrategy::Equality)
+        {
+            return Err(SendPerProviderError::UnsupportedConsensusStrategy(
+                strategy.clone(),
+            ));
+        }
+        let cycles = self.request.cycles;
+        Ok(sources
+            .into_iter()
+            .map(|source| {
+                let mut request = self
+                    .clone()
+                    .with_response_consensus(ConsensusStrategy::Equality);
+                request.request.rpc_sources = RpcSources::Custom(vec![source.clone()]);
+                async move {
+                    let request = match cycles {
+                        Some(cycles) => request.with_cycles(cycles),
+                        None => match request.clone().request_cost().try_send().await {
+                            Ok(Ok(cycles)) => request.with_cycles(cycles),
+                            Ok(Err(e)) => return (source, Ok(Err(e))),
+                            Err(e) => return (source, Err(e)),
+                        },
+                    };
+                    let result = request.try_send().await.map(into_provider_result);
+                    (source, result)
+                }
+            })
+            .collect::<FuturesUnordered<_>>())
+    }
+}
+
+/// Extracts the result of the single provider queried by a request.
+fn into_provider_result<T>(output: MultiRpcResult<T>) -> RpcResult<T> {
+    match output {
+        MultiRpcResult::Consistent(result) => result,
+        MultiRpcResult::Inconsistent(results) => results
+            .into_iter()
+            .next()
+            .map(|(_source, result)| result)
+            .unwrap_or_else(|| {
+                Err(RpcError::ValidationError(
+                    "No result returned by the provider".to_string(),
+                ))
+            }),
+    }
+}
+
+/// An error that occurred while trying to send a request separately to each of its RPC sources.
+/// See [`RequestBuilder::send_per_provider`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SendPerProviderError {
+    /// The request uses the default providers of the given cluster, which are only chosen by
+    /// the SOL RPC canister when handling the request. The results of these providers can be
+    /// obtained with [`ConsensusStrategy::CollectAll`] instead.
+    #[error("Providers must be specified explicitly, got default providers for {0}")]
+    DefaultRpcSources(SolanaCluster),
+    /// The request uses a consensus strategy other than [`ConsensusStrategy::Equality`], which
+    /// cannot be applied to the result of a single provider.
+    #[error("Unsupported consensus strategy when sending a request per provider: {0:?}")]
+    UnsupportedConsensusStrategy(ConsensusStrategy),
+}
+
 impl<Runtime, Params, CandidOutput, Output>
     RequestBuilder<Runtime, GetRecentPrioritizationFeesRpcConfig, Params, CandidOutput, Output>
 {
@@ -996,6 +2819,75 @@ impl<Runtime, Params, CandidOutput, Output>
     }
 }
 
+impl<Runtime, Params, CandidOutput, Output>
+    RequestBuilder<Runtime, GetBlockHeightRpcConfig, Params, CandidOutput, Output>
+{
+    /// Change the rounding error for `getBlockHeight` request.
+    pub fn with_rounding_error<T: Into<RoundingError>>(mut self, rounding_error: T) -> Self {
+        let config = self.request.rpc_config_mut().get_or_insert_default();
+        config.rounding_error = Some(rounding_error.into());
+        self
+    }
+}
+
+impl<Runtime, Params, CandidOutput, Output>
+    RequestBuilder<Runtime, GetEpochInfoRpcConfig, Params, CandidOutput, Output>
+{
+    /// Change the rounding error for the absolute slot and the block height of a `getEpochInfo`
+    /// request.
+    pub fn with_rounding_error<T: Into<RoundingError>>(mut self, rounding_error: T) -> Self {
+        let config = self.request.rpc_config_mut().get_or_insert_default();
+        config.rounding_error = Some(rounding_error.into());
+        self
+    }
+}
+
+impl<Runtime, Params, CandidOutput, Output>
+    RequestBuilder<Runtime, GetTransactionCountRpcConfig, Params, CandidOutput, Output>
+{
+    /// Change the rounding error for `getTransactionCount` request.
+    pub fn with_rounding_error<T: Into<RoundingError>>(mut self, rounding_error: T) -> Self {
+        let config = self.request.rpc_config_mut().get_or_insert_default();
+        config.rounding_error = Some(rounding_error.into());
+        self
+    }
+}
+
+impl<Runtime, Params, CandidOutput, Output>
+    RequestBuilder<Runtime, GetSupplyRpcConfig, Params, CandidOutput, Output>
+{
+    /// Change the rounding error for `getSupply` request.
+    pub fn with_rounding_error<T: Into<RoundingError>>(mut self, rounding_error: T) -> Self {
+        let config = self.request.rpc_config_mut().get_or_insert_default();
+        config.rounding_error = Some(rounding_error.into());
+        self
+    }
+}
+
+impl<Runtime, Params, CandidOutput, Output>
+    RequestBuilder<Runtime, WithContextRpcConfig, Params, CandidOutput, Output>
+{
+    /// Change the rounding error for the context slot returned alongside the value.
+    pub fn with_context_slot_rounding_error<T: Into<RoundingError>>(
+        mut self,
+        rounding_error: T,
+    ) -> Self {
+        let config = self.request.rpc_config_mut().get_or_insert_default();
+        config.context_slot_rounding_error = Some(rounding_error.into());
+        self
+    }
+}
+
+impl<Runtime, Config, Params, CandidOutput, T>
+    RequestBuilder<Runtime, Config, Params, CandidOutput, MultiRpcResult<WithContext<T>>>
+{
+    /// Record the context slot of the response in the client's [`SlotTracker`], if any.
+    fn observing_context_slot(mut self) -> Self {
+        self.observed_slot = Some(consistent_context_slot::<T>);
+        self
+    }
+}
+
 /// A request which can be executed with `SolRpcClient::execute_request` or `SolRpcClient::execute_query_request`.
 pub struct Request<Config, Params, CandidOutput, Output> {
     pub(super) endpoint: SolRpcEndpoint,
@@ -1093,6 +2985,27 @@ impl<Config, Params, CandidOutput, Output> Request<Config, Params, CandidOutput,
     }
 }
 
+/// A call to the SOL RPC canister prepared by [`RequestBuilder::build_args`], but not sent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreparedCall<Config, Params> {
+    /// The principal of the SOL RPC canister.
+    pub canister_id: Principal,
+    /// The name of the method called on the SOL RPC canister, e.g. `getSlot`.
+    pub method: &'static str,
+    /// The arguments of the call.
+    pub args: (RpcSources, Option<Config>, Params),
+    /// The amount of cycles attached to the call.
+    pub cycles: u128,
+}
+
+impl<Config: CandidType, Params: CandidType> PreparedCall<Config, Params> {
+    /// Encodes the arguments of the call in Candid, as they would be sent to the SOL RPC
+    /// canister.
+    pub fn encode_args(&self) -> Result<Vec<u8>, candid::Error> {
+        candid::encode_args((&self.args.0, &self.args.1, &self.args.2))
+    }
+}
+
 pub type RequestCost<Config, Params> = Request<Config, Params, RpcResult<u128>, RpcResult<u128>>;
 
 #[must_use = "RequestCostBuilder does nothing until you 'send' it"]
@@ -1110,6 +3023,18 @@ impl<R: Runtime, Config, Params> RequestCostBuilder<R, Config, Params> {
     {
         self.client.execute_cycles_cost_request(self.request).await
     }
+
+    /// Constructs the [`Request`] and send it using the [`SolRpcClient`]. This method returns
+    /// either the request response or any error that occurs while sending the request.
+    pub async fn try_send(self) -> Result<RpcResult<u128>, IcError>
+    where
+        Config: CandidType + Send,
+        Params: CandidType + Send,
+    {
+        self.client
+            .try_execute_cycles_cost_request(self.request)
+            .await
+    }
 }
 
 fn set_default<T>(default_value: Option<T>, value: &mut Option<T>) {
@@ -1120,6 +3045,23 @@ fn set_default<T>(default_value: Option<T>, value: &mut Option<T>) {
     }
 }
 
+/// Use the given default commitment level for a `getBlock`, `getBlocks` or `getBlocksWithLimit`
+/// request, see [`CommitmentPolicy::ConfirmedOrFinalized`].
+fn default_block_commitment_level(
+    default_commitment_level: Option<CommitmentLevel>,
+) -> Option<GetBlockCommitmentLevel> {
+    default_commitment_level
+        .and_then(|commitment| {
+            CommitmentPolicy::ConfirmedOrFinalized.effective_commitment(commitment)
+        })
+        .map(|commitment| match commitment {
+            CommitmentLevel::Processed | CommitmentLevel::Confirmed => {
+                GetBlockCommitmentLevel::Confirmed
+            }
+            CommitmentLevel::Finalized => GetBlockCommitmentLevel::Finalized,
+        })
+}
+
 /// An error that occurred while trying to fetch a recent block.
 /// See [`SolRpcClient::get_recent_block`]
 #[derive(Debug, Clone, PartialEq, Error)]
@@ -1260,3 +3202,384 @@ impl<R: Runtime> GetRecentBlockRequestBuilder<R> {
         }
     }
 }
+
+/// An error that occurred while trying to simulate and then send a transaction.
+/// See [`SendTransactionRequestBuilder::with_preflight_simulation`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SimulateThenSendTransactionError {
+    /// The simulation of the transaction failed and the transaction was not sent.
+    #[error("Transaction simulation failed: {0:?}")]
+    SimulationFailed(TransactionSimulationFailure),
+    /// The results from the different providers were not consistent for a `simulateTransaction`
+    /// call.
+    #[error("Inconsistent result while simulating transaction: {0:?}")]
+    SimulateTransactionConsensusError(Vec<(RpcSource, RpcResult<String>)>),
+    /// An error occurred during a `simulateTransaction` call.
+    #[error("Error while simulating transaction: {0}")]
+    SimulateTransactionRpcError(RpcError),
+    /// An IC error occurred while making the request.
+    #[error("IC error: {0}")]
+    IcError(IcError),
+}
+
+/// The outcome of a failed [`simulateTransaction`] call.
+///
+/// [`simulateTransaction`]: https://solana.com/docs/rpc/http/simulatetransaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionSimulationFailure {
+    /// The error returned by the simulation.
+    pub err: TransactionError,
+    /// The log messages emitted during the simulation.
+    pub logs: Vec<String>,
+    /// The number of compute units consumed during the simulation, if known.
+    pub units_consumed: Option<u64>,
+}
+
+/// Solana JSON-RPC error code returned by `sendTransaction` when the transaction fails the
+/// preflight checks, see [`solana-rpc-client-api`](https://github.com/anza-xyz/agave/blob/master/rpc-client-api/src/custom_error.rs).
+const JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE: i64 = -32002;
+
+impl TransactionSimulationFailure {
+    /// Decodes the failed preflight simulation from an error returned by a [`sendTransaction`]
+    /// call, e.g. to distinguish [`TransactionError::InsufficientFundsForFee`] from a
+    /// [`TransactionError::InstructionError`] without matching on the JSON-RPC error message.
+    ///
+    /// Returns `None` if the error is not a preflight failure or if it does not contain the
+    /// result of the simulation.
+    ///
+    /// [`sendTransaction`]: https://solana.com/docs/rpc/http/sendtransaction
+    pub fn from_preflight_error(error: &RpcError) -> Option<Self> {
+        match error {
+            RpcError::JsonRpcError(JsonRpcError {
+                code: JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE,
+                data: Some(data),
+                ..
+            }) => serde_json::from_str::<SimulateTransactionResult>(data)
+                .ok()?
+                .into_failure(),
+            _ => None,
+        }
+    }
+}
+
+/// The result of a `simulateTransaction` call, which is also the data of the JSON-RPC error
+/// returned by `sendTransaction` when the preflight checks fail.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulateTransactionResult {
+    err: Option<UiTransactionError>,
+    logs: Option<Vec<String>>,
+    units_consumed: Option<u64>,
+}
+
+impl SimulateTransactionResult {
+    fn into_failure(self) -> Option<TransactionSimulationFailure> {
+        self.err.map(|err| TransactionSimulationFailure {
+            err: TransactionError::from(err),
+            logs: self.logs.unwrap_or_default(),
+            units_consumed: self.units_consumed,
+        })
+    }
+}
+
+/// A builder to build a request to send a transaction after simulating it.
+/// See [`SendTransactionRequestBuilder::with_preflight_simulation`].
+#[must_use = "SimulateThenSendTransactionRequestBuilder does nothing until you 'send' it"]
+pub struct SimulateThenSendTransactionRequestBuilder<R> {
+    send_transaction: SendTransactionRequestBuilder<R>,
+}
+
+impl<R> SimulateThenSendTransactionRequestBuilder<R> {
+    /// Create a new [`SimulateThenSendTransactionRequestBuilder`] that simulates the transaction
+    /// of the given `sendTransaction` request before sending it.
+    pub fn new(send_transaction: SendTransactionRequestBuilder<R>) -> Self {
+        Self { send_transaction }
+    }
+
+    /// Construct the `simulateTransaction` request to send via `jsonRequest`.
+    ///
+    /// The simulation uses the same encoding, commitment level and minimum context slot as the
+    /// `sendTransaction` request, but is only sent to a single provider: the first one in case
+    /// of custom providers and any of the default providers otherwise.
+    fn simulate_transaction_request(&self) -> RpcResult<JsonRequestBuilder<R>> {
+        let Request {
+            rpc_sources,
+            rpc_config,
+            params,
+            ..
+        } = &self.send_transaction.request;
+        let config = serde_json::json!({
+            "sigVerify": true,
+            "encoding": params.get_encoding(),
+            "commitment": params.preflight_commitment,
+            "minContextSlot": params.min_context_slot,
+        });
+        let rpc_sources = match rpc_sources.clone() {
+            RpcSources::Custom(sources) => {
+                RpcSources::Custom(sources.into_iter().take(1).collect())
+            }
+            default_sources @ RpcSources::Default(_) => default_sources,
+        };
+        let rpc_config = RpcConfig {
+            response_size_estimate: rpc_config
+                .as_ref()
+                .and_then(|config| config.response_size_estimate),
+            response_consensus: Some(ConsensusStrategy::Threshold {
+                total: Some(1),
+                min: 1,
+            }),
+            priority: None,
+        };
+        let mut request = self
+            .send_transaction
+            .client
+            .try_json_request(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "simulateTransaction",
+                "params": [params.get_transaction(), config]
+            }))?
+            .with_rpc_config(rpc_config);
+        request.request.rpc_sources = rpc_sources;
+        Ok(request)
+    }
+}
+
+impl<R: Runtime> SimulateThenSendTransactionRequestBuilder<R> {
+    /// Simulates the transaction with [`simulateTransaction`] using a single provider and, if the
+    /// simulation succeeds, sends it with [`sendTransaction`] using the [`SolRpcClient`].
+    ///
+    /// [`simulateTransaction`]: https://solana.com/docs/rpc/http/simulatetransaction
+    /// [`sendTransaction`]: https://solana.com/docs/rpc/http/sendtransaction
+    pub async fn try_send(
+        self,
+    ) -> Result<MultiRpcResult<solana_signature::Signature>, SimulateThenSendTransactionError> {
+        self.simulate().await?;
+        self.send_transaction
+            .try_send()
+            .await
+            .map_err(SimulateThenSendTransactionError::IcError)
+    }
+
+    async fn simulate(&self) -> Result<(), SimulateThenSendTransactionError> {
+        let request = self
+            .simulate_transaction_request()
+            .map_err(SimulateThenSendTransactionError::SimulateTransactionRpcError)?;
+        let response = match request.try_send().await {
+            Ok(MultiRpcResult::Consistent(Ok(response))) => response,
+            Ok(MultiRpcResult::Consistent(Err(e))) => {
+                return Err(SimulateThenSendTransactionError::SimulateTransactionRpcError(e))
+            }
+            Ok(MultiRpcResult::Inconsistent(results)) => {
+                return Err(
+                    SimulateThenSendTransactionError::SimulateTransactionConsensusError(results),
+                )
+            }
+            Err(e) => return Err(SimulateThenSendTransactionError::IcError(e)),
+        };
+        match parse_simulate_transaction_response(&response)
+            .map_err(SimulateThenSendTransactionError::SimulateTransactionRpcError)?
+        {
+            Some(failure) => Err(SimulateThenSendTransactionError::SimulationFailed(failure)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse the JSON-RPC response of a `simulateTransaction` call, returning the simulation
+/// failure, if any.
+fn parse_simulate_transaction_response(
+    response: &str,
+) -> RpcResult<Option<TransactionSimulationFailure>> {
+    #[derive(Deserialize)]
+    struct JsonRpcResponse {
+        result: Option<WithContextValue>,
+        error: Option<JsonRpcErrorObject>,
+    }
+
+    #[derive(Deserialize)]
+    struct JsonRpcErrorObject {
+        code: i64,
+        message: String,
+        data: Option<serde_json::Value>,
+    }
+
+    #[derive(Deserialize)]
+    struct WithContextValue {
+        value: SimulateTransactionResult,
+    }
+
+    let response: JsonRpcResponse = serde_json::from_str(response).map_err(|e| {
+        RpcError::ValidationError(format!("Invalid simulateTransaction response: {e}"))
+    })?;
+    match (response.result, response.error) {
+        (_, Some(error)) => Err(RpcError::JsonRpcError(JsonRpcError {
+            code: error.code,
+            message: error.message,
+            data: error.data.map(|data| data.to_string()),
+        })),
+        (Some(WithContextValue { value }), None) => Ok(value.into_failure()),
+        (None, None) => Err(RpcError::ValidationError(
+            "Invalid simulateTransaction response: missing result".to_string(),
+        )),
+    }
+}
+
+/// An error that occurred while trying to send a transaction with automatic blockhash refresh.
+/// See [`SendTransactionRequestBuilder::with_blockhash_refresh`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SendTransactionWithBlockhashRefreshError {
+    /// A fresh blockhash could not be fetched.
+    #[error("Failed to fetch a recent block: {0:?}")]
+    GetRecentBlockError(Vec<GetRecentBlockError>),
+    /// The blockhash of the fetched block could not be parsed.
+    #[error("Invalid blockhash: {0}")]
+    InvalidBlockhash(String),
+    /// The signer failed to re-sign the transaction with the fresh blockhash.
+    #[error("Failed to sign transaction: {0}")]
+    SigningError(String),
+    /// An IC error occurred while making the request.
+    #[error("IC error: {0}")]
+    IcError(IcError),
+}
+
+/// A builder to build a request to send a transaction, which is automatically re-signed with a
+/// fresh blockhash and resent if the providers report that its blockhash was not found.
+/// See [`SendTransactionRequestBuilder::with_blockhash_refresh`].
+#[must_use = "SendTransactionWithBlockhashRefreshRequestBuilder does nothing until you 'send' it"]
+pub struct SendTransactionWithBlockhashRefreshRequestBuilder<R, F> {
+    send_transaction: SendTransactionRequestBuilder<R>,
+    signer: F,
+    max_refreshes: usize,
+}
+
+impl<R, F> SendTransactionWithBlockhashRefreshRequestBuilder<R, F> {
+    /// Create a new [`SendTransactionWithBlockhashRefreshRequestBuilder`] that re-signs the
+    /// transaction of the given `sendTransaction` request with the given `signer`.
+    ///
+    /// The maximum number of times the blockhash is refreshed is set to 3.
+    pub fn new(send_transaction: SendTransactionRequestBuilder<R>, signer: F) -> Self {
+        Self {
+            send_transaction,
+            signer,
+            max_refreshes: 3,
+        }
+    }
+
+    /// Sets the maximum number of times the blockhash is refreshed and the transaction resent.
+    ///
+    /// Each refresh consists of fetching a recent block (see
+    /// [`SolRpcClient::get_recent_block`]), re-signing the transaction and resending it.
+    pub fn with_max_refreshes(mut self, max_refreshes: usize) -> Self {
+        self.max_refreshes = max_refreshes;
+        self
+    }
+
+    /// Construct the `sendTransaction` request for the given transaction, using the same
+    /// parameters, RPC sources and config as the original request.
+    fn send_transaction_request(
+        &self,
+        transaction: SendTransactionParams,
+    ) -> SendTransactionRequestBuilder<R> {
+        let RequestBuilder {
+            client,
+            request,
+            observed_slot,
+        } = &self.send_transaction;
+        let mut params = transaction;
+        params.skip_preflight = request.params.skip_preflight;
+        params.preflight_commitment = request.params.preflight_commitment.clone();
+        params.max_retries = request.params.max_retries;
+        params.min_context_slot = request.params.min_context_slot;
+        params.validate_blockhash = request.params.validate_blockhash;
+        RequestBuilder {
+            client: client.clone(),
+            request: Request {
+                endpoint: request.endpoint.clone(),
+                rpc_sources: request.rpc_sources.clone(),
+                rpc_config: request.rpc_config.clone(),
+                params,
+                cycles: request.cycles,
+                _candid_marker: Default::default(),
+                _output_marker: Default::default(),
+            },
+            observed_slot: *observed_slot,
+        }
+    }
+}
+
+impl<R, F, Fut> SendTransactionWithBlockhashRefreshRequestBuilder<R, F>
+where
+    R: Runtime,
+    F: Fn(solana_hash::Hash) -> Fut,
+    Fut: Future<Output = Result<SendTransactionParams, String>>,
+{
+    /// Sends the transaction with [`sendTransaction`] using the [`SolRpcClient`] and, as long as
+    /// the providers consistently report that its blockhash was not found and the maximum number
+    /// of refreshes is not reached, fetches a fresh blockhash, re-signs the transaction and
+    /// resends it.
+    ///
+    /// The result of the last `sendTransaction` call is returned.
+    ///
+    /// [`sendTransaction`]: https://solana.com/docs/rpc/http/sendtransaction
+    pub async fn try_send(
+        self,
+    ) -> Result<MultiRpcResult<solana_signature::Signature>, SendTransactionWithBlockhashRefreshError>
+    {
+        let mut transaction = self.send_transaction.request.params.clone();
+        let mut num_refreshes = 0;
+        loop {
+            let result = self
+                .send_transaction_request(transaction)
+                .try_send()
+                .await
+                .map_err(SendTransactionWithBlockhashRefreshError::IcError)?;
+            let should_refresh = matches!(
+                &result,
+                MultiRpcResult::Consistent(Err(e)) if is_blockhash_not_found(e)
+            );
+            if !should_refresh || num_refreshes >= self.max_refreshes {
+                return Ok(result);
+            }
+            num_refreshes += 1;
+            let blockhash = self.fetch_blockhash().await?;
+            transaction = (self.signer)(blockhash)
+                .await
+                .map_err(SendTransactionWithBlockhashRefreshError::SigningError)?;
+        }
+    }
+
+    async fn fetch_blockhash(
+        &self,
+    ) -> Result<solana_hash::Hash, SendTransactionWithBlockhashRefreshError> {
+        let (_slot, block) = self
+            .send_transaction
+            .client
+            .get_recent_block()
+            .try_send()
+            .await
+            .map_err(SendTransactionWithBlockhashRefreshError::GetRecentBlockError)?;
+        block.blockhash.parse().map_err(|e| {
+            SendTransactionWithBlockhashRefreshError::InvalidBlockhash(format!(
+                "{}: {e}",
+                block.blockhash
+            ))
+        })
+    }
+}
+
+/// Whether the given error indicates that the recent blockhash of a transaction was not found,
+/// either because the providers rejected the transaction during the preflight checks, or because
+/// the SOL RPC canister determined that the blockhash is no longer valid (see
+/// [`SendTransactionRequestBuilder::with_blockhash_validation`]).
+fn is_blockhash_not_found(error: &RpcError) -> bool {
+    match error {
+        RpcError::JsonRpcError(JsonRpcError { message, .. }) => {
+            message.contains("Blockhash not found")
+        }
+        RpcError::ValidationError(message) => {
+            message == "The recent blockhash of the transaction is no longer valid"
+        }
+        _ => false,
+    }
+}
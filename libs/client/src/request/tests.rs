@@ -1,14 +1,25 @@
-use crate::{GetRecentBlockError, RequestBuilder, SolRpcClient, SolRpcEndpoint};
+use crate::{
+    GetRecentBlockError, PreparedCall, RequestBuilder, SolRpcClient, SolRpcEndpoint,
+    SOL_RPC_CANISTER,
+};
 use serde_json::json;
 use sol_rpc_types::{
-    CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetAccountInfoParams, GetBalanceParams,
-    GetBlockCommitmentLevel, GetBlockParams, GetSignatureStatusesParams,
-    GetSignaturesForAddressParams, GetSlotParams, GetTokenAccountBalanceParams,
-    GetTransactionEncoding, GetTransactionParams, SendTransactionEncoding, SendTransactionParams,
-    Slot, TransactionDetails,
+    CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetAccountInfoParams,
+    GetAccountSnapshotParams, GetBalanceParams, GetBlockCommitmentLevel, GetBlockEncoding,
+    GetBlockHeightParams, GetBlockParams, GetBlocksParams, GetBlocksWithLimitParams,
+    GetClusterNodesParams, GetEpochInfoParams, GetFeeForMessageParams, GetLeaderScheduleParams,
+    GetMinimumBalanceForRentExemptionParams, GetRecentPerformanceSamplesParams,
+    GetSignatureStatusesParams, GetSignaturesForAddressParams, GetSlotLeadersParams, GetSlotParams,
+    GetSupplyParams, GetTokenAccountBalanceParams, GetTokenAccountsByDelegateParams,
+    GetTokenAccountsByOwnerParams, GetTransactionCountParams, GetTransactionEncoding,
+    GetTransactionParams, HasAccountChangedParams, HttpHeader, RawHttpRequestParams,
+    SendTransactionEncoding, SendTransactionParams, Slot, TokenAccountsFilter, TransactionDetails,
 };
 use sol_rpc_types::{
-    ConfirmedBlock, Hash, MultiRpcResult, RpcError, RpcSource, SupportedRpcProviderId,
+    ConfirmedBlock, GetBlockHeightRpcConfig, GetEpochInfoRpcConfig, GetSlotRpcConfig,
+    GetSupplyRpcConfig, GetTransactionCountRpcConfig, Hash, JsonRequestRpcConfig, MultiRpcResult,
+    RequestPriority, RoundingError, RpcConfig, RpcError, RpcSource, RpcSources, SolanaCluster,
+    SupportedRpcProviderId, WithContextRpcConfig,
 };
 use solana_pubkey::{pubkey, Pubkey};
 use solana_signature::Signature;
@@ -36,6 +47,22 @@ fn should_set_correct_commitment_level() {
                     Some(CommitmentLevel::Confirmed)
                 );
             }
+            SolRpcEndpoint::GetAccountInfoWithContext => {
+                let builder = client_with_commitment_level
+                    .get_account_info(PUBKEY)
+                    .with_context();
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetAccountSnapshot => {
+                let builder = client_with_commitment_level.get_account_snapshot(PUBKEY);
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
             SolRpcEndpoint::GetBalance => {
                 let builder = client_with_commitment_level.get_balance(PUBKEY);
                 assert_eq!(
@@ -43,6 +70,15 @@ fn should_set_correct_commitment_level() {
                     Some(CommitmentLevel::Confirmed)
                 );
             }
+            SolRpcEndpoint::GetBalanceWithContext => {
+                let builder = client_with_commitment_level
+                    .get_balance(PUBKEY)
+                    .with_context();
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
             SolRpcEndpoint::GetBlock => {
                 let builder = client_with_commitment_level.get_block(1_u64);
                 assert_eq!(
@@ -50,6 +86,68 @@ fn should_set_correct_commitment_level() {
                     Some(GetBlockCommitmentLevel::Confirmed)
                 );
             }
+            SolRpcEndpoint::GetBlockHeight => {
+                let builder = client_with_commitment_level.get_block_height();
+                assert_eq!(
+                    builder.request.params.and_then(|p| p.commitment),
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetBlocks => {
+                let builder = client_with_commitment_level.get_blocks(1_u64);
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(GetBlockCommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetBlocksWithLimit => {
+                let builder = client_with_commitment_level.get_blocks_with_limit(1_u64);
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(GetBlockCommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetClusterNodes => {
+                // no op, GetClusterNodes does not use commitment level
+            }
+            SolRpcEndpoint::GetEpochInfo => {
+                let builder = client_with_commitment_level.get_epoch_info();
+                assert_eq!(
+                    builder.request.params.and_then(|p| p.commitment),
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetFeeForMessage => {
+                let builder = client_with_commitment_level.get_fee_for_message(message_params());
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetLeaderSchedule => {
+                let builder = client_with_commitment_level.get_leader_schedule(PUBKEY);
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetMinimumBalanceForRentExemption => {
+                let builder =
+                    client_with_commitment_level.get_minimum_balance_for_rent_exemption(165_u64);
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetEpochSchedule => {
+                // no op, GetEpochSchedule does not use commitment level
+            }
+            SolRpcEndpoint::GetHighestSnapshotSlot => {
+                // no op, GetHighestSnapshotSlot does not use commitment level
+            }
+            SolRpcEndpoint::GetRecentPerformanceSamples => {
+                // no op, GetRecentPerformanceSamples does not use commitment level
+            }
             SolRpcEndpoint::GetRecentPrioritizationFees => {
                 // no op, GetRecentPrioritizationFees does not use commitment level
             }
@@ -70,6 +168,16 @@ fn should_set_correct_commitment_level() {
                     Some(CommitmentLevel::Confirmed)
                 );
             }
+            SolRpcEndpoint::GetSlotLeaders => {
+                // no op, GetSlotLeaders does not use commitment level
+            }
+            SolRpcEndpoint::GetSupply => {
+                let builder = client_with_commitment_level.get_supply();
+                assert_eq!(
+                    builder.request.params.and_then(|p| p.commitment),
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
             SolRpcEndpoint::GetTokenAccountBalance => {
                 let builder = client_with_commitment_level.get_token_account_balance(PUBKEY);
                 assert_eq!(
@@ -77,6 +185,35 @@ fn should_set_correct_commitment_level() {
                     Some(CommitmentLevel::Confirmed)
                 );
             }
+            SolRpcEndpoint::GetTokenAccountBalanceWithContext => {
+                let builder = client_with_commitment_level
+                    .get_token_account_balance(PUBKEY)
+                    .with_context();
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetTokenAccountsByDelegate => {
+                let builder = client_with_commitment_level.get_token_accounts_by_delegate((
+                    PUBKEY,
+                    TokenAccountsFilter::Mint(PUBKEY.into()),
+                ));
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::GetTokenAccountsByOwner => {
+                let builder = client_with_commitment_level.get_token_accounts_by_owner((
+                    PUBKEY,
+                    TokenAccountsFilter::Mint(PUBKEY.into()),
+                ));
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
             SolRpcEndpoint::GetTransaction => {
                 let builder = client_with_commitment_level.get_transaction(signature());
                 assert_eq!(
@@ -84,20 +221,43 @@ fn should_set_correct_commitment_level() {
                     Some(CommitmentLevel::Confirmed)
                 );
             }
+            SolRpcEndpoint::GetTransactionCount => {
+                let builder = client_with_commitment_level.get_transaction_count();
+                assert_eq!(
+                    builder.request.params.and_then(|p| p.commitment),
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
+            SolRpcEndpoint::HasAccountChanged => {
+                let builder = client_with_commitment_level.has_account_changed(PUBKEY);
+                assert_eq!(
+                    builder.request.params.commitment,
+                    Some(CommitmentLevel::Confirmed)
+                );
+            }
             SolRpcEndpoint::JsonRequest => {
                 let json_req = json!({ "jsonrpc": "2.0", "id": 1, "method": "getVersion" });
-                let builder_with_level =
-                    client_with_commitment_level.json_request(json_req.clone());
-                let builder_without_level = client_without_commitment_level.json_request(json_req);
+                let builder_with_level = client_with_commitment_level
+                    .try_json_request(json_req.clone())
+                    .unwrap();
+                let builder_without_level = client_without_commitment_level
+                    .try_json_request(json_req)
+                    .unwrap();
                 assert_eq!(builder_with_level.request, builder_without_level.request);
             }
+            SolRpcEndpoint::MinimumLedgerSlot => {
+                // no op, MinimumLedgerSlot does not use commitment level
+            }
+            SolRpcEndpoint::RawHttpRequest => {
+                // no op, RawHttpRequest does not use commitment level
+            }
             SolRpcEndpoint::SendTransaction => {
-                let builder = client_with_commitment_level.send_transaction(
-                    SendTransactionParams::from_encoded_transaction(
+                let builder = client_with_commitment_level
+                    .try_send_transaction(SendTransactionParams::from_encoded_transaction(
                         "abcD".to_string(),
                         SendTransactionEncoding::Base64,
-                    ),
-                );
+                    ))
+                    .unwrap();
                 assert_eq!(
                     builder.request.params.preflight_commitment,
                     Some(CommitmentLevel::Confirmed)
@@ -136,6 +296,65 @@ fn should_set_request_parameters() {
                         min_context_slot: Some(MIN_CONTEXT_SLOT),
                     }),
             ),
+            SolRpcEndpoint::GetAccountInfoWithContext => assert_params_eq(
+                client
+                    .get_account_info(PUBKEY)
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_context(),
+                client
+                    .get_account_info(PUBKEY)
+                    .with_context()
+                    .with_params(GetAccountInfoParams {
+                        pubkey: PUBKEY.into(),
+                        commitment: Some(CommitmentLevel::Confirmed),
+                        encoding: None,
+                        data_slice: None,
+                        min_context_slot: Some(MIN_CONTEXT_SLOT),
+                    }),
+            ),
+            SolRpcEndpoint::GetAccountSnapshot => assert_params_eq(
+                client
+                    .get_account_snapshot(PUBKEY)
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_encoding(GetAccountInfoEncoding::Base64)
+                    .with_data_slice(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    })
+                    .with_signatures_limit(456.try_into().unwrap())
+                    .with_signatures_before(signature())
+                    .with_signatures_until(another_signature()),
+                client.get_account_snapshot(GetAccountSnapshotParams {
+                    pubkey: PUBKEY.into(),
+                    commitment: Some(CommitmentLevel::Confirmed),
+                    min_context_slot: Some(MIN_CONTEXT_SLOT),
+                    encoding: Some(GetAccountInfoEncoding::Base64),
+                    data_slice: Some(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    }),
+                    signatures_limit: Some(456.try_into().unwrap()),
+                    signatures_before: Some(signature().into()),
+                    signatures_until: Some(another_signature().into()),
+                }),
+            ),
+            SolRpcEndpoint::GetBalanceWithContext => assert_params_eq(
+                client
+                    .get_balance(PUBKEY)
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_context(),
+                client
+                    .get_balance(PUBKEY)
+                    .with_context()
+                    .with_params(GetBalanceParams {
+                        pubkey: PUBKEY.into(),
+                        commitment: Some(CommitmentLevel::Confirmed),
+                        min_context_slot: Some(MIN_CONTEXT_SLOT),
+                    }),
+            ),
             SolRpcEndpoint::GetBalance => assert_params_eq(
                 client
                     .get_balance(PUBKEY)
@@ -153,15 +372,120 @@ fn should_set_request_parameters() {
                     .with_commitment(GetBlockCommitmentLevel::Confirmed)
                     .with_max_supported_transaction_version(0)
                     .with_transaction_details(TransactionDetails::Signatures)
-                    .without_rewards(),
+                    .without_rewards()
+                    .with_encoding(GetBlockEncoding::JsonParsed),
                 client.get_block(GetBlockParams {
                     slot: 123,
                     commitment: Some(GetBlockCommitmentLevel::Confirmed),
                     max_supported_transaction_version: Some(0),
                     transaction_details: Some(TransactionDetails::Signatures),
                     rewards: Some(false),
+                    encoding: Some(GetBlockEncoding::JsonParsed),
                 }),
             ),
+            SolRpcEndpoint::GetBlockHeight => assert_params_eq(
+                client
+                    .get_block_height()
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_commitment(CommitmentLevel::Confirmed),
+                client
+                    .get_block_height()
+                    .with_params(Some(GetBlockHeightParams {
+                        commitment: Some(CommitmentLevel::Confirmed),
+                        min_context_slot: Some(MIN_CONTEXT_SLOT),
+                    })),
+            ),
+            SolRpcEndpoint::GetBlocks => assert_params_eq(
+                client
+                    .get_blocks(123)
+                    .with_end_slot(456)
+                    .with_commitment(GetBlockCommitmentLevel::Finalized),
+                client.get_blocks(GetBlocksParams {
+                    start_slot: 123,
+                    end_slot: Some(456),
+                    commitment: Some(GetBlockCommitmentLevel::Finalized),
+                }),
+            ),
+            SolRpcEndpoint::GetBlocksWithLimit => assert_params_eq(
+                client
+                    .get_blocks_with_limit(123)
+                    .with_limit(456.try_into().unwrap())
+                    .with_commitment(GetBlockCommitmentLevel::Finalized),
+                client.get_blocks_with_limit(GetBlocksWithLimitParams {
+                    start_slot: 123,
+                    limit: 456.try_into().unwrap(),
+                    commitment: Some(GetBlockCommitmentLevel::Finalized),
+                }),
+            ),
+            SolRpcEndpoint::GetClusterNodes => assert_params_eq(
+                client
+                    .get_cluster_nodes()
+                    .with_after(PUBKEY)
+                    .with_limit(10_u32.try_into().unwrap()),
+                client
+                    .get_cluster_nodes()
+                    .with_params(Some(GetClusterNodesParams {
+                        after: Some(PUBKEY.into()),
+                        limit: Some(10_u32.try_into().unwrap()),
+                    })),
+            ),
+            SolRpcEndpoint::GetEpochInfo => assert_params_eq(
+                client
+                    .get_epoch_info()
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_commitment(CommitmentLevel::Confirmed),
+                client
+                    .get_epoch_info()
+                    .with_params(Some(GetEpochInfoParams {
+                        commitment: Some(CommitmentLevel::Confirmed),
+                        min_context_slot: Some(MIN_CONTEXT_SLOT),
+                    })),
+            ),
+            SolRpcEndpoint::GetFeeForMessage => assert_params_eq(
+                client
+                    .get_fee_for_message(message_params())
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_min_context_slot(MIN_CONTEXT_SLOT),
+                client
+                    .get_fee_for_message(message_params())
+                    .with_params(GetFeeForMessageParams {
+                        commitment: Some(CommitmentLevel::Confirmed),
+                        min_context_slot: Some(MIN_CONTEXT_SLOT),
+                        ..message_params()
+                    }),
+            ),
+            SolRpcEndpoint::GetLeaderSchedule => assert_params_eq(
+                client
+                    .get_leader_schedule(PUBKEY)
+                    .with_slot(SLOT)
+                    .with_commitment(CommitmentLevel::Confirmed),
+                client.get_leader_schedule(GetLeaderScheduleParams {
+                    slot: Some(SLOT),
+                    identity: PUBKEY.into(),
+                    commitment: Some(CommitmentLevel::Confirmed),
+                }),
+            ),
+            SolRpcEndpoint::GetMinimumBalanceForRentExemption => assert_params_eq(
+                client
+                    .get_minimum_balance_for_rent_exemption(165_u64)
+                    .with_commitment(CommitmentLevel::Confirmed),
+                client.get_minimum_balance_for_rent_exemption(
+                    GetMinimumBalanceForRentExemptionParams {
+                        data_length: 165,
+                        commitment: Some(CommitmentLevel::Confirmed),
+                    },
+                ),
+            ),
+            SolRpcEndpoint::GetRecentPerformanceSamples => assert_params_eq(
+                client
+                    .get_recent_performance_samples()
+                    .with_limit(456.try_into().unwrap()),
+                client.get_recent_performance_samples().with_params(Some(
+                    GetRecentPerformanceSamplesParams {
+                        limit: Some(456.try_into().unwrap()),
+                    },
+                )),
+            ),
             SolRpcEndpoint::GetRecentPrioritizationFees => {
                 // No optional request parameters
             }
@@ -172,7 +496,9 @@ fn should_set_request_parameters() {
                     .with_min_context_slot(MIN_CONTEXT_SLOT)
                     .with_limit(456.try_into().unwrap())
                     .with_before(signature())
-                    .with_until(another_signature()),
+                    .with_until(another_signature())
+                    .with_min_block_time(1_750_000_000)
+                    .with_max_block_time(1_750_003_600),
                 client.get_signatures_for_address(GetSignaturesForAddressParams {
                     pubkey: PUBKEY.into(),
                     commitment: Some(CommitmentLevel::Confirmed),
@@ -180,6 +506,8 @@ fn should_set_request_parameters() {
                     limit: Some(456.try_into().unwrap()),
                     before: Some(signature().into()),
                     until: Some(another_signature().into()),
+                    min_block_time: Some(1_750_000_000),
+                    max_block_time: Some(1_750_003_600),
                 }),
             ),
             SolRpcEndpoint::GetSignatureStatuses => assert_params_eq(
@@ -205,6 +533,25 @@ fn should_set_request_parameters() {
                     min_context_slot: Some(MIN_CONTEXT_SLOT),
                 })),
             ),
+            SolRpcEndpoint::GetSlotLeaders => assert_params_eq(
+                client
+                    .get_slot_leaders(123)
+                    .with_limit(456.try_into().unwrap()),
+                client.get_slot_leaders(GetSlotLeadersParams {
+                    start_slot: 123,
+                    limit: 456.try_into().unwrap(),
+                }),
+            ),
+            SolRpcEndpoint::GetSupply => assert_params_eq(
+                client
+                    .get_supply()
+                    .with_exclude_non_circulating_accounts_list(true)
+                    .with_commitment(CommitmentLevel::Confirmed),
+                client.get_supply().with_params(Some(GetSupplyParams {
+                    commitment: Some(CommitmentLevel::Confirmed),
+                    exclude_non_circulating_accounts_list: Some(true),
+                })),
+            ),
             SolRpcEndpoint::GetTokenAccountBalance => assert_params_eq(
                 client
                     .get_token_account_balance(PUBKEY)
@@ -214,6 +561,73 @@ fn should_set_request_parameters() {
                     commitment: Some(CommitmentLevel::Confirmed),
                 }),
             ),
+            SolRpcEndpoint::GetTokenAccountBalanceWithContext => assert_params_eq(
+                client
+                    .get_token_account_balance(PUBKEY)
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_context(),
+                client
+                    .get_token_account_balance(PUBKEY)
+                    .with_context()
+                    .with_params(GetTokenAccountBalanceParams {
+                        pubkey: PUBKEY.into(),
+                        commitment: Some(CommitmentLevel::Confirmed),
+                    }),
+            ),
+            SolRpcEndpoint::GetTokenAccountsByDelegate => assert_params_eq(
+                client
+                    .get_token_accounts_by_delegate((
+                        PUBKEY,
+                        TokenAccountsFilter::ProgramId(PUBKEY.into()),
+                    ))
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_encoding(GetAccountInfoEncoding::Base64)
+                    .with_data_slice(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    })
+                    .with_min_context_slot(MIN_CONTEXT_SLOT),
+                client.get_token_accounts_by_delegate(GetTokenAccountsByDelegateParams {
+                    delegate: PUBKEY.into(),
+                    filter: TokenAccountsFilter::ProgramId(PUBKEY.into()),
+                    commitment: Some(CommitmentLevel::Confirmed),
+                    encoding: Some(GetAccountInfoEncoding::Base64),
+                    data_slice: Some(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    }),
+                    min_context_slot: Some(MIN_CONTEXT_SLOT),
+                }),
+            ),
+            SolRpcEndpoint::GetTokenAccountsByOwner => assert_params_eq(
+                client
+                    .get_token_accounts_by_owner((
+                        PUBKEY,
+                        TokenAccountsFilter::ProgramId(PUBKEY.into()),
+                    ))
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_encoding(GetAccountInfoEncoding::Base64)
+                    .with_data_slice(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    })
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_after(PUBKEY)
+                    .with_limit(10_u32.try_into().unwrap()),
+                client.get_token_accounts_by_owner(GetTokenAccountsByOwnerParams {
+                    owner: PUBKEY.into(),
+                    filter: TokenAccountsFilter::ProgramId(PUBKEY.into()),
+                    commitment: Some(CommitmentLevel::Confirmed),
+                    encoding: Some(GetAccountInfoEncoding::Base64),
+                    data_slice: Some(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    }),
+                    min_context_slot: Some(MIN_CONTEXT_SLOT),
+                    after: Some(PUBKEY.into()),
+                    limit: Some(10_u32.try_into().unwrap()),
+                }),
+            ),
             SolRpcEndpoint::GetTransaction => assert_params_eq(
                 client
                     .get_transaction(signature())
@@ -226,27 +640,433 @@ fn should_set_request_parameters() {
                     max_supported_transaction_version: Some(0),
                     encoding: Some(GetTransactionEncoding::Base64),
                 }),
-            ),
-            SolRpcEndpoint::JsonRequest => {
-                // No optional request parameters
-            }
-            SolRpcEndpoint::SendTransaction => assert_params_eq(
-                client
-                    .send_transaction(transaction())
-                    .with_skip_preflight(true)
-                    .with_preflight_commitment(CommitmentLevel::Confirmed)
-                    .with_max_retries(10)
-                    .with_min_context_slot(MIN_CONTEXT_SLOT),
-                client
-                    .send_transaction(transaction())
-                    .modify_params(|params| {
-                        params.skip_preflight = Some(true);
-                        params.preflight_commitment = Some(CommitmentLevel::Confirmed);
-                        params.max_retries = Some(10);
-                        params.min_context_slot = Some(MIN_CONTEXT_SLOT);
-                    }),
-            ),
-        }
+            ),
+            SolRpcEndpoint::GetTransactionCount => assert_params_eq(
+                client
+                    .get_transaction_count()
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_commitment(CommitmentLevel::Confirmed),
+                client
+                    .get_transaction_count()
+                    .with_params(Some(GetTransactionCountParams {
+                        commitment: Some(CommitmentLevel::Confirmed),
+                        min_context_slot: Some(MIN_CONTEXT_SLOT),
+                    })),
+            ),
+            SolRpcEndpoint::HasAccountChanged => assert_params_eq(
+                client
+                    .has_account_changed(PUBKEY)
+                    .with_last_known_hash(Some(Hash::from_str(BLOCKHASH).unwrap()))
+                    .with_commitment(CommitmentLevel::Confirmed)
+                    .with_data_slice(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    })
+                    .with_min_context_slot(MIN_CONTEXT_SLOT),
+                client.has_account_changed(HasAccountChangedParams {
+                    pubkey: PUBKEY.into(),
+                    last_known_hash: Some(Hash::from_str(BLOCKHASH).unwrap()),
+                    commitment: Some(CommitmentLevel::Confirmed),
+                    data_slice: Some(DataSlice {
+                        length: 1,
+                        offset: 2,
+                    }),
+                    min_context_slot: Some(MIN_CONTEXT_SLOT),
+                }),
+            ),
+            SolRpcEndpoint::GetEpochSchedule
+            | SolRpcEndpoint::GetHighestSnapshotSlot
+            | SolRpcEndpoint::JsonRequest
+            | SolRpcEndpoint::MinimumLedgerSlot => {
+                // No optional request parameters
+            }
+            SolRpcEndpoint::RawHttpRequest => assert_eq!(
+                client
+                    .raw_http_request(b"body".to_vec())
+                    .with_header("Content-Type", "text/plain")
+                    .with_response_header("X-Request-Id")
+                    .request
+                    .params,
+                RawHttpRequestParams {
+                    body: b"body".to_vec(),
+                    headers: Some(vec![HttpHeader {
+                        name: "Content-Type".to_string(),
+                        value: "text/plain".to_string(),
+                    }]),
+                    response_headers: Some(vec!["X-Request-Id".to_string()]),
+                }
+            ),
+            SolRpcEndpoint::SendTransaction => assert_params_eq(
+                client
+                    .try_send_transaction(transaction())
+                    .unwrap()
+                    .with_skip_preflight(true)
+                    .with_preflight_commitment(CommitmentLevel::Confirmed)
+                    .with_max_retries(10)
+                    .with_min_context_slot(MIN_CONTEXT_SLOT)
+                    .with_blockhash_validation(true),
+                client
+                    .try_send_transaction(transaction())
+                    .unwrap()
+                    .modify_params(|params| {
+                        params.skip_preflight = Some(true);
+                        params.preflight_commitment = Some(CommitmentLevel::Confirmed);
+                        params.max_retries = Some(10);
+                        params.min_context_slot = Some(MIN_CONTEXT_SLOT);
+                        params.validate_blockhash = Some(true);
+                    }),
+            ),
+        }
+    }
+}
+
+#[test]
+fn should_keep_request_settings_when_adding_context() {
+    let client = SolRpcClient::builder_for_ic()
+        .with_rpc_config(RpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+        })
+        .build();
+
+    let builder = client
+        .get_balance(PUBKEY)
+        .with_cycles(123)
+        .with_context()
+        .with_context_slot_rounding_error(RoundingError::new(10));
+
+    assert_eq!(
+        builder.request.endpoint,
+        SolRpcEndpoint::GetBalanceWithContext
+    );
+    assert_eq!(builder.request.cycles, Some(123));
+    assert_eq!(
+        builder.request.rpc_config,
+        Some(WithContextRpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+            context_slot_rounding_error: Some(RoundingError::new(10)),
+        })
+    );
+}
+
+#[test]
+fn should_set_rounding_error_of_block_height_epoch_info_supply_and_transaction_count() {
+    let client = SolRpcClient::builder_for_ic()
+        .with_rpc_config(RpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+        })
+        .build();
+
+    assert_eq!(
+        client
+            .get_block_height()
+            .with_rounding_error(10)
+            .request
+            .rpc_config,
+        Some(GetBlockHeightRpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+            rounding_error: Some(RoundingError::new(10)),
+        })
+    );
+    assert_eq!(
+        client
+            .get_epoch_info()
+            .with_rounding_error(10)
+            .request
+            .rpc_config,
+        Some(GetEpochInfoRpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+            rounding_error: Some(RoundingError::new(10)),
+        })
+    );
+    assert_eq!(
+        client
+            .get_supply()
+            .with_rounding_error(10)
+            .request
+            .rpc_config,
+        Some(GetSupplyRpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+            rounding_error: Some(RoundingError::new(10)),
+        })
+    );
+    assert_eq!(
+        client
+            .get_transaction_count()
+            .with_rounding_error(10)
+            .request
+            .rpc_config,
+        Some(GetTransactionCountRpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+            rounding_error: Some(RoundingError::new(10)),
+        })
+    );
+}
+
+#[test]
+fn should_build_send_transaction_and_json_requests_without_panicking() {
+    let client = SolRpcClient::builder_for_ic().build();
+
+    let transaction_from_solana_type = client.try_send_transaction(transaction()).unwrap();
+    let transaction_from_params = client
+        .try_send_transaction(SendTransactionParams::try_from(transaction()).unwrap())
+        .unwrap();
+    assert_eq!(
+        transaction_from_solana_type.request,
+        transaction_from_params.request
+    );
+
+    let json = json!({ "jsonrpc": "2.0", "id": 1, "method": "getVersion" });
+    let json_request = client.try_json_request(json.clone()).unwrap();
+    assert_eq!(json_request.request.params, json.to_string());
+}
+
+#[test]
+fn should_set_raw_responses_of_json_request() {
+    let client = SolRpcClient::builder_for_ic()
+        .with_rpc_sources(RpcSources::Custom(vec![RpcSource::Supported(
+            SupportedRpcProviderId::AlchemyMainnet,
+        )]))
+        .build();
+
+    let json = json!({ "jsonrpc": "2.0", "id": 1, "method": "getVersion" });
+    assert_eq!(
+        client
+            .try_json_request(json)
+            .unwrap()
+            .with_response_size_estimate(1_000)
+            .with_raw_responses()
+            .request
+            .rpc_config,
+        Some(JsonRequestRpcConfig {
+            response_size_estimate: Some(1_000),
+            response_consensus: None,
+            priority: None,
+            raw_responses: Some(true),
+        })
+    );
+}
+
+#[test]
+fn should_set_request_priority() {
+    let client = SolRpcClient::builder_for_ic().build();
+
+    assert_eq!(
+        client
+            .get_slot()
+            .with_priority(RequestPriority::Expedited)
+            .request
+            .rpc_config,
+        Some(GetSlotRpcConfig {
+            priority: Some(RequestPriority::Expedited),
+            ..GetSlotRpcConfig::default()
+        })
+    );
+    assert_eq!(
+        client
+            .get_balance(PUBKEY)
+            .with_response_size_estimate(1_000)
+            .with_priority(RequestPriority::Expedited)
+            .request
+            .rpc_config,
+        Some(RpcConfig {
+            response_size_estimate: Some(1_000),
+            priority: Some(RequestPriority::Expedited),
+            ..RpcConfig::default()
+        })
+    );
+}
+
+#[test]
+fn should_build_args_with_request_hooks_applied() {
+    let client = SolRpcClient::builder_for_ic()
+        .with_rpc_sources(RpcSources::Default(SolanaCluster::Devnet))
+        .with_request_hook(|request| {
+            request.set_cycles(request.cycles() + 1);
+            if let Some(params) = request.params_mut::<GetBalanceParams>() {
+                params.commitment = Some(CommitmentLevel::Finalized);
+            }
+        })
+        .build();
+
+    let call = client
+        .get_balance(PUBKEY)
+        .with_min_context_slot(MIN_CONTEXT_SLOT)
+        .with_cycles(1_000)
+        .build_args();
+
+    let expected_params = GetBalanceParams {
+        pubkey: PUBKEY.into(),
+        commitment: Some(CommitmentLevel::Finalized),
+        min_context_slot: Some(MIN_CONTEXT_SLOT),
+    };
+    assert_eq!(
+        call,
+        PreparedCall {
+            canister_id: SOL_RPC_CANISTER,
+            method: "getBalance",
+            args: (
+                RpcSources::Default(SolanaCluster::Devnet),
+                None::<RpcConfig>,
+                expected_params.clone(),
+            ),
+            cycles: 1_001,
+        }
+    );
+    assert_eq!(
+        call.encode_args().unwrap(),
+        candid::encode_args((
+            RpcSources::Default(SolanaCluster::Devnet),
+            None::<RpcConfig>,
+            expected_params,
+        ))
+        .unwrap()
+    );
+}
+
+mod slot_tracker {
+    use super::*;
+    use crate::SlotTracker;
+    use sol_rpc_types::WithContext;
+
+    #[test]
+    fn should_set_min_context_slot_from_slot_tracker() {
+        let slot_tracker = SlotTracker::new();
+        let client = SolRpcClient::builder_for_ic()
+            .with_slot_tracker(slot_tracker.clone())
+            .build();
+
+        assert_eq!(
+            client.get_balance(PUBKEY).request.params.min_context_slot,
+            None
+        );
+        assert_eq!(client.get_slot().request.params, None);
+
+        slot_tracker.observe_slot(SLOT);
+
+        assert_eq!(
+            client
+                .get_account_info(PUBKEY)
+                .request
+                .params
+                .min_context_slot,
+            Some(SLOT)
+        );
+        assert_eq!(
+            client
+                .get_balance(PUBKEY)
+                .with_context()
+                .request
+                .params
+                .min_context_slot,
+            Some(SLOT)
+        );
+        assert_eq!(
+            client
+                .get_signatures_for_address(PUBKEY)
+                .request
+                .params
+                .min_context_slot,
+            Some(SLOT)
+        );
+        assert_eq!(
+            client.get_slot().request.params,
+            Some(GetSlotParams {
+                commitment: None,
+                min_context_slot: Some(SLOT),
+            })
+        );
+        assert_eq!(
+            client
+                .try_send_transaction(transaction())
+                .unwrap()
+                .request
+                .params
+                .min_context_slot,
+            Some(SLOT)
+        );
+    }
+
+    #[test]
+    fn should_not_lower_min_context_slot() {
+        let slot_tracker = SlotTracker::new();
+        slot_tracker.observe_slot(SLOT);
+        let client = SolRpcClient::builder_for_ic()
+            .with_slot_tracker(slot_tracker)
+            .build();
+
+        let builder = client.get_balance(GetBalanceParams {
+            min_context_slot: Some(SLOT + 1),
+            ..GetBalanceParams::from(PUBKEY)
+        });
+
+        assert_eq!(builder.request.params.min_context_slot, Some(SLOT + 1));
+
+        let builder = client.get_balance(GetBalanceParams {
+            min_context_slot: Some(SLOT - 1),
+            ..GetBalanceParams::from(PUBKEY)
+        });
+
+        assert_eq!(builder.request.params.min_context_slot, Some(SLOT));
+    }
+
+    #[tokio::test]
+    async fn should_record_context_slot_of_consistent_responses() {
+        let slot_tracker = SlotTracker::new();
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_responses()
+            .add_stub_response(MultiRpcResult::Consistent(Ok(WithContext {
+                context_slot: SLOT,
+                value: 1_u64,
+            })))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(WithContext {
+                context_slot: SLOT - 10,
+                value: 1_u64,
+            })))
+            .add_stub_response(MultiRpcResult::Inconsistent(vec![(
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                Ok(WithContext {
+                    context_slot: SLOT + 10,
+                    value: 1_u64,
+                }),
+            )]))
+            .with_slot_tracker(slot_tracker.clone())
+            .build();
+
+        client.get_balance(PUBKEY).with_context().send().await;
+        assert_eq!(slot_tracker.highest_slot(), Some(SLOT));
+
+        client.get_balance(PUBKEY).with_context().send().await;
+        assert_eq!(slot_tracker.highest_slot(), Some(SLOT));
+
+        client.get_balance(PUBKEY).with_context().send().await;
+        assert_eq!(slot_tracker.highest_slot(), Some(SLOT));
+    }
+
+    #[tokio::test]
+    async fn should_not_record_slot_of_responses_without_context() {
+        let slot_tracker = SlotTracker::new();
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_response(MultiRpcResult::Consistent(Ok(SLOT)))
+            .with_slot_tracker(slot_tracker.clone())
+            .build();
+
+        client.get_slot().send().await;
+
+        assert_eq!(slot_tracker.highest_slot(), None);
     }
 }
 
@@ -496,6 +1316,623 @@ mod get_recent_block {
     }
 }
 
+mod simulate_then_send_transaction {
+    use super::*;
+    use crate::{SimulateThenSendTransactionError, TransactionSimulationFailure};
+    use sol_rpc_types::{
+        ConsensusStrategy, InstructionError, JsonRpcError, RpcSources, SolanaCluster,
+        TransactionError,
+    };
+
+    #[test]
+    fn should_simulate_with_single_provider() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_rpc_sources(RpcSources::Custom(vec![
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet),
+            ]))
+            .build();
+
+        let request = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_preflight_commitment(CommitmentLevel::Confirmed)
+            .with_min_context_slot(MIN_CONTEXT_SLOT)
+            .with_preflight_simulation()
+            .simulate_transaction_request()
+            .unwrap()
+            .request;
+
+        assert_eq!(
+            request.rpc_sources,
+            RpcSources::Custom(vec![RpcSource::Supported(
+                SupportedRpcProviderId::AlchemyMainnet
+            )])
+        );
+        assert_eq!(
+            request
+                .rpc_config
+                .and_then(|config| config.response_consensus),
+            Some(ConsensusStrategy::Threshold {
+                total: Some(1),
+                min: 1
+            })
+        );
+        let params: serde_json::Value = serde_json::from_str(&request.params).unwrap();
+        let transaction = SendTransactionParams::try_from(transaction()).unwrap();
+        assert_eq!(
+            params,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "simulateTransaction",
+                "params": [
+                    transaction.get_transaction(),
+                    {
+                        "sigVerify": true,
+                        "encoding": "base64",
+                        "commitment": "confirmed",
+                        "minContextSlot": MIN_CONTEXT_SLOT
+                    }
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_send_transaction_when_simulation_succeeds() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+            .with_stub_responses()
+            .add_stub_response(MultiRpcResult::Consistent(Ok(
+                simulate_transaction_response(
+                    json!({ "err": null, "logs": ["Program log: ok"], "unitsConsumed": 150 }),
+                ),
+            )))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(signature().to_string())))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_preflight_simulation()
+            .try_send()
+            .await;
+
+        assert_eq!(result, Ok(MultiRpcResult::Consistent(Ok(signature()))));
+    }
+
+    #[tokio::test]
+    async fn should_not_send_transaction_when_simulation_fails() {
+        // Only the `simulateTransaction` call is stubbed since the transaction must not be sent.
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_response(MultiRpcResult::Consistent(Ok(
+                simulate_transaction_response(json!({
+                    "err": { "InstructionError": [0, { "Custom": 1 }] },
+                    "logs": [
+                        "Program 11111111111111111111111111111111 invoke [1]",
+                        "Transfer: insufficient lamports 0, need 1000",
+                        "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+                    ],
+                    "unitsConsumed": 150
+                })),
+            )))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_preflight_simulation()
+            .try_send()
+            .await;
+
+        assert_eq!(
+            result,
+            Err(SimulateThenSendTransactionError::SimulationFailed(
+                TransactionSimulationFailure {
+                    err: TransactionError::InstructionError(0, InstructionError::Custom(1)),
+                    logs: vec![
+                        "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+                        "Transfer: insufficient lamports 0, need 1000".to_string(),
+                        "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+                            .to_string(),
+                    ],
+                    units_consumed: Some(150),
+                }
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_return_json_rpc_error() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_response(MultiRpcResult::Consistent(Ok(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32602, "message": "invalid transaction" },
+                "id": 1
+            })
+            .to_string())))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_preflight_simulation()
+            .try_send()
+            .await;
+
+        assert_eq!(
+            result,
+            Err(
+                SimulateThenSendTransactionError::SimulateTransactionRpcError(
+                    RpcError::JsonRpcError(JsonRpcError {
+                        code: -32602,
+                        message: "invalid transaction".to_string(),
+                        data: None,
+                    })
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn should_return_simulation_rpc_error() {
+        let error = RpcError::ValidationError("simulateTransaction error".to_string());
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_response(MultiRpcResult::Consistent(Err::<String, _>(error.clone())))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_preflight_simulation()
+            .try_send()
+            .await;
+
+        assert_eq!(
+            result,
+            Err(SimulateThenSendTransactionError::SimulateTransactionRpcError(error))
+        );
+    }
+
+    fn simulate_transaction_response(value: serde_json::Value) -> String {
+        json!({
+            "jsonrpc": "2.0",
+            "result": { "context": { "slot": SLOT }, "value": value },
+            "id": 1
+        })
+        .to_string()
+    }
+}
+
+mod preflight_failure {
+    use super::*;
+    use crate::TransactionSimulationFailure;
+    use sol_rpc_types::{InstructionError, JsonRpcError, TransactionError};
+
+    #[test]
+    fn should_decode_insufficient_funds_for_fee() {
+        let error = preflight_error(json!({
+            "accounts": null,
+            "err": "InsufficientFundsForFee",
+            "logs": [],
+            "unitsConsumed": 0
+        }));
+
+        assert_eq!(
+            TransactionSimulationFailure::from_preflight_error(&error),
+            Some(TransactionSimulationFailure {
+                err: TransactionError::InsufficientFundsForFee,
+                logs: vec![],
+                units_consumed: Some(0),
+            })
+        );
+    }
+
+    #[test]
+    fn should_decode_program_error() {
+        let error = preflight_error(json!({
+            "err": { "InstructionError": [0, { "Custom": 1 }] },
+            "logs": [
+                "Program 11111111111111111111111111111111 invoke [1]",
+                "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+            ],
+            "unitsConsumed": 150
+        }));
+
+        assert_eq!(
+            TransactionSimulationFailure::from_preflight_error(&error),
+            Some(TransactionSimulationFailure {
+                err: TransactionError::InstructionError(0, InstructionError::Custom(1)),
+                logs: vec![
+                    "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+                    "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+                        .to_string(),
+                ],
+                units_consumed: Some(150),
+            })
+        );
+    }
+
+    #[test]
+    fn should_not_decode_other_errors() {
+        for error in [
+            RpcError::JsonRpcError(JsonRpcError {
+                code: -32002,
+                message: "Transaction simulation failed: Blockhash not found".to_string(),
+                data: None,
+            }),
+            RpcError::JsonRpcError(JsonRpcError {
+                code: -32005,
+                message: "Node is unhealthy".to_string(),
+                data: Some(json!({ "numSlotsBehind": null }).to_string()),
+            }),
+            RpcError::JsonRpcError(JsonRpcError {
+                code: -32002,
+                message: "Transaction simulation failed".to_string(),
+                data: Some("not JSON".to_string()),
+            }),
+            RpcError::ValidationError("Invalid transaction".to_string()),
+        ] {
+            assert_eq!(
+                TransactionSimulationFailure::from_preflight_error(&error),
+                None,
+                "{error:?}"
+            );
+        }
+    }
+
+    fn preflight_error(data: serde_json::Value) -> RpcError {
+        RpcError::JsonRpcError(JsonRpcError {
+            code: -32002,
+            message: "Transaction simulation failed".to_string(),
+            data: Some(data.to_string()),
+        })
+    }
+}
+
+mod send_transaction_with_blockhash_refresh {
+    use super::*;
+    use crate::SendTransactionWithBlockhashRefreshError;
+    use sol_rpc_types::JsonRpcError;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn should_resend_transaction_with_fresh_blockhash() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_responses()
+            .add_stub_response(blockhash_not_found())
+            .add_stub_response(MultiRpcResult::Consistent(Ok(SLOT)))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(Some(block()))))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(signature().to_string())))
+            .build();
+        let signer = RecordingSigner::default();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_blockhash_refresh(|blockhash| signer.sign(blockhash))
+            .try_send()
+            .await;
+
+        assert_eq!(result, Ok(MultiRpcResult::Consistent(Ok(signature()))));
+        assert_eq!(
+            signer.blockhashes(),
+            vec![solana_hash::Hash::from_str(BLOCKHASH).unwrap()]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_stop_after_max_refreshes() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_responses()
+            .add_stub_response(blockhash_not_found())
+            .add_stub_response(MultiRpcResult::Consistent(Ok(SLOT)))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(Some(block()))))
+            .add_stub_response(blockhash_not_found())
+            .build();
+        let signer = RecordingSigner::default();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_blockhash_refresh(|blockhash| signer.sign(blockhash))
+            .with_max_refreshes(1)
+            .try_send()
+            .await;
+
+        assert_eq!(
+            result,
+            Ok(MultiRpcResult::Consistent(Err(blockhash_not_found_error())))
+        );
+        assert_eq!(signer.blockhashes().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_not_refresh_blockhash_on_other_errors() {
+        let error = RpcError::JsonRpcError(JsonRpcError {
+            code: -32002,
+            message: "Transaction simulation failed: Attempt to debit an account but found no record of a prior credit.".to_string(),
+            data: None,
+        });
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_response(MultiRpcResult::Consistent(Err::<String, _>(error.clone())))
+            .build();
+        let signer = RecordingSigner::default();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_blockhash_refresh(|blockhash| signer.sign(blockhash))
+            .try_send()
+            .await;
+
+        assert_eq!(result, Ok(MultiRpcResult::Consistent(Err(error))));
+        assert_eq!(signer.blockhashes(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn should_return_signing_error() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_stub_responses()
+            .add_stub_response(blockhash_not_found())
+            .add_stub_response(MultiRpcResult::Consistent(Ok(SLOT)))
+            .add_stub_response(MultiRpcResult::Consistent(Ok(Some(block()))))
+            .build();
+
+        let result = client
+            .try_send_transaction(transaction())
+            .unwrap()
+            .with_blockhash_refresh(|_blockhash| async { Err("signing failed".to_string()) })
+            .try_send()
+            .await;
+
+        assert_eq!(
+            result,
+            Err(SendTransactionWithBlockhashRefreshError::SigningError(
+                "signing failed".to_string()
+            ))
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingSigner {
+        blockhashes: Mutex<Vec<solana_hash::Hash>>,
+    }
+
+    impl RecordingSigner {
+        async fn sign(
+            &self,
+            blockhash: solana_hash::Hash,
+        ) -> Result<SendTransactionParams, String> {
+            self.blockhashes.lock().unwrap().push(blockhash);
+            SendTransactionParams::try_from(transaction()).map_err(|e| e.to_string())
+        }
+
+        fn blockhashes(&self) -> Vec<solana_hash::Hash> {
+            self.blockhashes.lock().unwrap().clone()
+        }
+    }
+
+    fn blockhash_not_found() -> MultiRpcResult<String> {
+        MultiRpcResult::Consistent(Err(blockhash_not_found_error()))
+    }
+
+    fn blockhash_not_found_error() -> RpcError {
+        RpcError::JsonRpcError(JsonRpcError {
+            code: -32002,
+            message: "Transaction simulation failed: Blockhash not found".to_string(),
+            data: None,
+        })
+    }
+}
+
+mod send_per_provider {
+    use super::*;
+    use crate::{
+        fixtures::{MockCall, MockRuntime},
+        SendPerProviderError,
+    };
+    use futures::StreamExt;
+    use sol_rpc_types::ConsensusStrategy;
+    use std::time::Duration;
+
+    type GetSlotArgs = (RpcSources, Option<GetSlotRpcConfig>, Option<GetSlotParams>);
+
+    const ALCHEMY: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet);
+    const HELIUS: RpcSource = RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet);
+    const CYCLES: u128 = 1_000_000;
+
+    #[tokio::test]
+    async fn should_return_results_in_order_of_arrival() {
+        let runtime = MockRuntime::new()
+            .with_call(get_slot_cycles_cost().respond_with(Ok::<u128, RpcError>(CYCLES)))
+            .with_call(
+                get_slot(ALCHEMY)
+                    .respond_with(MultiRpcResult::Consistent(Ok(SLOT)))
+                    .with_delay(Duration::from_millis(50)),
+            )
+            .with_call(get_slot(HELIUS).respond_with(MultiRpcResult::Consistent(Ok(SLOT + 1))));
+
+        let results: Vec<_> = client(&runtime)
+            .get_slot()
+            .send_per_provider()
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(
+            results,
+            vec![(HELIUS, Ok(Ok(SLOT + 1))), (ALCHEMY, Ok(Ok(SLOT)))]
+        );
+        assert!(runtime.is_done());
+    }
+
+    #[tokio::test]
+    async fn should_attach_cycles_cost_of_single_provider_to_each_call() {
+        let runtime = MockRuntime::new()
+            .with_call(get_slot_cycles_cost_for(ALCHEMY).respond_with(Ok::<u128, RpcError>(CYCLES)))
+            .with_call(
+                get_slot_cycles_cost_for(HELIUS).respond_with(Ok::<u128, RpcError>(2 * CYCLES)),
+            )
+            .with_call(get_slot(ALCHEMY).respond_with(MultiRpcResult::Consistent(Ok(SLOT))))
+            .with_call(get_slot(HELIUS).respond_with(MultiRpcResult::Consistent(Ok(SLOT))));
+
+        let _results: Vec<_> = client(&runtime)
+            .get_slot()
+            .send_per_provider()
+            .unwrap()
+            .collect()
+            .await;
+
+        let calls: Vec<_> = runtime
+            .calls()
+            .into_iter()
+            .filter(|call| call.method == "getSlot")
+            .map(|call| {
+                let (sources, config, _params): GetSlotArgs = call.decode_args().unwrap();
+                assert_eq!(
+                    config.unwrap().response_consensus,
+                    Some(ConsensusStrategy::Equality)
+                );
+                (sources, call.cycles)
+            })
+            .collect();
+        assert_eq!(calls.len(), 2);
+        assert!(calls.contains(&(RpcSources::Custom(vec![ALCHEMY]), CYCLES)));
+        assert!(calls.contains(&(RpcSources::Custom(vec![HELIUS]), 2 * CYCLES)));
+        assert!(runtime.is_done());
+    }
+
+    #[tokio::test]
+    async fn should_attach_given_cycles_to_each_call() {
+        let runtime = MockRuntime::new()
+            .with_call(get_slot(ALCHEMY).respond_with(MultiRpcResult::Consistent(Ok(SLOT))))
+            .with_call(get_slot(HELIUS).respond_with(MultiRpcResult::Consistent(Ok(SLOT))));
+
+        let _results: Vec<_> = client(&runtime)
+            .get_slot()
+            .with_cycles(CYCLES)
+            .send_per_provider()
+            .unwrap()
+            .collect()
+            .await;
+
+        let calls = runtime.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls
+            .iter()
+            .all(|call| call.method == "getSlot" && call.cycles == CYCLES));
+    }
+
+    #[tokio::test]
+    async fn should_return_error_when_querying_cycles_cost_fails() {
+        let error = RpcError::ValidationError("Invalid params".to_string());
+        let runtime = MockRuntime::new()
+            .with_call(
+                get_slot_cycles_cost_for(ALCHEMY)
+                    .respond_with(Err::<u128, RpcError>(error.clone())),
+            )
+            .with_call(
+                get_slot_cycles_cost_for(HELIUS).fail_with(crate::IcError::CallPerformFailed),
+            );
+
+        let results: Vec<_> = client(&runtime)
+            .get_slot()
+            .send_per_provider()
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results.contains(&(ALCHEMY, Ok(Err(error)))));
+        assert!(results.contains(&(HELIUS, Err(crate::IcError::CallPerformFailed))));
+        assert!(runtime.calls().iter().all(|call| call.method != "getSlot"));
+    }
+
+    #[test]
+    fn should_fail_with_consensus_strategy_other_than_equality() {
+        let strategy = ConsensusStrategy::Threshold {
+            total: Some(2),
+            min: 2,
+        };
+
+        assert_eq!(
+            client(&MockRuntime::new())
+                .get_slot()
+                .with_response_consensus(strategy.clone())
+                .send_per_provider()
+                .err(),
+            Some(SendPerProviderError::UnsupportedConsensusStrategy(strategy))
+        );
+        assert!(client(&MockRuntime::new())
+            .get_slot()
+            .with_response_consensus(ConsensusStrategy::Equality)
+            .send_per_provider()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_return_errors_of_each_provider() {
+        let error = RpcError::ValidationError("Invalid slot".to_string());
+        let runtime = MockRuntime::new()
+            .with_call(
+                get_slot(ALCHEMY)
+                    .respond_with(MultiRpcResult::<Slot>::Consistent(Err(error.clone()))),
+            )
+            .with_call(get_slot(HELIUS).fail_with(crate::IcError::CallPerformFailed));
+
+        let results: Vec<_> = client(&runtime)
+            .get_slot()
+            .with_cycles(CYCLES)
+            .send_per_provider()
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results.contains(&(ALCHEMY, Ok(Err(error)))));
+        assert!(results.contains(&(HELIUS, Err(crate::IcError::CallPerformFailed))));
+    }
+
+    #[test]
+    fn should_fail_with_default_rpc_sources() {
+        let client = SolRpcClient::builder_for_ic()
+            .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+            .build();
+
+        assert_eq!(
+            client.get_slot().send_per_provider().err(),
+            Some(SendPerProviderError::DefaultRpcSources(
+                SolanaCluster::Mainnet
+            ))
+        );
+    }
+
+    fn get_slot_cycles_cost() -> MockCall {
+        MockCall::new("getSlotCyclesCost").repeatedly()
+    }
+
+    fn get_slot_cycles_cost_for(source: RpcSource) -> MockCall {
+        MockCall::new("getSlotCyclesCost").with_args_matching(
+            move |(sources, _, _): GetSlotArgs| sources == RpcSources::Custom(vec![source.clone()]),
+        )
+    }
+
+    fn get_slot(source: RpcSource) -> MockCall {
+        MockCall::new("getSlot").with_args_matching(move |(sources, _, _): GetSlotArgs| {
+            sources == RpcSources::Custom(vec![source.clone()])
+        })
+    }
+
+    fn client(runtime: &MockRuntime) -> SolRpcClient<MockRuntime> {
+        SolRpcClient::builder_for_ic()
+            .with_mock_runtime(runtime.clone())
+            .with_rpc_sources(RpcSources::Custom(vec![ALCHEMY, HELIUS]))
+            .build()
+    }
+}
+
 fn assert_params_eq<Runtime, Config, Params, CandidOutput, Output>(
     left: RequestBuilder<Runtime, Config, Params, CandidOutput, Output>,
     right: RequestBuilder<Runtime, Config, Params, CandidOutput, Output>,
@@ -519,6 +1956,10 @@ fn another_signature() -> Signature {
     .unwrap()
 }
 
+fn message_params() -> GetFeeForMessageParams {
+    GetFeeForMessageParams::from_encoded_message("AQABAgIAAAAA".to_string())
+}
+
 fn transaction() -> solana_transaction::Transaction {
     let keypair = solana_keypair::Keypair::from_base58_string(
         "3jipnj2WowKxqMaSoTj8v79kcSb5bbvJHomd5FwycLg1juPnWdhJBzszABAAxVEfRmsxdo2bnbi7hpag3CrLNU1c",
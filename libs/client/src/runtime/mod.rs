@@ -0,0 +1,142 @@
+//! Runtime to make inter-canister calls to the SOL RPC canister with the call API introduced in
+//! `ic-cdk` v0.18.
+
+#[cfg(test)]
+mod tests;
+
+use async_trait::async_trait;
+use candid::{utils::ArgumentEncoder, CandidType, Principal};
+use ic_canister_runtime::{IcError, Runtime};
+use ic_cdk::call::Call;
+use serde::de::DeserializeOwned;
+
+/// Semantics of the inter-canister calls made by an [`IcRuntimeV2`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CallSemantics {
+    /// Bounded-wait (a.k.a. best-effort response) calls.
+    ///
+    /// The call returns an error with reject code
+    /// [`SysUnknown`](ic_cdk::call::RejectCode::SysUnknown) if no response was received within
+    /// the given timeout, in which case the caller does not know whether the request was
+    /// processed by the SOL RPC canister, and the attached cycles may have been spent.
+    BoundedWait {
+        /// Number of seconds after which the call times out.
+        timeout_seconds: u32,
+    },
+    /// Unbounded-wait (a.k.a. guaranteed response) calls.
+    ///
+    /// The caller always learns the outcome of the call, but may have to wait arbitrarily
+    /// long for it and cannot be stopped in the meantime.
+    UnboundedWait,
+}
+
+impl Default for CallSemantics {
+    fn default() -> Self {
+        Self::BoundedWait {
+            timeout_seconds: IcRuntimeV2::DEFAULT_TIMEOUT_SECONDS,
+        }
+    }
+}
+
+/// [`Runtime`] to make inter-canister calls using the call API [`ic_cdk::call::Call`] of recent
+/// versions of `ic-cdk`.
+///
+/// Contrary to [`IcRuntime`](ic_canister_runtime::IcRuntime), which always makes unbounded-wait
+/// calls, the [`CallSemantics`] can be chosen and bounded-wait calls are made by default, so that
+/// the calling canister does not await a response from the SOL RPC canister indefinitely.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::{CallSemantics, IcRuntimeV2, SolRpcClient, SOL_RPC_CANISTER};
+///
+/// let runtime = IcRuntimeV2::new().with_timeout_seconds(60);
+/// assert_eq!(
+///     runtime.call_semantics(),
+///     CallSemantics::BoundedWait { timeout_seconds: 60 }
+/// );
+///
+/// let client = SolRpcClient::builder(runtime, SOL_RPC_CANISTER).build();
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct IcRuntimeV2 {
+    call_semantics: CallSemantics,
+}
+
+impl IcRuntimeV2 {
+    /// Default timeout in seconds of bounded-wait calls.
+    ///
+    /// This corresponds to the default timeout of bounded-wait calls made with [`Call`].
+    pub const DEFAULT_TIMEOUT_SECONDS: u32 = 300;
+
+    /// Creates a new [`IcRuntimeV2`] making bounded-wait calls with a timeout of
+    /// [`Self::DEFAULT_TIMEOUT_SECONDS`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mutates the runtime to make bounded-wait calls with the given timeout in seconds.
+    pub fn with_timeout_seconds(mut self, timeout_seconds: u32) -> Self {
+        self.call_semantics = CallSemantics::BoundedWait { timeout_seconds };
+        self
+    }
+
+    /// Mutates the runtime to make unbounded-wait calls.
+    pub fn with_unbounded_wait(mut self) -> Self {
+        self.call_semantics = CallSemantics::UnboundedWait;
+        self
+    }
+
+    /// Returns the semantics of the calls made by this runtime.
+    pub fn call_semantics(&self) -> CallSemantics {
+        self.call_semantics
+    }
+
+    fn call<'m>(&self, id: Principal, method: &'m str) -> Call<'m, 'static> {
+        match self.call_semantics {
+            CallSemantics::BoundedWait { timeout_seconds } => {
+                Call::bounded_wait(id, method).change_timeout(timeout_seconds)
+            }
+            CallSemantics::UnboundedWait => Call::unbounded_wait(id, method),
+        }
+    }
+}
+
+#[async_trait]
+impl Runtime for IcRuntimeV2 {
+    async fn update_call<In, Out>(
+        &self,
+        id: Principal,
+        method: &str,
+        args: In,
+        cycles: u128,
+    ) -> Result<Out, IcError>
+    where
+        In: ArgumentEncoder + Send,
+        Out: CandidType + DeserializeOwned,
+    {
+        self.call(id, method)
+            .with_args(&args)
+            .with_cycles(cycles)
+            .await
+            .map_err(IcError::from)
+            .and_then(|response| response.candid::<Out>().map_err(IcError::from))
+    }
+
+    async fn query_call<In, Out>(
+        &self,
+        id: Principal,
+        method: &str,
+        args: In,
+    ) -> Result<Out, IcError>
+    where
+        In: ArgumentEncoder + Send,
+        Out: CandidType + DeserializeOwned,
+    {
+        self.call(id, method)
+            .with_args(&args)
+            .await
+            .map_err(IcError::from)
+            .and_then(|response| response.candid::<Out>().map_err(IcError::from))
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{CallSemantics, IcRuntimeV2};
+
+#[test]
+fn should_make_bounded_wait_calls_by_default() {
+    assert_eq!(
+        IcRuntimeV2::new().call_semantics(),
+        CallSemantics::BoundedWait {
+            timeout_seconds: IcRuntimeV2::DEFAULT_TIMEOUT_SECONDS
+        }
+    );
+}
+
+#[test]
+fn should_configure_call_semantics() {
+    let runtime = IcRuntimeV2::new().with_unbounded_wait();
+    assert_eq!(runtime.call_semantics(), CallSemantics::UnboundedWait);
+
+    let runtime = runtime.with_timeout_seconds(10);
+    assert_eq!(
+        runtime.call_semantics(),
+        CallSemantics::BoundedWait {
+            timeout_seconds: 10
+        }
+    );
+}
@@ -0,0 +1,99 @@
+//! Module to keep track of the highest slot observed by a client, to prevent reads from going
+//! backwards in time across providers.
+
+#[cfg(test)]
+mod tests;
+
+use sol_rpc_types::{MultiRpcResult, Slot, WithContext};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Records the highest context slot observed in responses and uses it as `minContextSlot` for
+/// subsequent requests.
+///
+/// Different providers may not be at the same slot, so that two successive reads could return a
+/// value evaluated at an older slot than the first one. When a [`SlotTracker`] is set on a
+/// client with [`ClientBuilder::with_slot_tracker`](crate::ClientBuilder::with_slot_tracker):
+/// * the context slot of each consistent successful response of an endpoint returning a
+///   context (e.g. [`GetBalanceRequestBuilder::with_context`](crate::GetBalanceRequestBuilder::with_context))
+///   is recorded, and
+/// * the `minContextSlot` parameter of each subsequent request supporting it is set to at least
+///   the highest recorded slot, so that providers that did not yet reach that slot return an
+///   error instead of an outdated value.
+///
+/// Note that context slots are rounded down by the SOL RPC canister (see
+/// [`WithContextRpcConfig`](sol_rpc_types::WithContextRpcConfig)), so that the recorded slot
+/// may be slightly lower than the slot at which the value was actually evaluated.
+///
+/// A [`SlotTracker`] can be cloned cheaply and all clones share the same highest slot.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::SlotTracker;
+/// use sol_rpc_types::{MultiRpcResult, WithContext};
+///
+/// let tracker = SlotTracker::new();
+/// assert_eq!(tracker.highest_slot(), None);
+///
+/// tracker.observe(&MultiRpcResult::Consistent(Ok(WithContext {
+///     context_slot: 364_000_000,
+///     value: 1_000_000_000_u64,
+/// })));
+/// tracker.observe_slot(363_999_980);
+///
+/// assert_eq!(tracker.highest_slot(), Some(364_000_000));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SlotTracker(Arc<AtomicU64>);
+
+impl SlotTracker {
+    /// Creates a new [`SlotTracker`] without any observed slot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the highest observed slot, if any.
+    pub fn highest_slot(&self) -> Option<Slot> {
+        // Slot 0 is the genesis slot and never needs to be used as minimum context slot.
+        Some(self.0.load(Ordering::Relaxed)).filter(|slot| *slot > 0)
+    }
+
+    /// Records the given slot if it is higher than the highest observed slot.
+    pub fn observe_slot(&self, slot: Slot) {
+        self.0.fetch_max(slot, Ordering::Relaxed);
+    }
+
+    /// Records the context slot of the given result if it is consistent and successful.
+    pub fn observe<T>(&self, result: &MultiRpcResult<WithContext<T>>) {
+        if let Some(slot) = consistent_context_slot(result) {
+            self.observe_slot(slot);
+        }
+    }
+
+    /// Raises the given `minContextSlot` parameter to at least the highest observed slot.
+    pub fn apply(&self, min_context_slot: &mut Option<Slot>) {
+        if let Some(highest_slot) = self.highest_slot() {
+            *min_context_slot =
+                Some(min_context_slot.map_or(highest_slot, |slot| slot.max(highest_slot)));
+        }
+    }
+}
+
+/// Two [`SlotTracker`]s are equal if they share the same highest slot.
+impl PartialEq for SlotTracker {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SlotTracker {}
+
+pub(crate) fn consistent_context_slot<T>(result: &MultiRpcResult<WithContext<T>>) -> Option<Slot> {
+    match result {
+        MultiRpcResult::Consistent(Ok(value)) => Some(value.context_slot),
+        MultiRpcResult::Consistent(Err(_)) | MultiRpcResult::Inconsistent(_) => None,
+    }
+}
@@ -0,0 +1,71 @@
+use crate::SlotTracker;
+use sol_rpc_types::{MultiRpcResult, RpcError, RpcSource, SupportedRpcProviderId, WithContext};
+
+#[test]
+fn should_only_record_highest_slot() {
+    let slot_tracker = SlotTracker::new();
+    assert_eq!(slot_tracker.highest_slot(), None);
+
+    slot_tracker.observe_slot(0);
+    assert_eq!(slot_tracker.highest_slot(), None);
+
+    slot_tracker.observe_slot(100);
+    assert_eq!(slot_tracker.highest_slot(), Some(100));
+
+    slot_tracker.observe_slot(99);
+    assert_eq!(slot_tracker.highest_slot(), Some(100));
+
+    slot_tracker.observe_slot(101);
+    assert_eq!(slot_tracker.highest_slot(), Some(101));
+}
+
+#[test]
+fn should_share_highest_slot_between_clones() {
+    let slot_tracker = SlotTracker::new();
+    let clone = slot_tracker.clone();
+
+    clone.observe_slot(100);
+
+    assert_eq!(slot_tracker.highest_slot(), Some(100));
+    assert_eq!(slot_tracker, clone);
+    assert_ne!(slot_tracker, SlotTracker::new());
+}
+
+#[test]
+fn should_only_observe_consistent_successful_results() {
+    let slot_tracker = SlotTracker::new();
+
+    slot_tracker.observe::<u64>(&MultiRpcResult::Consistent(Err(RpcError::ValidationError(
+        "error".to_string(),
+    ))));
+    slot_tracker.observe(&MultiRpcResult::Inconsistent(vec![(
+        RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+        Ok(WithContext {
+            context_slot: 100,
+            value: 1_u64,
+        }),
+    )]));
+    assert_eq!(slot_tracker.highest_slot(), None);
+
+    slot_tracker.observe(&MultiRpcResult::Consistent(Ok(WithContext {
+        context_slot: 100,
+        value: 1_u64,
+    })));
+    assert_eq!(slot_tracker.highest_slot(), Some(100));
+}
+
+#[test]
+fn should_raise_min_context_slot() {
+    let slot_tracker = SlotTracker::new();
+
+    let mut min_context_slot = None;
+    slot_tracker.apply(&mut min_context_slot);
+    assert_eq!(min_context_slot, None);
+
+    slot_tracker.observe_slot(100);
+    for (min_context_slot, expected) in [(None, 100), (Some(50), 100), (Some(150), 150)] {
+        let mut min_context_slot = min_context_slot;
+        slot_tracker.apply(&mut min_context_slot);
+        assert_eq!(min_context_slot, Some(expected));
+    }
+}
@@ -0,0 +1,208 @@
+//! Module for interacting with Solana [stake accounts](https://solana.com/docs/references/staking/stake-accounts).
+
+use sol_rpc_types::{RpcError, RpcResult, RpcSource};
+use solana_account_decoder_client_types::UiAccount;
+use solana_pubkey::Pubkey;
+use solana_sdk_ids::stake;
+use solana_stake_interface::state::{Meta, Stake, StakeStateV2};
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// The content of an initialized stake account.
+///
+/// This is a decoded version of the stake account state, as defined by the
+/// [`solana_stake_interface`] crate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StakeAccount {
+    /// Total balance of the stake account in lamports, including the rent-exempt reserve.
+    pub lamports: u64,
+    /// Metadata of the stake account.
+    pub meta: StakeMeta,
+    /// Delegation of the stake account, if the stake account was delegated.
+    pub delegation: Option<StakeDelegation>,
+}
+
+impl StakeAccount {
+    /// Amount of lamports reserved to keep the stake account rent-exempt.
+    pub fn rent_exempt_reserve(&self) -> u64 {
+        self.meta.rent_exempt_reserve
+    }
+
+    /// Amount of lamports delegated to a validator, or 0 if the stake account is not delegated.
+    ///
+    /// Note that this is the amount set at delegation time and does not take into account
+    /// any warmup or cooldown period.
+    pub fn delegated_amount(&self) -> u64 {
+        self.delegation
+            .as_ref()
+            .map_or(0, |delegation| delegation.stake)
+    }
+}
+
+/// Metadata of a stake account, present as soon as the stake account is initialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakeMeta {
+    /// Amount of lamports reserved to keep the stake account rent-exempt.
+    pub rent_exempt_reserve: u64,
+    /// Keys allowed to delegate and withdraw from the stake account.
+    pub authorized: StakeAuthorized,
+    /// Restrictions on withdrawals from the stake account.
+    pub lockup: StakeLockup,
+}
+
+/// Keys allowed to manage a stake account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakeAuthorized {
+    /// Key allowed to delegate and deactivate the stake account.
+    pub staker: Pubkey,
+    /// Key allowed to withdraw lamports from the stake account.
+    pub withdrawer: Pubkey,
+}
+
+/// Restrictions on withdrawals from a stake account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakeLockup {
+    /// Unix timestamp until which withdrawals are not allowed, unless signed by the custodian.
+    pub unix_timestamp: i64,
+    /// Epoch until which withdrawals are not allowed, unless signed by the custodian.
+    pub epoch: u64,
+    /// Key allowed to withdraw lamports before the end of the lockup.
+    pub custodian: Pubkey,
+}
+
+/// Delegation of a stake account to a validator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakeDelegation {
+    /// Vote account of the validator the stake is delegated to.
+    pub voter: Pubkey,
+    /// Amount of lamports delegated.
+    pub stake: u64,
+    /// Epoch at which the stake was activated, or `u64::MAX` for a bootstrap stake.
+    pub activation_epoch: u64,
+    /// Epoch at which the stake was deactivated, or `u64::MAX` if it was not deactivated.
+    pub deactivation_epoch: u64,
+    /// Vote credits observed when the stake was last delegated or redeemed.
+    pub credits_observed: u64,
+}
+
+/// Decodes a stake account from the response of a `getAccountInfo` RPC call.
+///
+/// Since the [`getStakeActivation`](https://solana.com/docs/rpc/deprecated/getstakeactivation)
+/// RPC method is deprecated, this allows inspecting a stake account (e.g. its delegation and
+/// activation epoch) from its raw data.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_client::stake::stake_account_from_account;
+/// use solana_account_decoder_client_types::UiAccount;
+///
+/// # use sol_rpc_client::fixtures::stake_account;
+/// let account = UiAccount::from(stake_account());
+///
+/// let stake_account = stake_account_from_account(&account).unwrap();
+///
+/// assert_eq!(stake_account.rent_exempt_reserve(), 2_282_880);
+/// assert_eq!(stake_account.delegated_amount(), 1_000_000_000);
+/// ```
+///
+/// # Errors
+///
+/// The method will return an instance of [`ExtractStakeAccountError`] if the account data does
+/// not correspond to a valid and properly encoded stake account. See [`ExtractStakeAccountError`]
+/// for more details.
+pub fn stake_account_from_account(
+    account: &UiAccount,
+) -> Result<StakeAccount, ExtractStakeAccountError> {
+    if account.owner != stake::ID.to_string() {
+        return Err(ExtractStakeAccountError::InvalidAccountOwner(
+            account.owner.clone(),
+        ));
+    }
+    let data = account
+        .data
+        .decode()
+        .ok_or(ExtractStakeAccountError::UnsupportedEncodingFormat)?;
+    if data.is_empty() {
+        return Err(ExtractStakeAccountError::UnexpectedDataSize(data.len()));
+    }
+    let state = bincode::deserialize::<StakeStateV2>(data.as_slice())
+        .map_err(|e| ExtractStakeAccountError::InvalidAccountData(e.to_string()))?;
+    match state {
+        StakeStateV2::Uninitialized | StakeStateV2::RewardsPool => {
+            Err(ExtractStakeAccountError::Uninitialized)
+        }
+        StakeStateV2::Initialized(meta) => Ok(to_stake_account(account.lamports, meta, None)),
+        StakeStateV2::Stake(meta, stake, _flags) => {
+            Ok(to_stake_account(account.lamports, meta, Some(stake)))
+        }
+    }
+}
+
+fn to_stake_account(lamports: u64, meta: Meta, stake: Option<Stake>) -> StakeAccount {
+    // `solana_stake_interface` depends on a different version of the `solana_pubkey` crate
+    let to_pubkey = |bytes: [u8; 32]| Pubkey::new_from_array(bytes);
+    StakeAccount {
+        lamports,
+        meta: StakeMeta {
+            rent_exempt_reserve: meta.rent_exempt_reserve,
+            authorized: StakeAuthorized {
+                staker: to_pubkey(meta.authorized.staker.to_bytes()),
+                withdrawer: to_pubkey(meta.authorized.withdrawer.to_bytes()),
+            },
+            lockup: StakeLockup {
+                unix_timestamp: meta.lockup.unix_timestamp,
+                epoch: meta.lockup.epoch,
+                custodian: to_pubkey(meta.lockup.custodian.to_bytes()),
+            },
+        },
+        delegation: stake.map(|stake| StakeDelegation {
+            voter: to_pubkey(stake.delegation.voter_pubkey.to_bytes()),
+            stake: stake.delegation.stake,
+            activation_epoch: stake.delegation.activation_epoch,
+            deactivation_epoch: stake.delegation.deactivation_epoch,
+            credits_observed: stake.credits_observed,
+        }),
+    }
+}
+
+/// Errors that might happen when calling the [`stake_account_from_account`] method.
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum ExtractStakeAccountError {
+    /// The account data does not represent a valid stake account.
+    #[error("Invalid account data: {0}")]
+    InvalidAccountData(String),
+    /// The account owner is not the stake program.
+    #[error("Invalid account owner: {0}")]
+    InvalidAccountOwner(String),
+    /// The account data is empty.
+    #[error("Unexpected account data size: {0} bytes")]
+    UnexpectedDataSize(usize),
+    /// The account data is encoded in a format that is not supported. Currently, this
+    /// only applies to account data encoded in `jsonParsed` format.
+    #[error("Unsupported encoding format")]
+    UnsupportedEncodingFormat,
+    /// The stake account exists but is not initialized.
+    #[error("Stake account is not initialized")]
+    Uninitialized,
+}
+
+/// An error that occurred while trying to fetch a stake account.
+/// See [`SolRpcClient::get_stake_account`](crate::SolRpcClient::get_stake_account).
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum GetStakeAccountError {
+    /// The results from the different providers were not consistent for the `getAccountInfo` call.
+    #[error("Inconsistent result while fetching account: {0:?}")]
+    ConsensusError(Vec<(RpcSource, RpcResult<Option<UiAccount>>)>),
+    /// An error occurred during the `getAccountInfo` call.
+    #[error("Error while fetching account: {0}")]
+    RpcError(RpcError),
+    /// The fetched account is not a valid stake account.
+    #[error("Invalid stake account: {0}")]
+    InvalidStakeAccount(ExtractStakeAccountError),
+    /// An IC error occurred while making the request.
+    #[error("IC error: {0}")]
+    IcError(crate::IcError),
+}
@@ -0,0 +1,125 @@
+use crate::{
+    fixtures::stake_account,
+    stake::{
+        stake_account_from_account, ExtractStakeAccountError, StakeAccount, StakeAuthorized,
+        StakeDelegation, StakeLockup, StakeMeta,
+    },
+};
+use assert_matches::assert_matches;
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
+use solana_pubkey::{pubkey, Pubkey};
+
+mod stake_account {
+    use super::*;
+
+    const STAKER: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+
+    #[test]
+    fn should_decode_delegated_stake_account() {
+        let account = UiAccount::from(stake_account());
+
+        let stake_account = stake_account_from_account(&account);
+
+        assert_eq!(
+            stake_account,
+            Ok(StakeAccount {
+                lamports: 1_002_282_880,
+                meta: expected_meta(),
+                delegation: Some(StakeDelegation {
+                    voter: pubkey!("6pPYj3CpgfR6wwBkenmUpSd7rNqmeq2hccUVJq4au5eS"),
+                    stake: 1_000_000_000,
+                    activation_epoch: 800,
+                    deactivation_epoch: u64::MAX,
+                    credits_observed: 123_456,
+                }),
+            })
+        );
+        let stake_account = stake_account.unwrap();
+        assert_eq!(stake_account.rent_exempt_reserve(), 2_282_880);
+        assert_eq!(stake_account.delegated_amount(), 1_000_000_000);
+    }
+
+    #[test]
+    fn should_decode_initialized_stake_account() {
+        let mut account = UiAccount::from(stake_account());
+        account.data = UiAccountData::Binary(
+            "AQAAAIDVIgAAAAAA1EspXEHdQ88EHYhxgyA1f9NG6MweJS0HQwNp6/5e6kvUSylcQd1DzwQdiHGDIDV/00bozB4lLQdDA2nr/l7qSwAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+            UiAccountEncoding::Base64,
+        );
+
+        let stake_account = stake_account_from_account(&account).unwrap();
+
+        assert_eq!(stake_account.meta, expected_meta());
+        assert_eq!(stake_account.delegation, None);
+        assert_eq!(stake_account.delegated_amount(), 0);
+    }
+
+    #[test]
+    fn should_fail_for_uninitialized_account() {
+        let mut account = UiAccount::from(stake_account());
+        account.data = UiAccountData::Binary(
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+            UiAccountEncoding::Base64,
+        );
+
+        let stake_account = stake_account_from_account(&account);
+
+        assert_eq!(stake_account, Err(ExtractStakeAccountError::Uninitialized))
+    }
+
+    #[test]
+    fn should_fail_for_invalid_account_data() {
+        let mut account = UiAccount::from(stake_account());
+        account.data = UiAccountData::Binary("AgAAAA==".to_string(), UiAccountEncoding::Base64);
+
+        let stake_account = stake_account_from_account(&account);
+
+        assert_matches!(
+            stake_account,
+            Err(ExtractStakeAccountError::InvalidAccountData(_))
+        )
+    }
+
+    #[test]
+    fn should_fail_for_empty_account() {
+        let mut account = UiAccount::from(stake_account());
+        account.data = UiAccountData::Binary("".to_string(), UiAccountEncoding::Base64);
+
+        let stake_account = stake_account_from_account(&account);
+
+        assert_eq!(
+            stake_account,
+            Err(ExtractStakeAccountError::UnexpectedDataSize(0))
+        )
+    }
+
+    #[test]
+    fn should_fail_for_invalid_account_owner() {
+        let mut account = UiAccount::from(stake_account());
+        account.owner = "11111111111111111111111111111111".to_string();
+
+        let stake_account = stake_account_from_account(&account);
+
+        assert_eq!(
+            stake_account,
+            Err(ExtractStakeAccountError::InvalidAccountOwner(
+                "11111111111111111111111111111111".to_string()
+            ))
+        )
+    }
+
+    fn expected_meta() -> StakeMeta {
+        StakeMeta {
+            rent_exempt_reserve: 2_282_880,
+            authorized: StakeAuthorized {
+                staker: STAKER,
+                withdrawer: STAKER,
+            },
+            lockup: StakeLockup {
+                unix_timestamp: 0,
+                epoch: 0,
+                custodian: Pubkey::default(),
+            },
+        }
+    }
+}
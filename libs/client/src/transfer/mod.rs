@@ -0,0 +1,339 @@
+//! Module to transfer SOL or SPL tokens from an account controlled by the calling canister
+//! with threshold Ed25519, see [`SolRpcClient::transfer_sol`] and [`SolRpcClient::transfer_spl`].
+//!
+//! This module is only available with the `ed25519` feature. A transfer consists of the
+//! following steps, each of which may fail with a [`TransferError`]:
+//! 1. Fetch the public key of the payer with `schnorr_public_key`.
+//! 2. For SPL tokens only, fetch the mint account with `getAccountInfo` to determine its token
+//!    program and its number of decimals.
+//! 3. Fetch a recent blockhash with [`SolRpcClient::get_recent_block`].
+//! 4. Sign the transaction with `sign_with_schnorr`.
+//! 5. Submit the transaction with `sendTransaction`.
+//! 6. Check the status of the transaction with `getSignatureStatuses` until it reaches the
+//!    requested commitment level.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    confirmation::TransactionConfirmation,
+    ed25519::{get_pubkey, sign_message, DerivationPath, Ed25519KeyId},
+    instruction_decoder::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    pda::associated_token_address,
+    GetRecentBlockError, SolRpcClient,
+};
+use ic_canister_runtime::{IcError, Runtime};
+use sol_rpc_types::{
+    CommitmentLevel, GetAccountInfoEncoding, GetAccountInfoParams, MultiRpcResult, RpcError,
+    RpcResult, RpcSource,
+};
+use solana_account_decoder_client_types::UiAccount;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+use std::num::NonZeroUsize;
+use thiserror::Error;
+
+/// Size in bytes of the base state of an SPL token mint account. Mints of the Token-2022
+/// program may be larger because of extensions.
+const MINT_LEN: usize = 82;
+/// Offset of the number of decimals in the data of an SPL token mint account.
+const MINT_DECIMALS_OFFSET: usize = 44;
+/// Offset of the `is_initialized` flag in the data of an SPL token mint account.
+const MINT_IS_INITIALIZED_OFFSET: usize = 45;
+/// Tag of the [`TransferChecked` instruction](https://github.com/solana-program/token/blob/main/interface/src/instruction.rs)
+/// of the SPL Token and Token-2022 programs.
+const TRANSFER_CHECKED_TAG: u8 = 12;
+
+/// An asset transfer performed with a [`TransferRequestBuilder`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Transfer {
+    /// Transfer lamports from the payer's account to the recipient's account.
+    Sol {
+        /// The account receiving the lamports.
+        recipient: Pubkey,
+        /// The number of lamports to transfer.
+        lamports: u64,
+    },
+    /// Transfer tokens from the payer's associated token account to the recipient's associated
+    /// token account for the given mint.
+    Spl {
+        /// The mint of the transferred tokens.
+        mint: Pubkey,
+        /// The wallet owning the associated token account receiving the tokens.
+        recipient: Pubkey,
+        /// The amount of tokens to transfer, in the smallest unit of the mint.
+        amount: u64,
+    },
+}
+
+/// An error that occurred while trying to transfer SOL or SPL tokens.
+/// See [`SolRpcClient::transfer_sol`] and [`SolRpcClient::transfer_spl`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum TransferError {
+    /// The results from the different providers were not consistent for the `getAccountInfo`
+    /// call fetching the mint account.
+    #[error("Inconsistent result while fetching mint account: {0:?}")]
+    GetMintConsensusError(Vec<(RpcSource, RpcResult<Option<UiAccount>>)>),
+    /// An error occurred during the `getAccountInfo` call fetching the mint account.
+    #[error("Error while fetching mint account: {0}")]
+    GetMintRpcError(RpcError),
+    /// The mint account does not exist, or is not an initialized mint of the SPL Token or
+    /// Token-2022 programs.
+    #[error("Invalid mint account: {0}")]
+    InvalidMint(Pubkey),
+    /// A recent blockhash could not be fetched.
+    #[error("Failed to fetch a recent block: {0:?}")]
+    GetRecentBlockError(Vec<GetRecentBlockError>),
+    /// The blockhash of the fetched block could not be parsed.
+    #[error("Invalid blockhash: {0}")]
+    InvalidBlockhash(String),
+    /// The results from the different providers were not consistent for the `sendTransaction`
+    /// call.
+    #[error("Inconsistent result while sending transaction: {0:?}")]
+    SendTransactionConsensusError(Vec<(RpcSource, RpcResult<Signature>)>),
+    /// An error occurred during the `sendTransaction` call, e.g. because the transaction failed
+    /// the preflight checks.
+    #[error("Error while sending transaction: {0}")]
+    SendTransactionRpcError(RpcError),
+    /// The transaction was processed but failed, e.g. because the payer has insufficient funds.
+    #[error("Transaction {signature} failed: {error}")]
+    TransactionFailed {
+        /// The signature of the failed transaction.
+        signature: Signature,
+        /// The error of the failed transaction.
+        error: TransactionError,
+    },
+    /// The transaction was submitted but did not reach the requested commitment level within
+    /// the configured number of status checks. The transaction may still be confirmed later,
+    /// which can be checked with [`SolRpcClient::get_signature_statuses`].
+    #[error("Transaction {0} was not confirmed")]
+    NotConfirmed(Signature),
+    /// An IC error occurred, e.g. while deriving the payer's public key or signing the
+    /// transaction.
+    #[error("IC error: {0}")]
+    IcError(IcError),
+}
+
+/// A builder to build, sign, submit and confirm a SOL or SPL token transfer.
+/// See [`SolRpcClient::transfer_sol`] and [`SolRpcClient::transfer_spl`].
+#[must_use = "TransferRequestBuilder does nothing until you 'send' it"]
+pub struct TransferRequestBuilder<R> {
+    client: SolRpcClient<R>,
+    key_id: Ed25519KeyId,
+    payer: DerivationPath,
+    transfer: Transfer,
+    commitment: CommitmentLevel,
+    num_confirmation_checks: NonZeroUsize,
+}
+
+impl<R> TransferRequestBuilder<R> {
+    /// Create a new [`TransferRequestBuilder`] for the given transfer, paid by the account
+    /// derived from the given threshold Ed25519 key and derivation path.
+    ///
+    /// By default, the transfer is considered successful once the transaction reaches the
+    /// `confirmed` commitment level, and its status is checked at most 10 times.
+    pub fn new(
+        client: SolRpcClient<R>,
+        key_id: Ed25519KeyId,
+        payer: DerivationPath,
+        transfer: Transfer,
+    ) -> Self {
+        Self {
+            client,
+            key_id,
+            payer,
+            transfer,
+            commitment: CommitmentLevel::Confirmed,
+            num_confirmation_checks: NonZeroUsize::new(10).unwrap(),
+        }
+    }
+
+    /// Change the commitment level the transaction must reach for the transfer to succeed.
+    pub fn with_commitment(mut self, commitment: CommitmentLevel) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Change the maximum number of `getSignatureStatuses` calls made to check whether the
+    /// transaction reached the requested commitment level.
+    pub fn with_num_confirmation_checks(mut self, num_confirmation_checks: NonZeroUsize) -> Self {
+        self.num_confirmation_checks = num_confirmation_checks;
+        self
+    }
+}
+
+impl<R: Runtime> TransferRequestBuilder<R> {
+    /// Builds, signs and submits the transfer transaction, and waits until it reaches the
+    /// requested commitment level.
+    ///
+    /// Returns the signature of the confirmed transaction, or the [`TransferError`] of the
+    /// first step that failed.
+    pub async fn try_send(self) -> Result<Signature, TransferError> {
+        let runtime = self.client.runtime();
+        let (payer, _chain_code) = get_pubkey(runtime, None, Some(&self.payer), self.key_id)
+            .await
+            .map_err(TransferError::IcError)?;
+        let instruction = self.instruction(&payer).await?;
+        let blockhash = self.fetch_blockhash().await?;
+        let message = Message::new_with_blockhash(&[instruction], Some(&payer), &blockhash);
+        let signature = sign_message(runtime, &message, self.key_id, Some(&self.payer))
+            .await
+            .map_err(TransferError::IcError)?;
+        let transaction = Transaction {
+            message,
+            signatures: vec![signature],
+        };
+        let signature = self.send_transaction(transaction).await?;
+        self.wait_for_confirmation(signature).await?;
+        Ok(signature)
+    }
+
+    async fn instruction(&self, payer: &Pubkey) -> Result<Instruction, TransferError> {
+        match &self.transfer {
+            Transfer::Sol {
+                recipient,
+                lamports,
+            } => Ok(solana_system_interface::instruction::transfer(
+                payer, recipient, *lamports,
+            )),
+            Transfer::Spl {
+                mint,
+                recipient,
+                amount,
+            } => {
+                let (token_program, decimals) = self.fetch_mint(mint).await?;
+                Ok(transfer_checked_instruction(
+                    &token_program,
+                    &associated_token_address(payer, mint, &token_program),
+                    mint,
+                    &associated_token_address(recipient, mint, &token_program),
+                    payer,
+                    *amount,
+                    decimals,
+                ))
+            }
+        }
+    }
+
+    /// Returns the token program owning the given mint and the number of decimals of the mint.
+    async fn fetch_mint(&self, mint: &Pubkey) -> Result<(Pubkey, u8), TransferError> {
+        let account = match self
+            .client
+            .get_account_info(GetAccountInfoParams::from_pubkey(*mint))
+            .with_encoding(GetAccountInfoEncoding::Base64)
+            .try_send()
+            .await
+        {
+            Ok(MultiRpcResult::Consistent(Ok(account))) => account,
+            Ok(MultiRpcResult::Consistent(Err(e))) => {
+                return Err(TransferError::GetMintRpcError(e))
+            }
+            Ok(MultiRpcResult::Inconsistent(results)) => {
+                return Err(TransferError::GetMintConsensusError(results))
+            }
+            Err(e) => return Err(TransferError::IcError(e)),
+        };
+        account
+            .and_then(|account| mint_from_account(&account))
+            .ok_or(TransferError::InvalidMint(*mint))
+    }
+
+    async fn fetch_blockhash(&self) -> Result<solana_hash::Hash, TransferError> {
+        let (_slot, block) = self
+            .client
+            .get_recent_block()
+            .try_send()
+            .await
+            .map_err(TransferError::GetRecentBlockError)?;
+        block
+            .blockhash
+            .parse()
+            .map_err(|e| TransferError::InvalidBlockhash(format!("{}: {e}", block.blockhash)))
+    }
+
+    async fn send_transaction(&self, transaction: Transaction) -> Result<Signature, TransferError> {
+        let request = self
+            .client
+            .try_send_transaction(transaction)
+            .map_err(TransferError::SendTransactionRpcError)?;
+        match request.try_send().await {
+            Ok(MultiRpcResult::Consistent(Ok(signature))) => Ok(signature),
+            Ok(MultiRpcResult::Consistent(Err(e))) => {
+                Err(TransferError::SendTransactionRpcError(e))
+            }
+            Ok(MultiRpcResult::Inconsistent(results)) => {
+                Err(TransferError::SendTransactionConsensusError(results))
+            }
+            Err(e) => Err(TransferError::IcError(e)),
+        }
+    }
+
+    /// Checks the status of the transaction until it reaches the requested commitment level.
+    ///
+    /// Errors and inconsistent results of the `getSignatureStatuses` calls are not fatal, since
+    /// the transaction was already submitted, and the status is simply checked again.
+    async fn wait_for_confirmation(&self, signature: Signature) -> Result<(), TransferError> {
+        for _ in 0..self.num_confirmation_checks.get() {
+            let Ok(request) = self.client.get_signature_statuses(&[signature]) else {
+                break;
+            };
+            let Ok(MultiRpcResult::Consistent(Ok(statuses))) = request.try_send().await else {
+                continue;
+            };
+            let status = statuses.into_iter().next().flatten();
+            if let Some(error) = status.as_ref().and_then(|status| status.err.clone()) {
+                return Err(TransferError::TransactionFailed { signature, error });
+            }
+            if status.satisfies(self.commitment.clone()) {
+                return Ok(());
+            }
+        }
+        Err(TransferError::NotConfirmed(signature))
+    }
+}
+
+/// Returns the token program owning the given mint account and the number of decimals of the
+/// mint, or `None` if the account is not an initialized mint of the SPL Token or Token-2022
+/// programs.
+fn mint_from_account(account: &UiAccount) -> Option<(Pubkey, u8)> {
+    let token_program = account.owner.parse::<Pubkey>().ok()?;
+    if token_program != TOKEN_PROGRAM_ID && token_program != TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+    let data = account.data.decode()?;
+    if data.len() < MINT_LEN || data[MINT_IS_INITIALIZED_OFFSET] != 1 {
+        return None;
+    }
+    Some((token_program, data[MINT_DECIMALS_OFFSET]))
+}
+
+/// Creates a `TransferChecked` instruction of the given token program, which checks that the
+/// transferred amount is expressed with the number of decimals of the mint.
+fn transfer_checked_instruction(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    let mut data = Vec::with_capacity(10);
+    data.push(TRANSFER_CHECKED_TAG);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,368 @@
+use crate::{
+    ed25519::{DerivationPath, Ed25519KeyId},
+    fixtures::{usdc_account, MockCall, MockRuntime},
+    instruction_decoder::TOKEN_PROGRAM_ID,
+    pda::associated_token_address,
+    transfer::{TransferError, TRANSFER_CHECKED_TAG},
+    SolRpcClient, SOL_RPC_CANISTER,
+};
+use ic_management_canister_types::{
+    SchnorrPublicKeyArgs, SchnorrPublicKeyResult, SignWithSchnorrResult,
+};
+use sol_rpc_types::{
+    AccountInfo, ConfirmedBlock, InstructionError, MultiRpcResult, RpcConfig, RpcError, RpcSources,
+    SendTransactionParams, TransactionConfirmationStatus, TransactionError, TransactionStatus,
+};
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_message::Message;
+use solana_pubkey::{pubkey, Pubkey};
+use solana_signature::Signature;
+use solana_transaction::Transaction;
+use std::{num::NonZeroUsize, str::FromStr};
+
+const PAYER: Pubkey = pubkey!("BPebStjcgCPnWTK3FXZJ8KhqwNYLk9aubC9b4Cgqb6oE");
+const RECIPIENT: Pubkey = pubkey!("FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN");
+const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const BLOCKHASH: &str = "C6Cxgzq6yZWxjYnxwvxvP2dhWFeQSEVxRQbUXG2eMYsY";
+const SIGNATURE: &str =
+    "37HbmunhjSC1xxnVsaFX2xaS8gYnb5JYiLy9B51Ky9Up69aF7Qra6dHSLMCaiurRYq3Y8ZxSVUwC5sntziWuhZee";
+
+type SendTransactionArgs = (RpcSources, Option<RpcConfig>, SendTransactionParams);
+
+#[tokio::test]
+async fn should_transfer_sol() {
+    let runtime = MockRuntime::new()
+        .with_call(schnorr_public_key())
+        .with_call(get_slot())
+        .with_call(get_block())
+        .with_call(sign_with_schnorr())
+        .with_call(send_transaction())
+        .with_call(get_signature_statuses(Some(status(
+            TransactionConfirmationStatus::Confirmed,
+        ))));
+
+    let signature = client(&runtime)
+        .transfer_sol(
+            Ed25519KeyId::MainnetTestKey1,
+            payer_path(),
+            RECIPIENT,
+            1_000,
+        )
+        .try_send()
+        .await;
+
+    assert_eq!(signature, Ok(signature_()));
+    assert!(runtime.is_done());
+    let (derivation_path_arg,) = runtime.calls()[0]
+        .decode_args::<(SchnorrPublicKeyArgs,)>()
+        .unwrap();
+    assert_eq!(
+        derivation_path_arg.derivation_path,
+        Vec::<Vec<u8>>::from(payer_path())
+    );
+    assert_eq!(
+        sent_transaction(&runtime),
+        expected_transaction(solana_system_interface::instruction::transfer(
+            &PAYER, &RECIPIENT, 1_000
+        ))
+    );
+}
+
+#[tokio::test]
+async fn should_transfer_spl_tokens_with_transfer_checked() {
+    let runtime = MockRuntime::new()
+        .with_call(schnorr_public_key())
+        .with_call(
+            MockCall::new("getAccountInfo")
+                .respond_with(MultiRpcResult::Consistent(Ok(Some(usdc_account())))),
+        )
+        .with_call(get_slot())
+        .with_call(get_block())
+        .with_call(sign_with_schnorr())
+        .with_call(send_transaction())
+        .with_call(get_signature_statuses(Some(status(
+            TransactionConfirmationStatus::Finalized,
+        ))));
+
+    let signature = client(&runtime)
+        .transfer_spl(
+            Ed25519KeyId::MainnetTestKey1,
+            payer_path(),
+            USDC_MINT,
+            RECIPIENT,
+            1_500_000,
+        )
+        .try_send()
+        .await;
+
+    assert_eq!(signature, Ok(signature_()));
+    assert!(runtime.is_done());
+    let expected_instruction = Instruction {
+        program_id: TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(
+                associated_token_address(&PAYER, &USDC_MINT, &TOKEN_PROGRAM_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(USDC_MINT, false),
+            AccountMeta::new(
+                associated_token_address(&RECIPIENT, &USDC_MINT, &TOKEN_PROGRAM_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(PAYER, true),
+        ],
+        // USDC has 6 decimals
+        data: [
+            vec![TRANSFER_CHECKED_TAG],
+            1_500_000_u64.to_le_bytes().to_vec(),
+            vec![6],
+        ]
+        .concat(),
+    };
+    assert_eq!(
+        sent_transaction(&runtime),
+        expected_transaction(expected_instruction)
+    );
+}
+
+#[tokio::test]
+async fn should_fail_when_mint_is_not_a_token_mint() {
+    let runtime = MockRuntime::new()
+        .with_call(schnorr_public_key())
+        .with_call(
+            MockCall::new("getAccountInfo").respond_with(MultiRpcResult::Consistent(Ok(Some(
+                AccountInfo {
+                    owner: "11111111111111111111111111111111".to_string(),
+                    ..usdc_account()
+                },
+            )))),
+        );
+
+    let result = client(&runtime)
+        .transfer_spl(
+            Ed25519KeyId::MainnetTestKey1,
+            payer_path(),
+            USDC_MINT,
+            RECIPIENT,
+            1_500_000,
+        )
+        .try_send()
+        .await;
+
+    assert_eq!(result, Err(TransferError::InvalidMint(USDC_MINT)));
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_fail_when_transaction_is_rejected() {
+    let error = RpcError::ValidationError("Blockhash not found".to_string());
+    let runtime = MockRuntime::new()
+        .with_call(schnorr_public_key())
+        .with_call(get_slot())
+        .with_call(get_block())
+        .with_call(sign_with_schnorr())
+        .with_call(
+            MockCall::new("sendTransaction").respond_with(
+                MultiRpcResult::<sol_rpc_types::Signature>::Consistent(Err(error.clone())),
+            ),
+        );
+
+    let result = client(&runtime)
+        .transfer_sol(
+            Ed25519KeyId::MainnetTestKey1,
+            payer_path(),
+            RECIPIENT,
+            1_000,
+        )
+        .try_send()
+        .await;
+
+    assert_eq!(result, Err(TransferError::SendTransactionRpcError(error)));
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_fail_when_transaction_fails() {
+    let error = TransactionError::InstructionError(0, InstructionError::Custom(1));
+    let runtime = MockRuntime::new()
+        .with_call(schnorr_public_key())
+        .with_call(get_slot())
+        .with_call(get_block())
+        .with_call(sign_with_schnorr())
+        .with_call(send_transaction())
+        .with_call(get_signature_statuses(Some(TransactionStatus {
+            status: Err(error.clone()),
+            err: Some(error.clone()),
+            ..status(TransactionConfirmationStatus::Processed)
+        })));
+
+    let result = client(&runtime)
+        .transfer_sol(
+            Ed25519KeyId::MainnetTestKey1,
+            payer_path(),
+            RECIPIENT,
+            1_000,
+        )
+        .try_send()
+        .await;
+
+    assert_eq!(
+        result,
+        Err(TransferError::TransactionFailed {
+            signature: signature_(),
+            error: error.into(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn should_check_status_until_commitment_is_reached() {
+    let runtime = MockRuntime::new()
+        .with_call(schnorr_public_key())
+        .with_call(get_slot())
+        .with_call(get_block())
+        .with_call(sign_with_schnorr())
+        .with_call(send_transaction())
+        .with_call(get_signature_statuses(None))
+        .with_call(get_signature_statuses(Some(status(
+            TransactionConfirmationStatus::Processed,
+        ))))
+        .with_call(get_signature_statuses(Some(status(
+            TransactionConfirmationStatus::Confirmed,
+        ))));
+
+    let signature = client(&runtime)
+        .transfer_sol(
+            Ed25519KeyId::MainnetTestKey1,
+            payer_path(),
+            RECIPIENT,
+            1_000,
+        )
+        .try_send()
+        .await;
+
+    assert_eq!(signature, Ok(signature_()));
+    assert!(runtime.is_done());
+}
+
+#[tokio::test]
+async fn should_fail_when_transaction_is_not_confirmed() {
+    let runtime = MockRuntime::new()
+        .with_call(schnorr_public_key())
+        .with_call(get_slot())
+        .with_call(get_block())
+        .with_call(sign_with_schnorr())
+        .with_call(send_transaction())
+        .with_call(
+            get_signature_statuses(Some(status(TransactionConfirmationStatus::Confirmed)))
+                .repeatedly(),
+        );
+
+    let result = client(&runtime)
+        .transfer_sol(
+            Ed25519KeyId::MainnetTestKey1,
+            payer_path(),
+            RECIPIENT,
+            1_000,
+        )
+        .with_commitment(sol_rpc_types::CommitmentLevel::Finalized)
+        .with_num_confirmation_checks(NonZeroUsize::new(3).unwrap())
+        .try_send()
+        .await;
+
+    assert_eq!(result, Err(TransferError::NotConfirmed(signature_())));
+    let num_status_checks = runtime
+        .calls()
+        .iter()
+        .filter(|call| call.method == "getSignatureStatuses")
+        .count();
+    assert_eq!(num_status_checks, 3);
+}
+
+fn client(runtime: &MockRuntime) -> SolRpcClient<MockRuntime> {
+    SolRpcClient::builder(runtime.clone(), SOL_RPC_CANISTER).build()
+}
+
+fn payer_path() -> DerivationPath {
+    DerivationPath::from(b"payer".as_slice())
+}
+
+fn signature_() -> Signature {
+    Signature::from_str(SIGNATURE).unwrap()
+}
+
+fn status(confirmation_status: TransactionConfirmationStatus) -> TransactionStatus {
+    TransactionStatus {
+        slot: 332_577_900,
+        status: Ok(()),
+        err: None,
+        confirmation_status: Some(confirmation_status),
+    }
+}
+
+fn schnorr_public_key() -> MockCall {
+    MockCall::new("schnorr_public_key").respond_with(SchnorrPublicKeyResult {
+        public_key: PAYER.as_ref().to_vec(),
+        chain_code: vec![0; 32],
+    })
+}
+
+fn sign_with_schnorr() -> MockCall {
+    MockCall::new("sign_with_schnorr").respond_with(SignWithSchnorrResult {
+        signature: signature_().as_ref().to_vec(),
+    })
+}
+
+fn get_slot() -> MockCall {
+    MockCall::new("getSlot").respond_with(MultiRpcResult::Consistent(Ok(332_577_897_u64)))
+}
+
+fn get_block() -> MockCall {
+    MockCall::new("getBlock").respond_with(MultiRpcResult::Consistent(Ok(Some(ConfirmedBlock {
+        previous_blockhash: Default::default(),
+        blockhash: BLOCKHASH.parse().unwrap(),
+        parent_slot: 332_577_896,
+        block_time: None,
+        block_height: None,
+        signatures: None,
+        rewards: None,
+        num_reward_partitions: None,
+        transactions: None,
+    }))))
+}
+
+fn send_transaction() -> MockCall {
+    MockCall::new("sendTransaction").respond_with(MultiRpcResult::Consistent(Ok(
+        sol_rpc_types::Signature::from(signature_()),
+    )))
+}
+
+fn get_signature_statuses(status: Option<TransactionStatus>) -> MockCall {
+    MockCall::new("getSignatureStatuses").respond_with(MultiRpcResult::Consistent(Ok(vec![status])))
+}
+
+fn sent_transaction(runtime: &MockRuntime) -> Transaction {
+    let call = runtime
+        .calls()
+        .into_iter()
+        .find(|call| call.method == "sendTransaction")
+        .expect("No call to `sendTransaction`");
+    let (_, _, params) = call.decode_args::<SendTransactionArgs>().unwrap();
+    params
+        .decode_transaction()
+        .unwrap()
+        .into_legacy_transaction()
+        .unwrap()
+}
+
+fn expected_transaction(instruction: Instruction) -> Transaction {
+    Transaction {
+        message: Message::new_with_blockhash(
+            &[instruction],
+            Some(&PAYER),
+            &Hash::from_str(BLOCKHASH).unwrap(),
+        ),
+        signatures: vec![signature_()],
+    }
+}
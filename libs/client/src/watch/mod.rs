@@ -0,0 +1,164 @@
+//! Module to watch an address for new transactions by polling `getSignaturesForAddress`.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{IcError, SolRpcClient};
+use candid::CandidType;
+use ic_canister_runtime::Runtime;
+use serde::{Deserialize, Serialize};
+use sol_rpc_types::{
+    CommitmentLevel, ConfirmedTransactionStatusWithSignature, GetSignaturesForAddressLimit,
+    MultiRpcResult, Pubkey, RpcError, RpcResult, RpcSource, Signature,
+};
+use thiserror::Error;
+
+/// Position of an [`AddressWatcher`] in the transaction history of the watched address.
+///
+/// The cursor only contains the signature of the most recent transaction returned so far and can
+/// be persisted (e.g. in the stable memory of a canister) to resume watching an address after an
+/// upgrade with [`AddressWatcher::with_cursor`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct AddressCursor {
+    /// Signature of the most recent transaction seen for the watched address, if any.
+    pub last_seen: Option<Signature>,
+}
+
+/// Polls `getSignaturesForAddress` to return only the signatures of transactions involving an
+/// address that were not returned by a previous poll.
+///
+/// Created with [`SolRpcClient::watch_address`]. Each call to [`AddressWatcher::poll`] fetches the
+/// signatures more recent than the cursor (using the `until` parameter), paginating with the
+/// `before` parameter when there are more new signatures than fit in a single page, and returns
+/// them in chronological order, i.e. oldest first. The cursor is only advanced when the poll
+/// succeeds, so that a failed poll can simply be retried, e.g. on the next timer tick.
+///
+/// The first poll without a cursor only fetches a single page of the most recent signatures,
+/// instead of crawling the whole history of the address.
+///
+/// It is recommended to use the `finalized` commitment level (the default of `getSignaturesForAddress`),
+/// so that the cursor never points to a transaction that could be rolled back.
+#[derive(Clone)]
+pub struct AddressWatcher<R> {
+    client: SolRpcClient<R>,
+    pubkey: Pubkey,
+    cursor: AddressCursor,
+    commitment: Option<CommitmentLevel>,
+    limit: Option<GetSignaturesForAddressLimit>,
+}
+
+impl<R> AddressWatcher<R> {
+    pub(crate) fn new(client: SolRpcClient<R>, pubkey: Pubkey) -> Self {
+        Self {
+            client,
+            pubkey,
+            cursor: AddressCursor::default(),
+            commitment: None,
+            limit: None,
+        }
+    }
+
+    /// Resume watching the address from the given cursor.
+    pub fn with_cursor(mut self, cursor: AddressCursor) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Change the `commitment` parameter of the `getSignaturesForAddress` requests.
+    pub fn with_commitment(mut self, commitment: CommitmentLevel) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
+    /// Change the `limit` parameter of the `getSignaturesForAddress` requests, i.e. the maximum
+    /// number of signatures fetched with a single request.
+    pub fn with_limit(mut self, limit: GetSignaturesForAddressLimit) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns the watched address.
+    pub fn pubkey(&self) -> &Pubkey {
+        &self.pubkey
+    }
+
+    /// Returns the current cursor, which should be persisted to resume watching the address later.
+    pub fn cursor(&self) -> &AddressCursor {
+        &self.cursor
+    }
+}
+
+impl<R: Runtime> AddressWatcher<R> {
+    /// Fetch the signatures of the transactions involving the watched address since the previous
+    /// poll, in chronological order (oldest first), and advance the cursor.
+    ///
+    /// # Errors
+    ///
+    /// The method returns an instance of [`WatchAddressError`] if any of the
+    /// `getSignaturesForAddress` calls fails. The cursor is then left unchanged.
+    pub async fn poll(
+        &mut self,
+    ) -> Result<Vec<ConfirmedTransactionStatusWithSignature>, WatchAddressError> {
+        let page_size = u32::from(self.limit.unwrap_or_default()) as usize;
+        let mut new_signatures = Vec::new();
+        let mut before: Option<Signature> = None;
+        loop {
+            let mut request = self.client.get_signatures_for_address(self.pubkey.clone());
+            if let Some(commitment) = &self.commitment {
+                request = request.with_commitment(commitment.clone());
+            }
+            if let Some(limit) = self.limit {
+                request = request.with_limit(limit);
+            }
+            if let Some(until) = &self.cursor.last_seen {
+                request = request.with_until(until.clone());
+            }
+            if let Some(before) = before {
+                request = request.with_before(before);
+            }
+            let page = match request.try_send().await {
+                Ok(MultiRpcResult::Consistent(Ok(page))) => page,
+                Ok(MultiRpcResult::Consistent(Err(e))) => {
+                    return Err(WatchAddressError::RpcError(e))
+                }
+                Ok(MultiRpcResult::Inconsistent(results)) => {
+                    return Err(WatchAddressError::ConsensusError(results))
+                }
+                Err(e) => return Err(WatchAddressError::IcError(e)),
+            };
+            // Signatures are returned from newest to oldest and the `until` signature is
+            // excluded, so that a page shorter than the limit means all new signatures were fetched.
+            let is_last_page = page.len() < page_size || self.cursor.last_seen.is_none();
+            before = page.last().map(|status| status.signature.clone());
+            new_signatures.extend(page);
+            if is_last_page || before.is_none() {
+                break;
+            }
+        }
+        if let Some(newest) = new_signatures.first() {
+            self.cursor.last_seen = Some(newest.signature.clone());
+        }
+        new_signatures.reverse();
+        Ok(new_signatures)
+    }
+}
+
+/// An error that occurred while polling for new signatures with [`AddressWatcher::poll`].
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum WatchAddressError {
+    /// The results from the different providers were not consistent for a
+    /// `getSignaturesForAddress` call.
+    #[error("Inconsistent result while fetching signatures: {0:?}")]
+    ConsensusError(
+        Vec<(
+            RpcSource,
+            RpcResult<Vec<ConfirmedTransactionStatusWithSignature>>,
+        )>,
+    ),
+    /// An error occurred during a `getSignaturesForAddress` call.
+    #[error("Error while fetching signatures: {0}")]
+    RpcError(RpcError),
+    /// An IC error occurred while making the request.
+    #[error("IC error: {0}")]
+    IcError(IcError),
+}
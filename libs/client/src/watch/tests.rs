@@ -0,0 +1,143 @@
+use crate::{
+    watch::{AddressCursor, WatchAddressError},
+    SolRpcClient,
+};
+use assert_matches::assert_matches;
+use ic_canister_runtime::StubRuntime;
+use sol_rpc_types::{
+    ConfirmedTransactionStatusWithSignature, MultiRpcResult, RpcError, RpcSource, Signature,
+    SupportedRpcProviderId,
+};
+use solana_pubkey::{pubkey, Pubkey};
+
+const PUBKEY: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+#[tokio::test]
+async fn should_return_most_recent_signatures_on_first_poll() {
+    let client = client_with_pages(vec![vec![status(3), status(2), status(1)]]);
+    let mut watcher = client.watch_address(PUBKEY);
+
+    let new_signatures = watcher.poll().await;
+
+    assert_eq!(new_signatures, Ok(vec![status(1), status(2), status(3)]));
+    assert_eq!(watcher.cursor().last_seen, Some(signature(3)));
+}
+
+#[tokio::test]
+async fn should_only_fetch_single_page_on_first_poll() {
+    let client = client_with_pages(vec![vec![status(3), status(2)], vec![status(1)]]);
+    let mut watcher = client
+        .watch_address(PUBKEY)
+        .with_limit(2.try_into().unwrap());
+
+    assert_eq!(watcher.poll().await, Ok(vec![status(2), status(3)]));
+    assert_eq!(watcher.cursor().last_seen, Some(signature(3)));
+}
+
+#[tokio::test]
+async fn should_paginate_until_cursor() {
+    let client = client_with_pages(vec![
+        vec![status(5), status(4)],
+        vec![status(3), status(2)],
+        vec![],
+    ]);
+    let mut watcher = client
+        .watch_address(PUBKEY)
+        .with_limit(2.try_into().unwrap())
+        .with_cursor(AddressCursor {
+            last_seen: Some(signature(1)),
+        });
+
+    let new_signatures = watcher.poll().await;
+
+    assert_eq!(
+        new_signatures,
+        Ok(vec![status(2), status(3), status(4), status(5)])
+    );
+    assert_eq!(watcher.cursor().last_seen, Some(signature(5)));
+}
+
+#[tokio::test]
+async fn should_not_advance_cursor_without_new_signatures() {
+    let client = client_with_pages(vec![vec![status(1)], vec![]]);
+    let mut watcher = client.watch_address(PUBKEY);
+
+    assert_eq!(watcher.poll().await, Ok(vec![status(1)]));
+    assert_eq!(watcher.poll().await, Ok(vec![]));
+    assert_eq!(watcher.cursor().last_seen, Some(signature(1)));
+}
+
+#[tokio::test]
+async fn should_not_advance_cursor_on_error() {
+    let error = RpcError::ValidationError("error".to_string());
+    let client = SolRpcClient::builder_for_ic()
+        .with_stub_responses()
+        .add_stub_response(page(vec![status(3), status(2)]))
+        .add_stub_response(
+            MultiRpcResult::<Vec<ConfirmedTransactionStatusWithSignature>>::Consistent(Err(
+                error.clone()
+            )),
+        )
+        .add_stub_response(MultiRpcResult::Inconsistent(vec![
+            (
+                RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet),
+                Ok(vec![status(4)]),
+            ),
+            (
+                RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet),
+                Ok(vec![]),
+            ),
+        ]))
+        .build();
+    let mut watcher = client
+        .watch_address(PUBKEY)
+        .with_limit(2.try_into().unwrap())
+        .with_cursor(AddressCursor {
+            last_seen: Some(signature(1)),
+        });
+
+    assert_eq!(
+        watcher.poll().await,
+        Err(WatchAddressError::RpcError(error))
+    );
+    assert_eq!(watcher.cursor().last_seen, Some(signature(1)));
+
+    assert_matches!(
+        watcher.poll().await,
+        Err(WatchAddressError::ConsensusError(_))
+    );
+    assert_eq!(watcher.cursor().last_seen, Some(signature(1)));
+}
+
+fn client_with_pages(
+    pages: Vec<Vec<ConfirmedTransactionStatusWithSignature>>,
+) -> SolRpcClient<StubRuntime> {
+    pages
+        .into_iter()
+        .fold(
+            SolRpcClient::builder_for_ic().with_stub_responses(),
+            |builder, statuses| builder.add_stub_response(page(statuses)),
+        )
+        .build()
+}
+
+fn page(
+    statuses: Vec<ConfirmedTransactionStatusWithSignature>,
+) -> MultiRpcResult<Vec<ConfirmedTransactionStatusWithSignature>> {
+    MultiRpcResult::Consistent(Ok(statuses))
+}
+
+fn status(n: u8) -> ConfirmedTransactionStatusWithSignature {
+    ConfirmedTransactionStatusWithSignature {
+        signature: signature(n),
+        slot: 340_372_399 + n as u64,
+        err: None,
+        memo: None,
+        block_time: None,
+        confirmation_status: None,
+    }
+}
+
+fn signature(n: u8) -> Signature {
+    Signature::from(solana_signature::Signature::from([n; 64]))
+}
@@ -0,0 +1,264 @@
+//! Module to interact with the SOL RPC canister from a browser, e.g. from the frontend of a dapp,
+//! rather than from a canister.
+//!
+//! This module is only available with the `wasm-web` feature and is meant for the
+//! `wasm32-unknown-unknown` target when running in a JavaScript environment with
+//! [`wasm-bindgen`](https://docs.rs/wasm-bindgen). It provides:
+//! * An [`AgentJsRuntime`], i.e. a [`Runtime`] making calls to canisters through a
+//!   [`CanisterCaller`] implemented in JavaScript, e.g. with
+//!   [`@dfinity/agent`](https://www.npmjs.com/package/@dfinity/agent).
+//! * A [`SolRpcWebClient`] exposing a JavaScript-friendly subset of the [`SolRpcClient`].
+//!
+//! Calls made from a browser are ingress messages, which cannot attach cycles. Calls to endpoints
+//! that are not free are therefore paid with the prepaid cycles of the caller, see
+//! [`SolRpcClient::deposit_cycles`].
+//!
+//! Canister-specific functionality, such as [`SolRpcClient::builder_for_ic`] or
+//! [`IcRuntimeV2`](crate::IcRuntimeV2), must not be used with this feature, since the System API
+//! of the Internet Computer is not available in a browser.
+
+use crate::{IcError, SolRpcClient, SOL_RPC_CANISTER};
+use async_trait::async_trait;
+use candid::{utils::ArgumentEncoder, CandidType, Principal};
+use ic_canister_runtime::Runtime;
+use ic_cdk::call::RejectCode;
+use send_wrapper::SendWrapper;
+use serde::de::DeserializeOwned;
+use sol_rpc_types::{MultiRpcResult, RpcSources, SolanaCluster};
+use solana_pubkey::Pubkey;
+use std::{fmt, fmt::Debug, str::FromStr};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CANISTER_CALLER: &str = r#"
+/**
+ * Makes calls to canisters, e.g. with the `call` and `query` methods of an `HttpAgent`.
+ *
+ * Both methods take the Candid-encoded arguments of the call and resolve with the
+ * Candid-encoded reply of the canister, or reject if the call was not successful.
+ */
+export interface CanisterCaller {
+  update(canisterId: string, method: string, arg: Uint8Array): Promise<Uint8Array>;
+  query(canisterId: string, method: string, arg: Uint8Array): Promise<Uint8Array>;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// JavaScript object making calls to canisters, see the `CanisterCaller` TypeScript interface.
+    #[wasm_bindgen(typescript_type = "CanisterCaller")]
+    #[derive(Clone)]
+    pub type CanisterCaller;
+
+    #[wasm_bindgen(method, catch)]
+    async fn update(
+        this: &CanisterCaller,
+        canister_id: String,
+        method: String,
+        arg: Vec<u8>,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    async fn query(
+        this: &CanisterCaller,
+        canister_id: String,
+        method: String,
+        arg: Vec<u8>,
+    ) -> Result<JsValue, JsValue>;
+}
+
+/// [`Runtime`] to make calls to canisters from a browser through a [`CanisterCaller`].
+///
+/// Since calls made from a browser cannot attach cycles, the cycles given to
+/// [`Runtime::update_call`] are ignored.
+#[derive(Clone)]
+pub struct AgentJsRuntime {
+    // JavaScript values can only be used from the thread that created them, which is the only
+    // thread in a browser.
+    caller: SendWrapper<CanisterCaller>,
+}
+
+impl AgentJsRuntime {
+    /// Creates a new [`AgentJsRuntime`] making calls with the given [`CanisterCaller`].
+    pub fn new(caller: CanisterCaller) -> Self {
+        Self {
+            caller: SendWrapper::new(caller),
+        }
+    }
+}
+
+impl Debug for AgentJsRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AgentJsRuntime").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Runtime for AgentJsRuntime {
+    async fn update_call<In, Out>(
+        &self,
+        id: Principal,
+        method: &str,
+        args: In,
+        _cycles: u128,
+    ) -> Result<Out, IcError>
+    where
+        In: ArgumentEncoder + Send,
+        Out: CandidType + DeserializeOwned,
+    {
+        let arg = encode_args(args)?;
+        let reply = SendWrapper::new(self.caller.update(id.to_text(), method.to_string(), arg))
+            .await
+            .map_err(call_rejected)?;
+        decode_reply(reply)
+    }
+
+    async fn query_call<In, Out>(
+        &self,
+        id: Principal,
+        method: &str,
+        args: In,
+    ) -> Result<Out, IcError>
+    where
+        In: ArgumentEncoder + Send,
+        Out: CandidType + DeserializeOwned,
+    {
+        let arg = encode_args(args)?;
+        let reply = SendWrapper::new(self.caller.query(id.to_text(), method.to_string(), arg))
+            .await
+            .map_err(call_rejected)?;
+        decode_reply(reply)
+    }
+}
+
+fn encode_args<In: ArgumentEncoder>(args: In) -> Result<Vec<u8>, IcError> {
+    candid::utils::encode_args(args).map_err(|e| IcError::CallRejected {
+        code: RejectCode::CanisterError,
+        message: format!("Failed to encode arguments: {e}"),
+    })
+}
+
+fn decode_reply<Out: CandidType + DeserializeOwned>(reply: JsValue) -> Result<Out, IcError> {
+    let bytes = js_sys::Uint8Array::new(&reply).to_vec();
+    candid::decode_one(&bytes).map_err(|e| IcError::CallRejected {
+        code: RejectCode::CanisterError,
+        message: format!("Failed to decode reply: {e}"),
+    })
+}
+
+fn call_rejected(error: JsValue) -> IcError {
+    let message = error
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .or_else(|| error.as_string())
+        .unwrap_or_else(|| format!("{error:?}"));
+    IcError::CallRejected {
+        code: RejectCode::SysTransient,
+        message,
+    }
+}
+
+/// JavaScript-friendly client for the SOL RPC canister.
+///
+/// Each method returns a `Promise` that resolves with the result agreed upon by the providers,
+/// or rejects if the call failed or the providers did not agree.
+///
+/// # Examples
+///
+/// ```js
+/// import init, { SolRpcWebClient } from "sol_rpc_client";
+///
+/// await init();
+/// // Implements the `CanisterCaller` interface, e.g. with an `HttpAgent` from `@dfinity/agent`.
+/// const caller = {
+///   update: async (canisterId, method, arg) => { /* ... */ },
+///   query: async (canisterId, method, arg) => { /* ... */ },
+/// };
+/// const client = new SolRpcWebClient(caller, undefined, "mainnet");
+/// const slot = await client.getSlot();
+/// ```
+#[wasm_bindgen]
+pub struct SolRpcWebClient {
+    client: SolRpcClient<AgentJsRuntime>,
+}
+
+#[wasm_bindgen]
+impl SolRpcWebClient {
+    /// Creates a new client making calls with the given `CanisterCaller` to the SOL RPC canister
+    /// with the given ID (the [`SOL_RPC_CANISTER`] by default), and querying the default
+    /// providers of the given Solana cluster (`mainnet` by default, or `devnet`).
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        caller: CanisterCaller,
+        canister_id: Option<String>,
+        cluster: Option<String>,
+    ) -> Result<SolRpcWebClient, JsError> {
+        let canister_id = match canister_id {
+            Some(id) => Principal::from_text(&id)
+                .map_err(|e| JsError::new(&format!("Invalid canister ID '{id}': {e}")))?,
+            None => SOL_RPC_CANISTER,
+        };
+        let cluster = match cluster.as_deref() {
+            None | Some("mainnet") => SolanaCluster::Mainnet,
+            Some("devnet") => SolanaCluster::Devnet,
+            Some(cluster) => {
+                return Err(JsError::new(&format!(
+                    "Unsupported Solana cluster '{cluster}'"
+                )))
+            }
+        };
+        let client = SolRpcClient::builder(AgentJsRuntime::new(caller), canister_id)
+            .with_rpc_sources(RpcSources::Default(cluster))
+            .build();
+        Ok(Self { client })
+    }
+
+    /// Calls `getSlot` and resolves with the slot as a `bigint`.
+    #[wasm_bindgen(js_name = getSlot)]
+    pub fn get_slot(&self) -> js_sys::Promise {
+        let client = self.client.clone();
+        future_to_promise(async move {
+            into_js_result(client.get_slot().try_send().await).map(JsValue::from)
+        })
+    }
+
+    /// Calls `getBalance` for the given base58-encoded account and resolves with its balance in
+    /// lamports as a `bigint`.
+    #[wasm_bindgen(js_name = getBalance)]
+    pub fn get_balance(&self, pubkey: String) -> js_sys::Promise {
+        let client = self.client.clone();
+        future_to_promise(async move {
+            let pubkey = Pubkey::from_str(&pubkey)
+                .map_err(|e| JsError::new(&format!("Invalid public key '{pubkey}': {e}")))?;
+            into_js_result(client.get_balance(pubkey).try_send().await).map(JsValue::from)
+        })
+    }
+
+    /// Calls `jsonRequest` with the given JSON-RPC request and resolves with the JSON-RPC response
+    /// as a string.
+    #[wasm_bindgen(js_name = jsonRequest)]
+    pub fn json_request(&self, json_request: String) -> js_sys::Promise {
+        let client = self.client.clone();
+        future_to_promise(async move {
+            let json_request = serde_json::from_str(&json_request)
+                .map_err(|e| JsError::new(&format!("Invalid JSON-RPC request: {e}")))?;
+            let request = client
+                .try_json_request(json_request)
+                .map_err(|e| JsError::new(&e.to_string()))?;
+            into_js_result(request.try_send().await).map(JsValue::from)
+        })
+    }
+}
+
+fn into_js_result<T: Debug>(result: Result<MultiRpcResult<T>, IcError>) -> Result<T, JsValue> {
+    match result {
+        Ok(MultiRpcResult::Consistent(Ok(value))) => Ok(value),
+        Ok(MultiRpcResult::Consistent(Err(e))) => Err(JsError::new(&e.to_string()).into()),
+        Ok(MultiRpcResult::Inconsistent(results)) => Err(JsError::new(&format!(
+            "Providers returned inconsistent results: {results:?}"
+        ))
+        .into()),
+        Err(e) => Err(JsError::new(&format!("Call to the SOL RPC canister failed: {e}")).into()),
+    }
+}
@@ -0,0 +1,469 @@
+//! Test utilities to integration-test canisters against a local SOL RPC canister with mocked
+//! HTTPS outcalls, running in [PocketIC](https://github.com/dfinity/pocketic).
+//!
+//! The Wasm modules to install are read from the files pointed to by the
+//! [`SOL_RPC_CANISTER_WASM_PATH`] and [`WALLET_WASM_PATH`] environment variables, which must both
+//! be set: [`Setup::new`] panics with instructions on how to obtain the missing Wasm module
+//! otherwise. The SOL RPC canister Wasm module can be downloaded from the
+//! [releases](https://github.com/dfinity/sol-rpc-canister/releases) and the cycles wallet from the
+//! [DFINITY SDK](https://github.com/dfinity/sdk/tree/main/src/distributed). Alternatively, use
+//! [`Setup::with_pocket_ic_and_wasms`] to provide the Wasm modules directly.
+//!
+//! ```shell
+//! export SOL_RPC_CANISTER_WASM_PATH=/path/to/sol_rpc_canister.wasm.gz
+//! export WALLET_WASM_PATH=/path/to/wallet.wasm.gz
+//! ```
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use sol_rpc_test_utils::{
+//!     mock_sequential_json_rpc_responses, JsonRpcRequestMatcher, JsonRpcResponse, Setup,
+//!     SolRpcTestClient,
+//! };
+//! use serde_json::json;
+//! use sol_rpc_types::{RpcSources, SolanaCluster};
+//!
+//! # async fn run() {
+//! let setup = Setup::new().await.with_mock_api_keys().await;
+//!
+//! // The SOL RPC canister queries 3 providers by default, each with a different JSON-RPC ID.
+//! let mocks = mock_sequential_json_rpc_responses(
+//!     || JsonRpcRequestMatcher::with_method("getSlot"),
+//!     std::iter::repeat_with(|| {
+//!         JsonRpcResponse::from(json!({"jsonrpc": "2.0", "id": 0, "result": 1234}))
+//!     }),
+//!     0..3,
+//! );
+//! let client = setup
+//!     .client_without_mocks()
+//!     .with_http_mocks(mocks)
+//!     .with_rpc_sources(RpcSources::Default(SolanaCluster::Mainnet))
+//!     .build();
+//!
+//! let slot = client.get_slot().send().await.expect_consistent();
+//!
+//! assert_eq!(slot, Ok(1220));
+//! setup.drop().await;
+//! # }
+//! ```
+
+#![forbid(unsafe_code)]
+#![forbid(missing_docs)]
+
+use candid::{encode_one, Encode, Principal};
+use canlog::{Log, LogEntry, LogPriorityLevels};
+use ic_canister_runtime::{CyclesWalletRuntime, Runtime};
+use ic_http_types::{HttpRequest, HttpResponse};
+use ic_management_canister_types::{CanisterId, CanisterSettings};
+use ic_metrics_assert::{MetricsAssert, PocketIcAsyncHttpQuery};
+use num_traits::ToPrimitive;
+use pocket_ic::{nonblocking::PocketIc, PocketIcBuilder};
+use serde::de::DeserializeOwned;
+use sol_rpc_client::{ClientBuilder, SolRpcClient};
+use sol_rpc_types::{
+    CyclesReport, InstallArgs, ResponseMetadata, RpcAccess, RpcSource, StateValidationIssue,
+    SupportedRpcProviderId,
+};
+use std::{env::var, time::Duration};
+
+pub use sol_rpc_client::pocket_ic::{
+    CanisterHttpReject, CanisterHttpReply, JsonRpcRequestMatcher, JsonRpcResponse,
+    MockHttpOutcalls, MockHttpOutcallsBuilder, PocketIcRuntime,
+};
+
+/// Principal of the cycles wallet used to make calls to the SOL RPC canister.
+pub const DEFAULT_CALLER_TEST_ID: Principal =
+    Principal::from_slice(&[0x0, 0x0, 0x0, 0x0, 0x0, 0x31, 0x1, 0x8, 0x1, 0x1]);
+/// Principal of the controller of the SOL RPC canister and of the cycles wallet.
+pub const DEFAULT_CONTROLLER_TEST_ID: Principal = Principal::from_slice(&[0x9d, 0xf7, 0x02]);
+/// Environment variable containing the path to the SOL RPC canister Wasm module.
+pub const SOL_RPC_CANISTER_WASM_PATH: &str = "SOL_RPC_CANISTER_WASM_PATH";
+/// Environment variable containing the path to the cycles wallet Wasm module.
+pub const WALLET_WASM_PATH: &str = "WALLET_WASM_PATH";
+/// API key set for all providers requiring one by [`Setup::with_mock_api_keys`].
+pub const MOCK_API_KEY: &str = "mock-api-key";
+
+/// A PocketIC instance with a SOL RPC canister and a cycles wallet to call it.
+pub struct Setup {
+    env: PocketIc,
+    controller: Principal,
+    sol_rpc_canister_id: CanisterId,
+    sol_rpc_wasm: Vec<u8>,
+    wallet_canister_id: CanisterId,
+}
+
+impl Setup {
+    /// Install the SOL RPC canister with the default [`InstallArgs`].
+    pub async fn new() -> Self {
+        Self::with_args(InstallArgs::default()).await
+    }
+
+    /// Install the SOL RPC canister with the given [`InstallArgs`] in a new PocketIC instance
+    /// containing a fiduciary subnet.
+    pub async fn with_args(args: InstallArgs) -> Self {
+        Self::with_pocket_ic_and_args(
+            PocketIcBuilder::new()
+                .with_fiduciary_subnet()
+                .build_async()
+                .await,
+            args,
+        )
+        .await
+    }
+
+    /// Install the SOL RPC canister with the given [`InstallArgs`] in the given PocketIC instance.
+    pub async fn with_pocket_ic_and_args(env: PocketIc, args: InstallArgs) -> Self {
+        Self::with_pocket_ic_and_wasms(
+            env,
+            load_wasm(SOL_RPC_CANISTER_WASM_PATH),
+            load_wasm(WALLET_WASM_PATH),
+            args,
+        )
+        .await
+    }
+
+    /// Install the given SOL RPC canister and cycles wallet Wasm modules in the given PocketIC
+    /// instance, instead of reading them from the files pointed to by the environment variables.
+    pub async fn with_pocket_ic_and_wasms(
+        env: PocketIc,
+        sol_rpc_wasm: Vec<u8>,
+        wallet_wasm: Vec<u8>,
+        args: InstallArgs,
+    ) -> Self {
+        let controller = DEFAULT_CONTROLLER_TEST_ID;
+        let wallet = DEFAULT_CALLER_TEST_ID;
+
+        let sol_rpc_canister_id = env
+            .create_canister_with_settings(
+                None,
+                Some(CanisterSettings {
+                    controllers: Some(vec![controller]),
+                    ..CanisterSettings::default()
+                }),
+            )
+            .await;
+        env.add_cycles(sol_rpc_canister_id, u64::MAX as u128).await;
+        env.install_canister(
+            sol_rpc_canister_id,
+            sol_rpc_wasm.clone(),
+            Encode!(&args).unwrap(),
+            Some(controller),
+        )
+        .await;
+
+        let wallet_canister_id = env
+            .create_canister_with_id(
+                None,
+                Some(CanisterSettings {
+                    controllers: Some(vec![controller]),
+                    ..CanisterSettings::default()
+                }),
+                wallet,
+            )
+            .await
+            .unwrap();
+        env.add_cycles(wallet_canister_id, u64::MAX as u128).await;
+        env.install_canister(wallet_canister_id, wallet_wasm, vec![], Some(controller))
+            .await;
+
+        Self {
+            env,
+            controller,
+            sol_rpc_canister_id,
+            sol_rpc_wasm,
+            wallet_canister_id,
+        }
+    }
+
+    /// Upgrade the SOL RPC canister with the same Wasm module and the given [`InstallArgs`].
+    pub async fn upgrade_canister(&self, args: InstallArgs) {
+        self.env.tick().await;
+        // Avoid `CanisterInstallCodeRateLimited` error
+        self.env.advance_time(Duration::from_secs(600)).await;
+        self.env.tick().await;
+        self.env
+            .upgrade_canister(
+                self.sol_rpc_canister_id,
+                self.sol_rpc_wasm.clone(),
+                Encode!(&args).unwrap(),
+                Some(self.controller),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("Upgrade canister failed: {:?}", err));
+    }
+
+    /// Cycles balance of the SOL RPC canister.
+    pub async fn get_canister_cycle_balance(&self) -> u128 {
+        self.env.cycle_balance(self.sol_rpc_canister_id).await
+    }
+
+    /// Set [`MOCK_API_KEY`] as API key for all supported providers requiring one.
+    pub async fn with_mock_api_keys(self) -> Self {
+        let client = self.client(MockHttpOutcalls::never()).build();
+        let providers = client.get_providers().await;
+        let mut api_keys = Vec::new();
+        for (id, provider) in providers {
+            match provider.access {
+                RpcAccess::Authenticated { .. } => {
+                    api_keys.push((id, Some(MOCK_API_KEY.to_string())));
+                }
+                RpcAccess::Unauthenticated { .. } => {}
+            }
+        }
+        self.env
+            .update_call(
+                self.sol_rpc_canister_id,
+                self.controller,
+                "updateApiKeys",
+                encode_one(api_keys).expect("Failed to encode arguments."),
+            )
+            .await
+            .expect("BUG: Failed to call updateApiKeys");
+        self
+    }
+
+    /// Verify that the API key of the given provider is the expected one.
+    ///
+    /// Panics if this is not the case.
+    pub async fn verify_api_key(&self, api_key: (SupportedRpcProviderId, Option<String>)) {
+        let runtime = self.new_pocket_ic_runtime();
+        runtime
+            .query_call(self.sol_rpc_canister_id, "verifyApiKey", (api_key,))
+            .await
+            .unwrap()
+    }
+
+    /// Call `addApiKeyPrincipal` on the SOL RPC canister as its controller.
+    pub async fn add_api_key_principal(&self, principal: Principal) {
+        self.env
+            .update_call(
+                self.sol_rpc_canister_id,
+                self.controller,
+                "addApiKeyPrincipal",
+                encode_one(principal).expect("Failed to encode arguments."),
+            )
+            .await
+            .expect("BUG: Failed to call addApiKeyPrincipal");
+    }
+
+    /// Call `removeApiKeyPrincipal` on the SOL RPC canister as its controller.
+    pub async fn remove_api_key_principal(&self, principal: Principal) {
+        self.env
+            .update_call(
+                self.sol_rpc_canister_id,
+                self.controller,
+                "removeApiKeyPrincipal",
+                encode_one(principal).expect("Failed to encode arguments."),
+            )
+            .await
+            .expect("BUG: Failed to call removeApiKeyPrincipal");
+    }
+
+    /// Call `getApiKeyPrincipals` on the SOL RPC canister as its controller.
+    pub async fn get_api_key_principals(&self) -> Vec<Principal> {
+        let runtime = self.new_pocket_ic_runtime();
+        runtime
+            .query_call(self.sol_rpc_canister_id, "getApiKeyPrincipals", ())
+            .await
+            .unwrap()
+    }
+
+    /// Call `validateState` on the SOL RPC canister as its controller.
+    pub async fn validate_state(&self) -> Vec<StateValidationIssue> {
+        let runtime = self.new_pocket_ic_runtime();
+        runtime
+            .query_call(self.sol_rpc_canister_id, "validateState", ())
+            .await
+            .unwrap()
+    }
+
+    /// Call `getCyclesReport` on the SOL RPC canister for the cycles wallet, which makes the
+    /// calls of the [`Setup::client`].
+    pub async fn get_cycles_report(&self) -> Option<CyclesReport> {
+        let runtime = self.new_pocket_ic_runtime();
+        runtime
+            .query_call(
+                self.sol_rpc_canister_id,
+                "getCyclesReport",
+                (Some(self.wallet_canister_id),),
+            )
+            .await
+            .unwrap()
+    }
+
+    /// Call `getResponseMetadata` on the SOL RPC canister for the cycles wallet, which makes the
+    /// calls of the [`Setup::client`].
+    pub async fn get_response_metadata(&self) -> Option<Vec<(RpcSource, ResponseMetadata)>> {
+        let runtime = self.new_pocket_ic_runtime();
+        runtime
+            .query_call(
+                self.sol_rpc_canister_id,
+                "getResponseMetadata",
+                (Some(self.wallet_canister_id),),
+            )
+            .await
+            .unwrap()
+    }
+
+    /// Retrieve the SOL RPC canister logs with the given priority, e.g. `INFO`.
+    pub async fn retrieve_logs<Priority>(&self, priority: &str) -> Vec<LogEntry<Priority>>
+    where
+        Priority: LogPriorityLevels + DeserializeOwned,
+    {
+        let request = HttpRequest {
+            method: "POST".to_string(),
+            url: format!("/logs?priority={priority}"),
+            headers: vec![],
+            body: serde_bytes::ByteBuf::new(),
+        };
+        let runtime = self.new_pocket_ic_runtime();
+        let response: HttpResponse = runtime
+            .query_call(self.sol_rpc_canister_id, "http_request", (request,))
+            .await
+            .unwrap();
+        serde_json::from_slice::<Log<Priority>>(&response.body)
+            .expect("failed to parse SOL RPC canister log")
+            .entries
+    }
+
+    /// Client making calls to the SOL RPC canister through the cycles wallet, where the HTTPS
+    /// outcalls are answered by the given mocks.
+    pub fn client(
+        &self,
+        mocks: impl Into<MockHttpOutcalls>,
+    ) -> ClientBuilder<CyclesWalletRuntime<PocketIcRuntime<'_>>> {
+        self.client_without_mocks().with_http_mocks(mocks)
+    }
+
+    /// Client making calls to the SOL RPC canister through the cycles wallet, without mocking
+    /// any HTTPS outcall.
+    pub fn client_without_mocks(&self) -> ClientBuilder<CyclesWalletRuntime<PocketIcRuntime<'_>>> {
+        SolRpcClient::builder(self.new_pocket_ic_runtime(), self.sol_rpc_canister_id)
+    }
+
+    /// Cycles balance of the SOL RPC canister, as reported by the canister status.
+    pub async fn sol_rpc_canister_cycles_balance(&self) -> u128 {
+        self.env
+            .canister_status(self.sol_rpc_canister_id, Some(self.controller))
+            .await
+            .unwrap()
+            .cycles
+            .0
+            .to_u128()
+            .unwrap()
+    }
+
+    fn new_pocket_ic_runtime(&self) -> CyclesWalletRuntime<PocketIcRuntime<'_>> {
+        CyclesWalletRuntime::new(
+            PocketIcRuntime::new(&self.env, self.controller),
+            self.wallet_canister_id,
+        )
+    }
+
+    /// Delete the PocketIC instance.
+    pub async fn drop(self) {
+        self.env.drop().await
+    }
+
+    /// Controller of the SOL RPC canister.
+    pub fn controller(&self) -> Principal {
+        self.controller
+    }
+
+    /// ID of the SOL RPC canister.
+    pub fn sol_rpc_canister_id(&self) -> CanisterId {
+        self.sol_rpc_canister_id
+    }
+
+    /// Assert the metrics exported by the SOL RPC canister.
+    pub async fn check_metrics(self) -> MetricsAssert<Self> {
+        MetricsAssert::from_async_http_query(self).await
+    }
+}
+
+impl PocketIcAsyncHttpQuery for Setup {
+    fn get_pocket_ic(&self) -> &PocketIc {
+        &self.env
+    }
+
+    fn get_canister_id(&self) -> CanisterId {
+        self.sol_rpc_canister_id
+    }
+}
+
+impl AsRef<PocketIc> for Setup {
+    fn as_ref(&self) -> &PocketIc {
+        &self.env
+    }
+}
+
+/// Extension trait for a [`ClientBuilder`] running in PocketIC to mock the HTTPS outcalls made
+/// by the SOL RPC canister.
+pub trait SolRpcTestClient {
+    /// Answer the HTTPS outcalls made by the SOL RPC canister with the given mocks.
+    fn with_http_mocks(self, mocks: impl Into<MockHttpOutcalls>) -> Self;
+}
+
+impl SolRpcTestClient for ClientBuilder<CyclesWalletRuntime<PocketIcRuntime<'_>>> {
+    fn with_http_mocks(self, mocks: impl Into<MockHttpOutcalls>) -> Self {
+        self.with_runtime(|wallet_runtime| {
+            wallet_runtime
+                .with_runtime(|pocket_ic_runtime| pocket_ic_runtime.with_http_mocks(mocks.into()))
+        })
+    }
+}
+
+/// Mock the responses to the given JSON-RPC request, one for each of the given JSON-RPC IDs.
+///
+/// The SOL RPC canister assigns sequential IDs to the JSON-RPC requests it makes, so that
+/// a canister call querying `n` providers results in `n` requests with consecutive IDs. The
+/// `i`-th response is returned to the request with the `i`-th ID, and the ID of the response
+/// is set accordingly.
+pub fn mock_sequential_json_rpc_responses(
+    request: impl Fn() -> JsonRpcRequestMatcher,
+    responses: impl IntoIterator<Item = JsonRpcResponse>,
+    ids: impl IntoIterator<Item = u64>,
+) -> MockHttpOutcallsBuilder {
+    let mut mocks = MockHttpOutcallsBuilder::new();
+    for (response, id) in responses.into_iter().zip(ids) {
+        mocks = mocks
+            .given(request().with_id(id))
+            .respond_with(response.with_id(id))
+    }
+    mocks
+}
+
+fn load_wasm(env_var: &str) -> Vec<u8> {
+    let path = var(env_var).unwrap_or_else(|_| {
+        panic!(
+            "The environment variable {env_var} is not set. It must contain the path to the Wasm \
+            module to install: {}",
+            wasm_source(env_var)
+        )
+    });
+    std::fs::read(&path).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read the Wasm module at {path} given by the environment variable \
+            {env_var}: {e}. The file must exist and be readable: {}",
+            wasm_source(env_var)
+        )
+    })
+}
+
+fn wasm_source(env_var: &str) -> &'static str {
+    match env_var {
+        SOL_RPC_CANISTER_WASM_PATH => {
+            "download `sol_rpc_canister.wasm.gz` from \
+            https://github.com/dfinity/sol-rpc-canister/releases/latest or, from a checkout of the \
+            repository, build it with \
+            `cargo build -p sol_rpc_canister --target wasm32-unknown-unknown --release` and use \
+            `target/wasm32-unknown-unknown/release/sol_rpc_canister.wasm`"
+        }
+        WALLET_WASM_PATH => {
+            "download `wallet.wasm.gz` from \
+            https://github.com/dfinity/sdk/raw/0a82e042adec6f24ba53665312713923bf276a34/src/distributed/wallet.wasm.gz"
+        }
+        _ => "see the documentation of the `sol_rpc_test_utils` crate",
+    }
+}
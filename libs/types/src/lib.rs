@@ -1,9 +1,16 @@
 //! Candid types used by the candid interface of the SOL RPC canister.
 //!
+//! # Features
+//!
+//! * `solana-compat`: conversions from and into the types of the Solana SDK crates, e.g.
+//!   `solana_pubkey::Pubkey` or `solana_transaction_status_client_types::UiConfirmedBlock`.
+//!   Disabled by default, so that consumers only needing the Candid types do not depend on the
+//!   Solana SDK.
+//!
 //! ⚠️ **Build Requirements**
 //!
-//! If you are using the `sol_rpc_types` crate inside a canister, make sure to follow the steps
-//! outlined [here](https://github.com/dfinity/sol-rpc-canister/blob/main/libs/client/README.md#build-requirements)
+//! If you are using the `sol_rpc_types` crate with the `solana-compat` feature inside a canister,
+//! make sure to follow the steps outlined [here](https://github.com/dfinity/sol-rpc-canister/blob/main/libs/client/README.md#build-requirements)
 //! to ensure your code compiles.
 
 #![forbid(unsafe_code)]
@@ -20,36 +27,77 @@ mod solana;
 use candid::{CandidType, Deserialize};
 use derive_more::Into;
 
-pub use lifecycle::{InstallArgs, Mode, NumSubnetNodes};
-pub use response::MultiRpcResult;
+pub use lifecycle::{
+    AllowedCallers, CachedSlot, Capabilities, CyclesReport, DepositCyclesArgs, DepositCyclesError,
+    InstallArgs, IpVersion, LogDrain, LogDrainEntry, LogFormat, LogPriority, LogRateLimit,
+    LogTarget, LogThrottle, Mode, NumSubnetNodes, OutcallConcurrency, PricingPolicy,
+    ProviderEndpoint, ProviderQuota, RequestIdFormat, RequestIdStrategy, ResponseCompression,
+    RetryPolicy, SampledVersion, SlotTicker, StateValidationIssue, UpgradeReadiness,
+    VersionSampler,
+};
+pub use response::{
+    AccountChange, ClusterOutage, CustomResolver, HttpRequestPreview, MultiRpcResult,
+    ProviderHealth, ProviderHealthReport, ProviderScore, ProviderScorecard, RawHttpResponse,
+    ResolutionError, ResolutionPolicy, ResponseMetadata, ScorecardPeriod, ShadowProviderStats,
+    WithContext,
+};
 pub use rpc_client::{
-    ConsensusStrategy, GetRecentPrioritizationFeesRpcConfig, GetSlotRpcConfig, HttpHeader,
-    HttpOutcallError, JsonRpcError, LegacyRejectionCode, NonZeroU8, OverrideProvider,
-    ProviderError, RegexString, RegexSubstitution, RoundingError, RpcAccess, RpcAuth, RpcConfig,
-    RpcEndpoint, RpcError, RpcResult, RpcSource, RpcSources, SolanaCluster, SupportedRpcProvider,
-    SupportedRpcProviderId,
+    ConsensusStrategy, GetBlockHeightRpcConfig, GetEpochInfoRpcConfig,
+    GetRecentPrioritizationFeesRpcConfig, GetSlotRpcConfig, GetSupplyRpcConfig,
+    GetTransactionCountRpcConfig, HttpHeader, HttpOutcallError, JsonRequestRpcConfig, JsonRpcError,
+    LegacyRejectionCode, NonZeroU8, OutcallShedReason, OverrideProvider, ProviderError,
+    RawHttpRequestParams, RegexString, RegexSubstitution, RequestPriority, RoundingError,
+    RpcAccess, RpcAuth, RpcConfig, RpcEndpoint, RpcEndpointApiKey, RpcEndpointAuth, RpcError,
+    RpcResult, RpcSource, RpcSources, SolanaCluster, SupportedRpcProvider, SupportedRpcProviderId,
+    WithContextRpcConfig,
 };
 use serde::{Serialize, Serializer};
+#[cfg(feature = "solana-compat")]
+pub use solana::transaction::ConfirmedTransactionWithStatusMeta;
 pub use solana::{
-    account::{AccountData, AccountEncoding, AccountInfo, ParsedAccount},
+    account::{AccountData, AccountEncoding, AccountInfo, KeyedAccount, ParsedAccount},
     request::{
-        CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetAccountInfoParams, GetBalanceParams,
-        GetBlockCommitmentLevel, GetBlockParams, GetRecentPrioritizationFeesParams,
-        GetSignatureStatusesParams, GetSignaturesForAddressLimit, GetSignaturesForAddressParams,
-        GetSlotParams, GetTokenAccountBalanceParams, GetTransactionEncoding, GetTransactionParams,
-        SendTransactionEncoding, SendTransactionParams, TransactionDetails,
+        CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetAccountInfoParams,
+        GetAccountInfoParamsBuilder, GetAccountSnapshotParams, GetAccountSnapshotParamsBuilder,
+        GetBalanceParams, GetBalanceParamsBuilder, GetBlockCommitmentLevel, GetBlockEncoding,
+        GetBlockHeightParams, GetBlockHeightParamsBuilder, GetBlockParams, GetBlockParamsBuilder,
+        GetBlocksLimit, GetBlocksParams, GetBlocksParamsBuilder, GetBlocksWithLimitParams,
+        GetBlocksWithLimitParamsBuilder, GetClusterNodesLimit, GetClusterNodesParams,
+        GetClusterNodesParamsBuilder, GetEpochInfoParams, GetEpochInfoParamsBuilder,
+        GetFeeForMessageParams, GetFeeForMessageParamsBuilder, GetLeaderScheduleParams,
+        GetLeaderScheduleParamsBuilder, GetMinimumBalanceForRentExemptionParams,
+        GetMinimumBalanceForRentExemptionParamsBuilder, GetRecentPerformanceSamplesLimit,
+        GetRecentPerformanceSamplesParams, GetRecentPerformanceSamplesParamsBuilder,
+        GetRecentPrioritizationFeesParams, GetSignatureStatusesParams,
+        GetSignatureStatusesParamsBuilder, GetSignaturesForAddressLimit,
+        GetSignaturesForAddressParams, GetSignaturesForAddressParamsBuilder, GetSlotLeadersLimit,
+        GetSlotLeadersParams, GetSlotLeadersParamsBuilder, GetSlotParams, GetSlotParamsBuilder,
+        GetSupplyParams, GetSupplyParamsBuilder, GetTokenAccountBalanceParams,
+        GetTokenAccountBalanceParamsBuilder, GetTokenAccountsByDelegateParams,
+        GetTokenAccountsByDelegateParamsBuilder, GetTokenAccountsByOwnerLimit,
+        GetTokenAccountsByOwnerParams, GetTokenAccountsByOwnerParamsBuilder,
+        GetTransactionCountParams, GetTransactionCountParamsBuilder, GetTransactionEncoding,
+        GetTransactionParams, GetTransactionParamsBuilder, HasAccountChangedParams,
+        HasAccountChangedParamsBuilder, SendTransactionEncoding, SendTransactionParams,
+        SendTransactionParamsBuilder, TokenAccountsFilter, TransactionDetails,
     },
     transaction::{
         error::{InstructionError, TransactionError},
-        instruction::{CompiledInstruction, InnerInstructions, Instruction},
+        instruction::{
+            CompiledInstruction, InnerInstructions, Instruction, ParsedInstruction,
+            PartiallyDecodedInstruction,
+        },
         reward::{Reward, RewardType},
-        ConfirmedTransactionStatusWithSignature, EncodedConfirmedTransactionWithStatusMeta,
-        EncodedTransaction, EncodedTransactionWithStatusMeta, LoadedAddresses, TokenAmount,
+        AddressTableLookup, ConfirmedTransactionStatusWithSignature,
+        EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
+        EncodedTransactionWithStatusMeta, LoadedAddresses, ParsedAccountKey,
+        ParsedAccountKeySource, ParsedMessage, ParsedTransaction, TokenAmount,
         TransactionBinaryEncoding, TransactionConfirmationStatus, TransactionReturnData,
         TransactionStatus, TransactionStatusMeta, TransactionTokenBalance, TransactionVersion,
     },
-    ConfirmedBlock, Hash, Lamport, MicroLamport, PrioritizationFee, Pubkey, Signature, Slot,
-    Timestamp,
+    AccountSnapshot, ClusterNode, ConfirmedBlock, EpochInfo, EpochSchedule, Hash, Lamport,
+    MicroLamport, ParseHashError, ParsePubkeyError, ParseSignatureError, PerformanceSample,
+    PrioritizationFee, Pubkey, Signature, Slot, SnapshotSlotInfo, SolanaVersion, Supply, Timestamp,
 };
 
 /// A vector with a maximum capacity.
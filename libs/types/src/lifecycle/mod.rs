@@ -1,4 +1,7 @@
-use crate::OverrideProvider;
+use crate::{
+    CommitmentLevel, OverrideProvider, RpcAccess, RpcSources, Slot, SolanaCluster, SolanaVersion,
+    SupportedRpcProviderId,
+};
 use candid::{CandidType, Principal};
 use canlog::LogFilter;
 use serde::{Deserialize, Serialize};
@@ -19,6 +22,12 @@ pub struct InstallArgs {
     /// Default is `LogFilter::ShowAll`.
     #[serde(rename = "logFilter")]
     pub log_filter: Option<LogFilter>,
+    /// Format of the log entries printed to the canister logs, e.g. with `dfx canister logs`.
+    /// The log entries returned by the `/logs` endpoint are not affected.
+    /// If not specified, the existing setting is not modified.
+    /// Default is `LogFormat::Text`.
+    #[serde(rename = "logFormat")]
+    pub log_format: Option<LogFormat>,
     /// Number of subnet nodes.
     /// Default is `34` (i.e. the number of nodes in the fiduciary subnet).
     #[serde(rename = "numSubnetNodes")]
@@ -26,6 +35,415 @@ pub struct InstallArgs {
     /// Mode of operation.
     /// Default is `Mode::Normal`.
     pub mode: Option<Mode>,
+    /// Fallback chains for supported providers requiring an API key.
+    /// When no API key is set for a provider, the providers of its fallback chain are tried in
+    /// order, each with its API key if available or otherwise with its public URL, before
+    /// using the provider's own public URL.
+    /// Fallback providers must belong to the same Solana cluster as the provider they replace.
+    /// If not specified, the existing fallback chains are not modified.
+    #[serde(rename = "providerFallbacks")]
+    pub provider_fallbacks: Option<Vec<(SupportedRpcProviderId, Vec<SupportedRpcProviderId>)>>,
+    /// Principals allowed to call the endpoints making HTTPS outcalls.
+    /// If not specified, the existing allowlist is not modified.
+    /// Default is `AllowedCallers::Any`.
+    #[serde(rename = "allowedCallers")]
+    pub allowed_callers: Option<AllowedCallers>,
+    /// Methods that any principal may call, even if [`InstallArgs::allowed_callers`] restricts
+    /// the callers, e.g. `getSlotCyclesCost`.
+    /// If not specified, the existing exemptions are not modified.
+    /// Default is all the methods estimating the cycles cost of a request, i.e. whose name ends
+    /// with `CyclesCost`.
+    #[serde(rename = "allowedCallersExemptions")]
+    pub allowed_callers_exemptions: Option<Vec<String>>,
+    /// Whether RPC providers are asked to compress their responses.
+    /// If not specified, the existing setting is not modified.
+    /// Default is `ResponseCompression::Enabled` for a new installation, while a canister upgraded
+    /// from a version without this setting keeps `ResponseCompression::Disabled`.
+    #[serde(rename = "responseCompression")]
+    pub response_compression: Option<ResponseCompression>,
+    /// Request quotas for supported providers, e.g. to match the rate limits of the plans
+    /// associated with their API keys.
+    /// When the quota of a provider is exhausted, the provider is only selected by default
+    /// (i.e. with `RpcSources::Default`) if not enough other providers are available.
+    /// Providers explicitly requested by the caller are not affected.
+    /// Requests are only counted in heap memory, so that all quota windows start anew when the
+    /// canister is upgraded.
+    /// If not specified, the existing quotas are not modified.
+    #[serde(rename = "providerQuotas")]
+    pub provider_quotas: Option<Vec<(SupportedRpcProviderId, ProviderQuota)>>,
+    /// Policies to retry the HTTPS outcalls to supported providers failing with a transient
+    /// error, e.g. when the provider is rate limiting requests.
+    /// Requests to providers without a retry policy are only retried when the response was too
+    /// large, so that the estimated cycles cost of a request is enough to pay for it.
+    /// The number of retries is capped for each JSON-RPC method, e.g. `sendTransaction`
+    /// requests are retried at most once.
+    /// If not specified, the existing retry policies are not modified.
+    #[serde(rename = "retryPolicies")]
+    pub retry_policies: Option<Vec<(SupportedRpcProviderId, RetryPolicy)>>,
+    /// How JSON-RPC request IDs are assigned to retried HTTPS outcalls.
+    /// If not specified, the existing setting is not modified.
+    /// Default is `RequestIdStrategy::Unique`.
+    #[serde(rename = "requestIdStrategy")]
+    pub request_id_strategy: Option<RequestIdStrategy>,
+    /// Formats of the JSON-RPC request IDs sent to supported providers.
+    /// Providers without a format receive request IDs in the default format.
+    /// If not specified, the existing formats are not modified.
+    #[serde(rename = "requestIdFormats")]
+    pub request_id_formats: Option<Vec<(SupportedRpcProviderId, RequestIdFormat)>>,
+    /// Canister to which `INFO` log entries are periodically pushed.
+    /// If not specified, the existing setting is not modified.
+    /// Default is `LogDrain::Disabled`.
+    #[serde(rename = "logDrain")]
+    pub log_drain: Option<LogDrain>,
+    /// Limits the number of concurrent HTTPS outcalls, so that a burst of requests does not
+    /// exhaust the HTTPS outcall capacity of the subnet.
+    /// If not specified, the existing setting is not modified.
+    /// Default is `OutcallConcurrency::Unlimited`.
+    #[serde(rename = "outcallConcurrency")]
+    pub outcall_concurrency: Option<OutcallConcurrency>,
+    /// Preferred endpoints of supported providers, replacing their default endpoints, e.g. to
+    /// reach a provider through an IPv6 gateway when its default host is only reachable over
+    /// IPv4. Unlike `overrideProvider`, only the given providers are affected.
+    /// Passing an empty list restores the default endpoints of all providers.
+    /// If not specified, the existing preferred endpoints are not modified.
+    #[serde(rename = "providerEndpoints")]
+    pub provider_endpoints: Option<Vec<(SupportedRpcProviderId, ProviderEndpoint)>>,
+    /// Periodically fetches the current slot, which can then be cheaply retrieved with the
+    /// `getCachedSlot` query.
+    /// If not specified, the existing setting is not modified.
+    /// Default is `SlotTicker::Disabled`.
+    #[serde(rename = "slotTicker")]
+    pub slot_ticker: Option<SlotTicker>,
+    /// Policy to charge callers for the HTTPS outcalls made on their behalf.
+    /// If not specified, the existing policy is not modified.
+    /// Default is `PricingPolicy::default()`.
+    #[serde(rename = "pricingPolicy")]
+    pub pricing_policy: Option<PricingPolicy>,
+    /// Cycles ledger from which callers can deposit cycles with `depositCycles` to prepay for
+    /// their requests.
+    /// If not specified, the existing setting is not modified.
+    /// Default is the cycles ledger on the ICP mainnet, i.e. `um5iw-rqaaa-aaaaq-qaaba-cai`.
+    #[serde(rename = "cyclesLedgerId")]
+    pub cycles_ledger_id: Option<Principal>,
+    /// Limits on the log entries recorded for each log priority, e.g. so that `TRACE_HTTP` log
+    /// entries can stay enabled in production without swamping the log buffers.
+    /// Log priorities that are not listed are not limited.
+    /// If not specified, the existing limits are not modified.
+    #[serde(rename = "logThrottles")]
+    pub log_throttles: Option<Vec<(LogPriority, LogThrottle)>>,
+    /// Priorities of the log entries recorded by some modules of the canister, e.g. to mute the
+    /// `TRACE_HTTP` log entries of the `http` module while keeping the `DEBUG` log entries of the
+    /// `providers` module.
+    /// Modules that are not covered by any target record log entries of all priorities.
+    /// If not specified, the existing targets are not modified.
+    #[serde(rename = "logTargets")]
+    pub log_targets: Option<Vec<LogTarget>>,
+    /// Supported providers queried in shadow mode, e.g. to evaluate a provider before adding it
+    /// to the default set of providers.
+    /// A provider in shadow mode is sent the same requests as the providers of its cluster
+    /// queried by the callers, but its responses are never taken into account to reach
+    /// consensus. The HTTPS outcalls to providers in shadow mode are paid by the SOL RPC canister.
+    /// If not specified, the existing shadow providers are not modified.
+    #[serde(rename = "shadowProviders")]
+    pub shadow_providers: Option<Vec<SupportedRpcProviderId>>,
+    /// Periodically fetches the software version of the supported providers, which is then
+    /// reported by `getClusterHealth` and may be required to be at least a minimum version for
+    /// the providers to be selected by default.
+    /// If not specified, the existing setting is not modified.
+    /// Default is `VersionSampler::Disabled`.
+    #[serde(rename = "versionSampler")]
+    pub version_sampler: Option<VersionSampler>,
+}
+
+/// Policy to charge callers for the HTTPS outcalls made on their behalf, e.g. so that alternative
+/// deployments of the SOL RPC canister can set their own pricing.
+///
+/// Callers are charged the cost of each HTTPS outcall increased by `markup_percent` percent, plus
+/// `collateral_cycles_per_node` cycles per subnet node. HTTPS outcalls costing at most
+/// `free_tier_max_cycles` cycles are not charged to the caller and are paid by the SOL RPC
+/// canister instead. Requests with [`RequestPriority::Expedited`](crate::RequestPriority) are
+/// additionally charged `expedited_markup_percent` percent of the cost of each HTTPS outcall.
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct PricingPolicy {
+    /// Cycles per subnet node charged on top of the cost of each HTTPS outcall.
+    #[serde(rename = "collateralCyclesPerNode")]
+    pub collateral_cycles_per_node: u128,
+    /// Markup in percent applied to the cost of each HTTPS outcall. Must be at most
+    /// [`PricingPolicy::MAX_MARKUP_PERCENT`].
+    #[serde(rename = "markupPercent")]
+    pub markup_percent: u32,
+    /// HTTPS outcalls costing at most this amount of cycles are free for the caller.
+    /// If 0, all HTTPS outcalls are charged.
+    #[serde(rename = "freeTierMaxCycles")]
+    pub free_tier_max_cycles: u128,
+    /// Additional markup in percent applied to the cost of each HTTPS outcall of expedited
+    /// requests. Must be at most [`PricingPolicy::MAX_MARKUP_PERCENT`].
+    /// If not specified, [`PricingPolicy::DEFAULT_EXPEDITED_MARKUP_PERCENT`] is used.
+    #[serde(rename = "expeditedMarkupPercent")]
+    pub expedited_markup_percent: Option<u32>,
+}
+
+impl PricingPolicy {
+    /// Default amount of cycles per subnet node charged on top of the cost of each HTTPS outcall.
+    pub const DEFAULT_COLLATERAL_CYCLES_PER_NODE: u128 = 10_000_000;
+    /// Maximum markup in percent, i.e. callers are charged at most 11 times the cost of an
+    /// HTTPS outcall, excluding collateral.
+    pub const MAX_MARKUP_PERCENT: u32 = 1_000;
+    /// Default additional markup in percent for expedited requests, i.e. expedited requests
+    /// cost twice as much as normal ones, excluding collateral.
+    pub const DEFAULT_EXPEDITED_MARKUP_PERCENT: u32 = 100;
+
+    /// Additional markup in percent applied to the cost of each HTTPS outcall of expedited
+    /// requests.
+    pub fn expedited_markup_percent(&self) -> u32 {
+        self.expedited_markup_percent
+            .unwrap_or(Self::DEFAULT_EXPEDITED_MARKUP_PERCENT)
+    }
+}
+
+impl Default for PricingPolicy {
+    fn default() -> Self {
+        Self {
+            collateral_cycles_per_node: Self::DEFAULT_COLLATERAL_CYCLES_PER_NODE,
+            markup_percent: 0,
+            free_tier_max_cycles: 0,
+            expedited_markup_percent: None,
+        }
+    }
+}
+
+/// Arguments of the `depositCycles` endpoint, which withdraws cycles from the caller's account on
+/// the cycles ledger and adds them to the caller's prepaid balance on the SOL RPC canister.
+///
+/// The caller must have first approved the SOL RPC canister to spend at least `amount` plus the
+/// fee of the cycles ledger from that account, with `icrc2_approve`.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct DepositCyclesArgs {
+    /// Amount of cycles to add to the caller's prepaid balance.
+    pub amount: u128,
+    /// Subaccount of the caller's account on the cycles ledger from which the cycles are
+    /// withdrawn. If not specified, the default subaccount is used.
+    #[serde(rename = "fromSubaccount")]
+    pub from_subaccount: Option<[u8; 32]>,
+    /// Used by the cycles ledger to deduplicate withdrawals, in nanoseconds since the UNIX epoch.
+    #[serde(rename = "createdAtTime")]
+    pub created_at_time: Option<u64>,
+}
+
+impl DepositCyclesArgs {
+    /// Deposit the given amount of cycles from the default subaccount of the caller.
+    pub fn new(amount: u128) -> Self {
+        Self {
+            amount,
+            from_subaccount: None,
+            created_at_time: None,
+        }
+    }
+}
+
+/// Cycles attached to and consumed by the most recent call of a caller to an endpoint of the SOL
+/// RPC canister making HTTPS outcalls, as returned by the `getCyclesReport` endpoint.
+///
+/// Unlike the estimate returned by the `*CyclesCost` endpoints, the consumed cycles are the cycles
+/// that were actually charged to the caller, so that callers can adapt the amount of cycles they
+/// attach to their requests over time.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct CyclesReport {
+    /// Time at which the call completed, in nanoseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// Cycles attached to the call.
+    pub attached: u128,
+    /// Cycles taken from the attached cycles, i.e. not refunded to the caller.
+    pub consumed: u128,
+    /// Cycles taken from the caller's prepaid cycles, see `depositCycles`.
+    pub prepaid: u128,
+}
+
+impl CyclesReport {
+    /// Cycles refunded to the caller.
+    pub fn refunded(&self) -> u128 {
+        self.attached.saturating_sub(self.consumed)
+    }
+}
+
+/// Errors returned by the `depositCycles` endpoint.
+///
+/// If an error is returned, the caller's prepaid balance is unchanged.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum DepositCyclesError {
+    /// The anonymous principal cannot have a prepaid balance.
+    AnonymousCaller,
+    /// The balance of the caller's account on the cycles ledger is too low.
+    InsufficientFunds {
+        /// Balance of the caller's account on the cycles ledger.
+        balance: u128,
+    },
+    /// The SOL RPC canister is not approved to withdraw that many cycles from the caller's
+    /// account on the cycles ledger.
+    InsufficientAllowance {
+        /// Amount that the SOL RPC canister is approved to withdraw.
+        allowance: u128,
+    },
+    /// The cycles ledger rejected the withdrawal for another reason.
+    LedgerError {
+        /// Description of the error returned by the cycles ledger.
+        message: String,
+    },
+    /// The call to the cycles ledger failed.
+    CallFailed {
+        /// Description of the failure.
+        message: String,
+    },
+    /// The SOL RPC canister is being upgraded.
+    UpgradeInProgress,
+    /// It is unknown whether the cycles were withdrawn from the caller's account on the cycles
+    /// ledger, e.g. because the call timed out. The deposit should be retried with the same
+    /// arguments and the given creation time, so that the cycles are deposited at most once.
+    UnknownOutcome {
+        /// Creation time of the withdrawal on the cycles ledger.
+        created_at_time: u64,
+    },
+}
+
+/// Maximum number of requests that the SOL RPC canister should make to a provider within a
+/// sliding time window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct ProviderQuota {
+    /// Maximum number of requests within the window.
+    #[serde(rename = "maxRequests")]
+    pub max_requests: u32,
+    /// Duration of the sliding window in seconds. Must be greater than 0.
+    #[serde(rename = "windowSeconds")]
+    pub window_seconds: u32,
+}
+
+/// Capabilities of a SOL RPC canister deployment, as returned by its `getCapabilities` endpoint.
+///
+/// Clients may use the capabilities to degrade gracefully when talking to older deployments,
+/// e.g. by not calling endpoints that are not supported. Deployments that predate the
+/// `getCapabilities` endpoint reject the call.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct Capabilities {
+    /// Semantic version of the SOL RPC canister, e.g. `1.3.2`.
+    pub version: String,
+    /// Names of the public endpoints of the canister, e.g. `getBalance` or `getBalanceCyclesCost`.
+    pub endpoints: Vec<String>,
+    /// Maximum number of bytes of a response from an RPC provider, i.e. the maximum response
+    /// size of HTTPS outcalls.
+    #[serde(rename = "maxResponseBytes")]
+    pub max_response_bytes: u64,
+    /// Mode of operation of the canister.
+    pub mode: Mode,
+    /// Optional features that are enabled, see the `FEATURE_*` constants of [`Capabilities`].
+    ///
+    /// Features are identified by their name rather than by a variant, so that clients can
+    /// decode the capabilities of newer deployments supporting unknown features.
+    pub features: Vec<String>,
+}
+
+impl Capabilities {
+    /// RPC providers are asked to compress their responses, see [`ResponseCompression`].
+    pub const FEATURE_RESPONSE_COMPRESSION: &'static str = "responseCompression";
+    /// Account data can be returned in the `base64+zstd` encoding.
+    pub const FEATURE_ZSTD_ENCODING: &'static str = "zstdEncoding";
+    /// The number of concurrent HTTPS outcalls is limited, see [`OutcallConcurrency`].
+    pub const FEATURE_OUTCALL_CONCURRENCY_LIMIT: &'static str = "outcallConcurrencyLimit";
+    /// Only some principals may call the endpoints making HTTPS outcalls, see
+    /// [`AllowedCallers`].
+    pub const FEATURE_CALLER_ALLOWLIST: &'static str = "callerAllowlist";
+    /// `INFO` log entries are pushed to a log drain canister, see [`LogDrain`].
+    pub const FEATURE_LOG_DRAIN: &'static str = "logDrain";
+    /// The current slot is periodically fetched by the canister, see [`SlotTicker`].
+    pub const FEATURE_SLOT_TICKER: &'static str = "slotTicker";
+
+    /// Returns `true` if the canister has an endpoint with the given name.
+    pub fn supports_endpoint(&self, endpoint: &str) -> bool {
+        self.endpoints.iter().any(|e| e == endpoint)
+    }
+
+    /// Returns `true` if the given feature is enabled.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+}
+
+/// Preferred endpoint of a supported provider, used instead of the provider's default endpoint.
+///
+/// The access method must be of the same kind as the provider's default one, i.e. a provider
+/// requiring an API key must still require an API key, and its API key is used with the
+/// preferred endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct ProviderEndpoint {
+    /// The access method replacing the provider's default access method.
+    pub access: RpcAccess,
+    /// IP versions over which the endpoint's host is reachable.
+    #[serde(rename = "ipVersion")]
+    pub ip_version: IpVersion,
+}
+
+/// IP versions over which the host of an RPC endpoint is reachable.
+///
+/// HTTPS outcalls are made from the nodes of the subnet, some of which may only be able to reach
+/// IPv6 hosts, so that endpoints reachable over IPv6 should be preferred.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, CandidType, Deserialize, EnumIter, Serialize)]
+pub enum IpVersion {
+    /// The host is only reachable over IPv4.
+    Ipv4,
+    /// The host is only reachable over IPv6.
+    Ipv6,
+    /// The host is reachable over both IPv4 and IPv6.
+    DualStack,
+}
+
+/// Policy to retry the HTTPS outcalls to a provider that failed with a transient error.
+///
+/// Retries are sent after an exponential backoff and each retry is an additional HTTPS outcall
+/// paid with the cycles attached to the request. Since the estimated cycles cost of a request does
+/// not include retries, enough additional cycles must be attached for retries to happen.
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct RetryPolicy {
+    /// Maximum number of retries of a request after the initial attempt.
+    #[serde(rename = "maxRetries")]
+    pub max_retries: u8,
+    /// Whether to retry requests whose HTTPS outcall was rejected with a transient error
+    /// (i.e. with the `SysTransient` rejection code).
+    #[serde(rename = "retryOnTransientError")]
+    pub retry_on_transient_error: bool,
+    /// HTTP status codes of the responses to retry. Must be client or server error codes
+    /// (i.e. `4xx` or `5xx`).
+    /// Default is `429` (Too Many Requests) and `503` (Service Unavailable).
+    #[serde(rename = "retryOnHttpStatus")]
+    pub retry_on_http_status: Option<Vec<u16>>,
+    /// Delay in milliseconds before the first retry, which is doubled for each subsequent retry.
+    /// Default is 1 second.
+    #[serde(rename = "initialBackoffMs")]
+    pub initial_backoff_ms: Option<u64>,
+}
+
+/// Restricts which principals may call the SOL RPC canister endpoints that make HTTPS outcalls.
+///
+/// Controllers of the canister are always allowed. The endpoints estimating the cycles cost of a
+/// request are also restricted, unless they are exempted with
+/// [`InstallArgs::allowed_callers_exemptions`], which is the case by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum AllowedCallers {
+    /// Any principal may call the canister.
+    #[default]
+    Any,
+    /// Only the given principals (and the controllers) may call the canister.
+    Only(Vec<Principal>),
+}
+
+impl AllowedCallers {
+    /// Returns `true` if the given principal is allowed by this allowlist.
+    ///
+    /// This does not take the controllers of the canister into account.
+    pub fn is_allowed(&self, principal: &Principal) -> bool {
+        match self {
+            AllowedCallers::Any => true,
+            AllowedCallers::Only(principals) => principals.contains(principal),
+        }
+    }
 }
 
 /// Mode of operation
@@ -40,6 +458,349 @@ pub enum Mode {
     Demo,
 }
 
+/// Whether RPC providers are asked to compress their responses.
+///
+/// When enabled, HTTPS outcalls include an `Accept-Encoding: gzip, deflate` header and
+/// compressed responses are decompressed by the canister before being processed. Since the
+/// `max_response_bytes` limit applies to the response as sent by the provider, this allows
+/// large responses (e.g. for `getBlock`) to fit in a smaller limit, and therefore to be cheaper.
+/// Providers that do not support compression simply return uncompressed responses.
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, CandidType, Deserialize, EnumIter, Serialize,
+)]
+pub enum ResponseCompression {
+    /// Ask RPC providers to compress their responses with `gzip` or `deflate`.
+    #[default]
+    Enabled,
+    /// Do not ask RPC providers to compress their responses.
+    Disabled,
+}
+
+/// How JSON-RPC request IDs are assigned to the HTTPS outcalls of a request that is retried,
+/// e.g. because the response was too large or because the provider is rate limiting requests.
+///
+/// In all cases, each provider receives a request with a different ID and the ID of a response
+/// must match the ID of the corresponding request.
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, CandidType, Deserialize, EnumIter, Serialize,
+)]
+pub enum RequestIdStrategy {
+    /// Each HTTPS outcall, including retries, is sent with a new request ID.
+    #[default]
+    Unique,
+    /// Retries are sent with the same request ID as the initial HTTPS outcall to the same
+    /// provider, so that the request ID can be used by the provider as an idempotency key, e.g.
+    /// to avoid submitting the same transaction twice with `sendTransaction`.
+    ReuseOnRetry,
+}
+
+/// Format of the log entries printed to the canister logs.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum LogFormat {
+    /// Free-form lines consisting of the priority, the source location and the message of the
+    /// log entry, e.g. `INFO canister/src/main.rs:42 message`.
+    #[default]
+    Text,
+    /// One JSON object per line with the `timestamp` (in nanoseconds since the UNIX epoch),
+    /// `level`, `file`, `line`, `message` and `canister_id` of the log entry, so that the
+    /// canister logs can be reliably parsed by log aggregation tools.
+    Json,
+}
+
+/// Format of the JSON-RPC request IDs sent to a provider, e.g. for gateways that only accept
+/// numeric request IDs.
+///
+/// Whatever the format, the ID of a response must match the ID of the corresponding request.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    CandidType,
+    Deserialize,
+    EnumIter,
+    Serialize,
+)]
+pub enum RequestIdFormat {
+    /// Strings of constant size, e.g. `"00000000000000000001"`, so that the size and hence the
+    /// cost of a request does not depend on its ID.
+    #[default]
+    ConstantSizeString,
+    /// Sequential numbers, e.g. `1`.
+    Number,
+    /// Strings formatted as UUIDs, e.g. `"00000000-0000-4000-8000-000000000001"`.
+    ///
+    /// Since all replicas must send identical requests, the UUIDs are derived from a sequential
+    /// counter and are not random.
+    Uuid,
+}
+
+/// Canister to which the SOL RPC canister periodically pushes its `INFO` log entries, so that
+/// the logs of several SOL RPC canisters can be collected in a single place.
+///
+/// Log entries are pushed in batches of increasing [`LogDrainEntry::counter`] by calling the
+/// given method of the log drain canister with a single argument of type `vec LogDrainEntry`.
+/// Entries that could not be pushed, e.g. because the call was rejected, are pushed again with
+/// the next batch, as long as they are still in the log buffer of the SOL RPC canister.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum LogDrain {
+    /// Log entries are not pushed to any canister.
+    #[default]
+    Disabled,
+    /// Log entries are pushed to the given canister.
+    Enabled {
+        /// Principal of the log drain canister.
+        #[serde(rename = "canisterId")]
+        canister_id: Principal,
+        /// Name of the update method of the log drain canister receiving the log entries.
+        method: String,
+    },
+}
+
+/// Log entry pushed to a [`LogDrain`] canister.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct LogDrainEntry {
+    /// Time at which the entry was recorded, in nanoseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// Source file in which the entry was recorded.
+    pub file: String,
+    /// Line in the source file at which the entry was recorded.
+    pub line: u32,
+    /// Log message.
+    pub message: String,
+    /// Sequence number of the entry, which increases with every recorded entry.
+    pub counter: u64,
+}
+
+/// Priority of the log entries recorded by the SOL RPC canister.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, CandidType, Deserialize, Serialize,
+)]
+pub enum LogPriority {
+    /// `INFO` log entries.
+    #[serde(rename = "INFO")]
+    Info,
+    /// `DEBUG` log entries.
+    #[serde(rename = "DEBUG")]
+    Debug,
+    /// `TRACE_HTTP` log entries, recording every HTTPS outcall.
+    #[serde(rename = "TRACE_HTTP")]
+    TraceHttp,
+}
+
+/// Limits on the log entries recorded for a log priority.
+///
+/// Log entries are first sampled and the sampled entries are then rate limited. Log entries that
+/// are not recorded are counted in the `solrpc_suppressed_log_entries` metric.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct LogThrottle {
+    /// Only record 1 out of every `sample_rate` log entries. Must be greater than 0.
+    /// If not specified, all log entries are sampled.
+    #[serde(rename = "sampleRate")]
+    pub sample_rate: Option<u32>,
+    /// Rate limit of the sampled log entries.
+    /// If not specified, the sampled log entries are not rate limited.
+    #[serde(rename = "rateLimit")]
+    pub rate_limit: Option<LogRateLimit>,
+}
+
+/// Priorities of the log entries recorded by a module of the SOL RPC canister and its submodules.
+///
+/// Log entries are only recorded if their priority is allowed by the most specific target
+/// matching the module in which they are logged, if any, and by the [`LogFilter`].
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct LogTarget {
+    /// Path of the module relative to the root of the canister crate, e.g. `http` for the log
+    /// entries recorded in the `http` module and all its submodules, or `http::retry` for the
+    /// ones recorded in the `http::retry` module only. Must not be empty.
+    pub module: String,
+    /// Priorities of the log entries recorded for the module. All other log entries are dropped.
+    pub priorities: Vec<LogPriority>,
+}
+
+/// Token bucket limiting the rate at which log entries are recorded.
+///
+/// Recording a log entry consumes a token and log entries are dropped when the bucket is empty.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct LogRateLimit {
+    /// Maximum number of tokens in the bucket, i.e. the maximum number of log entries recorded
+    /// in a burst. Must be greater than 0.
+    #[serde(rename = "maxBurst")]
+    pub max_burst: u32,
+    /// Number of tokens added to the bucket every second, i.e. the sustained number of log
+    /// entries recorded per second. Must be greater than 0.
+    #[serde(rename = "tokensPerSecond")]
+    pub tokens_per_second: u32,
+}
+
+/// Periodic fetching of the current slot by the SOL RPC canister itself.
+///
+/// When enabled, the canister calls `getSlot` on a timer and caches the result, so that consumers
+/// only needing an approximately current slot can retrieve it with the `getCachedSlot` query
+/// instead of paying for HTTPS outcalls. The HTTPS outcalls made by the timer are paid with the
+/// cycles of the SOL RPC canister.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum SlotTicker {
+    /// The current slot is not fetched periodically.
+    #[default]
+    Disabled,
+    /// The current slot is fetched periodically.
+    Enabled {
+        /// Interval in seconds between two consecutive fetches. Must be greater than 0.
+        #[serde(rename = "intervalSeconds")]
+        interval_seconds: u32,
+        /// Providers to fetch the current slot from. Their responses are aggregated as for the
+        /// `getSlot` endpoint with the default configuration.
+        sources: RpcSources,
+        /// Commitment level of the fetched slot.
+        /// If not specified, the default commitment level of the providers is used.
+        commitment: Option<CommitmentLevel>,
+    },
+}
+
+/// Slot fetched by the [`SlotTicker`] of the SOL RPC canister.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct CachedSlot {
+    /// The latest slot fetched from the providers. Slots are rounded as for the `getSlot`
+    /// endpoint with the default configuration.
+    pub slot: Slot,
+    /// Commitment level of the slot, as configured in [`SlotTicker::Enabled::commitment`].
+    pub commitment: Option<CommitmentLevel>,
+    /// Time at which the slot was fetched, in nanoseconds since the UNIX epoch.
+    pub timestamp: u64,
+}
+
+/// Periodic fetching of the software version of the supported providers by the SOL RPC canister
+/// itself.
+///
+/// Providers of the same cluster may run different versions of `solana-core`, e.g. during a
+/// cluster upgrade, which may change the fields of their responses and prevent them from reaching
+/// consensus. When enabled, the canister calls `getVersion` on each supported provider of the
+/// given clusters on a timer and records the result. The HTTPS outcalls made by the timer are
+/// paid with the cycles of the SOL RPC canister.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum VersionSampler {
+    /// The version of the providers is not fetched periodically.
+    #[default]
+    Disabled,
+    /// The version of the providers is fetched periodically.
+    Enabled {
+        /// Interval in seconds between two consecutive fetches. Must be greater than 0.
+        #[serde(rename = "intervalSeconds")]
+        interval_seconds: u32,
+        /// Clusters whose supported providers are queried.
+        clusters: Vec<SolanaCluster>,
+        /// Minimum version of `solana-core`, e.g. `2.3.0`, that a provider must run to be
+        /// selected by default (i.e. with `RpcSources::Default`). A provider running an older
+        /// version is only selected if not enough other providers are available, as for a
+        /// provider whose request quota is exhausted. Providers whose version is not known yet
+        /// are not affected.
+        /// If not specified, the version of the providers does not affect their selection.
+        #[serde(rename = "minVersion")]
+        min_version: Option<String>,
+    },
+}
+
+/// Version of a supported provider fetched by the [`VersionSampler`] of the SOL RPC canister.
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct SampledVersion {
+    /// The result of the most recent successful Solana `getVersion` RPC method call.
+    pub version: SolanaVersion,
+    /// Time at which the version was fetched, in nanoseconds since the UNIX epoch.
+    pub timestamp: u64,
+}
+
+/// A problem with the state of the SOL RPC canister, as reported by the `validateState` query.
+///
+/// Such problems do not prevent the SOL RPC canister from being upgraded, but may cause some
+/// requests to fail or some settings to be silently ignored, and should be fixed by upgrading
+/// the SOL RPC canister with the corresponding [`InstallArgs`].
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum StateValidationIssue {
+    /// An API key is stored for a provider that is no longer supported.
+    OrphanApiKey {
+        /// The provider that is no longer supported.
+        provider: SupportedRpcProviderId,
+    },
+    /// A setting refers to a provider that is no longer supported.
+    OrphanProviderSetting {
+        /// The provider that is no longer supported.
+        provider: SupportedRpcProviderId,
+        /// The name of the setting in the [`InstallArgs`], e.g. `providerFallbacks`.
+        setting: String,
+    },
+    /// The regular expression of the [`OverrideProvider`] cannot be compiled, so that all
+    /// requests fail.
+    InvalidOverrideProviderRegex {
+        /// The invalid regular expression.
+        pattern: String,
+        /// The reason why the regular expression is invalid.
+        error: String,
+    },
+    /// The state was written by a more recent version of the SOL RPC canister, e.g. because the
+    /// SOL RPC canister was downgraded, and may contain data that is not understood.
+    UnsupportedSchemaVersion {
+        /// The schema version of the stored state.
+        stored: u32,
+        /// The most recent schema version supported by this version of the SOL RPC canister.
+        supported: u32,
+    },
+}
+
+/// The HTTPS outcalls that were still pending when the `prepareUpgrade` endpoint of the SOL RPC
+/// canister returned.
+///
+/// The SOL RPC canister is ready to be upgraded when no HTTPS outcall is pending. Otherwise, the
+/// requests waiting for these HTTPS outcalls may fail when the SOL RPC canister is upgraded.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub struct UpgradeReadiness {
+    /// Number of HTTPS outcalls in flight.
+    #[serde(rename = "numOutcallsInFlight")]
+    pub num_outcalls_in_flight: u32,
+    /// Number of HTTPS outcalls waiting in the queue, see [`OutcallConcurrency`].
+    #[serde(rename = "numQueuedOutcalls")]
+    pub num_queued_outcalls: u32,
+}
+
+impl UpgradeReadiness {
+    /// Whether no HTTPS outcall is pending, i.e. the SOL RPC canister can be upgraded without
+    /// disrupting requests.
+    pub fn is_ready(&self) -> bool {
+        self.num_outcalls_in_flight == 0 && self.num_queued_outcalls == 0
+    }
+}
+
+/// Limits the number of HTTPS outcalls that the SOL RPC canister makes concurrently.
+///
+/// When the maximum number of HTTPS outcalls is in flight, further outcalls wait in a FIFO
+/// queue until an in-flight outcall completes. Outcalls are shed, i.e. the request fails without
+/// making the outcall and without charging cycles for it, when the queue is full or when they
+/// waited longer than the queue timeout. Each retry of a request is a separate HTTPS outcall.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CandidType, Deserialize, Serialize)]
+pub enum OutcallConcurrency {
+    /// The number of concurrent HTTPS outcalls is not limited.
+    #[default]
+    Unlimited,
+    /// The number of concurrent HTTPS outcalls is limited.
+    Limited {
+        /// Maximum number of HTTPS outcalls in flight. Must be greater than 0.
+        #[serde(rename = "maxInFlight")]
+        max_in_flight: u32,
+        /// Maximum number of HTTPS outcalls waiting in the queue. If 0, outcalls are shed as
+        /// soon as the maximum number of HTTPS outcalls is in flight.
+        #[serde(rename = "maxQueueLength")]
+        max_queue_length: u32,
+        /// Maximum time in seconds that an HTTPS outcall waits in the queue before being shed.
+        #[serde(rename = "queueTimeoutSeconds")]
+        queue_timeout_seconds: u32,
+    },
+}
+
 /// Number of subnet nodes with a default value set to 34.
 #[derive(Debug, Copy, Clone, CandidType, Deserialize, Serialize)]
 pub struct NumSubnetNodes(u32);
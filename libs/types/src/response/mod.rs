@@ -1,10 +1,23 @@
+mod resolve;
+#[cfg(test)]
+mod tests;
+
+pub use resolve::{CustomResolver, ResolutionError, ResolutionPolicy};
+
 use crate::{
-    solana::account::AccountInfo, ConfirmedBlock, EncodedConfirmedTransactionWithStatusMeta,
-    RpcResult, RpcSource, Signature, TokenAmount, TransactionStatus,
+    solana::account::AccountInfo, Hash, HttpHeader, RpcError, RpcResult, RpcSource, SampledVersion,
+    Slot, SolanaVersion, SupportedRpcProviderId,
+};
+#[cfg(feature = "solana-compat")]
+use crate::{
+    ConfirmedBlock, EncodedConfirmedTransactionWithStatusMeta, Pubkey, Signature, TokenAmount,
+    TransactionStatus,
 };
 use candid::CandidType;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "solana-compat")]
 use solana_account_decoder_client_types::{token::UiTokenAmount, UiAccount};
+#[cfg(feature = "solana-compat")]
 use solana_transaction_status_client_types::UiConfirmedBlock;
 use std::fmt::Debug;
 
@@ -15,9 +28,31 @@ pub enum MultiRpcResult<T> {
     /// The results from the different providers were consistent.
     Consistent(RpcResult<T>),
     /// The results from the different providers were not consistent.
+    ///
+    /// Metadata about the responses of the providers, see [`ResponseMetadata`], can be
+    /// retrieved with `getResponseMetadata`.
     Inconsistent(Vec<(RpcSource, RpcResult<T>)>),
 }
 
+/// Metadata about the response of a provider to a request that returned a
+/// [`MultiRpcResult::Inconsistent`], to help diagnose why the providers did not agree.
+///
+/// For example, results from public endpoints that are consistently slower or lagging behind
+/// the other providers indicate that setting API keys for these providers may improve the
+/// consistency of the results.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct ResponseMetadata {
+    /// Time in milliseconds spent by the HTTPS outcalls to the provider in flight, including
+    /// retries but excluding the time spent waiting in the queue of HTTPS outcalls. `None` if no
+    /// HTTPS outcall was made to the provider, e.g. because the request could not be built.
+    #[serde(rename = "latencyMs")]
+    pub latency_ms: Option<u64>,
+    /// Whether the request was authenticated with an API key when it was sent, as opposed to
+    /// being sent to a public URL without API key.
+    #[serde(rename = "usesApiKey")]
+    pub uses_api_key: bool,
+}
+
 impl<T> From<RpcResult<T>> for MultiRpcResult<T> {
     fn from(result: RpcResult<T>) -> Self {
         MultiRpcResult::Consistent(result)
@@ -84,36 +119,389 @@ impl<T: Debug> MultiRpcResult<T> {
     }
 }
 
+/// A value returned by a Solana RPC method together with the slot at which it was evaluated.
+///
+/// The context slot is rounded down (see [`crate::WithContextRpcConfig`]) so that the responses
+/// of the different providers can reach consensus.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct WithContext<T> {
+    /// The (rounded) slot at which the value was evaluated.
+    #[serde(rename = "contextSlot")]
+    pub context_slot: Slot,
+    /// The value returned by the Solana RPC method.
+    pub value: T,
+}
+
+impl<T> WithContext<T> {
+    /// Maps the value of a [`WithContext`] while keeping the same context slot.
+    pub fn map<R, F: FnOnce(T) -> R>(self, f: F) -> WithContext<R> {
+        WithContext {
+            context_slot: self.context_slot,
+            value: f(self.value),
+        }
+    }
+}
+
+/// The health of a single RPC provider, as reported by the Solana `getHealth` RPC method.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub enum ProviderHealth {
+    /// The node is healthy, i.e. within `HEALTH_CHECK_SLOT_DISTANCE` slots of the latest cluster
+    /// confirmed slot.
+    Ok,
+    /// The node is behind the latest cluster confirmed slot.
+    Behind {
+        /// The number of slots the node is behind, if reported by the provider.
+        #[serde(rename = "numSlotsBehind")]
+        num_slots_behind: Option<u64>,
+    },
+    /// The health of the node could not be determined.
+    Error(RpcError),
+}
+
+/// The health and version of a single RPC provider, as returned by the `getClusterHealth`
+/// endpoint of the SOL RPC canister.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct ProviderHealthReport {
+    /// The RPC provider.
+    pub source: RpcSource,
+    /// The result of the Solana `getHealth` RPC method.
+    pub health: ProviderHealth,
+    /// The result of the Solana `getVersion` RPC method.
+    pub version: RpcResult<SolanaVersion>,
+    /// The most recent total outage of the Solana cluster of the provider, if any.
+    ///
+    /// Always `None` for [`RpcSource::Custom`] providers.
+    #[serde(rename = "clusterOutage")]
+    pub cluster_outage: Option<ClusterOutage>,
+    /// The spread, i.e. the difference between the largest and the smallest slot, of the slots
+    /// returned by the providers of the Solana cluster of the provider for the most recent
+    /// `getSlot` request, if any. Slots are compared after being rounded, so that the spread is
+    /// a multiple of the rounding error of that request.
+    ///
+    /// Always `None` for [`RpcSource::Custom`] providers.
+    #[serde(rename = "slotSkew")]
+    pub slot_skew: Option<u64>,
+    /// The version of the provider most recently fetched by the version sampler of the SOL RPC
+    /// canister, if enabled.
+    ///
+    /// Always `None` for [`RpcSource::Custom`] providers.
+    #[serde(rename = "sampledVersion")]
+    pub sampled_version: Option<SampledVersion>,
+}
+
+/// A period during which all the RPC providers queried for a Solana cluster failed, e.g. because
+/// of a regional outage.
+///
+/// Only failures that are not caused by the request itself are considered, e.g. an HTTPS outcall
+/// that could not be made or an HTTP status `5xx`, but not a JSON-RPC error.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct ClusterOutage {
+    /// Time in nanoseconds since the UNIX epoch of the first request for which all providers failed.
+    #[serde(rename = "startedAt")]
+    pub started_at: u64,
+    /// Time in nanoseconds since the UNIX epoch of the last request for which all providers failed.
+    #[serde(rename = "lastFailureAt")]
+    pub last_failure_at: u64,
+    /// Time in nanoseconds since the UNIX epoch of the first successful request after the outage,
+    /// or `None` if the outage is ongoing.
+    #[serde(rename = "endedAt")]
+    pub ended_at: Option<u64>,
+    /// Number of requests for which all providers failed during the outage.
+    #[serde(rename = "numFailedRequests")]
+    pub num_failed_requests: u64,
+}
+
+/// A calendar month (in UTC) for which the SOL RPC canister aggregates a [`ProviderScorecard`].
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, CandidType, Deserialize, Serialize,
+)]
+pub struct ScorecardPeriod {
+    /// The year, e.g. `2025`.
+    pub year: u16,
+    /// The month, from `1` (January) to `12` (December).
+    pub month: u8,
+}
+
+const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+impl ScorecardPeriod {
+    /// Returns the period containing the given time in nanoseconds since the UNIX epoch.
+    pub fn containing(timestamp_nanos: u64) -> Self {
+        // Civil-from-days algorithm, see http://howardhinnant.github.io/date_algorithms.html
+        let z = timestamp_nanos / NANOS_PER_DAY + 719_468;
+        let era = z / 146_097;
+        let day_of_era = z % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+        Self {
+            year: year as u16,
+            month: month as u8,
+        }
+    }
+
+    /// Returns the start of the period in nanoseconds since the UNIX epoch.
+    pub fn start(&self) -> u64 {
+        // Days-from-civil algorithm, see http://howardhinnant.github.io/date_algorithms.html
+        let month = u64::from(self.month);
+        let year = u64::from(self.year) - u64::from(month <= 2);
+        let era = year / 400;
+        let year_of_era = year % 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = (era * 146_097 + day_of_era).saturating_sub(719_468);
+        days * NANOS_PER_DAY
+    }
+
+    /// Returns the period following this one.
+    pub fn next(&self) -> Self {
+        if self.month >= 12 {
+            Self {
+                year: self.year + 1,
+                month: 1,
+            }
+        } else {
+            Self {
+                year: self.year,
+                month: self.month + 1,
+            }
+        }
+    }
+
+    /// Returns the period preceding this one.
+    pub fn previous(&self) -> Self {
+        if self.month <= 1 {
+            Self {
+                year: self.year - 1,
+                month: 12,
+            }
+        } else {
+            Self {
+                year: self.year,
+                month: self.month - 1,
+            }
+        }
+    }
+}
+
+/// Reliability statistics of a single supported RPC provider over a [`ScorecardPeriod`].
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct ProviderScore {
+    /// The RPC provider.
+    pub provider: SupportedRpcProviderId,
+    /// Number of requests sent to the provider.
+    #[serde(rename = "numRequests")]
+    pub num_requests: u64,
+    /// Number of requests for which the provider returned a response, including JSON-RPC errors.
+    #[serde(rename = "numResponses")]
+    pub num_responses: u64,
+    /// Number of requests that failed because of the provider, e.g. an HTTPS outcall that could
+    /// not be made or an HTTP status `5xx`.
+    #[serde(rename = "numFailures")]
+    pub num_failures: u64,
+    /// Number of responses that differed from the response returned by a strict majority of the
+    /// other providers queried for the same request.
+    #[serde(rename = "numDisagreements")]
+    pub num_disagreements: u64,
+}
+
+impl ProviderScore {
+    /// Returns an empty score for the given provider.
+    pub fn new(provider: SupportedRpcProviderId) -> Self {
+        Self {
+            provider,
+            num_requests: 0,
+            num_responses: 0,
+            num_failures: 0,
+            num_disagreements: 0,
+        }
+    }
+}
+
+/// Per-provider reliability statistics aggregated over a calendar month, as returned by the
+/// `getProviderScorecard` endpoint of the SOL RPC canister.
+///
+/// Scorecards are meant as evidence when proposing to add or remove providers from the default
+/// set of providers used by the SOL RPC canister.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct ProviderScorecard {
+    /// The month over which the statistics were aggregated.
+    pub period: ScorecardPeriod,
+    /// The statistics of each provider that was queried during the period, sorted by provider.
+    pub scores: Vec<ProviderScore>,
+}
+
+/// Statistics of a supported RPC provider queried in shadow mode, as returned by the
+/// `getShadowProviderStats` endpoint of the SOL RPC canister.
+///
+/// A provider in shadow mode is sent the same requests as the other providers of its cluster,
+/// but its responses are only compared with the results returned to the callers and are never
+/// taken into account to reach consensus. Shadow statistics are meant as evidence before adding
+/// a provider to the default set of providers.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct ShadowProviderStats {
+    /// The RPC provider in shadow mode.
+    pub provider: SupportedRpcProviderId,
+    /// Number of requests sent to the provider.
+    #[serde(rename = "numRequests")]
+    pub num_requests: u64,
+    /// Number of responses equal to the consistent result returned to the caller.
+    #[serde(rename = "numAgreements")]
+    pub num_agreements: u64,
+    /// Number of responses, including JSON-RPC errors, that differed from the consistent result
+    /// returned to the caller.
+    #[serde(rename = "numDisagreements")]
+    pub num_disagreements: u64,
+    /// Number of requests that failed without a response from the provider, e.g. an HTTPS
+    /// outcall that could not be made or an HTTP status `5xx`.
+    #[serde(rename = "numFailures")]
+    pub num_failures: u64,
+    /// Number of responses that could not be compared, because the providers queried by the
+    /// caller did not return a consistent result.
+    #[serde(rename = "numUncompared")]
+    pub num_uncompared: u64,
+    /// Sum of the latencies of all requests sent to the provider, in milliseconds.
+    /// The average latency is obtained by dividing it by the number of requests.
+    #[serde(rename = "totalLatencyMs")]
+    pub total_latency_ms: u64,
+}
+
+impl ShadowProviderStats {
+    /// Returns empty statistics for the given provider.
+    pub fn new(provider: SupportedRpcProviderId) -> Self {
+        Self {
+            provider,
+            num_requests: 0,
+            num_agreements: 0,
+            num_disagreements: 0,
+            num_failures: 0,
+            num_uncompared: 0,
+            total_latency_ms: 0,
+        }
+    }
+}
+
+/// An HTTP request that the SOL RPC canister would send to an RPC provider, as returned by the
+/// `previewRequest` endpoint of the SOL RPC canister.
+///
+/// API keys contained in the URL or the headers are replaced by the `{API_KEY}` placeholder.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct HttpRequestPreview {
+    /// The RPC provider the request would be sent to.
+    pub source: RpcSource,
+    /// The request URL, after applying the override provider, if any.
+    pub url: String,
+    /// The HTTP request headers.
+    pub headers: Vec<HttpHeader>,
+    /// The HTTP request body, i.e. the JSON-RPC request.
+    pub body: String,
+    /// The maximum number of bytes expected in the response.
+    #[serde(rename = "maxResponseBytes")]
+    pub max_response_bytes: u64,
+}
+
+/// An HTTP response returned by an RPC provider to a call to the `rawHttpRequest` endpoint of
+/// the SOL RPC canister.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct RawHttpResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The HTTP response headers selected by
+    /// [`RawHttpRequestParams::response_headers`](crate::RawHttpRequestParams::response_headers),
+    /// with lowercase names.
+    pub headers: Vec<HttpHeader>,
+    /// The HTTP response body. If the body is valid JSON, it is re-encoded with the object keys
+    /// sorted, and otherwise returned as-is.
+    pub body: Vec<u8>,
+}
+
+/// The result of a call to the `hasAccountChanged` endpoint of the SOL RPC canister.
+#[derive(Clone, Debug, Eq, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct AccountChange {
+    /// The hash of the account computed by the SOL RPC canister, or `None` if the account does
+    /// not exist. To be passed as
+    /// [`HasAccountChangedParams::last_known_hash`](crate::HasAccountChangedParams::last_known_hash)
+    /// in the next call.
+    pub hash: Option<Hash>,
+    /// Whether the hash of the account differs from the last known hash.
+    pub changed: bool,
+    /// The account, which is only returned if it changed and exists.
+    pub account: Option<AccountInfo>,
+}
+
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Signature>> for MultiRpcResult<solana_signature::Signature> {
     fn from(result: MultiRpcResult<Signature>) -> Self {
         result.map(solana_signature::Signature::from)
     }
 }
 
+#[cfg(feature = "solana-compat")]
+impl From<MultiRpcResult<Vec<Pubkey>>> for MultiRpcResult<Vec<solana_pubkey::Pubkey>> {
+    fn from(result: MultiRpcResult<Vec<Pubkey>>) -> Self {
+        result.map(|pubkeys| {
+            pubkeys
+                .into_iter()
+                .map(solana_pubkey::Pubkey::from)
+                .collect()
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Option<AccountInfo>>> for MultiRpcResult<Option<UiAccount>> {
     fn from(result: MultiRpcResult<Option<AccountInfo>>) -> Self {
         result.map(|maybe_account| maybe_account.map(|account| account.into()))
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Option<UiAccount>>> for MultiRpcResult<Option<AccountInfo>> {
     fn from(result: MultiRpcResult<Option<UiAccount>>) -> Self {
         result.map(|maybe_account| maybe_account.map(|account| account.into()))
     }
 }
 
+#[cfg(feature = "solana-compat")]
+impl From<MultiRpcResult<WithContext<Option<AccountInfo>>>>
+    for MultiRpcResult<WithContext<Option<UiAccount>>>
+{
+    fn from(result: MultiRpcResult<WithContext<Option<AccountInfo>>>) -> Self {
+        result.map(|response| response.map(|maybe_account| maybe_account.map(UiAccount::from)))
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<MultiRpcResult<WithContext<Option<UiAccount>>>>
+    for MultiRpcResult<WithContext<Option<AccountInfo>>>
+{
+    fn from(result: MultiRpcResult<WithContext<Option<UiAccount>>>) -> Self {
+        result.map(|response| response.map(|maybe_account| maybe_account.map(AccountInfo::from)))
+    }
+}
+
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Option<ConfirmedBlock>>> for MultiRpcResult<Option<UiConfirmedBlock>> {
     fn from(result: MultiRpcResult<Option<ConfirmedBlock>>) -> Self {
         result.map(|maybe_block| maybe_block.map(|block| block.into()))
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Option<UiConfirmedBlock>>> for MultiRpcResult<Option<ConfirmedBlock>> {
     fn from(result: MultiRpcResult<Option<UiConfirmedBlock>>) -> Self {
         result.and_then(|maybe_block| maybe_block.map(ConfirmedBlock::try_from).transpose())
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl
     From<
         MultiRpcResult<
@@ -138,6 +526,7 @@ impl
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Option<EncodedConfirmedTransactionWithStatusMeta>>>
     for MultiRpcResult<
         Option<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>,
@@ -148,18 +537,35 @@ impl From<MultiRpcResult<Option<EncodedConfirmedTransactionWithStatusMeta>>>
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<TokenAmount>> for MultiRpcResult<UiTokenAmount> {
     fn from(result: MultiRpcResult<TokenAmount>) -> Self {
         result.map(UiTokenAmount::from)
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<UiTokenAmount>> for MultiRpcResult<TokenAmount> {
     fn from(result: MultiRpcResult<UiTokenAmount>) -> Self {
         result.map(TokenAmount::from)
     }
 }
 
+#[cfg(feature = "solana-compat")]
+impl From<MultiRpcResult<WithContext<TokenAmount>>> for MultiRpcResult<WithContext<UiTokenAmount>> {
+    fn from(result: MultiRpcResult<WithContext<TokenAmount>>) -> Self {
+        result.map(|response| response.map(UiTokenAmount::from))
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<MultiRpcResult<WithContext<UiTokenAmount>>> for MultiRpcResult<WithContext<TokenAmount>> {
+    fn from(result: MultiRpcResult<WithContext<UiTokenAmount>>) -> Self {
+        result.map(|response| response.map(TokenAmount::from))
+    }
+}
+
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Vec<Option<TransactionStatus>>>>
     for MultiRpcResult<Vec<Option<solana_transaction_status_client_types::TransactionStatus>>>
 {
@@ -176,6 +582,7 @@ impl From<MultiRpcResult<Vec<Option<TransactionStatus>>>>
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<MultiRpcResult<Vec<Option<solana_transaction_status_client_types::TransactionStatus>>>>
     for MultiRpcResult<Vec<Option<TransactionStatus>>>
 {
@@ -0,0 +1,175 @@
+use crate::{MultiRpcResult, RpcError, RpcResult, RpcSource, Slot, WithContext};
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// A custom resolver, see [`ResolutionPolicy::Custom`].
+pub type CustomResolver<T> =
+    Box<dyn FnOnce(Vec<(RpcSource, RpcResult<T>)>) -> Result<T, ResolutionError<T>>>;
+
+/// Defines how [`MultiRpcResult::resolve`] handles inconsistent results.
+///
+/// Consistent results are always resolved to their value or error, regardless of the policy.
+pub enum ResolutionPolicy<T> {
+    /// Inconsistent results are never resolved.
+    RequireConsistent,
+    /// Resolve to the result returned by at least `min` providers.
+    ///
+    /// If several results are returned by at least `min` providers, the result returned by the
+    /// most providers is chosen. Inconsistent results are not resolved if there is a tie.
+    Threshold {
+        /// Minimum number of providers that must return the same result.
+        min: u8,
+    },
+    /// Resolve to the successful result with the highest slot, as returned by the given function.
+    ///
+    /// This is useful to prefer the most recent value when providers are not at the same slot.
+    /// If several successful results have the highest slot, the first one is chosen.
+    /// Inconsistent results are not resolved if no provider returned a successful result.
+    HighestSlot(Box<dyn Fn(&T) -> Slot>),
+    /// Resolve inconsistent results with the given function.
+    Custom(CustomResolver<T>),
+}
+
+impl<T> ResolutionPolicy<T> {
+    /// Creates a [`ResolutionPolicy::HighestSlot`] policy with the given slot extractor.
+    pub fn highest_slot(slot: impl Fn(&T) -> Slot + 'static) -> Self {
+        Self::HighestSlot(Box::new(slot))
+    }
+
+    /// Creates a [`ResolutionPolicy::Custom`] policy with the given resolver.
+    pub fn custom(
+        resolver: impl FnOnce(Vec<(RpcSource, RpcResult<T>)>) -> Result<T, ResolutionError<T>> + 'static,
+    ) -> Self {
+        Self::Custom(Box::new(resolver))
+    }
+}
+
+impl<T> ResolutionPolicy<WithContext<T>> {
+    /// Creates a [`ResolutionPolicy::HighestSlot`] policy preferring the value with the
+    /// highest context slot.
+    pub fn highest_context_slot() -> Self {
+        Self::highest_slot(|value: &WithContext<T>| value.context_slot)
+    }
+}
+
+impl<T> Debug for ResolutionPolicy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolutionPolicy::RequireConsistent => write!(f, "RequireConsistent"),
+            ResolutionPolicy::Threshold { min } => {
+                f.debug_struct("Threshold").field("min", min).finish()
+            }
+            ResolutionPolicy::HighestSlot(_) => write!(f, "HighestSlot(..)"),
+            ResolutionPolicy::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// An error returned by [`MultiRpcResult::resolve`].
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum ResolutionError<T: Debug> {
+    /// The result was resolved to an error.
+    #[error("RPC error: {0}")]
+    RpcError(RpcError),
+    /// The inconsistent results could not be resolved with the given policy.
+    #[error("Unresolved inconsistent results: {0:?}")]
+    Unresolved(Vec<(RpcSource, RpcResult<T>)>),
+}
+
+impl<T: Debug> From<RpcError> for ResolutionError<T> {
+    fn from(error: RpcError) -> Self {
+        ResolutionError::RpcError(error)
+    }
+}
+
+impl<T: Debug + PartialEq> MultiRpcResult<T> {
+    /// Converts a [`MultiRpcResult`] into a [`Result`], resolving inconsistent results
+    /// according to the given [`ResolutionPolicy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_types::{
+    ///     MultiRpcResult, ResolutionError, ResolutionPolicy, RpcError, RpcSource,
+    ///     SupportedRpcProviderId,
+    /// };
+    ///
+    /// let result = MultiRpcResult::Inconsistent(vec![
+    ///     (RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet), Ok(42)),
+    ///     (RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet), Ok(42)),
+    ///     (RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet), Ok(43)),
+    /// ]);
+    ///
+    /// assert_eq!(result.clone().resolve(ResolutionPolicy::Threshold { min: 2 }), Ok(42));
+    /// assert_eq!(result.clone().resolve(ResolutionPolicy::highest_slot(|slot| *slot)), Ok(43));
+    /// assert!(matches!(
+    ///     result.resolve(ResolutionPolicy::RequireConsistent),
+    ///     Err(ResolutionError::Unresolved(_))
+    /// ));
+    /// ```
+    pub fn resolve(self, policy: ResolutionPolicy<T>) -> Result<T, ResolutionError<T>> {
+        let results = match self {
+            MultiRpcResult::Consistent(result) => return result.map_err(ResolutionError::from),
+            MultiRpcResult::Inconsistent(results) => results,
+        };
+        match policy {
+            ResolutionPolicy::RequireConsistent => Err(ResolutionError::Unresolved(results)),
+            ResolutionPolicy::Threshold { min } => resolve_with_threshold(results, min),
+            ResolutionPolicy::HighestSlot(slot) => resolve_with_highest_slot(results, slot),
+            ResolutionPolicy::Custom(resolver) => resolver(results),
+        }
+    }
+}
+
+fn resolve_with_threshold<T: Debug + PartialEq>(
+    results: Vec<(RpcSource, RpcResult<T>)>,
+    min: u8,
+) -> Result<T, ResolutionError<T>> {
+    // Number of occurrences of each distinct result, indexed by its first occurrence
+    let mut counts: Vec<(usize, usize)> = Vec::new();
+    for (index, (_source, result)) in results.iter().enumerate() {
+        match counts
+            .iter_mut()
+            .find(|(other, _count)| &results[*other].1 == result)
+        {
+            Some((_other, count)) => *count += 1,
+            None => counts.push((index, 1)),
+        }
+    }
+    counts.sort_by(|(_, left), (_, right)| right.cmp(left));
+    match counts[..] {
+        [(index, count), ..]
+            if count >= min as usize && counts.get(1).is_none_or(|(_, second)| *second < count) =>
+        {
+            let (_source, result) = results.into_iter().nth(index).expect("BUG: invalid index");
+            result.map_err(ResolutionError::from)
+        }
+        _ => Err(ResolutionError::Unresolved(results)),
+    }
+}
+
+fn resolve_with_highest_slot<T: Debug>(
+    results: Vec<(RpcSource, RpcResult<T>)>,
+    slot: Box<dyn Fn(&T) -> Slot>,
+) -> Result<T, ResolutionError<T>> {
+    let highest = results
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (_source, result))| {
+            result.as_ref().ok().map(|value| (index, slot(value)))
+        })
+        .fold(
+            None,
+            |highest: Option<(usize, Slot)>, (index, value_slot)| match highest {
+                Some((_, highest_slot)) if highest_slot >= value_slot => highest,
+                _ => Some((index, value_slot)),
+            },
+        );
+    match highest {
+        Some((index, _slot)) => {
+            let (_source, result) = results.into_iter().nth(index).expect("BUG: invalid index");
+            result.map_err(ResolutionError::from)
+        }
+        None => Err(ResolutionError::Unresolved(results)),
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{
+    JsonRpcError, MultiRpcResult, ResolutionError, ResolutionPolicy, RpcError, RpcResult,
+    RpcSource, ScorecardPeriod, SupportedRpcProviderId, WithContext,
+};
+
+const ALCHEMY: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AlchemyMainnet);
+const ANKR: RpcSource = RpcSource::Supported(SupportedRpcProviderId::AnkrMainnet);
+const HELIUS: RpcSource = RpcSource::Supported(SupportedRpcProviderId::HeliusMainnet);
+const DRPC: RpcSource = RpcSource::Supported(SupportedRpcProviderId::DrpcMainnet);
+
+mod resolve {
+    use super::*;
+
+    #[test]
+    fn should_resolve_consistent_results_with_any_policy() {
+        for policy in all_policies() {
+            assert_eq!(MultiRpcResult::Consistent(Ok(1)).resolve(policy), Ok(1));
+        }
+        for policy in all_policies() {
+            assert_eq!(
+                MultiRpcResult::Consistent(Err(json_rpc_error())).resolve(policy),
+                Err(ResolutionError::RpcError(json_rpc_error()))
+            );
+        }
+    }
+
+    #[test]
+    fn should_not_resolve_inconsistent_results_when_requiring_consistency() {
+        let results = vec![(ALCHEMY, Ok(1)), (ANKR, Ok(2))];
+
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results.clone())
+                .resolve(ResolutionPolicy::RequireConsistent),
+            Err(ResolutionError::Unresolved(results))
+        );
+    }
+
+    #[test]
+    fn should_resolve_with_threshold() {
+        let results = vec![
+            (ALCHEMY, Ok(1)),
+            (ANKR, Ok(2)),
+            (HELIUS, Ok(1)),
+            (DRPC, Err(json_rpc_error())),
+        ];
+
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results.clone())
+                .resolve(ResolutionPolicy::Threshold { min: 2 }),
+            Ok(1)
+        );
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results.clone())
+                .resolve(ResolutionPolicy::Threshold { min: 3 }),
+            Err(ResolutionError::Unresolved(results))
+        );
+    }
+
+    #[test]
+    fn should_resolve_to_error_with_threshold() {
+        let results = vec![
+            (ALCHEMY, Err(json_rpc_error())),
+            (ANKR, Ok(2)),
+            (HELIUS, Err(json_rpc_error())),
+        ];
+
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results).resolve(ResolutionPolicy::Threshold { min: 2 }),
+            Err(ResolutionError::RpcError(json_rpc_error()))
+        );
+    }
+
+    #[test]
+    fn should_not_resolve_tie_with_threshold() {
+        let results = vec![
+            (ALCHEMY, Ok(1)),
+            (ANKR, Ok(2)),
+            (HELIUS, Ok(1)),
+            (DRPC, Ok(2)),
+        ];
+
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results.clone())
+                .resolve(ResolutionPolicy::Threshold { min: 2 }),
+            Err(ResolutionError::Unresolved(results))
+        );
+    }
+
+    #[test]
+    fn should_resolve_with_highest_slot() {
+        let results = vec![
+            (ALCHEMY, Ok(with_context(100, "a"))),
+            (ANKR, Ok(with_context(102, "b"))),
+            (HELIUS, Err(json_rpc_error())),
+            (DRPC, Ok(with_context(102, "c"))),
+        ];
+
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results).resolve(ResolutionPolicy::highest_context_slot()),
+            Ok(with_context(102, "b"))
+        );
+    }
+
+    #[test]
+    fn should_not_resolve_with_highest_slot_without_successful_result() {
+        let results: Vec<(RpcSource, RpcResult<u64>)> = vec![
+            (ALCHEMY, Err(json_rpc_error())),
+            (ANKR, Err(RpcError::ValidationError("invalid".to_string()))),
+        ];
+
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results.clone())
+                .resolve(ResolutionPolicy::highest_slot(|slot| *slot)),
+            Err(ResolutionError::Unresolved(results))
+        );
+    }
+
+    #[test]
+    fn should_resolve_with_custom_resolver() {
+        let results = vec![(ALCHEMY, Ok(1)), (ANKR, Ok(5)), (HELIUS, Ok(3))];
+
+        let sum_of_values = ResolutionPolicy::<u64>::custom(|results| {
+            results
+                .into_iter()
+                .map(|(_source, result)| result.map_err(ResolutionError::from))
+                .sum()
+        });
+
+        assert_eq!(
+            MultiRpcResult::Inconsistent(results).resolve(sum_of_values),
+            Ok(9)
+        );
+    }
+
+    fn all_policies() -> Vec<ResolutionPolicy<u64>> {
+        vec![
+            ResolutionPolicy::RequireConsistent,
+            ResolutionPolicy::Threshold { min: 2 },
+            ResolutionPolicy::highest_slot(|slot| *slot),
+            ResolutionPolicy::custom(|results| Err(ResolutionError::Unresolved(results))),
+        ]
+    }
+
+    fn with_context(context_slot: u64, value: &str) -> WithContext<String> {
+        WithContext {
+            context_slot,
+            value: value.to_string(),
+        }
+    }
+
+    fn json_rpc_error() -> RpcError {
+        RpcError::JsonRpcError(JsonRpcError {
+            code: -32_007,
+            message: "Slot 1450300 was skipped".to_string(),
+            data: None,
+        })
+    }
+}
+
+mod scorecard_period {
+    use super::*;
+
+    const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+    #[test]
+    fn should_compute_period_containing_timestamp() {
+        for (timestamp_secs, expected) in [
+            (0, period(1970, 1)),
+            // 2024-02-29T23:59:59Z
+            (1_709_251_199, period(2024, 2)),
+            // 2024-03-01T00:00:00Z
+            (1_709_251_200, period(2024, 3)),
+            // 2025-12-31T23:59:59Z
+            (1_767_225_599, period(2025, 12)),
+            // 2026-01-01T00:00:00Z
+            (1_767_225_600, period(2026, 1)),
+        ] {
+            assert_eq!(
+                ScorecardPeriod::containing(timestamp_secs * NANOS_PER_SECOND),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn should_compute_start_of_period() {
+        assert_eq!(period(1970, 1).start(), 0);
+        assert_eq!(period(2024, 3).start(), 1_709_251_200 * NANOS_PER_SECOND);
+        assert_eq!(period(2026, 1).start(), 1_767_225_600 * NANOS_PER_SECOND);
+    }
+
+    #[test]
+    fn should_compute_adjacent_periods() {
+        assert_eq!(period(2025, 12).next(), period(2026, 1));
+        assert_eq!(period(2025, 6).next(), period(2025, 7));
+        assert_eq!(period(2026, 1).previous(), period(2025, 12));
+        assert_eq!(period(2025, 7).previous(), period(2025, 6));
+
+        let mut current = period(2024, 1);
+        for _ in 0..36 {
+            let next = current.next();
+            assert!(current.start() < next.start());
+            assert_eq!(ScorecardPeriod::containing(next.start() - 1), current);
+            assert_eq!(ScorecardPeriod::containing(next.start()), next);
+            current = next;
+        }
+    }
+
+    fn period(year: u16, month: u8) -> ScorecardPeriod {
+        ScorecardPeriod { year, month }
+    }
+}
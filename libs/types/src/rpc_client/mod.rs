@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod tests;
 
+use crate::RequestIdFormat;
 use candid::{
     types::{Serializer, Type, TypeInner},
-    CandidType,
+    CandidType, Principal,
 };
 use derive_more::{From, Into};
 use ic_cdk::call::RejectCode;
@@ -32,23 +33,29 @@ pub enum RpcError {
     /// A validation error occurred.
     #[error("Validation error: {0}")]
     ValidationError(String),
+    /// The caller is not allowed to call this endpoint, see [`crate::AllowedCallers`].
+    #[error("Unauthorized caller: {caller}")]
+    #[from(skip)]
+    Unauthorized {
+        /// The principal of the rejected caller.
+        caller: Principal,
+    },
+    /// The SOL RPC canister is about to be upgraded and does not accept new requests,
+    /// see the `prepareUpgrade` endpoint. The request can be retried after the upgrade.
+    #[error("The SOL RPC canister is being upgraded")]
+    #[from(skip)]
+    UpgradeInProgress,
 }
 
-impl From<solana_pubkey::ParsePubkeyError> for RpcError {
-    fn from(e: solana_pubkey::ParsePubkeyError) -> Self {
-        RpcError::ValidationError(format!("Invalid public key: {e}"))
-    }
-}
-
-impl From<solana_signature::ParseSignatureError> for RpcError {
-    fn from(e: solana_signature::ParseSignatureError) -> Self {
-        RpcError::ValidationError(format!("Invalid signature: {e}"))
+impl From<serde_json::Error> for RpcError {
+    fn from(e: serde_json::Error) -> Self {
+        RpcError::ValidationError(format!("Invalid JSON: {e}"))
     }
 }
 
-impl From<solana_hash::ParseHashError> for RpcError {
-    fn from(e: solana_hash::ParseHashError) -> Self {
-        RpcError::ValidationError(format!("Invalid hash: {e}"))
+impl From<std::convert::Infallible> for RpcError {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
     }
 }
 
@@ -69,6 +76,26 @@ pub enum ProviderError {
     /// The [`SolanaCluster`] is not supported.
     #[error("Unsupported Solana cluster: {0}")]
     UnsupportedCluster(String),
+    /// The provider rejected the request because of missing or invalid credentials,
+    /// i.e. it responded with HTTP status `401` (Unauthorized) or `403` (Forbidden).
+    #[error("Unauthorized: status {status}, body: {body}")]
+    Unauthorized {
+        /// The HTTP status code returned.
+        status: u16,
+        /// The serialized response body.
+        body: String,
+    },
+    /// The provider rejected the request because too many requests were made,
+    /// i.e. it responded with HTTP status `429` (Too Many Requests).
+    #[error("Rate limited (retry after: {retry_after:?} seconds), body: {body}")]
+    RateLimited {
+        /// Number of seconds to wait before making a new request, if specified by the provider
+        /// in the `Retry-After` response header.
+        #[serde(rename = "retryAfter")]
+        retry_after: Option<u64>,
+        /// The serialized response body.
+        body: String,
+    },
 }
 
 /// An HTTP outcall error.
@@ -96,6 +123,24 @@ pub enum HttpOutcallError {
         #[serde(rename = "parsingError")]
         parsing_error: Option<String>,
     },
+    /// The HTTPS outcall was not made because too many HTTPS outcalls were already in flight,
+    /// see [`crate::OutcallConcurrency`].
+    #[error("HTTPS outcall shed: {reason}")]
+    OutcallShed {
+        /// Why the HTTPS outcall was shed.
+        reason: OutcallShedReason,
+    },
+}
+
+/// Reason why an HTTPS outcall was shed, see [`HttpOutcallError::OutcallShed`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, CandidType, Deserialize, Error)]
+pub enum OutcallShedReason {
+    /// The queue of HTTPS outcalls waiting for an in-flight HTTPS outcall to complete was full.
+    #[error("queue is full")]
+    QueueFull,
+    /// The HTTPS outcall waited in the queue longer than the queue timeout.
+    #[error("queue timeout expired")]
+    QueueTimeout,
 }
 
 /// A JSON-RPC 2.0 error as per the [specifications](https://www.jsonrpc.org/specification#error_object).
@@ -106,6 +151,11 @@ pub struct JsonRpcError {
     pub code: i64,
     /// The error message.
     pub message: String,
+    /// Additional information about the error as a JSON string, if any.
+    ///
+    /// For example, when a `sendTransaction` call fails the preflight checks, this contains the
+    /// result of the transaction simulation, including the transaction error and the logs.
+    pub data: Option<String>,
 }
 
 /// Configures how to perform RPC HTTP calls.
@@ -120,6 +170,28 @@ pub struct RpcConfig {
     /// a single response.
     #[serde(rename = "responseConsensus")]
     pub response_consensus: Option<ConsensusStrategy>,
+
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+}
+
+/// Scheduling lane of a request to the SOL RPC canister.
+///
+/// Latency-sensitive callers, e.g. price oracles, may expedite their requests to bound their
+/// tail latency, at the cost of a higher cycles premium, see
+/// [`PricingPolicy::expedited_markup_percent`](crate::PricingPolicy::expedited_markup_percent).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, CandidType, Deserialize)]
+pub enum RequestPriority {
+    /// The request is processed in the normal lane, i.e. its HTTPS outcalls wait in the queue
+    /// when the limit on concurrent HTTPS outcalls is reached, and the default providers are
+    /// selected according to their recent usage.
+    #[default]
+    Normal,
+    /// The request is processed in the expedited lane, i.e. its HTTPS outcalls skip the queue
+    /// of HTTPS outcalls waiting for the limit on concurrent HTTPS outcalls, and the default
+    /// providers with the lowest recent latency are selected.
+    Expedited,
 }
 
 /// Configures how to perform HTTP calls for the Solana `getSlot` RPC method.
@@ -135,6 +207,10 @@ pub struct GetSlotRpcConfig {
     #[serde(rename = "responseConsensus")]
     pub response_consensus: Option<ConsensusStrategy>,
 
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
     /// The result of the `getSlot` method will be rounded down to the nearest value within
     /// this error threshold. This is done to achieve consensus between nodes on the value
     /// of the latest slot despite the fast Solana block time.
@@ -147,6 +223,7 @@ impl From<GetSlotRpcConfig> for RpcConfig {
         RpcConfig {
             response_size_estimate: config.response_size_estimate,
             response_consensus: config.response_consensus,
+            priority: config.priority,
         }
     }
 }
@@ -156,6 +233,300 @@ impl From<RpcConfig> for GetSlotRpcConfig {
         GetSlotRpcConfig {
             response_size_estimate: value.response_size_estimate,
             response_consensus: value.response_consensus,
+            priority: value.priority,
+            ..Default::default()
+        }
+    }
+}
+
+/// Configures how to perform HTTP calls for the Solana `getBlockHeight` RPC method.
+#[derive(Clone, Debug, PartialEq, Eq, Default, CandidType, Deserialize)]
+pub struct GetBlockHeightRpcConfig {
+    /// Describes the expected (90th percentile) number of bytes in the HTTP response body.
+    /// This number should be less than `MAX_PAYLOAD_SIZE`.
+    #[serde(rename = "responseSizeEstimate")]
+    pub response_size_estimate: Option<u64>,
+
+    /// Specifies how the responses of the different RPC providers should be aggregated into
+    /// a single response.
+    #[serde(rename = "responseConsensus")]
+    pub response_consensus: Option<ConsensusStrategy>,
+
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
+    /// The result of the `getBlockHeight` method will be rounded down to the nearest value within
+    /// this error threshold. Similarly to `getSlot`, this is required to achieve consensus since
+    /// providers are rarely at the exact same block height.
+    #[serde(rename = "roundingError")]
+    pub rounding_error: Option<RoundingError>,
+}
+
+impl From<GetBlockHeightRpcConfig> for RpcConfig {
+    fn from(config: GetBlockHeightRpcConfig) -> Self {
+        RpcConfig {
+            response_size_estimate: config.response_size_estimate,
+            response_consensus: config.response_consensus,
+            priority: config.priority,
+        }
+    }
+}
+
+impl From<RpcConfig> for GetBlockHeightRpcConfig {
+    fn from(value: RpcConfig) -> Self {
+        GetBlockHeightRpcConfig {
+            response_size_estimate: value.response_size_estimate,
+            response_consensus: value.response_consensus,
+            priority: value.priority,
+            ..Default::default()
+        }
+    }
+}
+
+/// Configures how to perform HTTP calls for the Solana `getEpochInfo` RPC method.
+///
+/// To achieve consensus between providers that are at slightly different slots, the response
+/// is normalized as follows:
+/// 1. The absolute slot is rounded down to the nearest value within `rounding_error`, but not
+///    below the first slot of the current epoch, and the slot index is adjusted accordingly.
+/// 2. The block height is rounded down to the nearest value within `rounding_error`.
+/// 3. The transaction count, which changes with every block, is removed from the response.
+#[derive(Clone, Debug, PartialEq, Eq, Default, CandidType, Deserialize)]
+pub struct GetEpochInfoRpcConfig {
+    /// Describes the expected (90th percentile) number of bytes in the HTTP response body.
+    /// This number should be less than `MAX_PAYLOAD_SIZE`.
+    #[serde(rename = "responseSizeEstimate")]
+    pub response_size_estimate: Option<u64>,
+
+    /// Specifies how the responses of the different RPC providers should be aggregated into
+    /// a single response.
+    #[serde(rename = "responseConsensus")]
+    pub response_consensus: Option<ConsensusStrategy>,
+
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
+    /// The absolute slot and the block height in the result of the `getEpochInfo` method will be
+    /// rounded down to the nearest value within this error threshold.
+    #[serde(rename = "roundingError")]
+    pub rounding_error: Option<RoundingError>,
+}
+
+impl From<GetEpochInfoRpcConfig> for RpcConfig {
+    fn from(config: GetEpochInfoRpcConfig) -> Self {
+        RpcConfig {
+            response_size_estimate: config.response_size_estimate,
+            response_consensus: config.response_consensus,
+            priority: config.priority,
+        }
+    }
+}
+
+impl From<RpcConfig> for GetEpochInfoRpcConfig {
+    fn from(value: RpcConfig) -> Self {
+        GetEpochInfoRpcConfig {
+            response_size_estimate: value.response_size_estimate,
+            response_consensus: value.response_consensus,
+            priority: value.priority,
+            ..Default::default()
+        }
+    }
+}
+
+/// Configures how to perform HTTP calls for the Solana `getTransactionCount` RPC method.
+#[derive(Clone, Debug, PartialEq, Eq, Default, CandidType, Deserialize)]
+pub struct GetTransactionCountRpcConfig {
+    /// Describes the expected (90th percentile) number of bytes in the HTTP response body.
+    /// This number should be less than `MAX_PAYLOAD_SIZE`.
+    #[serde(rename = "responseSizeEstimate")]
+    pub response_size_estimate: Option<u64>,
+
+    /// Specifies how the responses of the different RPC providers should be aggregated into
+    /// a single response.
+    #[serde(rename = "responseConsensus")]
+    pub response_consensus: Option<ConsensusStrategy>,
+
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
+    /// The result of the `getTransactionCount` method will be rounded down to the nearest value
+    /// within this error threshold. Since the transaction count increases by several thousands
+    /// with every slot, this is required to achieve consensus between providers that are at
+    /// slightly different slots. Defaults to [`GetTransactionCountRpcConfig::DEFAULT_ROUNDING_ERROR`].
+    #[serde(rename = "roundingError")]
+    pub rounding_error: Option<RoundingError>,
+}
+
+impl GetTransactionCountRpcConfig {
+    /// The rounding error used when none is specified, which roughly corresponds to the number
+    /// of transactions processed by Solana Mainnet during the default slot rounding window.
+    pub const DEFAULT_ROUNDING_ERROR: u64 = 100_000;
+}
+
+impl From<GetTransactionCountRpcConfig> for RpcConfig {
+    fn from(config: GetTransactionCountRpcConfig) -> Self {
+        RpcConfig {
+            response_size_estimate: config.response_size_estimate,
+            response_consensus: config.response_consensus,
+            priority: config.priority,
+        }
+    }
+}
+
+impl From<RpcConfig> for GetTransactionCountRpcConfig {
+    fn from(value: RpcConfig) -> Self {
+        GetTransactionCountRpcConfig {
+            response_size_estimate: value.response_size_estimate,
+            response_consensus: value.response_consensus,
+            priority: value.priority,
+            ..Default::default()
+        }
+    }
+}
+
+/// Configures how to perform HTTP calls for the Solana `getSupply` RPC method.
+#[derive(Clone, Debug, PartialEq, Eq, Default, CandidType, Deserialize)]
+pub struct GetSupplyRpcConfig {
+    /// Describes the expected (90th percentile) number of bytes in the HTTP response body.
+    /// This number should be less than `MAX_PAYLOAD_SIZE`.
+    #[serde(rename = "responseSizeEstimate")]
+    pub response_size_estimate: Option<u64>,
+
+    /// Specifies how the responses of the different RPC providers should be aggregated into
+    /// a single response.
+    #[serde(rename = "responseConsensus")]
+    pub response_consensus: Option<ConsensusStrategy>,
+
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
+    /// The total, circulating and non-circulating supplies returned by the `getSupply` method
+    /// will be rounded down to the nearest value within this error threshold. Since fees are
+    /// burned with every slot, this is required to achieve consensus between providers that
+    /// are at slightly different slots. Defaults to [`GetSupplyRpcConfig::DEFAULT_ROUNDING_ERROR`].
+    #[serde(rename = "roundingError")]
+    pub rounding_error: Option<RoundingError>,
+}
+
+impl GetSupplyRpcConfig {
+    /// The rounding error used when none is specified, i.e. 1'000 SOL, which is several orders
+    /// of magnitude more than the fees burned during the default slot rounding window.
+    pub const DEFAULT_ROUNDING_ERROR: u64 = 1_000_000_000_000;
+}
+
+impl From<GetSupplyRpcConfig> for RpcConfig {
+    fn from(config: GetSupplyRpcConfig) -> Self {
+        RpcConfig {
+            response_size_estimate: config.response_size_estimate,
+            response_consensus: config.response_consensus,
+            priority: config.priority,
+        }
+    }
+}
+
+impl From<RpcConfig> for GetSupplyRpcConfig {
+    fn from(value: RpcConfig) -> Self {
+        GetSupplyRpcConfig {
+            response_size_estimate: value.response_size_estimate,
+            response_consensus: value.response_consensus,
+            priority: value.priority,
+            ..Default::default()
+        }
+    }
+}
+
+/// Configures how to perform HTTP calls for the `jsonRequest` endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Default, CandidType, Deserialize)]
+pub struct JsonRequestRpcConfig {
+    /// Describes the expected (90th percentile) number of bytes in the HTTP response body.
+    /// This number should be less than `MAX_PAYLOAD_SIZE`.
+    #[serde(rename = "responseSizeEstimate")]
+    pub response_size_estimate: Option<u64>,
+
+    /// Specifies how the responses of the different RPC providers should be aggregated into
+    /// a single response.
+    #[serde(rename = "responseConsensus")]
+    pub response_consensus: Option<ConsensusStrategy>,
+
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
+    /// When `true`, the response body is not normalized by the SOL RPC canister before being
+    /// returned, e.g. object keys are not sorted and numbers are not canonicalized. This is
+    /// useful to debug discrepancies between providers, but since unnormalized responses can
+    /// hardly be compared, it is only supported for requests to a single provider.
+    /// Defaults to `false`.
+    #[serde(rename = "rawResponses")]
+    pub raw_responses: Option<bool>,
+}
+
+impl From<JsonRequestRpcConfig> for RpcConfig {
+    fn from(config: JsonRequestRpcConfig) -> Self {
+        RpcConfig {
+            response_size_estimate: config.response_size_estimate,
+            response_consensus: config.response_consensus,
+            priority: config.priority,
+        }
+    }
+}
+
+impl From<RpcConfig> for JsonRequestRpcConfig {
+    fn from(value: RpcConfig) -> Self {
+        JsonRequestRpcConfig {
+            response_size_estimate: value.response_size_estimate,
+            response_consensus: value.response_consensus,
+            priority: value.priority,
+            ..Default::default()
+        }
+    }
+}
+
+/// Configures how to perform HTTP calls for Solana RPC methods whose result is returned together
+/// with the slot at which it was evaluated, e.g. `getBalanceWithContext`.
+#[derive(Clone, Debug, PartialEq, Eq, Default, CandidType, Deserialize)]
+pub struct WithContextRpcConfig {
+    /// Describes the expected (90th percentile) number of bytes in the HTTP response body.
+    /// This number should be less than `MAX_PAYLOAD_SIZE`.
+    #[serde(rename = "responseSizeEstimate")]
+    pub response_size_estimate: Option<u64>,
+
+    /// Specifies how the responses of the different RPC providers should be aggregated into
+    /// a single response.
+    #[serde(rename = "responseConsensus")]
+    pub response_consensus: Option<ConsensusStrategy>,
+
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
+    /// The context slot returned by each provider will be rounded down to the nearest value
+    /// within this error threshold. Similarly to `getSlot`, this is required to achieve consensus
+    /// since providers are rarely at the exact same slot.
+    #[serde(rename = "contextSlotRoundingError")]
+    pub context_slot_rounding_error: Option<RoundingError>,
+}
+
+impl From<WithContextRpcConfig> for RpcConfig {
+    fn from(config: WithContextRpcConfig) -> Self {
+        RpcConfig {
+            response_size_estimate: config.response_size_estimate,
+            response_consensus: config.response_consensus,
+            priority: config.priority,
+        }
+    }
+}
+
+impl From<RpcConfig> for WithContextRpcConfig {
+    fn from(value: RpcConfig) -> Self {
+        WithContextRpcConfig {
+            response_size_estimate: value.response_size_estimate,
+            response_consensus: value.response_consensus,
+            priority: value.priority,
             ..Default::default()
         }
     }
@@ -204,6 +575,10 @@ pub struct GetRecentPrioritizationFeesRpcConfig {
     #[serde(rename = "responseConsensus")]
     pub response_consensus: Option<ConsensusStrategy>,
 
+    /// Scheduling lane of the request, see [`RequestPriority`].
+    /// Defaults to [`RequestPriority::Normal`].
+    pub priority: Option<RequestPriority>,
+
     /// Round down the slot with the maximum value.
     /// Increasing that value will reduce the freshness of the returned prioritization fees
     /// but increase the likelihood of nodes reaching consensus.
@@ -244,6 +619,11 @@ impl GetRecentPrioritizationFeesRpcConfig {
     pub fn set_response_size_estimate(&mut self, response_size_estimate: u64) {
         self.response_size_estimate = Some(response_size_estimate);
     }
+
+    /// Change the `priority` value.
+    pub fn set_priority(&mut self, priority: RequestPriority) {
+        self.priority = Some(priority);
+    }
 }
 
 impl From<RpcConfig> for GetRecentPrioritizationFeesRpcConfig {
@@ -251,6 +631,7 @@ impl From<RpcConfig> for GetRecentPrioritizationFeesRpcConfig {
         GetRecentPrioritizationFeesRpcConfig {
             response_size_estimate: value.response_size_estimate,
             response_consensus: value.response_consensus,
+            priority: value.priority,
             ..Default::default()
         }
     }
@@ -274,18 +655,129 @@ pub enum ConsensusStrategy {
         /// Minimum number of providers that must return the same (non-error) result.
         min: u8,
     },
+
+    /// The median of the (non-error) results returned by a subset of providers is taken.
+    ///
+    /// This strategy is only supported for methods returning a single integer, such as
+    /// `getSlot` or `getBalance`, and will result in a validation error otherwise.
+    /// Since the returned value is always one of the values returned by the providers,
+    /// a minority of faulty providers cannot skew the result arbitrarily.
+    MedianNumeric {
+        /// Total number of providers to be queried. See [`ConsensusStrategy::Threshold`].
+        total: Option<u8>,
+
+        /// Minimum number of providers that must return a (non-error) result.
+        min: u8,
+    },
+
+    /// The most recent result reported by a supermajority (strictly more than two thirds)
+    /// of the queried providers is taken.
+    ///
+    /// This strategy is only supported for methods returning a slot (e.g. `getSlot`) or a value
+    /// together with its context slot (e.g. `getBalanceWithContext`), and will result in a
+    /// validation error otherwise. The returned slot is the highest slot reached by a
+    /// supermajority of providers. For methods returning a value with its context slot, those
+    /// providers must additionally agree on the value.
+    SupermajorityBySlot {
+        /// Total number of providers to be queried. See [`ConsensusStrategy::Threshold`].
+        total: Option<u8>,
+    },
+
+    /// The results of all queried providers are returned without being reduced, so that the
+    /// caller can apply its own consensus logic.
+    ///
+    /// Unless all providers returned the same result, or the same error, which is then returned as
+    /// a [`crate::MultiRpcResult::Consistent`], the results are returned in a
+    /// [`crate::MultiRpcResult::Inconsistent`], even if most providers agree.
+    CollectAll {
+        /// Total number of providers to be queried. See [`ConsensusStrategy::Threshold`].
+        total: Option<u8>,
+    },
+}
+
+/// The parameters for a call to the `rawHttpRequest` endpoint of the SOL RPC canister.
+///
+/// The request is sent as an HTTP `POST` request to the URL of each selected provider, without
+/// being interpreted as a JSON-RPC request. This allows using provider-specific extensions that
+/// are not part of the Solana JSON-RPC API.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, CandidType)]
+pub struct RawHttpRequestParams {
+    /// The HTTP request body.
+    pub body: Vec<u8>,
+    /// Additional HTTP headers to include in the request. If no `Content-Type` header is given,
+    /// the request is sent with `Content-Type: application/json`.
+    pub headers: Option<Vec<HttpHeader>>,
+    /// Names of the HTTP response headers to return, compared case-insensitively.
+    /// All other response headers are dropped, since their values (e.g. `Date`) typically
+    /// prevent reaching consensus. By default, no response header is returned.
+    #[serde(rename = "responseHeaders")]
+    pub response_headers: Option<Vec<String>>,
 }
 
 /// An API defining how to make an HTTP RPC request.
+///
+/// Use [`RpcEndpoint::new`] and the `with_*` methods to construct an [`RpcEndpoint`], so that
+/// adding new optional fields does not break existing code.
+///
+/// # Examples
+///
+/// ```rust
+/// use sol_rpc_types::{RpcEndpoint, RpcEndpointApiKey, RpcEndpointAuth};
+///
+/// let endpoint = RpcEndpoint::new("https://my-solana-rpc.com/").with_auth(
+///     RpcEndpointAuth::BearerToken {
+///         api_key: RpcEndpointApiKey::Value("my-api-key".to_string()),
+///     },
+/// );
+///
+/// assert_eq!(endpoint.url, "https://my-solana-rpc.com/");
+/// assert_eq!(endpoint.headers, None);
+/// ```
 #[derive(Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize, CandidType)]
+#[non_exhaustive]
 pub struct RpcEndpoint {
     /// The request URL to use when accessing the API.
     pub url: String,
     /// The HTTP headers to include in the requests to the API.
     pub headers: Option<Vec<HttpHeader>>,
+    /// The authentication method to use when accessing the API, if any.
+    pub auth: Option<RpcEndpointAuth>,
+    /// The format of the JSON-RPC request IDs sent to the API.
+    /// Default is `RequestIdFormat::ConstantSizeString`.
+    #[serde(rename = "requestIdFormat")]
+    pub request_id_format: Option<RequestIdFormat>,
 }
 
 impl RpcEndpoint {
+    /// Creates an [`RpcEndpoint`] for the given URL, without additional headers or
+    /// authentication.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            headers: None,
+            auth: None,
+            request_id_format: None,
+        }
+    }
+
+    /// Sets the HTTP headers to include in the requests to the API.
+    pub fn with_headers(mut self, headers: Vec<HttpHeader>) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
+    /// Sets the authentication method to use when accessing the API.
+    pub fn with_auth(mut self, auth: RpcEndpointAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Sets the format of the JSON-RPC request IDs sent to the API.
+    pub fn with_request_id_format(mut self, request_id_format: RequestIdFormat) -> Self {
+        self.request_id_format = Some(request_id_format);
+        self
+    }
+
     /// Returns the [`RpcEndpoint::url`]'s host.
     pub fn host_str(&self) -> Option<String> {
         url::Url::parse(&self.url)
@@ -301,6 +793,62 @@ impl Debug for RpcEndpoint {
     }
 }
 
+/// Defines the authentication method for a custom [`RpcEndpoint`].
+///
+/// Contrary to API keys embedded directly in [`RpcEndpoint::url`] or [`RpcEndpoint::headers`],
+/// the API key is only inserted in the request by the SOL RPC canister right before the HTTPs outcall.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize, CandidType,
+)]
+pub enum RpcEndpointAuth {
+    /// API key will be used in an Authorization header as Bearer token, e.g.,
+    /// `Authorization: Bearer API_KEY`
+    BearerToken {
+        /// The API key to use.
+        #[serde(rename = "apiKey")]
+        api_key: RpcEndpointApiKey,
+    },
+    /// API key will be inserted as a parameter into the request URL, which must then contain
+    /// the `{API_KEY}` placeholder, e.g. `https://my-solana-rpc.com/?api-key={API_KEY}`.
+    UrlParameter {
+        /// The API key to use.
+        #[serde(rename = "apiKey")]
+        api_key: RpcEndpointApiKey,
+    },
+}
+
+impl RpcEndpointAuth {
+    /// Returns the API key used by this authentication method.
+    pub fn api_key(&self) -> &RpcEndpointApiKey {
+        match self {
+            RpcEndpointAuth::BearerToken { api_key }
+            | RpcEndpointAuth::UrlParameter { api_key } => api_key,
+        }
+    }
+}
+
+/// Defines the API key used to authenticate requests to a custom [`RpcEndpoint`].
+#[derive(Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize, CandidType)]
+pub enum RpcEndpointApiKey {
+    /// Use the given API key.
+    Value(String),
+    /// Use the API key stored in the SOL RPC canister for [`RpcEndpoint::url`].
+    ///
+    /// Such API keys are managed by the same principals that manage API keys of supported providers,
+    /// with the `updateCustomApiKeys` endpoint. Since a stored API key is only ever sent to the exact
+    /// URL it was registered for, it cannot be leaked by callers using a different URL or path.
+    Stored,
+}
+
+impl Debug for RpcEndpointApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcEndpointApiKey::Value(_) => write!(f, "Value(***)"), // Redact credentials
+            RpcEndpointApiKey::Stored => write!(f, "Stored"),
+        }
+    }
+}
+
 /// [Solana clusters](https://solana.com/docs/references/clusters).
 #[derive(
     Copy,
@@ -401,6 +949,8 @@ impl RpcSource {
 )]
 pub enum RpcSources {
     /// A collection of [`RpcSource`] (either [`RpcSource::Supported`] or [`RpcSource::Custom`]).
+    ///
+    /// All [`RpcSource::Supported`] providers must belong to the same [`SolanaCluster`].
     Custom(Vec<RpcSource>),
     /// Use the default supported providers for the given [`SolanaCluster`].
     Default(SolanaCluster),
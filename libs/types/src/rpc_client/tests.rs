@@ -1,4 +1,4 @@
-use crate::{HttpHeader, RpcEndpoint};
+use crate::{HttpHeader, RpcEndpoint, RpcEndpointApiKey, RpcEndpointAuth};
 use candid::{CandidType, Decode, Encode};
 use proptest::{prelude::TestCaseError, prop_assert_eq};
 use serde::de::DeserializeOwned;
@@ -9,10 +9,14 @@ fn should_contain_host_without_sensitive_information() {
         RpcEndpoint {
             url: "https://solana-mainnet.g.alchemy.com/v2".to_string(),
             headers: None,
+            auth: None,
+            request_id_format: None,
         },
         RpcEndpoint {
             url: "https://solana-mainnet.g.alchemy.com/v2/key".to_string(),
             headers: None,
+            auth: None,
+            request_id_format: None,
         },
         RpcEndpoint {
             url: "https://solana-mainnet.g.alchemy.com/v2".to_string(),
@@ -20,6 +24,16 @@ fn should_contain_host_without_sensitive_information() {
                 name: "authorization".to_string(),
                 value: "Bearer key".to_string(),
             }]),
+            auth: None,
+            request_id_format: None,
+        },
+        RpcEndpoint {
+            url: "https://solana-mainnet.g.alchemy.com/v2".to_string(),
+            headers: None,
+            auth: Some(RpcEndpointAuth::BearerToken {
+                api_key: RpcEndpointApiKey::Value("key".to_string()),
+            }),
+            request_id_format: None,
         },
     ] {
         let debug = format!("{:?}", provider);
@@ -30,6 +44,21 @@ fn should_contain_host_without_sensitive_information() {
     }
 }
 
+#[test]
+fn should_redact_custom_api_key() {
+    let auth = RpcEndpointAuth::UrlParameter {
+        api_key: RpcEndpointApiKey::Value("secret-key".to_string()),
+    };
+
+    let debug = format!("{:?}", auth);
+
+    assert_eq!(debug, "UrlParameter { api_key: Value(***) }");
+    assert_eq!(
+        format!("{:?}", RpcEndpointApiKey::Stored),
+        "Stored".to_string()
+    );
+}
+
 mod rounding_error_tests {
     use crate::{rpc_client::tests::encode_decode_roundtrip, RoundingError};
     use proptest::proptest;
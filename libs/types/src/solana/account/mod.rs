@@ -1,5 +1,9 @@
+use crate::Pubkey;
+#[cfg(feature = "solana-compat")]
+use crate::RpcError;
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
+#[cfg(feature = "solana-compat")]
 use solana_account_decoder_client_types::UiAccountEncoding;
 
 /// Solana [account](https://solana.com/docs/references/terminology#account) information.
@@ -20,6 +24,7 @@ pub struct AccountInfo {
     pub space: u64,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_account_decoder_client_types::UiAccount> for AccountInfo {
     fn from(account: solana_account_decoder_client_types::UiAccount) -> Self {
         AccountInfo {
@@ -35,6 +40,7 @@ impl From<solana_account_decoder_client_types::UiAccount> for AccountInfo {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<AccountInfo> for solana_account_decoder_client_types::UiAccount {
     fn from(account: AccountInfo) -> Self {
         solana_account_decoder_client_types::UiAccount {
@@ -48,6 +54,47 @@ impl From<AccountInfo> for solana_account_decoder_client_types::UiAccount {
     }
 }
 
+#[cfg(feature = "solana-compat")]
+/// Parses the `value` of a raw JSON-RPC `getAccountInfo` response, as returned by Solana RPC
+/// providers.
+impl TryFrom<serde_json::Value> for AccountInfo {
+    type Error = RpcError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let account: solana_account_decoder_client_types::UiAccount =
+            serde_json::from_value(value)?;
+        if account.space.is_none() {
+            return Err(RpcError::ValidationError(
+                "Missing 'space' field in account".to_string(),
+            ));
+        }
+        Ok(Self::from(account))
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+/// Serializes an [`AccountInfo`] in the JSON format of the Solana JSON-RPC API.
+impl From<AccountInfo> for serde_json::Value {
+    fn from(account: AccountInfo) -> Self {
+        serde_json::to_value(solana_account_decoder_client_types::UiAccount::from(
+            account,
+        ))
+        .expect("BUG: failed to serialize account as JSON")
+    }
+}
+
+/// A Solana [account](https://solana.com/docs/references/terminology#account) together with its
+/// address, as returned e.g. by the
+/// [`getTokenAccountsByDelegate`](https://solana.com/docs/rpc/http/gettokenaccountsbydelegate)
+/// RPC method.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct KeyedAccount {
+    /// The account address.
+    pub pubkey: Pubkey,
+    /// The account information.
+    pub account: AccountInfo,
+}
+
 /// Represents the data stored in a Solana [account](https://solana.com/docs/references/terminology#account).
 #[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
 pub enum AccountData {
@@ -63,6 +110,7 @@ pub enum AccountData {
     Binary(String, AccountEncoding),
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_account_decoder_client_types::UiAccountData> for AccountData {
     fn from(data: solana_account_decoder_client_types::UiAccountData) -> Self {
         use solana_account_decoder_client_types::UiAccountData;
@@ -74,6 +122,7 @@ impl From<solana_account_decoder_client_types::UiAccountData> for AccountData {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<AccountData> for solana_account_decoder_client_types::UiAccountData {
     fn from(data: AccountData) -> Self {
         use solana_account_decoder_client_types::UiAccountData;
@@ -96,6 +145,7 @@ pub struct ParsedAccount {
     pub space: u64,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_account_decoder_client_types::ParsedAccount> for ParsedAccount {
     fn from(account: solana_account_decoder_client_types::ParsedAccount) -> Self {
         Self {
@@ -107,6 +157,7 @@ impl From<solana_account_decoder_client_types::ParsedAccount> for ParsedAccount
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<ParsedAccount> for solana_account_decoder_client_types::ParsedAccount {
     fn from(account: ParsedAccount) -> Self {
         Self {
@@ -138,6 +189,7 @@ pub enum AccountEncoding {
     JsonParsed,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<UiAccountEncoding> for AccountEncoding {
     fn from(encoding: UiAccountEncoding) -> Self {
         use solana_account_decoder_client_types::UiAccountEncoding;
@@ -151,6 +203,7 @@ impl From<UiAccountEncoding> for AccountEncoding {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<AccountEncoding> for UiAccountEncoding {
     fn from(encoding: AccountEncoding) -> Self {
         match encoding {
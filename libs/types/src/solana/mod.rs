@@ -5,7 +5,10 @@ pub mod account;
 pub mod request;
 pub mod transaction;
 
-use crate::{EncodedTransactionWithStatusMeta, Reward, RpcError};
+use crate::{
+    AccountInfo, ConfirmedTransactionStatusWithSignature, EncodedTransactionWithStatusMeta, Reward,
+    RpcError,
+};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::{fmt::Debug, str::FromStr};
@@ -59,6 +62,7 @@ pub struct ConfirmedBlock {
     pub transactions: Option<Vec<EncodedTransactionWithStatusMeta>>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction_status_client_types::UiConfirmedBlock> for ConfirmedBlock {
     type Error = RpcError;
 
@@ -90,6 +94,7 @@ impl TryFrom<solana_transaction_status_client_types::UiConfirmedBlock> for Confi
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<ConfirmedBlock> for solana_transaction_status_client_types::UiConfirmedBlock {
     fn from(block: ConfirmedBlock) -> Self {
         Self {
@@ -118,6 +123,28 @@ impl From<ConfirmedBlock> for solana_transaction_status_client_types::UiConfirme
     }
 }
 
+/// Parses the `result` of a raw JSON-RPC `getBlock` response, as returned by Solana RPC
+/// providers.
+#[cfg(feature = "solana-compat")]
+impl TryFrom<serde_json::Value> for ConfirmedBlock {
+    type Error = RpcError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let block: solana_transaction_status_client_types::UiConfirmedBlock =
+            serde_json::from_value(value)?;
+        Self::try_from(block)
+    }
+}
+
+/// Serializes a [`ConfirmedBlock`] in the JSON format of the Solana JSON-RPC API.
+#[cfg(feature = "solana-compat")]
+impl From<ConfirmedBlock> for serde_json::Value {
+    fn from(block: ConfirmedBlock) -> Self {
+        serde_json::to_value(solana_transaction_status_client_types::UiConfirmedBlock::from(block))
+            .expect("BUG: failed to serialize block as JSON")
+    }
+}
+
 /// An entry in the result of a Solana `getRecentPrioritizationFees` RPC method call.
 #[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
 pub struct PrioritizationFee {
@@ -129,46 +156,335 @@ pub struct PrioritizationFee {
     pub prioritization_fee: MicroLamport,
 }
 
+/// The result of a Solana `getHighestSnapshotSlot` RPC method call.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq, Eq)]
+pub struct SnapshotSlotInfo {
+    /// The highest full snapshot slot.
+    pub full: Slot,
+    /// The highest incremental snapshot slot based on `full`, if any.
+    pub incremental: Option<Slot>,
+}
+
+/// The result of a Solana `getSupply` RPC method call.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq, Eq)]
+pub struct Supply {
+    /// Total supply in lamports.
+    pub total: Lamport,
+    /// Circulating supply in lamports.
+    pub circulating: Lamport,
+    /// Non-circulating supply in lamports.
+    #[serde(rename = "nonCirculating")]
+    pub non_circulating: Lamport,
+    /// Addresses of the non-circulating accounts, sorted in ascending order. Empty if
+    /// `excludeNonCirculatingAccountsList` was set.
+    #[serde(rename = "nonCirculatingAccounts")]
+    pub non_circulating_accounts: Vec<Pubkey>,
+}
+
+/// The result of a call to the `getAccountSnapshot` endpoint of the SOL RPC canister.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct AccountSnapshot {
+    /// Balance of the account in lamports, as returned by `getBalance`.
+    pub balance: Lamport,
+    /// Information about the account as returned by `getAccountInfo`, or `None` if the account
+    /// does not exist.
+    pub account: Option<AccountInfo>,
+    /// Most recent transaction signatures involving the account, as returned by
+    /// `getSignaturesForAddress`.
+    pub signatures: Vec<ConfirmedTransactionStatusWithSignature>,
+}
+
+/// An entry in the result of a Solana `getRecentPerformanceSamples` RPC method call.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq, Eq)]
+pub struct PerformanceSample {
+    /// Slot in which the sample was taken.
+    pub slot: Slot,
+    /// Number of transactions processed during the sample period.
+    #[serde(rename = "numTransactions")]
+    pub num_transactions: u64,
+    /// Number of non-vote transactions processed during the sample period.
+    #[serde(rename = "numNonVoteTransactions")]
+    pub num_non_vote_transactions: Option<u64>,
+    /// Number of slots completed during the sample period.
+    #[serde(rename = "numSlots")]
+    pub num_slots: u64,
+    /// Number of seconds in the sample window.
+    #[serde(rename = "samplePeriodSecs")]
+    pub sample_period_secs: u16,
+}
+
+impl PerformanceSample {
+    /// The average number of transactions processed per second during the sample period, or
+    /// `None` if the sample period is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sol_rpc_types::PerformanceSample;
+    ///
+    /// let sample = PerformanceSample {
+    ///     slot: 348_125_000,
+    ///     num_transactions: 240_000,
+    ///     num_non_vote_transactions: Some(60_000),
+    ///     num_slots: 150,
+    ///     sample_period_secs: 60,
+    /// };
+    ///
+    /// assert_eq!(sample.transactions_per_second(), Some(4_000.0));
+    /// assert_eq!(sample.non_vote_transactions_per_second(), Some(1_000.0));
+    /// ```
+    pub fn transactions_per_second(&self) -> Option<f64> {
+        per_second(self.num_transactions, self.sample_period_secs)
+    }
+
+    /// The average number of non-vote transactions processed per second during the sample
+    /// period, or `None` if the sample period is empty or the number of non-vote transactions
+    /// was not reported.
+    pub fn non_vote_transactions_per_second(&self) -> Option<f64> {
+        per_second(self.num_non_vote_transactions?, self.sample_period_secs)
+    }
+}
+
+fn per_second(count: u64, period_secs: u16) -> Option<f64> {
+    (period_secs > 0).then(|| count as f64 / f64::from(period_secs))
+}
+
+/// The result of a Solana `getEpochInfo` RPC method call.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq, Eq)]
+pub struct EpochInfo {
+    /// The current epoch.
+    pub epoch: u64,
+    /// The current slot, relative to the start of the current epoch.
+    #[serde(rename = "slotIndex")]
+    pub slot_index: u64,
+    /// The number of slots in this epoch.
+    #[serde(rename = "slotsInEpoch")]
+    pub slots_in_epoch: u64,
+    /// The current slot.
+    #[serde(rename = "absoluteSlot")]
+    pub absolute_slot: Slot,
+    /// The current block height.
+    #[serde(rename = "blockHeight")]
+    pub block_height: u64,
+    /// Total number of transactions processed without error since genesis.
+    #[serde(rename = "transactionCount")]
+    pub transaction_count: Option<u64>,
+}
+
+/// The result of a Solana `getEpochSchedule` RPC method call.
+///
+/// Besides the raw epoch schedule, this type provides helpers to compute the boundaries of an
+/// epoch, e.g. to schedule work around the end of the current epoch, taking into account the
+/// shorter epochs of the warmup period, if any.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq, Eq)]
+pub struct EpochSchedule {
+    /// The maximum number of slots in each epoch.
+    #[serde(rename = "slotsPerEpoch")]
+    pub slots_per_epoch: u64,
+    /// The number of slots before the beginning of an epoch to calculate a leader schedule for
+    /// that epoch.
+    #[serde(rename = "leaderScheduleSlotOffset")]
+    pub leader_schedule_slot_offset: u64,
+    /// Whether epochs start short and grow.
+    pub warmup: bool,
+    /// The first epoch with `slots_per_epoch` slots, i.e. after the warmup period.
+    #[serde(rename = "firstNormalEpoch")]
+    pub first_normal_epoch: u64,
+    /// The first slot of `first_normal_epoch`.
+    #[serde(rename = "firstNormalSlot")]
+    pub first_normal_slot: Slot,
+}
+
+impl EpochSchedule {
+    /// The minimum number of slots in an epoch, i.e. the number of slots in the first epoch
+    /// of the warmup period.
+    pub const MINIMUM_SLOTS_PER_EPOCH: u64 = 32;
+
+    /// Returns the number of slots in the given epoch.
+    pub fn slots_in_epoch(&self, epoch: u64) -> u64 {
+        if epoch < self.first_normal_epoch {
+            2_u64.saturating_pow(
+                (epoch as u32).saturating_add(Self::MINIMUM_SLOTS_PER_EPOCH.trailing_zeros()),
+            )
+        } else {
+            self.slots_per_epoch
+        }
+    }
+
+    /// Returns the epoch containing the given slot.
+    pub fn epoch(&self, slot: Slot) -> u64 {
+        self.epoch_and_slot_index(slot).0
+    }
+
+    /// Returns the epoch containing the given slot, and the index of the slot relative to the
+    /// first slot of that epoch.
+    pub fn epoch_and_slot_index(&self, slot: Slot) -> (u64, u64) {
+        if slot < self.first_normal_slot {
+            let epoch = slot
+                .saturating_add(Self::MINIMUM_SLOTS_PER_EPOCH)
+                .saturating_add(1)
+                .next_power_of_two()
+                .trailing_zeros()
+                .saturating_sub(Self::MINIMUM_SLOTS_PER_EPOCH.trailing_zeros())
+                .saturating_sub(1);
+            let epoch_len = 2_u64.saturating_pow(
+                epoch.saturating_add(Self::MINIMUM_SLOTS_PER_EPOCH.trailing_zeros()),
+            );
+            (
+                u64::from(epoch),
+                slot.saturating_sub(epoch_len.saturating_sub(Self::MINIMUM_SLOTS_PER_EPOCH)),
+            )
+        } else {
+            let normal_slot_index = slot.saturating_sub(self.first_normal_slot);
+            let normal_epoch_index = normal_slot_index
+                .checked_div(self.slots_per_epoch)
+                .unwrap_or(0);
+            let epoch = self.first_normal_epoch.saturating_add(normal_epoch_index);
+            let slot_index = normal_slot_index
+                .checked_rem(self.slots_per_epoch)
+                .unwrap_or(0);
+            (epoch, slot_index)
+        }
+    }
+
+    /// Returns the first slot of the given epoch.
+    pub fn first_slot_in_epoch(&self, epoch: u64) -> Slot {
+        if epoch <= self.first_normal_epoch {
+            2_u64
+                .saturating_pow(epoch as u32)
+                .saturating_sub(1)
+                .saturating_mul(Self::MINIMUM_SLOTS_PER_EPOCH)
+        } else {
+            epoch
+                .saturating_sub(self.first_normal_epoch)
+                .saturating_mul(self.slots_per_epoch)
+                .saturating_add(self.first_normal_slot)
+        }
+    }
+
+    /// Returns the last slot of the given epoch.
+    pub fn last_slot_in_epoch(&self, epoch: u64) -> Slot {
+        self.first_slot_in_epoch(epoch)
+            .saturating_add(self.slots_in_epoch(epoch))
+            .saturating_sub(1)
+    }
+
+    /// Returns the number of slots after the given slot until the end of its epoch, i.e. `0`
+    /// if the given slot is the last slot of its epoch.
+    pub fn slots_remaining_in_epoch(&self, slot: Slot) -> u64 {
+        self.last_slot_in_epoch(self.epoch(slot))
+            .saturating_sub(slot)
+    }
+}
+
+/// A node participating in the cluster, as returned by a Solana `getClusterNodes` RPC method
+/// call.
+///
+/// Only a bounded subset of the fields returned by the Solana JSON-RPC API is kept, so that the
+/// nodes of a large cluster fit in a response of the SOL RPC canister.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq, Eq)]
+pub struct ClusterNode {
+    /// Node public key.
+    pub pubkey: Pubkey,
+    /// Gossip network address for the node.
+    pub gossip: Option<String>,
+    /// The software version of the node, or `None` if the version information is not available.
+    pub version: Option<String>,
+}
+
+/// The result of a Solana `getVersion` RPC method call.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq, Eq)]
+pub struct SolanaVersion {
+    /// Software version of `solana-core`.
+    #[serde(rename = "solanaCore")]
+    pub solana_core: String,
+    /// Unique identifier of the current software's feature set.
+    #[serde(rename = "featureSet")]
+    pub feature_set: Option<u32>,
+}
+
 macro_rules! impl_candid {
-    ($name: ident($data: ty), $error: ty) => {
-        #[doc = concat!("Candid wrapper around `", stringify!($data), "`. ")]
-        #[derive(Clone, Default, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+    ($name: ident([u8; $len: literal]), $error: ident, $description: literal, $solana: ty, $solana_error: ty) => {
+        #[doc = concat!("A Solana ", $description, ", encoded as a base58 string in Candid and JSON.")]
+        ///
+        #[doc = concat!("With the `solana-compat` feature, it can be converted from and into `", stringify!($solana), "`.")]
+        #[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
         #[serde(try_from = "String", into = "String")]
-        pub struct $name($data);
+        pub struct $name([u8; $len]);
+
+        impl $name {
+            #[doc = concat!("Returns the bytes of the ", $description, ".")]
+            pub const fn to_bytes(&self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self([0; $len])
+            }
+        }
 
         impl std::fmt::Display for $name {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                write!(f, "{}", self.0)
+                f.write_str(&bs58::encode(self.0).into_string())
             }
         }
 
         impl std::fmt::Debug for $name {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                write!(f, "{:?}", self.0)
+                std::fmt::Display::fmt(self, f)
             }
         }
 
-        impl From<$data> for $name {
-            fn from(value: $data) -> Self {
+        impl From<[u8; $len]> for $name {
+            fn from(value: [u8; $len]) -> Self {
                 Self(value)
             }
         }
 
-        impl From<&$data> for $name {
-            fn from(value: &$data) -> Self {
-                Self(*value)
+        impl From<$name> for [u8; $len] {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        #[cfg(feature = "solana-compat")]
+        impl From<$solana> for $name {
+            fn from(value: $solana) -> Self {
+                Self(
+                    value
+                        .as_ref()
+                        .try_into()
+                        .expect(concat!("BUG: invalid ", $description, " length")),
+                )
             }
         }
 
-        impl From<$name> for $data {
+        #[cfg(feature = "solana-compat")]
+        impl From<&$solana> for $name {
+            fn from(value: &$solana) -> Self {
+                Self::from(*value)
+            }
+        }
+
+        #[cfg(feature = "solana-compat")]
+        impl From<$name> for $solana {
             fn from(value: $name) -> Self {
-                value.0
+                <$solana>::from(value.0)
+            }
+        }
+
+        #[cfg(feature = "solana-compat")]
+        impl From<$solana_error> for RpcError {
+            fn from(e: $solana_error) -> Self {
+                RpcError::ValidationError(format!(concat!("Invalid ", $description, ": {}"), e))
             }
         }
 
         impl AsRef<[u8]> for $name {
             fn as_ref(&self) -> &[u8] {
-                self.0.as_ref()
+                &self.0
             }
         }
 
@@ -185,11 +501,35 @@ macro_rules! impl_candid {
             }
         }
 
+        #[doc = concat!("An error parsing a [`", stringify!($name), "`] from a base58 string.")]
+        #[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+        pub enum $error {
+            /// The string does not encode the expected number of bytes.
+            #[error("String is the wrong size")]
+            WrongSize,
+            /// The string is not valid base58.
+            #[error("Invalid Base58 string")]
+            Invalid,
+        }
+
+        impl From<$error> for RpcError {
+            fn from(e: $error) -> Self {
+                RpcError::ValidationError(format!(concat!("Invalid ", $description, ": {}"), e))
+            }
+        }
+
         impl std::str::FromStr for $name {
             type Err = $error;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
-                s.parse::<$data>().map(Self)
+                // Every byte takes at most log(256)/log(58) < 1.37 base58 characters.
+                if s.len() > $len * 137 / 100 + 1 {
+                    return Err($error::WrongSize);
+                }
+                let bytes = bs58::decode(s).into_vec().map_err(|_| $error::Invalid)?;
+                <[u8; $len]>::try_from(bytes)
+                    .map(Self)
+                    .map_err(|_| $error::WrongSize)
             }
         }
 
@@ -210,17 +550,30 @@ macro_rules! impl_candid {
 }
 
 impl_candid!(
-    Pubkey(solana_pubkey::Pubkey),
+    Pubkey([u8; 32]),
+    ParsePubkeyError,
+    "public key",
+    solana_pubkey::Pubkey,
     solana_pubkey::ParsePubkeyError
 );
 
 impl_candid!(
-    Signature(solana_signature::Signature),
+    Signature([u8; 64]),
+    ParseSignatureError,
+    "signature",
+    solana_signature::Signature,
     solana_signature::ParseSignatureError
 );
 
-impl_candid!(Hash(solana_hash::Hash), solana_hash::ParseHashError);
+impl_candid!(
+    Hash([u8; 32]),
+    ParseHashError,
+    "hash",
+    solana_hash::Hash,
+    solana_hash::ParseHashError
+);
 
+#[cfg(feature = "solana-compat")]
 fn parse_vec<T, E>(values: Vec<String>) -> Result<Vec<T>, E>
 where
     T: FromStr<Err = E>,
@@ -228,6 +581,7 @@ where
     values.into_iter().map(|v| v.parse()).collect()
 }
 
+#[cfg(feature = "solana-compat")]
 fn parse_opt<V, T, E>(value: V) -> Result<Option<T>, E>
 where
     V: Into<Option<String>>,
@@ -236,6 +590,7 @@ where
     value.into().map(|v| v.parse()).transpose()
 }
 
+#[cfg(feature = "solana-compat")]
 fn try_from_vec<U, V, E>(values: Vec<U>) -> Result<Vec<V>, E>
 where
     V: TryFrom<U, Error = E>,
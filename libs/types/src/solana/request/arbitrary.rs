@@ -0,0 +1,830 @@
+//! [`Arbitrary`] implementations for the request parameter types, available with the
+//! `test-strategies` feature.
+//!
+//! The generated values are always valid requests, i.e. they satisfy the constraints enforced
+//! when deserializing them (e.g. maximum number of signatures or limit range).
+
+use crate::{
+    CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetAccountInfoParams,
+    GetAccountSnapshotParams, GetBalanceParams, GetBlockCommitmentLevel, GetBlockEncoding,
+    GetBlockHeightParams, GetBlockParams, GetBlocksLimit, GetBlocksParams,
+    GetBlocksWithLimitParams, GetClusterNodesLimit, GetClusterNodesParams, GetEpochInfoParams,
+    GetFeeForMessageParams, GetLeaderScheduleParams, GetMinimumBalanceForRentExemptionParams,
+    GetRecentPerformanceSamplesLimit, GetRecentPerformanceSamplesParams,
+    GetRecentPrioritizationFeesParams, GetSignatureStatusesParams, GetSignaturesForAddressLimit,
+    GetSignaturesForAddressParams, GetSlotLeadersLimit, GetSlotLeadersParams, GetSlotParams,
+    GetSupplyParams, GetTokenAccountBalanceParams, GetTokenAccountsByDelegateParams,
+    GetTokenAccountsByOwnerLimit, GetTokenAccountsByOwnerParams, GetTransactionCountParams,
+    GetTransactionEncoding, GetTransactionParams, HasAccountChangedParams, Hash, Pubkey,
+    SendTransactionEncoding, SendTransactionParams, Signature, Slot, TokenAccountsFilter,
+    TransactionDetails, VecWithMaxLen,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    collection::vec,
+    option,
+    prelude::{BoxedStrategy, Just, Strategy},
+    prop_oneof,
+};
+
+impl Arbitrary for Pubkey {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<[u8; 32]>().prop_map(Pubkey::from).boxed()
+    }
+}
+
+impl Arbitrary for Signature {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        vec(any::<u8>(), 64)
+            .prop_map(|bytes| {
+                let bytes: [u8; 64] = bytes.try_into().expect("BUG: expected 64 bytes");
+                Signature::from(bytes)
+            })
+            .boxed()
+    }
+}
+
+impl<T: Arbitrary + 'static, const CAPACITY: usize> Arbitrary for VecWithMaxLen<T, CAPACITY> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        vec(any::<T>(), 0..=CAPACITY)
+            .prop_map(|values| {
+                VecWithMaxLen::try_from(values).expect("BUG: vector exceeds capacity")
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CommitmentLevel {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(CommitmentLevel::Processed),
+            Just(CommitmentLevel::Confirmed),
+            Just(CommitmentLevel::Finalized),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for GetBlockCommitmentLevel {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(GetBlockCommitmentLevel::Confirmed),
+            Just(GetBlockCommitmentLevel::Finalized),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for GetAccountInfoEncoding {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(GetAccountInfoEncoding::Base58),
+            Just(GetAccountInfoEncoding::Base64),
+            Just(GetAccountInfoEncoding::Base64ZStd),
+            Just(GetAccountInfoEncoding::JsonParsed),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for DataSlice {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (any::<u32>(), any::<u32>())
+            .prop_map(|(length, offset)| DataSlice { length, offset })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TransactionDetails {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TransactionDetails::Accounts),
+            Just(TransactionDetails::None),
+            Just(TransactionDetails::Signatures),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for GetBlockEncoding {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(GetBlockEncoding::Base64),
+            Just(GetBlockEncoding::Base58),
+            Just(GetBlockEncoding::JsonParsed),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for GetTransactionEncoding {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(GetTransactionEncoding::Base64),
+            Just(GetTransactionEncoding::Base58),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for GetSignaturesForAddressLimit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (1..=GetSignaturesForAddressLimit::MAX_LIMIT)
+            .prop_map(|limit| {
+                GetSignaturesForAddressLimit::try_from(limit).expect("BUG: invalid limit")
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetBlocksLimit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (1..=GetBlocksLimit::MAX_LIMIT)
+            .prop_map(|limit| GetBlocksLimit::try_from(limit).expect("BUG: invalid limit"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetRecentPerformanceSamplesLimit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (1..=GetRecentPerformanceSamplesLimit::MAX_LIMIT)
+            .prop_map(|limit| {
+                GetRecentPerformanceSamplesLimit::try_from(limit).expect("BUG: invalid limit")
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetSlotLeadersLimit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (1..=GetSlotLeadersLimit::MAX_LIMIT)
+            .prop_map(|limit| GetSlotLeadersLimit::try_from(limit).expect("BUG: invalid limit"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetAccountInfoParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Pubkey>(),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<GetAccountInfoEncoding>()),
+            option::of(any::<DataSlice>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(
+                |(pubkey, commitment, encoding, data_slice, min_context_slot)| {
+                    GetAccountInfoParams {
+                        pubkey,
+                        commitment,
+                        encoding,
+                        data_slice,
+                        min_context_slot,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetAccountSnapshotParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Pubkey>(),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+            option::of(any::<GetAccountInfoEncoding>()),
+            option::of(any::<DataSlice>()),
+            option::of(any::<GetSignaturesForAddressLimit>()),
+            option::of(any::<Signature>()),
+            option::of(any::<Signature>()),
+        )
+            .prop_map(
+                |(
+                    pubkey,
+                    commitment,
+                    min_context_slot,
+                    encoding,
+                    data_slice,
+                    signatures_limit,
+                    signatures_before,
+                    signatures_until,
+                )| {
+                    GetAccountSnapshotParams {
+                        pubkey,
+                        commitment,
+                        min_context_slot,
+                        encoding,
+                        data_slice,
+                        signatures_limit,
+                        signatures_before,
+                        signatures_until,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for HasAccountChangedParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Pubkey>(),
+            option::of(any::<[u8; 32]>()),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<DataSlice>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(
+                |(pubkey, last_known_hash, commitment, data_slice, min_context_slot)| {
+                    HasAccountChangedParams {
+                        pubkey,
+                        last_known_hash: last_known_hash.map(Hash::from),
+                        commitment,
+                        data_slice,
+                        min_context_slot,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for TokenAccountsFilter {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<Pubkey>().prop_map(TokenAccountsFilter::Mint),
+            any::<Pubkey>().prop_map(TokenAccountsFilter::ProgramId),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for GetTokenAccountsByDelegateParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Pubkey>(),
+            any::<TokenAccountsFilter>(),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<GetAccountInfoEncoding>()),
+            option::of(any::<DataSlice>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(
+                |(delegate, filter, commitment, encoding, data_slice, min_context_slot)| {
+                    GetTokenAccountsByDelegateParams {
+                        delegate,
+                        filter,
+                        commitment,
+                        encoding,
+                        data_slice,
+                        min_context_slot,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetTokenAccountsByOwnerLimit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (1..=GetTokenAccountsByOwnerLimit::MAX_LIMIT)
+            .prop_map(|limit| {
+                GetTokenAccountsByOwnerLimit::try_from(limit).expect("BUG: invalid limit")
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetTokenAccountsByOwnerParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Pubkey>(),
+            any::<TokenAccountsFilter>(),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<GetAccountInfoEncoding>()),
+            option::of(any::<DataSlice>()),
+            option::of(any::<Slot>()),
+            option::of(any::<Pubkey>()),
+            option::of(any::<GetTokenAccountsByOwnerLimit>()),
+        )
+            .prop_map(
+                |(
+                    owner,
+                    filter,
+                    commitment,
+                    encoding,
+                    data_slice,
+                    min_context_slot,
+                    after,
+                    limit,
+                )| {
+                    GetTokenAccountsByOwnerParams {
+                        owner,
+                        filter,
+                        commitment,
+                        encoding,
+                        data_slice,
+                        min_context_slot,
+                        after,
+                        limit,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetBalanceParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Pubkey>(),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(|(pubkey, commitment, min_context_slot)| GetBalanceParams {
+                pubkey,
+                commitment,
+                min_context_slot,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetBlockParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Slot>(),
+            option::of(any::<GetBlockCommitmentLevel>()),
+            option::of(any::<u8>()),
+            option::of(any::<TransactionDetails>()),
+            option::of(any::<bool>()),
+            option::of(any::<GetBlockEncoding>()),
+        )
+            .prop_map(
+                |(
+                    slot,
+                    commitment,
+                    max_supported_transaction_version,
+                    transaction_details,
+                    rewards,
+                    encoding,
+                )| {
+                    GetBlockParams {
+                        slot,
+                        commitment,
+                        max_supported_transaction_version,
+                        transaction_details,
+                        rewards,
+                        encoding,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetBlockHeightParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(|(commitment, min_context_slot)| GetBlockHeightParams {
+                commitment,
+                min_context_slot,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetBlocksParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Slot>(),
+            option::of(0..GetBlocksLimit::MAX_LIMIT as u64),
+            option::of(any::<GetBlockCommitmentLevel>()),
+        )
+            .prop_map(|(start_slot, range, commitment)| GetBlocksParams {
+                start_slot,
+                end_slot: range.map(|range| start_slot.saturating_add(range)),
+                commitment,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetBlocksWithLimitParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Slot>(),
+            any::<GetBlocksLimit>(),
+            option::of(any::<GetBlockCommitmentLevel>()),
+        )
+            .prop_map(|(start_slot, limit, commitment)| GetBlocksWithLimitParams {
+                start_slot,
+                limit,
+                commitment,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetClusterNodesLimit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (1..=GetClusterNodesLimit::MAX_LIMIT)
+            .prop_map(|limit| GetClusterNodesLimit::try_from(limit).expect("BUG: invalid limit"))
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetClusterNodesParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(any::<Pubkey>()),
+            option::of(any::<GetClusterNodesLimit>()),
+        )
+            .prop_map(|(after, limit)| GetClusterNodesParams { after, limit })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetEpochInfoParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(|(commitment, min_context_slot)| GetEpochInfoParams {
+                commitment,
+                min_context_slot,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetFeeForMessageParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            vec(any::<u8>(), 1..=1_232),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(
+                |(message, commitment, min_context_slot)| GetFeeForMessageParams {
+                    commitment,
+                    min_context_slot,
+                    ..GetFeeForMessageParams::from_encoded_message(BASE64_STANDARD.encode(message))
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetLeaderScheduleParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(any::<Slot>()),
+            any::<Pubkey>(),
+            option::of(any::<CommitmentLevel>()),
+        )
+            .prop_map(|(slot, identity, commitment)| GetLeaderScheduleParams {
+                slot,
+                identity,
+                commitment,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetMinimumBalanceForRentExemptionParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (any::<u64>(), option::of(any::<CommitmentLevel>()))
+            .prop_map(
+                |(data_length, commitment)| GetMinimumBalanceForRentExemptionParams {
+                    data_length,
+                    commitment,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetRecentPerformanceSamplesParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        option::of(any::<GetRecentPerformanceSamplesLimit>())
+            .prop_map(|limit| GetRecentPerformanceSamplesParams { limit })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetRecentPrioritizationFeesParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<VecWithMaxLen<Pubkey, 128>>()
+            .prop_map(GetRecentPrioritizationFeesParams)
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetSignaturesForAddressParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Pubkey>(),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+            option::of(any::<GetSignaturesForAddressLimit>()),
+            option::of(any::<Signature>()),
+            option::of(any::<Signature>()),
+            option::of(any::<Timestamp>()),
+            option::of(any::<Timestamp>()),
+        )
+            .prop_map(
+                |(
+                    pubkey,
+                    commitment,
+                    min_context_slot,
+                    limit,
+                    before,
+                    until,
+                    min_block_time,
+                    max_block_time,
+                )| {
+                    GetSignaturesForAddressParams {
+                        pubkey,
+                        commitment,
+                        min_context_slot,
+                        limit,
+                        before,
+                        until,
+                        min_block_time,
+                        max_block_time,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetSignatureStatusesParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<VecWithMaxLen<Signature, 256>>(),
+            option::of(any::<bool>()),
+        )
+            .prop_map(
+                |(signatures, search_transaction_history)| GetSignatureStatusesParams {
+                    signatures,
+                    search_transaction_history,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetSlotParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(|(commitment, min_context_slot)| GetSlotParams {
+                commitment,
+                min_context_slot,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetSlotLeadersParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (any::<Slot>(), any::<GetSlotLeadersLimit>())
+            .prop_map(|(start_slot, limit)| GetSlotLeadersParams { start_slot, limit })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetSupplyParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<bool>()),
+        )
+            .prop_map(
+                |(commitment, exclude_non_circulating_accounts_list)| GetSupplyParams {
+                    commitment,
+                    exclude_non_circulating_accounts_list,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetTokenAccountBalanceParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (any::<Pubkey>(), option::of(any::<CommitmentLevel>()))
+            .prop_map(|(pubkey, commitment)| GetTokenAccountBalanceParams { pubkey, commitment })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetTransactionParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Signature>(),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<u8>()),
+            option::of(any::<GetTransactionEncoding>()),
+        )
+            .prop_map(
+                |(signature, commitment, max_supported_transaction_version, encoding)| {
+                    GetTransactionParams {
+                        signature,
+                        commitment,
+                        max_supported_transaction_version,
+                        encoding,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GetTransactionCountParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<Slot>()),
+        )
+            .prop_map(|(commitment, min_context_slot)| GetTransactionCountParams {
+                commitment,
+                min_context_slot,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SendTransactionParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            vec(any::<u8>(), 1..=1_232),
+            option::of(any::<bool>()),
+            option::of(any::<CommitmentLevel>()),
+            option::of(any::<u32>()),
+            option::of(any::<Slot>()),
+            option::of(any::<bool>()),
+        )
+            .prop_map(
+                |(
+                    transaction,
+                    skip_preflight,
+                    preflight_commitment,
+                    max_retries,
+                    min_context_slot,
+                    validate_blockhash,
+                )| {
+                    SendTransactionParams {
+                        skip_preflight,
+                        preflight_commitment,
+                        max_retries,
+                        min_context_slot,
+                        validate_blockhash,
+                        ..SendTransactionParams::from_encoded_transaction(
+                            BASE64_STANDARD.encode(transaction),
+                            SendTransactionEncoding::Base64,
+                        )
+                    }
+                },
+            )
+            .boxed()
+    }
+}
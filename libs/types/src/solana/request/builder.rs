@@ -0,0 +1,942 @@
+//! Builders for the parameters of the Solana RPC methods.
+//!
+//! Every `*Params` struct with optional fields has a `builder` function taking the required
+//! parameters, so that the optional ones can be set one by one without spelling out (or
+//! defaulting) the remaining fields. Builders whose parameters need validation return a
+//! [`Result`] from `build`.
+
+use crate::{
+    solana::{Hash, Pubkey},
+    CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetAccountInfoParams,
+    GetAccountSnapshotParams, GetBalanceParams, GetBlockCommitmentLevel, GetBlockEncoding,
+    GetBlockHeightParams, GetBlockParams, GetBlocksLimit, GetBlocksParams,
+    GetBlocksWithLimitParams, GetClusterNodesLimit, GetClusterNodesParams, GetEpochInfoParams,
+    GetFeeForMessageParams, GetLeaderScheduleParams, GetMinimumBalanceForRentExemptionParams,
+    GetRecentPerformanceSamplesLimit, GetRecentPerformanceSamplesParams,
+    GetSignatureStatusesParams, GetSignaturesForAddressLimit, GetSignaturesForAddressParams,
+    GetSlotLeadersLimit, GetSlotLeadersParams, GetSlotParams, GetSupplyParams,
+    GetTokenAccountBalanceParams, GetTokenAccountsByDelegateParams, GetTokenAccountsByOwnerLimit,
+    GetTokenAccountsByOwnerParams, GetTransactionCountParams, GetTransactionEncoding,
+    GetTransactionParams, HasAccountChangedParams, RpcError, SendTransactionEncoding,
+    SendTransactionParams, Signature, Slot, Timestamp, TokenAccountsFilter, TransactionDetails,
+};
+
+/// The maximum length of account data that can be returned with
+/// [`GetAccountInfoEncoding::Base58`].
+const MAX_BASE58_ACCOUNT_DATA_LENGTH: u32 = 128;
+
+/// Generates setters for the optional fields of the parameters wrapped by a builder.
+macro_rules! optional_setters {
+    ($($(#[$meta:meta])* $field:ident: $ty:ty),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            pub fn $field(mut self, $field: $ty) -> Self {
+                self.params.$field = Some($field);
+                self
+            }
+        )*
+    };
+}
+
+fn validate_limit<L: TryFrom<u32, Error = RpcError>>(
+    limit: Option<u32>,
+) -> Result<Option<L>, RpcError> {
+    limit.map(L::try_from).transpose()
+}
+
+fn validate_data_slice(
+    encoding: &Option<GetAccountInfoEncoding>,
+    data_slice: &Option<DataSlice>,
+) -> Result<(), RpcError> {
+    if let (Some(GetAccountInfoEncoding::Base58), Some(data_slice)) = (encoding, data_slice) {
+        if data_slice.length > MAX_BASE58_ACCOUNT_DATA_LENGTH {
+            return Err(RpcError::ValidationError(format!(
+                "Expected a data slice of at most {MAX_BASE58_ACCOUNT_DATA_LENGTH} bytes with base58 encoding, but got {}",
+                data_slice.length
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl GetAccountInfoParams {
+    /// Builder for the parameters of a `getAccountInfo` request with the given pubkey.
+    pub fn builder<P: Into<Pubkey>>(pubkey: P) -> GetAccountInfoParamsBuilder {
+        GetAccountInfoParamsBuilder {
+            params: Self::from_pubkey(pubkey),
+        }
+    }
+}
+
+/// Builder for [`GetAccountInfoParams`].
+#[derive(Clone, Debug)]
+pub struct GetAccountInfoParamsBuilder {
+    params: GetAccountInfoParams,
+}
+
+impl GetAccountInfoParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetAccountInfoParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`encoding`](GetAccountInfoParams::encoding) parameter.
+        encoding: GetAccountInfoEncoding,
+        /// Sets the [`data_slice`](GetAccountInfoParams::data_slice) parameter.
+        data_slice: DataSlice,
+        /// Sets the [`min_context_slot`](GetAccountInfoParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters, failing if the data slice is too large for the requested encoding.
+    pub fn build(self) -> Result<GetAccountInfoParams, RpcError> {
+        validate_data_slice(&self.params.encoding, &self.params.data_slice)?;
+        Ok(self.params)
+    }
+}
+
+impl GetAccountSnapshotParams {
+    /// Builder for the parameters of a `getAccountSnapshot` request with the given pubkey.
+    pub fn builder<P: Into<Pubkey>>(pubkey: P) -> GetAccountSnapshotParamsBuilder {
+        GetAccountSnapshotParamsBuilder {
+            params: Self::from_pubkey(pubkey),
+            signatures_limit: None,
+        }
+    }
+}
+
+/// Builder for [`GetAccountSnapshotParams`].
+#[derive(Clone, Debug)]
+pub struct GetAccountSnapshotParamsBuilder {
+    params: GetAccountSnapshotParams,
+    signatures_limit: Option<u32>,
+}
+
+impl GetAccountSnapshotParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetAccountSnapshotParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetAccountSnapshotParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+        /// Sets the [`encoding`](GetAccountSnapshotParams::encoding) parameter.
+        encoding: GetAccountInfoEncoding,
+        /// Sets the [`data_slice`](GetAccountSnapshotParams::data_slice) parameter.
+        data_slice: DataSlice,
+        /// Sets the [`signatures_before`](GetAccountSnapshotParams::signatures_before) parameter.
+        signatures_before: Signature,
+        /// Sets the [`signatures_until`](GetAccountSnapshotParams::signatures_until) parameter.
+        signatures_until: Signature,
+    }
+
+    /// Sets the [`signatures_limit`](GetAccountSnapshotParams::signatures_limit) parameter,
+    /// which must be between 1 and [`GetSignaturesForAddressLimit::MAX_LIMIT`].
+    pub fn signatures_limit(mut self, signatures_limit: u32) -> Self {
+        self.signatures_limit = Some(signatures_limit);
+        self
+    }
+
+    /// Builds the parameters, failing if the signatures limit is out of range or if the data
+    /// slice is too large for the requested encoding.
+    pub fn build(self) -> Result<GetAccountSnapshotParams, RpcError> {
+        let mut params = self.params;
+        params.signatures_limit =
+            validate_limit::<GetSignaturesForAddressLimit>(self.signatures_limit)?;
+        validate_data_slice(&params.encoding, &params.data_slice)?;
+        Ok(params)
+    }
+}
+
+impl HasAccountChangedParams {
+    /// Builder for the parameters of a `hasAccountChanged` request with the given pubkey.
+    pub fn builder<P: Into<Pubkey>>(pubkey: P) -> HasAccountChangedParamsBuilder {
+        HasAccountChangedParamsBuilder {
+            params: Self::from_pubkey(pubkey),
+        }
+    }
+}
+
+/// Builder for [`HasAccountChangedParams`].
+#[derive(Clone, Debug)]
+pub struct HasAccountChangedParamsBuilder {
+    params: HasAccountChangedParams,
+}
+
+impl HasAccountChangedParamsBuilder {
+    optional_setters! {
+        /// Sets the [`last_known_hash`](HasAccountChangedParams::last_known_hash) parameter.
+        last_known_hash: Hash,
+        /// Sets the [`commitment`](HasAccountChangedParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`data_slice`](HasAccountChangedParams::data_slice) parameter.
+        data_slice: DataSlice,
+        /// Sets the [`min_context_slot`](HasAccountChangedParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> HasAccountChangedParams {
+        self.params
+    }
+}
+
+impl GetBalanceParams {
+    /// Builder for the parameters of a `getBalance` request with the given pubkey.
+    pub fn builder<P: Into<Pubkey>>(pubkey: P) -> GetBalanceParamsBuilder {
+        GetBalanceParamsBuilder {
+            params: Self::from_pubkey(pubkey),
+        }
+    }
+}
+
+/// Builder for [`GetBalanceParams`].
+#[derive(Clone, Debug)]
+pub struct GetBalanceParamsBuilder {
+    params: GetBalanceParams,
+}
+
+impl GetBalanceParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetBalanceParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetBalanceParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetBalanceParams {
+        self.params
+    }
+}
+
+impl GetBlockParams {
+    /// Builder for the parameters of a `getBlock` request for the given slot.
+    pub fn builder(slot: Slot) -> GetBlockParamsBuilder {
+        GetBlockParamsBuilder {
+            params: Self::from(slot),
+        }
+    }
+}
+
+/// Builder for [`GetBlockParams`].
+#[derive(Clone, Debug)]
+pub struct GetBlockParamsBuilder {
+    params: GetBlockParams,
+}
+
+impl GetBlockParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetBlockParams::commitment) parameter.
+        commitment: GetBlockCommitmentLevel,
+        /// Sets the [`max_supported_transaction_version`](GetBlockParams::max_supported_transaction_version) parameter.
+        max_supported_transaction_version: u8,
+        /// Sets the [`transaction_details`](GetBlockParams::transaction_details) parameter.
+        transaction_details: TransactionDetails,
+        /// Sets the [`rewards`](GetBlockParams::rewards) parameter.
+        rewards: bool,
+        /// Sets the [`encoding`](GetBlockParams::encoding) parameter.
+        encoding: GetBlockEncoding,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetBlockParams {
+        self.params
+    }
+}
+
+impl GetBlockHeightParams {
+    /// Builder for the parameters of a `getBlockHeight` request.
+    pub fn builder() -> GetBlockHeightParamsBuilder {
+        GetBlockHeightParamsBuilder {
+            params: Self::default(),
+        }
+    }
+}
+
+/// Builder for [`GetBlockHeightParams`].
+#[derive(Clone, Debug)]
+pub struct GetBlockHeightParamsBuilder {
+    params: GetBlockHeightParams,
+}
+
+impl GetBlockHeightParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetBlockHeightParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetBlockHeightParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetBlockHeightParams {
+        self.params
+    }
+}
+
+impl GetBlocksParams {
+    /// Builder for the parameters of a `getBlocks` request starting at the given slot.
+    pub fn builder(start_slot: Slot) -> GetBlocksParamsBuilder {
+        GetBlocksParamsBuilder {
+            params: Self::from(start_slot),
+        }
+    }
+}
+
+/// Builder for [`GetBlocksParams`].
+#[derive(Clone, Debug)]
+pub struct GetBlocksParamsBuilder {
+    params: GetBlocksParams,
+}
+
+impl GetBlocksParamsBuilder {
+    optional_setters! {
+        /// Sets the [`end_slot`](GetBlocksParams::end_slot) parameter.
+        end_slot: Slot,
+        /// Sets the [`commitment`](GetBlocksParams::commitment) parameter.
+        commitment: GetBlockCommitmentLevel,
+    }
+
+    /// Builds the parameters, failing if the range of slots is empty or contains more than
+    /// [`GetBlocksLimit::MAX_LIMIT`] slots.
+    pub fn build(self) -> Result<GetBlocksParams, RpcError> {
+        let start_slot = self.params.start_slot;
+        if let Some(end_slot) = self.params.end_slot {
+            let max_end_slot = start_slot.saturating_add(GetBlocksLimit::MAX_LIMIT as u64 - 1);
+            if end_slot < start_slot || end_slot > max_end_slot {
+                return Err(RpcError::ValidationError(format!(
+                    "Expected an end slot between {start_slot} and {max_end_slot}, but got {end_slot}"
+                )));
+            }
+        }
+        Ok(self.params)
+    }
+}
+
+impl GetBlocksWithLimitParams {
+    /// Builder for the parameters of a `getBlocksWithLimit` request starting at the given slot.
+    pub fn builder(start_slot: Slot) -> GetBlocksWithLimitParamsBuilder {
+        GetBlocksWithLimitParamsBuilder {
+            params: Self::from(start_slot),
+            limit: None,
+        }
+    }
+}
+
+/// Builder for [`GetBlocksWithLimitParams`].
+#[derive(Clone, Debug)]
+pub struct GetBlocksWithLimitParamsBuilder {
+    params: GetBlocksWithLimitParams,
+    limit: Option<u32>,
+}
+
+impl GetBlocksWithLimitParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetBlocksWithLimitParams::commitment) parameter.
+        commitment: GetBlockCommitmentLevel,
+    }
+
+    /// Sets the [`limit`](GetBlocksWithLimitParams::limit) parameter, which must be between 1
+    /// and [`GetBlocksLimit::MAX_LIMIT`].
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builds the parameters, failing if the limit is out of range.
+    pub fn build(self) -> Result<GetBlocksWithLimitParams, RpcError> {
+        let mut params = self.params;
+        if let Some(limit) = validate_limit::<GetBlocksLimit>(self.limit)? {
+            params.limit = limit;
+        }
+        Ok(params)
+    }
+}
+
+impl GetClusterNodesParams {
+    /// Builder for the parameters of a `getClusterNodes` request.
+    pub fn builder() -> GetClusterNodesParamsBuilder {
+        GetClusterNodesParamsBuilder {
+            params: Self::default(),
+            limit: None,
+        }
+    }
+}
+
+/// Builder for [`GetClusterNodesParams`].
+#[derive(Clone, Debug)]
+pub struct GetClusterNodesParamsBuilder {
+    params: GetClusterNodesParams,
+    limit: Option<u32>,
+}
+
+impl GetClusterNodesParamsBuilder {
+    optional_setters! {
+        /// Sets the [`after`](GetClusterNodesParams::after) parameter.
+        after: Pubkey,
+    }
+
+    /// Sets the [`limit`](GetClusterNodesParams::limit) parameter, which must be between
+    /// 1 and [`GetClusterNodesLimit::MAX_LIMIT`].
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builds the parameters, failing if the limit is out of range.
+    pub fn build(self) -> Result<GetClusterNodesParams, RpcError> {
+        let mut params = self.params;
+        params.limit = validate_limit::<GetClusterNodesLimit>(self.limit)?;
+        Ok(params)
+    }
+}
+
+impl GetEpochInfoParams {
+    /// Builder for the parameters of a `getEpochInfo` request.
+    pub fn builder() -> GetEpochInfoParamsBuilder {
+        GetEpochInfoParamsBuilder {
+            params: Self::default(),
+        }
+    }
+}
+
+/// Builder for [`GetEpochInfoParams`].
+#[derive(Clone, Debug)]
+pub struct GetEpochInfoParamsBuilder {
+    params: GetEpochInfoParams,
+}
+
+impl GetEpochInfoParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetEpochInfoParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetEpochInfoParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetEpochInfoParams {
+        self.params
+    }
+}
+
+impl GetFeeForMessageParams {
+    /// Builder for the parameters of a `getFeeForMessage` request with the given base-64 encoded
+    /// message.
+    pub fn builder(message: String) -> GetFeeForMessageParamsBuilder {
+        GetFeeForMessageParamsBuilder {
+            params: Self::from_encoded_message(message),
+        }
+    }
+}
+
+/// Builder for [`GetFeeForMessageParams`].
+#[derive(Clone, Debug)]
+pub struct GetFeeForMessageParamsBuilder {
+    params: GetFeeForMessageParams,
+}
+
+impl GetFeeForMessageParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetFeeForMessageParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetFeeForMessageParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetFeeForMessageParams {
+        self.params
+    }
+}
+
+impl GetLeaderScheduleParams {
+    /// Builder for the parameters of a `getLeaderSchedule` request for the given validator
+    /// identity.
+    pub fn builder<P: Into<Pubkey>>(identity: P) -> GetLeaderScheduleParamsBuilder {
+        GetLeaderScheduleParamsBuilder {
+            params: Self::from_identity(identity),
+        }
+    }
+}
+
+/// Builder for [`GetLeaderScheduleParams`].
+#[derive(Clone, Debug)]
+pub struct GetLeaderScheduleParamsBuilder {
+    params: GetLeaderScheduleParams,
+}
+
+impl GetLeaderScheduleParamsBuilder {
+    optional_setters! {
+        /// Sets the [`slot`](GetLeaderScheduleParams::slot) parameter.
+        slot: Slot,
+        /// Sets the [`commitment`](GetLeaderScheduleParams::commitment) parameter.
+        commitment: CommitmentLevel,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetLeaderScheduleParams {
+        self.params
+    }
+}
+
+impl GetMinimumBalanceForRentExemptionParams {
+    /// Builder for the parameters of a `getMinimumBalanceForRentExemption` request for the given
+    /// data length.
+    pub fn builder(data_length: u64) -> GetMinimumBalanceForRentExemptionParamsBuilder {
+        GetMinimumBalanceForRentExemptionParamsBuilder {
+            params: Self::from(data_length),
+        }
+    }
+}
+
+/// Builder for [`GetMinimumBalanceForRentExemptionParams`].
+#[derive(Clone, Debug)]
+pub struct GetMinimumBalanceForRentExemptionParamsBuilder {
+    params: GetMinimumBalanceForRentExemptionParams,
+}
+
+impl GetMinimumBalanceForRentExemptionParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetMinimumBalanceForRentExemptionParams::commitment) parameter.
+        commitment: CommitmentLevel,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetMinimumBalanceForRentExemptionParams {
+        self.params
+    }
+}
+
+impl GetRecentPerformanceSamplesParams {
+    /// Builder for the parameters of a `getRecentPerformanceSamples` request.
+    pub fn builder() -> GetRecentPerformanceSamplesParamsBuilder {
+        GetRecentPerformanceSamplesParamsBuilder { limit: None }
+    }
+}
+
+/// Builder for [`GetRecentPerformanceSamplesParams`].
+#[derive(Clone, Debug)]
+pub struct GetRecentPerformanceSamplesParamsBuilder {
+    limit: Option<u32>,
+}
+
+impl GetRecentPerformanceSamplesParamsBuilder {
+    /// Sets the [`limit`](GetRecentPerformanceSamplesParams::limit) parameter, which must be
+    /// between 1 and [`GetRecentPerformanceSamplesLimit::MAX_LIMIT`].
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builds the parameters, failing if the limit is out of range.
+    pub fn build(self) -> Result<GetRecentPerformanceSamplesParams, RpcError> {
+        Ok(GetRecentPerformanceSamplesParams {
+            limit: validate_limit::<GetRecentPerformanceSamplesLimit>(self.limit)?,
+        })
+    }
+}
+
+impl GetSignaturesForAddressParams {
+    /// Builder for the parameters of a `getSignaturesForAddress` request for the given account.
+    pub fn builder<P: Into<Pubkey>>(pubkey: P) -> GetSignaturesForAddressParamsBuilder {
+        GetSignaturesForAddressParamsBuilder {
+            params: Self::from(pubkey.into()),
+            limit: None,
+        }
+    }
+}
+
+/// Builder for [`GetSignaturesForAddressParams`].
+#[derive(Clone, Debug)]
+pub struct GetSignaturesForAddressParamsBuilder {
+    params: GetSignaturesForAddressParams,
+    limit: Option<u32>,
+}
+
+impl GetSignaturesForAddressParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetSignaturesForAddressParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetSignaturesForAddressParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+        /// Sets the [`before`](GetSignaturesForAddressParams::before) parameter.
+        before: Signature,
+        /// Sets the [`until`](GetSignaturesForAddressParams::until) parameter.
+        until: Signature,
+        /// Sets the [`min_block_time`](GetSignaturesForAddressParams::min_block_time) parameter.
+        min_block_time: Timestamp,
+        /// Sets the [`max_block_time`](GetSignaturesForAddressParams::max_block_time) parameter.
+        max_block_time: Timestamp,
+    }
+
+    /// Sets the [`limit`](GetSignaturesForAddressParams::limit) parameter, which must be between
+    /// 1 and [`GetSignaturesForAddressLimit::MAX_LIMIT`].
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builds the parameters, failing if the limit is out of range or if the minimum block time
+    /// is greater than the maximum block time.
+    pub fn build(self) -> Result<GetSignaturesForAddressParams, RpcError> {
+        let mut params = self.params;
+        params.limit = validate_limit::<GetSignaturesForAddressLimit>(self.limit)?;
+        if let (Some(min_block_time), Some(max_block_time)) =
+            (params.min_block_time, params.max_block_time)
+        {
+            if min_block_time > max_block_time {
+                return Err(RpcError::ValidationError(format!(
+                    "Expected the minimum block time {min_block_time} to be at most the maximum block time {max_block_time}"
+                )));
+            }
+        }
+        Ok(params)
+    }
+}
+
+impl GetSignatureStatusesParams {
+    /// Builder for the parameters of a `getSignatureStatuses` request for the given signatures.
+    pub fn builder<S: Into<Signature>>(
+        signatures: impl IntoIterator<Item = S>,
+    ) -> GetSignatureStatusesParamsBuilder {
+        GetSignatureStatusesParamsBuilder {
+            signatures: signatures.into_iter().map(Into::into).collect(),
+            search_transaction_history: None,
+        }
+    }
+}
+
+/// Builder for [`GetSignatureStatusesParams`].
+#[derive(Clone, Debug)]
+pub struct GetSignatureStatusesParamsBuilder {
+    signatures: Vec<Signature>,
+    search_transaction_history: Option<bool>,
+}
+
+impl GetSignatureStatusesParamsBuilder {
+    /// Sets the [`search_transaction_history`](GetSignatureStatusesParams::search_transaction_history) parameter.
+    pub fn search_transaction_history(mut self, search_transaction_history: bool) -> Self {
+        self.search_transaction_history = Some(search_transaction_history);
+        self
+    }
+
+    /// Builds the parameters, failing if there are too many signatures.
+    pub fn build(self) -> Result<GetSignatureStatusesParams, RpcError> {
+        Ok(GetSignatureStatusesParams {
+            signatures: self.signatures.try_into()?,
+            search_transaction_history: self.search_transaction_history,
+        })
+    }
+}
+
+impl GetSlotParams {
+    /// Builder for the parameters of a `getSlot` request.
+    pub fn builder() -> GetSlotParamsBuilder {
+        GetSlotParamsBuilder {
+            params: Self::default(),
+        }
+    }
+}
+
+/// Builder for [`GetSlotParams`].
+#[derive(Clone, Debug)]
+pub struct GetSlotParamsBuilder {
+    params: GetSlotParams,
+}
+
+impl GetSlotParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetSlotParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetSlotParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetSlotParams {
+        self.params
+    }
+}
+
+impl GetSlotLeadersParams {
+    /// Builder for the parameters of a `getSlotLeaders` request starting at the given slot.
+    pub fn builder(start_slot: Slot) -> GetSlotLeadersParamsBuilder {
+        GetSlotLeadersParamsBuilder {
+            params: Self::from(start_slot),
+            limit: None,
+        }
+    }
+}
+
+/// Builder for [`GetSlotLeadersParams`].
+#[derive(Clone, Debug)]
+pub struct GetSlotLeadersParamsBuilder {
+    params: GetSlotLeadersParams,
+    limit: Option<u32>,
+}
+
+impl GetSlotLeadersParamsBuilder {
+    /// Sets the [`limit`](GetSlotLeadersParams::limit) parameter, which must be between 1 and
+    /// [`GetSlotLeadersLimit::MAX_LIMIT`].
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builds the parameters, failing if the limit is out of range.
+    pub fn build(self) -> Result<GetSlotLeadersParams, RpcError> {
+        let mut params = self.params;
+        if let Some(limit) = validate_limit::<GetSlotLeadersLimit>(self.limit)? {
+            params.limit = limit;
+        }
+        Ok(params)
+    }
+}
+
+impl GetTokenAccountBalanceParams {
+    /// Builder for the parameters of a `getTokenAccountBalance` request for the given token
+    /// account.
+    pub fn builder<P: Into<Pubkey>>(pubkey: P) -> GetTokenAccountBalanceParamsBuilder {
+        GetTokenAccountBalanceParamsBuilder {
+            params: Self::from_pubkey(pubkey),
+        }
+    }
+}
+
+/// Builder for [`GetTokenAccountBalanceParams`].
+#[derive(Clone, Debug)]
+pub struct GetTokenAccountBalanceParamsBuilder {
+    params: GetTokenAccountBalanceParams,
+}
+
+impl GetTokenAccountBalanceParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetTokenAccountBalanceParams::commitment) parameter.
+        commitment: CommitmentLevel,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetTokenAccountBalanceParams {
+        self.params
+    }
+}
+
+impl GetTokenAccountsByDelegateParams {
+    /// Builder for the parameters of a `getTokenAccountsByDelegate` request with the given
+    /// delegate and filter.
+    pub fn builder<P: Into<Pubkey>>(
+        delegate: P,
+        filter: TokenAccountsFilter,
+    ) -> GetTokenAccountsByDelegateParamsBuilder {
+        GetTokenAccountsByDelegateParamsBuilder {
+            params: Self::from_delegate(delegate, filter),
+        }
+    }
+}
+
+/// Builder for [`GetTokenAccountsByDelegateParams`].
+#[derive(Clone, Debug)]
+pub struct GetTokenAccountsByDelegateParamsBuilder {
+    params: GetTokenAccountsByDelegateParams,
+}
+
+impl GetTokenAccountsByDelegateParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetTokenAccountsByDelegateParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`encoding`](GetTokenAccountsByDelegateParams::encoding) parameter.
+        encoding: GetAccountInfoEncoding,
+        /// Sets the [`data_slice`](GetTokenAccountsByDelegateParams::data_slice) parameter.
+        data_slice: DataSlice,
+        /// Sets the [`min_context_slot`](GetTokenAccountsByDelegateParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters, failing if the data slice is too large for the requested encoding.
+    pub fn build(self) -> Result<GetTokenAccountsByDelegateParams, RpcError> {
+        validate_data_slice(&self.params.encoding, &self.params.data_slice)?;
+        Ok(self.params)
+    }
+}
+
+impl GetTokenAccountsByOwnerParams {
+    /// Builder for the parameters of a `getTokenAccountsByOwner` request with the given owner
+    /// and filter.
+    pub fn builder<P: Into<Pubkey>>(
+        owner: P,
+        filter: TokenAccountsFilter,
+    ) -> GetTokenAccountsByOwnerParamsBuilder {
+        GetTokenAccountsByOwnerParamsBuilder {
+            params: Self::from_owner(owner, filter),
+            limit: None,
+        }
+    }
+}
+
+/// Builder for [`GetTokenAccountsByOwnerParams`].
+#[derive(Clone, Debug)]
+pub struct GetTokenAccountsByOwnerParamsBuilder {
+    params: GetTokenAccountsByOwnerParams,
+    limit: Option<u32>,
+}
+
+impl GetTokenAccountsByOwnerParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetTokenAccountsByOwnerParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`encoding`](GetTokenAccountsByOwnerParams::encoding) parameter.
+        encoding: GetAccountInfoEncoding,
+        /// Sets the [`data_slice`](GetTokenAccountsByOwnerParams::data_slice) parameter.
+        data_slice: DataSlice,
+        /// Sets the [`min_context_slot`](GetTokenAccountsByOwnerParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+        /// Sets the [`after`](GetTokenAccountsByOwnerParams::after) parameter.
+        after: Pubkey,
+    }
+
+    /// Sets the [`limit`](GetTokenAccountsByOwnerParams::limit) parameter, which must be between
+    /// 1 and [`GetTokenAccountsByOwnerLimit::MAX_LIMIT`].
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builds the parameters, failing if the limit is out of range or if the data slice is too
+    /// large for the requested encoding.
+    pub fn build(self) -> Result<GetTokenAccountsByOwnerParams, RpcError> {
+        let mut params = self.params;
+        validate_data_slice(&params.encoding, &params.data_slice)?;
+        params.limit = validate_limit::<GetTokenAccountsByOwnerLimit>(self.limit)?;
+        Ok(params)
+    }
+}
+
+impl GetTransactionParams {
+    /// Builder for the parameters of a `getTransaction` request for the given signature.
+    pub fn builder<S: Into<Signature>>(signature: S) -> GetTransactionParamsBuilder {
+        GetTransactionParamsBuilder {
+            params: Self {
+                signature: signature.into(),
+                commitment: None,
+                max_supported_transaction_version: None,
+                encoding: None,
+            },
+        }
+    }
+}
+
+/// Builder for [`GetTransactionParams`].
+#[derive(Clone, Debug)]
+pub struct GetTransactionParamsBuilder {
+    params: GetTransactionParams,
+}
+
+impl GetTransactionParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetTransactionParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`max_supported_transaction_version`](GetTransactionParams::max_supported_transaction_version) parameter.
+        max_supported_transaction_version: u8,
+        /// Sets the [`encoding`](GetTransactionParams::encoding) parameter.
+        encoding: GetTransactionEncoding,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetTransactionParams {
+        self.params
+    }
+}
+
+impl GetSupplyParams {
+    /// Builder for the parameters of a `getSupply` request.
+    pub fn builder() -> GetSupplyParamsBuilder {
+        GetSupplyParamsBuilder {
+            params: Self::default(),
+        }
+    }
+}
+
+/// Builder for [`GetSupplyParams`].
+#[derive(Clone, Debug)]
+pub struct GetSupplyParamsBuilder {
+    params: GetSupplyParams,
+}
+
+impl GetSupplyParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetSupplyParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`exclude_non_circulating_accounts_list`](GetSupplyParams::exclude_non_circulating_accounts_list) parameter.
+        exclude_non_circulating_accounts_list: bool,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetSupplyParams {
+        self.params
+    }
+}
+
+impl GetTransactionCountParams {
+    /// Builder for the parameters of a `getTransactionCount` request.
+    pub fn builder() -> GetTransactionCountParamsBuilder {
+        GetTransactionCountParamsBuilder {
+            params: Self::default(),
+        }
+    }
+}
+
+/// Builder for [`GetTransactionCountParams`].
+#[derive(Clone, Debug)]
+pub struct GetTransactionCountParamsBuilder {
+    params: GetTransactionCountParams,
+}
+
+impl GetTransactionCountParamsBuilder {
+    optional_setters! {
+        /// Sets the [`commitment`](GetTransactionCountParams::commitment) parameter.
+        commitment: CommitmentLevel,
+        /// Sets the [`min_context_slot`](GetTransactionCountParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> GetTransactionCountParams {
+        self.params
+    }
+}
+
+impl SendTransactionParams {
+    /// Builder for the parameters of a `sendTransaction` request with the given transaction
+    /// already encoded with the given encoding.
+    pub fn builder(
+        transaction: String,
+        encoding: SendTransactionEncoding,
+    ) -> SendTransactionParamsBuilder {
+        SendTransactionParamsBuilder {
+            params: Self::from_encoded_transaction(transaction, encoding),
+        }
+    }
+}
+
+/// Builder for [`SendTransactionParams`].
+#[derive(Clone, Debug)]
+pub struct SendTransactionParamsBuilder {
+    params: SendTransactionParams,
+}
+
+impl SendTransactionParamsBuilder {
+    optional_setters! {
+        /// Sets the [`skip_preflight`](SendTransactionParams::skip_preflight) parameter.
+        skip_preflight: bool,
+        /// Sets the [`preflight_commitment`](SendTransactionParams::preflight_commitment) parameter.
+        preflight_commitment: CommitmentLevel,
+        /// Sets the [`max_retries`](SendTransactionParams::max_retries) parameter.
+        max_retries: u32,
+        /// Sets the [`min_context_slot`](SendTransactionParams::min_context_slot) parameter.
+        min_context_slot: Slot,
+        /// Sets the [`validate_blockhash`](SendTransactionParams::validate_blockhash) parameter.
+        validate_blockhash: bool,
+    }
+
+    /// Builds the parameters.
+    pub fn build(self) -> SendTransactionParams {
+        self.params
+    }
+}
@@ -1,14 +1,37 @@
+#[cfg(any(test, feature = "test-strategies"))]
+mod arbitrary;
+mod builder;
 #[cfg(test)]
 mod tests;
 
-use crate::{solana::Pubkey, RpcError, Signature, Slot, VecWithMaxLen};
+pub use builder::{
+    GetAccountInfoParamsBuilder, GetAccountSnapshotParamsBuilder, GetBalanceParamsBuilder,
+    GetBlockHeightParamsBuilder, GetBlockParamsBuilder, GetBlocksParamsBuilder,
+    GetBlocksWithLimitParamsBuilder, GetClusterNodesParamsBuilder, GetEpochInfoParamsBuilder,
+    GetFeeForMessageParamsBuilder, GetLeaderScheduleParamsBuilder,
+    GetMinimumBalanceForRentExemptionParamsBuilder, GetRecentPerformanceSamplesParamsBuilder,
+    GetSignatureStatusesParamsBuilder, GetSignaturesForAddressParamsBuilder,
+    GetSlotLeadersParamsBuilder, GetSlotParamsBuilder, GetSupplyParamsBuilder,
+    GetTokenAccountBalanceParamsBuilder, GetTokenAccountsByDelegateParamsBuilder,
+    GetTokenAccountsByOwnerParamsBuilder, GetTransactionCountParamsBuilder,
+    GetTransactionParamsBuilder, HasAccountChangedParamsBuilder, SendTransactionParamsBuilder,
+};
+
+use crate::{
+    solana::{Hash, Pubkey},
+    RpcError, Signature, Slot, Timestamp, VecWithMaxLen,
+};
+#[cfg(feature = "solana-compat")]
+use crate::{EncodedTransaction, TransactionBinaryEncoding};
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
+#[cfg(feature = "solana-compat")]
+use solana_transaction::versioned::VersionedTransaction;
 use strum::EnumIter;
 
 /// The parameters for a Solana [`getAccountInfo`](https://solana.com/docs/rpc/http/getaccountinfo) RPC method call.
-#[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
 pub struct GetAccountInfoParams {
     /// The public key of the account whose info to fetch formatted as a base-58 string.
     pub pubkey: Pubkey,
@@ -37,12 +60,154 @@ impl GetAccountInfoParams {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_pubkey::Pubkey> for GetAccountInfoParams {
     fn from(pubkey: solana_pubkey::Pubkey) -> Self {
         Self::from_pubkey(pubkey)
     }
 }
 
+/// The parameters for a call to the `hasAccountChanged` endpoint of the SOL RPC canister.
+///
+/// The account is fetched with the Solana [`getAccountInfo`](https://solana.com/docs/rpc/http/getaccountinfo)
+/// RPC method and hashed by the SOL RPC canister, so that the account is only returned if its hash
+/// differs from [`last_known_hash`](Self::last_known_hash).
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct HasAccountChangedParams {
+    /// The public key of the account to check formatted as a base-58 string.
+    pub pubkey: Pubkey,
+    /// The hash of the account returned by the last call to `hasAccountChanged`, or `None` if the
+    /// account did not exist or is not known.
+    #[serde(rename = "lastKnownHash")]
+    pub last_known_hash: Option<Hash>,
+    /// The commitment describes how finalized a block is at that point in time.
+    pub commitment: Option<CommitmentLevel>,
+    /// Only check (and return) a slice of the account's data.
+    #[serde(rename = "dataSlice")]
+    pub data_slice: Option<DataSlice>,
+    /// The minimum slot that the request can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+}
+
+impl HasAccountChangedParams {
+    /// Parameters for a `hasAccountChanged` request with the given pubkey and no last known hash.
+    pub fn from_pubkey<P: Into<Pubkey>>(pubkey: P) -> Self {
+        Self {
+            pubkey: pubkey.into(),
+            last_known_hash: None,
+            commitment: None,
+            data_slice: None,
+            min_context_slot: None,
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<solana_pubkey::Pubkey> for HasAccountChangedParams {
+    fn from(pubkey: solana_pubkey::Pubkey) -> Self {
+        Self::from_pubkey(pubkey)
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<(solana_pubkey::Pubkey, Hash)> for HasAccountChangedParams {
+    fn from((pubkey, last_known_hash): (solana_pubkey::Pubkey, Hash)) -> Self {
+        Self {
+            last_known_hash: Some(last_known_hash),
+            ..Self::from_pubkey(pubkey)
+        }
+    }
+}
+
+/// The parameters for a call to the `getAccountSnapshot` endpoint of the SOL RPC canister.
+///
+/// The balance, the account info and the most recent transaction signatures of the account are
+/// fetched with the Solana [`getBalance`](https://solana.com/docs/rpc/http/getbalance),
+/// [`getAccountInfo`](https://solana.com/docs/rpc/http/getaccountinfo) and
+/// [`getSignaturesForAddress`](https://solana.com/docs/rpc/http/getsignaturesforaddress) RPC
+/// methods, which all use the same commitment level and minimum context slot.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetAccountSnapshotParams {
+    /// The public key of the account formatted as a base-58 string.
+    pub pubkey: Pubkey,
+    /// The commitment describes how finalized a block is at that point in time.
+    pub commitment: Option<CommitmentLevel>,
+    /// The minimum slot that the requests can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+    /// Encoding format for the account data.
+    pub encoding: Option<GetAccountInfoEncoding>,
+    /// Request a slice of the account's data.
+    #[serde(rename = "dataSlice")]
+    pub data_slice: Option<DataSlice>,
+    /// Maximum transaction signatures to return (between 1 and 1,000).
+    #[serde(rename = "signaturesLimit")]
+    pub signatures_limit: Option<GetSignaturesForAddressLimit>,
+    /// Start searching the transaction signatures backwards from this transaction signature,
+    /// see [`GetSignaturesForAddressParams::before`].
+    #[serde(rename = "signaturesBefore")]
+    pub signatures_before: Option<Signature>,
+    /// Search the transaction signatures until this transaction signature, if found before the
+    /// limit is reached, see [`GetSignaturesForAddressParams::until`].
+    #[serde(rename = "signaturesUntil")]
+    pub signatures_until: Option<Signature>,
+}
+
+impl GetAccountSnapshotParams {
+    /// Parameters for a `getAccountSnapshot` request with the given pubkey.
+    pub fn from_pubkey<P: Into<Pubkey>>(pubkey: P) -> Self {
+        Self {
+            pubkey: pubkey.into(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<solana_pubkey::Pubkey> for GetAccountSnapshotParams {
+    fn from(pubkey: solana_pubkey::Pubkey) -> Self {
+        Self::from_pubkey(pubkey)
+    }
+}
+
+impl From<&GetAccountSnapshotParams> for GetBalanceParams {
+    fn from(params: &GetAccountSnapshotParams) -> Self {
+        Self {
+            pubkey: params.pubkey.clone(),
+            commitment: params.commitment.clone(),
+            min_context_slot: params.min_context_slot,
+        }
+    }
+}
+
+impl From<&GetAccountSnapshotParams> for GetAccountInfoParams {
+    fn from(params: &GetAccountSnapshotParams) -> Self {
+        Self {
+            pubkey: params.pubkey.clone(),
+            commitment: params.commitment.clone(),
+            encoding: params.encoding.clone(),
+            data_slice: params.data_slice.clone(),
+            min_context_slot: params.min_context_slot,
+        }
+    }
+}
+
+impl From<&GetAccountSnapshotParams> for GetSignaturesForAddressParams {
+    fn from(params: &GetAccountSnapshotParams) -> Self {
+        Self {
+            pubkey: params.pubkey.clone(),
+            commitment: params.commitment.clone(),
+            min_context_slot: params.min_context_slot,
+            limit: params.signatures_limit,
+            before: params.signatures_before.clone(),
+            until: params.signatures_until.clone(),
+            min_block_time: None,
+            max_block_time: None,
+        }
+    }
+}
+
 /// Encoding for the return value of the Solana [`getAccountInfo`](https://solana.com/docs/rpc/http/getaccountinfo) RPC method.
 #[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
 pub enum GetAccountInfoEncoding {
@@ -73,7 +238,7 @@ pub struct DataSlice {
 }
 
 /// The parameters for a Solana [`getBalance`](https://solana.com/docs/rpc/http/getbalance) RPC method call.
-#[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
 pub struct GetBalanceParams {
     /// The public key of the account to query formatted as a base-58 string.
     pub pubkey: Pubkey,
@@ -95,6 +260,7 @@ impl GetBalanceParams {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_pubkey::Pubkey> for GetBalanceParams {
     fn from(pubkey: solana_pubkey::Pubkey) -> Self {
         Self::from_pubkey(pubkey)
@@ -125,6 +291,9 @@ pub struct GetBlockParams {
     pub transaction_details: Option<TransactionDetails>,
     /// Whether to populate the rewards array. If not provided, the default includes rewards.
     pub rewards: Option<bool>,
+    /// Encoding format for each returned transaction. Only relevant for transaction details
+    /// including full transactions.
+    pub encoding: Option<GetBlockEncoding>,
 }
 
 impl From<Slot> for GetBlockParams {
@@ -135,10 +304,29 @@ impl From<Slot> for GetBlockParams {
             max_supported_transaction_version: None,
             transaction_details: None,
             rewards: None,
+            encoding: None,
         }
     }
 }
 
+/// Encoding format for the transactions returned by a [`getBlock`](https://solana.com/docs/rpc/http/getblock)
+/// RPC method call.
+#[derive(Clone, Copy, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+pub enum GetBlockEncoding {
+    /// The transactions are base64-encoded.
+    #[serde(rename = "base64")]
+    Base64,
+    /// The transactions are base58-encoded.
+    #[serde(rename = "base58")]
+    Base58,
+    /// The transactions and their instructions are parsed into a human-readable format, see
+    /// [`EncodedTransaction::Json`](crate::EncodedTransaction::Json). Instructions of programs
+    /// without a parser are returned as
+    /// [`Instruction::PartiallyDecoded`](crate::Instruction::PartiallyDecoded).
+    #[serde(rename = "jsonParsed")]
+    JsonParsed,
+}
+
 /// Determines whether and how transactions are included in `getBlock` response.
 ///
 /// *Warning:* If this value is not specified, the default value of [`TransactionDetails::None`]
@@ -160,8 +348,342 @@ pub enum TransactionDetails {
     Signatures,
 }
 
+/// The parameters for a Solana [`getBlockHeight`](https://solana.com/docs/rpc/http/getblockheight) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetBlockHeightParams {
+    /// The request returns the block height of the block that has reached this or the default
+    /// commitment level.
+    pub commitment: Option<CommitmentLevel>,
+    /// The minimum slot that the request can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+}
+
+/// The parameters for a Solana [`getBlocks`](https://solana.com/docs/rpc/http/getblocks) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetBlocksParams {
+    /// Start slot (inclusive) of the range of confirmed blocks to return.
+    #[serde(rename = "startSlot")]
+    pub start_slot: Slot,
+    /// End slot (inclusive) of the range of confirmed blocks to return.
+    ///
+    /// The range may contain at most [`GetBlocksLimit::MAX_LIMIT`] slots. If not provided, the
+    /// range ends [`GetBlocksLimit::MAX_LIMIT`] slots after (and including) `start_slot`.
+    ///
+    /// To obtain an idempotent response, and hence allow the replicas to reach consensus, the
+    /// range should not extend beyond the latest block with the requested commitment level.
+    #[serde(rename = "endSlot")]
+    pub end_slot: Option<Slot>,
+    /// The commitment describes how finalized a block is at that point in time.
+    pub commitment: Option<GetBlockCommitmentLevel>,
+}
+
+impl From<Slot> for GetBlocksParams {
+    fn from(start_slot: Slot) -> Self {
+        Self {
+            start_slot,
+            end_slot: None,
+            commitment: None,
+        }
+    }
+}
+
+/// The parameters for a Solana [`getBlocksWithLimit`](https://solana.com/docs/rpc/http/getblockswithlimit) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetBlocksWithLimitParams {
+    /// Start slot (inclusive) of the range of confirmed blocks to return.
+    #[serde(rename = "startSlot")]
+    pub start_slot: Slot,
+    /// The maximum number of blocks to return.
+    ///
+    /// To obtain an idempotent response, and hence allow the replicas to reach consensus, enough
+    /// blocks with the requested commitment level should follow `start_slot`.
+    pub limit: GetBlocksLimit,
+    /// The commitment describes how finalized a block is at that point in time.
+    pub commitment: Option<GetBlockCommitmentLevel>,
+}
+
+impl From<Slot> for GetBlocksWithLimitParams {
+    fn from(start_slot: Slot) -> Self {
+        Self {
+            start_slot,
+            limit: GetBlocksLimit::default(),
+            commitment: None,
+        }
+    }
+}
+
+/// The maximum number of blocks to return in the response of a
+/// [`getBlocksWithLimit`](https://solana.com/docs/rpc/http/getblockswithlimit) request, or the
+/// maximum number of slots in the range of a
+/// [`getBlocks`](https://solana.com/docs/rpc/http/getblocks) request.
+#[derive(Clone, Copy, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct GetBlocksLimit(u32);
+
+impl GetBlocksLimit {
+    /// The maximum number of blocks that can be returned by a `getBlocks` or `getBlocksWithLimit` call.
+    pub const MAX_LIMIT: u32 = 1000;
+}
+
+impl Default for GetBlocksLimit {
+    fn default() -> Self {
+        Self(Self::MAX_LIMIT)
+    }
+}
+
+impl TryFrom<u32> for GetBlocksLimit {
+    type Error = RpcError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1..=Self::MAX_LIMIT => Ok(Self(value)),
+            _ => Err(RpcError::ValidationError(format!(
+                "Expected a value between 1 and {}, but got {}",
+                Self::MAX_LIMIT,
+                value
+            ))),
+        }
+    }
+}
+
+impl From<GetBlocksLimit> for u32 {
+    fn from(value: GetBlocksLimit) -> Self {
+        value.0
+    }
+}
+
+/// The parameters for a Solana [`getEpochInfo`](https://solana.com/docs/rpc/http/getepochinfo) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetEpochInfoParams {
+    /// The request returns information about the epoch of the slot that has reached this or the
+    /// default commitment level.
+    pub commitment: Option<CommitmentLevel>,
+    /// The minimum slot that the request can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+}
+
+/// The parameters for a Solana [`getClusterNodes`](https://solana.com/docs/rpc/http/getclusternodes) RPC method call.
+///
+/// The `getClusterNodes` RPC method does not have any parameters and returns all the nodes
+/// participating in the cluster. The returned nodes, sorted by public key, can be paginated with
+/// the [`after`](Self::after) and [`limit`](Self::limit) parameters, which are both applied by the
+/// SOL RPC canister.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetClusterNodesParams {
+    /// Only return the nodes whose public key is strictly greater than the given one,
+    /// typically the public key of the last node of the previous page.
+    pub after: Option<Pubkey>,
+    /// Maximum number of nodes to return.
+    pub limit: Option<GetClusterNodesLimit>,
+}
+
+/// The maximum number of nodes returned by a `getClusterNodes` request.
+#[derive(Clone, Copy, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct GetClusterNodesLimit(u32);
+
+impl GetClusterNodesLimit {
+    /// The maximum number of nodes that can be returned by a `getClusterNodes` call.
+    pub const MAX_LIMIT: u32 = 1000;
+}
+
+impl Default for GetClusterNodesLimit {
+    fn default() -> Self {
+        Self(Self::MAX_LIMIT)
+    }
+}
+
+impl TryFrom<u32> for GetClusterNodesLimit {
+    type Error = RpcError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1..=Self::MAX_LIMIT => Ok(Self(value)),
+            _ => Err(RpcError::ValidationError(format!(
+                "Expected a value between 1 and {}, but got {}",
+                Self::MAX_LIMIT,
+                value
+            ))),
+        }
+    }
+}
+
+impl From<GetClusterNodesLimit> for u32 {
+    fn from(value: GetClusterNodesLimit) -> Self {
+        value.0
+    }
+}
+
+/// The parameters for a Solana [`getFeeForMessage`](https://solana.com/docs/rpc/http/getfeeformessage) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetFeeForMessageParams {
+    /// Base-64 encoded message whose fee to compute.
+    message: String,
+    /// The request computes the fee of the message with the bank that has reached this or the
+    /// default commitment level.
+    pub commitment: Option<CommitmentLevel>,
+    /// The minimum slot that the request can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+}
+
+impl GetFeeForMessageParams {
+    /// Parameters for a `getFeeForMessage` request with the given base-64 encoded message.
+    pub fn from_encoded_message(message: String) -> Self {
+        Self {
+            message,
+            commitment: None,
+            min_context_slot: None,
+        }
+    }
+
+    /// The message whose fee to compute as a base-64 encoded string.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Checks that the message is a non-empty, valid base-64 encoded string.
+    ///
+    /// Malformed messages would otherwise be rejected by each provider with a different error.
+    pub fn validate(&self) -> Result<(), RpcError> {
+        decode_base64_param("message", &self.message).map(drop)
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<solana_message::VersionedMessage> for GetFeeForMessageParams {
+    type Error = RpcError;
+
+    fn try_from(message: solana_message::VersionedMessage) -> Result<Self, RpcError> {
+        let serialized = bincode::serialize(&message)
+            .map_err(|e| RpcError::ValidationError(format!("Message serialization failed: {e}")))?;
+        Ok(Self::from_encoded_message(
+            BASE64_STANDARD.encode(serialized),
+        ))
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<solana_message::Message> for GetFeeForMessageParams {
+    type Error = RpcError;
+
+    fn try_from(message: solana_message::Message) -> Result<Self, RpcError> {
+        Self::try_from(solana_message::VersionedMessage::Legacy(message))
+    }
+}
+
+/// The parameters for a Solana [`getLeaderSchedule`](https://solana.com/docs/rpc/http/getleaderschedule) RPC method call.
+///
+/// Since the full leader schedule of an epoch is too large to fit in the response of an HTTPS
+/// outcall, only the leader slots of the validator with the given `identity` are returned.
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetLeaderScheduleParams {
+    /// Fetch the leader schedule for the epoch that corresponds to the provided slot.
+    /// If not provided, the leader schedule for the current epoch is fetched.
+    pub slot: Option<Slot>,
+    /// The identity of the validator whose leader slots to return formatted as a base-58 string.
+    pub identity: Pubkey,
+    /// The commitment describes how finalized a block is at that point in time.
+    pub commitment: Option<CommitmentLevel>,
+}
+
+impl GetLeaderScheduleParams {
+    /// Parameters for a `getLeaderSchedule` request for the current epoch and the given validator
+    /// identity.
+    pub fn from_identity<P: Into<Pubkey>>(identity: P) -> Self {
+        Self {
+            slot: None,
+            identity: identity.into(),
+            commitment: None,
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<solana_pubkey::Pubkey> for GetLeaderScheduleParams {
+    fn from(identity: solana_pubkey::Pubkey) -> Self {
+        Self::from_identity(identity)
+    }
+}
+
+/// The parameters for a Solana [`getMinimumBalanceForRentExemption`](https://solana.com/docs/rpc/http/getminimumbalanceforrentexemption) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetMinimumBalanceForRentExemptionParams {
+    /// The account's data length in bytes.
+    #[serde(rename = "dataLength")]
+    pub data_length: u64,
+    /// The request returns the minimum balance computed with the bank that has reached this or
+    /// the default commitment level.
+    pub commitment: Option<CommitmentLevel>,
+}
+
+impl From<u64> for GetMinimumBalanceForRentExemptionParams {
+    fn from(data_length: u64) -> Self {
+        Self {
+            data_length,
+            commitment: None,
+        }
+    }
+}
+
+/// The parameters for a Solana [`getRecentPerformanceSamples`](https://solana.com/docs/rpc/http/getrecentperformancesamples) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetRecentPerformanceSamplesParams {
+    /// The number of samples to return. Samples are taken every 60 seconds and returned from the
+    /// most recent to the oldest one. If not provided, [`GetRecentPerformanceSamplesLimit::default`]
+    /// is used so that the size of the response is always bounded.
+    pub limit: Option<GetRecentPerformanceSamplesLimit>,
+}
+
+impl From<GetRecentPerformanceSamplesLimit> for GetRecentPerformanceSamplesParams {
+    fn from(limit: GetRecentPerformanceSamplesLimit) -> Self {
+        Self { limit: Some(limit) }
+    }
+}
+
+/// The number of samples to return in the response of a
+/// [`getRecentPerformanceSamples`](https://solana.com/docs/rpc/http/getrecentperformancesamples) request.
+#[derive(Clone, Copy, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct GetRecentPerformanceSamplesLimit(u32);
+
+impl GetRecentPerformanceSamplesLimit {
+    /// The maximum number of samples that can be returned by a `getRecentPerformanceSamples` call.
+    pub const MAX_LIMIT: u32 = 720;
+}
+
+impl Default for GetRecentPerformanceSamplesLimit {
+    /// The samples of the last hour.
+    fn default() -> Self {
+        Self(60)
+    }
+}
+
+impl TryFrom<u32> for GetRecentPerformanceSamplesLimit {
+    type Error = RpcError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1..=Self::MAX_LIMIT => Ok(Self(value)),
+            _ => Err(RpcError::ValidationError(format!(
+                "Expected a value between 1 and {}, but got {}",
+                Self::MAX_LIMIT,
+                value
+            ))),
+        }
+    }
+}
+
+impl From<GetRecentPerformanceSamplesLimit> for u32 {
+    fn from(value: GetRecentPerformanceSamplesLimit) -> Self {
+        value.0
+    }
+}
+
 /// The parameters for a Solana [`getRecentPrioritizationFees`](https://solana.com/de/docs/rpc/http/getrecentprioritizationfees) RPC method call.
-#[derive(Clone, Debug, Default, CandidType, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
 pub struct GetRecentPrioritizationFeesParams(VecWithMaxLen<Pubkey, 128>);
 
 impl<P: Into<Pubkey>> TryFrom<Vec<P>> for GetRecentPrioritizationFeesParams {
@@ -178,6 +700,7 @@ impl<P: Into<Pubkey>> TryFrom<Vec<P>> for GetRecentPrioritizationFeesParams {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_pubkey::Pubkey> for GetRecentPrioritizationFeesParams {
     fn from(value: solana_pubkey::Pubkey) -> Self {
         Self(VecWithMaxLen::try_from(vec![Pubkey::from(value)]).unwrap())
@@ -214,6 +737,19 @@ pub struct GetSignaturesForAddressParams {
     pub before: Option<Signature>,
     /// Search until this transaction signature, if found before `limit` reached.
     pub until: Option<Signature>,
+    /// Only return signatures whose block time (Unix timestamp in seconds) is at least this value.
+    ///
+    /// This filter is not part of the Solana JSON-RPC API: it is applied by the SOL RPC canister on
+    /// each provider response before the responses are aggregated, and hence after the `limit` is
+    /// applied by the provider. Signatures without a block time are excluded if this filter is set.
+    #[serde(rename = "minBlockTime")]
+    pub min_block_time: Option<Timestamp>,
+    /// Only return signatures whose block time (Unix timestamp in seconds) is at most this value.
+    ///
+    /// Like `min_block_time`, this filter is applied by the SOL RPC canister on each provider
+    /// response. Signatures without a block time are excluded if this filter is set.
+    #[serde(rename = "maxBlockTime")]
+    pub max_block_time: Option<Timestamp>,
 }
 
 impl<P: Into<Pubkey>> From<P> for GetSignaturesForAddressParams {
@@ -225,6 +761,8 @@ impl<P: Into<Pubkey>> From<P> for GetSignaturesForAddressParams {
             limit: None,
             before: None,
             until: None,
+            min_block_time: None,
+            max_block_time: None,
         }
     }
 }
@@ -302,8 +840,81 @@ pub struct GetSlotParams {
     pub min_context_slot: Option<Slot>,
 }
 
+/// The parameters for a Solana [`getSlotLeaders`](https://solana.com/docs/rpc/http/getslotleaders) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetSlotLeadersParams {
+    /// Start slot (inclusive) of the range of slots whose leaders to return.
+    #[serde(rename = "startSlot")]
+    pub start_slot: Slot,
+    /// The number of slot leaders to return.
+    ///
+    /// To obtain an idempotent response, and hence allow the replicas to reach consensus, the
+    /// range of slots should be within the current or the next epoch, since the leader schedule
+    /// of later epochs is not yet known.
+    pub limit: GetSlotLeadersLimit,
+}
+
+impl From<Slot> for GetSlotLeadersParams {
+    fn from(start_slot: Slot) -> Self {
+        Self {
+            start_slot,
+            limit: GetSlotLeadersLimit::default(),
+        }
+    }
+}
+
+/// The number of slot leaders to return in the response of a
+/// [`getSlotLeaders`](https://solana.com/docs/rpc/http/getslotleaders) request.
+#[derive(Clone, Copy, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct GetSlotLeadersLimit(u32);
+
+impl GetSlotLeadersLimit {
+    /// The maximum number of slot leaders that can be returned by a `getSlotLeaders` call.
+    pub const MAX_LIMIT: u32 = 5000;
+}
+
+impl Default for GetSlotLeadersLimit {
+    fn default() -> Self {
+        Self(100)
+    }
+}
+
+impl TryFrom<u32> for GetSlotLeadersLimit {
+    type Error = RpcError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1..=Self::MAX_LIMIT => Ok(Self(value)),
+            _ => Err(RpcError::ValidationError(format!(
+                "Expected a value between 1 and {}, but got {}",
+                Self::MAX_LIMIT,
+                value
+            ))),
+        }
+    }
+}
+
+impl From<GetSlotLeadersLimit> for u32 {
+    fn from(value: GetSlotLeadersLimit) -> Self {
+        value.0
+    }
+}
+
+/// The parameters for a Solana [`getSupply`](https://solana.com/docs/rpc/http/getsupply) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetSupplyParams {
+    /// The request returns the supply of the bank that has reached this or the default
+    /// commitment level.
+    pub commitment: Option<CommitmentLevel>,
+    /// Exclude the list of non-circulating accounts from the response. Since that list
+    /// can be large and is rarely needed, setting this flag reduces the size of the response.
+    #[serde(rename = "excludeNonCirculatingAccountsList")]
+    pub exclude_non_circulating_accounts_list: Option<bool>,
+}
+
 /// The parameters for a Solana [`getTokenAccountBalance`](https://solana.com/docs/rpc/http/gettokenaccountbalance) RPC method call.
-#[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
 pub struct GetTokenAccountBalanceParams {
     /// The public key of the token account to query formatted as a base-58 string.
     pub pubkey: Pubkey,
@@ -321,14 +932,162 @@ impl GetTokenAccountBalanceParams {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_pubkey::Pubkey> for GetTokenAccountBalanceParams {
     fn from(pubkey: solana_pubkey::Pubkey) -> Self {
         Self::from_pubkey(pubkey)
     }
 }
 
-/// The parameters for a Solana [`getTransaction`](https://solana.com/docs/rpc/http/gettransaction) RPC method call.
+/// The parameters for a Solana [`getTokenAccountsByDelegate`](https://solana.com/docs/rpc/http/gettokenaccountsbydelegate) RPC method call.
 #[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetTokenAccountsByDelegateParams {
+    /// The public key of the delegate whose token accounts to fetch formatted as a base-58 string.
+    pub delegate: Pubkey,
+    /// Restricts the returned token accounts to a given mint or token program.
+    pub filter: TokenAccountsFilter,
+    /// The commitment describes how finalized a block is at that point in time.
+    pub commitment: Option<CommitmentLevel>,
+    /// Encoding format for Account data.
+    pub encoding: Option<GetAccountInfoEncoding>,
+    /// Request a slice of the accounts' data.
+    #[serde(rename = "dataSlice")]
+    pub data_slice: Option<DataSlice>,
+    /// The minimum slot that the request can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+}
+
+impl GetTokenAccountsByDelegateParams {
+    /// Parameters for a `getTokenAccountsByDelegate` request with the given delegate and filter.
+    pub fn from_delegate<P: Into<Pubkey>>(delegate: P, filter: TokenAccountsFilter) -> Self {
+        Self {
+            delegate: delegate.into(),
+            filter,
+            commitment: None,
+            encoding: None,
+            data_slice: None,
+            min_context_slot: None,
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<(solana_pubkey::Pubkey, TokenAccountsFilter)> for GetTokenAccountsByDelegateParams {
+    fn from((delegate, filter): (solana_pubkey::Pubkey, TokenAccountsFilter)) -> Self {
+        Self::from_delegate(delegate, filter)
+    }
+}
+
+/// The parameters for a Solana [`getTokenAccountsByOwner`](https://solana.com/docs/rpc/http/gettokenaccountsbyowner) RPC method call.
+///
+/// Since an owner may have thousands of token accounts, the returned accounts, sorted by address,
+/// can be paginated with the [`after`](Self::after) and [`limit`](Self::limit) parameters.
+/// Setting [`data_slice`](Self::data_slice) additionally reduces the size of each account.
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetTokenAccountsByOwnerParams {
+    /// The public key of the owner whose token accounts to fetch formatted as a base-58 string.
+    pub owner: Pubkey,
+    /// Restricts the returned token accounts to a given mint or token program.
+    pub filter: TokenAccountsFilter,
+    /// The commitment describes how finalized a block is at that point in time.
+    pub commitment: Option<CommitmentLevel>,
+    /// Encoding format for Account data.
+    pub encoding: Option<GetAccountInfoEncoding>,
+    /// Request a slice of the accounts' data.
+    #[serde(rename = "dataSlice")]
+    pub data_slice: Option<DataSlice>,
+    /// The minimum slot that the request can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+    /// Only return the token accounts whose address is strictly greater than the given one,
+    /// typically the address of the last token account of the previous page.
+    ///
+    /// This parameter is not part of the Solana JSON-RPC API and is applied by the SOL RPC canister.
+    pub after: Option<Pubkey>,
+    /// Maximum number of token accounts to return.
+    ///
+    /// This parameter is not part of the Solana JSON-RPC API and is applied by the SOL RPC canister.
+    pub limit: Option<GetTokenAccountsByOwnerLimit>,
+}
+
+impl GetTokenAccountsByOwnerParams {
+    /// Parameters for a `getTokenAccountsByOwner` request with the given owner and filter.
+    pub fn from_owner<P: Into<Pubkey>>(owner: P, filter: TokenAccountsFilter) -> Self {
+        Self {
+            owner: owner.into(),
+            filter,
+            commitment: None,
+            encoding: None,
+            data_slice: None,
+            min_context_slot: None,
+            after: None,
+            limit: None,
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<(solana_pubkey::Pubkey, TokenAccountsFilter)> for GetTokenAccountsByOwnerParams {
+    fn from((owner, filter): (solana_pubkey::Pubkey, TokenAccountsFilter)) -> Self {
+        Self::from_owner(owner, filter)
+    }
+}
+
+/// The maximum number of token accounts returned by a `getTokenAccountsByOwner` request.
+#[derive(Clone, Copy, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct GetTokenAccountsByOwnerLimit(u32);
+
+impl GetTokenAccountsByOwnerLimit {
+    /// The maximum number of token accounts that can be returned by a `getTokenAccountsByOwner` call.
+    pub const MAX_LIMIT: u32 = 1000;
+}
+
+impl Default for GetTokenAccountsByOwnerLimit {
+    fn default() -> Self {
+        Self(Self::MAX_LIMIT)
+    }
+}
+
+impl TryFrom<u32> for GetTokenAccountsByOwnerLimit {
+    type Error = RpcError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            1..=Self::MAX_LIMIT => Ok(Self(value)),
+            _ => Err(RpcError::ValidationError(format!(
+                "Expected a value between 1 and {}, but got {}",
+                Self::MAX_LIMIT,
+                value
+            ))),
+        }
+    }
+}
+
+impl From<GetTokenAccountsByOwnerLimit> for u32 {
+    fn from(value: GetTokenAccountsByOwnerLimit) -> Self {
+        value.0
+    }
+}
+
+/// Filter for the token accounts returned by the Solana
+/// [`getTokenAccountsByDelegate`](https://solana.com/docs/rpc/http/gettokenaccountsbydelegate)
+/// and [`getTokenAccountsByOwner`](https://solana.com/docs/rpc/http/gettokenaccountsbyowner)
+/// RPC methods.
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+pub enum TokenAccountsFilter {
+    /// Only return the token accounts for the given mint.
+    #[serde(rename = "mint")]
+    Mint(Pubkey),
+    /// Only return the token accounts owned by the given token program,
+    /// e.g. the SPL Token or the Token-2022 program.
+    #[serde(rename = "programId")]
+    ProgramId(Pubkey),
+}
+
+/// The parameters for a Solana [`getTransaction`](https://solana.com/docs/rpc/http/gettransaction) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
 pub struct GetTransactionParams {
     /// Transaction signature.
     pub signature: Signature,
@@ -346,6 +1105,7 @@ pub struct GetTransactionParams {
     pub encoding: Option<GetTransactionEncoding>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_signature::Signature> for GetTransactionParams {
     fn from(signature: solana_signature::Signature) -> Self {
         Self {
@@ -370,8 +1130,19 @@ pub enum GetTransactionEncoding {
     Base58,
 }
 
+/// The parameters for a Solana [`getTransactionCount`](https://solana.com/docs/rpc/http/gettransactioncount) RPC method call.
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
+pub struct GetTransactionCountParams {
+    /// The request returns the transaction count of the bank that has reached this or the
+    /// default commitment level.
+    pub commitment: Option<CommitmentLevel>,
+    /// The minimum slot that the request can be evaluated at.
+    #[serde(rename = "minContextSlot")]
+    pub min_context_slot: Option<Slot>,
+}
+
 /// The parameters for a Solana [`sendTransaction`](https://solana.com/docs/rpc/http/sendtransaction) RPC method call.
-#[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize, Serialize)]
 pub struct SendTransactionParams {
     /// Fully-signed transaction, as encoded string.
     transaction: String,
@@ -391,6 +1162,15 @@ pub struct SendTransactionParams {
     /// Set the minimum slot at which to perform preflight transaction checks
     #[serde(rename = "minContextSlot")]
     pub min_context_slot: Option<Slot>,
+    /// When true, the SOL RPC canister first checks with the Solana
+    /// [`isBlockhashValid`](https://solana.com/docs/rpc/http/isblockhashvalid) RPC method that
+    /// the recent blockhash of the transaction is still valid and only sends the transaction if
+    /// that is the case. This additional request is paid with the cycles attached to the call,
+    /// but avoids paying for sending a transaction that would expire anyway. Default: false.
+    ///
+    /// Unlike the other parameters, this parameter is not forwarded to the RPC providers.
+    #[serde(rename = "validateBlockhash")]
+    pub validate_blockhash: Option<bool>,
 }
 
 impl SendTransactionParams {
@@ -407,6 +1187,7 @@ impl SendTransactionParams {
             preflight_commitment: None,
             max_retries: None,
             min_context_slot: None,
+            validate_blockhash: None,
         }
     }
 
@@ -419,8 +1200,41 @@ impl SendTransactionParams {
     pub fn get_encoding(&self) -> Option<&SendTransactionEncoding> {
         self.encoding.as_ref()
     }
+
+    /// Checks that the transaction is a non-empty, valid string in its encoding, which is assumed
+    /// to be base-58 if no encoding is specified.
+    ///
+    /// Malformed transactions would otherwise be rejected by each provider with a different error.
+    pub fn validate(&self) -> Result<(), RpcError> {
+        match self.encoding {
+            Some(SendTransactionEncoding::Base64) => {
+                decode_base64_param("transaction", &self.transaction)
+            }
+            Some(SendTransactionEncoding::Base58) | None => {
+                decode_base58_param("transaction", &self.transaction)
+            }
+        }
+        .map(drop)
+    }
+
+    /// Decodes the transaction being sent.
+    ///
+    /// The transaction is assumed to be base-58 encoded if no encoding is specified, which is
+    /// the default encoding of the `sendTransaction` RPC method.
+    #[cfg(feature = "solana-compat")]
+    pub fn decode_transaction(&self) -> Result<VersionedTransaction, RpcError> {
+        let encoding = match self.encoding {
+            Some(SendTransactionEncoding::Base64) => TransactionBinaryEncoding::Base64,
+            Some(SendTransactionEncoding::Base58) | None => TransactionBinaryEncoding::Base58,
+        };
+        VersionedTransaction::try_from(EncodedTransaction::Binary(
+            self.transaction.clone(),
+            encoding,
+        ))
+    }
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction::Transaction> for SendTransactionParams {
     type Error = RpcError;
 
@@ -435,6 +1249,28 @@ impl TryFrom<solana_transaction::Transaction> for SendTransactionParams {
     }
 }
 
+fn decode_base58_param(name: &str, value: &str) -> Result<Vec<u8>, RpcError> {
+    if value.is_empty() {
+        return Err(RpcError::ValidationError(format!(
+            "Expected a non-empty base58-encoded `{name}`"
+        )));
+    }
+    bs58::decode(value)
+        .into_vec()
+        .map_err(|e| RpcError::ValidationError(format!("Invalid base58-encoded `{name}`: {e}")))
+}
+
+fn decode_base64_param(name: &str, value: &str) -> Result<Vec<u8>, RpcError> {
+    if value.is_empty() {
+        return Err(RpcError::ValidationError(format!(
+            "Expected a non-empty base64-encoded `{name}`"
+        )));
+    }
+    BASE64_STANDARD
+        .decode(value)
+        .map_err(|e| RpcError::ValidationError(format!("Invalid base64-encoded `{name}`: {e}")))
+}
+
 /// The encoding format for the transaction argument to the Solana
 /// [`sendTransaction`](https://solana.com/docs/rpc/http/sendtransaction) RPC method call.
 #[derive(Clone, Debug, PartialEq, CandidType, Deserialize, Serialize)]
@@ -463,6 +1299,7 @@ pub enum CommitmentLevel {
     Finalized,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<CommitmentLevel> for solana_commitment_config::CommitmentConfig {
     fn from(commitment_level: CommitmentLevel) -> Self {
         match commitment_level {
@@ -485,6 +1322,7 @@ pub enum GetBlockCommitmentLevel {
     Finalized,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<GetBlockCommitmentLevel> for solana_commitment_config::CommitmentConfig {
     fn from(commitment_level: GetBlockCommitmentLevel) -> Self {
         match commitment_level {
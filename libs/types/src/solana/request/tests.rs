@@ -1,8 +1,19 @@
 use crate::{
+    GetAccountInfoParams, GetAccountSnapshotParams, GetBalanceParams, GetBlockHeightParams,
+    GetBlockParams, GetBlocksParams, GetBlocksWithLimitParams, GetClusterNodesParams,
+    GetEpochInfoParams, GetFeeForMessageParams, GetLeaderScheduleParams,
+    GetMinimumBalanceForRentExemptionParams, GetRecentPerformanceSamplesParams,
     GetRecentPrioritizationFeesParams, GetSignatureStatusesParams, GetSignaturesForAddressParams,
+    GetSlotLeadersParams, GetSlotParams, GetSupplyParams, GetTokenAccountBalanceParams,
+    GetTokenAccountsByDelegateParams, GetTokenAccountsByOwnerParams, GetTransactionCountParams,
+    GetTransactionParams, HasAccountChangedParams, SendTransactionParams,
 };
-use proptest::proptest;
-use serde::Deserialize;
+use candid::{CandidType, Decode, Encode};
+use proptest::{
+    prelude::{any, TestCaseError},
+    prop_assert_eq, proptest,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::json;
 
 mod get_signature_statuses_params_tests {
@@ -103,3 +114,629 @@ mod get_signatures_for_address_params_tests {
         }
     }
 }
+
+mod get_blocks_with_limit_params_tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn should_deserialize(limit in 1..1000) {
+            let params = json!({
+                "startSlot": 123,
+                "limit": limit
+            });
+
+            let result = GetBlocksWithLimitParams::deserialize(&params);
+
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn should_not_deserialize() {
+        for limit in [0, 1001, 1234] {
+            let params = json!({
+                "startSlot": 123,
+                "limit": limit
+            });
+
+            let result = GetBlocksWithLimitParams::deserialize(&params);
+
+            assert!(result.is_err());
+            assert_eq!(
+                result.err().unwrap().to_string(),
+                format!("Validation error: Expected a value between 1 and 1000, but got {limit}")
+            );
+        }
+    }
+}
+
+mod get_recent_performance_samples_params_tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn should_deserialize(limit in 1..=720) {
+            let params = json!({
+                "limit": limit
+            });
+
+            let result = GetRecentPerformanceSamplesParams::deserialize(&params);
+
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn should_not_deserialize() {
+        for limit in [0, 721, 1234] {
+            let params = json!({
+                "limit": limit
+            });
+
+            let result = GetRecentPerformanceSamplesParams::deserialize(&params);
+
+            assert!(result.is_err());
+            assert_eq!(
+                result.err().unwrap().to_string(),
+                format!("Validation error: Expected a value between 1 and 720, but got {limit}")
+            );
+        }
+    }
+}
+
+mod get_slot_leaders_params_tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn should_deserialize(limit in 1..5000) {
+            let params = json!({
+                "startSlot": 123,
+                "limit": limit
+            });
+
+            let result = GetSlotLeadersParams::deserialize(&params);
+
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn should_not_deserialize() {
+        for limit in [0, 5001, 12345] {
+            let params = json!({
+                "startSlot": 123,
+                "limit": limit
+            });
+
+            let result = GetSlotLeadersParams::deserialize(&params);
+
+            assert!(result.is_err());
+            assert_eq!(
+                result.err().unwrap().to_string(),
+                format!("Validation error: Expected a value between 1 and 5000, but got {limit}")
+            );
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+mod send_transaction_params_tests {
+    use super::*;
+    use crate::{RpcError, SendTransactionEncoding};
+    use assert_matches::assert_matches;
+    use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+    use solana_transaction::versioned::VersionedTransaction;
+
+    #[test]
+    fn should_decode_transaction() {
+        let transaction = transaction();
+        let bytes = bincode::serialize(&transaction).unwrap();
+
+        for params in [
+            SendTransactionParams::from_encoded_transaction(
+                BASE64_STANDARD.encode(&bytes),
+                SendTransactionEncoding::Base64,
+            ),
+            SendTransactionParams::from_encoded_transaction(
+                bs58::encode(&bytes).into_string(),
+                SendTransactionEncoding::Base58,
+            ),
+            SendTransactionParams {
+                encoding: None,
+                ..SendTransactionParams::from_encoded_transaction(
+                    bs58::encode(&bytes).into_string(),
+                    SendTransactionEncoding::Base58,
+                )
+            },
+        ] {
+            assert_eq!(params.decode_transaction(), Ok(transaction.clone()));
+        }
+    }
+
+    #[test]
+    fn should_not_decode_invalid_transaction() {
+        for params in [
+            SendTransactionParams::from_encoded_transaction(
+                "0OIl".to_string(),
+                SendTransactionEncoding::Base58,
+            ),
+            SendTransactionParams::from_encoded_transaction(
+                "%%%".to_string(),
+                SendTransactionEncoding::Base64,
+            ),
+            SendTransactionParams::from_encoded_transaction(
+                BASE64_STANDARD.encode([1_u8, 2, 3]),
+                SendTransactionEncoding::Base64,
+            ),
+        ] {
+            assert_matches!(
+                params.decode_transaction(),
+                Err(RpcError::ValidationError(_))
+            );
+        }
+    }
+
+    #[test]
+    fn should_validate_transaction_encoding() {
+        let bytes = bincode::serialize(&transaction()).unwrap();
+
+        for params in [
+            SendTransactionParams::from_encoded_transaction(
+                BASE64_STANDARD.encode(&bytes),
+                SendTransactionEncoding::Base64,
+            ),
+            SendTransactionParams {
+                encoding: None,
+                ..SendTransactionParams::from_encoded_transaction(
+                    bs58::encode(&bytes).into_string(),
+                    SendTransactionEncoding::Base58,
+                )
+            },
+        ] {
+            assert_eq!(params.validate(), Ok(()));
+        }
+
+        for (params, expected_error) in [
+            (
+                SendTransactionParams::from_encoded_transaction(
+                    "0OIl".to_string(),
+                    SendTransactionEncoding::Base58,
+                ),
+                "Invalid base58-encoded `transaction`",
+            ),
+            (
+                SendTransactionParams::from_encoded_transaction(
+                    "%%%".to_string(),
+                    SendTransactionEncoding::Base64,
+                ),
+                "Invalid base64-encoded `transaction`",
+            ),
+            (
+                SendTransactionParams::from_encoded_transaction(
+                    String::new(),
+                    SendTransactionEncoding::Base64,
+                ),
+                "Expected a non-empty base64-encoded `transaction`",
+            ),
+        ] {
+            assert_matches!(
+                params.validate(),
+                Err(RpcError::ValidationError(message)) if message.starts_with(expected_error)
+            );
+        }
+    }
+
+    fn transaction() -> VersionedTransaction {
+        let mut transaction = VersionedTransaction::default();
+        transaction
+            .signatures
+            .push(solana_signature::Signature::from([1_u8; 64]));
+        transaction
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+mod get_fee_for_message_params_tests {
+    use super::*;
+    use crate::RpcError;
+    use assert_matches::assert_matches;
+    use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+    use solana_message::{Message, VersionedMessage};
+
+    #[test]
+    fn should_encode_message_with_base64() {
+        let message = Message::new(&[], Some(&solana_pubkey::Pubkey::new_from_array([1; 32])));
+        let bytes = bincode::serialize(&VersionedMessage::Legacy(message.clone())).unwrap();
+
+        let params = GetFeeForMessageParams::try_from(message).unwrap();
+
+        assert_eq!(params.get_message(), BASE64_STANDARD.encode(bytes));
+        assert_eq!(params.commitment, None);
+        assert_eq!(params.min_context_slot, None);
+        assert_eq!(params.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_not_validate_invalid_message() {
+        for (message, expected_error) in [
+            ("", "Expected a non-empty base64-encoded `message`"),
+            ("not base64!", "Invalid base64-encoded `message`"),
+        ] {
+            assert_matches!(
+                GetFeeForMessageParams::from_encoded_message(message.to_string()).validate(),
+                Err(RpcError::ValidationError(e)) if e.starts_with(expected_error)
+            );
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+mod params_builder_tests {
+    use super::*;
+    use crate::{
+        CommitmentLevel, DataSlice, GetAccountInfoEncoding, GetBlocksLimit,
+        GetSignaturesForAddressLimit, RpcError, SendTransactionEncoding, Signature,
+        TokenAccountsFilter,
+    };
+    use assert_matches::assert_matches;
+
+    const PUBKEY: solana_pubkey::Pubkey = solana_pubkey::Pubkey::new_from_array([1; 32]);
+
+    #[test]
+    fn should_build_params_with_optional_fields() {
+        let params = GetAccountInfoParams::builder(PUBKEY)
+            .commitment(CommitmentLevel::Confirmed)
+            .encoding(GetAccountInfoEncoding::Base64)
+            .data_slice(DataSlice {
+                length: 1_000,
+                offset: 32,
+            })
+            .build();
+
+        assert_eq!(
+            params,
+            Ok(GetAccountInfoParams {
+                commitment: Some(CommitmentLevel::Confirmed),
+                encoding: Some(GetAccountInfoEncoding::Base64),
+                data_slice: Some(DataSlice {
+                    length: 1_000,
+                    offset: 32,
+                }),
+                ..GetAccountInfoParams::from_pubkey(PUBKEY)
+            })
+        );
+
+        let params = SendTransactionParams::builder(
+            "transaction".to_string(),
+            SendTransactionEncoding::Base64,
+        )
+        .skip_preflight(true)
+        .max_retries(3)
+        .build();
+
+        assert_eq!(params.get_transaction(), "transaction");
+        assert_eq!(
+            params.get_encoding(),
+            Some(&SendTransactionEncoding::Base64)
+        );
+        assert_eq!(params.skip_preflight, Some(true));
+        assert_eq!(params.max_retries, Some(3));
+        assert_eq!(params.preflight_commitment, None);
+    }
+
+    #[test]
+    fn should_not_build_params_with_too_large_base58_data_slice() {
+        let data_slice = DataSlice {
+            length: 129,
+            offset: 0,
+        };
+
+        assert_matches!(
+            GetAccountInfoParams::builder(PUBKEY)
+                .encoding(GetAccountInfoEncoding::Base58)
+                .data_slice(data_slice.clone())
+                .build(),
+            Err(RpcError::ValidationError(_))
+        );
+        assert_matches!(
+            GetTokenAccountsByOwnerParams::builder(
+                PUBKEY,
+                TokenAccountsFilter::Mint(PUBKEY.into())
+            )
+            .encoding(GetAccountInfoEncoding::Base58)
+            .data_slice(data_slice.clone())
+            .build(),
+            Err(RpcError::ValidationError(_))
+        );
+        assert_matches!(
+            GetAccountSnapshotParams::builder(PUBKEY)
+                .encoding(GetAccountInfoEncoding::Base58)
+                .data_slice(data_slice.clone())
+                .build(),
+            Err(RpcError::ValidationError(_))
+        );
+        assert_matches!(
+            GetAccountInfoParams::builder(PUBKEY)
+                .encoding(GetAccountInfoEncoding::Base64)
+                .data_slice(data_slice)
+                .build(),
+            Ok(_)
+        );
+    }
+
+    #[test]
+    fn should_validate_limits() {
+        assert_eq!(
+            GetSignaturesForAddressParams::builder(PUBKEY)
+                .limit(10)
+                .build()
+                .map(|params| params.limit),
+            Ok(Some(GetSignaturesForAddressLimit::try_from(10).unwrap()))
+        );
+        assert_eq!(
+            GetBlocksWithLimitParams::builder(123)
+                .build()
+                .map(|params| params.limit),
+            Ok(GetBlocksLimit::default())
+        );
+
+        for limit in [0, 1_001] {
+            let expected_error = Err(RpcError::ValidationError(format!(
+                "Expected a value between 1 and 1000, but got {limit}"
+            )));
+            assert_eq!(
+                GetSignaturesForAddressParams::builder(PUBKEY)
+                    .limit(limit)
+                    .build(),
+                expected_error
+            );
+            assert_eq!(
+                GetAccountSnapshotParams::builder(PUBKEY)
+                    .signatures_limit(limit)
+                    .build(),
+                expected_error
+            );
+            assert_eq!(
+                GetBlocksWithLimitParams::builder(123).limit(limit).build(),
+                expected_error
+            );
+            assert_eq!(
+                GetTokenAccountsByOwnerParams::builder(
+                    PUBKEY,
+                    TokenAccountsFilter::Mint(PUBKEY.into())
+                )
+                .limit(limit)
+                .build(),
+                expected_error
+            );
+        }
+        assert_matches!(
+            GetRecentPerformanceSamplesParams::builder()
+                .limit(721)
+                .build(),
+            Err(RpcError::ValidationError(_))
+        );
+        assert_matches!(
+            GetSlotLeadersParams::builder(123).limit(5_001).build(),
+            Err(RpcError::ValidationError(_))
+        );
+    }
+
+    #[test]
+    fn should_validate_block_ranges() {
+        assert_matches!(GetBlocksParams::builder(123).end_slot(1_122).build(), Ok(_));
+        for end_slot in [122, 1_123] {
+            assert_eq!(
+                GetBlocksParams::builder(123).end_slot(end_slot).build(),
+                Err(RpcError::ValidationError(format!(
+                    "Expected an end slot between 123 and 1122, but got {end_slot}"
+                )))
+            );
+        }
+
+        assert_matches!(
+            GetSignaturesForAddressParams::builder(PUBKEY)
+                .min_block_time(2)
+                .max_block_time(1)
+                .build(),
+            Err(RpcError::ValidationError(_))
+        );
+    }
+
+    #[test]
+    fn should_not_build_get_signature_statuses_params_with_too_many_signatures() {
+        let signature = Signature::from(solana_signature::Signature::from([1_u8; 64]));
+
+        assert_matches!(
+            GetSignatureStatusesParams::builder(vec![signature.clone(); 256])
+                .search_transaction_history(true)
+                .build(),
+            Ok(params) if params.signatures.len() == 256 && params.search_transaction_history == Some(true)
+        );
+        assert_eq!(
+            GetSignatureStatusesParams::builder(vec![signature; 257]).build(),
+            Err(RpcError::ValidationError(
+                "Expected at most 256 items, but got 257".to_string()
+            ))
+        );
+    }
+}
+
+mod arbitrary_params_tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn should_encode_decode_get_account_info_params(params in any::<GetAccountInfoParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_account_snapshot_params(params in any::<GetAccountSnapshotParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_balance_params(params in any::<GetBalanceParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_block_params(params in any::<GetBlockParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_block_height_params(params in any::<GetBlockHeightParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_blocks_params(params in any::<GetBlocksParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_blocks_with_limit_params(
+            params in any::<GetBlocksWithLimitParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_cluster_nodes_params(
+            params in any::<GetClusterNodesParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_epoch_info_params(params in any::<GetEpochInfoParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_fee_for_message_params(
+            params in any::<GetFeeForMessageParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_leader_schedule_params(
+            params in any::<GetLeaderScheduleParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_minimum_balance_for_rent_exemption_params(
+            params in any::<GetMinimumBalanceForRentExemptionParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_recent_performance_samples_params(
+            params in any::<GetRecentPerformanceSamplesParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_recent_prioritization_fees_params(
+            params in any::<GetRecentPrioritizationFeesParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_signatures_for_address_params(
+            params in any::<GetSignaturesForAddressParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_signature_statuses_params(
+            params in any::<GetSignatureStatusesParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_slot_params(params in any::<GetSlotParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_slot_leaders_params(params in any::<GetSlotLeadersParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_token_account_balance_params(
+            params in any::<GetTokenAccountBalanceParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_token_accounts_by_delegate_params(
+            params in any::<GetTokenAccountsByDelegateParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_has_account_changed_params(
+            params in any::<HasAccountChangedParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_token_accounts_by_owner_params(
+            params in any::<GetTokenAccountsByOwnerParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_supply_params(params in any::<GetSupplyParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_transaction_params(params in any::<GetTransactionParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_get_transaction_count_params(
+            params in any::<GetTransactionCountParams>()
+        ) {
+            encode_decode_roundtrip(params)?;
+        }
+
+        #[test]
+        fn should_encode_decode_send_transaction_params(params in any::<SendTransactionParams>()) {
+            encode_decode_roundtrip(params)?;
+        }
+    }
+
+    fn encode_decode_roundtrip<T>(params: T) -> Result<(), TestCaseError>
+    where
+        T: CandidType + Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let candid_decoded = Decode!(&Encode!(&params).unwrap(), T).unwrap();
+        prop_assert_eq!(&candid_decoded, &params);
+
+        let json_decoded: T =
+            serde_json::from_value(serde_json::to_value(&params).unwrap()).unwrap();
+        prop_assert_eq!(&json_decoded, &params);
+        Ok(())
+    }
+}
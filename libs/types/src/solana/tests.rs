@@ -101,3 +101,397 @@ mod impl_candid {
             .prop_map(|bytes| bs58::encode(bytes).into_string())
     }
 }
+
+#[cfg(feature = "solana-compat")]
+mod decode_transaction {
+    use crate::{
+        ConfirmedTransactionWithStatusMeta, EncodedConfirmedTransactionWithStatusMeta,
+        EncodedTransaction, EncodedTransactionWithStatusMeta, Instruction, ParsedAccountKeySource,
+        RpcError, TransactionBinaryEncoding, TransactionVersion,
+    };
+    use assert_matches::assert_matches;
+    use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+    use serde_json::json;
+    use solana_transaction::versioned::VersionedTransaction;
+
+    #[test]
+    fn should_decode_transaction() {
+        let transaction = transaction();
+        let bytes = bincode::serialize(&transaction).unwrap();
+
+        for encoded_transaction in [
+            EncodedTransaction::LegacyBinary(bs58::encode(&bytes).into_string()),
+            EncodedTransaction::Binary(
+                bs58::encode(&bytes).into_string(),
+                TransactionBinaryEncoding::Base58,
+            ),
+            EncodedTransaction::Binary(
+                BASE64_STANDARD.encode(&bytes),
+                TransactionBinaryEncoding::Base64,
+            ),
+        ] {
+            assert_eq!(
+                VersionedTransaction::try_from(encoded_transaction),
+                Ok(transaction.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn should_decode_confirmed_transaction() {
+        let transaction = transaction();
+        let encoded = EncodedConfirmedTransactionWithStatusMeta {
+            slot: 123,
+            block_time: Some(1_758_792_475),
+            transaction: EncodedTransactionWithStatusMeta {
+                meta: None,
+                transaction: EncodedTransaction::Binary(
+                    BASE64_STANDARD.encode(bincode::serialize(&transaction).unwrap()),
+                    TransactionBinaryEncoding::Base64,
+                ),
+                version: Some(TransactionVersion::Legacy),
+            },
+        };
+
+        assert_eq!(
+            ConfirmedTransactionWithStatusMeta::try_from(encoded),
+            Ok(ConfirmedTransactionWithStatusMeta {
+                slot: 123,
+                block_time: Some(1_758_792_475),
+                transaction,
+                meta: None,
+                version: Some(TransactionVersion::Legacy),
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_to_decode_invalid_transaction() {
+        for encoded_transaction in [
+            EncodedTransaction::LegacyBinary("0OIl".to_string()),
+            EncodedTransaction::Binary("%%%".to_string(), TransactionBinaryEncoding::Base64),
+            EncodedTransaction::Binary(
+                BASE64_STANDARD.encode([1_u8, 2, 3]),
+                TransactionBinaryEncoding::Base64,
+            ),
+        ] {
+            assert_matches!(
+                VersionedTransaction::try_from(encoded_transaction),
+                Err(RpcError::ValidationError(_))
+            );
+        }
+    }
+
+    #[test]
+    fn should_convert_json_parsed_transaction() {
+        let transaction: solana_transaction_status_client_types::EncodedTransaction =
+            serde_json::from_value(json!({
+                "signatures": ["5ZxWzc5mEStLx4fNRMjYGZ1NZ4yQZvRGEXHoSLQRkQp5aGdyvsWEuDjD2sZFU1wAMTTQHNf4V4mbnLJUY4WttyWW"],
+                "message": {
+                    "accountKeys": [
+                        {
+                            "pubkey": "FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN",
+                            "writable": true,
+                            "signer": true,
+                            "source": "transaction"
+                        },
+                        {
+                            "pubkey": "6pPYj3CpgfR6wwBkenmUpSd7rNqmeq2hccUVJq4au5eS",
+                            "writable": true,
+                            "signer": false,
+                            "source": "transaction"
+                        },
+                        {
+                            "pubkey": "11111111111111111111111111111111",
+                            "writable": false,
+                            "signer": false,
+                            "source": "transaction"
+                        }
+                    ],
+                    "recentBlockhash": "4Pcj2yJkCYyhnWe8Ze3uK2D2EtesBxhAevweDoTcxXf3",
+                    "instructions": [
+                        {
+                            "program": "system",
+                            "programId": "11111111111111111111111111111111",
+                            "parsed": {
+                                "type": "transfer",
+                                "info": {
+                                    "source": "FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN",
+                                    "destination": "6pPYj3CpgfR6wwBkenmUpSd7rNqmeq2hccUVJq4au5eS",
+                                    "lamports": 1000
+                                }
+                            },
+                            "stackHeight": null
+                        }
+                    ]
+                }
+            }))
+            .unwrap();
+
+        let converted = EncodedTransaction::try_from(transaction.clone()).unwrap();
+
+        let parsed = assert_matches!(&converted, EncodedTransaction::Json(parsed) => parsed);
+        assert_eq!(parsed.message.account_keys.len(), 3);
+        assert_eq!(
+            parsed.message.account_keys[0].source,
+            Some(ParsedAccountKeySource::Transaction)
+        );
+        let instruction = assert_matches!(
+            &parsed.message.instructions[..],
+            [Instruction::Parsed(instruction)] => instruction
+        );
+        assert_eq!(instruction.program, "system");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&instruction.parsed).unwrap(),
+            json!({
+                "type": "transfer",
+                "info": {
+                    "source": "FHhuW58hQLbJrfbNCT3f9U2j6u8nPUzdfDAn9S3KCEAN",
+                    "destination": "6pPYj3CpgfR6wwBkenmUpSd7rNqmeq2hccUVJq4au5eS",
+                    "lamports": 1000
+                }
+            })
+        );
+        assert_eq!(
+            solana_transaction_status_client_types::EncodedTransaction::from(converted.clone()),
+            transaction
+        );
+        assert_matches!(
+            VersionedTransaction::try_from(converted),
+            Err(RpcError::ValidationError(_))
+        );
+    }
+
+    fn transaction() -> VersionedTransaction {
+        let mut transaction = VersionedTransaction::default();
+        transaction
+            .signatures
+            .push(solana_signature::Signature::from([1_u8; 64]));
+        transaction
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+mod json_wire_format {
+    use crate::{
+        AccountData, AccountEncoding, AccountInfo, ConfirmedBlock,
+        EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, RpcError,
+        TransactionBinaryEncoding, TransactionVersion,
+    };
+    use assert_matches::assert_matches;
+    use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+    use serde_json::json;
+    use solana_transaction::versioned::VersionedTransaction;
+
+    #[test]
+    fn should_convert_account_info_from_and_to_json() {
+        let json = json!({
+            "data": ["AQID", "base64"],
+            "executable": false,
+            "lamports": 88_849_814_690_250_u64,
+            "owner": "11111111111111111111111111111111",
+            "rentEpoch": 18_446_744_073_709_551_615_u64,
+            "space": 3
+        });
+
+        let account = AccountInfo::try_from(json.clone()).unwrap();
+
+        assert_eq!(
+            account,
+            AccountInfo {
+                lamports: 88_849_814_690_250,
+                data: AccountData::Binary("AQID".to_string(), AccountEncoding::Base64),
+                owner: "11111111111111111111111111111111".to_string(),
+                executable: false,
+                rent_epoch: u64::MAX,
+                space: 3,
+            }
+        );
+        assert_eq!(serde_json::Value::from(account), json);
+    }
+
+    #[test]
+    fn should_fail_to_convert_invalid_account_info_from_json() {
+        assert_matches!(
+            AccountInfo::try_from(json!({
+                "data": ["AQID", "base64"],
+                "executable": false,
+                "lamports": 1,
+                "owner": "11111111111111111111111111111111",
+                "rentEpoch": 0
+            })),
+            Err(RpcError::ValidationError(_))
+        );
+        assert_matches!(
+            AccountInfo::try_from(json!({ "lamports": "not a number" })),
+            Err(RpcError::ValidationError(_))
+        );
+    }
+
+    #[test]
+    fn should_convert_confirmed_block_from_and_to_json() {
+        let json = json!({
+            "blockHeight": 428,
+            "blockTime": 1_758_792_475,
+            "blockhash": "3Eq21vXNB5s86c62bVuUfTeaMif1N2kUqRPBmGRJhyTA",
+            "parentSlot": 429,
+            "previousBlockhash": "mfcyqEXB3DnHXki6KjjmZck6YjmZLvpAByy2fj4nh6B",
+            "signatures": [
+                "5iBbqBJzgqafuQn93Np8ztWyXeYe2ReGPzUB1zXP2suZ8b5EaxSwe74ZUhg5pZQuDQkNGW7XApgfXX91YLYUuo5y"
+            ],
+            "rewards": []
+        });
+
+        let block = ConfirmedBlock::try_from(json).unwrap();
+
+        assert_eq!(block.block_height, Some(428));
+        assert_eq!(block.parent_slot, 429);
+        assert_eq!(
+            block.blockhash.to_string(),
+            "3Eq21vXNB5s86c62bVuUfTeaMif1N2kUqRPBmGRJhyTA"
+        );
+        assert_eq!(block.signatures.as_ref().map(Vec::len), Some(1));
+        assert_eq!(block.rewards, Some(vec![]));
+        assert_eq!(block.transactions, None);
+        assert_eq!(
+            ConfirmedBlock::try_from(serde_json::Value::from(block.clone())),
+            Ok(block)
+        );
+    }
+
+    #[test]
+    fn should_fail_to_convert_confirmed_block_with_invalid_blockhash_from_json() {
+        assert_matches!(
+            ConfirmedBlock::try_from(json!({
+                "blockHeight": 428,
+                "blockTime": null,
+                "blockhash": "invalid",
+                "parentSlot": 429,
+                "previousBlockhash": "mfcyqEXB3DnHXki6KjjmZck6YjmZLvpAByy2fj4nh6B"
+            })),
+            Err(RpcError::ValidationError(_))
+        );
+    }
+
+    #[test]
+    fn should_convert_confirmed_transaction_from_and_to_json() {
+        let encoded_transaction =
+            BASE64_STANDARD.encode(bincode::serialize(&VersionedTransaction::default()).unwrap());
+        let json = json!({
+            "slot": 430,
+            "blockTime": 1_758_792_475,
+            "transaction": [encoded_transaction, "base64"],
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5_000,
+                "preBalances": [1_000_000, 0],
+                "postBalances": [995_000, 0],
+                "logMessages": ["Program 11111111111111111111111111111111 invoke [1]"],
+                "computeUnitsConsumed": 150
+            },
+            "version": "legacy"
+        });
+
+        let transaction = EncodedConfirmedTransactionWithStatusMeta::try_from(json).unwrap();
+
+        assert_eq!(transaction.slot, 430);
+        assert_eq!(transaction.block_time, Some(1_758_792_475));
+        assert_eq!(
+            transaction.transaction.transaction,
+            EncodedTransaction::Binary(encoded_transaction, TransactionBinaryEncoding::Base64)
+        );
+        assert_eq!(
+            transaction.transaction.version,
+            Some(TransactionVersion::Legacy)
+        );
+        let meta = transaction.transaction.meta.clone().unwrap();
+        assert_eq!(meta.status, Ok(()));
+        assert_eq!(meta.fee, 5_000);
+        assert_eq!(
+            meta.log_messages,
+            Some(vec![
+                "Program 11111111111111111111111111111111 invoke [1]".to_string()
+            ])
+        );
+        assert_eq!(meta.compute_units_consumed, Some(150));
+        assert_eq!(
+            EncodedConfirmedTransactionWithStatusMeta::try_from(serde_json::Value::from(
+                transaction.clone()
+            )),
+            Ok(transaction)
+        );
+    }
+}
+
+mod epoch_schedule {
+    use crate::EpochSchedule;
+
+    #[test]
+    fn should_compute_epoch_boundaries_without_warmup() {
+        let schedule = EpochSchedule {
+            slots_per_epoch: 432_000,
+            leader_schedule_slot_offset: 432_000,
+            warmup: false,
+            first_normal_epoch: 0,
+            first_normal_slot: 0,
+        };
+
+        assert_eq!(schedule.slots_in_epoch(853), 432_000);
+        assert_eq!(schedule.first_slot_in_epoch(853), 368_496_000);
+        assert_eq!(schedule.last_slot_in_epoch(853), 368_927_999);
+        assert_eq!(schedule.epoch_and_slot_index(368_724_300), (853, 228_300));
+        assert_eq!(schedule.slots_remaining_in_epoch(368_724_300), 203_699);
+        assert_eq!(schedule.slots_remaining_in_epoch(368_927_999), 0);
+        assert_eq!(schedule.epoch(368_928_000), 854);
+    }
+
+    #[test]
+    fn should_compute_epoch_boundaries_with_warmup() {
+        let schedule = EpochSchedule {
+            slots_per_epoch: 432_000,
+            leader_schedule_slot_offset: 432_000,
+            warmup: true,
+            first_normal_epoch: 14,
+            first_normal_slot: 524_256,
+        };
+
+        assert_eq!(schedule.slots_in_epoch(0), 32);
+        assert_eq!(schedule.first_slot_in_epoch(0), 0);
+        assert_eq!(schedule.last_slot_in_epoch(0), 31);
+        assert_eq!(schedule.epoch_and_slot_index(31), (0, 31));
+        assert_eq!(schedule.epoch_and_slot_index(32), (1, 0));
+        assert_eq!(schedule.last_slot_in_epoch(1), 95);
+
+        assert_eq!(schedule.slots_in_epoch(13), 262_144);
+        assert_eq!(schedule.first_slot_in_epoch(13), 262_112);
+        assert_eq!(schedule.last_slot_in_epoch(13), 524_255);
+        assert_eq!(schedule.epoch_and_slot_index(524_255), (13, 262_143));
+
+        assert_eq!(schedule.first_slot_in_epoch(14), 524_256);
+        assert_eq!(schedule.epoch_and_slot_index(524_256), (14, 0));
+        assert_eq!(schedule.epoch_and_slot_index(1_000_000), (15, 43_744));
+        assert_eq!(schedule.slots_remaining_in_epoch(1_000_000), 388_255);
+    }
+
+    #[test]
+    fn should_be_consistent_for_every_slot_of_warmup_period() {
+        let schedule = EpochSchedule {
+            slots_per_epoch: 8_192,
+            leader_schedule_slot_offset: 8_192,
+            warmup: true,
+            first_normal_epoch: 8,
+            first_normal_slot: 8_160,
+        };
+
+        for slot in 0..3 * schedule.first_normal_slot {
+            let (epoch, slot_index) = schedule.epoch_and_slot_index(slot);
+            assert_eq!(schedule.first_slot_in_epoch(epoch) + slot_index, slot);
+            assert!(slot <= schedule.last_slot_in_epoch(epoch));
+            assert_eq!(
+                schedule.slots_remaining_in_epoch(slot),
+                schedule.slots_in_epoch(epoch) - slot_index - 1
+            );
+        }
+    }
+}
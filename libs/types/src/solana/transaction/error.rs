@@ -1,5 +1,6 @@
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
+#[cfg(feature = "solana-compat")]
 use solana_transaction_status_client_types::UiTransactionError;
 
 /// Represents errors that can occur during the processing of a Solana transaction.
@@ -99,6 +100,7 @@ pub enum TransactionError {
     CommitCancelled,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_transaction_error::TransactionError> for TransactionError {
     fn from(error: solana_transaction_error::TransactionError) -> Self {
         use solana_transaction_error::TransactionError;
@@ -168,6 +170,7 @@ impl From<solana_transaction_error::TransactionError> for TransactionError {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionError> for solana_transaction_error::TransactionError {
     fn from(value: TransactionError) -> Self {
         match value {
@@ -296,12 +299,14 @@ impl From<TransactionError> for solana_transaction_error::TransactionError {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<UiTransactionError> for TransactionError {
     fn from(error: UiTransactionError) -> Self {
         TransactionError::from(solana_transaction_error::TransactionError::from(error))
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionError> for UiTransactionError {
     fn from(error: TransactionError) -> Self {
         UiTransactionError::from(solana_transaction_error::TransactionError::from(error))
@@ -439,6 +444,7 @@ pub enum InstructionError {
     // conversions must also be added
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_instruction::error::InstructionError> for InstructionError {
     fn from(value: solana_instruction::error::InstructionError) -> Self {
         use solana_instruction::error::InstructionError;
@@ -518,6 +524,7 @@ impl From<solana_instruction::error::InstructionError> for InstructionError {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<InstructionError> for solana_instruction::error::InstructionError {
     fn from(value: InstructionError) -> Self {
         match value {
@@ -1,8 +1,12 @@
-use crate::RpcError;
+use crate::Pubkey;
+#[cfg(feature = "solana-compat")]
+use crate::{solana::parse_vec, RpcError};
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
+#[cfg(feature = "solana-compat")]
 use solana_transaction_status_client_types::{
-    UiCompiledInstruction, UiInnerInstructions, UiInstruction,
+    UiCompiledInstruction, UiInnerInstructions, UiInstruction, UiParsedInstruction,
+    UiPartiallyDecodedInstruction,
 };
 
 /// List of [inner instructions](https://solana.com/de/docs/rpc/json-structures#inner-instructions)
@@ -16,6 +20,7 @@ pub struct InnerInstructions {
     pub instructions: Vec<Instruction>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<UiInnerInstructions> for InnerInstructions {
     type Error = RpcError;
 
@@ -31,6 +36,7 @@ impl TryFrom<UiInnerInstructions> for InnerInstructions {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<InnerInstructions> for UiInnerInstructions {
     fn from(instructions: InnerInstructions) -> Self {
         Self {
@@ -50,25 +56,138 @@ pub enum Instruction {
     /// A compiled Solana instruction.
     #[serde(rename = "compiled")]
     Compiled(CompiledInstruction),
+    /// An instruction of a known program, parsed into a human-readable format.
+    ///
+    /// Only returned when the `jsonParsed` encoding is requested.
+    #[serde(rename = "parsed")]
+    Parsed(ParsedInstruction),
+    /// An instruction of a program that could not be parsed.
+    ///
+    /// Only returned when the `jsonParsed` encoding is requested.
+    #[serde(rename = "partiallyDecoded")]
+    PartiallyDecoded(PartiallyDecodedInstruction),
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<Instruction> for UiInstruction {
     fn from(instruction: Instruction) -> Self {
         match instruction {
             Instruction::Compiled(compiled) => Self::Compiled(compiled.into()),
+            Instruction::Parsed(parsed) => Self::Parsed(UiParsedInstruction::Parsed(parsed.into())),
+            Instruction::PartiallyDecoded(partially_decoded) => Self::Parsed(
+                UiParsedInstruction::PartiallyDecoded(partially_decoded.into()),
+            ),
         }
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<UiInstruction> for Instruction {
     type Error = RpcError;
 
     fn try_from(instruction: UiInstruction) -> Result<Self, Self::Error> {
         match instruction {
             UiInstruction::Compiled(compiled) => Ok(Self::Compiled(compiled.into())),
-            UiInstruction::Parsed(_) => Err(RpcError::ValidationError(
-                "Parsed instructions are not supported".to_string(),
-            )),
+            UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) => {
+                Ok(Self::Parsed(ParsedInstruction::try_from(parsed)?))
+            }
+            UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(partially_decoded)) => Ok(
+                Self::PartiallyDecoded(PartiallyDecodedInstruction::try_from(partially_decoded)?),
+            ),
+        }
+    }
+}
+
+/// An [instruction](https://solana.com/de/docs/references/terminology#instruction) of a known
+/// Solana program, such as the System or the SPL Token program, parsed into a human-readable
+/// format.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct ParsedInstruction {
+    /// The name of the program that executes this instruction, e.g. `system` or `spl-token`.
+    pub program: String,
+    /// The program account that executes this instruction.
+    #[serde(rename = "programId")]
+    pub program_id: Pubkey,
+    /// The instruction parsed as JSON and formatted as a string, e.g.
+    /// `{"type":"transfer","info":{"source":"...","destination":"...","lamports":1000}}`.
+    pub parsed: String,
+    /// The stack height at which this instruction was invoked during cross-program execution.
+    #[serde(rename = "stackHeight")]
+    pub stack_height: Option<u32>,
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<solana_transaction_status_client_types::ParsedInstruction> for ParsedInstruction {
+    type Error = RpcError;
+
+    fn try_from(
+        instruction: solana_transaction_status_client_types::ParsedInstruction,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program: instruction.program,
+            program_id: instruction.program_id.parse()?,
+            parsed: serde_json::to_string(&instruction.parsed)
+                .expect("Unable to convert JSON to string"),
+            stack_height: instruction.stack_height,
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<ParsedInstruction> for solana_transaction_status_client_types::ParsedInstruction {
+    fn from(instruction: ParsedInstruction) -> Self {
+        Self {
+            program: instruction.program,
+            program_id: instruction.program_id.to_string(),
+            parsed: serde_json::from_str(&instruction.parsed)
+                .expect("Unable to parse string as JSON"),
+            stack_height: instruction.stack_height,
+        }
+    }
+}
+
+/// An [instruction](https://solana.com/de/docs/references/terminology#instruction) of a Solana
+/// program that could not be parsed, where the accounts are resolved to their addresses.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct PartiallyDecodedInstruction {
+    /// The program account that executes this instruction.
+    #[serde(rename = "programId")]
+    pub program_id: Pubkey,
+    /// The ordered list of accounts passed to the program.
+    pub accounts: Vec<Pubkey>,
+    /// The program input data encoded in a base-58 string.
+    pub data: String,
+    /// The stack height at which this instruction was invoked during cross-program execution.
+    #[serde(rename = "stackHeight")]
+    pub stack_height: Option<u32>,
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<UiPartiallyDecodedInstruction> for PartiallyDecodedInstruction {
+    type Error = RpcError;
+
+    fn try_from(instruction: UiPartiallyDecodedInstruction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            program_id: instruction.program_id.parse()?,
+            accounts: parse_vec(instruction.accounts)?,
+            data: instruction.data,
+            stack_height: instruction.stack_height,
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<PartiallyDecodedInstruction> for UiPartiallyDecodedInstruction {
+    fn from(instruction: PartiallyDecodedInstruction) -> Self {
+        Self {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction
+                .accounts
+                .into_iter()
+                .map(|account| account.to_string())
+                .collect(),
+            data: instruction.data,
+            stack_height: instruction.stack_height,
         }
     }
 }
@@ -93,6 +212,7 @@ pub struct CompiledInstruction {
     pub stack_height: Option<u32>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<UiCompiledInstruction> for CompiledInstruction {
     fn from(instruction: UiCompiledInstruction) -> Self {
         Self {
@@ -104,6 +224,7 @@ impl From<UiCompiledInstruction> for CompiledInstruction {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<CompiledInstruction> for UiCompiledInstruction {
     fn from(instruction: CompiledInstruction) -> Self {
         Self {
@@ -2,18 +2,27 @@ pub mod error;
 pub mod instruction;
 pub mod reward;
 
+#[cfg(feature = "solana-compat")]
 use crate::{
     solana::{parse_opt, parse_vec, try_from_vec},
-    Pubkey, RpcError, Signature, Slot, Timestamp,
+    RpcError,
 };
+use crate::{Hash, Pubkey, Signature, Slot, Timestamp};
+#[cfg(feature = "solana-compat")]
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
 use candid::{CandidType, Deserialize};
 use error::TransactionError;
-use instruction::InnerInstructions;
+use instruction::{InnerInstructions, Instruction};
 use reward::Reward;
 use serde::Serialize;
+#[cfg(feature = "solana-compat")]
 use solana_account_decoder_client_types::token::UiTokenAmount;
+#[cfg(feature = "solana-compat")]
+use solana_transaction::versioned::VersionedTransaction;
+#[cfg(feature = "solana-compat")]
 use solana_transaction_status_client_types::{
-    option_serializer::OptionSerializer, UiReturnDataEncoding, UiTransactionError,
+    option_serializer::OptionSerializer, ParsedAccount, ParsedAccountSource, UiAddressTableLookup,
+    UiMessage, UiParsedMessage, UiReturnDataEncoding, UiTransaction, UiTransactionError,
     UiTransactionReturnData, UiTransactionStatusMeta,
 };
 
@@ -30,6 +39,7 @@ pub struct EncodedConfirmedTransactionWithStatusMeta {
     pub transaction: EncodedTransactionWithStatusMeta,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>
     for EncodedConfirmedTransactionWithStatusMeta
 {
@@ -46,6 +56,7 @@ impl TryFrom<solana_transaction_status_client_types::EncodedConfirmedTransaction
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<EncodedConfirmedTransactionWithStatusMeta>
     for solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta
 {
@@ -61,6 +72,101 @@ impl From<EncodedConfirmedTransactionWithStatusMeta>
     }
 }
 
+#[cfg(feature = "solana-compat")]
+/// Parses the `result` of a raw JSON-RPC `getTransaction` response, as returned by Solana RPC
+/// providers.
+impl TryFrom<serde_json::Value> for EncodedConfirmedTransactionWithStatusMeta {
+    type Error = RpcError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_value(value)?;
+        Self::try_from(transaction)
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+/// Serializes an [`EncodedConfirmedTransactionWithStatusMeta`] in the JSON format of the Solana
+/// JSON-RPC API.
+impl From<EncodedConfirmedTransactionWithStatusMeta> for serde_json::Value {
+    fn from(transaction: EncodedConfirmedTransactionWithStatusMeta) -> Self {
+        serde_json::to_value(
+            solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta::from(
+                transaction,
+            ),
+        )
+        .expect("BUG: failed to serialize transaction as JSON")
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+/// Solana [transaction](https://solana.com/docs/references/terminology#transaction) information
+/// for a confirmed transaction, where the transaction is decoded into a [`VersionedTransaction`].
+///
+/// This is the decoded counterpart of [`EncodedConfirmedTransactionWithStatusMeta`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmedTransactionWithStatusMeta {
+    /// The slot this transaction was processed in.
+    pub slot: Slot,
+    /// Estimated production time of when the transaction was processed. [`None`] if not available
+    pub block_time: Option<Timestamp>,
+    /// The decoded transaction.
+    pub transaction: VersionedTransaction,
+    /// Transaction status [metadata](https://solana.com/de/docs/rpc/json-structures#transaction-status-metadata)
+    /// object or [`None`].
+    pub meta: Option<TransactionStatusMeta>,
+    /// Transaction version. [`None`] if `maxSupportedTransactionVersion` is not set in request params.
+    pub version: Option<TransactionVersion>,
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<EncodedConfirmedTransactionWithStatusMeta> for ConfirmedTransactionWithStatusMeta {
+    type Error = RpcError;
+
+    fn try_from(
+        transaction: EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Result<Self, Self::Error> {
+        let EncodedTransactionWithStatusMeta {
+            meta,
+            transaction: encoded_transaction,
+            version,
+        } = transaction.transaction;
+        Ok(Self {
+            slot: transaction.slot,
+            block_time: transaction.block_time,
+            transaction: VersionedTransaction::try_from(encoded_transaction)?,
+            meta,
+            version,
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>
+    for ConfirmedTransactionWithStatusMeta
+{
+    type Error = RpcError;
+
+    fn try_from(
+        transaction: solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(EncodedConfirmedTransactionWithStatusMeta::try_from(
+            transaction,
+        )?)
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<EncodedConfirmedTransactionWithStatusMeta> for VersionedTransaction {
+    type Error = RpcError;
+
+    fn try_from(
+        transaction: EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Result<Self, Self::Error> {
+        Self::try_from(transaction.transaction)
+    }
+}
+
 /// Solana [transaction](https://solana.com/docs/references/terminology#transaction) information.
 #[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
 pub struct EncodedTransactionWithStatusMeta {
@@ -74,6 +180,7 @@ pub struct EncodedTransactionWithStatusMeta {
     pub version: Option<TransactionVersion>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction_status_client_types::EncodedTransactionWithStatusMeta>
     for EncodedTransactionWithStatusMeta
 {
@@ -93,6 +200,7 @@ impl TryFrom<solana_transaction_status_client_types::EncodedTransactionWithStatu
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<EncodedTransactionWithStatusMeta>
     for solana_transaction_status_client_types::EncodedTransactionWithStatusMeta
 {
@@ -105,6 +213,15 @@ impl From<EncodedTransactionWithStatusMeta>
     }
 }
 
+#[cfg(feature = "solana-compat")]
+impl TryFrom<EncodedTransactionWithStatusMeta> for VersionedTransaction {
+    type Error = RpcError;
+
+    fn try_from(transaction: EncodedTransactionWithStatusMeta) -> Result<Self, Self::Error> {
+        Self::try_from(transaction.transaction)
+    }
+}
+
 /// Solana transaction signature information as returned by the [`getSignaturesForAddress`](https://solana.com/de/docs/rpc/http/getsignaturestatuses)
 /// RPC method.
 #[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
@@ -127,6 +244,7 @@ pub struct ConfirmedTransactionStatusWithSignature {
     pub confirmation_status: Option<TransactionConfirmationStatus>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<ConfirmedTransactionStatusWithSignature>
     for solana_transaction_status_client_types::ConfirmedTransactionStatusWithSignature
 {
@@ -166,6 +284,7 @@ pub struct TransactionStatus {
     pub confirmation_status: Option<TransactionConfirmationStatus>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_transaction_status_client_types::TransactionStatus> for TransactionStatus {
     fn from(status: solana_transaction_status_client_types::TransactionStatus) -> Self {
         Self {
@@ -179,6 +298,7 @@ impl From<solana_transaction_status_client_types::TransactionStatus> for Transac
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionStatus> for solana_transaction_status_client_types::TransactionStatus {
     fn from(status: TransactionStatus) -> Self {
         Self {
@@ -212,6 +332,7 @@ pub enum TransactionConfirmationStatus {
     Finalized,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_transaction_status_client_types::TransactionConfirmationStatus>
     for TransactionConfirmationStatus
 {
@@ -225,6 +346,7 @@ impl From<solana_transaction_status_client_types::TransactionConfirmationStatus>
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionConfirmationStatus>
     for solana_transaction_status_client_types::TransactionConfirmationStatus
 {
@@ -286,6 +408,7 @@ pub struct TransactionStatusMeta {
     pub cost_units: Option<u64>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
     fn from(meta: TransactionStatusMeta) -> Self {
         let status = meta.status.map_err(UiTransactionError::from);
@@ -325,6 +448,7 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<UiTransactionStatusMeta> for TransactionStatusMeta {
     type Error = RpcError;
 
@@ -355,7 +479,7 @@ impl TryFrom<UiTransactionStatusMeta> for TransactionStatusMeta {
 
 /// [Transaction](https://solana.com/de/docs/rpc/json-structures#transactions) object, either in
 /// JSON format or encoded binary data.
-// TODO XC-343: Add variants corresponding to `Json` and `Accounts` in
+// TODO XC-343: Add a variant corresponding to `Accounts` in
 //  `solana_transaction_status_client_types::EncodedTransaction`.
 #[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
 pub enum EncodedTransaction {
@@ -365,8 +489,13 @@ pub enum EncodedTransaction {
     ///The transaction is encoded in one of the [`TransactionBinaryEncoding`] formats.
     #[serde(rename = "binary")]
     Binary(String, TransactionBinaryEncoding),
+    /// The transaction is parsed into a human-readable format, as returned with the
+    /// `jsonParsed` encoding.
+    #[serde(rename = "json")]
+    Json(ParsedTransaction),
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction_status_client_types::EncodedTransaction> for EncodedTransaction {
     type Error = RpcError;
 
@@ -377,18 +506,257 @@ impl TryFrom<solana_transaction_status_client_types::EncodedTransaction> for Enc
         match transaction {
             EncodedTransaction::LegacyBinary(binary) => Ok(Self::LegacyBinary(binary)),
             EncodedTransaction::Binary(blob, encoding) => Ok(Self::Binary(blob, encoding.into())),
-            EncodedTransaction::Json(_) | EncodedTransaction::Accounts(_) => Err(
-                RpcError::ValidationError("Unknown transaction encoding".to_string()),
-            ),
+            EncodedTransaction::Json(transaction) => {
+                Ok(Self::Json(ParsedTransaction::try_from(transaction)?))
+            }
+            EncodedTransaction::Accounts(_) => Err(RpcError::ValidationError(
+                "Unknown transaction encoding".to_string(),
+            )),
         }
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<EncodedTransaction> for solana_transaction_status_client_types::EncodedTransaction {
     fn from(transaction: EncodedTransaction) -> Self {
         match transaction {
             EncodedTransaction::LegacyBinary(binary) => Self::LegacyBinary(binary),
             EncodedTransaction::Binary(blob, encoding) => Self::Binary(blob, encoding.into()),
+            EncodedTransaction::Json(transaction) => Self::Json(transaction.into()),
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<EncodedTransaction> for VersionedTransaction {
+    type Error = RpcError;
+
+    fn try_from(transaction: EncodedTransaction) -> Result<Self, Self::Error> {
+        let bytes = match transaction {
+            EncodedTransaction::LegacyBinary(blob)
+            | EncodedTransaction::Binary(blob, TransactionBinaryEncoding::Base58) => {
+                bs58::decode(blob).into_vec().map_err(|e| {
+                    RpcError::ValidationError(format!("Invalid base58-encoded transaction: {e}"))
+                })?
+            }
+            EncodedTransaction::Binary(blob, TransactionBinaryEncoding::Base64) => {
+                BASE64_STANDARD.decode(blob).map_err(|e| {
+                    RpcError::ValidationError(format!("Invalid base64-encoded transaction: {e}"))
+                })?
+            }
+            EncodedTransaction::Json(_) => {
+                return Err(RpcError::ValidationError(
+                    "Parsed transactions cannot be decoded, use a binary encoding instead"
+                        .to_string(),
+                ))
+            }
+        };
+        bincode::deserialize(&bytes).map_err(|e| {
+            RpcError::ValidationError(format!("Transaction deserialization failed: {e}"))
+        })
+    }
+}
+
+/// A Solana [transaction](https://solana.com/de/docs/rpc/json-structures#transactions) parsed
+/// into a human-readable format, as returned with the `jsonParsed` encoding.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct ParsedTransaction {
+    /// List of signatures applied to the transaction.
+    pub signatures: Vec<Signature>,
+    /// Content of the transaction.
+    pub message: ParsedMessage,
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<UiTransaction> for ParsedTransaction {
+    type Error = RpcError;
+
+    fn try_from(transaction: UiTransaction) -> Result<Self, Self::Error> {
+        let message =
+            match transaction.message {
+                UiMessage::Parsed(message) => ParsedMessage::try_from(message)?,
+                UiMessage::Raw(_) => return Err(RpcError::ValidationError(
+                    "Raw JSON transaction messages are not supported, use the jsonParsed encoding"
+                        .to_string(),
+                )),
+            };
+        Ok(Self {
+            signatures: parse_vec(transaction.signatures)?,
+            message,
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<ParsedTransaction> for UiTransaction {
+    fn from(transaction: ParsedTransaction) -> Self {
+        Self {
+            signatures: transaction
+                .signatures
+                .into_iter()
+                .map(|signature| signature.to_string())
+                .collect(),
+            message: UiMessage::Parsed(transaction.message.into()),
+        }
+    }
+}
+
+/// Content of a [`ParsedTransaction`].
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct ParsedMessage {
+    /// List of accounts used by the transaction, including the accounts loaded from address
+    /// lookup tables.
+    #[serde(rename = "accountKeys")]
+    pub account_keys: Vec<ParsedAccountKey>,
+    /// A recent blockhash used to prevent transaction duplication and to give transactions
+    /// lifetimes.
+    #[serde(rename = "recentBlockhash")]
+    pub recent_blockhash: Hash,
+    /// List of program instructions executed in sequence by the transaction.
+    pub instructions: Vec<Instruction>,
+    /// List of address table lookups used by the transaction to dynamically load addresses
+    /// from on-chain address lookup tables. [`None`] for legacy transactions.
+    #[serde(rename = "addressTableLookups")]
+    pub address_table_lookups: Option<Vec<AddressTableLookup>>,
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<UiParsedMessage> for ParsedMessage {
+    type Error = RpcError;
+
+    fn try_from(message: UiParsedMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account_keys: try_from_vec(message.account_keys)?,
+            recent_blockhash: message.recent_blockhash.parse()?,
+            instructions: try_from_vec(message.instructions)?,
+            address_table_lookups: message
+                .address_table_lookups
+                .map(try_from_vec)
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<ParsedMessage> for UiParsedMessage {
+    fn from(message: ParsedMessage) -> Self {
+        Self {
+            account_keys: message.account_keys.into_iter().map(Into::into).collect(),
+            recent_blockhash: message.recent_blockhash.to_string(),
+            instructions: message.instructions.into_iter().map(Into::into).collect(),
+            address_table_lookups: message
+                .address_table_lookups
+                .map(|lookups| lookups.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+/// An account used by a [`ParsedTransaction`].
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct ParsedAccountKey {
+    /// The account address.
+    pub pubkey: Pubkey,
+    /// Whether the account is writable by the transaction.
+    pub writable: bool,
+    /// Whether the account signed the transaction.
+    pub signer: bool,
+    /// Whether the account address is part of the transaction or was loaded from an address
+    /// lookup table.
+    pub source: Option<ParsedAccountKeySource>,
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<ParsedAccount> for ParsedAccountKey {
+    type Error = RpcError;
+
+    fn try_from(account: ParsedAccount) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pubkey: account.pubkey.parse()?,
+            writable: account.writable,
+            signer: account.signer,
+            source: account.source.map(Into::into),
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<ParsedAccountKey> for ParsedAccount {
+    fn from(account: ParsedAccountKey) -> Self {
+        Self {
+            pubkey: account.pubkey.to_string(),
+            writable: account.writable,
+            signer: account.signer,
+            source: account.source.map(Into::into),
+        }
+    }
+}
+
+/// Origin of a [`ParsedAccountKey`].
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub enum ParsedAccountKeySource {
+    /// The account address is part of the transaction.
+    #[serde(rename = "transaction")]
+    Transaction,
+    /// The account address was loaded from an address lookup table.
+    #[serde(rename = "lookupTable")]
+    LookupTable,
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<ParsedAccountSource> for ParsedAccountKeySource {
+    fn from(source: ParsedAccountSource) -> Self {
+        match source {
+            ParsedAccountSource::Transaction => Self::Transaction,
+            ParsedAccountSource::LookupTable => Self::LookupTable,
+        }
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<ParsedAccountKeySource> for ParsedAccountSource {
+    fn from(source: ParsedAccountKeySource) -> Self {
+        match source {
+            ParsedAccountKeySource::Transaction => Self::Transaction,
+            ParsedAccountKeySource::LookupTable => Self::LookupTable,
+        }
+    }
+}
+
+/// Address table lookup used by a transaction to dynamically load addresses from an on-chain
+/// address lookup table.
+#[derive(Debug, Clone, Deserialize, Serialize, CandidType, PartialEq)]
+pub struct AddressTableLookup {
+    /// Address of the address lookup table account.
+    #[serde(rename = "accountKey")]
+    pub account_key: Pubkey,
+    /// List of indices used to load addresses of writable accounts from the lookup table.
+    #[serde(rename = "writableIndexes")]
+    pub writable_indexes: Vec<u8>,
+    /// List of indices used to load addresses of read-only accounts from the lookup table.
+    #[serde(rename = "readonlyIndexes")]
+    pub readonly_indexes: Vec<u8>,
+}
+
+#[cfg(feature = "solana-compat")]
+impl TryFrom<UiAddressTableLookup> for AddressTableLookup {
+    type Error = RpcError;
+
+    fn try_from(lookup: UiAddressTableLookup) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account_key: lookup.account_key.parse()?,
+            writable_indexes: lookup.writable_indexes,
+            readonly_indexes: lookup.readonly_indexes,
+        })
+    }
+}
+
+#[cfg(feature = "solana-compat")]
+impl From<AddressTableLookup> for UiAddressTableLookup {
+    fn from(lookup: AddressTableLookup) -> Self {
+        Self {
+            account_key: lookup.account_key.to_string(),
+            writable_indexes: lookup.writable_indexes,
+            readonly_indexes: lookup.readonly_indexes,
         }
     }
 }
@@ -404,6 +772,7 @@ pub enum TransactionBinaryEncoding {
     Base58,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_transaction_status_client_types::TransactionBinaryEncoding>
     for TransactionBinaryEncoding
 {
@@ -416,6 +785,7 @@ impl From<solana_transaction_status_client_types::TransactionBinaryEncoding>
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionBinaryEncoding>
     for solana_transaction_status_client_types::TransactionBinaryEncoding
 {
@@ -445,6 +815,7 @@ pub struct TransactionTokenBalance {
     pub program_id: Option<Pubkey>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction_status_client_types::UiTransactionTokenBalance>
     for TransactionTokenBalance
 {
@@ -462,6 +833,7 @@ impl TryFrom<solana_transaction_status_client_types::UiTransactionTokenBalance>
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionTokenBalance>
     for solana_transaction_status_client_types::UiTransactionTokenBalance
 {
@@ -491,6 +863,7 @@ pub struct TokenAmount {
     pub ui_amount_string: String,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TokenAmount> for UiTokenAmount {
     fn from(amount: TokenAmount) -> Self {
         Self {
@@ -502,6 +875,7 @@ impl From<TokenAmount> for UiTokenAmount {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<UiTokenAmount> for TokenAmount {
     fn from(amount: UiTokenAmount) -> Self {
         Self {
@@ -522,6 +896,7 @@ pub struct LoadedAddresses {
     pub readonly: Vec<Pubkey>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction_status_client_types::UiLoadedAddresses> for LoadedAddresses {
     type Error = RpcError;
     fn try_from(
@@ -534,6 +909,7 @@ impl TryFrom<solana_transaction_status_client_types::UiLoadedAddresses> for Load
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<LoadedAddresses> for solana_transaction_status_client_types::UiLoadedAddresses {
     fn from(addresses: LoadedAddresses) -> Self {
         Self {
@@ -561,6 +937,7 @@ pub struct TransactionReturnData {
     pub data: String,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<UiTransactionReturnData> for TransactionReturnData {
     type Error = RpcError;
 
@@ -575,6 +952,7 @@ impl TryFrom<UiTransactionReturnData> for TransactionReturnData {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionReturnData> for UiTransactionReturnData {
     fn from(return_data: TransactionReturnData) -> Self {
         Self {
@@ -595,6 +973,7 @@ pub enum TransactionVersion {
     Number(u8),
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_transaction::versioned::TransactionVersion> for TransactionVersion {
     fn from(version: solana_transaction::versioned::TransactionVersion) -> Self {
         match version {
@@ -606,6 +985,7 @@ impl From<solana_transaction::versioned::TransactionVersion> for TransactionVers
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<TransactionVersion> for solana_transaction::versioned::TransactionVersion {
     fn from(version: TransactionVersion) -> Self {
         match version {
@@ -1,4 +1,6 @@
-use crate::{Pubkey, RpcError};
+use crate::Pubkey;
+#[cfg(feature = "solana-compat")]
+use crate::RpcError;
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
 
@@ -20,6 +22,7 @@ pub struct Reward {
     pub commission: Option<u8>,
 }
 
+#[cfg(feature = "solana-compat")]
 impl TryFrom<solana_transaction_status_client_types::Reward> for Reward {
     type Error = RpcError;
 
@@ -36,6 +39,7 @@ impl TryFrom<solana_transaction_status_client_types::Reward> for Reward {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<Reward> for solana_transaction_status_client_types::Reward {
     fn from(reward: Reward) -> Self {
         Self {
@@ -65,6 +69,7 @@ pub enum RewardType {
     Voting,
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<solana_reward_info::RewardType> for RewardType {
     fn from(reward_type: solana_reward_info::RewardType) -> Self {
         match reward_type {
@@ -76,6 +81,7 @@ impl From<solana_reward_info::RewardType> for RewardType {
     }
 }
 
+#[cfg(feature = "solana-compat")]
 impl From<RewardType> for solana_reward_info::RewardType {
     fn from(reward_type: RewardType) -> Self {
         match reward_type {